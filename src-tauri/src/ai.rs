@@ -1,13 +1,102 @@
-use crate::profiles::read_ai_api_key;
-use crate::types::{DbAiSchemaContextObject, DbAiSuggestQueryRequest, DbAiSuggestQueryResult};
+use crate::profiles::{app_data_file_path, read_ai_api_key};
+use crate::providers::ProviderRegistry;
+use crate::state::{lock_session, SessionRegistry};
+use crate::types::{
+    DatabaseProvider, DbAiDdlReviewResult, DbAiDdlRiskNote, DbAiIdentifierCheck,
+    DbAiPromptSettings, DbAiReviewDdlRequest, DbAiRiskSeverity, DbAiSchemaContextObject,
+    DbAiSchemaContextStatus, DbAiSuggestQueryRequest, DbAiSuggestQueryResult, DbObjectDependent,
+};
+use crate::workspace::now_millis;
 use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
 use std::time::Duration;
+use tauri::AppHandle;
+
+pub(crate) const AI_SETTINGS_STORE_FILE: &str = "ai_settings.json";
+const MAX_PROMPT_TEMPLATE_CHARS: usize = 20_000;
+const MAX_EXTRA_RULES: usize = 50;
+const PROMPT_TEMPLATE_VARIABLES: [&str; 4] = ["dialect", "dialect_rules", "schema", "extra_rules"];
+const DEFAULT_SYSTEM_PROMPT_TEMPLATE: &str = "\
+You are an expert {{dialect}} SQL assistant that suggests query completions.
+The user is writing a {{dialect}} SQL query against the {{schema}} schema and needs a natural continuation.
+
+Rules:
+- Suggest ONLY the continuation text that comes AFTER what the user has already typed.
+- Do NOT repeat any part of the current SQL.
+- Use ONLY columns and tables from the provided schema context.
+- Prefer read-only SQL (SELECT) unless the user's intent clearly requires DML.
+- {{dialect_rules}}
+- When joining tables, use the correct column names from the schema context.
+- Keep suggestions concise and focused - complete the current statement, do not add extra statements.
+- Tables marked with [REFERENCED] are already used in the query - strongly prefer their columns for completions.
+- suggestionText must be raw SQL continuation text only, without markdown fences and without prose.
+{{extra_rules}}";
+// Appended to every template so a customized prompt cannot break response parsing.
+const AI_RESPONSE_CONTRACT: &str = "Return valid JSON only (no markdown) with keys: suggestionText, confidence (0.0-1.0), reasoningShort (one sentence), isPotentiallyMutating (boolean).";
+
+const DDL_RISK_CATEGORIES: [&str; 6] = [
+    "breakingChange",
+    "grants",
+    "locking",
+    "dependencies",
+    "dataLoss",
+    "other",
+];
+const DDL_REVIEW_SYSTEM_PROMPT: &str = "\
+You review database DDL changes before they are applied. Compare the proposed DDL with the \
+current definition and point out concrete risks: breaking signature or column changes for the \
+listed dependent objects, grants or synonyms lost when an object is dropped and re-created, \
+locks or long rebuilds on large tables, data loss, and objects left invalid. Do not restate \
+the change or give style advice. Return valid JSON only (no markdown) with keys: riskLevel \
+(low, medium or high), summary (one sentence), notes (array of objects with keys category \
+(breakingChange, grants, locking, dependencies, dataLoss or other), severity (low, medium or \
+high) and message). Return an empty notes array when there is nothing to flag.";
+
+const AI_MAX_SCHEMA_OBJECTS: usize = 120;
+const AI_MAX_REFERENCED_COLUMNS: usize = 60;
+const AI_MAX_OTHER_COLUMNS: usize = 20;
+const SQL_IDENTIFIER_STOP_WORDS: &str = "\
+    SELECT FROM WHERE JOIN LEFT RIGHT FULL INNER OUTER ON GROUP ORDER BY HAVING AS DISTINCT \
+    UNION ALL WITH AND OR NOT NULL IS IN EXISTS CASE WHEN THEN ELSE END LIKE BETWEEN FETCH \
+    FIRST NEXT ROWS ROW ONLY OFFSET INSERT INTO VALUES UPDATE SET DELETE MERGE TRUNCATE ALTER \
+    DROP CREATE TABLE VIEW INDEX SEQUENCE GRANT REVOKE DESC DESCRIBE EXPLAIN";
+// Dictionary views and pseudo-columns the cached catalog never lists.
+const SYSTEM_OBJECT_PREFIXES: [&str; 8] = [
+    "ALL_",
+    "USER_",
+    "DBA_",
+    "V$",
+    "GV$",
+    "SYS.",
+    "INFORMATION_SCHEMA.",
+    "PG_CATALOG.",
+];
+const PSEUDO_COLUMNS: [&str; 5] = ["ROWID", "ROWNUM", "ORA_ROWSCN", "NEXTVAL", "CURRVAL"];
 
 #[derive(Debug, Deserialize)]
 struct OpenAiChatCompletionResponse {
     choices: Vec<OpenAiChoice>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AiDdlReviewPayload {
+    risk_level: String,
+    summary: String,
+    notes: Vec<AiDdlRiskNotePayload>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AiDdlRiskNotePayload {
+    category: String,
+    severity: String,
+    message: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct OpenAiChoice {
     message: OpenAiMessage,
@@ -20,11 +109,8 @@ struct OpenAiMessage {
 
 pub(crate) async fn suggest_query(
     request: DbAiSuggestQueryRequest,
+    settings: DbAiPromptSettings,
 ) -> Result<DbAiSuggestQueryResult, String> {
-    let api_key = read_ai_api_key()?
-        .ok_or_else(|| "AI API key is not configured. Add it in Settings -> AI.".to_string())?;
-
-    let endpoint = normalize_ai_endpoint(request.endpoint.as_str());
     let schema_context_prompt = build_ai_schema_context_prompt(&request.schema_context);
     let clause_hint = request
         .cursor_clause
@@ -40,29 +126,97 @@ pub(crate) async fn suggest_query(
         clause_hint
     );
 
-    let system_prompt = [
-        "You are an expert Oracle SQL assistant that suggests query completions.",
-        "The user is writing an Oracle SQL query and needs a natural continuation.",
-        "",
-        "Rules:",
-        "- Suggest ONLY the continuation text that comes AFTER what the user has already typed.",
-        "- Do NOT repeat any part of the current SQL.",
-        "- Use ONLY columns and tables from the provided schema context.",
-        "- Prefer read-only SQL (SELECT) unless the user's intent clearly requires DML.",
-        "- Use correct Oracle SQL syntax (NVL instead of COALESCE, ROWNUM or FETCH FIRST instead of LIMIT, etc.).",
-        "- When joining tables, use the correct column names from the schema context.",
-        "- Keep suggestions concise and focused - complete the current statement, do not add extra statements.",
-        "- Tables marked with [REFERENCED] are already used in the query - strongly prefer their columns for completions.",
-        "- suggestionText must be raw SQL continuation text only, without markdown fences and without prose.",
-        "",
-        "Return valid JSON only (no markdown) with keys: suggestionText, confidence (0.0-1.0), reasoningShort (one sentence), isPotentiallyMutating (boolean).",
-    ]
-    .join("\n");
+    let system_prompt = render_system_prompt(
+        &settings,
+        request.provider.unwrap_or(DatabaseProvider::Oracle),
+        request.connected_schema.trim(),
+    );
+
+    let content = request_chat_completion(
+        request.endpoint.as_str(),
+        request.model.as_str(),
+        system_prompt.as_str(),
+        user_message.as_str(),
+        300,
+    )
+    .await?;
+
+    let mut result = parse_ai_suggestion_payload(content.as_str(), request.current_sql.as_str())?;
+    result.is_potentially_mutating = result.is_potentially_mutating
+        || is_potentially_mutating_sql(result.suggestion_text.as_str());
+
+    if result.suggestion_text.is_empty() {
+        return Err("AI response did not include suggestion text.".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Asks the model for risk notes on a DDL change before it is applied. `current_ddl` is
+/// `None` for a new object and `dependents` is `None` when the provider cannot list them.
+pub(crate) async fn review_ddl(
+    request: &DbAiReviewDdlRequest,
+    provider: DatabaseProvider,
+    current_ddl: Option<&str>,
+    dependents: Option<Vec<DbObjectDependent>>,
+) -> Result<DbAiDdlReviewResult, String> {
+    let dependents_section = match dependents.as_deref() {
+        None => "Dependent objects: unknown (not available for this database).".to_string(),
+        Some([]) => "Dependent objects: none.".to_string(),
+        Some(dependents) => {
+            let mut section = "Dependent objects:".to_string();
+            for dependent in dependents {
+                section.push_str(&format!(
+                    "\n- {} {}.{}",
+                    dependent.object_type, dependent.schema, dependent.object_name
+                ));
+            }
+            section
+        }
+    };
+    let user_message = format!(
+        "Object: {} {}.{}\nCurrent definition:\n{}\n\nProposed DDL:\n{}\n\n{}",
+        request.object_type.trim(),
+        request.schema.trim(),
+        request.object_name.trim(),
+        current_ddl.unwrap_or("(object does not exist yet)"),
+        request.ddl,
+        dependents_section
+    );
+    let system_prompt = format!(
+        "{DDL_REVIEW_SYSTEM_PROMPT}\nThe database is {}. {}",
+        dialect_name(provider),
+        dialect_rules(provider)
+    );
+
+    let content = request_chat_completion(
+        request.endpoint.as_str(),
+        request.model.as_str(),
+        system_prompt.as_str(),
+        user_message.as_str(),
+        800,
+    )
+    .await?;
+    let mut result = parse_ddl_review_payload(content.as_str())?;
+    result.dependents = dependents.unwrap_or_default();
+    Ok(result)
+}
+
+async fn request_chat_completion(
+    endpoint: &str,
+    model: &str,
+    system_prompt: &str,
+    user_message: &str,
+    max_tokens: u32,
+) -> Result<String, String> {
+    let api_key = read_ai_api_key()?
+        .ok_or_else(|| "AI API key is not configured. Add it in Settings -> AI.".to_string())?;
+    let endpoint = normalize_ai_endpoint(endpoint);
 
     let payload = serde_json::json!({
-        "model": request.model.trim(),
+        "model": model.trim(),
         "temperature": 0.05,
-        "max_tokens": 300,
+        "max_tokens": max_tokens,
         "response_format": { "type": "json_object" },
         "messages": [
             {
@@ -105,22 +259,164 @@ pub(crate) async fn suggest_query(
         .json::<OpenAiChatCompletionResponse>()
         .await
         .map_err(|error| format!("Failed to parse AI response envelope: {error}"))?;
-    let content = parsed
+    parsed
         .choices
         .first()
-        .map(|choice| choice.message.content.trim())
+        .map(|choice| choice.message.content.trim().to_string())
         .filter(|value| !value.is_empty())
-        .ok_or_else(|| "AI response did not include a suggestion.".to_string())?;
+        .ok_or_else(|| "AI response did not include any content.".to_string())
+}
 
-    let mut result = parse_ai_suggestion_payload(content, request.current_sql.as_str())?;
-    result.is_potentially_mutating = result.is_potentially_mutating
-        || is_potentially_mutating_sql(result.suggestion_text.as_str());
+pub(crate) fn read_prompt_settings(app: &AppHandle) -> Result<DbAiPromptSettings, String> {
+    let path = app_data_file_path(app, AI_SETTINGS_STORE_FILE)?;
+    read_prompt_settings_from_path(path.as_path())
+}
 
-    if result.suggestion_text.is_empty() {
-        return Err("AI response did not include suggestion text.".to_string());
+pub(crate) fn save_prompt_settings(
+    app: &AppHandle,
+    settings: DbAiPromptSettings,
+) -> Result<DbAiPromptSettings, String> {
+    let settings = normalize_prompt_settings(settings)?;
+    let path = app_data_file_path(app, AI_SETTINGS_STORE_FILE)?;
+    let payload = serde_json::to_string_pretty(&settings)
+        .map_err(|error| format!("Failed to serialize AI settings: {error}"))?;
+    fs::write(path, payload)
+        .map_err(|error| format!("Failed to write AI settings file: {error}"))?;
+    Ok(settings)
+}
+
+pub(crate) fn default_system_prompt_template() -> &'static str {
+    DEFAULT_SYSTEM_PROMPT_TEMPLATE
+}
+
+pub(crate) fn read_prompt_settings_from_path(path: &Path) -> Result<DbAiPromptSettings, String> {
+    if !path.exists() {
+        return Ok(DbAiPromptSettings::default());
     }
 
-    Ok(result)
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read AI settings file: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(DbAiPromptSettings::default());
+    }
+
+    serde_json::from_str(&content)
+        .map_err(|error| format!("Failed to parse AI settings file: {error}"))
+}
+
+fn normalize_prompt_settings(settings: DbAiPromptSettings) -> Result<DbAiPromptSettings, String> {
+    let system_prompt_template = settings
+        .system_prompt_template
+        .map(|template| template.trim().to_string())
+        .filter(|template| !template.is_empty());
+    if let Some(template) = system_prompt_template.as_deref() {
+        if template.chars().count() > MAX_PROMPT_TEMPLATE_CHARS {
+            return Err(format!(
+                "System prompt template is too long (limit is {MAX_PROMPT_TEMPLATE_CHARS} characters)."
+            ));
+        }
+        let unknown = template_variables(template)
+            .into_iter()
+            .filter(|name| !PROMPT_TEMPLATE_VARIABLES.contains(name))
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            return Err(format!(
+                "Unknown template variable(s): {}. Available: {}.",
+                unknown.join(", "),
+                PROMPT_TEMPLATE_VARIABLES.join(", ")
+            ));
+        }
+    }
+
+    let extra_rules = settings
+        .extra_rules
+        .into_iter()
+        .map(|rule| rule.trim().trim_start_matches('-').trim().to_string())
+        .filter(|rule| !rule.is_empty())
+        .collect::<Vec<_>>();
+    if extra_rules.len() > MAX_EXTRA_RULES {
+        return Err(format!(
+            "At most {MAX_EXTRA_RULES} extra rules are supported."
+        ));
+    }
+
+    Ok(DbAiPromptSettings {
+        system_prompt_template,
+        extra_rules,
+    })
+}
+
+fn template_variables(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+        names.push(after_open[..end].trim());
+        rest = &after_open[end + 2..];
+    }
+    names
+}
+
+/// Renders the system prompt. A line holding only `{{extra_rules}}` expands to one
+/// `- rule` line per rule (or disappears); inline uses are joined with "; ".
+fn render_system_prompt(
+    settings: &DbAiPromptSettings,
+    provider: DatabaseProvider,
+    schema: &str,
+) -> String {
+    let template = settings
+        .system_prompt_template
+        .as_deref()
+        .unwrap_or(DEFAULT_SYSTEM_PROMPT_TEMPLATE);
+    let inline_rules = settings.extra_rules.join("; ");
+
+    let mut lines = Vec::new();
+    for line in template.lines() {
+        if line.trim() == "{{extra_rules}}" {
+            lines.extend(settings.extra_rules.iter().map(|rule| format!("- {rule}")));
+            continue;
+        }
+        lines.push(
+            line.replace("{{dialect}}", dialect_name(provider))
+                .replace("{{dialect_rules}}", dialect_rules(provider))
+                .replace("{{schema}}", schema)
+                .replace("{{extra_rules}}", inline_rules.as_str()),
+        );
+    }
+
+    let mut prompt = lines.join("\n").trim_end().to_string();
+    prompt.push_str("\n\n");
+    prompt.push_str(AI_RESPONSE_CONTRACT);
+    prompt
+}
+
+fn dialect_name(provider: DatabaseProvider) -> &'static str {
+    match provider {
+        DatabaseProvider::Oracle => "Oracle",
+        DatabaseProvider::Postgres => "PostgreSQL",
+        DatabaseProvider::Mysql => "MySQL",
+        DatabaseProvider::Sqlite | DatabaseProvider::Libsql => "SQLite",
+        DatabaseProvider::Duckdb => "DuckDB",
+        DatabaseProvider::Clickhouse => "ClickHouse",
+        DatabaseProvider::Snowflake => "Snowflake",
+        DatabaseProvider::External => "standard SQL",
+    }
+}
+
+fn dialect_rules(provider: DatabaseProvider) -> &'static str {
+    match provider {
+        DatabaseProvider::Oracle => "Use correct Oracle SQL syntax (NVL instead of COALESCE, ROWNUM or FETCH FIRST instead of LIMIT, etc.).",
+        DatabaseProvider::Postgres => "Use correct PostgreSQL syntax (LIMIT/OFFSET, COALESCE, double-quoted identifiers, :: casts).",
+        DatabaseProvider::Mysql => "Use correct MySQL syntax (LIMIT, IFNULL or COALESCE, backtick-quoted identifiers).",
+        DatabaseProvider::Sqlite | DatabaseProvider::Libsql => "Use correct SQLite syntax (LIMIT/OFFSET, IFNULL or COALESCE, no RIGHT or FULL joins on older versions).",
+        DatabaseProvider::Duckdb => "Use correct DuckDB syntax (LIMIT/OFFSET, COALESCE, double-quoted identifiers, read_parquet/read_csv for files).",
+        DatabaseProvider::Clickhouse => "Use correct ClickHouse SQL syntax (LIMIT, ifNull/coalesce, backtick-quoted case-sensitive identifiers, camelCase function names such as toDate and countIf).",
+        DatabaseProvider::Snowflake => "Use correct Snowflake SQL syntax (LIMIT or TOP, IFF/NVL/COALESCE, QUALIFY, double-quoted identifiers, :: casts).",
+        DatabaseProvider::External => "Use portable ANSI SQL (FETCH FIRST or LIMIT, COALESCE, double-quoted identifiers); the database is reached through an external provider adapter.",
+    }
 }
 
 fn normalize_ai_endpoint(endpoint: &str) -> String {
@@ -139,24 +435,8 @@ fn parse_ai_suggestion_payload(
     content: &str,
     current_sql: &str,
 ) -> Result<DbAiSuggestQueryResult, String> {
-    let mut candidates = Vec::new();
-    let trimmed = content.trim();
-    if !trimmed.is_empty() {
-        candidates.push(trimmed.to_string());
-    }
-    if let Some(code_fence_inner) = strip_markdown_code_fence(trimmed) {
-        if !candidates.contains(&code_fence_inner) {
-            candidates.push(code_fence_inner);
-        }
-    }
-    if let Some(json_object) = extract_first_json_object(trimmed) {
-        if !candidates.contains(&json_object) {
-            candidates.push(json_object);
-        }
-    }
-
     let mut parse_errors = Vec::new();
-    for payload in candidates {
+    for payload in json_payload_candidates(content) {
         match serde_json::from_str::<DbAiSuggestQueryResult>(payload.as_str()) {
             Ok(mut result) => {
                 result.suggestion_text =
@@ -178,6 +458,85 @@ fn parse_ai_suggestion_payload(
     ))
 }
 
+fn parse_ddl_review_payload(content: &str) -> Result<DbAiDdlReviewResult, String> {
+    let mut parse_errors = Vec::new();
+    for payload in json_payload_candidates(content) {
+        match serde_json::from_str::<AiDdlReviewPayload>(payload.as_str()) {
+            Ok(review) => {
+                let notes = review
+                    .notes
+                    .into_iter()
+                    .filter(|note| !note.message.trim().is_empty())
+                    .map(|note| DbAiDdlRiskNote {
+                        category: normalize_risk_category(note.category.as_str()),
+                        severity: parse_risk_severity(note.severity.as_str()),
+                        message: note.message.trim().to_string(),
+                    })
+                    .collect::<Vec<_>>();
+                // The overall level is never lower than the worst individual note.
+                let risk_level = notes
+                    .iter()
+                    .map(|note| note.severity)
+                    .chain([parse_risk_severity(review.risk_level.as_str())])
+                    .max()
+                    .unwrap_or_default();
+                return Ok(DbAiDdlReviewResult {
+                    risk_level,
+                    summary: review.summary.trim().to_string(),
+                    notes,
+                    dependents: Vec::new(),
+                });
+            }
+            Err(error) => parse_errors.push(error.to_string()),
+        }
+    }
+
+    Err(format!(
+        "Failed to parse AI review payload: {}",
+        parse_errors
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| "No JSON object found in response.".to_string())
+    ))
+}
+
+fn parse_risk_severity(value: &str) -> DbAiRiskSeverity {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "high" | "critical" => DbAiRiskSeverity::High,
+        "medium" | "moderate" => DbAiRiskSeverity::Medium,
+        _ => DbAiRiskSeverity::Low,
+    }
+}
+
+fn normalize_risk_category(value: &str) -> String {
+    let category = value.trim();
+    DDL_RISK_CATEGORIES
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(category))
+        .unwrap_or(&"other")
+        .to_string()
+}
+
+// The raw content plus any fenced or embedded JSON object, in the order worth trying.
+fn json_payload_candidates(content: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let trimmed = content.trim();
+    if !trimmed.is_empty() {
+        candidates.push(trimmed.to_string());
+    }
+    if let Some(code_fence_inner) = strip_markdown_code_fence(trimmed) {
+        if !candidates.contains(&code_fence_inner) {
+            candidates.push(code_fence_inner);
+        }
+    }
+    if let Some(json_object) = extract_first_json_object(trimmed) {
+        if !candidates.contains(&json_object) {
+            candidates.push(json_object);
+        }
+    }
+    candidates
+}
+
 fn strip_markdown_code_fence(value: &str) -> Option<String> {
     let trimmed = value.trim();
     if !trimmed.starts_with("```") {
@@ -291,6 +650,478 @@ fn strip_case_insensitive_prefix<'a>(value: &'a str, prefix: &str) -> Option<&'a
     value.get(prefix.len()..)
 }
 
+/// Table and column catalog per session, built in the backend so suggestion requests do
+/// not have to carry the schema context.
+#[derive(Default)]
+pub(crate) struct AiSchemaContextCache {
+    entries: Mutex<HashMap<u64, CachedAiSchema>>,
+}
+
+struct CachedAiSchema {
+    objects: Vec<AiCatalogObject>,
+    built_at: u64,
+}
+
+#[derive(Clone, Debug)]
+struct AiCatalogObject {
+    schema: String,
+    object_name: String,
+    /// Column descriptions in prompt form, e.g. `EMAIL VARCHAR2 NOT NULL`.
+    columns: Vec<String>,
+}
+
+impl AiSchemaContextCache {
+    pub(crate) fn remove(&self, session_id: u64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&session_id);
+        }
+    }
+
+    /// Picks the cached objects most relevant to `current_sql`, in the shape the frontend
+    /// used to send. Returns `None` when nothing has been cached for the session.
+    pub(crate) fn context_for(
+        &self,
+        session_id: u64,
+        current_sql: &str,
+    ) -> Option<Vec<DbAiSchemaContextObject>> {
+        let entries = self.entries.lock().ok()?;
+        let cached = entries.get(&session_id)?;
+        Some(rank_schema_context(cached.objects.as_slice(), current_sql))
+    }
+
+    fn store(&self, session_id: u64, objects: Vec<AiCatalogObject>) -> DbAiSchemaContextStatus {
+        let status = DbAiSchemaContextStatus {
+            session_id,
+            object_count: objects.len() as u32,
+            column_count: objects
+                .iter()
+                .map(|object| object.columns.len() as u32)
+                .sum(),
+            built_at: now_millis(),
+        };
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                session_id,
+                CachedAiSchema {
+                    objects,
+                    built_at: status.built_at,
+                },
+            );
+        }
+        status
+    }
+
+    pub(crate) fn status(&self, session_id: u64) -> Option<DbAiSchemaContextStatus> {
+        let entries = self.entries.lock().ok()?;
+        let cached = entries.get(&session_id)?;
+        Some(DbAiSchemaContextStatus {
+            session_id,
+            object_count: cached.objects.len() as u32,
+            column_count: cached
+                .objects
+                .iter()
+                .map(|object| object.columns.len() as u32)
+                .sum(),
+            built_at: cached.built_at,
+        })
+    }
+
+    /// Applies `check` to a suggestion: unknown tables and qualified columns are listed in
+    /// `warnings`, and under [`DbAiIdentifierCheck::Discard`] the whole suggestion is discarded.
+    pub(crate) fn check_suggestion(
+        &self,
+        session_id: Option<u64>,
+        check: DbAiIdentifierCheck,
+        current_sql: &str,
+        result: &mut DbAiSuggestQueryResult,
+    ) {
+        if check == DbAiIdentifierCheck::Off {
+            return;
+        }
+        let unknown = session_id.and_then(|session_id| {
+            let entries = self.entries.lock().ok()?;
+            let cached = entries.get(&session_id)?;
+            Some(find_unknown_identifiers(
+                cached.objects.as_slice(),
+                current_sql,
+                result.suggestion_text.as_str(),
+            ))
+        });
+        let Some(unknown) = unknown else {
+            result.warnings.push(
+                "Identifiers were not checked because no schema catalog is cached for this session."
+                    .to_string(),
+            );
+            return;
+        };
+        if unknown.is_empty() {
+            return;
+        }
+        if check == DbAiIdentifierCheck::Discard {
+            result.suggestion_text.clear();
+            result.confidence = 0.0;
+            result.is_potentially_mutating = false;
+        }
+        result.warnings.extend(unknown);
+    }
+}
+
+/// Reads tables, views and their columns for a session and caches them for suggestions.
+pub(crate) fn prefetch_schema_context(
+    sessions: &SessionRegistry,
+    cache: &AiSchemaContextCache,
+    session_id: u64,
+) -> Result<DbAiSchemaContextStatus, String> {
+    let (objects, columns) = {
+        let session = sessions.get(session_id)?;
+        let session = lock_session(&session)?;
+        (
+            ProviderRegistry::list_objects(&session)?,
+            ProviderRegistry::list_object_columns(&session)?,
+        )
+    };
+
+    let mut catalog = objects
+        .into_iter()
+        .filter(|object| matches!(object.object_type.as_str(), "TABLE" | "VIEW"))
+        .map(|object| {
+            (
+                (
+                    object.schema.to_uppercase(),
+                    object.object_name.to_uppercase(),
+                ),
+                AiCatalogObject {
+                    schema: object.schema,
+                    object_name: object.object_name,
+                    columns: Vec::new(),
+                },
+            )
+        })
+        .collect::<HashMap<_, _>>();
+    for column in columns {
+        let key = (
+            column.schema.to_uppercase(),
+            column.object_name.to_uppercase(),
+        );
+        if let Some(object) = catalog.get_mut(&key) {
+            object.columns.push(describe_ai_column(
+                column.column_name.as_str(),
+                column.data_type.as_str(),
+                column.nullable.as_str(),
+            ));
+        }
+    }
+
+    let mut objects = catalog.into_values().collect::<Vec<_>>();
+    objects.sort_by(|left, right| {
+        (left.schema.as_str(), left.object_name.as_str())
+            .cmp(&(right.schema.as_str(), right.object_name.as_str()))
+    });
+    Ok(cache.store(session_id, objects))
+}
+
+fn describe_ai_column(column_name: &str, data_type: &str, nullable: &str) -> String {
+    let column_name = column_name.trim();
+    let data_type = data_type.trim();
+    let not_null = if nullable.trim() == "N" {
+        " NOT NULL"
+    } else {
+        ""
+    };
+    if data_type.is_empty() {
+        column_name.to_string()
+    } else {
+        format!("{column_name} {data_type}{not_null}")
+    }
+}
+
+// Mirrors the scoring the editor applies client-side: referenced tables first, then
+// objects whose names or columns match identifiers in the SQL.
+fn rank_schema_context(
+    objects: &[AiCatalogObject],
+    current_sql: &str,
+) -> Vec<DbAiSchemaContextObject> {
+    let normalized_sql = strip_sql_comments_and_literals(current_sql).to_uppercase();
+    let referenced_names = extract_referenced_tables(normalized_sql.as_str());
+    let identifier_hints = extract_identifier_hints(normalized_sql.as_str());
+
+    let mut scored = objects
+        .iter()
+        .map(|object| {
+            let bare_name = object.object_name.to_uppercase();
+            let qualified_name = format!("{}.{}", object.schema.to_uppercase(), bare_name);
+            let is_referenced =
+                referenced_names.contains(&qualified_name) || referenced_names.contains(&bare_name);
+            let mut score = if is_referenced { 1000 } else { 0 };
+            if identifier_hints.contains(&bare_name) {
+                score += 200;
+            }
+            score += identifier_hints
+                .iter()
+                .filter(|hint| hint.len() >= 3 && bare_name.contains(hint.as_str()))
+                .count()
+                * 25;
+
+            let max_columns = if is_referenced {
+                AI_MAX_REFERENCED_COLUMNS
+            } else {
+                AI_MAX_OTHER_COLUMNS
+            };
+            let columns = object
+                .columns
+                .iter()
+                .take(max_columns)
+                .cloned()
+                .collect::<Vec<_>>();
+            let column_hint_matches = columns
+                .iter()
+                .filter(|column| {
+                    column
+                        .split_whitespace()
+                        .next()
+                        .is_some_and(|name| identifier_hints.contains(&name.to_uppercase()))
+                })
+                .count();
+            score += column_hint_matches.min(8) * 20;
+            if !object.columns.is_empty() {
+                score += 5;
+            }
+
+            (
+                score,
+                DbAiSchemaContextObject {
+                    schema: object.schema.clone(),
+                    object_name: object.object_name.clone(),
+                    columns,
+                    is_referenced_in_query: is_referenced,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+
+    scored.sort_by(|(left_score, left), (right_score, right)| {
+        right_score
+            .cmp(left_score)
+            .then_with(|| left.schema.cmp(&right.schema))
+            .then_with(|| left.object_name.cmp(&right.object_name))
+    });
+    scored
+        .into_iter()
+        .take(AI_MAX_SCHEMA_OBJECTS)
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+fn extract_referenced_tables(normalized_sql: &str) -> HashSet<String> {
+    let tokens = normalized_sql
+        .split(|ch: char| !(is_sql_identifier_char(ch) || ch == '.'))
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+
+    tokens
+        .windows(2)
+        .filter(|pair| matches!(pair[0], "FROM" | "JOIN" | "INTO" | "UPDATE"))
+        .map(|pair| pair[1].to_string())
+        .collect()
+}
+
+fn extract_identifier_hints(normalized_sql: &str) -> HashSet<String> {
+    normalized_sql
+        .split(|ch: char| !is_sql_identifier_char(ch))
+        .filter(|token| {
+            token.len() >= 2
+                && token.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+                && !SQL_IDENTIFIER_STOP_WORDS
+                    .split_whitespace()
+                    .any(|word| word == *token)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Tables and alias-qualified columns that appear in the suggestion but not in the catalog.
+/// Names in schemas the catalog does not cover are left alone, since they cannot be checked.
+fn find_unknown_identifiers(
+    objects: &[AiCatalogObject],
+    current_sql: &str,
+    suggestion_text: &str,
+) -> Vec<String> {
+    let current_sql = strip_sql_comments_and_literals(current_sql).to_uppercase();
+    let suggestion = strip_sql_comments_and_literals(suggestion_text).to_uppercase();
+    let mut tokens = sql_tokens(current_sql.as_str());
+    let suggestion_start = tokens.len();
+    tokens.extend(sql_tokens(suggestion.as_str()));
+
+    let mut catalog = HashMap::new();
+    let mut schemas = HashSet::new();
+    for object in objects {
+        let schema = object.schema.to_uppercase();
+        let bare_name = object.object_name.to_uppercase();
+        catalog.insert(format!("{schema}.{bare_name}"), object);
+        catalog.entry(bare_name).or_insert(object);
+        schemas.insert(schema);
+    }
+
+    let table_refs = extract_table_refs(tokens.as_slice());
+    let cte_names = tokens
+        .windows(3)
+        .filter(|window| window[1] == "AS" && window[2] == "(")
+        .map(|window| window[0])
+        .collect::<HashSet<_>>();
+
+    let mut unknown = Vec::new();
+    let mut push_unknown = |message: String| {
+        if !unknown.contains(&message) {
+            unknown.push(message);
+        }
+    };
+
+    for table_ref in table_refs
+        .iter()
+        .filter(|table_ref| table_ref.token_index >= suggestion_start)
+    {
+        let name = table_ref.name;
+        let checkable = match name.rsplit_once('.') {
+            Some((schema, _)) => schemas.contains(schema),
+            None => name != "DUAL" && !cte_names.contains(name),
+        };
+        let is_system = SYSTEM_OBJECT_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix));
+        if checkable && !is_system && !catalog.contains_key(name) {
+            push_unknown(format!("Unknown table {name}"));
+        }
+    }
+
+    let aliases = table_refs
+        .iter()
+        .filter_map(|table_ref| table_ref.alias.map(|alias| (alias, table_ref.name)))
+        .collect::<HashMap<_, _>>();
+    for (index, token) in tokens.iter().enumerate().skip(suggestion_start) {
+        if table_refs
+            .iter()
+            .any(|table_ref| table_ref.token_index == index)
+        {
+            continue;
+        }
+        let Some((qualifier, column)) = token.rsplit_once('.') else {
+            continue;
+        };
+        if column.is_empty() || PSEUDO_COLUMNS.contains(&column) {
+            continue;
+        }
+        let table_name = aliases.get(qualifier).copied().unwrap_or(qualifier);
+        let Some(object) = catalog.get(table_name) else {
+            continue;
+        };
+        let has_column = object.columns.iter().any(|description| {
+            description
+                .split_whitespace()
+                .next()
+                .is_some_and(|name| name.eq_ignore_ascii_case(column))
+        });
+        if !object.columns.is_empty() && !has_column {
+            push_unknown(format!(
+                "Unknown column {column} on {}.{}",
+                object.schema, object.object_name
+            ));
+        }
+    }
+
+    unknown
+}
+
+struct SqlTableRef<'a> {
+    name: &'a str,
+    alias: Option<&'a str>,
+    token_index: usize,
+}
+
+// Identifiers (dotted names kept whole) plus the punctuation that shapes FROM lists.
+fn sql_tokens(normalized_sql: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (index, ch) in normalized_sql.char_indices() {
+        if is_sql_identifier_char(ch) || ch == '.' {
+            start.get_or_insert(index);
+            continue;
+        }
+        if let Some(token_start) = start.take() {
+            tokens.push(&normalized_sql[token_start..index]);
+        }
+        if matches!(ch, '(' | ')' | ',') {
+            tokens.push(&normalized_sql[index..index + 1]);
+        }
+    }
+    if let Some(token_start) = start {
+        tokens.push(&normalized_sql[token_start..]);
+    }
+    tokens
+}
+
+fn extract_table_refs<'a>(tokens: &[&'a str]) -> Vec<SqlTableRef<'a>> {
+    let is_name = |token: &str| {
+        token.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_')
+            && !SQL_IDENTIFIER_STOP_WORDS
+                .split_whitespace()
+                .any(|word| word == token)
+    };
+
+    let mut table_refs = Vec::new();
+    // One entry per open parenthesis: whether it starts a subquery. `FROM` inside other
+    // parentheses belongs to expressions such as `EXTRACT(YEAR FROM ...)`.
+    let mut paren_is_subquery = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index];
+        match token {
+            "(" => paren_is_subquery.push(matches!(
+                tokens.get(index + 1).copied(),
+                Some("SELECT" | "WITH")
+            )),
+            ")" => {
+                paren_is_subquery.pop();
+            }
+            "FROM" | "JOIN" | "INTO" | "UPDATE"
+                if paren_is_subquery.last().copied().unwrap_or(true)
+                    // `FOR UPDATE OF ...` and `ON DUPLICATE KEY UPDATE ...` name columns.
+                    && !(token == "UPDATE"
+                        && index > 0
+                        && matches!(tokens[index - 1], "FOR" | "KEY")) =>
+            {
+                let mut next = index + 1;
+                while let Some(name) = tokens.get(next).copied().filter(|name| is_name(name)) {
+                    let mut alias_index = next + 1;
+                    if tokens.get(alias_index) == Some(&"AS") {
+                        alias_index += 1;
+                    }
+                    let alias = tokens
+                        .get(alias_index)
+                        .copied()
+                        .filter(|alias| is_name(alias));
+                    table_refs.push(SqlTableRef {
+                        name,
+                        alias,
+                        token_index: next,
+                    });
+                    next = if alias.is_some() {
+                        alias_index + 1
+                    } else {
+                        next + 1
+                    };
+                    if token != "FROM" || tokens.get(next) != Some(&",") {
+                        break;
+                    }
+                    next += 1;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    table_refs
+}
+
 fn build_ai_schema_context_prompt(schema_context: &[DbAiSchemaContextObject]) -> String {
     if schema_context.is_empty() {
         return "\nNo schema context available.".to_string();
@@ -471,3 +1302,147 @@ fn contains_sql_keyword(sql: &str, keyword: &str) -> bool {
 fn is_sql_identifier_char(ch: char) -> bool {
     ch.is_ascii_alphanumeric() || ch == '_' || ch == '$' || ch == '#'
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        describe_ai_column, find_unknown_identifiers, normalize_prompt_settings,
+        parse_ddl_review_payload, rank_schema_context, render_system_prompt, AiCatalogObject,
+        AiSchemaContextCache, AI_RESPONSE_CONTRACT,
+    };
+    use crate::types::{
+        DatabaseProvider, DbAiIdentifierCheck, DbAiPromptSettings, DbAiRiskSeverity,
+        DbAiSuggestQueryResult,
+    };
+
+    fn catalog_object(name: &str, columns: &[&str]) -> AiCatalogObject {
+        AiCatalogObject {
+            schema: "APP".to_string(),
+            object_name: name.to_string(),
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn ranks_referenced_and_hinted_objects_first() {
+        let objects = vec![
+            catalog_object("AUDIT_LOG", &["ID NUMBER"]),
+            catalog_object("CUSTOMERS", &["ID NUMBER", "EMAIL VARCHAR2"]),
+            catalog_object("ORDERS", &["ID NUMBER", "CUSTOMER_ID NUMBER"]),
+        ];
+
+        let ranked = rank_schema_context(
+            objects.as_slice(),
+            "select o.id from app.orders o where o.customer_id = 'CUSTOMERS'",
+        );
+
+        assert_eq!(ranked[0].object_name, "ORDERS");
+        assert!(ranked[0].is_referenced_in_query);
+        assert!(!ranked[1].is_referenced_in_query);
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(
+            describe_ai_column("EMAIL", "VARCHAR2", "N"),
+            "EMAIL VARCHAR2 NOT NULL"
+        );
+    }
+
+    #[test]
+    fn renders_prompt_templates_with_dialect_schema_and_extra_rules() {
+        let default_prompt = render_system_prompt(
+            &DbAiPromptSettings::default(),
+            DatabaseProvider::Oracle,
+            "APP",
+        );
+        assert!(default_prompt.starts_with("You are an expert Oracle SQL assistant"));
+        assert!(default_prompt.contains("against the APP schema"));
+        assert!(default_prompt.contains("- Use correct Oracle SQL syntax (NVL"));
+        assert!(!default_prompt.contains("{{"));
+        assert!(default_prompt.ends_with(AI_RESPONSE_CONTRACT));
+
+        let settings = normalize_prompt_settings(DbAiPromptSettings {
+            system_prompt_template: Some(
+                "Write {{dialect}} for {{schema}}.\n{{extra_rules}}".to_string(),
+            ),
+            extra_rules: vec!["- never suggest DELETE ".to_string(), " ".to_string()],
+        })
+        .expect("settings should be valid");
+        assert_eq!(
+            render_system_prompt(&settings, DatabaseProvider::Mysql, "shop"),
+            format!("Write MySQL for shop.\n- never suggest DELETE\n\n{AI_RESPONSE_CONTRACT}")
+        );
+
+        let error = normalize_prompt_settings(DbAiPromptSettings {
+            system_prompt_template: Some("Use {{dialekt}}".to_string()),
+            extra_rules: Vec::new(),
+        })
+        .expect_err("unknown variables should be rejected");
+        assert!(error.contains("dialekt"));
+    }
+
+    #[test]
+    fn flags_tables_and_columns_missing_from_the_catalog() {
+        let objects = vec![
+            catalog_object("CUSTOMERS", &["ID NUMBER", "EMAIL VARCHAR2"]),
+            catalog_object("ORDERS", &["ID NUMBER", "CUSTOMER_ID NUMBER"]),
+        ];
+
+        let unknown = find_unknown_identifiers(
+            objects.as_slice(),
+            "select o.id from orders o join ",
+            "customers c on c.id = o.customer_ref join shipments s on s.order_id = o.id \
+             where extract(year from o.id) > 0 and o.rowid is not null",
+        );
+        assert_eq!(
+            unknown,
+            vec![
+                "Unknown table SHIPMENTS".to_string(),
+                "Unknown column CUSTOMER_REF on APP.ORDERS".to_string(),
+            ]
+        );
+
+        assert!(find_unknown_identifiers(
+            objects.as_slice(),
+            "",
+            "with recent as (select * from app.orders) select r.id from recent r, dual, hr.jobs"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn discard_mode_drops_the_whole_suggestion_but_keeps_the_warnings() {
+        let cache = AiSchemaContextCache::default();
+        cache.store(1, vec![catalog_object("ORDERS", &["ID NUMBER"])]);
+        let mut result = DbAiSuggestQueryResult {
+            suggestion_text: "o.id, s.id from orders o, shipments s".to_string(),
+            confidence: 0.9,
+            reasoning_short: String::new(),
+            is_potentially_mutating: false,
+            warnings: Vec::new(),
+        };
+        let check = serde_json::from_str::<DbAiIdentifierCheck>("\"drop\"").expect("check");
+        assert_eq!(check, DbAiIdentifierCheck::Discard);
+
+        cache.check_suggestion(Some(1), check, "select ", &mut result);
+        assert!(result.suggestion_text.is_empty());
+        assert_eq!(result.warnings, vec!["Unknown table SHIPMENTS".to_string()]);
+    }
+
+    #[test]
+    fn parses_ddl_review_notes_and_raises_the_overall_level() {
+        let review = parse_ddl_review_payload(
+            "```json\n{\"riskLevel\":\"low\",\"summary\":\" Drops a column. \",\"notes\":[\
+             {\"category\":\"BREAKINGCHANGE\",\"severity\":\"High\",\"message\":\"V_ORDERS selects STATUS\"},\
+             {\"category\":\"performance\",\"severity\":\"medium\",\"message\":\"Rewrites the table\"},\
+             {\"category\":\"grants\",\"severity\":\"low\",\"message\":\" \"}]}\n```",
+        )
+        .expect("review should parse");
+
+        assert_eq!(review.risk_level, DbAiRiskSeverity::High);
+        assert_eq!(review.summary, "Drops a column.");
+        assert_eq!(review.notes.len(), 2);
+        assert_eq!(review.notes[0].category, "breakingChange");
+        assert_eq!(review.notes[1].category, "other");
+        assert_eq!(review.notes[1].severity, DbAiRiskSeverity::Medium);
+        assert!(parse_ddl_review_payload("no json here").is_err());
+    }
+}
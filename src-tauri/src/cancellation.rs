@@ -0,0 +1,194 @@
+use crate::providers::QueryCancelHandle;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) const CANCELLED_MESSAGE: &str = "Cancelled by user.";
+
+/// Lets a long provider call be stopped by the user or by a time limit. Clones share one
+/// state. Loops call `check` between units of work, such as rows or objects; a call that
+/// blocks on the server registers an interrupt through `interrupt_with`, which `cancel`
+/// fires.
+#[derive(Clone, Default)]
+pub(crate) struct CancelToken {
+    inner: Arc<TokenState>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    interrupt: Mutex<Option<QueryCancelHandle>>,
+}
+
+impl CancelToken {
+    /// A token that also stops the call once `timeout` has passed.
+    pub(crate) fn with_timeout(timeout: Option<Duration>) -> Self {
+        Self {
+            inner: Arc::new(TokenState {
+                timeout,
+                deadline: timeout.map(|timeout| Instant::now() + timeout),
+                ..TokenState::default()
+            }),
+        }
+    }
+
+    pub(crate) fn cancel(&self) -> Result<(), String> {
+        self.inner.cancelled.store(true, Ordering::Relaxed);
+        match self.inner.interrupt.lock() {
+            Ok(interrupt) => interrupt.as_ref().map_or(Ok(()), QueryCancelHandle::cancel),
+            Err(_) => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Time left before the time limit, if there is one.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        self.inner
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Fails once the token is cancelled or out of time.
+    pub(crate) fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err(CANCELLED_MESSAGE.to_string());
+        }
+        match (self.remaining(), self.inner.timeout) {
+            (Some(remaining), Some(timeout)) if remaining.is_zero() => Err(format!(
+                "Stopped after the {} second time limit.",
+                timeout.as_secs()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reports why a call failed: an interrupted call fails with a provider error, which
+    /// is replaced with the reason it was stopped.
+    pub(crate) fn explain(&self, error: String) -> String {
+        self.check().err().unwrap_or(error)
+    }
+
+    /// Makes `cancel` interrupt the provider call running until the guard drops. A token
+    /// that is already cancelled interrupts at once.
+    pub(crate) fn interrupt_with(&self, handle: QueryCancelHandle) -> InterruptGuard<'_> {
+        if let Ok(mut interrupt) = self.inner.interrupt.lock() {
+            if self.is_cancelled() {
+                let _ = handle.cancel();
+            }
+            *interrupt = Some(handle);
+        }
+        InterruptGuard { token: self }
+    }
+}
+
+pub(crate) struct InterruptGuard<'a> {
+    token: &'a CancelToken,
+}
+
+impl Drop for InterruptGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut interrupt) = self.token.inner.interrupt.lock() {
+            *interrupt = None;
+        }
+    }
+}
+
+/// Tokens of the calls running on each session, so `db_cancel_query` can stop them. Kept
+/// outside the session, whose lock a running call holds.
+#[derive(Default)]
+pub(crate) struct CancelRegistry {
+    next_call_id: AtomicU64,
+    calls: Mutex<HashMap<u64, Vec<(u64, CancelToken)>>>,
+}
+
+impl CancelRegistry {
+    /// Makes `token` cancellable through its session until the guard drops.
+    pub(crate) fn register(&self, session_id: u64, token: &CancelToken) -> RegisteredCall<'_> {
+        let call_id = self.next_call_id.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut calls) = self.calls.lock() {
+            calls
+                .entry(session_id)
+                .or_default()
+                .push((call_id, token.clone()));
+        }
+        RegisteredCall {
+            registry: self,
+            session_id,
+            call_id,
+        }
+    }
+
+    /// Cancels every call running on the session and returns how many there were.
+    pub(crate) fn cancel_session(&self, session_id: u64) -> Result<usize, String> {
+        let calls = self
+            .calls
+            .lock()
+            .map_err(|_| "Failed to acquire cancellation lock".to_string())?;
+        let Some(tokens) = calls.get(&session_id) else {
+            return Ok(0);
+        };
+        for (_, token) in tokens {
+            token.cancel()?;
+        }
+        Ok(tokens.len())
+    }
+}
+
+pub(crate) struct RegisteredCall<'a> {
+    registry: &'a CancelRegistry,
+    session_id: u64,
+    call_id: u64,
+}
+
+impl Drop for RegisteredCall<'_> {
+    fn drop(&mut self) {
+        let Ok(mut calls) = self.registry.calls.lock() else {
+            return;
+        };
+        if let Some(tokens) = calls.get_mut(&self.session_id) {
+            tokens.retain(|(call_id, _)| *call_id != self.call_id);
+            if tokens.is_empty() {
+                calls.remove(&self.session_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CancelRegistry, CancelToken, CANCELLED_MESSAGE};
+    use std::time::Duration;
+
+    #[test]
+    fn cancelling_a_session_stops_only_its_running_calls() {
+        let registry = CancelRegistry::default();
+        let running = CancelToken::default();
+        let other = CancelToken::default();
+        let call = registry.register(1, &running);
+        let _other_call = registry.register(2, &other);
+
+        assert_eq!(registry.cancel_session(1), Ok(1));
+        assert_eq!(running.check(), Err(CANCELLED_MESSAGE.to_string()));
+        assert!(other.check().is_ok());
+
+        drop(call);
+        assert_eq!(registry.cancel_session(1), Ok(0));
+    }
+
+    #[test]
+    fn time_limit_stops_the_call_and_explains_the_error() {
+        let token = CancelToken::with_timeout(Some(Duration::ZERO));
+        let error = token.explain("ORA-01013: user requested cancel".to_string());
+        assert!(error.contains("time limit"), "{error}");
+        assert_eq!(
+            CancelToken::default().explain("ORA-00942".to_string()),
+            "ORA-00942"
+        );
+    }
+}
@@ -0,0 +1,456 @@
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::types::{
+    DbGenerateCodeRequest, DbGenerateCodeResult, DbGeneratedFile, DbObjectColumnEntry,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tera::{Context, Tera, Value};
+
+const CONTENT_TEMPLATE: &str = "content";
+const FILE_NAME_TEMPLATE: &str = "file_name";
+
+/// What templates see as `table` (and each entry of `tables`).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CodegenTable {
+    schema: String,
+    name: String,
+    object_type: String,
+    camel_name: String,
+    pascal_name: String,
+    snake_name: String,
+    columns: Vec<CodegenColumn>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CodegenColumn {
+    name: String,
+    data_type: String,
+    nullable: bool,
+    camel_name: String,
+    pascal_name: String,
+    snake_name: String,
+    java_type: &'static str,
+    ts_type: &'static str,
+}
+
+/// Renders `request.template` over the metadata of the requested objects. The file name is
+/// a template too: when it renders differently per object, each object gets its own file
+/// (with `table` set); otherwise one file receives all of them as `tables`.
+pub(crate) fn generate_code(
+    session: &AppSession,
+    request: &DbGenerateCodeRequest,
+) -> Result<DbGenerateCodeResult, String> {
+    if request.objects.is_empty() {
+        return Err("Select at least one table to generate code from".to_string());
+    }
+    let destination = PathBuf::from(request.destination_directory.trim());
+    if !request.dry_run && request.destination_directory.trim().is_empty() {
+        return Err("Destination directory is required".to_string());
+    }
+
+    let tera = build_tera(request)?;
+    let tables = load_tables(session, request)?;
+    let provider = session.provider.label();
+    let files = render_files(&tera, tables, provider)?;
+
+    if !request.dry_run {
+        write_files(&destination, &files, request.overwrite)?;
+    }
+
+    Ok(DbGenerateCodeResult {
+        files,
+        written: !request.dry_run,
+    })
+}
+
+fn build_tera(request: &DbGenerateCodeRequest) -> Result<Tera, String> {
+    let mut tera = Tera::default();
+    // Generated code is not HTML.
+    tera.autoescape_on(Vec::new());
+    tera.add_raw_template(CONTENT_TEMPLATE, &request.template)
+        .map_err(|error| format!("Invalid template: {}", describe_tera_error(&error)))?;
+    tera.add_raw_template(FILE_NAME_TEMPLATE, request.file_name.trim())
+        .map_err(|error| {
+            format!(
+                "Invalid file name template: {}",
+                describe_tera_error(&error)
+            )
+        })?;
+    tera.register_filter("camel_case", case_filter(camel_case));
+    tera.register_filter("pascal_case", case_filter(pascal_case));
+    tera.register_filter("snake_case", case_filter(snake_case));
+    Ok(tera)
+}
+
+fn case_filter(
+    convert: fn(&str) -> String,
+) -> impl Fn(&Value, &HashMap<String, Value>) -> tera::Result<Value> + Send + Sync {
+    move |value, _| match value.as_str() {
+        Some(text) => Ok(Value::String(convert(text))),
+        None => Err(tera::Error::msg("case filters expect a string")),
+    }
+}
+
+fn load_tables(
+    session: &AppSession,
+    request: &DbGenerateCodeRequest,
+) -> Result<Vec<CodegenTable>, String> {
+    let object_types = ProviderRegistry::list_objects(session)?
+        .into_iter()
+        .map(|object| ((object.schema, object.object_name), object.object_type))
+        .collect::<HashMap<_, _>>();
+    let mut columns_by_object = HashMap::<_, Vec<DbObjectColumnEntry>>::new();
+    for column in ProviderRegistry::list_object_columns(session)? {
+        columns_by_object
+            .entry((column.schema.clone(), column.object_name.clone()))
+            .or_default()
+            .push(column);
+    }
+
+    request
+        .objects
+        .iter()
+        .map(|object| {
+            let key = (object.schema.clone(), object.object_name.clone());
+            let columns = columns_by_object.remove(&key).ok_or_else(|| {
+                format!(
+                    "No columns found for {}.{}",
+                    object.schema, object.object_name
+                )
+            })?;
+            Ok(CodegenTable {
+                schema: object.schema.clone(),
+                name: object.object_name.clone(),
+                object_type: object_types
+                    .get(&key)
+                    .cloned()
+                    .unwrap_or_else(|| "TABLE".to_string()),
+                camel_name: camel_case(&object.object_name),
+                pascal_name: pascal_case(&object.object_name),
+                snake_name: snake_case(&object.object_name),
+                columns: columns.into_iter().map(codegen_column).collect(),
+            })
+        })
+        .collect()
+}
+
+fn codegen_column(column: DbObjectColumnEntry) -> CodegenColumn {
+    CodegenColumn {
+        camel_name: camel_case(&column.column_name),
+        pascal_name: pascal_case(&column.column_name),
+        snake_name: snake_case(&column.column_name),
+        java_type: java_type(&column.data_type),
+        ts_type: ts_type(&column.data_type),
+        nullable: !column.nullable.eq_ignore_ascii_case("N"),
+        name: column.column_name,
+        data_type: column.data_type,
+    }
+}
+
+fn render_files(
+    tera: &Tera,
+    tables: Vec<CodegenTable>,
+    provider: &str,
+) -> Result<Vec<DbGeneratedFile>, String> {
+    // Group tables by rendered file name, keeping the order they were requested in.
+    let mut groups: Vec<(String, Vec<CodegenTable>)> = Vec::new();
+    for table in tables {
+        let mut context = Context::new();
+        context.insert("table", &table);
+        context.insert("provider", provider);
+        let file_name = render(tera, FILE_NAME_TEMPLATE, &context)?
+            .trim()
+            .to_string();
+        validate_relative_path(&file_name)?;
+        match groups.iter_mut().find(|(name, _)| *name == file_name) {
+            Some((_, group)) => group.push(table),
+            None => groups.push((file_name, vec![table])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(path, tables)| {
+            let mut context = Context::new();
+            if let [table] = tables.as_slice() {
+                context.insert("table", table);
+            }
+            context.insert("tables", &tables);
+            context.insert("provider", provider);
+            Ok(DbGeneratedFile {
+                content: render(tera, CONTENT_TEMPLATE, &context)?,
+                object_names: tables.iter().map(|table| table.name.clone()).collect(),
+                path,
+            })
+        })
+        .collect()
+}
+
+fn render(tera: &Tera, template: &str, context: &Context) -> Result<String, String> {
+    tera.render(template, context)
+        .map_err(|error| format!("Failed to render template: {}", describe_tera_error(&error)))
+}
+
+/// Tera nests the useful part of an error (e.g. the missing variable) in its source chain.
+fn describe_tera_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn validate_relative_path(file_name: &str) -> Result<(), String> {
+    if file_name.is_empty() {
+        return Err("The file name template rendered an empty name".to_string());
+    }
+    let path = Path::new(file_name);
+    if path
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!(
+            "Generated file name '{file_name}' must stay inside the destination directory"
+        ));
+    }
+    Ok(())
+}
+
+fn write_files(
+    destination: &Path,
+    files: &[DbGeneratedFile],
+    overwrite: bool,
+) -> Result<(), String> {
+    // Check everything first so a conflict does not leave half the files written.
+    if !overwrite {
+        if let Some(existing) = files
+            .iter()
+            .find(|file| destination.join(&file.path).exists())
+        {
+            return Err(format!(
+                "{} already exists; enable overwrite to replace it",
+                existing.path
+            ));
+        }
+    }
+
+    for file in files {
+        let path = destination.join(&file.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|error| format!("Failed to create directory: {error}"))?;
+        }
+        fs::write(&path, &file.content)
+            .map_err(|error| format!("Failed to write {}: {error}", file.path))?;
+    }
+    Ok(())
+}
+
+/// Splits `ORDER_LINES`, `orderLines` and `order-lines` alike into lowercase words.
+fn words(identifier: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for ch in identifier.chars() {
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if ch.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = ch.is_lowercase() || ch.is_ascii_digit();
+        current.extend(ch.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn pascal_case(identifier: &str) -> String {
+    words(identifier)
+        .iter()
+        .map(|word| capitalize(word))
+        .collect()
+}
+
+fn camel_case(identifier: &str) -> String {
+    let words = words(identifier);
+    let mut result = words.first().cloned().unwrap_or_default();
+    for word in words.iter().skip(1) {
+        result.push_str(&capitalize(word));
+    }
+    result
+}
+
+fn snake_case(identifier: &str) -> String {
+    words(identifier).join("_")
+}
+
+/// Base type name without length, precision or modifiers, e.g. `NUMBER(10,2)` -> `NUMBER`.
+fn base_type(data_type: &str) -> String {
+    let upper = data_type.trim().to_uppercase();
+    // ClickHouse wraps types: Nullable(Int64), LowCardinality(String).
+    let unwrapped = ["NULLABLE(", "LOWCARDINALITY("]
+        .iter()
+        .fold(upper.as_str(), |value, wrapper| {
+            value
+                .strip_prefix(wrapper)
+                .and_then(|inner| inner.strip_suffix(')'))
+                .unwrap_or(value)
+        })
+        .to_string();
+    unwrapped
+        .split(['(', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn java_type(data_type: &str) -> &'static str {
+    let base = base_type(data_type);
+    let upper = data_type.to_uppercase();
+    match base.as_str() {
+        "BIGINT" | "INT8" | "INT64" | "UINT32" | "UINT64" | "LONG" => "Long",
+        "INT" | "INTEGER" | "MEDIUMINT" | "SMALLINT" | "TINYINT" | "INT4" | "INT2" | "INT16"
+        | "INT32" | "UINT8" | "UINT16" | "PLS_INTEGER" => "Integer",
+        "NUMBER" | "NUMERIC" | "DECIMAL" | "DEC" | "DECIMAL128" | "BIGNUMERIC" => "BigDecimal",
+        "FLOAT" | "FLOAT4" | "FLOAT8" | "FLOAT32" | "FLOAT64" | "REAL" | "DOUBLE"
+        | "BINARY_FLOAT" | "BINARY_DOUBLE" => "Double",
+        "BOOL" | "BOOLEAN" | "BIT" => "Boolean",
+        "DATE" | "DATE32" => "LocalDate",
+        "TIME" => "LocalTime",
+        "TIMESTAMP" if upper.contains("TIME ZONE") => "OffsetDateTime",
+        "TIMESTAMPTZ" | "TIMESTAMP_TZ" | "TIMESTAMP_LTZ" => "OffsetDateTime",
+        "TIMESTAMP" | "TIMESTAMP_NTZ" | "DATETIME" | "DATETIME64" => "LocalDateTime",
+        "BLOB" | "RAW" | "LONGBLOB" | "MEDIUMBLOB" | "TINYBLOB" | "BINARY" | "VARBINARY"
+        | "BYTEA" => "byte[]",
+        "UUID" => "UUID",
+        _ => "String",
+    }
+}
+
+fn ts_type(data_type: &str) -> &'static str {
+    match java_type(data_type) {
+        "Long" | "Integer" | "BigDecimal" | "Double" => "number",
+        "Boolean" => "boolean",
+        _ => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_tera, camel_case, java_type, pascal_case, render_files, snake_case, ts_type,
+        validate_relative_path, CodegenColumn, CodegenTable,
+    };
+    use crate::types::DbGenerateCodeRequest;
+
+    fn table(name: &str) -> CodegenTable {
+        CodegenTable {
+            schema: "HR".to_string(),
+            name: name.to_string(),
+            object_type: "TABLE".to_string(),
+            camel_name: camel_case(name),
+            pascal_name: pascal_case(name),
+            snake_name: snake_case(name),
+            columns: vec![CodegenColumn {
+                name: "EMPLOYEE_ID".to_string(),
+                data_type: "NUMBER(10)".to_string(),
+                nullable: false,
+                camel_name: camel_case("EMPLOYEE_ID"),
+                pascal_name: pascal_case("EMPLOYEE_ID"),
+                snake_name: snake_case("EMPLOYEE_ID"),
+                java_type: java_type("NUMBER(10)"),
+                ts_type: ts_type("NUMBER(10)"),
+            }],
+        }
+    }
+
+    fn request(template: &str, file_name: &str) -> DbGenerateCodeRequest {
+        DbGenerateCodeRequest {
+            session_id: 1,
+            objects: Vec::new(),
+            template: template.to_string(),
+            file_name: file_name.to_string(),
+            destination_directory: String::new(),
+            overwrite: false,
+            dry_run: true,
+        }
+    }
+
+    #[test]
+    fn converts_identifier_case_and_maps_types() {
+        assert_eq!(pascal_case("ORDER_LINES"), "OrderLines");
+        assert_eq!(camel_case("order_lines"), "orderLines");
+        assert_eq!(camel_case("orderLines"), "orderLines");
+        assert_eq!(snake_case("OrderLines"), "order_lines");
+        assert_eq!(snake_case("order-lines 2"), "order_lines_2");
+
+        assert_eq!(java_type("NUMBER(10,2)"), "BigDecimal");
+        assert_eq!(java_type("Nullable(Int64)"), "Long");
+        assert_eq!(java_type("TIMESTAMP(6) WITH TIME ZONE"), "OffsetDateTime");
+        assert_eq!(java_type("TIMESTAMP(6)"), "LocalDateTime");
+        assert_eq!(java_type("VARCHAR2(100 CHAR)"), "String");
+        assert_eq!(ts_type("BOOLEAN"), "boolean");
+        assert_eq!(ts_type("DATE"), "string");
+    }
+
+    #[test]
+    fn renders_one_file_per_table_or_one_file_for_all() {
+        let template = concat!(
+            "{% for t in tables %}class {{ t.pascalName }} { ",
+            "{% for c in t.columns %}{{ c.javaType }} {{ c.camelName }}; {% endfor %}}\n",
+            "{% endfor %}"
+        );
+
+        let tera = build_tera(&request(template, "{{ table.pascalName }}.java")).expect("tera");
+        let files = render_files(
+            &tera,
+            vec![table("EMPLOYEES"), table("JOB_HISTORY")],
+            "oracle",
+        )
+        .expect("per table");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[1].path, "JobHistory.java");
+        assert_eq!(
+            files[0].content,
+            "class Employees { BigDecimal employeeId; }\n"
+        );
+
+        let tera = build_tera(&request(template, "model/dto.java")).expect("tera");
+        let files = render_files(
+            &tera,
+            vec![table("EMPLOYEES"), table("JOB_HISTORY")],
+            "oracle",
+        )
+        .expect("single file");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].object_names, vec!["EMPLOYEES", "JOB_HISTORY"]);
+
+        let tera = build_tera(&request("{{ missing }}", "out.txt")).expect("tera");
+        assert!(render_files(&tera, vec![table("EMPLOYEES")], "oracle").is_err());
+        assert!(validate_relative_path("../outside.java").is_err());
+        assert!(validate_relative_path("/etc/passwd").is_err());
+        assert!(validate_relative_path("dto/Employee.java").is_ok());
+    }
+}
@@ -1,30 +1,129 @@
 use crate::ai;
+use crate::cancellation::CancelToken;
+use crate::codegen;
+use crate::data_export;
+use crate::ddl_backups;
+use crate::ddl_changelog::{self, PendingDdlChange};
+use crate::diagnostics;
+use crate::external_edit;
 use crate::files;
+use crate::grants;
+use crate::idle_timeout;
+use crate::keep_alive;
+use crate::masking;
+use crate::menu::{EVENT_QUERY_ROWS, EVENT_SESSION_RECONNECTED};
+use crate::metadata_query;
+use crate::object_columns;
+use crate::oracle_clients;
+use crate::oracle_wallets;
 use crate::profiles;
 use crate::providers::{AppSession, ProviderRegistry};
-use crate::state::AppState;
+use crate::query_stream;
+use crate::report;
+use crate::schema_snapshots;
+use crate::scratch;
+use crate::sensitive_data;
+use crate::session_timeline;
+use crate::snapshots;
+use crate::sql_safety;
+use crate::sql_script;
+use crate::sql_splitter;
+use crate::sqlplus;
+use crate::standby;
+use crate::state::{lock_session, AppState};
+use crate::support_bundle;
+use crate::table_import;
+use crate::text_diff;
+use crate::tnsnames;
 use crate::types::{
-    ConnectionProfile, ConnectionProfileRef, DbAiApiKeyPresence, DbAiSuggestQueryRequest,
-    DbAiSuggestQueryResult, DbConnectError, DbConnectRequest, DbConnectionProfile,
-    DbExportSchemaRequest, DbObjectColumnEntry, DbObjectDdlUpdateRequest, DbObjectEntry,
-    DbObjectRef, DbQueryRequest, DbQueryResult, DbSaveQuerySheetRequest,
-    DbSaveQuerySheetsRequest, DbSaveQuerySheetsResult, DbSchemaExportResult,
-    DbSchemaSearchRequest, DbSchemaSearchResult, DbSessionSummary, DbTransactionState,
-    NetworkConnectionOptions, OracleConnectionOptions, SaveConnectionProfileRequest,
-    SessionRequest, StoredConnectionProfile,
+    ConnectionProfile, ConnectionProfileRef, DatabaseProvider, DbAiApiKeyPresence,
+    DbAiDdlReviewResult, DbAiPromptSettings, DbAiReviewDdlRequest, DbAiSchemaContextStatus,
+    DbAiSuggestQueryRequest, DbAiSuggestQueryResult, DbAqBrowseRequest, DbAqBrowseResult,
+    DbAqQueueEntry, DbClipboardImportResult, DbCloseCursorRequest, DbConnectConnection,
+    DbConnectError, DbConnectRequest, DbConnectionProfile, DbConnectionTestResult,
+    DbConsistentQueriesRequest, DbConsistentQueriesResult, DbContainer,
+    DbCreateScratchTableRequest, DbCreateSupportBundleRequest, DbDataExportResult, DbDdlBackup,
+    DbDdlBackupRetention, DbDdlChange, DbDdlChangelogExport, DbDetectSensitiveColumnsRequest,
+    DbDiagnosticsReport, DbDiffCellValuesRequest, DbDownloadLobRequest,
+    DbEditObjectExternallyRequest, DbEncodingInfo, DbErrorSummary, DbExportDdlChangelogRequest,
+    DbExportQueryDataRequest, DbExportResultReportRequest, DbExportSchemaRequest,
+    DbExportSessionTimelineRequest, DbExportWorkspaceRequest, DbExternalEdit, DbExternalProvider,
+    DbFetchMoreRowsRequest, DbGenerateCodeRequest, DbGenerateCodeResult, DbGenerateGrantsRequest,
+    DbGetDdlChangelogRequest, DbGrantScript, DbIdentifierUsage, DbIdentifierUsageRequest,
+    DbIdleTimeoutRequest, DbImportClipboardRowsRequest, DbImportOracleWalletRequest,
+    DbImportWorkspaceRequest, DbJobRequest, DbJobStatus, DbKeepAliveRequest,
+    DbListBindNamesRequest, DbListDdlBackupsRequest, DbListObjectColumnsRequest,
+    DbListTaggedObjectsRequest, DbListTnsAliasesRequest, DbLobDownloadResult, DbMaskedQueryRequest,
+    DbMaskedQueryResult, DbMaskingRule, DbMetadataDocument, DbMetadataQueryRequest,
+    DbObjectAccessStatsRequest, DbObjectAccessStatsResult, DbObjectColumnEntry, DbObjectDdl,
+    DbObjectDdlAsOf, DbObjectDdlAsOfRequest, DbObjectDdlUpdateRequest, DbObjectEditionInfo,
+    DbObjectEntry, DbObjectFileDiffRequest, DbObjectFileDiffResult, DbObjectRef,
+    DbObjectTagRequest, DbOracleClient, DbOracleClientPathRequest, DbOracleWallet,
+    DbOracleWalletNameRequest, DbPinObjectRequest, DbPinnedObject, DbQueryHistoryEntry,
+    DbQueryHistoryRequest, DbQueryRequest, DbQueryResult, DbQueryStreamSummary, DbReaderEndpoint,
+    DbRecentErrorsRequest, DbRecentErrorsResult, DbReconnectRequest, DbReconnectSessionRequest,
+    DbResourceLimits, DbResultReport, DbResultSnapshot, DbResultSnapshotRef,
+    DbResultSnapshotSummary, DbRowLocksRequest, DbRowLocksResult, DbRunDiagnosticsRequest,
+    DbRunQueryRequest, DbRunScriptRequest, DbRunSnippetRequest, DbSaveEditorBuffersRequest,
+    DbSaveMaskingRulesRequest, DbSaveOracleClientRequest, DbSaveQuerySheetRequest,
+    DbSaveQuerySheetsRequest, DbSaveQuerySheetsResult, DbSaveResultSnapshotRequest,
+    DbSaveSchemaSnapshotScheduleRequest, DbSaveSnippetParameterSetRequest, DbSaveSnippetRequest,
+    DbSchemaExportResult, DbSchemaSearchRequest, DbSchemaSearchResult, DbSchemaSnapshotSchedule,
+    DbSchemaSnapshotScheduleRef, DbScratchTable, DbScratchTableRef, DbScriptResult,
+    DbSensitiveColumnsReport, DbServerInfo, DbSessionInfo, DbSessionNls, DbSessionReconnected,
+    DbSessionSummary, DbSessionTimeline, DbSessionTimelineEvent, DbSessionTimelineEventKind,
+    DbSessionTimelineExport, DbSetContainerRequest, DbSetResourceLimitsRequest,
+    DbSetSessionNlsRequest, DbSnippet, DbSnippetParameterSet, DbSnippetParameterSetRef,
+    DbSnippetRef, DbSplitSqlRequest, DbSqlFileImportRequest, DbSqlStatement, DbStandbyConnection,
+    DbStateFileRecovery, DbStopExternalEditRequest, DbStreamQueryRequest, DbSupportBundleResult,
+    DbTaggedObject, DbTestConnectionRequest, DbTextDiffResult, DbTimestampTzModeRequest,
+    DbTnsAliasList, DbTransactionState, DbWorkspaceArchiveSummary, DbWorkspaceSearchRequest,
+    DbWorkspaceSearchResult, DbXlsxImportRequest, DbXlsxPreview, DbXlsxPreviewRequest,
+    DuckdbConnectionOptions, ExternalConnectionOptions, LibsqlConnectionOptions,
+    NetworkConnectionOptions, OracleAddress, OracleConnectionOptions, OracleExternalPasswordStore,
+    OracleLdapNaming, OracleProtocol, OracleTlsOptions, OracleTnsAliasRef, OracleWalletRef,
+    SaveConnectionProfileRequest, SessionRequest, SnowflakeConnectionOptions, SshTunnelOptions,
+    StoredConnectionProfile, TimestampTzMode,
 };
 use crate::validation::{
-    validate_ai_suggest_request, validate_connect_request, validate_profile_request,
+    validate_ai_review_ddl_request, validate_ai_suggest_request,
+    validate_create_scratch_table_request, validate_profile_request,
 };
+use crate::workspace;
+use crate::workspace_archive;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
 
 #[tauri::command]
-pub(crate) fn db_connect(
+pub(crate) async fn db_connect(
     request: DbConnectRequest,
-    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
 ) -> Result<DbSessionSummary, DbConnectError> {
-    validate_connect_request(&request).map_err(DbConnectError::general)?;
-    let (session, display_name, schema) = ProviderRegistry::connect(&request)?;
+    run_connect_blocking(app, move |state, app| connect_session(request, &state, app)).await
+}
+
+fn connect_session(
+    request: DbConnectRequest,
+    state: &tauri::State<'_, AppState>,
+    app: &tauri::AppHandle,
+) -> Result<DbSessionSummary, DbConnectError> {
+    let request =
+        profiles::resolve_connect_request(app, request).map_err(DbConnectError::general)?;
+    let (session, display_name, schema) = match state.standby.take(&request) {
+        Some(standby) => {
+            let mut session = standby.session;
+            session.adopt(&request);
+            standby::refresh_in_background(app);
+            (session, standby.display_name, standby.schema)
+        }
+        None => ProviderRegistry::connect(&request)?,
+    };
+    let server_version = ProviderRegistry::server_version(&session);
+    let instance_name = ProviderRegistry::instance_name(&session);
+    let (host, service, username) = connection_details(&request.connection);
 
     let session_id = state.next_session_id.fetch_add(1, Ordering::Relaxed);
     let summary = DbSessionSummary {
@@ -32,153 +131,1538 @@ pub(crate) fn db_connect(
         display_name,
         schema,
         provider: request.provider(),
+        host,
+        service,
+        username,
+        read_only: request.read_only,
+        environment: normalize_environment(request.environment.as_deref()),
+        server_version,
+        instance_name,
     };
 
-    let mut sessions = state
+    state
         .sessions
-        .lock()
-        .map_err(|_| DbConnectError::general("Failed to acquire session lock"))?;
-    sessions.insert(session_id, session);
+        .insert(session_id, session)
+        .map_err(DbConnectError::general)?;
+    state.session_pools.register(session_id, &request);
+    state.read_replicas.register(session_id, &request);
+    state
+        .session_timeline
+        .start(session_id, &summary.display_name);
+
+    if request.prefetch_ai_schema_context {
+        let sessions = state.sessions.clone();
+        let cache = state.ai_schema_context.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            // Suggestions fall back to the context sent by the editor if this fails.
+            let _ = ai::prefetch_schema_context(&sessions, &cache, session_id);
+        });
+    }
 
     Ok(summary)
 }
 
+/// Connects and runs a trivial query, then closes the connection without registering a
+/// session.
+#[tauri::command]
+pub(crate) async fn db_test_connection(
+    request: DbTestConnectionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbConnectionTestResult, DbConnectError> {
+    run_connect_blocking(app, move |_, app| test_connection(request, app)).await
+}
+
+fn test_connection(
+    request: DbTestConnectionRequest,
+    app: &tauri::AppHandle,
+) -> Result<DbConnectionTestResult, DbConnectError> {
+    let DbTestConnectionRequest {
+        connect: mut request,
+        profile_id,
+    } = request;
+    if let Some(profile_id) = profile_id.as_deref().map(str::trim) {
+        profiles::fill_profile_secrets(&mut request, profile_id)
+            .map_err(DbConnectError::general)?;
+    }
+    let request =
+        profiles::resolve_connect_request(app, request).map_err(DbConnectError::general)?;
+
+    let started = Instant::now();
+    let (mut session, display_name, schema) = ProviderRegistry::connect(&request)?;
+    let connect_millis = started.elapsed().as_millis() as u64;
+    let latency = ProviderRegistry::ping(&mut session).map_err(DbConnectError::general)?;
+    let server_version = ProviderRegistry::server_version(&session);
+    let instance_name = ProviderRegistry::instance_name(&session);
+    drop(session);
+
+    let latency_millis = latency.map(|latency| latency.as_millis() as u64);
+    let landed = instance_name
+        .as_deref()
+        .map(|instance| format!(" (instance {instance})"))
+        .unwrap_or_default();
+    let message = match latency_millis {
+        Some(latency_millis) => format!(
+            "Connected to {display_name}{landed} in {connect_millis} ms; a query round trip took {latency_millis} ms."
+        ),
+        None => format!("Connected to {display_name}{landed} in {connect_millis} ms."),
+    };
+    Ok(DbConnectionTestResult {
+        provider: request.provider(),
+        display_name,
+        schema,
+        server_version,
+        instance_name,
+        connect_millis,
+        latency_millis,
+        message,
+    })
+}
+
 #[tauri::command]
-pub(crate) fn db_disconnect(
+pub(crate) async fn db_disconnect(
     request: SessionRequest,
-    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
 ) -> Result<(), String> {
-    let mut sessions = state
-        .sessions
-        .lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
+    run_blocking(app, move |state, _| {
+        if state.close_session(request.session_id)? {
+            state.session_timeline.record(
+                request.session_id,
+                session_timeline::event(
+                    DbSessionTimelineEventKind::Disconnect,
+                    "Disconnected".to_string(),
+                ),
+            );
+            Ok(())
+        } else {
+            Err("Session not found".to_string())
+        }
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_list_external_providers() -> Result<Vec<DbExternalProvider>, String> {
+    ProviderRegistry::list_external_providers()
+}
+
+#[tauri::command]
+pub(crate) async fn db_list_objects(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbObjectEntry>, String> {
+    run_blocking(app, move |state, _| {
+        with_pooled_session(&state, request.session_id, ProviderRegistry::list_objects)
+    })
+    .await
+}
+
+/// Every column of the connected schema, or only those of `objects`. A list of objects
+/// is read over up to `concurrency` pooled connections at once.
+#[tauri::command]
+pub(crate) async fn db_list_object_columns(
+    request: DbListObjectColumnsRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    run_blocking(app, move |state, _| {
+        let session_id = request.session_id;
+        let Some(objects) = request.objects.filter(|objects| !objects.is_empty()) else {
+            return with_pooled_session(&state, session_id, ProviderRegistry::list_object_columns);
+        };
+        let provider = with_pooled_session(&state, session_id, |session| Ok(session.provider))?;
+        let workers = if ProviderRegistry::lists_columns_per_object(provider) {
+            let pool_size = state.session_pools.usage(session_id, provider).size;
+            object_columns::worker_count(request.concurrency, pool_size, objects.len())
+        } else {
+            1
+        };
+        object_columns::fetch_parallel(&objects, workers, |batch| {
+            with_pooled_session(&state, session_id, |session| {
+                ProviderRegistry::list_columns_of(session, batch)
+            })
+        })
+    })
+    .await
+}
+
+/// Objects with the facets `include` asks for (columns, constraints, stats, grants) as one
+/// nested document, read on a pooled connection when one is free.
+#[tauri::command]
+pub(crate) async fn db_query_metadata(
+    request: DbMetadataQueryRequest,
+    app: tauri::AppHandle,
+) -> Result<DbMetadataDocument, String> {
+    run_blocking(app, move |state, _| {
+        with_pooled_session(&state, request.session_id, |session| {
+            metadata_query::query_metadata(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_object_ddl(
+    request: DbObjectRef,
+    app: tauri::AppHandle,
+) -> Result<DbObjectDdl, String> {
+    run_blocking(app, move |state, _| {
+        with_pooled_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_ddl_detail(session, &request)
+        })
+    })
+    .await
+}
 
-    match sessions.remove(&request.session_id) {
-        Some(_) => Ok(()),
-        None => Err("Session not found".to_string()),
+/// An earlier version of an object's DDL, from a flashback query where the database
+/// allows it and from Clarity's DDL backups otherwise.
+#[tauri::command]
+pub(crate) async fn db_get_object_ddl_as_of(
+    request: DbObjectDdlAsOfRequest,
+    app: tauri::AppHandle,
+) -> Result<DbObjectDdlAsOf, String> {
+    run_blocking(app, move |state, app| {
+        let seconds_ago = workspace::now_millis()
+            .checked_sub(request.as_of)
+            .map(|millis| millis / 1000)
+            .ok_or_else(|| "The time to compare against must be in the past".to_string())?;
+        let object = DbObjectRef {
+            session_id: request.session_id,
+            schema: request.schema.clone(),
+            object_type: request.object_type.clone(),
+            object_name: request.object_name.clone(),
+        };
+        let profile_id = with_session(&state, request.session_id, |session| {
+            Ok(session_profile_id(session))
+        })?;
+        let flashback = with_pooled_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_source_as_of(session, &object, seconds_ago)
+        });
+        ddl_backups::ddl_as_of(app, &request, profile_id.as_deref(), flashback)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_object_edition(
+    request: DbObjectRef,
+    app: tauri::AppHandle,
+) -> Result<DbObjectEditionInfo, String> {
+    run_blocking(app, move |state, _| {
+        with_pooled_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_edition(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_update_object_ddl(
+    request: DbObjectDdlUpdateRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    run_blocking(app, move |state, app| {
+        let object = DbObjectRef {
+            session_id: request.session_id,
+            schema: request.schema.clone(),
+            object_type: request.object_type.clone(),
+            object_name: request.object_name.clone(),
+        };
+        let started = Instant::now();
+        let result = with_session_mut(&state, request.session_id, |session| {
+            let statements = [DbSqlStatement {
+                sql: request.ddl.clone(),
+                line: 1,
+            }];
+            sql_safety::check_production(
+                request.session_id,
+                session,
+                &statements,
+                request.production_confirmation.as_deref(),
+            )?;
+            // An object that cannot be read back does not exist yet, so there is nothing to lose.
+            let current_ddl = ProviderRegistry::get_object_ddl(session, &object).ok();
+            if let Some(current_ddl) = &current_ddl {
+                let profile_id = session_profile_id(session);
+                ddl_backups::backup_object_ddl(
+                    app,
+                    profile_id.as_deref(),
+                    &object,
+                    current_ddl.as_str(),
+                )
+                .map_err(|error| format!("{error}. The object was not changed."))?;
+            }
+            let change = PendingDdlChange::edit(&object, &request.ddl, current_ddl.as_deref());
+            let result = ProviderRegistry::update_object_ddl(session, &request);
+            let error = result.as_ref().err().cloned();
+            let _ = ddl_changelog::record_changes(app, session, vec![change], |_, _| {
+                Some(error.clone())
+            });
+            result
+        });
+        state.session_timeline.record(
+            request.session_id,
+            session_timeline::sql_event(
+                DbSessionTimelineEventKind::DdlEdit,
+                &request.ddl,
+                &result,
+                |_| {
+                    format!(
+                        "Updated {} {}.{}",
+                        request.object_type, request.schema, request.object_name
+                    )
+                },
+                started.elapsed(),
+            ),
+        );
+        result
+    })
+    .await
+}
+
+/// Runs a statement. On Oracle, `&name` substitution variables are replaced with the given
+/// values first; while some have none, the result lists them in `requiredVariables` and
+/// nothing runs, so the caller can ask for them and submit again.
+#[tauri::command]
+pub(crate) async fn db_run_query(
+    request: DbRunQueryRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    run_blocking(app, move |state, app| {
+        run_query(
+            request.query,
+            &request.substitutions,
+            request.allow_destructive,
+            &state,
+            app,
+        )
+    })
+    .await
+}
+
+fn run_query(
+    mut request: DbQueryRequest,
+    substitutions: &HashMap<String, String>,
+    allow_destructive: bool,
+    state: &tauri::State<'_, AppState>,
+    app: &tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    let submitted_sql = request.sql.clone();
+    let mut provider = None;
+    let mut reconnect_notice = None;
+    let started = Instant::now();
+    let cancel = CancelToken::with_timeout(timeout(request.timeout_secs));
+    let _registered = state.cancellations.register(request.session_id, &cancel);
+    let result = with_session_mut(state, request.session_id, |session| {
+        if session.provider == DatabaseProvider::Oracle {
+            let required_variables = sqlplus::missing_variables(&request.sql, substitutions);
+            if !required_variables.is_empty() {
+                return Ok(variables_required_result(required_variables));
+            }
+            request.sql = sqlplus::substitute_variables(&request.sql, substitutions)
+                .map_err(|name| format!("Substitution variable &{name} has no value."))?;
+        }
+        provider = Some(session.provider);
+        let statements =
+            sql_safety::split_confirmed(&request.sql, session.provider, allow_destructive)?;
+        sql_safety::check_production(
+            request.session_id,
+            session,
+            &statements,
+            request.production_confirmation.as_deref(),
+        )?;
+        let pending = ddl_changelog::pending_changes(session, request.session_id, &statements);
+        let (routing, routed) =
+            state
+                .read_replicas
+                .route(request.session_id, session, &statements, |replica| {
+                    ProviderRegistry::run_query(replica, &request, &cancel)
+                });
+        let result = match routed {
+            Some(result) => Ok(result),
+            None => cancel
+                .check()
+                .and_then(|()| ProviderRegistry::run_query(session, &request, &cancel)),
+        };
+        let error = result.as_ref().err().cloned();
+        let _ = ddl_changelog::record_changes(app, session, pending, |_, _| Some(error.clone()));
+        let mut result = match result {
+            Err(_) if cancel.is_cancelled() => Ok(cancelled_result()),
+            result => result,
+        };
+        if let Ok(result) = result.as_mut() {
+            state
+                .read_replicas
+                .set_cursor(request.session_id, &routing, result.cursor_id);
+            if let Some(routing) = routing.describe() {
+                result.message.push(' ');
+                result.message.push_str(&routing);
+            }
+        }
+        reconnect_notice = ProviderRegistry::take_reconnect_notice(session);
+        result
+    });
+    if provider.is_none() && result.is_ok() {
+        // Waiting for substitution values; nothing ran.
+        return result;
+    }
+    let elapsed = started.elapsed();
+    emit_reconnect_notice(app, state, reconnect_notice);
+    state.session_timeline.record(
+        request.session_id,
+        session_timeline::sql_event(
+            DbSessionTimelineEventKind::Statement,
+            &submitted_sql,
+            &result,
+            |result| result.message.clone(),
+            elapsed,
+        ),
+    );
+
+    if let Some(provider) = provider {
+        let _ =
+            workspace::record_query_history(app, submitted_sql.as_str(), provider, result.is_ok());
+    }
+
+    result
+}
+
+/// Returns the next rows of a result `db_run_query` cut off at its row limit, without
+/// running the query again.
+#[tauri::command]
+pub(crate) async fn db_fetch_more_rows(
+    request: DbFetchMoreRowsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    run_blocking(app, move |state, _| {
+        let cancel = CancelToken::default();
+        let _registered = state.cancellations.register(request.session_id, &cancel);
+        if let Some(replica) = state
+            .read_replicas
+            .cursor_connection(request.session_id, request.cursor_id)
+        {
+            let mut replica = lock_session(&replica)?;
+            return ProviderRegistry::fetch_more_rows(&mut replica, &request, &cancel);
+        }
+        with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::fetch_more_rows(session, &request, &cancel)
+        })
+    })
+    .await
+}
+
+/// Releases a result the grid no longer pages through. Returns false if it had already
+/// closed.
+#[tauri::command]
+pub(crate) async fn db_close_cursor(
+    request: DbCloseCursorRequest,
+    app: tauri::AppHandle,
+) -> Result<bool, String> {
+    run_blocking(app, move |state, _| {
+        if let Some(replica) = state
+            .read_replicas
+            .cursor_connection(request.session_id, request.cursor_id)
+        {
+            return ProviderRegistry::close_cursor(&mut *lock_session(&replica)?, &request);
+        }
+        with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::close_cursor(session, &request)
+        })
+    })
+    .await
+}
+
+/// Result of SQL that still needs `&name` values; the editor asks for them and runs it again.
+fn variables_required_result(required_variables: Vec<String>) -> DbQueryResult {
+    let names = required_variables
+        .iter()
+        .map(|name| format!("&{name}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    DbQueryResult {
+        columns: Vec::new(),
+        rows: Vec::new(),
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message: format!("Nothing was run: values are required for {names}."),
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables,
+        ref_cursors: Vec::new(),
+    }
+}
+
+/// Result of a query the user cancelled; the cancellation is not an error.
+fn cancelled_result() -> DbQueryResult {
+    DbQueryResult {
+        columns: Vec::new(),
+        rows: Vec::new(),
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message: "Query cancelled by user.".to_string(),
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     }
 }
 
+fn timeout(timeout_secs: Option<u32>) -> Option<Duration> {
+    timeout_secs.map(|secs| Duration::from_secs(secs.into()))
+}
+
+/// Cancels every long call running on the session: queries, scripts, searches and exports.
+/// A cancelled query returns a "cancelled by user" result. Returns false if nothing was
+/// running.
 #[tauri::command]
-pub(crate) fn db_list_objects(
+pub(crate) fn db_cancel_query(
     request: SessionRequest,
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<DbObjectEntry>, String> {
-    with_session(&state, request.session_id, ProviderRegistry::list_objects)
+) -> Result<bool, String> {
+    Ok(state.cancellations.cancel_session(request.session_id)? > 0)
+}
+
+#[tauri::command]
+pub(crate) fn db_split_sql(request: DbSplitSqlRequest) -> Vec<DbSqlStatement> {
+    sql_splitter::split_statements(request.sql.as_str(), request.provider)
+}
+
+/// Placeholders in the statement, so the editor can ask for values before running it.
+#[tauri::command]
+pub(crate) fn db_list_bind_names(request: DbListBindNamesRequest) -> Vec<String> {
+    sql_safety::bind_names(&request.sql)
+}
+
+/// Runs a SELECT and sends its rows as `clarity://query-rows` events in batches, ending with
+/// a summary event that is also the command's result. Meant for results too large for one
+/// `db_run_query` payload.
+#[tauri::command]
+pub(crate) async fn db_stream_query(
+    request: DbStreamQueryRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryStreamSummary, String> {
+    run_blocking(app, move |state, app| {
+        let started = Instant::now();
+        let cancel = CancelToken::with_timeout(timeout(request.timeout_secs));
+        let _registered = state.cancellations.register(request.session_id, &cancel);
+        let result = with_session(&state, request.session_id, |session| {
+            query_stream::stream_query(session, &request, &cancel, &mut |event| {
+                let _ = app.emit(EVENT_QUERY_ROWS, event);
+            })
+        });
+        if let Ok(DbQueryStreamSummary {
+            error: Some(error), ..
+        }) = &result
+        {
+            state.errors.record(request.session_id, error);
+        }
+        state.session_timeline.record(
+            request.session_id,
+            session_timeline::sql_event(
+                DbSessionTimelineEventKind::Statement,
+                &request.sql,
+                &result,
+                |summary| summary.message.clone(),
+                started.elapsed(),
+            ),
+        );
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_run_script(
+    request: DbRunScriptRequest,
+    app: tauri::AppHandle,
+) -> Result<DbScriptResult, String> {
+    run_blocking(app, move |state, app| run_script(request, &state, app)).await
+}
+
+fn run_script(
+    request: DbRunScriptRequest,
+    state: &tauri::State<'_, AppState>,
+    app: &tauri::AppHandle,
+) -> Result<DbScriptResult, String> {
+    let started = Instant::now();
+    let cancel = CancelToken::with_timeout(timeout(request.timeout_secs));
+    let _registered = state.cancellations.register(request.session_id, &cancel);
+    let outcome = with_session_mut(state, request.session_id, |session| {
+        let result = sql_script::run_script(app, session, &request, &cancel)?;
+        let notice = ProviderRegistry::take_reconnect_notice(session);
+        Ok((session.provider, result, notice))
+    });
+    state.session_timeline.record(
+        request.session_id,
+        session_timeline::sql_event(
+            DbSessionTimelineEventKind::Script,
+            &request.sql,
+            &outcome,
+            |(_, result, _)| {
+                format!(
+                    "Ran {} statement(s), {} failed",
+                    result.statements.len(),
+                    result.failed_statements
+                )
+            },
+            started.elapsed(),
+        ),
+    );
+    let (provider, result, reconnect_notice) = outcome?;
+    emit_reconnect_notice(app, state, reconnect_notice);
+
+    let _ = workspace::record_query_history(
+        app,
+        request.sql.as_str(),
+        provider,
+        result.failed_statements == 0,
+    );
+    Ok(result)
+}
+
+#[tauri::command]
+pub(crate) fn db_set_keep_alive_interval(
+    request: DbKeepAliveRequest,
+    state: tauri::State<'_, AppState>,
+) -> u64 {
+    let interval = u64::from(request.interval_secs).min(keep_alive::MAX_KEEP_ALIVE_SECS);
+    state
+        .keep_alive_interval_secs
+        .store(interval, Ordering::Relaxed);
+    interval
+}
+
+#[tauri::command]
+pub(crate) fn db_set_idle_timeout(
+    request: DbIdleTimeoutRequest,
+    state: tauri::State<'_, AppState>,
+) -> u32 {
+    let minutes = request.minutes.min(idle_timeout::MAX_IDLE_TIMEOUT_MINUTES);
+    state
+        .idle_timeout_secs
+        .store(u64::from(minutes) * 60, Ordering::Relaxed);
+    minutes
+}
+
+#[tauri::command]
+pub(crate) async fn db_session_info(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSessionInfo, String> {
+    run_blocking(app, move |state, _| {
+        let (provider, idle_secs, connected_at, server) = {
+            let session = state.sessions.get(request.session_id)?;
+            let session = lock_session(&session)?;
+            (
+                session.provider,
+                session.idle_for().as_secs(),
+                session.connected_at(),
+                ProviderRegistry::server_info(&session),
+            )
+        };
+        let usage = state.session_pools.usage(request.session_id, provider);
+        Ok(DbSessionInfo {
+            session_id: request.session_id,
+            provider,
+            pool_size: usage.size,
+            open_connections: usage.open,
+            busy_pooled_connections: usage.in_use,
+            idle_secs,
+            connected_at,
+            server,
+        })
+    })
+    .await
+}
+
+/// Opens a new connection with the parameters the session was opened with and swaps it in
+/// under the same session id, so open tabs stay attached. Work left uncommitted on the old
+/// connection is lost.
+#[tauri::command]
+pub(crate) async fn db_reconnect(
+    request: DbReconnectRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSessionReconnected, String> {
+    run_blocking(app, move |state, _| {
+        let session_id = request.session_id;
+        let result = reconnect_session(&state, request);
+        state.session_timeline.record(
+            session_id,
+            session_timeline::outcome_event(
+                DbSessionTimelineEventKind::Reconnect,
+                &result,
+                |result| result.message.clone(),
+            ),
+        );
+        result
+    })
+    .await
+}
+
+fn reconnect_session(
+    state: &tauri::State<'_, AppState>,
+    request: DbReconnectRequest,
+) -> Result<DbSessionReconnected, String> {
+    let mut connect_request = {
+        let session = state.sessions.get(request.session_id)?;
+        let session = lock_session(&session)?;
+        session
+            .connect_request()
+            .cloned()
+            .ok_or_else(|| "This session cannot be reconnected".to_string())?
+    };
+    if let Some(profile_id) = request
+        .profile_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|profile_id| !profile_id.is_empty())
+    {
+        if let (Some(secret), Some(stored)) = (
+            connect_request.connection.secret_mut(),
+            profiles::read_profile_secret(profile_id)?,
+        ) {
+            *secret = stored;
+        }
+        if let Some(tunnel) = connect_request.ssh_tunnel.as_mut() {
+            if let Some(stored) = profiles::read_profile_ssh_secret(profile_id)? {
+                tunnel.password = Some(stored);
+            }
+        }
+    }
+    if let (Some(secret), Some(password)) =
+        (connect_request.connection.secret_mut(), request.password)
+    {
+        *secret = password;
+    }
+
+    let (mut replacement, display_name, _) =
+        ProviderRegistry::connect(&connect_request).map_err(DbConnectError::into_message)?;
+    let session = state.sessions.get(request.session_id)?;
+    let previous = {
+        let mut session = lock_session(&session)?;
+        ProviderRegistry::restore_session_settings(&session, &mut replacement)?;
+        std::mem::replace(&mut *session, replacement)
+    };
+    // Closes the old connection and its SSH tunnel.
+    drop(previous);
+    state.session_pools.remove(request.session_id);
+    state
+        .session_pools
+        .register(request.session_id, &connect_request);
+    state.read_replicas.remove(request.session_id);
+    state
+        .read_replicas
+        .register(request.session_id, &connect_request);
+
+    Ok(DbSessionReconnected {
+        session_id: request.session_id,
+        reconnected: true,
+        replayed: false,
+        needs_password: false,
+        message: format!("Reconnected to {display_name}."),
+    })
+}
+
+#[tauri::command]
+pub(crate) fn db_get_session_timeline(
+    request: SessionRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbSessionTimeline, String> {
+    state.session_timeline.get(request.session_id)
+}
+
+#[tauri::command]
+pub(crate) fn db_export_session_timeline(
+    request: DbExportSessionTimelineRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbSessionTimelineExport, String> {
+    state.session_timeline.export(&request)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_standby_connections(
+    state: tauri::State<'_, AppState>,
+) -> Vec<DbStandbyConnection> {
+    state.standby.list()
+}
+
+#[tauri::command]
+pub(crate) async fn db_reconnect_session(
+    request: DbReconnectSessionRequest,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    run_blocking(app, move |state, _| {
+        let result = with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::reconnect(session, request.password)
+        });
+        state.session_timeline.record(
+            request.session_id,
+            session_timeline::outcome_event(DbSessionTimelineEventKind::Reconnect, &result, |_| {
+                "Reconnected with a new password".to_string()
+            }),
+        );
+        result
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_download_lob(
+    request: DbDownloadLobRequest,
+    app: tauri::AppHandle,
+) -> Result<DbLobDownloadResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            ProviderRegistry::download_lob(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_run_consistent_queries(
+    request: DbConsistentQueriesRequest,
+    app: tauri::AppHandle,
+) -> Result<DbConsistentQueriesResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::run_consistent_queries(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_start_sql_file_import(
+    request: DbSqlFileImportRequest,
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<DbJobStatus, String> {
+    sql_script::start_sql_file_import_job(request, state.sessions.clone(), state.jobs.clone(), app)
+}
+
+#[tauri::command]
+pub(crate) async fn db_run_query_filtered(
+    request: crate::types::DbFilteredQueryRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::run_filtered_query(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_session_nls(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSessionNls, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, ProviderRegistry::session_nls)
+    })
+    .await
+}
+
+/// The read replica connection is closed so the next routed query picks up the settings.
+#[tauri::command]
+pub(crate) async fn db_set_session_nls(
+    request: DbSetSessionNlsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSessionNls, String> {
+    run_blocking(app, move |state, _| {
+        let nls = with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::set_session_nls(session, &request)
+        })?;
+        state.session_pools.reset(request.session_id);
+        state.read_replicas.reset(request.session_id);
+        Ok(nls)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_set_resource_limits(
+    request: DbSetResourceLimitsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbResourceLimits, String> {
+    run_blocking(app, move |state, _| {
+        let limits = with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::set_resource_limits(session, &request)
+        })?;
+        state.session_pools.reset(request.session_id);
+        Ok(limits)
+    })
+    .await
+}
+
+/// Pooled connections stay in the old container, so they are dropped and reopened in the
+/// new one. A read replica reconnects into the new container on its next query.
+#[tauri::command]
+pub(crate) async fn db_set_container(
+    request: DbSetContainerRequest,
+    app: tauri::AppHandle,
+) -> Result<DbServerInfo, String> {
+    run_blocking(app, move |state, _| {
+        let info = with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::set_container(session, &request)
+        })?;
+        state.session_pools.reset(request.session_id);
+        state.read_replicas.reset(request.session_id);
+        Ok(info)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_list_containers(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbContainer>, String> {
+    run_blocking(app, move |state, _| {
+        with_session(
+            &state,
+            request.session_id,
+            ProviderRegistry::list_containers,
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_set_timestamp_tz_mode(
+    request: DbTimestampTzModeRequest,
+    app: tauri::AppHandle,
+) -> Result<TimestampTzMode, String> {
+    run_blocking(app, move |state, _| {
+        let mode = with_session_mut(&state, request.session_id, |session| {
+            ProviderRegistry::set_timestamp_tz_mode(session, request.mode)
+        })?;
+        state.session_pools.reset(request.session_id);
+        Ok(mode)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_transaction_state(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTransactionState, String> {
+    run_blocking(app, move |state, _| {
+        let active = with_session(
+            &state,
+            request.session_id,
+            ProviderRegistry::transaction_active,
+        )?;
+        Ok(DbTransactionState { active })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_begin_transaction(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTransactionState, String> {
+    run_blocking(app, move |state, _| {
+        let active = with_session_mut(
+            &state,
+            request.session_id,
+            ProviderRegistry::begin_transaction,
+        )?;
+        Ok(DbTransactionState { active })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_commit_transaction(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTransactionState, String> {
+    run_blocking(app, move |state, _| {
+        let active = with_session_mut(
+            &state,
+            request.session_id,
+            ProviderRegistry::commit_transaction,
+        )?;
+        Ok(DbTransactionState { active })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_rollback_transaction(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTransactionState, String> {
+    run_blocking(app, move |state, _| {
+        let active = with_session_mut(
+            &state,
+            request.session_id,
+            ProviderRegistry::rollback_transaction,
+        )?;
+        Ok(DbTransactionState { active })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_search_schema_text(
+    request: DbSchemaSearchRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbSchemaSearchResult>, String> {
+    run_blocking(app, move |state, app| {
+        let cancel = CancelToken::with_timeout(timeout(request.timeout_secs));
+        let _registered = state.cancellations.register(request.session_id, &cancel);
+        let mut results = with_pooled_session(&state, request.session_id, |session| {
+            ProviderRegistry::search_schema_text(session, &request, &cancel)
+        })?;
+        if let Some(profile_id) = request.profile_id.as_deref() {
+            workspace::tag_schema_search_results(
+                app,
+                profile_id,
+                &request.search_term,
+                &mut results,
+            )?;
+        }
+        Ok(results)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_list_aq_queues(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbAqQueueEntry>, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, ProviderRegistry::list_aq_queues)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_browse_aq_messages(
+    request: DbAqBrowseRequest,
+    app: tauri::AppHandle,
+) -> Result<DbAqBrowseResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            ProviderRegistry::browse_aq_messages(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_recent_errors(
+    request: DbRecentErrorsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbRecentErrorsResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_recent_errors(session, &request)
+        })
+    })
+    .await
+}
+
+/// Every distinct provider error the session has hit, with how often it occurred.
+#[tauri::command]
+pub(crate) fn db_get_error_summary(
+    request: SessionRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbErrorSummary, String> {
+    state.sessions.get(request.session_id)?;
+    state.errors.summary(request.session_id)
+}
+
+/// State files moved aside at startup because they no longer parsed; empty normally.
+#[tauri::command]
+pub(crate) fn db_get_state_recoveries(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<DbStateFileRecovery>, String> {
+    state.state_recoveries.list()
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_row_locks(
+    request: DbRowLocksRequest,
+    app: tauri::AppHandle,
+) -> Result<DbRowLocksResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_row_locks(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_object_access_stats(
+    request: DbObjectAccessStatsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbObjectAccessStatsResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_access_stats(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_get_encoding_info(
+    request: SessionRequest,
+    app: tauri::AppHandle,
+) -> Result<DbEncodingInfo, String> {
+    run_blocking(app, move |state, _| {
+        with_session(
+            &state,
+            request.session_id,
+            ProviderRegistry::get_encoding_info,
+        )
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_save_editor_buffers(
+    request: DbSaveEditorBuffersRequest,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    workspace::save_editor_buffers(&app, request.buffers)
+}
+
+#[tauri::command]
+pub(crate) async fn db_create_scratch_table(
+    request: DbCreateScratchTableRequest,
+    app: tauri::AppHandle,
+) -> Result<DbScratchTable, String> {
+    run_blocking(app, move |state, _| {
+        validate_create_scratch_table_request(&request)?;
+        with_session_mut(&state, request.session_id, |session| {
+            scratch::create_scratch_table(session, &state.scratch_tables, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_list_scratch_tables(
+    request: SessionRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<DbScratchTable>, String> {
+    state.scratch_tables.list(request.session_id)
+}
+
+#[tauri::command]
+pub(crate) async fn db_drop_scratch_table(
+    request: DbScratchTableRef,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    run_blocking(app, move |state, _| {
+        with_session_mut(&state, request.session_id, |session| {
+            scratch::drop_scratch_table(session, &state.scratch_tables, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_detect_sensitive_columns(
+    request: DbDetectSensitiveColumnsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSensitiveColumnsReport, String> {
+    run_blocking(app, move |state, _| {
+        with_session_mut(&state, request.session_id, |session| {
+            sensitive_data::detect_sensitive_columns(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_list_masking_rules(app: tauri::AppHandle) -> Result<Vec<DbMaskingRule>, String> {
+    masking::list_masking_rules(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_save_masking_rules(
+    request: DbSaveMaskingRulesRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbMaskingRule>, String> {
+    masking::save_masking_rules(&app, request.rules)
+}
+
+#[tauri::command]
+pub(crate) async fn db_preview_masked_query(
+    request: DbMaskedQueryRequest,
+    app: tauri::AppHandle,
+) -> Result<DbMaskedQueryResult, String> {
+    run_blocking(app, move |state, app| {
+        let rules = masking::list_masking_rules(app)?;
+        with_session_mut(&state, request.session_id, |session| {
+            masking::preview_masked_query(session, &request, &rules)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_list_query_history(
+    request: DbQueryHistoryRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbQueryHistoryEntry>, String> {
+    workspace::list_query_history(&app, request.limit)
+}
+
+/// Names from the query history, most used first, for ranking editor completions.
+#[tauri::command]
+pub(crate) fn db_get_identifier_usage(
+    request: Option<DbIdentifierUsageRequest>,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbIdentifierUsage>, String> {
+    workspace::identifier_usage(&app, &request.unwrap_or_default())
+}
+
+#[tauri::command]
+pub(crate) fn db_clear_query_history(app: tauri::AppHandle) -> Result<(), String> {
+    workspace::clear_query_history(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_snippets(app: tauri::AppHandle) -> Result<Vec<DbSnippet>, String> {
+    Ok(workspace::read_workspace(&app)?.snippets)
+}
+
+#[tauri::command]
+pub(crate) fn db_save_snippet(
+    request: DbSaveSnippetRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSnippet, String> {
+    workspace::save_snippet(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_delete_snippet(
+    request: DbSnippetRef,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    workspace::delete_snippet(&app, request.snippet_id.as_str())
+}
+
+#[tauri::command]
+pub(crate) fn db_save_snippet_parameter_set(
+    request: DbSaveSnippetParameterSetRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSnippetParameterSet, String> {
+    workspace::save_snippet_parameter_set(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_delete_snippet_parameter_set(
+    request: DbSnippetParameterSetRef,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    workspace::delete_snippet_parameter_set(
+        &app,
+        request.snippet_id.as_str(),
+        request.parameter_set_id.as_str(),
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn db_run_snippet(
+    request: DbRunSnippetRequest,
+    app: tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    run_blocking(app, move |state, app| run_snippet(request, &state, app)).await
+}
+
+fn run_snippet(
+    request: DbRunSnippetRequest,
+    state: &tauri::State<'_, AppState>,
+    app: &tauri::AppHandle,
+) -> Result<DbQueryResult, String> {
+    let (sql, binds) = workspace::snippet_query(
+        app,
+        request.snippet_id.as_str(),
+        request.parameter_set_id.as_deref(),
+    )?;
+    run_query(
+        DbQueryRequest {
+            session_id: request.session_id,
+            sql,
+            row_limit: request.row_limit,
+            binds,
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: request.production_confirmation,
+            timeout_secs: None,
+        },
+        &HashMap::new(),
+        request.allow_destructive,
+        state,
+        app,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn db_save_result_snapshot(
+    request: DbSaveResultSnapshotRequest,
+    app: tauri::AppHandle,
+) -> Result<DbResultSnapshotSummary, String> {
+    snapshots::save_result_snapshot(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_result_snapshots(
+    app: tauri::AppHandle,
+) -> Result<Vec<DbResultSnapshotSummary>, String> {
+    snapshots::list_result_snapshots(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_get_result_snapshot(
+    request: DbResultSnapshotRef,
+    app: tauri::AppHandle,
+) -> Result<DbResultSnapshot, String> {
+    snapshots::get_result_snapshot(&app, &request.snapshot_id)
+}
+
+#[tauri::command]
+pub(crate) fn db_delete_result_snapshot(
+    request: DbResultSnapshotRef,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    snapshots::delete_result_snapshot(&app, &request.snapshot_id)
+}
+
+#[tauri::command]
+pub(crate) fn db_import_oracle_wallet(
+    request: DbImportOracleWalletRequest,
+    app: tauri::AppHandle,
+) -> Result<DbOracleWallet, String> {
+    oracle_wallets::import_wallet(&app, &request)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_oracle_wallets(app: tauri::AppHandle) -> Result<Vec<DbOracleWallet>, String> {
+    oracle_wallets::list_wallets(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_oracle_clients(app: tauri::AppHandle) -> Result<Vec<DbOracleClient>, String> {
+    oracle_clients::list_clients(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_save_oracle_client(
+    request: DbSaveOracleClientRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbOracleClient>, String> {
+    oracle_clients::save_client(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_delete_oracle_client(
+    request: DbOracleClientPathRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbOracleClient>, String> {
+    oracle_clients::delete_client(&app, request.path.as_str())
+}
+
+#[tauri::command]
+pub(crate) fn db_list_tns_aliases(
+    request: DbListTnsAliasesRequest,
+) -> Result<DbTnsAliasList, String> {
+    tnsnames::list_aliases(request.tns_admin.as_deref())
+}
+
+#[tauri::command]
+pub(crate) fn db_delete_oracle_wallet(
+    request: DbOracleWalletNameRequest,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    oracle_wallets::delete_wallet(&app, request.name.as_str())
+}
+
+#[tauri::command]
+pub(crate) fn db_list_ddl_backups(
+    request: Option<DbListDdlBackupsRequest>,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbDdlBackup>, String> {
+    ddl_backups::list_ddl_backups(&app, &request.unwrap_or_default())
+}
+
+/// DDL applied through Clarity, oldest first: object editor saves, and DDL run as a query,
+/// script or `.sql` file.
+#[tauri::command]
+pub(crate) fn db_get_ddl_changelog(
+    request: Option<DbGetDdlChangelogRequest>,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbDdlChange>, String> {
+    ddl_changelog::get_changelog(&app, &request.unwrap_or_default())
+}
+
+#[tauri::command]
+pub(crate) fn db_export_ddl_changelog(
+    request: DbExportDdlChangelogRequest,
+    app: tauri::AppHandle,
+) -> Result<DbDdlChangelogExport, String> {
+    ddl_changelog::export_changelog(&app, &request)
+}
+
+#[tauri::command]
+pub(crate) fn db_get_ddl_backup_retention(
+    app: tauri::AppHandle,
+) -> Result<DbDdlBackupRetention, String> {
+    ddl_backups::get_retention(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_set_ddl_backup_retention(
+    request: DbDdlBackupRetention,
+    app: tauri::AppHandle,
+) -> Result<DbDdlBackupRetention, String> {
+    ddl_backups::set_retention(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_tag_object(
+    request: DbObjectTagRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTaggedObject, String> {
+    workspace::tag_object(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_untag_object(
+    request: DbObjectTagRequest,
+    app: tauri::AppHandle,
+) -> Result<DbTaggedObject, String> {
+    workspace::untag_object(&app, request)
 }
 
 #[tauri::command]
-pub(crate) fn db_list_object_columns(
-    request: SessionRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<Vec<DbObjectColumnEntry>, String> {
-    with_session(
-        &state,
-        request.session_id,
-        ProviderRegistry::list_object_columns,
-    )
+pub(crate) fn db_list_tagged_objects(
+    request: DbListTaggedObjectsRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbTaggedObject>, String> {
+    workspace::list_tagged_objects(&app, &request.profile_id, request.tag.as_deref())
 }
 
 #[tauri::command]
-pub(crate) fn db_get_object_ddl(
-    request: DbObjectRef,
-    state: tauri::State<'_, AppState>,
-) -> Result<String, String> {
-    with_session(&state, request.session_id, |session| {
-        ProviderRegistry::get_object_ddl(session, &request)
-    })
+pub(crate) fn db_list_schema_snapshot_schedules(
+    app: tauri::AppHandle,
+) -> Result<Vec<DbSchemaSnapshotSchedule>, String> {
+    schema_snapshots::list_schedules(&app)
 }
 
 #[tauri::command]
-pub(crate) fn db_update_object_ddl(
-    request: DbObjectDdlUpdateRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbQueryResult, String> {
-    with_session_mut(&state, request.session_id, |session| {
-        ProviderRegistry::update_object_ddl(session, &request)
-    })
+pub(crate) fn db_save_schema_snapshot_schedule(
+    request: DbSaveSchemaSnapshotScheduleRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSchemaSnapshotSchedule, String> {
+    schema_snapshots::save_schedule(&app, request)
 }
 
 #[tauri::command]
-pub(crate) fn db_run_query(
-    request: DbQueryRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbQueryResult, String> {
-    with_session_mut(&state, request.session_id, |session| {
-        ProviderRegistry::run_query(session, &request)
-    })
+pub(crate) fn db_delete_schema_snapshot_schedule(
+    request: DbSchemaSnapshotScheduleRef,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    schema_snapshots::delete_schedule(&app, &request.id)
 }
 
 #[tauri::command]
-pub(crate) fn db_run_query_filtered(
-    request: crate::types::DbFilteredQueryRequest,
+pub(crate) fn db_run_schema_snapshot(
+    request: DbSchemaSnapshotScheduleRef,
     state: tauri::State<'_, AppState>,
-) -> Result<DbQueryResult, String> {
-    with_session_mut(&state, request.session_id, |session| {
-        ProviderRegistry::run_filtered_query(session, &request)
-    })
+    app: tauri::AppHandle,
+) -> Result<DbJobStatus, String> {
+    schema_snapshots::run_schedule_now(&app, state.jobs.clone(), &request.id)
 }
 
 #[tauri::command]
-pub(crate) fn db_get_transaction_state(
-    request: SessionRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbTransactionState, String> {
-    let active = with_session(
-        &state,
-        request.session_id,
-        ProviderRegistry::transaction_active,
-    )?;
-    Ok(DbTransactionState { active })
+pub(crate) fn db_search_workspace(
+    request: DbWorkspaceSearchRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbWorkspaceSearchResult>, String> {
+    workspace::search_workspace(&app, &request)
 }
 
 #[tauri::command]
-pub(crate) fn db_begin_transaction(
-    request: SessionRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbTransactionState, String> {
-    let active = with_session_mut(
-        &state,
-        request.session_id,
-        ProviderRegistry::begin_transaction,
-    )?;
-    Ok(DbTransactionState { active })
+pub(crate) fn db_diff_cell_values(
+    request: DbDiffCellValuesRequest,
+) -> Result<DbTextDiffResult, String> {
+    text_diff::diff_text(&request.left, &request.right, request.context_lines)
 }
 
 #[tauri::command]
-pub(crate) fn db_commit_transaction(
-    request: SessionRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbTransactionState, String> {
-    let active = with_session_mut(
-        &state,
-        request.session_id,
-        ProviderRegistry::commit_transaction,
-    )?;
-    Ok(DbTransactionState { active })
+pub(crate) async fn db_diff_object_against_file(
+    request: DbObjectFileDiffRequest,
+    app: tauri::AppHandle,
+) -> Result<DbObjectFileDiffResult, String> {
+    run_blocking(app, move |state, _| {
+        let object = DbObjectRef {
+            session_id: request.session_id,
+            schema: request.schema.clone(),
+            object_type: request.object_type.clone(),
+            object_name: request.object_name.clone(),
+        };
+        let ddl = with_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_ddl(session, &object)
+        })?;
+        let ddl_label = format!(
+            "{}.{} ({})",
+            request.schema, request.object_name, request.object_type
+        );
+        text_diff::diff_ddl_against_file(
+            ddl.as_str(),
+            ddl_label.as_str(),
+            Path::new(request.file_path.trim()),
+            request.context_lines,
+        )
+    })
+    .await
 }
 
 #[tauri::command]
-pub(crate) fn db_rollback_transaction(
-    request: SessionRequest,
-    state: tauri::State<'_, AppState>,
-) -> Result<DbTransactionState, String> {
-    let active = with_session_mut(
-        &state,
-        request.session_id,
-        ProviderRegistry::rollback_transaction,
-    )?;
-    Ok(DbTransactionState { active })
+pub(crate) async fn db_edit_object_externally(
+    request: DbEditObjectExternallyRequest,
+    app: tauri::AppHandle,
+) -> Result<DbExternalEdit, String> {
+    run_blocking(app, move |state, app| {
+        let object = DbObjectRef {
+            session_id: request.session_id,
+            schema: request.schema.clone(),
+            object_type: request.object_type.clone(),
+            object_name: request.object_name.clone(),
+        };
+        let ddl = with_session(&state, request.session_id, |session| {
+            ProviderRegistry::get_object_ddl(session, &object)
+        })?;
+        external_edit::edit_object_externally(
+            app.clone(),
+            state.external_edits.clone(),
+            &request,
+            ddl.as_str(),
+        )
+    })
+    .await
 }
 
 #[tauri::command]
-pub(crate) fn db_search_schema_text(
-    request: DbSchemaSearchRequest,
+pub(crate) fn db_stop_external_edit(
+    request: DbStopExternalEditRequest,
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<DbSchemaSearchResult>, String> {
-    with_session(&state, request.session_id, |session| {
-        ProviderRegistry::search_schema_text(session, &request)
-    })
+) -> Result<(), String> {
+    state.external_edits.stop(request.edit_id)
 }
 
 #[tauri::command]
@@ -204,10 +1688,103 @@ pub(crate) fn db_clear_ai_api_key() -> Result<(), String> {
 
 #[tauri::command]
 pub(crate) async fn db_ai_suggest_query(
-    request: DbAiSuggestQueryRequest,
+    mut request: DbAiSuggestQueryRequest,
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
 ) -> Result<DbAiSuggestQueryResult, String> {
     validate_ai_suggest_request(&request)?;
-    ai::suggest_query(request).await
+    if request.provider.is_none() {
+        if let Some(session_id) = request.session_id {
+            request.provider =
+                with_session(&state, session_id, |session| Ok(session.provider)).ok();
+        }
+    }
+    if request.schema_context.is_empty() {
+        if let Some(context) = request.session_id.and_then(|session_id| {
+            state
+                .ai_schema_context
+                .context_for(session_id, request.current_sql.as_str())
+        }) {
+            request.schema_context = context;
+        }
+    }
+    let settings = ai::read_prompt_settings(&app)?;
+    let session_id = request.session_id;
+    let identifier_check = request.identifier_check;
+    let current_sql = request.current_sql.clone();
+    let mut result = ai::suggest_query(request, settings).await?;
+    state.ai_schema_context.check_suggestion(
+        session_id,
+        identifier_check,
+        current_sql.as_str(),
+        &mut result,
+    );
+    Ok(result)
+}
+
+#[tauri::command]
+pub(crate) async fn db_ai_review_ddl(
+    request: DbAiReviewDdlRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbAiDdlReviewResult, String> {
+    validate_ai_review_ddl_request(&request)?;
+    let object = DbObjectRef {
+        session_id: request.session_id,
+        schema: request.schema.clone(),
+        object_type: request.object_type.clone(),
+        object_name: request.object_name.clone(),
+    };
+    // A missing definition means the DDL creates the object; missing dependents means the
+    // provider cannot list them. Both are reported to the model rather than failing.
+    let (provider, current_ddl, dependents) =
+        with_session(&state, request.session_id, |session| {
+            Ok((
+                session.provider,
+                ProviderRegistry::get_object_ddl(session, &object).ok(),
+                ProviderRegistry::list_object_dependents(session, &object).ok(),
+            ))
+        })?;
+    ai::review_ddl(&request, provider, current_ddl.as_deref(), dependents).await
+}
+
+#[tauri::command]
+pub(crate) fn db_get_ai_prompt_settings(
+    app: tauri::AppHandle,
+) -> Result<DbAiPromptSettings, String> {
+    ai::read_prompt_settings(&app)
+}
+
+#[tauri::command]
+pub(crate) fn db_save_ai_prompt_settings(
+    settings: DbAiPromptSettings,
+    app: tauri::AppHandle,
+) -> Result<DbAiPromptSettings, String> {
+    ai::save_prompt_settings(&app, settings)
+}
+
+#[tauri::command]
+pub(crate) fn db_get_default_ai_prompt_template() -> String {
+    ai::default_system_prompt_template().to_string()
+}
+
+#[tauri::command]
+pub(crate) fn db_refresh_ai_schema_context(
+    request: SessionRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbAiSchemaContextStatus, String> {
+    ai::prefetch_schema_context(
+        &state.sessions,
+        &state.ai_schema_context,
+        request.session_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn db_get_ai_schema_context_status(
+    request: SessionRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<DbAiSchemaContextStatus>, String> {
+    Ok(state.ai_schema_context.status(request.session_id))
 }
 
 #[tauri::command]
@@ -241,6 +1818,18 @@ pub(crate) fn db_save_connection_profile(
         id: id.clone(),
         name: request.name.trim().to_string(),
         connection: normalize_profile_connection(&request.connection),
+        ssh_tunnel: request.ssh_tunnel.as_ref().map(normalize_ssh_tunnel),
+        read_only: request.read_only,
+        environment: normalize_environment(request.environment.as_deref()),
+        startup_sql: request
+            .startup_sql
+            .as_deref()
+            .map(str::trim)
+            .filter(|sql| !sql.is_empty())
+            .map(str::to_string),
+        warm_standby: request.warm_standby,
+        drop_allowlist: normalize_drop_allowlist(&request.drop_allowlist),
+        reader_endpoint: normalize_reader_endpoint(request.reader_endpoint.as_ref()),
     };
 
     if let Some(position) = profiles_list.iter().position(|profile| profile.id == id) {
@@ -261,6 +1850,18 @@ pub(crate) fn db_save_connection_profile(
         profiles::clear_profile_secret(id.as_str())?;
     }
 
+    match request.ssh_password.as_deref() {
+        Some(ssh_password)
+            if request.save_password
+                && updated.ssh_tunnel.is_some()
+                && !ssh_password.is_empty() =>
+        {
+            profiles::write_profile_ssh_secret(id.as_str(), ssh_password)?
+        }
+        _ => profiles::clear_profile_ssh_secret(id.as_str())?,
+    }
+    standby::refresh_in_background(&app);
+
     Ok(profiles::to_connection_profile(updated))
 }
 
@@ -283,10 +1884,30 @@ pub(crate) fn db_delete_connection_profile(
     }
 
     profiles::write_profiles(&app, &profiles_list)?;
+    standby::refresh_in_background(&app);
     profiles::clear_profile_secret(profile_id)?;
+    profiles::clear_profile_ssh_secret(profile_id)?;
+    workspace::remove_profile_objects(&app, profile_id)?;
+    schema_snapshots::remove_profile_schedules(&app, profile_id)?;
     Ok(())
 }
 
+#[tauri::command]
+pub(crate) fn db_pin_object(
+    request: DbPinObjectRequest,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbPinnedObject>, String> {
+    workspace::pin_object(&app, request)
+}
+
+#[tauri::command]
+pub(crate) fn db_list_pinned_objects(
+    request: ConnectionProfileRef,
+    app: tauri::AppHandle,
+) -> Result<Vec<DbPinnedObject>, String> {
+    workspace::list_pinned_objects(&app, request.profile_id.as_str())
+}
+
 #[tauri::command]
 pub(crate) fn db_get_connection_profile_secret(
     request: ConnectionProfileRef,
@@ -299,6 +1920,65 @@ pub(crate) fn db_get_connection_profile_secret(
     profiles::read_profile_secret(profile_id)
 }
 
+#[tauri::command]
+pub(crate) fn db_get_connection_profile_ssh_secret(
+    request: ConnectionProfileRef,
+) -> Result<Option<String>, String> {
+    let profile_id = request.profile_id.trim();
+    if profile_id.is_empty() {
+        return Err("Profile id is required".to_string());
+    }
+
+    profiles::read_profile_ssh_secret(profile_id)
+}
+
+#[tauri::command]
+pub(crate) async fn db_run_diagnostics(
+    request: Option<DbRunDiagnosticsRequest>,
+    app: tauri::AppHandle,
+) -> Result<DbDiagnosticsReport, String> {
+    diagnostics::run_diagnostics(request.unwrap_or_default(), app).await
+}
+
+/// Packages diagnostics, redacted settings, recent session timelines and errors, and the
+/// last failed command into a zip to attach to a bug report.
+#[tauri::command]
+pub(crate) async fn db_create_support_bundle(
+    request: DbCreateSupportBundleRequest,
+    app: tauri::AppHandle,
+) -> Result<DbSupportBundleResult, String> {
+    run_blocking(app, move |state, app| {
+        support_bundle::create_support_bundle(&request, app, &state.session_timeline, &state.errors)
+    })
+    .await
+}
+
+/// Writes profiles, snippets, pins, tags and settings, optionally with secrets, to one
+/// passphrase-encrypted archive for moving to another machine.
+#[tauri::command]
+pub(crate) async fn db_export_workspace(
+    request: DbExportWorkspaceRequest,
+    app: tauri::AppHandle,
+) -> Result<DbWorkspaceArchiveSummary, String> {
+    run_blocking(app, move |_, app| {
+        workspace_archive::export_workspace(app, &request)
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_import_workspace(
+    request: DbImportWorkspaceRequest,
+    app: tauri::AppHandle,
+) -> Result<DbWorkspaceArchiveSummary, String> {
+    run_blocking(app, move |state, app| {
+        let summary = workspace_archive::import_workspace(app, &request, &state.next_profile_id)?;
+        standby::refresh_in_background(app);
+        Ok(summary)
+    })
+    .await
+}
+
 #[tauri::command]
 pub(crate) fn db_pick_directory() -> Result<Option<String>, String> {
     files::pick_directory()
@@ -324,22 +2004,245 @@ pub(crate) async fn db_export_schema(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
 ) -> Result<DbSchemaExportResult, String> {
-    files::export_schema(request, state.sessions.clone(), app).await
+    let session_id = request.session_id;
+    let cancel = CancelToken::default();
+    let _registered = state.cancellations.register(session_id, &cancel);
+    let result = files::export_schema(request, state.sessions.clone(), app, cancel.clone()).await;
+    state.session_timeline.record(
+        session_id,
+        session_timeline::outcome_event(DbSessionTimelineEventKind::Export, &result, |result| {
+            result.message.clone()
+        }),
+    );
+    result
+}
+
+#[tauri::command]
+pub(crate) async fn db_generate_code(
+    request: DbGenerateCodeRequest,
+    app: tauri::AppHandle,
+) -> Result<DbGenerateCodeResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            codegen::generate_code(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_generate_grants(
+    request: DbGenerateGrantsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbGrantScript, String> {
+    run_blocking(app, move |state, _| {
+        with_session(&state, request.session_id, |session| {
+            grants::generate_grants(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn db_export_query_data(
+    request: DbExportQueryDataRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbDataExportResult, String> {
+    let session_id = request.session_id;
+    let sql = request.sql.clone();
+    let started = Instant::now();
+    let cancel = CancelToken::default();
+    let _registered = state.cancellations.register(session_id, &cancel);
+    let result =
+        data_export::export_query_data(request, state.sessions.clone(), cancel.clone()).await;
+    state.session_timeline.record(
+        session_id,
+        session_timeline::sql_event(
+            DbSessionTimelineEventKind::Export,
+            &sql,
+            &result,
+            |result| result.message.clone(),
+            started.elapsed(),
+        ),
+    );
+    result
+}
+
+#[tauri::command]
+pub(crate) async fn db_export_result_report(
+    request: DbExportResultReportRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<DbResultReport, String> {
+    let session_id = request.session_id;
+    let sql = request.sql.clone();
+    let started = Instant::now();
+    let result = report::export_result_report(request, state.sessions.clone()).await;
+    state.session_timeline.record(
+        session_id,
+        session_timeline::sql_event(
+            DbSessionTimelineEventKind::Export,
+            &sql,
+            &result,
+            |result| result.message.clone(),
+            started.elapsed(),
+        ),
+    );
+    result
+}
+
+#[tauri::command]
+pub(crate) fn db_start_data_export(
+    request: DbExportQueryDataRequest,
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<DbJobStatus, String> {
+    let session_id = request.session_id;
+    let sql = request.sql.clone();
+    let file_path = request.file_path.clone();
+    let result = data_export::start_data_export_job(
+        request,
+        state.sessions.clone(),
+        state.jobs.clone(),
+        app,
+    );
+    // The job reports its own progress; the timeline records that it was started.
+    let mut event =
+        session_timeline::outcome_event(DbSessionTimelineEventKind::Export, &result, |_| {
+            format!("Started export to {file_path}")
+        });
+    event.sql = Some(sql);
+    state.session_timeline.record(session_id, event);
+    result
+}
+
+#[tauri::command]
+pub(crate) fn db_preview_xlsx_import(
+    request: DbXlsxPreviewRequest,
+) -> Result<DbXlsxPreview, String> {
+    table_import::preview_xlsx_import(&request)
+}
+
+#[tauri::command]
+pub(crate) fn db_start_xlsx_import(
+    request: DbXlsxImportRequest,
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<DbJobStatus, String> {
+    table_import::start_xlsx_import_job(request, state.sessions.clone(), state.jobs.clone(), app)
+}
+
+#[tauri::command]
+pub(crate) async fn db_import_clipboard_rows(
+    request: DbImportClipboardRowsRequest,
+    app: tauri::AppHandle,
+) -> Result<DbClipboardImportResult, String> {
+    run_blocking(app, move |state, _| {
+        with_session_mut(&state, request.session_id, |session| {
+            table_import::import_clipboard_rows(session, &request)
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn db_list_jobs(state: tauri::State<'_, AppState>) -> Result<Vec<DbJobStatus>, String> {
+    state.jobs.list()
+}
+
+/// Asks a running job to stop; it reports the cancelled state once it has. Returns false
+/// if the job has already finished.
+#[tauri::command]
+pub(crate) fn db_cancel_job(
+    request: DbJobRequest,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    state.jobs.cancel(request.job_id)
+}
+
+/// Runs a command body on the blocking thread pool, so a long statement holds neither the
+/// main thread nor an async worker. Work on one session still runs one piece at a time, as
+/// each session has its own lock; other sessions are not held up.
+async fn run_blocking<T: Send + 'static>(
+    app: tauri::AppHandle,
+    f: impl FnOnce(tauri::State<'_, AppState>, &tauri::AppHandle) -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(move || f(app.state::<AppState>(), &app))
+        .await
+        .map_err(|error| format!("The database worker stopped unexpectedly: {error}"))?
+}
+
+/// `run_blocking` for connects, which report failures as [`DbConnectError`].
+async fn run_connect_blocking<T: Send + 'static>(
+    app: tauri::AppHandle,
+    f: impl FnOnce(tauri::State<'_, AppState>, &tauri::AppHandle) -> Result<T, DbConnectError>
+        + Send
+        + 'static,
+) -> Result<T, DbConnectError> {
+    tauri::async_runtime::spawn_blocking(move || f(app.state::<AppState>(), &app))
+        .await
+        .map_err(|error| {
+            DbConnectError::general(format!("The database worker stopped unexpectedly: {error}"))
+        })?
 }
 
+/// Saved profile the session was opened from, if any.
+fn session_profile_id(session: &AppSession) -> Option<String> {
+    session
+        .connect_request()
+        .and_then(|request| request.profile_id.clone())
+}
+
+/// Provider errors are also recorded in the session's error summary. Looking up a session
+/// that is not open is not a provider error and is not recorded.
 fn with_session<T>(
     state: &tauri::State<'_, AppState>,
     session_id: u64,
     f: impl FnOnce(&AppSession) -> Result<T, String>,
 ) -> Result<T, String> {
-    let sessions = state
-        .sessions
-        .lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let session = sessions
-        .get(&session_id)
-        .ok_or_else(|| "Session not found".to_string())?;
-    f(session)
+    state.sessions.with_session(session_id, |session| {
+        f(session).inspect_err(|error| state.errors.record(session_id, error))
+    })
+}
+
+/// Runs a read on an idle pooled connection when the session has one, else on the primary.
+fn with_pooled_session<T>(
+    state: &tauri::State<'_, AppState>,
+    session_id: u64,
+    f: impl Fn(&AppSession) -> Result<T, String>,
+) -> Result<T, String> {
+    let pooled = state.sessions.get(session_id).ok().and_then(|primary| {
+        state
+            .session_pools
+            .with_connection(session_id, &primary, &f)
+    });
+    match pooled {
+        Some(result) => {
+            state.sessions.touch(session_id);
+            result.inspect_err(|error| state.errors.record(session_id, error))
+        }
+        None => with_session(state, session_id, f),
+    }
+}
+
+fn emit_reconnect_notice(
+    app: &tauri::AppHandle,
+    state: &tauri::State<'_, AppState>,
+    notice: Option<DbSessionReconnected>,
+) {
+    if let Some(notice) = notice {
+        state.session_timeline.record(
+            notice.session_id,
+            DbSessionTimelineEvent {
+                succeeded: notice.reconnected,
+                error: (!notice.reconnected).then(|| notice.message.clone()),
+                ..session_timeline::event(
+                    DbSessionTimelineEventKind::Reconnect,
+                    notice.message.clone(),
+                )
+            },
+        );
+        let _ = app.emit(EVENT_SESSION_RECONNECTED, notice);
+    }
 }
 
 fn with_session_mut<T>(
@@ -347,14 +2250,9 @@ fn with_session_mut<T>(
     session_id: u64,
     f: impl FnOnce(&mut AppSession) -> Result<T, String>,
 ) -> Result<T, String> {
-    let mut sessions = state
-        .sessions
-        .lock()
-        .map_err(|_| "Failed to acquire session lock".to_string())?;
-    let session = sessions
-        .get_mut(&session_id)
-        .ok_or_else(|| "Session not found".to_string())?;
-    f(session)
+    state.sessions.with_session(session_id, |session| {
+        f(session).inspect_err(|error| state.errors.record(session_id, error))
+    })
 }
 
 fn next_profile_id(
@@ -374,6 +2272,78 @@ fn next_profile_id(
     candidate
 }
 
+/// Host, service and username shown in session summaries.
+fn connection_details(
+    connection: &DbConnectConnection,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let non_empty = |value: &str| {
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    };
+    match connection {
+        DbConnectConnection::Oracle(connection) => (
+            non_empty(connection.host.as_str()),
+            non_empty(connection.service_name.as_str()),
+            non_empty(connection.username.as_str()).map(|username| {
+                match connection.proxy_user.as_deref().and_then(non_empty) {
+                    Some(proxy_user) => format!("{username}[{proxy_user}]"),
+                    None => username,
+                }
+            }),
+        ),
+        DbConnectConnection::Postgres(connection)
+        | DbConnectConnection::Mysql(connection)
+        | DbConnectConnection::Clickhouse(connection) => (
+            non_empty(connection.host.as_str()),
+            non_empty(connection.database.as_str()),
+            non_empty(connection.username.as_str()),
+        ),
+        DbConnectConnection::Sqlite(connection) => {
+            (None, non_empty(connection.file_path.as_str()), None)
+        }
+        DbConnectConnection::Duckdb(connection) => {
+            (None, non_empty(connection.file_path.as_str()), None)
+        }
+        DbConnectConnection::Snowflake(connection) => (
+            non_empty(connection.account.as_str()),
+            non_empty(connection.database.as_str()),
+            non_empty(connection.username.as_str()),
+        ),
+        DbConnectConnection::Libsql(connection) => (non_empty(connection.url.as_str()), None, None),
+        DbConnectConnection::External(_) => (None, None, None),
+    }
+}
+
+fn normalize_environment(environment: Option<&str>) -> Option<String> {
+    environment
+        .map(str::trim)
+        .filter(|environment| !environment.is_empty())
+        .map(str::to_string)
+}
+
+fn normalize_drop_allowlist(patterns: &[String]) -> Vec<String> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn normalize_reader_endpoint(endpoint: Option<&DbReaderEndpoint>) -> Option<DbReaderEndpoint> {
+    let endpoint = endpoint.filter(|endpoint| !endpoint.host.trim().is_empty())?;
+    Some(DbReaderEndpoint {
+        host: endpoint.host.trim().to_string(),
+        port: endpoint.port,
+        service_name: endpoint
+            .service_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|service| !service.is_empty())
+            .map(str::to_string),
+    })
+}
+
 fn normalize_profile_connection(connection: &DbConnectionProfile) -> DbConnectionProfile {
     match connection {
         DbConnectionProfile::Oracle(details) => {
@@ -384,6 +2354,66 @@ fn normalize_profile_connection(connection: &DbConnectionProfile) -> DbConnectio
                 username: details.username.trim().to_string(),
                 schema: details.schema.trim().to_uppercase(),
                 oracle_auth_mode: details.oracle_auth_mode,
+                edition: details
+                    .edition
+                    .as_deref()
+                    .map(|edition| edition.trim().to_uppercase())
+                    .filter(|edition| !edition.is_empty()),
+                proxy_user: details
+                    .proxy_user
+                    .as_deref()
+                    .map(|proxy_user| proxy_user.trim().to_uppercase())
+                    .filter(|proxy_user| !proxy_user.is_empty()),
+                protocol: details.protocol,
+                tls: match details.protocol {
+                    OracleProtocol::Tcp => None,
+                    OracleProtocol::Tcps => details
+                        .tls
+                        .as_deref()
+                        .map(normalize_oracle_tls)
+                        .map(Box::new),
+                },
+                wallet: details.wallet.as_ref().map(|wallet| OracleWalletRef {
+                    wallet_name: wallet.wallet_name.trim().to_string(),
+                    service_alias: wallet.service_alias.trim().to_string(),
+                }),
+                tns_alias: details
+                    .tns_alias
+                    .as_ref()
+                    .map(|tns_alias| OracleTnsAliasRef {
+                        alias: tns_alias.alias.trim().to_string(),
+                        tns_admin: trimmed(&tns_alias.tns_admin),
+                    }),
+                ldap: details.ldap.as_ref().map(|ldap| {
+                    Box::new(OracleLdapNaming {
+                        name: ldap.name.trim().to_string(),
+                        directory_servers: ldap
+                            .directory_servers
+                            .iter()
+                            .map(|server| server.trim().to_string())
+                            .filter(|server| !server.is_empty())
+                            .collect(),
+                        default_admin_context: trimmed(&ldap.default_admin_context),
+                        tns_admin: trimmed(&ldap.tns_admin),
+                    })
+                }),
+                failover_hosts: details
+                    .failover_hosts
+                    .iter()
+                    .map(|address| OracleAddress {
+                        host: address.host.trim().to_string(),
+                        port: address.port,
+                    })
+                    .collect(),
+                failover: details.failover.clone(),
+                connect_descriptor: trimmed(&details.connect_descriptor),
+                external_password_store: details.external_password_store.as_ref().map(|store| {
+                    Box::new(OracleExternalPasswordStore {
+                        alias: store.alias.trim().to_string(),
+                        wallet_dir: store.wallet_dir.trim().to_string(),
+                    })
+                }),
+                oracle_client_lib_dir: trimmed(&details.oracle_client_lib_dir),
             })
         }
         DbConnectionProfile::Postgres(details) => {
@@ -392,7 +2422,67 @@ fn normalize_profile_connection(connection: &DbConnectionProfile) -> DbConnectio
         DbConnectionProfile::Mysql(details) => {
             DbConnectionProfile::Mysql(normalize_network_connection(details))
         }
+        DbConnectionProfile::Clickhouse(details) => {
+            DbConnectionProfile::Clickhouse(normalize_network_connection(details))
+        }
         DbConnectionProfile::Sqlite(details) => DbConnectionProfile::Sqlite(details.clone()),
+        DbConnectionProfile::Duckdb(details) => {
+            DbConnectionProfile::Duckdb(DuckdbConnectionOptions {
+                file_path: details.file_path.trim().to_string(),
+                cli_path: details
+                    .cli_path
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|cli_path| !cli_path.is_empty())
+                    .map(str::to_string),
+            })
+        }
+        DbConnectionProfile::Snowflake(details) => {
+            let optional = |value: &Option<String>| {
+                value
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_string)
+            };
+            DbConnectionProfile::Snowflake(SnowflakeConnectionOptions {
+                account: details.account.trim().to_string(),
+                username: details.username.trim().to_string(),
+                database: details.database.trim().to_string(),
+                schema: optional(&details.schema),
+                warehouse: optional(&details.warehouse),
+                role: optional(&details.role),
+                token_type: details.token_type,
+            })
+        }
+        DbConnectionProfile::Libsql(details) => {
+            DbConnectionProfile::Libsql(LibsqlConnectionOptions {
+                url: details.url.trim().to_string(),
+            })
+        }
+        DbConnectionProfile::External(details) => {
+            DbConnectionProfile::External(ExternalConnectionOptions {
+                provider_id: details.provider_id.trim().to_string(),
+                options: details.options.clone(),
+            })
+        }
+    }
+}
+
+fn normalize_ssh_tunnel(details: &SshTunnelOptions) -> SshTunnelOptions {
+    let optional = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    SshTunnelOptions {
+        host: details.host.trim().to_string(),
+        port: details.port,
+        username: details.username.trim().to_string(),
+        identity_file: optional(&details.identity_file),
+        cli_path: optional(&details.cli_path),
     }
 }
 
@@ -410,3 +2500,19 @@ fn normalize_network_connection(details: &NetworkConnectionOptions) -> NetworkCo
             .map(str::to_string),
     }
 }
+
+fn normalize_oracle_tls(tls: &OracleTlsOptions) -> OracleTlsOptions {
+    OracleTlsOptions {
+        wallet_location: trimmed(&tls.wallet_location),
+        server_cert_dn: trimmed(&tls.server_cert_dn),
+        server_dn_match: tls.server_dn_match,
+    }
+}
+
+fn trimmed(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
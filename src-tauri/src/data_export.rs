@@ -0,0 +1,1020 @@
+use crate::cancellation::CancelToken;
+use crate::jobs::{start_job, JobRegistry};
+use crate::providers::ProviderRegistry;
+use crate::sql_ident;
+use crate::state::{lock_session, SessionRegistry};
+use crate::table_import;
+use crate::types::{
+    CsvEncoding, CsvNullStyle, CsvQuoteStyle, DatabaseProvider, DbCsvExportOptions,
+    DbDataExportResult, DbExportQueryDataRequest, DbJobKind, DbJobStatus,
+};
+use crate::workspace::now_millis;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+const CSV_LINE_ENDING: &str = "\r\n";
+const CHECKPOINT_ROW_INTERVAL: u64 = 10_000;
+const CHECKPOINT_FILE_SUFFIX: &str = ".checkpoint.json";
+
+/// A single cell value as produced by a provider for file exports. Keeping the
+/// value kind lets exports apply locale options that plain display strings lose.
+pub(crate) enum ExportValue {
+    Null,
+    Text(String),
+    Number(String),
+    DateTime(ExportDateTime),
+}
+
+pub(crate) struct ExportDateTime {
+    pub(crate) year: i32,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+    pub(crate) nanosecond: u32,
+    pub(crate) offset_seconds: Option<i32>,
+    pub(crate) display: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DataExportCheckpoint {
+    sql: String,
+    options_fingerprint: String,
+    rows_written: u64,
+    bytes_written: u64,
+    last_key: Option<String>,
+    /// How to write `last_key` back into SQL; `None` for keys that cannot be, such as dates.
+    #[serde(default)]
+    last_key_kind: Option<ExportKeyKind>,
+    unmappable_chars: usize,
+    updated_at: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+enum ExportKeyKind {
+    Number,
+    Text,
+}
+
+pub(crate) async fn export_query_data(
+    request: DbExportQueryDataRequest,
+    sessions: SessionRegistry,
+    cancel: CancelToken,
+) -> Result<DbDataExportResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        export_query_data_blocking(request, sessions, &mut |_| {}, &cancel)
+    })
+    .await
+    .map_err(|error| format!("Data export task failed: {error}"))?
+}
+
+/// Runs a data export as a background job, reporting rows written at each checkpoint.
+pub(crate) fn start_data_export_job(
+    request: DbExportQueryDataRequest,
+    sessions: SessionRegistry,
+    jobs: Arc<JobRegistry>,
+    app: AppHandle,
+) -> Result<DbJobStatus, String> {
+    let action = if request.resume {
+        "Resume export"
+    } else {
+        "Export"
+    };
+    let label = format!("{action} to {}", request.file_path.trim());
+    start_job(jobs, app, DbJobKind::DataExport, label, move |reporter| {
+        export_query_data_blocking(
+            request,
+            sessions,
+            &mut |rows| reporter.progress(rows, &format!("{rows} row(s) written.")),
+            reporter.cancel_token(),
+        )
+        .map(|result| result.message)
+    })
+}
+
+pub(crate) fn export_query_data_blocking(
+    request: DbExportQueryDataRequest,
+    sessions: SessionRegistry,
+    on_progress: &mut dyn FnMut(u64),
+    cancel: &CancelToken,
+) -> Result<DbDataExportResult, String> {
+    let file_path = request.file_path.trim();
+    if file_path.is_empty() {
+        return Err("Destination file is required".to_string());
+    }
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let mut writer = CsvWriter::new(&request.options)?;
+    let key_column = request
+        .key_column
+        .as_deref()
+        .map(str::trim)
+        .filter(|column| !column.is_empty());
+    let options_fingerprint = export_options_fingerprint(&request.options, key_column)?;
+    let path = PathBuf::from(file_path);
+    let checkpoint_path = checkpoint_path_for(&path);
+
+    let session = sessions.get(request.session_id)?;
+    let session = lock_session(&session)?;
+
+    let (file, resume_from) = if request.resume {
+        let checkpoint = read_checkpoint(&checkpoint_path)?;
+        if checkpoint.sql != sql || checkpoint.options_fingerprint != options_fingerprint {
+            return Err(
+                "The query or export options differ from the interrupted export; start a new export instead"
+                    .to_string(),
+            );
+        }
+        if key_column.is_none() {
+            return Err(
+                "Resuming needs a key column to continue after the last exported row; start a new export instead"
+                    .to_string(),
+            );
+        }
+        let file = open_for_resume(&path, checkpoint.bytes_written)?;
+        writer.unmappable_chars = checkpoint.unmappable_chars;
+        (file, Some(checkpoint))
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| {
+                format!("Failed to create directory '{}': {error}", parent.display())
+            })?;
+        }
+        let mut file = File::create(&path)
+            .map_err(|error| format!("Failed to create '{}': {error}", path.display()))?;
+        file.write_all(writer.preamble())
+            .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+        let _ = fs::remove_file(&checkpoint_path);
+        (file, None)
+    };
+
+    let query = keyed_query(session.provider, sql, key_column, resume_from.as_ref())?;
+    let mut sink = CsvFileSink {
+        writer,
+        output: BufWriter::new(file),
+        key_column: key_column.map(str::to_string),
+        key_index: None,
+        resumed: resume_from.is_some(),
+        rows_seen: resume_from
+            .as_ref()
+            .map_or(0, |checkpoint| checkpoint.rows_written),
+        last_key: None,
+        last_key_kind: None,
+        checkpoint_path: checkpoint_path.clone(),
+        checkpoint_interval: CHECKPOINT_ROW_INTERVAL,
+        sql: sql.to_string(),
+        options_fingerprint,
+        on_progress,
+    };
+    let stream_result = ProviderRegistry::stream_query_rows(&session, &query, &mut sink, cancel);
+    let flush_result = sink.output.flush();
+    stream_result
+        .map_err(|error| format!("Data export to '{}' failed: {error}", path.display()))?;
+    flush_result.map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+    let row_count = sink.rows_seen;
+    let _ = fs::remove_file(&checkpoint_path);
+
+    let mut message = format!(
+        "Exported {} row(s) to {}.",
+        row_count,
+        path.to_string_lossy()
+    );
+    if let Some(checkpoint) = resume_from {
+        message.push_str(&format!(
+            " Resumed after {} previously written row(s).",
+            checkpoint.rows_written
+        ));
+    }
+    if sink.writer.unmappable_chars > 0 {
+        message.push_str(&format!(
+            " {} character(s) could not be represented in the selected encoding and were replaced with '?'.",
+            sink.writer.unmappable_chars
+        ));
+    }
+
+    Ok(DbDataExportResult {
+        file_path: path.to_string_lossy().to_string(),
+        row_count,
+        message,
+    })
+}
+
+/// With a key column the export reads the query ordered by that key, so a resumed export
+/// can continue with the rows after the last key it wrote instead of reading them all again.
+fn keyed_query(
+    provider: DatabaseProvider,
+    sql: &str,
+    key_column: Option<&str>,
+    resume_from: Option<&DataExportCheckpoint>,
+) -> Result<String, String> {
+    let Some(key_column) = key_column else {
+        return Ok(sql.to_string());
+    };
+    let key = sql_ident::quote_identifier(
+        provider,
+        &sql_ident::parse_identifier(provider, key_column, "Key column")?,
+    );
+    let after = match resume_from.and_then(|checkpoint| checkpoint.last_key.as_deref()) {
+        None => String::new(),
+        Some(last_key) => {
+            let literal = match resume_from.and_then(|checkpoint| checkpoint.last_key_kind) {
+                Some(ExportKeyKind::Number)
+                    if last_key
+                        .chars()
+                        .all(|ch| ch.is_ascii_digit() || matches!(ch, '.' | '-' | '+' | 'e' | 'E')) =>
+                {
+                    last_key.to_string()
+                }
+                Some(ExportKeyKind::Text) => table_import::string_literal(provider, last_key),
+                _ => {
+                    return Err(
+                        "Only exports keyed by a number or text column can be resumed; start a new export instead"
+                            .to_string(),
+                    )
+                }
+            };
+            format!(" WHERE {key} > {literal}")
+        }
+    };
+    Ok(format!(
+        "SELECT * FROM (\n{}\n){}{after} ORDER BY {key}",
+        sql.trim_end_matches(';'),
+        sql_ident::derived_table_alias(provider, "clarity_export")
+    ))
+}
+
+fn checkpoint_path_for(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(CHECKPOINT_FILE_SUFFIX);
+    path.with_file_name(file_name)
+}
+
+fn export_options_fingerprint(
+    options: &DbCsvExportOptions,
+    key_column: Option<&str>,
+) -> Result<String, String> {
+    let options = serde_json::to_string(options)
+        .map_err(|error| format!("Failed to serialize export options: {error}"))?;
+    Ok(format!("{options}|key={}", key_column.unwrap_or_default()))
+}
+
+fn read_checkpoint(path: &Path) -> Result<DataExportCheckpoint, String> {
+    let content = fs::read_to_string(path).map_err(|error| {
+        format!(
+            "No export checkpoint found at '{}': {error}",
+            path.display()
+        )
+    })?;
+    serde_json::from_str(&content).map_err(|error| {
+        format!(
+            "Failed to parse export checkpoint '{}': {error}",
+            path.display()
+        )
+    })
+}
+
+fn write_checkpoint(path: &Path, checkpoint: &DataExportCheckpoint) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(checkpoint)
+        .map_err(|error| format!("Failed to serialize export checkpoint: {error}"))?;
+    // Write then rename so an interruption never leaves a half-written checkpoint.
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, content)
+        .and_then(|_| fs::rename(&temp_path, path))
+        .map_err(|error| {
+            format!(
+                "Failed to write export checkpoint '{}': {error}",
+                path.display()
+            )
+        })
+}
+
+fn open_for_resume(path: &Path, bytes_written: u64) -> Result<File, String> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(|error| format!("Failed to open '{}' for resume: {error}", path.display()))?;
+    let length = file
+        .metadata()
+        .map_err(|error| format!("Failed to read '{}': {error}", path.display()))?
+        .len();
+    if length < bytes_written {
+        return Err(format!(
+            "'{}' is shorter than its checkpoint; start a new export instead",
+            path.display()
+        ));
+    }
+
+    // Drop anything written after the last checkpoint, including a partial row.
+    file.set_len(bytes_written)
+        .and_then(|_| file.seek(SeekFrom::End(0)))
+        .map_err(|error| format!("Failed to prepare '{}' for resume: {error}", path.display()))?;
+    Ok(file)
+}
+
+/// Receives the columns and then each row of a streamed query result.
+pub(crate) trait ExportRowSink {
+    fn columns(&mut self, columns: &[String]) -> Result<(), String>;
+    fn row(&mut self, values: &[ExportValue]) -> Result<(), String>;
+}
+
+/// Writes CSV rows to a file, checkpointing every `checkpoint_interval` rows. A resumed
+/// export starts counting at the rows already written and writes no header.
+struct CsvFileSink<'a, W: Write + Seek> {
+    writer: CsvWriter,
+    output: W,
+    key_column: Option<String>,
+    key_index: Option<usize>,
+    resumed: bool,
+    rows_seen: u64,
+    last_key: Option<String>,
+    last_key_kind: Option<ExportKeyKind>,
+    checkpoint_path: PathBuf,
+    checkpoint_interval: u64,
+    sql: String,
+    options_fingerprint: String,
+    on_progress: &'a mut dyn FnMut(u64),
+}
+
+impl<W: Write + Seek> CsvFileSink<'_, W> {
+    fn write_line(&mut self, line: &str) -> Result<(), String> {
+        let bytes = self.writer.encode(line);
+        self.output
+            .write_all(&bytes)
+            .map_err(|error| error.to_string())
+    }
+
+    fn save_checkpoint(&mut self) -> Result<(), String> {
+        self.output.flush().map_err(|error| error.to_string())?;
+        let bytes_written = self
+            .output
+            .stream_position()
+            .map_err(|error| error.to_string())?;
+        write_checkpoint(
+            &self.checkpoint_path,
+            &DataExportCheckpoint {
+                sql: self.sql.clone(),
+                options_fingerprint: self.options_fingerprint.clone(),
+                rows_written: self.rows_seen,
+                bytes_written,
+                last_key: self.last_key.clone(),
+                last_key_kind: self.last_key_kind,
+                unmappable_chars: self.writer.unmappable_chars,
+                updated_at: now_millis(),
+            },
+        )?;
+        (self.on_progress)(self.rows_seen);
+        Ok(())
+    }
+}
+
+impl<W: Write + Seek> ExportRowSink for CsvFileSink<'_, W> {
+    fn columns(&mut self, columns: &[String]) -> Result<(), String> {
+        if let Some(key_column) = self.key_column.as_deref() {
+            let index = columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(key_column))
+                .ok_or_else(|| format!("Key column '{key_column}' is not part of the result"))?;
+            self.key_index = Some(index);
+        }
+
+        if self.resumed || !self.writer.include_header {
+            return Ok(());
+        }
+        let line = self.writer.format_header(columns);
+        self.write_line(line.as_str())
+    }
+
+    fn row(&mut self, values: &[ExportValue]) -> Result<(), String> {
+        let key = self.key_index.and_then(|index| values.get(index));
+        // `key > last_key` never matches NULL, so a resume would skip such rows silently.
+        if matches!(key, Some(ExportValue::Null)) {
+            return Err(format!(
+                "Key column '{}' has a NULL value; choose a NOT NULL key column",
+                self.key_column.as_deref().unwrap_or_default()
+            ));
+        }
+        self.rows_seen += 1;
+
+        let line = self.writer.format_row(values);
+        self.write_line(line.as_str())?;
+        self.last_key = key.map(export_value_key);
+        self.last_key_kind = key.and_then(export_key_kind);
+        if self.rows_seen.is_multiple_of(self.checkpoint_interval) {
+            self.save_checkpoint()?;
+        }
+        Ok(())
+    }
+}
+
+fn export_key_kind(value: &ExportValue) -> Option<ExportKeyKind> {
+    match value {
+        ExportValue::Number(_) => Some(ExportKeyKind::Number),
+        ExportValue::Text(_) => Some(ExportKeyKind::Text),
+        ExportValue::Null | ExportValue::DateTime(_) => None,
+    }
+}
+
+fn export_value_key(value: &ExportValue) -> String {
+    match value {
+        ExportValue::Null => String::new(),
+        ExportValue::Text(text) | ExportValue::Number(text) => text.clone(),
+        ExportValue::DateTime(date_time) => date_time.display.clone(),
+    }
+}
+
+pub(crate) struct CsvWriter {
+    delimiter: char,
+    quote_style: CsvQuoteStyle,
+    null_marker: &'static str,
+    encoding: CsvEncoding,
+    decimal_separator: char,
+    date_format: Option<String>,
+    include_header: bool,
+    unmappable_chars: usize,
+}
+
+impl CsvWriter {
+    pub(crate) fn new(options: &DbCsvExportOptions) -> Result<Self, String> {
+        let delimiter = parse_single_char_option(options.delimiter.as_deref(), ',', "Delimiter")?;
+        if matches!(delimiter, '"' | '\r' | '\n') {
+            return Err("Delimiter cannot be a quote or line break".to_string());
+        }
+        let decimal_separator = parse_single_char_option(
+            options.decimal_separator.as_deref(),
+            '.',
+            "Decimal separator",
+        )?;
+        if decimal_separator == delimiter && options.quote_style == CsvQuoteStyle::Never {
+            return Err(
+                "Decimal separator must differ from the delimiter when quoting is disabled"
+                    .to_string(),
+            );
+        }
+
+        Ok(Self {
+            delimiter,
+            quote_style: options.quote_style,
+            null_marker: match options.null_style {
+                CsvNullStyle::Empty => "",
+                CsvNullStyle::Null => "NULL",
+                CsvNullStyle::BackslashN => "\\N",
+            },
+            encoding: options.encoding,
+            decimal_separator,
+            date_format: options
+                .date_format
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            include_header: options.include_header.unwrap_or(true),
+            unmappable_chars: 0,
+        })
+    }
+
+    pub(crate) fn preamble(&self) -> &'static [u8] {
+        match self.encoding {
+            CsvEncoding::Utf8 | CsvEncoding::Windows1252 => &[],
+            CsvEncoding::Utf8Bom => &[0xEF, 0xBB, 0xBF],
+            CsvEncoding::Utf16Le => &[0xFF, 0xFE],
+        }
+    }
+
+    pub(crate) fn format_header(&self, columns: &[String]) -> String {
+        let cells = columns
+            .iter()
+            .map(|column| self.quote(column.as_str()))
+            .collect::<Vec<_>>();
+        self.join_line(cells)
+    }
+
+    /// NULL cells are written as the bare marker; text that equals the marker is quoted
+    /// unless quoting is disabled, so loaders can tell the two apart.
+    pub(crate) fn format_row(&self, values: &[ExportValue]) -> String {
+        let cells = values
+            .iter()
+            .map(|value| match value {
+                ExportValue::Null => self.null_marker.to_string(),
+                value => {
+                    let text = self.render_value(value);
+                    if text == self.null_marker && self.quote_style == CsvQuoteStyle::Minimal {
+                        format!("\"{text}\"")
+                    } else {
+                        self.quote(text.as_str())
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        self.join_line(cells)
+    }
+
+    pub(crate) fn encode(&mut self, text: &str) -> Vec<u8> {
+        match self.encoding {
+            CsvEncoding::Utf8 | CsvEncoding::Utf8Bom => text.as_bytes().to_vec(),
+            CsvEncoding::Utf16Le => text
+                .encode_utf16()
+                .flat_map(|unit| unit.to_le_bytes())
+                .collect(),
+            CsvEncoding::Windows1252 => text
+                .chars()
+                .map(|ch| {
+                    encode_windows_1252(ch).unwrap_or_else(|| {
+                        self.unmappable_chars += 1;
+                        b'?'
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn render_value(&self, value: &ExportValue) -> String {
+        match value {
+            ExportValue::Null => String::new(),
+            ExportValue::Text(text) => text.clone(),
+            ExportValue::Number(number) => {
+                if self.decimal_separator == '.' {
+                    number.clone()
+                } else {
+                    number.replace('.', self.decimal_separator.to_string().as_str())
+                }
+            }
+            ExportValue::DateTime(date_time) => match self.date_format.as_deref() {
+                Some(pattern) => format_export_date_time(date_time, pattern),
+                None => date_time.display.clone(),
+            },
+        }
+    }
+
+    /// `Never` still quotes a value holding the delimiter, a quote or a line break, which
+    /// would otherwise split the row.
+    fn quote(&self, value: &str) -> String {
+        let needs_quotes = match self.quote_style {
+            CsvQuoteStyle::All => true,
+            CsvQuoteStyle::Minimal | CsvQuoteStyle::Never => value
+                .chars()
+                .any(|ch| ch == self.delimiter || ch == '"' || ch == '\r' || ch == '\n'),
+        };
+
+        if needs_quotes {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn join_line(&self, cells: Vec<String>) -> String {
+        let mut line = cells.join(self.delimiter.to_string().as_str());
+        line.push_str(CSV_LINE_ENDING);
+        line
+    }
+}
+
+fn parse_single_char_option(
+    value: Option<&str>,
+    default: char,
+    label: &str,
+) -> Result<char, String> {
+    let Some(raw) = value.filter(|value| !value.is_empty()) else {
+        return Ok(default);
+    };
+    if raw.eq_ignore_ascii_case("tab") || raw == "\\t" {
+        return Ok('\t');
+    }
+
+    let mut chars = raw.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Ok(ch),
+        _ => Err(format!("{label} must be a single character")),
+    }
+}
+
+/// Formats a date/time using Oracle-style tokens: YYYY, YY, MM, DD, HH24, HH, MI, SS,
+/// FF1-FF9 (FF alone means FF6), AM/PM and TZH:TZM. Other characters are copied as-is.
+fn format_export_date_time(value: &ExportDateTime, pattern: &str) -> String {
+    let mut output = String::with_capacity(pattern.len() + 8);
+    let mut rest = pattern;
+
+    while !rest.is_empty() {
+        let upper = rest
+            .get(..4.min(rest.len()))
+            .unwrap_or(rest)
+            .to_ascii_uppercase();
+        if upper.starts_with("YYYY") {
+            output.push_str(&format!("{:04}", value.year));
+            rest = &rest[4..];
+        } else if upper.starts_with("HH24") {
+            output.push_str(&format!("{:02}", value.hour));
+            rest = &rest[4..];
+        } else if upper.starts_with("FF") {
+            let digits = rest[2..]
+                .chars()
+                .next()
+                .and_then(|ch| ch.to_digit(10))
+                .filter(|digit| (1..=9).contains(digit));
+            let precision = digits.unwrap_or(6) as usize;
+            let fraction = format!("{:09}", value.nanosecond);
+            output.push_str(&fraction[..precision]);
+            rest = &rest[if digits.is_some() { 3 } else { 2 }..];
+        } else if upper.starts_with("TZH") {
+            let offset = value.offset_seconds.unwrap_or(0);
+            let sign = if offset < 0 { '-' } else { '+' };
+            output.push_str(&format!("{sign}{:02}", offset.abs() / 3600));
+            rest = &rest[3..];
+        } else if upper.starts_with("TZM") {
+            let offset = value.offset_seconds.unwrap_or(0);
+            output.push_str(&format!("{:02}", offset.abs() % 3600 / 60));
+            rest = &rest[3..];
+        } else if upper.starts_with("YY") {
+            output.push_str(&format!("{:02}", value.year.rem_euclid(100)));
+            rest = &rest[2..];
+        } else if upper.starts_with("MM") {
+            output.push_str(&format!("{:02}", value.month));
+            rest = &rest[2..];
+        } else if upper.starts_with("DD") {
+            output.push_str(&format!("{:02}", value.day));
+            rest = &rest[2..];
+        } else if upper.starts_with("HH") {
+            let hour = match value.hour % 12 {
+                0 => 12,
+                hour => hour,
+            };
+            output.push_str(&format!("{hour:02}"));
+            rest = &rest[2..];
+        } else if upper.starts_with("MI") {
+            output.push_str(&format!("{:02}", value.minute));
+            rest = &rest[2..];
+        } else if upper.starts_with("SS") {
+            output.push_str(&format!("{:02}", value.second));
+            rest = &rest[2..];
+        } else if upper.starts_with("AM") || upper.starts_with("PM") {
+            output.push_str(if value.hour < 12 { "AM" } else { "PM" });
+            rest = &rest[2..];
+        } else {
+            let ch = rest.chars().next().unwrap_or_default();
+            output.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+
+    output
+}
+
+fn encode_windows_1252(ch: char) -> Option<u8> {
+    let code = ch as u32;
+    if code < 0x80 || (0xA0..=0xFF).contains(&code) {
+        return Some(code as u8);
+    }
+
+    let byte = match code {
+        0x20AC => 0x80,
+        0x201A => 0x82,
+        0x0192 => 0x83,
+        0x201E => 0x84,
+        0x2026 => 0x85,
+        0x2020 => 0x86,
+        0x2021 => 0x87,
+        0x02C6 => 0x88,
+        0x2030 => 0x89,
+        0x0160 => 0x8A,
+        0x2039 => 0x8B,
+        0x0152 => 0x8C,
+        0x017D => 0x8E,
+        0x2018 => 0x91,
+        0x2019 => 0x92,
+        0x201C => 0x93,
+        0x201D => 0x94,
+        0x2022 => 0x95,
+        0x2013 => 0x96,
+        0x2014 => 0x97,
+        0x02DC => 0x98,
+        0x2122 => 0x99,
+        0x0161 => 0x9A,
+        0x203A => 0x9B,
+        0x0153 => 0x9C,
+        0x017E => 0x9E,
+        0x0178 => 0x9F,
+        _ => return None,
+    };
+    Some(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checkpoint_path_for, format_export_date_time, keyed_query, open_for_resume,
+        read_checkpoint, CsvFileSink, CsvWriter, ExportDateTime, ExportKeyKind, ExportRowSink,
+        ExportValue,
+    };
+    use crate::types::{
+        CsvEncoding, CsvNullStyle, CsvQuoteStyle, DatabaseProvider, DbCsvExportOptions,
+    };
+    use std::fs::{self, File};
+    use std::io::{BufWriter, Write};
+    use std::path::{Path, PathBuf};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    struct TempTestDir {
+        path: PathBuf,
+    }
+
+    impl TempTestDir {
+        fn new(name: &str) -> Self {
+            let unique = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos();
+            let path = std::env::temp_dir().join(format!(
+                "clarity_data_export_tests_{name}_{}_{}",
+                std::process::id(),
+                unique
+            ));
+            fs::create_dir_all(&path).expect("failed to create temp test directory");
+            Self { path }
+        }
+    }
+
+    impl Drop for TempTestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn test_sink<'a>(
+        file: File,
+        path: &Path,
+        rows_written: Option<u64>,
+        on_progress: &'a mut dyn FnMut(u64),
+    ) -> CsvFileSink<'a, BufWriter<File>> {
+        CsvFileSink {
+            writer: CsvWriter::new(&DbCsvExportOptions::default()).expect("valid options"),
+            output: BufWriter::new(file),
+            key_column: Some("id".to_string()),
+            key_index: None,
+            resumed: rows_written.is_some(),
+            rows_seen: rows_written.unwrap_or(0),
+            last_key: None,
+            last_key_kind: None,
+            checkpoint_path: checkpoint_path_for(path),
+            checkpoint_interval: 2,
+            sql: "SELECT id, name FROM t ORDER BY id".to_string(),
+            options_fingerprint: "test".to_string(),
+            on_progress,
+        }
+    }
+
+    fn feed_rows(
+        sink: &mut dyn ExportRowSink,
+        ids: std::ops::RangeInclusive<u32>,
+    ) -> Result<(), String> {
+        sink.columns(&["ID".to_string(), "NAME".to_string()])?;
+        for id in ids {
+            sink.row(&[
+                ExportValue::Number(id.to_string()),
+                ExportValue::Text(format!("name {id}")),
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn sample_date_time() -> ExportDateTime {
+        ExportDateTime {
+            year: 2026,
+            month: 3,
+            day: 7,
+            hour: 14,
+            minute: 5,
+            second: 9,
+            nanosecond: 123_456_789,
+            offset_seconds: Some(-(5 * 3600 + 30 * 60)),
+            display: "2026-03-07 14:05:09.123456789 -05:30".to_string(),
+        }
+    }
+
+    #[test]
+    fn formats_rows_with_european_locale_options() {
+        let options = DbCsvExportOptions {
+            delimiter: Some(";".to_string()),
+            decimal_separator: Some(",".to_string()),
+            date_format: Some("DD.MM.YYYY HH24:MI".to_string()),
+            ..Default::default()
+        };
+        let writer = CsvWriter::new(&options).expect("valid options");
+
+        let header = writer.format_header(&["ID".to_string(), "NOTE;X".to_string()]);
+        assert_eq!(header, "ID;\"NOTE;X\"\r\n");
+
+        let row = writer.format_row(&[
+            ExportValue::Number("1234.5".to_string()),
+            ExportValue::Text("say \"hi\"".to_string()),
+            ExportValue::Null,
+            ExportValue::DateTime(sample_date_time()),
+        ]);
+        assert_eq!(row, "1234,5;\"say \"\"hi\"\"\";;07.03.2026 14:05\r\n");
+    }
+
+    #[test]
+    fn supports_quote_styles_and_rejects_invalid_delimiters() {
+        let all = CsvWriter::new(&DbCsvExportOptions {
+            quote_style: CsvQuoteStyle::All,
+            delimiter: Some("tab".to_string()),
+            ..Default::default()
+        })
+        .expect("valid options");
+        assert_eq!(
+            all.format_row(&[ExportValue::Text("a".to_string()), ExportValue::Null]),
+            "\"a\"\t\r\n"
+        );
+
+        let never = CsvWriter::new(&DbCsvExportOptions {
+            quote_style: CsvQuoteStyle::Never,
+            ..Default::default()
+        })
+        .expect("valid options");
+        assert_eq!(
+            never.format_row(&[
+                ExportValue::Text("plain".to_string()),
+                ExportValue::Text("a,b".to_string()),
+                ExportValue::Text("say \"hi\"".to_string()),
+                ExportValue::Text("two\nlines".to_string()),
+            ]),
+            "plain,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+
+        for delimiter in ["\"", ";;"] {
+            assert!(CsvWriter::new(&DbCsvExportOptions {
+                delimiter: Some(delimiter.to_string()),
+                ..Default::default()
+            })
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn keeps_nulls_apart_from_matching_text() {
+        let row = [
+            ExportValue::Null,
+            ExportValue::Text(String::new()),
+            ExportValue::Text("NULL".to_string()),
+            ExportValue::Text("\\N".to_string()),
+        ];
+        let expected = [
+            (CsvNullStyle::Empty, ",\"\",NULL,\\N\r\n"),
+            (CsvNullStyle::Null, "NULL,,\"NULL\",\\N\r\n"),
+            (CsvNullStyle::BackslashN, "\\N,,NULL,\"\\N\"\r\n"),
+        ];
+        for (null_style, line) in expected {
+            let writer = CsvWriter::new(&DbCsvExportOptions {
+                null_style,
+                ..Default::default()
+            })
+            .expect("valid options");
+            assert_eq!(writer.format_row(&row), line);
+        }
+    }
+
+    #[test]
+    fn encodes_utf16_and_windows_1252() {
+        let mut utf16 = CsvWriter::new(&DbCsvExportOptions {
+            encoding: CsvEncoding::Utf16Le,
+            ..Default::default()
+        })
+        .expect("valid options");
+        assert_eq!(utf16.preamble(), &[0xFF, 0xFE]);
+        assert_eq!(utf16.encode("é"), vec![0xE9, 0x00]);
+
+        let mut windows = CsvWriter::new(&DbCsvExportOptions {
+            encoding: CsvEncoding::Windows1252,
+            ..Default::default()
+        })
+        .expect("valid options");
+        assert_eq!(windows.encode("€é–ł"), vec![0x80, 0xE9, 0x96, b'?']);
+        assert_eq!(windows.unmappable_chars, 1);
+    }
+
+    #[test]
+    fn formats_oracle_style_date_tokens() {
+        let value = sample_date_time();
+        assert_eq!(
+            format_export_date_time(&value, "yyyy-mm-dd\"T\"hh24:mi:ss.FF3 TZH:TZM"),
+            "2026-03-07\"T\"14:05:09.123 -05:30"
+        );
+        assert_eq!(
+            format_export_date_time(&value, "MM/DD/YY HH:MI PM"),
+            "03/07/26 02:05 PM"
+        );
+        assert_eq!(format_export_date_time(&value, "SS.FF"), "09.123456");
+    }
+
+    #[test]
+    fn resumes_interrupted_export_from_last_checkpoint() {
+        let temp = TempTestDir::new("resume");
+        let path = temp.path.join("rows.csv");
+        let mut progress = Vec::new();
+
+        {
+            let file = File::create(&path).expect("create output");
+            let mut on_progress = |rows| progress.push(rows);
+            let mut sink = test_sink(file, &path, None, &mut on_progress);
+            feed_rows(&mut sink, 1..=5).expect("write rows");
+            // Simulate a crash mid-row after the last checkpoint.
+            sink.output.write_all(b"6,name").expect("write partial row");
+            sink.output.flush().expect("flush");
+        }
+        assert_eq!(progress, vec![2, 4]);
+
+        let checkpoint = read_checkpoint(&checkpoint_path_for(&path)).expect("checkpoint");
+        assert_eq!(checkpoint.rows_written, 4);
+        assert_eq!(checkpoint.last_key.as_deref(), Some("4"));
+        assert_eq!(checkpoint.last_key_kind, Some(ExportKeyKind::Number));
+
+        // The resumed query only returns the rows after the last checkpointed key.
+        let file = open_for_resume(&path, checkpoint.bytes_written).expect("open for resume");
+        let mut on_progress = |_| {};
+        let mut sink = test_sink(file, &path, Some(4), &mut on_progress);
+        feed_rows(&mut sink, 5..=6).expect("resume rows");
+        assert_eq!(sink.rows_seen, 6);
+        sink.output.flush().expect("flush");
+
+        let content = fs::read_to_string(&path).expect("read output");
+        let expected = (1..=6).fold("ID,NAME\r\n".to_string(), |mut text, id| {
+            text.push_str(&format!("{id},name {id}\r\n"));
+            text
+        });
+        assert_eq!(content, expected);
+    }
+
+    #[test]
+    fn resumes_after_the_last_key_instead_of_rereading_rows() {
+        let checkpoint = |last_key: Option<&str>, last_key_kind| super::DataExportCheckpoint {
+            sql: "SELECT id, name FROM t".to_string(),
+            options_fingerprint: "test".to_string(),
+            rows_written: 4,
+            bytes_written: 40,
+            last_key: last_key.map(str::to_string),
+            last_key_kind,
+            unmappable_chars: 0,
+            updated_at: 0,
+        };
+        let sql = "SELECT id, name FROM t;";
+
+        assert_eq!(
+            keyed_query(DatabaseProvider::Postgres, sql, None, None).unwrap(),
+            sql
+        );
+        assert_eq!(
+            keyed_query(DatabaseProvider::Postgres, sql, Some("id"), None).unwrap(),
+            "SELECT * FROM (\nSELECT id, name FROM t\n) AS clarity_export ORDER BY \"id\""
+        );
+        assert_eq!(
+            keyed_query(
+                DatabaseProvider::Oracle,
+                sql,
+                Some("id"),
+                Some(&checkpoint(Some("4"), Some(ExportKeyKind::Number)))
+            )
+            .unwrap(),
+            "SELECT * FROM (\nSELECT id, name FROM t\n) clarity_export WHERE \"ID\" > 4 ORDER BY \"ID\""
+        );
+        assert_eq!(
+            keyed_query(
+                DatabaseProvider::Mysql,
+                sql,
+                Some("code"),
+                Some(&checkpoint(Some("O'Brien"), Some(ExportKeyKind::Text)))
+            )
+            .unwrap(),
+            "SELECT * FROM (\nSELECT id, name FROM t\n) AS clarity_export WHERE `code` > 'O''Brien' ORDER BY `code`"
+        );
+        for refused in [
+            checkpoint(Some("2026-03-07"), None),
+            checkpoint(Some("4 OR 1=1"), Some(ExportKeyKind::Number)),
+        ] {
+            assert!(
+                keyed_query(DatabaseProvider::Oracle, sql, Some("id"), Some(&refused)).is_err()
+            );
+        }
+
+        // A NULL key would fall outside `key > last_key` on resume, so it stops the export.
+        let temp = TempTestDir::new("null_key");
+        let path = temp.path.join("rows.csv");
+        let file = File::create(&path).expect("create output");
+        let mut on_progress = |_| {};
+        let mut sink = test_sink(file, &path, None, &mut on_progress);
+        feed_rows(&mut sink, 1..=2).expect("write rows");
+        let error = sink
+            .row(&[ExportValue::Null, ExportValue::Text("no id".to_string())])
+            .expect_err("NULL key");
+        assert!(error.contains("NOT NULL"));
+        assert_eq!(sink.rows_seen, 2);
+    }
+}
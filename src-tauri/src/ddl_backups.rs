@@ -0,0 +1,353 @@
+use crate::ddl_changelog::format_utc;
+use crate::files::sanitize_export_file_stem;
+use crate::profiles::app_data_file_path;
+use crate::types::{
+    DbDdlBackup, DbDdlBackupRetention, DbDdlHistorySource, DbListDdlBackupsRequest,
+    DbObjectDdlAsOf, DbObjectDdlAsOfRequest, DbObjectRef,
+};
+use crate::workspace::now_millis;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const DDL_BACKUP_STORE_FILE: &str = "ddl_backups.json";
+const DDL_BACKUP_DIRECTORY: &str = "ddl-backups";
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+static DDL_BACKUP_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Index of the backup files; the DDL itself lives in one `.sql` file per backup so it can
+/// be opened without Clarity.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct DdlBackupStore {
+    retention: DbDdlBackupRetention,
+    backups: Vec<DbDdlBackup>,
+}
+
+/// Archives the DDL an object has on the server before it is replaced.
+pub(crate) fn backup_object_ddl(
+    app: &AppHandle,
+    profile_id: Option<&str>,
+    object: &DbObjectRef,
+    ddl: &str,
+) -> Result<DbDdlBackup, String> {
+    let directory = app_data_file_path(app, DDL_BACKUP_DIRECTORY)?;
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("Failed to create DDL backup directory: {error}"))?;
+
+    update_store(app, |store| {
+        let created_at = now_millis();
+        let id = next_backup_id(store, created_at);
+        let path = directory.join(format!(
+            "{id}-{}.{}.sql",
+            sanitize_export_file_stem(object.schema.as_str()),
+            sanitize_export_file_stem(object.object_name.as_str())
+        ));
+        fs::write(&path, ddl)
+            .map_err(|error| format!("Failed to write DDL backup {}: {error}", path.display()))?;
+
+        let backup = DbDdlBackup {
+            id,
+            profile_id: profile_id.map(str::to_string),
+            schema: object.schema.clone(),
+            object_type: object.object_type.clone(),
+            object_name: object.object_name.clone(),
+            file_path: path.to_string_lossy().to_string(),
+            size_bytes: ddl.len() as u64,
+            created_at,
+        };
+        store.backups.push(backup.clone());
+        remove_files(&prune(store, created_at));
+        Ok(backup)
+    })
+}
+
+/// Newest first, optionally narrowed to one schema or object.
+pub(crate) fn list_ddl_backups(
+    app: &AppHandle,
+    request: &DbListDdlBackupsRequest,
+) -> Result<Vec<DbDdlBackup>, String> {
+    let matches = |filter: &Option<String>, value: &str| {
+        filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty())
+            .is_none_or(|filter| filter.eq_ignore_ascii_case(value))
+    };
+    let store = read_store(app)?;
+    Ok(store
+        .backups
+        .into_iter()
+        .rev()
+        .filter(|backup| {
+            matches(&request.schema, backup.schema.as_str())
+                && matches(&request.object_type, backup.object_type.as_str())
+                && matches(&request.object_name, backup.object_name.as_str())
+        })
+        .collect())
+}
+
+/// An object's DDL as of `request.as_of`: what the flashback query read, or else the first
+/// backup taken after that time, which holds the version that was live until Clarity next
+/// replaced the object. Only backups taken through the same saved profile are considered.
+pub(crate) fn ddl_as_of(
+    app: &AppHandle,
+    request: &DbObjectDdlAsOfRequest,
+    profile_id: Option<&str>,
+    flashback: Result<Option<String>, String>,
+) -> Result<DbObjectDdlAsOf, String> {
+    let when = format_utc(request.as_of);
+    let flashback_note = match flashback {
+        Ok(Some(ddl)) => {
+            return Ok(DbObjectDdlAsOf {
+                ddl,
+                source: DbDdlHistorySource::Flashback,
+                captured_at: None,
+                message: format!("Read with a flashback query as of {when} UTC."),
+            })
+        }
+        Ok(None) => "the flashback query found no source for it".to_string(),
+        Err(error) => format!("the flashback query failed: {error}"),
+    };
+
+    let store = read_store(app)?;
+    let Some(backup) = first_backup_after(&store.backups, request, profile_id) else {
+        return Err(format!(
+            "No version of {}.{} as of {when} UTC was found: {flashback_note}, and no DDL backup was taken after that time",
+            request.schema, request.object_name
+        ));
+    };
+    let ddl = fs::read_to_string(&backup.file_path)
+        .map_err(|error| format!("Failed to read DDL backup {}: {error}", backup.file_path))?;
+    Ok(DbObjectDdlAsOf {
+        ddl,
+        source: DbDdlHistorySource::Backup,
+        captured_at: Some(backup.created_at),
+        message: format!(
+            "From the DDL backup taken {} UTC, when Clarity next replaced the object ({flashback_note}). Changes made outside Clarity in between are not seen.",
+            format_utc(backup.created_at)
+        ),
+    })
+}
+
+fn first_backup_after<'a>(
+    backups: &'a [DbDdlBackup],
+    request: &DbObjectDdlAsOfRequest,
+    profile_id: Option<&str>,
+) -> Option<&'a DbDdlBackup> {
+    backups
+        .iter()
+        .filter(|backup| {
+            backup.created_at > request.as_of
+                && backup.profile_id.as_deref() == profile_id
+                && backup.schema.eq_ignore_ascii_case(request.schema.trim())
+                && backup
+                    .object_type
+                    .eq_ignore_ascii_case(request.object_type.trim())
+                && backup.object_name == request.object_name.trim()
+        })
+        .min_by_key(|backup| backup.created_at)
+}
+
+pub(crate) fn get_retention(app: &AppHandle) -> Result<DbDdlBackupRetention, String> {
+    Ok(read_store(app)?.retention)
+}
+
+/// Saves the retention settings and applies them to the existing backups right away.
+pub(crate) fn set_retention(
+    app: &AppHandle,
+    retention: DbDdlBackupRetention,
+) -> Result<DbDdlBackupRetention, String> {
+    if retention.max_per_object == 0 {
+        return Err("At least one backup per object must be kept".to_string());
+    }
+
+    update_store(app, |store| {
+        store.retention = retention;
+        remove_files(&prune(store, now_millis()));
+        Ok(store.retention.clone())
+    })
+}
+
+/// Drops backups older than the retention age and all but the newest `max_per_object`
+/// of each object. Returns the dropped entries so their files can be deleted.
+fn prune(store: &mut DdlBackupStore, now: u64) -> Vec<DbDdlBackup> {
+    let cutoff = store
+        .retention
+        .max_age_days
+        .map(|days| now.saturating_sub(u64::from(days) * MILLIS_PER_DAY));
+    let max_per_object = store.retention.max_per_object as usize;
+
+    let mut kept = Vec::with_capacity(store.backups.len());
+    let mut removed = Vec::new();
+    // Walk newest first so the per-object count keeps the most recent backups.
+    for backup in std::mem::take(&mut store.backups).into_iter().rev() {
+        let newer_of_same_object = kept
+            .iter()
+            .filter(|other: &&DbDdlBackup| is_same_object(other, &backup))
+            .count();
+        let expired = cutoff.is_some_and(|cutoff| backup.created_at < cutoff);
+        if expired || newer_of_same_object >= max_per_object {
+            removed.push(backup);
+        } else {
+            kept.push(backup);
+        }
+    }
+    kept.reverse();
+    store.backups = kept;
+    removed
+}
+
+fn is_same_object(left: &DbDdlBackup, right: &DbDdlBackup) -> bool {
+    left.schema.eq_ignore_ascii_case(right.schema.as_str())
+        && left
+            .object_type
+            .eq_ignore_ascii_case(right.object_type.as_str())
+        && left.object_name == right.object_name
+}
+
+fn remove_files(backups: &[DbDdlBackup]) {
+    for backup in backups {
+        let _ = fs::remove_file(&backup.file_path);
+    }
+}
+
+fn next_backup_id(store: &DdlBackupStore, seed: u64) -> String {
+    let mut candidate = format!("ddl-{seed}");
+    let mut suffix = 2;
+    while store.backups.iter().any(|backup| backup.id == candidate) {
+        candidate = format!("ddl-{seed}-{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+fn read_store(app: &AppHandle) -> Result<DdlBackupStore, String> {
+    let path = app_data_file_path(app, DDL_BACKUP_STORE_FILE)?;
+    let _guard = lock_store()?;
+    read_store_from_path(path.as_path())
+}
+
+fn update_store<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut DdlBackupStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = app_data_file_path(app, DDL_BACKUP_STORE_FILE)?;
+    let _guard = lock_store()?;
+    let mut store = read_store_from_path(path.as_path())?;
+    let value = f(&mut store)?;
+    write_store_to_path(path.as_path(), &store)?;
+    Ok(value)
+}
+
+fn lock_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    DDL_BACKUP_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire DDL backup store lock".to_string())
+}
+
+fn read_store_from_path(path: &Path) -> Result<DdlBackupStore, String> {
+    if !path.exists() {
+        return Ok(DdlBackupStore::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read DDL backup index: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(DdlBackupStore::default());
+    }
+
+    serde_json::from_str::<DdlBackupStore>(&content)
+        .map_err(|error| format!("Failed to parse DDL backup index: {error}"))
+}
+
+fn write_store_to_path(path: &Path, store: &DdlBackupStore) -> Result<(), String> {
+    let payload = serde_json::to_string_pretty(store)
+        .map_err(|error| format!("Failed to serialize DDL backup index: {error}"))?;
+    fs::write(path, payload).map_err(|error| format!("Failed to write DDL backup index: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{first_backup_after, prune, DdlBackupStore};
+    use crate::types::{DbDdlBackup, DbDdlBackupRetention, DbObjectDdlAsOfRequest};
+
+    fn backup(id: &str, object_name: &str, created_at: u64) -> DbDdlBackup {
+        DbDdlBackup {
+            id: id.to_string(),
+            profile_id: Some("profile-1".to_string()),
+            schema: "HR".to_string(),
+            object_type: "PACKAGE".to_string(),
+            object_name: object_name.to_string(),
+            file_path: format!("/tmp/{id}.sql"),
+            size_bytes: 10,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn prunes_by_count_per_object_and_age() {
+        let day = super::MILLIS_PER_DAY;
+        let mut store = DdlBackupStore {
+            retention: DbDdlBackupRetention {
+                max_per_object: 2,
+                max_age_days: Some(30),
+            },
+            backups: vec![
+                backup("a1", "PKG_A", 40 * day),
+                backup("a2", "PKG_A", 50 * day),
+                backup("b1", "PKG_B", 55 * day),
+                backup("a3", "PKG_A", 60 * day),
+                backup("old", "PKG_C", 5 * day),
+            ],
+        };
+
+        let removed = prune(&mut store, 60 * day);
+        let ids = |backups: &[DbDdlBackup]| {
+            backups
+                .iter()
+                .map(|backup| backup.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&store.backups), vec!["a2", "b1", "a3"]);
+        assert_eq!(ids(&removed), vec!["old", "a1"]);
+    }
+
+    #[test]
+    fn the_version_as_of_a_time_is_the_next_backup_taken() {
+        let backups = vec![
+            backup("a1", "PKG_A", 100),
+            backup("b1", "PKG_B", 150),
+            DbDdlBackup {
+                profile_id: Some("profile-2".to_string()),
+                ..backup("other", "PKG_A", 160)
+            },
+            backup("a2", "PKG_A", 200),
+            backup("a3", "PKG_A", 300),
+        ];
+        let request = |as_of| DbObjectDdlAsOfRequest {
+            session_id: 1,
+            schema: "hr".to_string(),
+            object_type: "package".to_string(),
+            object_name: "PKG_A".to_string(),
+            as_of,
+        };
+        let found = |as_of| {
+            first_backup_after(&backups, &request(as_of), Some("profile-1"))
+                .map(|backup| backup.id.as_str())
+        };
+        assert_eq!(found(50), Some("a1"));
+        assert_eq!(found(120), Some("a2"));
+        assert_eq!(found(200), Some("a3"));
+        assert_eq!(found(300), None);
+        assert_eq!(
+            first_backup_after(&backups, &request(120), Some("profile-2"))
+                .map(|backup| backup.id.as_str()),
+            Some("other")
+        );
+        assert!(first_backup_after(&backups, &request(50), None).is_none());
+    }
+}
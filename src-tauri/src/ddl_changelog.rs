@@ -0,0 +1,439 @@
+use crate::profiles::app_data_file_path;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_safety;
+use crate::types::{
+    DbConnectConnection, DbConnectRequest, DbDdlChange, DbDdlChangelogExport,
+    DbExportDdlChangelogRequest, DbGetDdlChangelogRequest, DbObjectRef, DbSqlStatement,
+};
+use crate::workspace::now_millis;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const DDL_CHANGELOG_STORE_FILE: &str = "ddl_changelog.json";
+/// Changes kept across all profiles; the oldest are dropped first.
+const MAX_CHANGES: usize = 20_000;
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+static DDL_CHANGELOG_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct DdlChangelogStore {
+    changes: Vec<DbDdlChange>,
+}
+
+/// A DDL statement about to run, with the hash of its object's DDL from before.
+pub(crate) struct PendingDdlChange {
+    action: String,
+    object: DbObjectRef,
+    sql: String,
+    line: u32,
+    before_hash: Option<String>,
+}
+
+impl PendingDdlChange {
+    /// An object editor save, which replaces `object` with `sql`.
+    pub(crate) fn edit(object: &DbObjectRef, sql: &str, before_ddl: Option<&str>) -> Self {
+        Self {
+            action: "REPLACE".to_string(),
+            object: object.clone(),
+            sql: sql.to_string(),
+            line: 1,
+            before_hash: before_ddl.map(hash_ddl),
+        }
+    }
+}
+
+/// The DDL statements among `statements`, each with the current DDL of its object, so
+/// call this before they run. Names without a schema resolve to the session's default.
+/// In a script every object is read before the first statement and after the last.
+pub(crate) fn pending_changes(
+    session: &AppSession,
+    session_id: u64,
+    statements: &[DbSqlStatement],
+) -> Vec<PendingDdlChange> {
+    let default_schema = session.connect_request().and_then(default_schema);
+    statements
+        .iter()
+        .filter_map(|statement| {
+            let target = sql_safety::ddl_target(&statement.sql)?;
+            let object = DbObjectRef {
+                session_id,
+                schema: target
+                    .schema
+                    .or_else(|| default_schema.clone())
+                    .unwrap_or_default(),
+                object_type: target.object_type,
+                object_name: target.object_name,
+            };
+            let before_hash = read_hash(session, &object);
+            Some(PendingDdlChange {
+                action: target.action,
+                object,
+                sql: statement.sql.trim().to_string(),
+                line: statement.line,
+                before_hash,
+            })
+        })
+        .collect()
+}
+
+/// Adds the changes that ran to the changelog of the session's profile. `outcome` gives,
+/// by line and SQL, `Some(error)` for a statement that ran (`error` is `None` if it
+/// succeeded) or `None` for one that never ran.
+pub(crate) fn record_changes(
+    app: &AppHandle,
+    session: &AppSession,
+    pending: Vec<PendingDdlChange>,
+    outcome: impl Fn(u32, &str) -> Option<Option<String>>,
+) -> Result<(), String> {
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let connect_request = session.connect_request();
+    let executed_at = now_millis();
+    let changes = pending
+        .into_iter()
+        .filter_map(|change| {
+            let error = outcome(change.line, &change.sql)?;
+            let after_hash = if error.is_none() && change.action != "DROP" {
+                read_hash(session, &change.object)
+            } else {
+                None
+            };
+            Some(DbDdlChange {
+                id: String::new(),
+                profile_id: connect_request.and_then(|request| request.profile_id.clone()),
+                environment: connect_request.and_then(|request| request.environment.clone()),
+                action: change.action,
+                schema: change.object.schema,
+                object_type: change.object.object_type,
+                object_name: change.object.object_name,
+                sql: change.sql,
+                before_hash: change.before_hash,
+                after_hash,
+                user: connect_request.and_then(session_user),
+                executed_at,
+                error,
+            })
+        })
+        .collect::<Vec<_>>();
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    update_store(app, |store| {
+        for (index, mut change) in changes.into_iter().enumerate() {
+            change.id = format!("change-{executed_at}-{index}");
+            store.changes.push(change);
+        }
+        let excess = store.changes.len().saturating_sub(MAX_CHANGES);
+        store.changes.drain(..excess);
+        Ok(())
+    })
+}
+
+/// Oldest first, optionally narrowed to one profile and a time range.
+pub(crate) fn get_changelog(
+    app: &AppHandle,
+    request: &DbGetDdlChangelogRequest,
+) -> Result<Vec<DbDdlChange>, String> {
+    let profile_id = request
+        .profile_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|profile_id| !profile_id.is_empty());
+    Ok(read_store(app)?
+        .changes
+        .into_iter()
+        .filter(|change| {
+            profile_id.is_none_or(|profile_id| change.profile_id.as_deref() == Some(profile_id))
+                && request
+                    .since
+                    .is_none_or(|since| change.executed_at >= since)
+                && request
+                    .until
+                    .is_none_or(|until| change.executed_at <= until)
+        })
+        .collect())
+}
+
+/// Writes the changelog as a Markdown report: a table of the changes, then each statement.
+pub(crate) fn export_changelog(
+    app: &AppHandle,
+    request: &DbExportDdlChangelogRequest,
+) -> Result<DbDdlChangelogExport, String> {
+    let file_path = request.file_path.trim();
+    if file_path.is_empty() {
+        return Err("Destination file is required".to_string());
+    }
+    let path = PathBuf::from(file_path);
+    let changes = get_changelog(app, &request.filter)?;
+    let title = request
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("DDL changelog");
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!("Failed to create directory '{}': {error}", parent.display())
+        })?;
+    }
+    fs::write(&path, changelog_markdown(title, &changes))
+        .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+    Ok(DbDdlChangelogExport {
+        file_path: path.to_string_lossy().into_owned(),
+        change_count: changes.len() as u32,
+        message: format!(
+            "Exported {} DDL change(s) to {}",
+            changes.len(),
+            path.display()
+        ),
+    })
+}
+
+fn changelog_markdown(title: &str, changes: &[DbDdlChange]) -> String {
+    let mut markdown = format!("# {title}\n\n");
+    let (Some(first), Some(last)) = (changes.first(), changes.last()) else {
+        markdown.push_str("No DDL changes were recorded.\n");
+        return markdown;
+    };
+    let _ = writeln!(
+        markdown,
+        "{} change(s) from {} to {}.\n",
+        changes.len(),
+        format_utc(first.executed_at),
+        format_utc(last.executed_at)
+    );
+    markdown.push_str("| # | When (UTC) | User | Action | Object | Before | After | Result |\n");
+    markdown.push_str("|---|---|---|---|---|---|---|---|\n");
+    for (index, change) in changes.iter().enumerate() {
+        let _ = writeln!(
+            markdown,
+            "| {} | {} | {} | {} | {} | {} | {} | {} |",
+            index + 1,
+            format_utc(change.executed_at),
+            table_cell(change.user.as_deref().unwrap_or("")),
+            change.action,
+            table_cell(&format!(
+                "{} {}",
+                change.object_type,
+                qualified_name(&change.schema, &change.object_name)
+            )),
+            short_hash(change.before_hash.as_deref()),
+            short_hash(change.after_hash.as_deref()),
+            table_cell(change.error.as_deref().unwrap_or("OK")),
+        );
+    }
+
+    markdown.push_str("\n## Statements\n");
+    for (index, change) in changes.iter().enumerate() {
+        let _ = write!(
+            markdown,
+            "\n### {}. {} {}\n\n```sql\n{}\n```\n",
+            index + 1,
+            change.action,
+            qualified_name(&change.schema, &change.object_name),
+            change.sql
+        );
+    }
+    markdown
+}
+
+fn qualified_name(schema: &str, object_name: &str) -> String {
+    if schema.is_empty() {
+        object_name.to_string()
+    } else {
+        format!("{schema}.{object_name}")
+    }
+}
+
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+fn short_hash(hash: Option<&str>) -> &str {
+    hash.map_or("-", |hash| &hash[..hash.len().min(12)])
+}
+
+/// `YYYY-MM-DD HH:MM` for epoch milliseconds.
+pub(crate) fn format_utc(millis: u64) -> String {
+    let days = (millis / MILLIS_PER_DAY) as i64;
+    let minutes = millis % MILLIS_PER_DAY / 60_000;
+    // Civil date from days since 1970-01-01, after Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+fn read_hash(session: &AppSession, object: &DbObjectRef) -> Option<String> {
+    if object.schema.is_empty() {
+        return None;
+    }
+    ProviderRegistry::get_object_ddl(session, object)
+        .ok()
+        .map(|ddl| hash_ddl(&ddl))
+}
+
+fn hash_ddl(ddl: &str) -> String {
+    digest(&SHA256, ddl.trim().as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The user the session runs as.
+fn session_user(request: &DbConnectRequest) -> Option<String> {
+    let user = match &request.connection {
+        DbConnectConnection::Oracle(options) => options
+            .proxy_user
+            .clone()
+            .filter(|proxy_user| !proxy_user.trim().is_empty())
+            .unwrap_or_else(|| options.username.clone()),
+        DbConnectConnection::Postgres(options)
+        | DbConnectConnection::Mysql(options)
+        | DbConnectConnection::Clickhouse(options) => options.username.clone(),
+        DbConnectConnection::Snowflake(options) => options.username.clone(),
+        _ => return None,
+    };
+    Some(user.trim().to_string()).filter(|user| !user.is_empty())
+}
+
+/// The schema an unqualified name in the session's SQL refers to.
+fn default_schema(request: &DbConnectRequest) -> Option<String> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    match &request.connection {
+        DbConnectConnection::Oracle(options) => {
+            let schema = options.schema.trim();
+            if schema.is_empty() {
+                session_user(request).map(|user| user.to_uppercase())
+            } else {
+                Some(schema.to_uppercase())
+            }
+        }
+        DbConnectConnection::Postgres(options) => {
+            Some(non_empty(&options.schema).unwrap_or_else(|| "public".to_string()))
+        }
+        DbConnectConnection::Mysql(options) | DbConnectConnection::Clickhouse(options) => {
+            non_empty(&options.schema).or_else(|| non_empty(&Some(options.database.clone())))
+        }
+        DbConnectConnection::Snowflake(options) => non_empty(&options.schema),
+        DbConnectConnection::Sqlite(_)
+        | DbConnectConnection::Duckdb(_)
+        | DbConnectConnection::Libsql(_) => Some("main".to_string()),
+        DbConnectConnection::External(_) => None,
+    }
+}
+
+fn read_store(app: &AppHandle) -> Result<DdlChangelogStore, String> {
+    let path = app_data_file_path(app, DDL_CHANGELOG_STORE_FILE)?;
+    let _guard = lock_store()?;
+    read_store_from_path(path.as_path())
+}
+
+fn update_store<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut DdlChangelogStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = app_data_file_path(app, DDL_CHANGELOG_STORE_FILE)?;
+    let _guard = lock_store()?;
+    let mut store = read_store_from_path(path.as_path())?;
+    let value = f(&mut store)?;
+    write_store_to_path(path.as_path(), &store)?;
+    Ok(value)
+}
+
+fn lock_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    DDL_CHANGELOG_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire DDL changelog lock".to_string())
+}
+
+fn read_store_from_path(path: &Path) -> Result<DdlChangelogStore, String> {
+    if !path.exists() {
+        return Ok(DdlChangelogStore::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read DDL changelog: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(DdlChangelogStore::default());
+    }
+
+    serde_json::from_str::<DdlChangelogStore>(&content)
+        .map_err(|error| format!("Failed to parse DDL changelog: {error}"))
+}
+
+fn write_store_to_path(path: &Path, store: &DdlChangelogStore) -> Result<(), String> {
+    let payload = serde_json::to_string_pretty(store)
+        .map_err(|error| format!("Failed to serialize DDL changelog: {error}"))?;
+    fs::write(path, payload).map_err(|error| format!("Failed to write DDL changelog: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{changelog_markdown, format_utc};
+    use crate::types::DbDdlChange;
+
+    #[test]
+    fn writes_a_markdown_report_of_the_changes() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00");
+        assert_eq!(format_utc(1_709_210_096_000), "2024-02-29 12:34");
+
+        let change = DbDdlChange {
+            id: "change-1".to_string(),
+            profile_id: Some("uat".to_string()),
+            environment: Some("uat".to_string()),
+            action: "CREATE".to_string(),
+            schema: "HR".to_string(),
+            object_type: "VIEW".to_string(),
+            object_name: "ACTIVE_EMP".to_string(),
+            sql: "CREATE OR REPLACE VIEW active_emp AS SELECT * FROM emp".to_string(),
+            before_hash: None,
+            after_hash: Some("0123456789abcdef0123".to_string()),
+            user: Some("deploy".to_string()),
+            executed_at: 1_709_210_096_000,
+            error: Some("ORA-00942: table | view does not exist".to_string()),
+        };
+        let markdown = changelog_markdown("UAT", &[change]);
+        assert!(markdown.starts_with("# UAT\n\n1 change(s) from 2024-02-29 12:34"));
+        assert!(markdown.contains(
+            "| 1 | 2024-02-29 12:34 | deploy | CREATE | VIEW HR.ACTIVE_EMP | - | 0123456789ab | ORA-00942: table \\| view does not exist |"
+        ));
+        assert!(markdown.contains("### 1. CREATE HR.ACTIVE_EMP\n\n```sql\nCREATE OR REPLACE"));
+        assert_eq!(
+            changelog_markdown("UAT", &[]),
+            "# UAT\n\nNo DDL changes were recorded.\n"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use crate::ldap_naming;
+use crate::oracle_wallets;
+use crate::portable;
+use crate::profiles;
+use crate::providers::ProviderRegistry;
+use crate::ssh_tunnel;
+use crate::tnsnames;
+use crate::types::{
+    DatabaseProvider, DbConnectionProfile, DbDiagnosticCheck, DbDiagnosticStatus,
+    DbDiagnosticsReport, DbRunDiagnosticsRequest, StoredConnectionProfile,
+};
+use crate::workspace::now_millis;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const TCP_CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const DEFAULT_HTTPS_PORT: u16 = 443;
+const DEFAULT_HTTP_PORT: u16 = 80;
+const DEFAULT_SSH_PORT: u16 = 22;
+const WRITE_PROBE_FILE: &str = ".diagnostics-probe";
+
+pub(crate) async fn run_diagnostics(
+    request: DbRunDiagnosticsRequest,
+    app: AppHandle,
+) -> Result<DbDiagnosticsReport, String> {
+    tauri::async_runtime::spawn_blocking(move || run_diagnostics_blocking(&request, &app))
+        .await
+        .map_err(|error| format!("Diagnostics task failed: {error}"))
+}
+
+pub(crate) fn run_diagnostics_blocking(
+    request: &DbRunDiagnosticsRequest,
+    app: &AppHandle,
+) -> DbDiagnosticsReport {
+    let secret_store_label = if portable::is_enabled() {
+        "Portable secret store"
+    } else {
+        "OS keychain"
+    };
+    let mut checks = vec![
+        timed_check("keyring", secret_store_label, || {
+            profiles::probe_keyring().map(|()| "Secrets can be stored and read back.".to_string())
+        }),
+        timed_check("oracleClient", "Oracle client libraries", || {
+            ProviderRegistry::oracle_client_version(request.oracle_client_lib_dir.as_deref())
+                .map(|version| format!("Oracle Client {version} is loaded."))
+        }),
+        timed_check("appData", "App data directory", || check_app_data(app)),
+    ];
+
+    match profiles::read_profiles(app) {
+        Ok(stored_profiles) if request.skip_network => {
+            checks.extend(stored_profiles.iter().map(|profile| DbDiagnosticCheck {
+                id: format!("profile:{}", profile.id),
+                label: profile.name.clone(),
+                status: DbDiagnosticStatus::Skipped,
+                detail: "Network checks were skipped.".to_string(),
+                duration_ms: 0,
+            }));
+        }
+        Ok(stored_profiles) => {
+            let stored_profiles = stored_profiles
+                .into_iter()
+                .map(|profile| with_wallet_target(app, profile))
+                .collect::<Vec<_>>();
+            // Unreachable hosts take the full timeout, so probe the profiles concurrently.
+            let profile_checks = std::thread::scope(|scope| {
+                let handles = stored_profiles
+                    .iter()
+                    .map(|profile| scope.spawn(move || check_profile(profile)))
+                    .collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .zip(&stored_profiles)
+                    .map(|(handle, profile)| {
+                        handle.join().unwrap_or_else(|_| DbDiagnosticCheck {
+                            id: format!("profile:{}", profile.id),
+                            label: profile.name.clone(),
+                            status: DbDiagnosticStatus::Failed,
+                            detail: "The reachability check panicked.".to_string(),
+                            duration_ms: 0,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+            checks.extend(profile_checks);
+        }
+        Err(error) => checks.push(DbDiagnosticCheck {
+            id: "profiles".to_string(),
+            label: "Connection profiles".to_string(),
+            status: DbDiagnosticStatus::Failed,
+            detail: error,
+            duration_ms: 0,
+        }),
+    }
+
+    let package = app.package_info();
+    DbDiagnosticsReport {
+        app_version: package.version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        generated_at: now_millis(),
+        portable_data_dir: portable::data_dir().map(|directory| directory.display().to_string()),
+        checks,
+    }
+}
+
+fn timed_check(
+    id: &str,
+    label: &str,
+    check: impl FnOnce() -> Result<String, String>,
+) -> DbDiagnosticCheck {
+    let started = Instant::now();
+    let (status, detail) = match check() {
+        Ok(detail) => (DbDiagnosticStatus::Ok, detail),
+        Err(error) => (DbDiagnosticStatus::Failed, error),
+    };
+    DbDiagnosticCheck {
+        id: id.to_string(),
+        label: label.to_string(),
+        status,
+        detail,
+        duration_ms: elapsed_millis(started),
+    }
+}
+
+fn check_app_data(app: &AppHandle) -> Result<String, String> {
+    let path = profiles::app_data_file_path(app, WRITE_PROBE_FILE)?;
+    fs::write(&path, b"ok")
+        .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+    let _ = fs::remove_file(&path);
+    let directory = path.parent().unwrap_or(Path::new(""));
+    Ok(format!("{} is writable.", directory.display()))
+}
+
+/// Wallet, TNS alias, LDAP and raw descriptor profiles keep their host outside the host
+/// field.
+fn with_wallet_target(
+    app: &AppHandle,
+    mut profile: StoredConnectionProfile,
+) -> StoredConnectionProfile {
+    if let DbConnectionProfile::Oracle(connection) = &mut profile.connection {
+        if let Some((host, port)) = oracle_wallets::profile_target(app, connection)
+            .or_else(|| tnsnames::profile_target(connection))
+            .or_else(|| ldap_naming::profile_target(connection))
+            .or_else(|| tnsnames::descriptor_target(connection))
+            .or_else(|| tnsnames::password_store_target(connection))
+        {
+            connection.host = host;
+            connection.port = Some(port);
+        }
+    }
+    profile
+}
+
+/// Opens (and immediately closes) a TCP connection to the profile's server, or to its SSH
+/// bastion when it has one, since the server itself is only reachable through the tunnel.
+fn check_profile(profile: &StoredConnectionProfile) -> DbDiagnosticCheck {
+    let started = Instant::now();
+    let (status, detail) = match profile_endpoint(profile) {
+        Some((host, port, via)) => match tcp_connect(host.as_str(), port) {
+            Ok(address) => (
+                DbDiagnosticStatus::Ok,
+                format!("{via}{host}:{port} accepted a connection ({address})."),
+            ),
+            Err(error) => (
+                DbDiagnosticStatus::Failed,
+                format!("{via}{host}:{port}: {error}"),
+            ),
+        },
+        None => (
+            DbDiagnosticStatus::Skipped,
+            "The provider does not connect to a network host.".to_string(),
+        ),
+    };
+
+    DbDiagnosticCheck {
+        id: format!("profile:{}", profile.id),
+        label: profile.name.clone(),
+        status,
+        detail,
+        duration_ms: elapsed_millis(started),
+    }
+}
+
+/// Host, port and a label prefix describing what is probed.
+fn profile_endpoint(profile: &StoredConnectionProfile) -> Option<(String, u16, &'static str)> {
+    if let Some(tunnel) = &profile.ssh_tunnel {
+        return Some((
+            tunnel.host.trim().to_string(),
+            tunnel.port.unwrap_or(DEFAULT_SSH_PORT),
+            "SSH bastion ",
+        ));
+    }
+
+    let target = match &profile.connection {
+        DbConnectionProfile::Oracle(connection) => ssh_tunnel::network_target(
+            DatabaseProvider::Oracle,
+            connection.host.as_str(),
+            Some(
+                connection
+                    .port
+                    .unwrap_or(connection.protocol.default_port()),
+            ),
+        ),
+        DbConnectionProfile::Postgres(connection) => ssh_tunnel::network_target(
+            DatabaseProvider::Postgres,
+            connection.host.as_str(),
+            connection.port,
+        ),
+        DbConnectionProfile::Mysql(connection) => ssh_tunnel::network_target(
+            DatabaseProvider::Mysql,
+            connection.host.as_str(),
+            connection.port,
+        ),
+        DbConnectionProfile::Clickhouse(connection) => ssh_tunnel::network_target(
+            DatabaseProvider::Clickhouse,
+            connection.host.as_str(),
+            connection.port,
+        ),
+        connection => ProviderRegistry::http_base_url(connection)
+            .and_then(|base_url| url_host_port(base_url.as_str())),
+    };
+    target.map(|(host, port)| (host, port, ""))
+}
+
+fn url_host_port(url: &str) -> Option<(String, u16)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let default_port = if scheme.eq_ignore_ascii_case("http") {
+        DEFAULT_HTTP_PORT
+    } else {
+        DEFAULT_HTTPS_PORT
+    };
+    match authority.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => {
+            Some((host.to_string(), port.parse().ok()?))
+        }
+        _ => Some((authority.to_string(), default_port)),
+    }
+}
+
+fn tcp_connect(host: &str, port: u16) -> Result<String, String> {
+    if host.is_empty() {
+        return Err("no host is configured".to_string());
+    }
+    let addresses = (host, port)
+        .to_socket_addrs()
+        .map_err(|error| format!("could not resolve host: {error}"))?
+        .collect::<Vec<_>>();
+
+    let mut last_error = "no addresses found".to_string();
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, TCP_CONNECT_TIMEOUT) {
+            Ok(_) => return Ok(address.ip().to_string()),
+            Err(error) => last_error = error.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+fn elapsed_millis(started: Instant) -> u64 {
+    started.elapsed().as_millis().min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{profile_endpoint, url_host_port};
+    use crate::types::{
+        DbConnectionProfile, LibsqlConnectionOptions, NetworkConnectionOptions,
+        SqliteConnectionOptions, SshTunnelOptions, StoredConnectionProfile,
+    };
+
+    fn profile(connection: DbConnectionProfile) -> StoredConnectionProfile {
+        StoredConnectionProfile {
+            id: "profile-1".to_string(),
+            name: "Test".to_string(),
+            connection,
+            ssh_tunnel: None,
+            read_only: false,
+            environment: None,
+            startup_sql: None,
+            warm_standby: false,
+            drop_allowlist: Vec::new(),
+            reader_endpoint: None,
+        }
+    }
+
+    #[test]
+    fn resolves_profile_endpoints() {
+        let mut mysql = profile(DbConnectionProfile::Mysql(NetworkConnectionOptions {
+            host: " db.internal ".to_string(),
+            port: None,
+            database: "app".to_string(),
+            username: "app".to_string(),
+            schema: None,
+        }));
+        assert_eq!(
+            profile_endpoint(&mysql),
+            Some(("db.internal".to_string(), 3306, ""))
+        );
+
+        mysql.ssh_tunnel = Some(SshTunnelOptions {
+            host: "bastion.example.com".to_string(),
+            port: None,
+            username: "ops".to_string(),
+            identity_file: None,
+            cli_path: None,
+        });
+        assert_eq!(
+            profile_endpoint(&mysql),
+            Some(("bastion.example.com".to_string(), 22, "SSH bastion "))
+        );
+
+        let libsql = profile(DbConnectionProfile::Libsql(LibsqlConnectionOptions {
+            url: "libsql://db-org.turso.io".to_string(),
+        }));
+        assert_eq!(
+            profile_endpoint(&libsql),
+            Some(("db-org.turso.io".to_string(), 443, ""))
+        );
+
+        let sqlite = profile(DbConnectionProfile::Sqlite(SqliteConnectionOptions {
+            file_path: "app.db".to_string(),
+        }));
+        assert_eq!(profile_endpoint(&sqlite), None);
+
+        assert_eq!(
+            url_host_port("http://127.0.0.1:8080/v2"),
+            Some(("127.0.0.1".to_string(), 8080))
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use crate::menu::EVENT_SESSION_ERRORS;
+use crate::state::AppState;
+use crate::types::{DbErrorSummary, DbErrorSummaryEntry, DbSessionErrors};
+use crate::workspace::now_millis;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How long an error must stop repeating before its burst is reported.
+const QUIET_PERIOD: Duration = Duration::from_secs(1);
+const TICK: Duration = Duration::from_millis(250);
+/// Distinct messages kept per session; the oldest is dropped first.
+const MAX_DISTINCT_ERRORS: usize = 50;
+
+/// Provider errors by session. Identical errors in quick succession, such as every node of
+/// the explorer failing on a dead connection, are reported as one event with a count.
+#[derive(Default)]
+pub(crate) struct ErrorAggregator {
+    sessions: Mutex<HashMap<u64, Vec<ErrorGroup>>>,
+}
+
+struct ErrorGroup {
+    message: String,
+    count: u64,
+    first_at: u64,
+    last_at: u64,
+    last_seen: Instant,
+    /// Occurrences since the last event.
+    unreported: u64,
+}
+
+impl ErrorAggregator {
+    pub(crate) fn record(&self, session_id: u64, message: &str) {
+        let Ok(mut sessions) = self.sessions.lock() else {
+            return;
+        };
+        let groups = sessions.entry(session_id).or_default();
+        let now = now_millis();
+        match groups.iter().position(|group| group.message == message) {
+            Some(index) => {
+                // Keep the most recent error last.
+                let mut group = groups.remove(index);
+                group.count += 1;
+                group.unreported += 1;
+                group.last_at = now;
+                group.last_seen = Instant::now();
+                groups.push(group);
+            }
+            None => {
+                if groups.len() >= MAX_DISTINCT_ERRORS {
+                    groups.remove(0);
+                }
+                groups.push(ErrorGroup {
+                    message: message.to_string(),
+                    count: 1,
+                    first_at: now,
+                    last_at: now,
+                    last_seen: Instant::now(),
+                    unreported: 1,
+                });
+            }
+        }
+    }
+
+    /// Bursts that have not repeated for `quiet`, one entry per distinct error.
+    fn take_settled(&self, quiet: Duration) -> Vec<DbSessionErrors> {
+        let Ok(mut sessions) = self.sessions.lock() else {
+            return Vec::new();
+        };
+        sessions
+            .iter_mut()
+            .filter_map(|(session_id, groups)| {
+                let errors = groups
+                    .iter_mut()
+                    .filter(|group| group.unreported > 0 && group.last_seen.elapsed() >= quiet)
+                    .map(|group| {
+                        let entry = DbErrorSummaryEntry {
+                            message: group.message.clone(),
+                            count: group.unreported,
+                            first_at: group.first_at,
+                            last_at: group.last_at,
+                        };
+                        group.unreported = 0;
+                        entry
+                    })
+                    .collect::<Vec<_>>();
+                (!errors.is_empty()).then_some(DbSessionErrors {
+                    session_id: *session_id,
+                    errors,
+                })
+            })
+            .collect()
+    }
+
+    /// Every distinct error the session has hit, most recent first.
+    pub(crate) fn summary(&self, session_id: u64) -> Result<DbErrorSummary, String> {
+        let sessions = self
+            .sessions
+            .lock()
+            .map_err(|_| "Failed to acquire error summary lock".to_string())?;
+        let errors = sessions
+            .get(&session_id)
+            .map(|groups| {
+                groups
+                    .iter()
+                    .rev()
+                    .map(|group| DbErrorSummaryEntry {
+                        message: group.message.clone(),
+                        count: group.count,
+                        first_at: group.first_at,
+                        last_at: group.last_at,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        Ok(DbErrorSummary {
+            session_id,
+            total_errors: errors.iter().map(|error| error.count).sum(),
+            errors,
+        })
+    }
+
+    pub(crate) fn remove(&self, session_id: u64) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.remove(&session_id);
+        }
+    }
+}
+
+/// Starts the background task that reports each burst of errors once it has settled.
+pub(crate) fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        for notification in state.errors.take_settled(QUIET_PERIOD) {
+            let _ = app.emit(EVENT_SESSION_ERRORS, notification);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorAggregator;
+    use std::time::Duration;
+
+    #[test]
+    fn coalesces_repeated_errors_into_one_report() {
+        let errors = ErrorAggregator::default();
+        for _ in 0..3 {
+            errors.record(1, "ORA-03113: end-of-file on communication channel");
+        }
+        errors.record(1, "ORA-00942: table or view does not exist");
+
+        let reports = errors.take_settled(Duration::ZERO);
+        assert_eq!(reports.len(), 1);
+        let counts = reports[0]
+            .errors
+            .iter()
+            .map(|error| error.count)
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![3, 1]);
+        assert!(errors.take_settled(Duration::ZERO).is_empty());
+
+        errors.record(1, "ORA-03113: end-of-file on communication channel");
+        let summary = errors.summary(1).unwrap();
+        assert_eq!(summary.total_errors, 5);
+        assert_eq!(summary.errors[0].count, 4);
+    }
+}
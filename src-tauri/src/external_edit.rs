@@ -0,0 +1,280 @@
+use crate::files::sanitize_export_file_stem;
+use crate::menu::EVENT_EXTERNAL_EDIT_SAVED;
+use crate::types::{DbEditObjectExternallyRequest, DbExternalEdit, DbExternalEditSaved};
+use crate::workspace::now_millis;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+const EDIT_DIRECTORY: &str = "clarity-edits";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// Replaced by the file path in a configured editor command; appended when absent.
+const FILE_PLACEHOLDER: &str = "{file}";
+
+/// Objects open in an external editor. Each has a watcher thread that runs until the edit
+/// is stopped or its session disconnects.
+#[derive(Default)]
+pub(crate) struct ExternalEditRegistry {
+    next_edit_id: AtomicU64,
+    entries: Mutex<HashMap<u64, ActiveEdit>>,
+}
+
+struct ActiveEdit {
+    edit: DbExternalEdit,
+    stop: Arc<AtomicBool>,
+}
+
+impl ExternalEditRegistry {
+    pub(crate) fn stop(&self, edit_id: u64) -> Result<(), String> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Failed to acquire external edit lock".to_string())?;
+        let active = entries
+            .remove(&edit_id)
+            .ok_or_else(|| "External edit not found".to_string())?;
+        active.stop.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn stop_session(&self, session_id: u64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|_, active| {
+                let keep = active.edit.session_id != session_id;
+                if !keep {
+                    active.stop.store(true, Ordering::Relaxed);
+                }
+                keep
+            });
+        }
+    }
+
+    fn find(&self, request: &DbEditObjectExternallyRequest) -> Option<DbExternalEdit> {
+        let entries = self.entries.lock().ok()?;
+        entries
+            .values()
+            .map(|active| &active.edit)
+            .find(|edit| {
+                edit.session_id == request.session_id
+                    && edit.schema == request.schema
+                    && edit.object_type == request.object_type
+                    && edit.object_name == request.object_name
+            })
+            .cloned()
+    }
+}
+
+/// Writes `ddl` to a temp file, opens it in the editor and starts watching it. Each save
+/// that changes the content emits `EVENT_EXTERNAL_EDIT_SAVED`; compiling it is left to the
+/// caller. Reopening an object that is already being edited reuses its file.
+pub(crate) fn edit_object_externally(
+    app: AppHandle,
+    registry: Arc<ExternalEditRegistry>,
+    request: &DbEditObjectExternallyRequest,
+    ddl: &str,
+) -> Result<DbExternalEdit, String> {
+    let editor_command = request
+        .editor_command
+        .as_deref()
+        .map(str::trim)
+        .filter(|command| !command.is_empty());
+
+    if let Some(edit) = registry.find(request) {
+        open_in_editor(editor_command, Path::new(&edit.file_path))?;
+        return Ok(edit);
+    }
+
+    let edit_id = registry.next_edit_id.fetch_add(1, Ordering::Relaxed) + 1;
+    let directory = std::env::temp_dir().join(EDIT_DIRECTORY);
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("Failed to create temp directory: {error}"))?;
+    let file_path = directory.join(format!(
+        "{edit_id}-{}.{}.sql",
+        sanitize_export_file_stem(request.schema.as_str()),
+        sanitize_export_file_stem(request.object_name.as_str())
+    ));
+    fs::write(&file_path, ddl)
+        .map_err(|error| format!("Failed to write {}: {error}", file_path.display()))?;
+
+    if let Err(error) = open_in_editor(editor_command, &file_path) {
+        let _ = fs::remove_file(&file_path);
+        return Err(error);
+    }
+
+    let edit = DbExternalEdit {
+        edit_id,
+        session_id: request.session_id,
+        schema: request.schema.clone(),
+        object_type: request.object_type.clone(),
+        object_name: request.object_name.clone(),
+        file_path: file_path.to_string_lossy().to_string(),
+        started_at: now_millis(),
+    };
+    let stop = Arc::new(AtomicBool::new(false));
+    registry
+        .entries
+        .lock()
+        .map_err(|_| "Failed to acquire external edit lock".to_string())?
+        .insert(
+            edit_id,
+            ActiveEdit {
+                edit: edit.clone(),
+                stop: Arc::clone(&stop),
+            },
+        );
+
+    let watched = edit.clone();
+    let content = ddl.to_string();
+    std::thread::spawn(move || watch_file(app, watched, file_path, content, stop));
+    Ok(edit)
+}
+
+/// Polls the file's modification time; editors that save by replacing the file are
+/// handled the same way as in-place writes. The file is removed once the edit stops.
+fn watch_file(
+    app: AppHandle,
+    edit: DbExternalEdit,
+    path: PathBuf,
+    mut last_content: String,
+    stop: Arc<AtomicBool>,
+) {
+    let mut last_modified = modified_at(&path);
+    while !stop.load(Ordering::Relaxed) {
+        std::thread::sleep(POLL_INTERVAL);
+        let modified = modified_at(&path);
+        if modified.is_none() || modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        // A save can be caught half-written; the next poll picks up the rest.
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if content == last_content {
+            continue;
+        }
+        last_content = content.clone();
+        let _ = app.emit(
+            EVENT_EXTERNAL_EDIT_SAVED,
+            DbExternalEditSaved {
+                edit: edit.clone(),
+                ddl: content,
+            },
+        );
+    }
+
+    let _ = fs::remove_file(&path);
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Starts the configured editor without waiting for it, or the OS default application for
+/// `.sql` files when none is configured. Terminal editors need a command that opens a
+/// terminal, such as `alacritty -e nvim`.
+fn open_in_editor(editor_command: Option<&str>, path: &Path) -> Result<(), String> {
+    let Some(editor_command) = editor_command else {
+        return tauri_plugin_opener::open_path(path, None::<&str>)
+            .map_err(|error| format!("Failed to open {}: {error}", path.display()));
+    };
+
+    let (program, args) = editor_invocation(editor_command, path.to_string_lossy().as_ref())?;
+    let mut child = Command::new(&program)
+        .args(&args)
+        .spawn()
+        .map_err(|error| format!("Failed to start editor {program}: {error}"))?;
+    // Reap the process when it exits; `code --wait` and similar stay alive until closed.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+/// Splits an editor command line into the program and its arguments, honoring double
+/// quotes so paths with spaces survive, and substitutes the file path.
+fn editor_invocation(
+    editor_command: &str,
+    file_path: &str,
+) -> Result<(String, Vec<String>), String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut in_word = false;
+    for ch in editor_command.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_word = true;
+            }
+            ch if ch.is_whitespace() && !in_quotes => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                in_word = true;
+            }
+        }
+    }
+    if in_quotes {
+        return Err("Editor command has an unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+
+    let mut words = words.into_iter();
+    let program = words
+        .next()
+        .ok_or_else(|| "Editor command is empty".to_string())?;
+    let mut args = words
+        .map(|word| word.replace(FILE_PLACEHOLDER, file_path))
+        .collect::<Vec<_>>();
+    if !editor_command.contains(FILE_PLACEHOLDER) {
+        args.push(file_path.to_string());
+    }
+    Ok((program, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::editor_invocation;
+
+    #[test]
+    fn splits_editor_commands() {
+        assert_eq!(
+            editor_invocation("code --wait", "/tmp/a b.sql").expect("command"),
+            (
+                "code".to_string(),
+                vec!["--wait".to_string(), "/tmp/a b.sql".to_string()]
+            )
+        );
+        assert_eq!(
+            editor_invocation(
+                r#""C:\Program Files\Notepad++\notepad++.exe" -multiInst "{file}""#,
+                r"C:\Temp\x.sql"
+            )
+            .expect("command"),
+            (
+                r"C:\Program Files\Notepad++\notepad++.exe".to_string(),
+                vec!["-multiInst".to_string(), r"C:\Temp\x.sql".to_string()]
+            )
+        );
+        assert_eq!(
+            editor_invocation("gvim --remote-tab-silent {file}", "/tmp/x.sql")
+                .expect("command")
+                .1,
+            vec!["--remote-tab-silent", "/tmp/x.sql"]
+        );
+        assert!(editor_invocation("\"vim", "/tmp/x.sql").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_ident;
+use crate::types::{
+    DatabaseProvider, DbGenerateGrantsRequest, DbGrantAccess, DbGrantObject, DbGrantScript,
+};
+use std::collections::HashSet;
+
+/// Builds the `GRANT` (and optional `CREATE SYNONYM`) statements that give `grantee` the
+/// requested access to the listed objects and to the session's objects matching the
+/// patterns. The script is only generated here; running it is a separate, reviewed step.
+pub(crate) fn generate_grants(
+    session: &AppSession,
+    request: &DbGenerateGrantsRequest,
+) -> Result<DbGrantScript, String> {
+    if session.provider != DatabaseProvider::Oracle {
+        return Err("Grant scripts are only available for Oracle".to_string());
+    }
+    let grantee =
+        sql_ident::parse_identifier(DatabaseProvider::Oracle, &request.grantee, "Grantee")?;
+    if request.access.is_empty() {
+        return Err("Choose at least one kind of access to grant".to_string());
+    }
+    let synonym_schema = match request.synonym_schema.as_deref().map(str::trim) {
+        Some(schema) if !schema.is_empty() => Some(sql_ident::parse_identifier(
+            DatabaseProvider::Oracle,
+            schema,
+            "Synonym schema",
+        )?),
+        _ => None,
+    };
+
+    let mut objects = request
+        .objects
+        .iter()
+        .map(|object| DbGrantObject {
+            schema: sql_ident::stored_name(DatabaseProvider::Oracle, &object.schema),
+            object_type: object.object_type.trim().to_uppercase(),
+            object_name: sql_ident::stored_name(DatabaseProvider::Oracle, &object.object_name),
+        })
+        .collect::<Vec<_>>();
+    let patterns = request
+        .patterns
+        .iter()
+        .map(|pattern| pattern.trim())
+        .filter(|pattern| !pattern.is_empty())
+        .collect::<Vec<_>>();
+    if !patterns.is_empty() {
+        objects.extend(
+            ProviderRegistry::list_objects(session)?
+                .into_iter()
+                .filter(|object| {
+                    patterns
+                        .iter()
+                        .any(|pattern| sql_ident::matches_pattern(pattern, &object.object_name))
+                })
+                .map(|object| DbGrantObject {
+                    schema: object.schema,
+                    object_type: object.object_type,
+                    object_name: object.object_name,
+                }),
+        );
+    }
+    if objects.is_empty() {
+        return Err("No objects matched".to_string());
+    }
+
+    Ok(grant_script(
+        &grantee,
+        &request.access,
+        &objects,
+        synonym_schema.as_deref(),
+    ))
+}
+
+fn grant_script(
+    grantee: &str,
+    access: &[DbGrantAccess],
+    objects: &[DbGrantObject],
+    synonym_schema: Option<&str>,
+) -> DbGrantScript {
+    let provider = DatabaseProvider::Oracle;
+    let grantee = sql_ident::format_identifier(provider, grantee);
+    let mut seen = HashSet::new();
+    let mut grants = Vec::new();
+    let mut synonyms = Vec::new();
+    let mut skipped = Vec::new();
+    for object in objects {
+        let privileges = privileges_for(&object.object_type, access);
+        if privileges.is_empty() {
+            let object = format!(
+                "{}.{} ({})",
+                object.schema, object.object_name, object.object_type
+            );
+            if !skipped.contains(&object) {
+                skipped.push(object);
+            }
+            continue;
+        }
+        // A GRANT names the object without its type, so e.g. a PACKAGE BODY listed before its
+        // PACKAGE must not stand in for it.
+        if !seen.insert((object.schema.as_str(), object.object_name.as_str())) {
+            continue;
+        }
+        let target = sql_ident::qualified_name(provider, Some(&object.schema), &object.object_name);
+        grants.push(format!(
+            "GRANT {} ON {target} TO {grantee}",
+            privileges.join(", ")
+        ));
+        if let Some(schema) = synonym_schema.filter(|schema| *schema != object.schema) {
+            synonyms.push(format!(
+                "CREATE OR REPLACE SYNONYM {} FOR {target}",
+                sql_ident::qualified_name(provider, Some(schema), &object.object_name)
+            ));
+        }
+    }
+
+    let script = grants
+        .iter()
+        .chain(&synonyms)
+        .map(|statement| format!("{statement};\n"))
+        .collect::<String>();
+    DbGrantScript {
+        script,
+        grant_count: grants.len(),
+        synonym_count: synonyms.len(),
+        skipped,
+    }
+}
+
+fn privileges_for(object_type: &str, access: &[DbGrantAccess]) -> Vec<&'static str> {
+    let mut privileges = Vec::new();
+    let table = object_type == "TABLE";
+    let readable = table || matches!(object_type, "VIEW" | "MATERIALIZED VIEW" | "SEQUENCE");
+    let executable = matches!(object_type, "PROCEDURE" | "FUNCTION" | "PACKAGE" | "TYPE");
+    if access.contains(&DbGrantAccess::Read) && readable {
+        privileges.push("SELECT");
+    }
+    if access.contains(&DbGrantAccess::Write) && table {
+        privileges.extend(["INSERT", "UPDATE", "DELETE"]);
+    }
+    if access.contains(&DbGrantAccess::Execute) && executable {
+        privileges.push("EXECUTE");
+    }
+    privileges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_type: &str, object_name: &str) -> DbGrantObject {
+        DbGrantObject {
+            schema: "SALES".to_string(),
+            object_type: object_type.to_string(),
+            object_name: object_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn grants_the_privileges_each_object_type_supports() {
+        let objects = [
+            object("TABLE", "ORDERS"),
+            object("VIEW", "V_REVENUE"),
+            object("PACKAGE", "BILLING"),
+            object("TRIGGER", "ORDERS_BI"),
+            object("TABLE", "ORDERS"),
+        ];
+        let script = grant_script(
+            "ANALYST_ROLE",
+            &[DbGrantAccess::Read, DbGrantAccess::Execute],
+            &objects,
+            Some("JDOE"),
+        );
+
+        assert_eq!(
+            script.script,
+            "GRANT SELECT ON SALES.ORDERS TO ANALYST_ROLE;\n\
+             GRANT SELECT ON SALES.V_REVENUE TO ANALYST_ROLE;\n\
+             GRANT EXECUTE ON SALES.BILLING TO ANALYST_ROLE;\n\
+             CREATE OR REPLACE SYNONYM JDOE.ORDERS FOR SALES.ORDERS;\n\
+             CREATE OR REPLACE SYNONYM JDOE.V_REVENUE FOR SALES.V_REVENUE;\n\
+             CREATE OR REPLACE SYNONYM JDOE.BILLING FOR SALES.BILLING;\n"
+        );
+        assert_eq!(script.grant_count, 3);
+        assert_eq!(script.synonym_count, 3);
+        assert_eq!(
+            script.skipped,
+            vec!["SALES.ORDERS_BI (TRIGGER)".to_string()]
+        );
+    }
+
+    #[test]
+    fn a_package_body_listed_first_does_not_hide_its_package() {
+        let objects = [
+            object("PACKAGE BODY", "BILLING"),
+            object("PACKAGE", "BILLING"),
+            object("PACKAGE BODY", "BILLING"),
+        ];
+        let script = grant_script("ANALYST_ROLE", &[DbGrantAccess::Execute], &objects, None);
+
+        assert_eq!(
+            script.script,
+            "GRANT EXECUTE ON SALES.BILLING TO ANALYST_ROLE;\n"
+        );
+        assert_eq!(script.grant_count, 1);
+        assert_eq!(script.skipped, vec!["SALES.BILLING (PACKAGE BODY)"]);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::menu::EVENT_SESSION_IDLE_DISCONNECTED;
+use crate::session_timeline;
+use crate::state::AppState;
+use crate::types::{DbSessionIdleDisconnected, DbSessionTimelineEventKind};
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+pub(crate) const MAX_IDLE_TIMEOUT_MINUTES: u32 = 24 * 60;
+const TICK: Duration = Duration::from_secs(30);
+
+/// Starts the background task that disconnects sessions left unused for longer than the
+/// idle timeout. Keep-alive pings do not count as use.
+pub(crate) fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let state = app.state::<AppState>();
+        let timeout_secs = state.idle_timeout_secs.load(Ordering::Relaxed);
+        if timeout_secs == 0 {
+            continue;
+        }
+        for session_id in state
+            .sessions
+            .idle_session_ids(Duration::from_secs(timeout_secs))
+        {
+            if !matches!(state.close_session(session_id), Ok(true)) {
+                continue;
+            }
+            let idle_minutes = timeout_secs / 60;
+            state.session_timeline.record(
+                session_id,
+                session_timeline::event(
+                    DbSessionTimelineEventKind::Disconnect,
+                    format!("Disconnected after {idle_minutes} minute(s) without use"),
+                ),
+            );
+            let _ = app.emit(
+                EVENT_SESSION_IDLE_DISCONNECTED,
+                DbSessionIdleDisconnected {
+                    session_id,
+                    idle_minutes,
+                    message: format!("Disconnected after {idle_minutes} minute(s) without use."),
+                },
+            );
+        }
+    });
+}
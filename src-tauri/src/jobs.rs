@@ -0,0 +1,159 @@
+use crate::cancellation::CancelToken;
+use crate::menu::EVENT_JOB_PROGRESS;
+use crate::types::{DbJobKind, DbJobState, DbJobStatus};
+use crate::workspace::now_millis;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+/// Tracks background jobs for the lifetime of the app. Job records are kept in memory
+/// only; anything that must survive a restart (like export checkpoints) lives on disk.
+pub(crate) struct JobRegistry {
+    next_job_id: AtomicU64,
+    jobs: Mutex<HashMap<u64, DbJobStatus>>,
+    /// Tokens of the jobs still running.
+    tokens: Mutex<HashMap<u64, CancelToken>>,
+}
+
+impl Default for JobRegistry {
+    fn default() -> Self {
+        Self {
+            next_job_id: AtomicU64::new(1),
+            jobs: Mutex::new(HashMap::new()),
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl JobRegistry {
+    pub(crate) fn list(&self) -> Result<Vec<DbJobStatus>, String> {
+        let jobs = self
+            .jobs
+            .lock()
+            .map_err(|_| "Failed to acquire job lock".to_string())?;
+        let mut statuses = jobs.values().cloned().collect::<Vec<_>>();
+        statuses.sort_by_key(|status| std::cmp::Reverse(status.job_id));
+        Ok(statuses)
+    }
+
+    /// Asks a running job to stop at its next check. Returns false if the job has already
+    /// finished.
+    pub(crate) fn cancel(&self, job_id: u64) -> Result<bool, String> {
+        let tokens = self
+            .tokens
+            .lock()
+            .map_err(|_| "Failed to acquire job lock".to_string())?;
+        match tokens.get(&job_id) {
+            Some(token) => token.cancel().map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    fn update(&self, job_id: u64, f: impl FnOnce(&mut DbJobStatus)) -> Option<DbJobStatus> {
+        let mut jobs = self.jobs.lock().ok()?;
+        let status = jobs.get_mut(&job_id)?;
+        f(status);
+        status.updated_at = now_millis();
+        Some(status.clone())
+    }
+}
+
+/// Handed to a running job so it can publish progress.
+pub(crate) struct JobReporter {
+    job_id: u64,
+    registry: Arc<JobRegistry>,
+    app: AppHandle,
+    cancel: CancelToken,
+}
+
+impl JobReporter {
+    /// Cancelled through `db_cancel_job`; long jobs check it between units of work.
+    pub(crate) fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    pub(crate) fn progress(&self, processed: u64, message: &str) {
+        let status = self.registry.update(self.job_id, |status| {
+            status.processed = processed;
+            status.message = message.to_string();
+        });
+        if let Some(status) = status {
+            let _ = self.app.emit(EVENT_JOB_PROGRESS, status);
+        }
+    }
+
+    fn finish(&self, result: Result<String, String>) {
+        if let Ok(mut tokens) = self.registry.tokens.lock() {
+            tokens.remove(&self.job_id);
+        }
+        let status = self.registry.update(self.job_id, |status| {
+            match result {
+                Ok(message) => {
+                    status.state = DbJobState::Completed;
+                    status.message = message;
+                }
+                Err(error) if self.cancel.is_cancelled() => {
+                    status.state = DbJobState::Cancelled;
+                    status.message = error;
+                }
+                Err(error) => {
+                    status.state = DbJobState::Failed;
+                    status.message = error;
+                }
+            }
+            status.finished_at = Some(now_millis());
+        });
+        if let Some(status) = status {
+            let _ = self.app.emit(EVENT_JOB_PROGRESS, status);
+        }
+    }
+}
+
+/// Registers a job and runs `work` on a blocking worker thread. Returns the initial
+/// status immediately; completion is reported through the job progress event.
+pub(crate) fn start_job(
+    registry: Arc<JobRegistry>,
+    app: AppHandle,
+    kind: DbJobKind,
+    label: String,
+    work: impl FnOnce(&JobReporter) -> Result<String, String> + Send + 'static,
+) -> Result<DbJobStatus, String> {
+    let job_id = registry.next_job_id.fetch_add(1, Ordering::Relaxed);
+    let now = now_millis();
+    let status = DbJobStatus {
+        job_id,
+        kind,
+        label,
+        state: DbJobState::Running,
+        processed: 0,
+        message: "Started.".to_string(),
+        started_at: now,
+        updated_at: now,
+        finished_at: None,
+    };
+    registry
+        .jobs
+        .lock()
+        .map_err(|_| "Failed to acquire job lock".to_string())?
+        .insert(job_id, status.clone());
+    let cancel = CancelToken::default();
+    registry
+        .tokens
+        .lock()
+        .map_err(|_| "Failed to acquire job lock".to_string())?
+        .insert(job_id, cancel.clone());
+
+    let reporter = JobReporter {
+        job_id,
+        registry,
+        app,
+        cancel,
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = work(&reporter);
+        reporter.finish(result);
+    });
+
+    Ok(status)
+}
@@ -0,0 +1,73 @@
+use crate::providers::ProviderRegistry;
+use crate::state::SessionRegistry;
+use crate::types::DatabaseProvider;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+pub(crate) const DEFAULT_KEEP_ALIVE_SECS: u64 = 300;
+pub(crate) const MAX_KEEP_ALIVE_SECS: u64 = 3600;
+const TICK: Duration = Duration::from_secs(15);
+
+/// Starts the background task that pings idle sessions so firewalls do not drop them.
+/// An interval of 0 turns it off.
+pub(crate) fn spawn(sessions: SessionRegistry, interval_secs: Arc<AtomicU64>) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let interval = interval_secs.load(Ordering::Relaxed);
+        if interval > 0 {
+            ping_idle_sessions(&sessions, Duration::from_secs(interval));
+        }
+    });
+}
+
+fn ping_idle_sessions(sessions: &SessionRegistry, interval: Duration) {
+    for session in sessions.all() {
+        // A session that is busy running a command is not idle.
+        let Ok(mut session) = session.try_lock() else {
+            continue;
+        };
+        if !needs_ping(session.provider, session.since_last_contact(), interval) {
+            continue;
+        }
+        // A failed ping is left for the next statement to detect and reconnect.
+        let _ = ProviderRegistry::ping(&mut session);
+        session.mark_pinged();
+    }
+}
+
+/// Local files and external adapters have no connection for a firewall to drop.
+fn needs_ping(provider: DatabaseProvider, idle: Duration, interval: Duration) -> bool {
+    !matches!(
+        provider,
+        DatabaseProvider::Sqlite | DatabaseProvider::Duckdb | DatabaseProvider::External
+    ) && idle >= interval
+}
+
+#[cfg(test)]
+mod tests {
+    use super::needs_ping;
+    use crate::types::DatabaseProvider;
+    use std::time::Duration;
+
+    #[test]
+    fn pings_only_idle_network_sessions() {
+        let interval = Duration::from_secs(300);
+        assert!(needs_ping(
+            DatabaseProvider::Oracle,
+            Duration::from_secs(301),
+            interval
+        ));
+        assert!(!needs_ping(
+            DatabaseProvider::Oracle,
+            Duration::from_secs(10),
+            interval
+        ));
+        assert!(!needs_ping(
+            DatabaseProvider::Sqlite,
+            Duration::from_secs(900),
+            interval
+        ));
+    }
+}
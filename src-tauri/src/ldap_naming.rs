@@ -0,0 +1,485 @@
+use crate::tnsnames::{apply_target, parse_descriptor, tns_admin_dir, TnsTarget};
+use crate::types::{
+    DbConnectConnection, DbConnectRequest, OracleConnectionOptions, OracleLdapNaming,
+};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+const LDAP_ORA_FILE: &str = "ldap.ora";
+const DEFAULT_LDAP_PORT: u16 = 389;
+const NET_DESCRIPTOR_ATTRIBUTE: &str = "orclNetDescString";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const SEARCH_TIME_LIMIT_SECS: u32 = 10;
+const RESULT_SUCCESS: u32 = 0;
+const RESULT_NO_SUCH_OBJECT: u32 = 32;
+
+// BER tags of the LDAPv3 messages used here (RFC 4511).
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_ENUMERATED: u8 = 0x0a;
+const TAG_BOOLEAN: u8 = 0x01;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_SEARCH_REQUEST: u8 = 0x63;
+const TAG_SEARCH_RESULT_ENTRY: u8 = 0x64;
+const TAG_SEARCH_RESULT_DONE: u8 = 0x65;
+const TAG_FILTER_PRESENT: u8 = 0x87;
+
+/// Where and under which context net service names are registered.
+#[derive(Debug, PartialEq, Eq)]
+struct DirectorySettings {
+    servers: Vec<(String, u16)>,
+    admin_context: Option<String>,
+}
+
+/// Fills the host, port, service and TLS settings of an Oracle request that uses LDAP
+/// naming. Other requests are returned unchanged.
+pub(crate) fn resolve_connect_request(
+    mut request: DbConnectRequest,
+) -> Result<DbConnectRequest, String> {
+    if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+        if let Some(ldap) = connection.ldap.clone() {
+            apply_target(connection, resolve_name(&ldap)?);
+        }
+    }
+    Ok(request)
+}
+
+/// Host and port an LDAP-named profile connects to, for reachability checks.
+pub(crate) fn profile_target(connection: &OracleConnectionOptions) -> Option<(String, u16)> {
+    resolve_name(connection.ldap.as_ref()?)
+        .ok()
+        .map(|target| target.endpoint())
+}
+
+/// Reads the connect descriptor stored for a net service name, asking the directory servers
+/// in order until one answers. Searches are anonymous and unencrypted, which is how the
+/// Oracle client reads naming entries too.
+pub(crate) fn resolve_name(ldap: &OracleLdapNaming) -> Result<TnsTarget, String> {
+    let name = ldap.name.trim();
+    let settings = directory_settings(ldap)?;
+    let admin_context = settings.admin_context.ok_or_else(|| {
+        "No default admin context is configured for LDAP naming (DEFAULT_ADMIN_CONTEXT in ldap.ora)"
+            .to_string()
+    })?;
+    let base_dn = format!(
+        "cn={},cn=OracleContext,{admin_context}",
+        escape_rdn_value(name)
+    );
+
+    let mut failures = Vec::new();
+    for (host, port) in &settings.servers {
+        match search_descriptor(host.as_str(), *port, base_dn.as_str()) {
+            Ok(Some(descriptor)) => {
+                return parse_descriptor(descriptor.as_str())
+                    .map_err(|error| format!("LDAP entry {name}: {error}"));
+            }
+            Ok(None) => {
+                return Err(format!(
+                    "{name} is not registered in the directory under {admin_context}"
+                ));
+            }
+            Err(error) => failures.push(format!("{host}:{port}: {error}")),
+        }
+    }
+    Err(format!(
+        "No directory server could be reached. {}",
+        failures.join(" ")
+    ))
+}
+
+/// Explicit settings win; `ldap.ora` fills in whatever was left out.
+fn directory_settings(ldap: &OracleLdapNaming) -> Result<DirectorySettings, String> {
+    let mut settings = DirectorySettings {
+        servers: ldap
+            .directory_servers
+            .iter()
+            .filter_map(|server| parse_server(server))
+            .collect(),
+        admin_context: ldap
+            .default_admin_context
+            .as_deref()
+            .map(str::trim)
+            .filter(|context| !context.is_empty())
+            .map(str::to_string),
+    };
+    if settings.servers.is_empty() || settings.admin_context.is_none() {
+        let path = tns_admin_dir(ldap.tns_admin.as_deref())?.join(LDAP_ORA_FILE);
+        let content = fs::read_to_string(&path).map_err(|error| {
+            format!(
+                "Set the directory servers or provide {}: {error}",
+                path.display()
+            )
+        })?;
+        let file = parse_ldap_ora(content.as_str());
+        if settings.servers.is_empty() {
+            settings.servers = file.servers;
+        }
+        if settings.admin_context.is_none() {
+            settings.admin_context = file.admin_context;
+        }
+    }
+    if settings.servers.is_empty() {
+        return Err("No directory servers are configured for LDAP naming".to_string());
+    }
+    Ok(settings)
+}
+
+/// Reads `DIRECTORY_SERVERS = (host:port[:sslport], ...)` and `DEFAULT_ADMIN_CONTEXT`.
+fn parse_ldap_ora(content: &str) -> DirectorySettings {
+    let mut settings = DirectorySettings {
+        servers: Vec::new(),
+        admin_context: None,
+    };
+    let content = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n");
+    for (key, value) in ldap_ora_parameters(content.as_str()) {
+        if key.eq_ignore_ascii_case("DIRECTORY_SERVERS") {
+            settings.servers = value
+                .trim_matches(|ch: char| ch == '(' || ch == ')' || ch.is_whitespace())
+                .split(',')
+                .filter_map(parse_server)
+                .collect();
+        } else if key.eq_ignore_ascii_case("DEFAULT_ADMIN_CONTEXT") {
+            let context = value.trim().trim_matches('"').trim();
+            settings.admin_context = (!context.is_empty()).then(|| context.to_string());
+        }
+    }
+    settings
+}
+
+/// `KEY = value` pairs, where a parenthesised value may span lines.
+fn ldap_ora_parameters(content: &str) -> Vec<(String, String)> {
+    let mut parameters = Vec::new();
+    let mut rest = content;
+    while let Some(equals) = rest.find('=') {
+        let key = rest[..equals].trim().to_string();
+        let after = rest[equals + 1..].trim_start();
+        let end = if after.starts_with('(') {
+            after.find(')').map_or(after.len(), |close| close + 1)
+        } else {
+            after.find('\n').unwrap_or(after.len())
+        };
+        parameters.push((key, after[..end].trim().to_string()));
+        rest = &after[end..];
+    }
+    parameters
+}
+
+/// `host[:port[:sslport]]`; only the plain port is used.
+fn parse_server(entry: &str) -> Option<(String, u16)> {
+    let mut parts = entry.trim().split(':');
+    let host = parts.next()?.trim();
+    if host.is_empty() {
+        return None;
+    }
+    let port = parts
+        .next()
+        .and_then(|port| port.trim().parse().ok())
+        .unwrap_or(DEFAULT_LDAP_PORT);
+    Some((host.to_string(), port))
+}
+
+/// Escapes a value for use in a DN (RFC 4514).
+fn escape_rdn_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (index, ch) in value.chars().enumerate() {
+        let leading = index == 0 && (ch == '#' || ch == ' ');
+        let trailing = index == value.chars().count() - 1 && ch == ' ';
+        if leading || trailing || matches!(ch, ',' | '+' | '"' | '\\' | '<' | '>' | ';' | '=') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn search_descriptor(host: &str, port: u16, base_dn: &str) -> Result<Option<String>, String> {
+    let address = (host, port)
+        .to_socket_addrs()
+        .map_err(|error| error.to_string())?
+        .next()
+        .ok_or_else(|| "The host name did not resolve".to_string())?;
+    let mut stream =
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT).map_err(|error| error.to_string())?;
+    stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .map_err(|error| error.to_string())?;
+    stream
+        .write_all(&search_request(1, base_dn))
+        .map_err(|error| error.to_string())?;
+
+    let mut descriptor = None;
+    loop {
+        let message = read_message(&mut stream)?;
+        match parse_search_response(message.as_slice())? {
+            SearchResponse::Entry(value) => descriptor = descriptor.or(value),
+            SearchResponse::Done(RESULT_SUCCESS) => return Ok(descriptor),
+            SearchResponse::Done(RESULT_NO_SUCH_OBJECT) => return Ok(None),
+            SearchResponse::Done(code) => {
+                return Err(format!("The search failed with LDAP result code {code}"));
+            }
+            SearchResponse::Other => {}
+        }
+    }
+}
+
+/// A base-object search for the descriptor attribute, following alias entries.
+fn search_request(message_id: u32, base_dn: &str) -> Vec<u8> {
+    let search = [
+        ber(TAG_OCTET_STRING, base_dn.as_bytes()),
+        ber_integer(TAG_ENUMERATED, 0),
+        ber_integer(TAG_ENUMERATED, 3),
+        ber_integer(TAG_INTEGER, 1),
+        ber_integer(TAG_INTEGER, SEARCH_TIME_LIMIT_SECS),
+        ber(TAG_BOOLEAN, &[0]),
+        ber(TAG_FILTER_PRESENT, b"objectClass"),
+        ber(
+            TAG_SEQUENCE,
+            &ber(TAG_OCTET_STRING, NET_DESCRIPTOR_ATTRIBUTE.as_bytes()),
+        ),
+    ]
+    .concat();
+    ber(
+        TAG_SEQUENCE,
+        &[
+            ber_integer(TAG_INTEGER, message_id),
+            ber(TAG_SEARCH_REQUEST, &search),
+        ]
+        .concat(),
+    )
+}
+
+fn ber(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut encoded = vec![tag];
+    let length = content.len();
+    if length < 0x80 {
+        encoded.push(length as u8);
+    } else {
+        let bytes = length.to_be_bytes();
+        let significant = bytes.iter().skip_while(|byte| **byte == 0).count();
+        encoded.push(0x80 | significant as u8);
+        encoded.extend_from_slice(&bytes[bytes.len() - significant..]);
+    }
+    encoded.extend_from_slice(content);
+    encoded
+}
+
+fn ber_integer(tag: u8, value: u32) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(3);
+    let mut content = bytes[start..].to_vec();
+    // A leading bit of one would read as a negative number.
+    if content[0] & 0x80 != 0 {
+        content.insert(0, 0);
+    }
+    ber(tag, &content)
+}
+
+/// Reads one whole LDAP message, tag and length included.
+fn read_message(stream: &mut impl Read) -> Result<Vec<u8>, String> {
+    let mut read = |buffer: &mut [u8]| {
+        stream
+            .read_exact(buffer)
+            .map_err(|error| format!("Failed to read the directory response: {error}"))
+    };
+    let mut message = vec![0u8; 2];
+    read(&mut message)?;
+    let length = if message[1] & 0x80 == 0 {
+        usize::from(message[1])
+    } else {
+        let count = usize::from(message[1] & 0x7f);
+        if count == 0 || count > 4 {
+            return Err("The directory sent an unsupported message length".to_string());
+        }
+        let mut bytes = vec![0u8; count];
+        read(&mut bytes)?;
+        message.extend_from_slice(&bytes);
+        bytes
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | usize::from(*byte))
+    };
+    let header_len = message.len();
+    message.resize(header_len + length, 0);
+    read(&mut message[header_len..])?;
+    Ok(message)
+}
+
+enum SearchResponse {
+    /// The descriptor attribute of a returned entry, if it had one.
+    Entry(Option<String>),
+    Done(u32),
+    Other,
+}
+
+fn parse_search_response(message: &[u8]) -> Result<SearchResponse, String> {
+    let (_, envelope) = BerReader::new(message).next()?;
+    let mut envelope = BerReader::new(envelope);
+    envelope.next()?;
+    let (tag, operation) = envelope.next()?;
+    let mut operation = BerReader::new(operation);
+    match tag {
+        TAG_SEARCH_RESULT_ENTRY => {
+            operation.next()?;
+            let (_, attributes) = operation.next()?;
+            let mut attributes = BerReader::new(attributes);
+            while !attributes.is_empty() {
+                let (_, attribute) = attributes.next()?;
+                let mut attribute = BerReader::new(attribute);
+                let (_, name) = attribute.next()?;
+                if !name.eq_ignore_ascii_case(NET_DESCRIPTOR_ATTRIBUTE.as_bytes()) {
+                    continue;
+                }
+                let (_, values) = attribute.next()?;
+                let mut values = BerReader::new(values);
+                if values.is_empty() {
+                    return Ok(SearchResponse::Entry(None));
+                }
+                let (_, value) = values.next()?;
+                return Ok(SearchResponse::Entry(Some(
+                    String::from_utf8_lossy(value).to_string(),
+                )));
+            }
+            Ok(SearchResponse::Entry(None))
+        }
+        TAG_SEARCH_RESULT_DONE => {
+            let (_, code) = operation.next()?;
+            Ok(SearchResponse::Done(
+                code.iter()
+                    .fold(0u32, |value, byte| (value << 8) | u32::from(*byte)),
+            ))
+        }
+        _ => Ok(SearchResponse::Other),
+    }
+}
+
+struct BerReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BerReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The next tag and its content.
+    fn next(&mut self) -> Result<(u8, &'a [u8]), String> {
+        let malformed = || "The directory sent a malformed response".to_string();
+        let (&tag, rest) = self.bytes.split_first().ok_or_else(malformed)?;
+        let (&first, mut rest) = rest.split_first().ok_or_else(malformed)?;
+        let length = if first & 0x80 == 0 {
+            usize::from(first)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count > 4 || rest.len() < count {
+                return Err(malformed());
+            }
+            let (length, after) = rest.split_at(count);
+            rest = after;
+            length
+                .iter()
+                .fold(0usize, |length, byte| (length << 8) | usize::from(*byte))
+        };
+        if rest.len() < length {
+            return Err(malformed());
+        }
+        let (content, after) = rest.split_at(length);
+        self.bytes = after;
+        Ok((tag, content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ber, ber_integer, escape_rdn_value, parse_ldap_ora, parse_search_response, read_message,
+        search_request, BerReader, DirectorySettings, SearchResponse, TAG_INTEGER,
+        TAG_OCTET_STRING, TAG_SEARCH_RESULT_DONE, TAG_SEARCH_RESULT_ENTRY, TAG_SEQUENCE,
+    };
+
+    const TAG_SET: u8 = 0x31;
+
+    #[test]
+    fn reads_ldap_ora_settings() {
+        let content = "\
+# Generated by NetCA
+DIRECTORY_SERVERS = (oid1.example.com:389:636,
+                     oid2.example.com)
+DEFAULT_ADMIN_CONTEXT = \"dc=example,dc=com\"
+DIRECTORY_SERVER_TYPE = OID
+";
+        assert_eq!(
+            parse_ldap_ora(content),
+            DirectorySettings {
+                servers: vec![
+                    ("oid1.example.com".to_string(), 389),
+                    ("oid2.example.com".to_string(), 389),
+                ],
+                admin_context: Some("dc=example,dc=com".to_string()),
+            }
+        );
+        assert_eq!(escape_rdn_value("a,b"), "a\\,b");
+    }
+
+    #[test]
+    fn encodes_searches_and_decodes_entries() {
+        let request = search_request(1, "cn=sales,cn=OracleContext,dc=example,dc=com");
+        let (tag, envelope) = BerReader::new(&request).next().expect("envelope");
+        assert_eq!(tag, TAG_SEQUENCE);
+        let mut envelope = BerReader::new(envelope);
+        assert_eq!(envelope.next().expect("id"), (TAG_INTEGER, &[1u8][..]));
+        assert_eq!(ber_integer(TAG_INTEGER, 200), vec![TAG_INTEGER, 2, 0, 200]);
+
+        let descriptor = "(DESCRIPTION=(ADDRESS=(PROTOCOL=TCP)(HOST=db)(PORT=1521))(CONNECT_DATA=(SERVICE_NAME=sales)))";
+        let attribute = ber(
+            TAG_SEQUENCE,
+            &[
+                ber(TAG_OCTET_STRING, b"orclnetdescstring"),
+                ber(TAG_SET, &ber(TAG_OCTET_STRING, descriptor.as_bytes())),
+            ]
+            .concat(),
+        );
+        let entry = ber(
+            TAG_SEQUENCE,
+            &[
+                ber_integer(TAG_INTEGER, 1),
+                ber(
+                    TAG_SEARCH_RESULT_ENTRY,
+                    &[
+                        ber(TAG_OCTET_STRING, b"cn=sales"),
+                        ber(TAG_SEQUENCE, &attribute),
+                    ]
+                    .concat(),
+                ),
+            ]
+            .concat(),
+        );
+        let message = read_message(&mut entry.as_slice()).expect("read");
+        match parse_search_response(&message).expect("entry") {
+            SearchResponse::Entry(value) => assert_eq!(value.as_deref(), Some(descriptor)),
+            _ => panic!("expected an entry"),
+        }
+
+        let done = ber(
+            TAG_SEQUENCE,
+            &[
+                ber_integer(TAG_INTEGER, 1),
+                ber(TAG_SEARCH_RESULT_DONE, &ber_integer(0x0a, 32)),
+            ]
+            .concat(),
+        );
+        assert!(matches!(
+            parse_search_response(&done).expect("done"),
+            SearchResponse::Done(32)
+        ));
+    }
+}
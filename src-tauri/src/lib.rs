@@ -1,50 +1,226 @@
 mod ai;
+mod cancellation;
+mod codegen;
 mod commands;
+mod data_export;
+mod ddl_backups;
+mod ddl_changelog;
+mod diagnostics;
+mod error_summary;
+mod external_edit;
 mod files;
+mod grants;
+mod idle_timeout;
+mod jobs;
+mod keep_alive;
+mod ldap_naming;
+mod masking;
 mod menu;
+mod metadata_query;
+mod object_columns;
+mod oracle_clients;
+mod oracle_wallets;
+mod portable;
 mod profiles;
 mod providers;
+mod query_stream;
+mod read_replica;
+mod report;
+mod schema_snapshots;
+mod scratch;
+mod sensitive_data;
+mod session_pool;
+mod session_timeline;
+mod snapshots;
+mod sql_ident;
+mod sql_safety;
+mod sql_script;
+mod sql_splitter;
+mod sqlplus;
+mod ssh_tunnel;
+mod standby;
 mod state;
+mod state_recovery;
+mod support_bundle;
+mod table_import;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod text_diff;
+mod tnsnames;
 mod types;
 mod validation;
+mod workspace;
+mod workspace_archive;
 
+use providers::ProviderRegistry;
 use state::AppState;
+use tauri::Manager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .menu(|app| menu::build(app))
+        .menu(menu::build)
         .on_menu_event(|app, event| menu::handle_event(app, event.id().as_ref()))
         .manage(AppState::default())
+        .setup(|app| {
+            state_recovery::recover_on_startup(app.handle());
+            for warning in ProviderRegistry::register_external_providers(app.handle()) {
+                eprintln!("external provider: {warning}");
+            }
+            let state = app.state::<AppState>();
+            keep_alive::spawn(
+                state.sessions.clone(),
+                state.keep_alive_interval_secs.clone(),
+            );
+            schema_snapshots::spawn(app.handle().clone(), state.jobs.clone());
+            idle_timeout::spawn(app.handle().clone());
+            error_summary::spawn(app.handle().clone());
+            standby::spawn(app.handle().clone());
+            // Windows are created here rather than from the config so a portable install
+            // can keep the webview's local storage next to the executable.
+            for window in &app.config().app.windows {
+                let mut builder = tauri::WebviewWindowBuilder::from_config(app.handle(), window)?;
+                if let Some(directory) = portable::webview_data_dir() {
+                    builder = builder.data_directory(directory);
+                }
+                builder.build()?;
+            }
+            Ok(())
+        })
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
             commands::db_connect,
+            commands::db_test_connection,
             commands::db_disconnect,
+            commands::db_list_external_providers,
             commands::db_list_objects,
             commands::db_list_object_columns,
+            commands::db_query_metadata,
             commands::db_run_query,
+            commands::db_cancel_query,
+            commands::db_fetch_more_rows,
+            commands::db_close_cursor,
+            commands::db_split_sql,
+            commands::db_list_bind_names,
+            commands::db_run_script,
+            commands::db_run_consistent_queries,
+            commands::db_download_lob,
+            commands::db_reconnect,
+            commands::db_reconnect_session,
+            commands::db_get_session_timeline,
+            commands::db_list_standby_connections,
+            commands::db_export_session_timeline,
+            commands::db_pin_object,
+            commands::db_list_pinned_objects,
+            commands::db_set_keep_alive_interval,
+            commands::db_set_idle_timeout,
+            commands::db_list_oracle_clients,
+            commands::db_save_oracle_client,
+            commands::db_delete_oracle_client,
+            commands::db_tag_object,
+            commands::db_untag_object,
+            commands::db_list_tagged_objects,
+            commands::db_list_schema_snapshot_schedules,
+            commands::db_save_schema_snapshot_schedule,
+            commands::db_delete_schema_snapshot_schedule,
+            commands::db_run_schema_snapshot,
+            commands::db_session_info,
+            commands::db_start_sql_file_import,
             commands::db_run_query_filtered,
+            commands::db_get_session_nls,
+            commands::db_set_session_nls,
+            commands::db_set_resource_limits,
+            commands::db_set_container,
+            commands::db_list_containers,
+            commands::db_set_timestamp_tz_mode,
             commands::db_get_transaction_state,
             commands::db_begin_transaction,
             commands::db_commit_transaction,
             commands::db_rollback_transaction,
             commands::db_search_schema_text,
+            commands::db_list_aq_queues,
+            commands::db_browse_aq_messages,
+            commands::db_get_recent_errors,
+            commands::db_get_error_summary,
+            commands::db_get_state_recoveries,
+            commands::db_get_row_locks,
+            commands::db_get_object_access_stats,
+            commands::db_get_encoding_info,
+            commands::db_detect_sensitive_columns,
+            commands::db_list_masking_rules,
+            commands::db_save_masking_rules,
+            commands::db_preview_masked_query,
+            commands::db_create_scratch_table,
+            commands::db_list_scratch_tables,
+            commands::db_drop_scratch_table,
+            commands::db_diff_cell_values,
+            commands::db_diff_object_against_file,
+            commands::db_edit_object_externally,
+            commands::db_stop_external_edit,
+            commands::db_save_editor_buffers,
+            commands::db_list_query_history,
+            commands::db_get_identifier_usage,
+            commands::db_clear_query_history,
+            commands::db_list_snippets,
+            commands::db_save_snippet,
+            commands::db_delete_snippet,
+            commands::db_save_snippet_parameter_set,
+            commands::db_delete_snippet_parameter_set,
+            commands::db_run_snippet,
+            commands::db_import_oracle_wallet,
+            commands::db_list_oracle_wallets,
+            commands::db_delete_oracle_wallet,
+            commands::db_list_tns_aliases,
+            commands::db_list_ddl_backups,
+            commands::db_get_ddl_changelog,
+            commands::db_export_ddl_changelog,
+            commands::db_get_ddl_backup_retention,
+            commands::db_set_ddl_backup_retention,
+            commands::db_search_workspace,
+            commands::db_save_result_snapshot,
+            commands::db_list_result_snapshots,
+            commands::db_get_result_snapshot,
+            commands::db_delete_result_snapshot,
             commands::db_get_object_ddl,
+            commands::db_get_object_ddl_as_of,
+            commands::db_get_object_edition,
             commands::db_update_object_ddl,
             commands::db_list_connection_profiles,
             commands::db_save_connection_profile,
             commands::db_delete_connection_profile,
             commands::db_get_connection_profile_secret,
+            commands::db_get_connection_profile_ssh_secret,
+            commands::db_run_diagnostics,
+            commands::db_create_support_bundle,
+            commands::db_export_workspace,
+            commands::db_import_workspace,
             commands::db_has_ai_api_key,
             commands::db_set_ai_api_key,
             commands::db_clear_ai_api_key,
             commands::db_ai_suggest_query,
+            commands::db_ai_review_ddl,
+            commands::db_refresh_ai_schema_context,
+            commands::db_get_ai_schema_context_status,
+            commands::db_get_ai_prompt_settings,
+            commands::db_save_ai_prompt_settings,
+            commands::db_get_default_ai_prompt_template,
             commands::db_pick_directory,
             commands::db_save_query_sheet,
             commands::db_save_query_sheets,
-            commands::db_export_schema
+            commands::db_export_schema,
+            commands::db_generate_code,
+            commands::db_generate_grants,
+            commands::db_export_query_data,
+            commands::db_export_result_report,
+            commands::db_start_data_export,
+            commands::db_preview_xlsx_import,
+            commands::db_start_xlsx_import,
+            commands::db_import_clipboard_rows,
+            commands::db_list_jobs,
+            commands::db_cancel_job,
+            commands::db_stream_query
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,321 @@
+use crate::cancellation::CancelToken;
+use crate::profiles::app_data_file_path;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_ident;
+use crate::sql_safety;
+use crate::types::{
+    DatabaseProvider, DbMaskedColumn, DbMaskedQueryRequest, DbMaskedQueryResult, DbMaskingRule,
+    DbMaskingStrategy, DbQueryRequest, DbSqlStatement,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const MASKING_RULES_STORE_FILE: &str = "masking_rules.json";
+const MASKED_QUERY_ALIAS: &str = "clarity_masked";
+const REDACTED_TEXT: &str = "****";
+const PARTIAL_VISIBLE_CHARS: u32 = 4;
+
+static MASKING_RULES_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct MaskingRuleStore {
+    rules: Vec<DbMaskingRule>,
+}
+
+pub(crate) fn list_masking_rules(app: &AppHandle) -> Result<Vec<DbMaskingRule>, String> {
+    let path = app_data_file_path(app, MASKING_RULES_STORE_FILE)?;
+    let _guard = lock_store()?;
+    Ok(read_store_from_path(path.as_path())?.rules)
+}
+
+/// Replaces the rule list. Rules are checked in order and the first match wins.
+pub(crate) fn save_masking_rules(
+    app: &AppHandle,
+    rules: Vec<DbMaskingRule>,
+) -> Result<Vec<DbMaskingRule>, String> {
+    let mut cleaned = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let column_pattern = rule.column_pattern.trim().to_string();
+        if column_pattern.is_empty() {
+            return Err("Masking rules need a column pattern".to_string());
+        }
+        cleaned.push(DbMaskingRule {
+            column_pattern,
+            strategy: rule.strategy,
+        });
+    }
+
+    let path = app_data_file_path(app, MASKING_RULES_STORE_FILE)?;
+    let _guard = lock_store()?;
+    let store = MaskingRuleStore { rules: cleaned };
+    write_store_to_path(path.as_path(), &store)?;
+    Ok(store.rules)
+}
+
+/// Runs a SELECT with the masking rules applied by the database: the query is wrapped in
+/// an outer SELECT that replaces matching columns with masking expressions. The original
+/// SQL is left as it is.
+pub(crate) fn preview_masked_query(
+    session: &mut AppSession,
+    request: &DbMaskedQueryRequest,
+    rules: &[DbMaskingRule],
+) -> Result<DbMaskedQueryResult, String> {
+    if rules.is_empty() {
+        return Err("No masking rules are configured.".to_string());
+    }
+    let body = request.sql.trim().trim_end_matches(';').trim_end();
+    if !sql_safety::is_select_query(body) {
+        return Err("Masking only applies to SELECT queries.".to_string());
+    }
+    // A SELECT can still change data, e.g. a data-modifying CTE, which has no confirmation here.
+    sql_safety::check_production(
+        request.session_id,
+        session,
+        &[DbSqlStatement {
+            sql: body.to_string(),
+            line: 1,
+        }],
+        None,
+    )?;
+
+    let provider = session.provider;
+    // WHERE 1 = 0 reads the result columns without fetching rows.
+    let describe = ProviderRegistry::run_query(
+        session,
+        &DbQueryRequest {
+            session_id: request.session_id,
+            sql: format!(
+                "SELECT * FROM (\n{body}\n){} WHERE 1 = 0",
+                sql_ident::derived_table_alias(provider, MASKED_QUERY_ALIAS)
+            ),
+            row_limit: Some(1),
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        },
+        &CancelToken::default(),
+    )?;
+
+    let (masked_sql, masked_columns) = masked_select_sql(provider, body, &describe.columns, rules)?;
+    let mut result = ProviderRegistry::run_query(
+        session,
+        &DbQueryRequest {
+            session_id: request.session_id,
+            sql: masked_sql.clone(),
+            row_limit: request.row_limit,
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        },
+        &CancelToken::default(),
+    )?;
+    result
+        .message
+        .push_str(&format!(" Masked {} column(s).", masked_columns.len()));
+
+    Ok(DbMaskedQueryResult {
+        masked_sql,
+        masked_columns,
+        result,
+    })
+}
+
+fn masked_select_sql(
+    provider: DatabaseProvider,
+    body: &str,
+    columns: &[String],
+    rules: &[DbMaskingRule],
+) -> Result<(String, Vec<DbMaskedColumn>), String> {
+    let mut select_list = Vec::with_capacity(columns.len());
+    let mut masked_columns = Vec::new();
+    for column in columns {
+        let quoted = sql_ident::quote_identifier(provider, column);
+        let rule = rules
+            .iter()
+            .find(|rule| pattern_matches(&rule.column_pattern, column));
+        match rule {
+            Some(rule) => {
+                let expression = mask_expression(provider, rule.strategy, &quoted)?;
+                select_list.push(format!("{expression} AS {quoted}"));
+                masked_columns.push(DbMaskedColumn {
+                    column_name: column.clone(),
+                    strategy: rule.strategy,
+                });
+            }
+            None => select_list.push(quoted),
+        }
+    }
+
+    // The body goes on its own lines so a trailing line comment cannot swallow the wrapper.
+    let sql = format!(
+        "SELECT {} FROM (\n{body}\n){}",
+        select_list.join(", "),
+        sql_ident::derived_table_alias(provider, MASKED_QUERY_ALIAS)
+    );
+    Ok((sql, masked_columns))
+}
+
+fn mask_expression(
+    provider: DatabaseProvider,
+    strategy: DbMaskingStrategy,
+    column: &str,
+) -> Result<String, String> {
+    let visible = PARTIAL_VISIBLE_CHARS;
+    match strategy {
+        DbMaskingStrategy::Null => Ok("NULL".to_string()),
+        DbMaskingStrategy::Redact => Ok(format!(
+            "CASE WHEN {column} IS NULL THEN NULL ELSE '{REDACTED_TEXT}' END"
+        )),
+        // Values no longer than the visible tail are masked completely.
+        DbMaskingStrategy::Partial => match provider {
+            DatabaseProvider::Oracle => {
+                let text = format!("TO_CHAR({column})");
+                Ok(format!(
+                    "CASE WHEN {column} IS NULL THEN NULL \
+                     WHEN LENGTH({text}) <= {visible} THEN RPAD('*', LENGTH({text}), '*') \
+                     ELSE LPAD(SUBSTR({text}, -{visible}), LENGTH({text}), '*') END"
+                ))
+            }
+            DatabaseProvider::Mysql => {
+                let text = format!("CAST({column} AS CHAR)");
+                Ok(format!(
+                    "CASE WHEN {column} IS NULL THEN NULL \
+                     WHEN CHAR_LENGTH({text}) <= {visible} THEN REPEAT('*', CHAR_LENGTH({text})) \
+                     ELSE CONCAT(REPEAT('*', CHAR_LENGTH({text}) - {visible}), RIGHT({text}, {visible})) END"
+                ))
+            }
+            DatabaseProvider::Duckdb | DatabaseProvider::Snowflake => {
+                let text = format!("CAST({column} AS VARCHAR)");
+                Ok(format!(
+                    "CASE WHEN {column} IS NULL THEN NULL \
+                     WHEN LENGTH({text}) <= {visible} THEN REPEAT('*', LENGTH({text})) \
+                     ELSE CONCAT(REPEAT('*', LENGTH({text}) - {visible}), RIGHT({text}, {visible})) END"
+                ))
+            }
+            provider => Err(format!(
+                "Partial masking is not supported for provider '{}' yet; use redact instead.",
+                provider.label()
+            )),
+        },
+    }
+}
+
+/// Case-insensitive match where `*` stands for any run of characters.
+fn pattern_matches(pattern: &str, column: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    let column = column.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = column.strip_prefix(first) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+fn lock_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    MASKING_RULES_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire masking rules store lock".to_string())
+}
+
+fn read_store_from_path(path: &Path) -> Result<MaskingRuleStore, String> {
+    if !path.exists() {
+        return Ok(MaskingRuleStore::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read masking rules file: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(MaskingRuleStore::default());
+    }
+
+    serde_json::from_str::<MaskingRuleStore>(&content)
+        .map_err(|error| format!("Failed to parse masking rules file: {error}"))
+}
+
+fn write_store_to_path(path: &Path, store: &MaskingRuleStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create app data directory: {error}"))?;
+    }
+
+    let payload = serde_json::to_string_pretty(store)
+        .map_err(|error| format!("Failed to serialize masking rules: {error}"))?;
+    fs::write(path, payload).map_err(|error| format!("Failed to write masking rules file: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{masked_select_sql, pattern_matches};
+    use crate::types::{DatabaseProvider, DbMaskingRule, DbMaskingStrategy};
+
+    #[test]
+    fn wraps_matching_columns_in_masking_expressions() {
+        assert!(pattern_matches("*email*", "CUSTOMER_EMAIL_ADDR"));
+        assert!(pattern_matches("ssn", "SSN"));
+        assert!(pattern_matches("card_*_no", "card_primary_no"));
+        assert!(!pattern_matches("ssn", "SSN_HASH"));
+        assert!(!pattern_matches("card_*_no", "card_no"));
+
+        let rules = vec![
+            DbMaskingRule {
+                column_pattern: "*email*".to_string(),
+                strategy: DbMaskingStrategy::Redact,
+            },
+            DbMaskingRule {
+                column_pattern: "phone".to_string(),
+                strategy: DbMaskingStrategy::Partial,
+            },
+        ];
+        let columns = vec!["ID".to_string(), "EMAIL".to_string(), "PHONE".to_string()];
+
+        let (sql, masked) = masked_select_sql(
+            DatabaseProvider::Oracle,
+            "SELECT id, email, phone FROM customers -- all",
+            &columns,
+            &rules,
+        )
+        .unwrap();
+        assert!(sql.starts_with(
+            "SELECT \"ID\", CASE WHEN \"EMAIL\" IS NULL THEN NULL ELSE '****' END AS \"EMAIL\", CASE WHEN \"PHONE\" IS NULL"
+        ));
+        assert!(
+            sql.contains("LPAD(SUBSTR(TO_CHAR(\"PHONE\"), -4), LENGTH(TO_CHAR(\"PHONE\")), '*')")
+        );
+        assert!(sql
+            .ends_with("FROM (\nSELECT id, email, phone FROM customers -- all\n) clarity_masked"));
+        assert_eq!(
+            masked
+                .iter()
+                .map(|column| column.column_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["EMAIL", "PHONE"]
+        );
+
+        assert!(masked_select_sql(
+            DatabaseProvider::Libsql,
+            "SELECT phone FROM t",
+            &columns,
+            &rules
+        )
+        .is_err());
+    }
+}
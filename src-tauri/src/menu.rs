@@ -29,6 +29,13 @@ const EVENT_SAVE_ALL_QUERY_SHEETS: &str = "clarity://save-all-query-sheets";
 const EVENT_NAVIGATE_SCRIPT_LINE_BACK: &str = "clarity://navigate-script-line-back";
 const EVENT_NAVIGATE_SCRIPT_LINE_FORWARD: &str = "clarity://navigate-script-line-forward";
 pub(crate) const EVENT_SCHEMA_EXPORT_PROGRESS: &str = "clarity://schema-export-progress";
+pub(crate) const EVENT_JOB_PROGRESS: &str = "clarity://job-progress";
+pub(crate) const EVENT_EXTERNAL_EDIT_SAVED: &str = "clarity://external-edit-saved";
+pub(crate) const EVENT_SESSION_RECONNECTED: &str = "clarity://session-reconnected";
+pub(crate) const EVENT_SESSION_IDLE_DISCONNECTED: &str = "clarity://session-idle-disconnected";
+pub(crate) const EVENT_SESSION_ERRORS: &str = "clarity://session-errors";
+pub(crate) const EVENT_QUERY_ROWS: &str = "clarity://query-rows";
+pub(crate) const EVENT_STATE_RECOVERED: &str = "clarity://state-recovered";
 
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,222 @@
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_ident;
+use crate::types::{
+    DbMetadataColumn, DbMetadataDocument, DbMetadataFacet, DbMetadataFilter,
+    DbMetadataQueryRequest, DbObjectEntry, DbObjectMetadata, DbObjectName,
+};
+
+/// Objects described by one call; more are reported as `truncated`.
+const MAX_METADATA_OBJECTS: usize = 200;
+
+/// Describes objects and the requested facets in one document, so an explorer panel needs
+/// one call instead of one per facet and object. Each facet is read for all objects at
+/// once; a facet the provider cannot read is listed in `unavailable` and the rest are
+/// still returned.
+pub(crate) fn query_metadata(
+    session: &AppSession,
+    request: &DbMetadataQueryRequest,
+) -> Result<DbMetadataDocument, String> {
+    let entries = ProviderRegistry::list_objects(session)?;
+    let (mut objects, truncated) = select_objects(&entries, &request.objects, &request.filter);
+    let names = objects
+        .iter()
+        .map(|object| DbObjectName {
+            schema: object.schema.clone(),
+            object_name: object.object_name.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let mut unavailable = Vec::new();
+    let mut facets = Vec::new();
+    for facet in &request.include {
+        if !facets.contains(facet) {
+            facets.push(*facet);
+        }
+    }
+    for facet in facets {
+        let read = match facet {
+            DbMetadataFacet::Columns => {
+                ProviderRegistry::list_columns_of(session, &names).map(|columns| {
+                    for object in &mut objects {
+                        object.columns = Some(
+                            columns
+                                .iter()
+                                .filter(|column| {
+                                    column.schema.eq_ignore_ascii_case(&object.schema)
+                                        && column.object_name == object.object_name
+                                })
+                                .map(|column| DbMetadataColumn {
+                                    name: column.column_name.clone(),
+                                    data_type: column.data_type.clone(),
+                                    nullable: column.nullable != "N",
+                                })
+                                .collect(),
+                        );
+                    }
+                })
+            }
+            DbMetadataFacet::Constraints => ProviderRegistry::list_constraints_of(session, &names)
+                .map(|constraints| {
+                    for (object, constraints) in objects.iter_mut().zip(constraints) {
+                        object.constraints = Some(constraints);
+                    }
+                }),
+            DbMetadataFacet::Stats => {
+                ProviderRegistry::table_stats_of(session, &names).map(|stats| {
+                    for (object, stats) in objects.iter_mut().zip(stats) {
+                        object.stats = stats;
+                    }
+                })
+            }
+            DbMetadataFacet::Grants => {
+                ProviderRegistry::list_grants_of(session, &names).map(|grants| {
+                    for (object, grants) in objects.iter_mut().zip(grants) {
+                        object.grants = Some(grants);
+                    }
+                })
+            }
+        };
+        if let Err(error) = read {
+            unavailable.push(format!("{}: {error}", facet_label(facet)));
+        }
+    }
+
+    Ok(DbMetadataDocument {
+        objects,
+        truncated,
+        unavailable,
+    })
+}
+
+/// The listed objects, or the schema's objects that pass the filter, up to the limit.
+/// Listed objects that do not exist are kept without a type.
+fn select_objects(
+    entries: &[DbObjectEntry],
+    listed: &[DbObjectName],
+    filter: &DbMetadataFilter,
+) -> (Vec<DbObjectMetadata>, bool) {
+    let passes = |object_type: Option<&str>, object_name: &str| {
+        let name_matches = filter
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .is_none_or(|pattern| sql_ident::matches_pattern(pattern, object_name));
+        let type_matches = filter.object_types.is_empty()
+            || object_type.is_some_and(|object_type| {
+                filter
+                    .object_types
+                    .iter()
+                    .any(|wanted| wanted.trim().eq_ignore_ascii_case(object_type))
+            });
+        name_matches && type_matches
+    };
+
+    let mut objects = if listed.is_empty() {
+        entries
+            .iter()
+            .filter(|entry| passes(Some(&entry.object_type), &entry.object_name))
+            .map(|entry| metadata(&entry.schema, &entry.object_name, Some(entry)))
+            .collect::<Vec<_>>()
+    } else {
+        listed
+            .iter()
+            .map(|object| {
+                // Tables and their indexes can share a name; the table is meant.
+                let mut found = entries.iter().filter(|entry| {
+                    entry.schema.eq_ignore_ascii_case(object.schema.trim())
+                        && entry.object_name == object.object_name.trim()
+                });
+                let entry = found
+                    .clone()
+                    .find(|entry| entry.object_type == "TABLE")
+                    .or_else(|| found.next());
+                metadata(&object.schema, &object.object_name, entry)
+            })
+            .filter(|object| passes(object.object_type.as_deref(), &object.object_name))
+            .collect::<Vec<_>>()
+    };
+    let truncated = objects.len() > MAX_METADATA_OBJECTS;
+    objects.truncate(MAX_METADATA_OBJECTS);
+    (objects, truncated)
+}
+
+fn metadata(schema: &str, object_name: &str, entry: Option<&DbObjectEntry>) -> DbObjectMetadata {
+    DbObjectMetadata {
+        schema: entry.map_or_else(|| schema.trim().to_string(), |entry| entry.schema.clone()),
+        object_name: object_name.trim().to_string(),
+        object_type: entry.map(|entry| entry.object_type.clone()),
+        status: entry.and_then(|entry| entry.status.clone()),
+        columns: None,
+        constraints: None,
+        stats: None,
+        grants: None,
+    }
+}
+
+fn facet_label(facet: DbMetadataFacet) -> &'static str {
+    match facet {
+        DbMetadataFacet::Columns => "Columns",
+        DbMetadataFacet::Constraints => "Constraints",
+        DbMetadataFacet::Stats => "Stats",
+        DbMetadataFacet::Grants => "Grants",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::select_objects;
+    use crate::types::{DbMetadataFilter, DbObjectEntry, DbObjectName};
+
+    fn entry(object_type: &str, object_name: &str) -> DbObjectEntry {
+        DbObjectEntry {
+            schema: "HR".to_string(),
+            object_type: object_type.to_string(),
+            object_name: object_name.to_string(),
+            status: Some("VALID".to_string()),
+            invalid_reason: None,
+            edition_name: None,
+            created: None,
+            last_ddl_time: None,
+            temporary: false,
+            secondary: false,
+        }
+    }
+
+    #[test]
+    fn selects_listed_or_filtered_objects() {
+        let entries = vec![
+            entry("INDEX", "ORDERS"),
+            entry("TABLE", "ORDERS"),
+            entry("TABLE", "ORDER_LINES"),
+            entry("VIEW", "ORDER_TOTALS"),
+            entry("TABLE", "EMPLOYEES"),
+        ];
+        let filter = DbMetadataFilter {
+            name: Some("order*".to_string()),
+            object_types: vec!["table".to_string()],
+        };
+        let (objects, truncated) = select_objects(&entries, &[], &filter);
+        let names = objects
+            .iter()
+            .map(|object| object.object_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["ORDERS", "ORDER_LINES"]);
+        assert!(!truncated);
+
+        let listed = [
+            DbObjectName {
+                schema: "hr".to_string(),
+                object_name: "ORDERS".to_string(),
+            },
+            DbObjectName {
+                schema: "HR".to_string(),
+                object_name: "MISSING".to_string(),
+            },
+        ];
+        let (objects, _) = select_objects(&entries, &listed, &DbMetadataFilter::default());
+        assert_eq!(objects[0].object_type.as_deref(), Some("TABLE"));
+        assert_eq!(objects[0].schema, "HR");
+        assert_eq!(objects[1].object_type, None);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::session_pool::MAX_POOL_SIZE;
+use crate::types::{DbObjectColumnEntry, DbObjectName};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub(crate) const DEFAULT_CONCURRENCY: u32 = 4;
+
+/// Threads to read a batch with: the requested concurrency, limited by the connections the
+/// session may open and by the number of objects.
+pub(crate) fn worker_count(requested: Option<u32>, pool_size: u32, objects: usize) -> usize {
+    let workers = requested
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_POOL_SIZE)
+        .min(pool_size.max(1));
+    (workers as usize).min(objects.max(1))
+}
+
+/// Reads the columns of `objects` with up to `workers` calls of `read` at once, each taking
+/// the next object. Columns come back in the order of `objects`; the first failure, in
+/// that order, fails the batch.
+pub(crate) fn fetch_parallel(
+    objects: &[DbObjectName],
+    workers: usize,
+    read: impl Fn(&[DbObjectName]) -> Result<Vec<DbObjectColumnEntry>, String> + Sync,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    if workers <= 1 {
+        return read(objects);
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..objects.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(object) = objects.get(index) else {
+                    break;
+                };
+                let result = read(std::slice::from_ref(object));
+                let failed = result.is_err();
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+                if failed {
+                    // Let the other workers finish their object, but take no new ones.
+                    next.store(objects.len(), Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    let results = results
+        .into_inner()
+        .map_err(|_| "Failed to collect column results".to_string())?;
+    let mut columns = Vec::new();
+    for result in results.into_iter().flatten() {
+        columns.extend(result?);
+    }
+    Ok(columns)
+}
+
+/// The columns of `objects`, in their order, out of every column of a schema.
+pub(crate) fn select(
+    columns: Vec<DbObjectColumnEntry>,
+    objects: &[DbObjectName],
+) -> Vec<DbObjectColumnEntry> {
+    let position = |column: &DbObjectColumnEntry| {
+        objects.iter().position(|object| {
+            object.schema.eq_ignore_ascii_case(&column.schema)
+                && object.object_name == column.object_name
+        })
+    };
+    let mut selected = columns
+        .into_iter()
+        .filter_map(|column| position(&column).map(|index| (index, column)))
+        .collect::<Vec<_>>();
+    // Stable, so each object's columns keep their order.
+    selected.sort_by_key(|(index, _)| *index);
+    selected.into_iter().map(|(_, column)| column).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch_parallel, worker_count};
+    use crate::types::{DbObjectColumnEntry, DbObjectName};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    fn object(name: &str) -> DbObjectName {
+        DbObjectName {
+            schema: "HR".to_string(),
+            object_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_objects_concurrently_within_the_limit_and_keeps_their_order() {
+        assert_eq!(worker_count(None, 8, 50), 4);
+        assert_eq!(worker_count(Some(16), 3, 50), 3);
+        assert_eq!(worker_count(Some(6), 8, 2), 2);
+
+        let objects = (0..12)
+            .map(|i| object(&format!("T{i}")))
+            .collect::<Vec<_>>();
+        let running = AtomicUsize::new(0);
+        let most_running = AtomicUsize::new(0);
+        let columns = fetch_parallel(&objects, 3, |batch| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            most_running.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok(batch
+                .iter()
+                .map(|object| DbObjectColumnEntry {
+                    schema: object.schema.clone(),
+                    object_name: object.object_name.clone(),
+                    column_name: "ID".to_string(),
+                    data_type: "NUMBER".to_string(),
+                    nullable: "N".to_string(),
+                })
+                .collect())
+        })
+        .unwrap();
+
+        let names = columns
+            .iter()
+            .map(|column| column.object_name.as_str())
+            .collect::<Vec<_>>();
+        let expected = objects
+            .iter()
+            .map(|object| object.object_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected);
+        assert!(most_running.load(Ordering::SeqCst) <= 3);
+
+        let error = fetch_parallel(&objects, 3, |batch| {
+            if batch[0].object_name == "T4" {
+                Err("ORA-00942".to_string())
+            } else {
+                Ok(Vec::new())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(error, "ORA-00942");
+    }
+}
@@ -0,0 +1,138 @@
+use crate::profiles::app_data_file_path;
+use crate::providers::ProviderRegistry;
+use crate::types::{DbOracleClient, DbSaveOracleClientRequest};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+const ORACLE_CLIENTS_STORE_FILE: &str = "oracle-clients.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredOracleClient {
+    path: String,
+    label: String,
+}
+
+/// Known Instant Client installations, marking the one this process has loaded.
+pub(crate) fn list_clients(app: &AppHandle) -> Result<Vec<DbOracleClient>, String> {
+    let loaded = ProviderRegistry::loaded_oracle_client_dir().flatten();
+    Ok(read_clients(app)?
+        .into_iter()
+        .map(|client| {
+            let path = Path::new(&client.path);
+            DbOracleClient {
+                library_found: has_client_library(path),
+                loaded: loaded.as_deref() == Some(path),
+                path: client.path,
+                label: client.label,
+            }
+        })
+        .collect())
+}
+
+pub(crate) fn save_client(
+    app: &AppHandle,
+    request: DbSaveOracleClientRequest,
+) -> Result<Vec<DbOracleClient>, String> {
+    let path = request.path.trim();
+    if path.is_empty() {
+        return Err("Instant Client directory is required".to_string());
+    }
+    if !has_client_library(Path::new(path)) {
+        return Err(format!(
+            "No Oracle Client library (libclntsh or oci.dll) was found in {path}"
+        ));
+    }
+    let label = match request.label.as_deref().map(str::trim) {
+        Some(label) if !label.is_empty() => label.to_string(),
+        _ => path.to_string(),
+    };
+
+    let mut clients = read_clients(app)?;
+    upsert_client(
+        &mut clients,
+        StoredOracleClient {
+            path: path.to_string(),
+            label,
+        },
+    );
+    write_clients(app, &clients)?;
+    list_clients(app)
+}
+
+pub(crate) fn delete_client(app: &AppHandle, path: &str) -> Result<Vec<DbOracleClient>, String> {
+    let mut clients = read_clients(app)?;
+    clients.retain(|client| client.path != path.trim());
+    write_clients(app, &clients)?;
+    list_clients(app)
+}
+
+fn upsert_client(clients: &mut Vec<StoredOracleClient>, client: StoredOracleClient) {
+    match clients
+        .iter_mut()
+        .find(|existing| existing.path == client.path)
+    {
+        Some(existing) => existing.label = client.label,
+        None => clients.push(client),
+    }
+}
+
+fn has_client_library(directory: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_ascii_lowercase();
+        name.starts_with("libclntsh.") || name == "oci.dll"
+    })
+}
+
+fn read_clients(app: &AppHandle) -> Result<Vec<StoredOracleClient>, String> {
+    let path = app_data_file_path(app, ORACLE_CLIENTS_STORE_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read Oracle Client list: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&content)
+        .map_err(|error| format!("Failed to parse Oracle Client list: {error}"))
+}
+
+fn write_clients(app: &AppHandle, clients: &[StoredOracleClient]) -> Result<(), String> {
+    let path = app_data_file_path(app, ORACLE_CLIENTS_STORE_FILE)?;
+    let payload = serde_json::to_string_pretty(clients)
+        .map_err(|error| format!("Failed to serialize Oracle Client list: {error}"))?;
+    fs::write(path, payload).map_err(|error| format!("Failed to write Oracle Client list: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_client_library, upsert_client, StoredOracleClient};
+    use std::fs;
+
+    #[test]
+    fn recognizes_client_directories_and_relabels_known_paths() {
+        let directory =
+            std::env::temp_dir().join(format!("clarity_oracle_clients_{}", std::process::id()));
+        fs::create_dir_all(&directory).expect("create temp dir");
+        assert!(!has_client_library(&directory));
+        fs::write(directory.join("libclntsh.so.21.1"), b"").expect("write library");
+        assert!(has_client_library(&directory));
+        let _ = fs::remove_dir_all(&directory);
+
+        let mut clients = Vec::new();
+        let client = |label: &str| StoredOracleClient {
+            path: "/opt/oracle/instantclient_21_1".to_string(),
+            label: label.to_string(),
+        };
+        upsert_client(&mut clients, client("21c"));
+        upsert_client(&mut clients, client("21c basic"));
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].label, "21c basic");
+    }
+}
@@ -0,0 +1,176 @@
+use crate::files::sanitize_export_file_stem;
+use crate::profiles::app_data_file_path;
+use crate::tnsnames::{parse_descriptor, read_tnsnames, TnsTarget, TNSNAMES_FILE};
+use crate::types::{
+    DbConnectConnection, DbConnectRequest, DbImportOracleWalletRequest, DbOracleWallet,
+    OracleConnectionOptions, OracleTlsOptions, OracleWalletRef,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const WALLET_DIRECTORY: &str = "oracle-wallets";
+pub(crate) const AUTO_LOGIN_WALLET_FILE: &str = "cwallet.sso";
+const IMPORTING_SUFFIX: &str = ".importing";
+
+/// Unpacks a wallet zip (as downloaded for Autonomous Database) into app data. Importing a
+/// wallet under an existing name replaces it, which is how a rotated wallet is picked up.
+pub(crate) fn import_wallet(
+    app: &tauri::AppHandle,
+    request: &DbImportOracleWalletRequest,
+) -> Result<DbOracleWallet, String> {
+    let zip_path = Path::new(request.file_path.trim());
+    let name = match request.name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => sanitize_export_file_stem(name),
+        _ => sanitize_export_file_stem(
+            zip_path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("wallet"),
+        ),
+    };
+
+    let root = wallets_root(app)?;
+    let staging = root.join(format!("{name}{IMPORTING_SUFFIX}"));
+    let _ = fs::remove_dir_all(&staging);
+    if let Err(error) = extract_wallet(zip_path, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(error);
+    }
+
+    let directory = root.join(&name);
+    if directory.exists() {
+        fs::remove_dir_all(&directory)
+            .map_err(|error| format!("Failed to replace wallet {name}: {error}"))?;
+    }
+    fs::rename(&staging, &directory)
+        .map_err(|error| format!("Failed to store wallet {name}: {error}"))?;
+    describe_wallet(name, &directory)
+}
+
+pub(crate) fn list_wallets(app: &tauri::AppHandle) -> Result<Vec<DbOracleWallet>, String> {
+    let root = wallets_root(app)?;
+    let entries =
+        fs::read_dir(&root).map_err(|error| format!("Failed to read wallets: {error}"))?;
+    let mut wallets = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(IMPORTING_SUFFIX) {
+                return None;
+            }
+            describe_wallet(name, &entry.path()).ok()
+        })
+        .collect::<Vec<_>>();
+    wallets.sort_by(|left, right| left.name.cmp(&right.name));
+    Ok(wallets)
+}
+
+pub(crate) fn delete_wallet(app: &tauri::AppHandle, name: &str) -> Result<(), String> {
+    let directory = wallet_directory(app, name)?;
+    fs::remove_dir_all(&directory).map_err(|error| format!("Failed to delete wallet: {error}"))
+}
+
+/// Fills the host, port, service and TLS settings of an Oracle request that names a wallet
+/// alias. Other requests are returned unchanged.
+pub(crate) fn resolve_connect_request(
+    app: &tauri::AppHandle,
+    mut request: DbConnectRequest,
+) -> Result<DbConnectRequest, String> {
+    if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+        if let Some(wallet) = connection.wallet.clone() {
+            // The wallet's own `sqlnet.ora` locates the wallet through `TNS_ADMIN`, which is
+            // process-wide in the Oracle client; passing the directory in the connect
+            // descriptor instead lets sessions use different wallets.
+            let (directory, target) = resolve_alias(app, &wallet)?;
+            connection.protocol = target.protocol;
+            connection.host = target.host;
+            connection.port = target.port;
+            connection.service_name = target.service_name;
+            connection.tls = Some(Box::new(OracleTlsOptions {
+                wallet_location: Some(directory.to_string_lossy().to_string()),
+                server_cert_dn: target.server_cert_dn,
+                server_dn_match: Some(target.server_dn_match),
+            }));
+        }
+    }
+    Ok(request)
+}
+
+/// Host and port a wallet profile connects to, for reachability checks.
+pub(crate) fn profile_target(
+    app: &tauri::AppHandle,
+    connection: &OracleConnectionOptions,
+) -> Option<(String, u16)> {
+    let wallet = connection.wallet.as_ref()?;
+    let (_, target) = resolve_alias(app, wallet).ok()?;
+    Some(target.endpoint())
+}
+
+fn resolve_alias(
+    app: &tauri::AppHandle,
+    wallet: &OracleWalletRef,
+) -> Result<(PathBuf, TnsTarget), String> {
+    let directory = wallet_directory(app, wallet.wallet_name.as_str())?;
+    let tnsnames = read_tnsnames(&directory)?;
+    let alias = wallet.service_alias.trim();
+    let descriptor = tnsnames
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(alias))
+        .map(|(_, descriptor)| descriptor.as_str())
+        .ok_or_else(|| {
+            format!(
+                "Service alias {alias} was not found in wallet {}",
+                wallet.wallet_name.trim()
+            )
+        })?;
+    Ok((directory, parse_descriptor(descriptor)?))
+}
+
+fn wallets_root(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let root = app_data_file_path(app, WALLET_DIRECTORY)?;
+    fs::create_dir_all(&root)
+        .map_err(|error| format!("Failed to create wallet directory: {error}"))?;
+    Ok(root)
+}
+
+fn wallet_directory(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Wallet name is required".to_string());
+    }
+    let directory = wallets_root(app)?.join(sanitize_export_file_stem(name));
+    if !directory.is_dir() {
+        return Err(format!("Wallet {name} has not been imported"));
+    }
+    Ok(directory)
+}
+
+fn extract_wallet(zip_path: &Path, target: &Path) -> Result<(), String> {
+    let file = fs::File::open(zip_path)
+        .map_err(|error| format!("Failed to open {}: {error}", zip_path.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|error| format!("{} is not a zip file: {error}", zip_path.display()))?;
+    archive
+        .extract(target)
+        .map_err(|error| format!("Failed to unpack wallet: {error}"))?;
+
+    for required in [TNSNAMES_FILE, AUTO_LOGIN_WALLET_FILE] {
+        if !target.join(required).is_file() {
+            return Err(format!("The wallet zip has no {required}"));
+        }
+    }
+    Ok(())
+}
+
+fn describe_wallet(name: String, directory: &Path) -> Result<DbOracleWallet, String> {
+    let aliases = read_tnsnames(directory)?
+        .into_iter()
+        .map(|(alias, _)| alias)
+        .collect();
+    Ok(DbOracleWallet {
+        name,
+        directory: directory.to_string_lossy().to_string(),
+        aliases,
+    })
+}
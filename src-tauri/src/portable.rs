@@ -0,0 +1,208 @@
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+const PORTABLE_ENV: &str = "CLARITY_PORTABLE";
+const PORTABLE_MARKER_FILE: &str = "clarity.portable";
+const PORTABLE_DATA_DIRECTORY: &str = "clarity-data";
+const WEBVIEW_DATA_DIRECTORY: &str = "webview";
+const SECRET_STORE_FILE: &str = "secrets.bin";
+const SECRET_KEY_FILE: &str = "secrets.key";
+const SECRET_KEY_LEN: usize = 32;
+
+static DATA_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+// Serializes read-modify-write cycles on the secret store file.
+static SECRET_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// The directory next to the executable that replaces the OS app data directory, or `None`
+/// when not running portable. Portable mode is on when `CLARITY_PORTABLE` is set to a
+/// truthy value, or when a `clarity.portable` file sits next to the executable and the
+/// variable is not set to a falsy value. Resolved once per process.
+pub(crate) fn data_dir() -> Option<&'static Path> {
+    DATA_DIR.get_or_init(detect_data_dir).as_deref()
+}
+
+pub(crate) fn is_enabled() -> bool {
+    data_dir().is_some()
+}
+
+/// Where the webview keeps local storage, so frontend settings travel with the app too.
+pub(crate) fn webview_data_dir() -> Option<PathBuf> {
+    data_dir().map(|directory| directory.join(WEBVIEW_DATA_DIRECTORY))
+}
+
+fn detect_data_dir() -> Option<PathBuf> {
+    let executable = std::env::current_exe().ok()?;
+    let executable_dir = executable.parent()?;
+    let enabled = match std::env::var(PORTABLE_ENV) {
+        Ok(value) => parse_flag(value.as_str())
+            .unwrap_or_else(|| executable_dir.join(PORTABLE_MARKER_FILE).is_file()),
+        Err(_) => executable_dir.join(PORTABLE_MARKER_FILE).is_file(),
+    };
+    enabled.then(|| executable_dir.join(PORTABLE_DATA_DIRECTORY))
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+pub(crate) fn read_secret(account: &str) -> Result<Option<String>, String> {
+    let directory = secret_store_dir()?;
+    let _guard = lock_secret_store()?;
+    Ok(load_secrets(directory)?.remove(account))
+}
+
+pub(crate) fn write_secret(account: &str, value: &str) -> Result<(), String> {
+    let directory = secret_store_dir()?;
+    let _guard = lock_secret_store()?;
+    let mut secrets = load_secrets(directory)?;
+    secrets.insert(account.to_string(), value.to_string());
+    save_secrets(directory, &secrets)
+}
+
+pub(crate) fn clear_secret(account: &str) -> Result<(), String> {
+    let directory = secret_store_dir()?;
+    let _guard = lock_secret_store()?;
+    let mut secrets = load_secrets(directory)?;
+    if secrets.remove(account).is_some() {
+        save_secrets(directory, &secrets)?;
+    }
+    Ok(())
+}
+
+fn secret_store_dir() -> Result<&'static Path, String> {
+    data_dir().ok_or_else(|| "Portable mode is not enabled".to_string())
+}
+
+fn lock_secret_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    SECRET_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire secret store lock".to_string())
+}
+
+/// Secrets are kept as one JSON map sealed with ChaCha20-Poly1305. The key lives in a file
+/// beside the store, so this keeps secrets out of plain sight on a shared stick but does not
+/// protect them from someone who copies the whole data directory.
+fn load_secrets(directory: &Path) -> Result<BTreeMap<String, String>, String> {
+    let path = directory.join(SECRET_STORE_FILE);
+    let sealed = match fs::read(&path) {
+        Ok(sealed) => sealed,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(error) => return Err(format!("Failed to read {}: {error}", path.display())),
+    };
+    if sealed.len() < NONCE_LEN {
+        return Err(format!("{} is truncated", path.display()));
+    }
+
+    let key = secret_key(directory, false)?;
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| format!("{} has an invalid nonce", path.display()))?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::empty(), &mut in_out)
+        .map_err(|_| format!("Failed to decrypt {}", path.display()))?;
+    serde_json::from_slice(plaintext)
+        .map_err(|error| format!("Failed to parse {}: {error}", path.display()))
+}
+
+fn save_secrets(directory: &Path, secrets: &BTreeMap<String, String>) -> Result<(), String> {
+    fs::create_dir_all(directory)
+        .map_err(|error| format!("Failed to create {}: {error}", directory.display()))?;
+    let key = secret_key(directory, true)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Failed to generate a nonce".to_string())?;
+
+    let mut in_out = serde_json::to_vec(secrets)
+        .map_err(|error| format!("Failed to serialize secrets: {error}"))?;
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::empty(),
+        &mut in_out,
+    )
+    .map_err(|_| "Failed to encrypt secrets".to_string())?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&in_out);
+    let path = directory.join(SECRET_STORE_FILE);
+    write_private_file(&path, &sealed)
+}
+
+fn secret_key(directory: &Path, create: bool) -> Result<LessSafeKey, String> {
+    let path = directory.join(SECRET_KEY_FILE);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(error) if create && error.kind() == std::io::ErrorKind::NotFound => {
+            let mut bytes = vec![0u8; SECRET_KEY_LEN];
+            SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| "Failed to generate a secret store key".to_string())?;
+            write_private_file(&path, &bytes)?;
+            bytes
+        }
+        Err(error) => return Err(format!("Failed to read {}: {error}", path.display())),
+    };
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &bytes)
+        .map_err(|_| format!("{} is not a valid key", path.display()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn write_private_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|error| format!("Failed to write {}: {error}", path.display()))?;
+    std::io::Write::write_all(&mut file, contents)
+        .map_err(|error| format!("Failed to write {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_secrets, parse_flag, save_secrets, SECRET_STORE_FILE};
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn secret_store_round_trips_and_rejects_tampering() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        let directory = std::env::temp_dir().join(format!("clarity-portable-test-{nanos}"));
+
+        assert!(load_secrets(&directory).expect("empty store").is_empty());
+
+        let secrets = BTreeMap::from([("profile:1:password".to_string(), "hunter2".to_string())]);
+        save_secrets(&directory, &secrets).expect("save");
+        let sealed = fs::read(directory.join(SECRET_STORE_FILE)).expect("read store");
+        assert!(!sealed.windows(7).any(|window| window == b"hunter2"));
+        assert_eq!(load_secrets(&directory).expect("load"), secrets);
+
+        let mut tampered = sealed;
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        fs::write(directory.join(SECRET_STORE_FILE), tampered).expect("tamper");
+        assert!(load_secrets(&directory).is_err());
+
+        let _ = fs::remove_dir_all(&directory);
+        assert_eq!(parse_flag(" TRUE "), Some(true));
+        assert_eq!(parse_flag("0"), Some(false));
+        assert_eq!(parse_flag(""), None);
+    }
+}
@@ -1,16 +1,25 @@
+use crate::ldap_naming;
+use crate::oracle_wallets;
+use crate::portable;
+use crate::tnsnames;
 use crate::types::{
-    ConnectionProfile, DatabaseProvider, DbConnectionProfile, OracleAuthMode,
-    OracleConnectionOptions, StoredConnectionProfile,
+    ConnectionProfile, DatabaseProvider, DbConnectConnection, DbConnectRequest,
+    DbConnectionProfile, ExternalConnectOptions, LibsqlConnectOptions, NetworkConnectOptions,
+    NetworkConnectionOptions, OracleAuthMode, OracleConnectOptions, OracleConnectionOptions,
+    OracleProtocol, SnowflakeConnectOptions, SshTunnelConnectOptions, StoredConnectionProfile,
 };
+use crate::validation::validate_connect_request;
 use keyring::{Entry, Error as KeyringError};
 use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
-const PROFILE_STORE_FILE: &str = "connection_profiles.json";
+pub(crate) const PROFILE_STORE_FILE: &str = "connection_profiles.json";
 const KEYRING_SERVICE: &str = "com.waldencorp.clarity";
 const KEYRING_AI_API_KEY_ACCOUNT: &str = "ai:openai:api_key";
+const KEYRING_PROBE_ACCOUNT: &str = "diagnostics:probe";
+const KEYRING_PROBE_VALUE: &str = "clarity-keyring-probe";
 
 pub(crate) fn read_profiles(app: &AppHandle) -> Result<Vec<StoredConnectionProfile>, String> {
     let path = profiles_file_path(app)?;
@@ -25,7 +34,138 @@ pub(crate) fn write_profiles(
     write_profiles_to_path(path.as_path(), profiles)
 }
 
-fn read_profiles_from_path(path: &Path) -> Result<Vec<StoredConnectionProfile>, String> {
+/// Validates a connect request and resolves wallet, TNS alias and LDAP references.
+pub(crate) fn resolve_connect_request(
+    app: &AppHandle,
+    request: DbConnectRequest,
+) -> Result<DbConnectRequest, String> {
+    validate_connect_request(&request)?;
+    let request = oracle_wallets::resolve_connect_request(app, request)?;
+    let request = tnsnames::resolve_connect_request(request)?;
+    ldap_naming::resolve_connect_request(request)
+}
+
+/// Builds a connect request for a saved profile with its stored secrets, for connections
+/// opened without the UI such as scheduled jobs.
+pub(crate) fn profile_connect_request(
+    app: &AppHandle,
+    profile: &StoredConnectionProfile,
+) -> Result<DbConnectRequest, String> {
+    let network = |options: &NetworkConnectionOptions| NetworkConnectOptions {
+        host: options.host.clone(),
+        port: options.port,
+        database: options.database.clone(),
+        username: options.username.clone(),
+        password: String::new(),
+        schema: options.schema.clone(),
+    };
+    let connection = match &profile.connection {
+        DbConnectionProfile::Oracle(options) => DbConnectConnection::Oracle(OracleConnectOptions {
+            host: options.host.clone(),
+            port: options.port,
+            service_name: options.service_name.clone(),
+            username: options.username.clone(),
+            password: String::new(),
+            schema: options.schema.clone(),
+            oracle_auth_mode: options.oracle_auth_mode,
+            proxy_user: options.proxy_user.clone(),
+            oracle_client_lib_dir: options.oracle_client_lib_dir.clone(),
+            edition: options.edition.clone(),
+            protocol: options.protocol,
+            tls: options.tls.clone(),
+            wallet: options.wallet.clone(),
+            tns_alias: options.tns_alias.clone(),
+            ldap: options.ldap.clone(),
+            failover_hosts: options.failover_hosts.clone(),
+            failover: options.failover.clone(),
+            connect_descriptor: options.connect_descriptor.clone(),
+            external_password_store: options.external_password_store.clone(),
+        }),
+        DbConnectionProfile::Postgres(options) => DbConnectConnection::Postgres(network(options)),
+        DbConnectionProfile::Mysql(options) => DbConnectConnection::Mysql(network(options)),
+        DbConnectionProfile::Clickhouse(options) => {
+            DbConnectConnection::Clickhouse(network(options))
+        }
+        DbConnectionProfile::Sqlite(options) => DbConnectConnection::Sqlite(options.clone()),
+        DbConnectionProfile::Duckdb(options) => DbConnectConnection::Duckdb(options.clone()),
+        DbConnectionProfile::Snowflake(options) => {
+            DbConnectConnection::Snowflake(SnowflakeConnectOptions {
+                account: options.account.clone(),
+                username: options.username.clone(),
+                token: String::new(),
+                database: options.database.clone(),
+                schema: options.schema.clone(),
+                warehouse: options.warehouse.clone(),
+                role: options.role.clone(),
+                token_type: options.token_type,
+            })
+        }
+        DbConnectionProfile::Libsql(options) => DbConnectConnection::Libsql(LibsqlConnectOptions {
+            url: options.url.clone(),
+            auth_token: String::new(),
+        }),
+        DbConnectionProfile::External(options) => {
+            DbConnectConnection::External(ExternalConnectOptions {
+                provider_id: options.provider_id.clone(),
+                password: String::new(),
+                options: options.options.clone(),
+            })
+        }
+    };
+    let mut request = DbConnectRequest {
+        connection,
+        ssh_tunnel: profile
+            .ssh_tunnel
+            .as_ref()
+            .map(|tunnel| SshTunnelConnectOptions {
+                host: tunnel.host.clone(),
+                port: tunnel.port,
+                username: tunnel.username.clone(),
+                identity_file: tunnel.identity_file.clone(),
+                password: None,
+                cli_path: tunnel.cli_path.clone(),
+            }),
+        timestamp_tz_mode: Default::default(),
+        prefetch_ai_schema_context: false,
+        read_only: profile.read_only,
+        environment: profile.environment.clone(),
+        drop_allowlist: profile.drop_allowlist.clone(),
+        connect_timeout_secs: None,
+        connect_attempts: None,
+        retry_delay_millis: None,
+        startup_sql: profile.startup_sql.clone(),
+        pool_size: None,
+        profile_id: Some(profile.id.clone()),
+        reader_endpoint: profile.reader_endpoint.clone(),
+    };
+    fill_profile_secrets(&mut request, &profile.id)?;
+    resolve_connect_request(app, request)
+}
+
+/// Fills an empty password and SSH password from the profile's stored secrets.
+pub(crate) fn fill_profile_secrets(
+    request: &mut DbConnectRequest,
+    profile_id: &str,
+) -> Result<(), String> {
+    if profile_id.is_empty() {
+        return Ok(());
+    }
+    if let Some(secret) = request.connection.secret_mut() {
+        if secret.is_empty() {
+            if let Some(stored) = read_profile_secret(profile_id)? {
+                *secret = stored;
+            }
+        }
+    }
+    if let Some(tunnel) = request.ssh_tunnel.as_mut() {
+        if tunnel.password.as_deref().is_none_or(str::is_empty) {
+            tunnel.password = read_profile_ssh_secret(profile_id)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn read_profiles_from_path(path: &Path) -> Result<Vec<StoredConnectionProfile>, String> {
     if !path.exists() {
         return Ok(Vec::new());
     }
@@ -62,25 +202,38 @@ pub(crate) fn to_connection_profile(profile: StoredConnectionProfile) -> Connect
         .ok()
         .flatten()
         .is_some();
+    let has_ssh_password = profile.ssh_tunnel.is_some()
+        && read_profile_ssh_secret(profile.id.as_str())
+            .ok()
+            .flatten()
+            .is_some();
     ConnectionProfile {
         id: profile.id,
         name: profile.name,
         connection: profile.connection,
+        ssh_tunnel: profile.ssh_tunnel,
+        read_only: profile.read_only,
+        environment: profile.environment,
+        startup_sql: profile.startup_sql,
+        warm_standby: profile.warm_standby,
+        drop_allowlist: profile.drop_allowlist,
+        reader_endpoint: profile.reader_endpoint,
         has_password,
+        has_ssh_password,
     }
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum StoredConnectionProfileRecord {
-    Current(StoredConnectionProfile),
+    Current(Box<StoredConnectionProfile>),
     Legacy(LegacyStoredConnectionProfile),
 }
 
 impl StoredConnectionProfileRecord {
     fn into_current(self) -> StoredConnectionProfile {
         match self {
-            StoredConnectionProfileRecord::Current(profile) => profile,
+            StoredConnectionProfileRecord::Current(profile) => *profile,
             StoredConnectionProfileRecord::Legacy(profile) => profile.into_current(),
         }
     }
@@ -111,6 +264,18 @@ impl LegacyStoredConnectionProfile {
                 username: self.username,
                 schema: self.schema,
                 oracle_auth_mode: self.oracle_auth_mode,
+                edition: None,
+                proxy_user: None,
+                protocol: OracleProtocol::Tcp,
+                tls: None,
+                wallet: None,
+                tns_alias: None,
+                ldap: None,
+                failover_hosts: Vec::new(),
+                failover: None,
+                connect_descriptor: None,
+                external_password_store: None,
+                oracle_client_lib_dir: None,
             }),
             DatabaseProvider::Postgres => {
                 DbConnectionProfile::Postgres(crate::types::NetworkConnectionOptions {
@@ -130,23 +295,71 @@ impl LegacyStoredConnectionProfile {
                     schema: Some(self.schema),
                 })
             }
+            DatabaseProvider::Clickhouse => {
+                DbConnectionProfile::Clickhouse(crate::types::NetworkConnectionOptions {
+                    host: self.host,
+                    port: self.port,
+                    database: self.service_name,
+                    username: self.username,
+                    schema: Some(self.schema),
+                })
+            }
             DatabaseProvider::Sqlite => {
                 DbConnectionProfile::Sqlite(crate::types::SqliteConnectionOptions {
                     file_path: self.service_name,
                 })
             }
+            DatabaseProvider::Duckdb => {
+                DbConnectionProfile::Duckdb(crate::types::DuckdbConnectionOptions {
+                    file_path: self.service_name,
+                    cli_path: None,
+                })
+            }
+            DatabaseProvider::Snowflake => {
+                DbConnectionProfile::Snowflake(crate::types::SnowflakeConnectionOptions {
+                    account: self.host,
+                    username: self.username,
+                    database: self.service_name,
+                    schema: Some(self.schema),
+                    warehouse: None,
+                    role: None,
+                    token_type: Default::default(),
+                })
+            }
+            DatabaseProvider::Libsql => {
+                DbConnectionProfile::Libsql(crate::types::LibsqlConnectionOptions {
+                    url: self.host,
+                })
+            }
+            DatabaseProvider::External => {
+                DbConnectionProfile::External(crate::types::ExternalConnectionOptions {
+                    provider_id: self.host,
+                    options: Default::default(),
+                })
+            }
         };
 
         StoredConnectionProfile {
             id: self.id,
             name: self.name,
             connection,
+            ssh_tunnel: None,
+            read_only: false,
+            environment: None,
+            startup_sql: None,
+            warm_standby: false,
+            drop_allowlist: Vec::new(),
+            reader_endpoint: None,
         }
     }
 }
 
 pub(crate) fn read_profile_secret(profile_id: &str) -> Result<Option<String>, String> {
-    match keyring_entry(profile_id)?.get_password() {
+    let account = profile_account(profile_id);
+    if portable::is_enabled() {
+        return portable::read_secret(account.as_str());
+    }
+    match keyring_entry(account.as_str())?.get_password() {
         Ok(password) => Ok(Some(password)),
         Err(KeyringError::NoEntry) => Ok(None),
         Err(error) => Err(format!("Failed to read keychain secret: {error}")),
@@ -154,19 +367,63 @@ pub(crate) fn read_profile_secret(profile_id: &str) -> Result<Option<String>, St
 }
 
 pub(crate) fn write_profile_secret(profile_id: &str, password: &str) -> Result<(), String> {
-    keyring_entry(profile_id)?
+    let account = profile_account(profile_id);
+    if portable::is_enabled() {
+        return portable::write_secret(account.as_str(), password);
+    }
+    keyring_entry(account.as_str())?
         .set_password(password)
         .map_err(|error| format!("Failed to write keychain secret: {error}"))
 }
 
 pub(crate) fn clear_profile_secret(profile_id: &str) -> Result<(), String> {
-    match keyring_entry(profile_id)?.delete_credential() {
+    let account = profile_account(profile_id);
+    if portable::is_enabled() {
+        return portable::clear_secret(account.as_str());
+    }
+    match keyring_entry(account.as_str())?.delete_credential() {
+        Ok(()) | Err(KeyringError::NoEntry) => Ok(()),
+        Err(error) => Err(format!("Failed to clear keychain secret: {error}")),
+    }
+}
+
+pub(crate) fn read_profile_ssh_secret(profile_id: &str) -> Result<Option<String>, String> {
+    let account = ssh_account(profile_id);
+    if portable::is_enabled() {
+        return portable::read_secret(account.as_str());
+    }
+    match keyring_entry(account.as_str())?.get_password() {
+        Ok(password) => Ok(Some(password)),
+        Err(KeyringError::NoEntry) => Ok(None),
+        Err(error) => Err(format!("Failed to read keychain secret: {error}")),
+    }
+}
+
+pub(crate) fn write_profile_ssh_secret(profile_id: &str, password: &str) -> Result<(), String> {
+    let account = ssh_account(profile_id);
+    if portable::is_enabled() {
+        return portable::write_secret(account.as_str(), password);
+    }
+    keyring_entry(account.as_str())?
+        .set_password(password)
+        .map_err(|error| format!("Failed to write keychain secret: {error}"))
+}
+
+pub(crate) fn clear_profile_ssh_secret(profile_id: &str) -> Result<(), String> {
+    let account = ssh_account(profile_id);
+    if portable::is_enabled() {
+        return portable::clear_secret(account.as_str());
+    }
+    match keyring_entry(account.as_str())?.delete_credential() {
         Ok(()) | Err(KeyringError::NoEntry) => Ok(()),
         Err(error) => Err(format!("Failed to clear keychain secret: {error}")),
     }
 }
 
 pub(crate) fn read_ai_api_key() -> Result<Option<String>, String> {
+    if portable::is_enabled() {
+        return portable::read_secret(KEYRING_AI_API_KEY_ACCOUNT);
+    }
     match ai_keyring_entry()?.get_password() {
         Ok(value) => Ok(Some(value)),
         Err(KeyringError::NoEntry) => Ok(None),
@@ -175,31 +432,82 @@ pub(crate) fn read_ai_api_key() -> Result<Option<String>, String> {
 }
 
 pub(crate) fn write_ai_api_key(api_key: &str) -> Result<(), String> {
+    if portable::is_enabled() {
+        return portable::write_secret(KEYRING_AI_API_KEY_ACCOUNT, api_key);
+    }
     ai_keyring_entry()?
         .set_password(api_key)
         .map_err(|error| format!("Failed to write AI API key to keychain: {error}"))
 }
 
 pub(crate) fn clear_ai_api_key() -> Result<(), String> {
+    if portable::is_enabled() {
+        return portable::clear_secret(KEYRING_AI_API_KEY_ACCOUNT);
+    }
     match ai_keyring_entry()?.delete_credential() {
         Ok(()) | Err(KeyringError::NoEntry) => Ok(()),
         Err(error) => Err(format!("Failed to clear AI API key from keychain: {error}")),
     }
 }
 
+/// Writes, reads back and deletes a throwaway secret to confirm the secret store is usable:
+/// the keychain, or the encrypted file in portable mode.
+pub(crate) fn probe_keyring() -> Result<(), String> {
+    if portable::is_enabled() {
+        portable::write_secret(KEYRING_PROBE_ACCOUNT, KEYRING_PROBE_VALUE)?;
+        let read_back = portable::read_secret(KEYRING_PROBE_ACCOUNT);
+        portable::clear_secret(KEYRING_PROBE_ACCOUNT)?;
+        return match read_back? {
+            Some(value) if value == KEYRING_PROBE_VALUE => Ok(()),
+            _ => Err("The secret store returned a different secret than was written.".to_string()),
+        };
+    }
+
+    let entry = keyring_entry(KEYRING_PROBE_ACCOUNT)?;
+    entry
+        .set_password(KEYRING_PROBE_VALUE)
+        .map_err(|error| format!("Failed to write keychain secret: {error}"))?;
+    let read_back = entry
+        .get_password()
+        .map_err(|error| format!("Failed to read keychain secret: {error}"));
+    let _ = entry.delete_credential();
+
+    if read_back? != KEYRING_PROBE_VALUE {
+        return Err("The keychain returned a different secret than was written.".to_string());
+    }
+    Ok(())
+}
+
 fn profiles_file_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let mut app_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
+    app_data_file_path(app, PROFILE_STORE_FILE)
+}
+
+/// Resolves a file in the OS app data directory, or in the portable data directory next to
+/// the executable when running portable.
+pub(crate) fn app_data_file_path(app: &AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let mut app_dir = match portable::data_dir() {
+        Some(directory) => directory.to_path_buf(),
+        None => app
+            .path()
+            .app_data_dir()
+            .map_err(|error| format!("Failed to resolve app data directory: {error}"))?,
+    };
     fs::create_dir_all(&app_dir)
         .map_err(|error| format!("Failed to create app data directory: {error}"))?;
-    app_dir.push(PROFILE_STORE_FILE);
+    app_dir.push(file_name);
     Ok(app_dir)
 }
 
-fn keyring_entry(profile_id: &str) -> Result<Entry, String> {
-    Entry::new(KEYRING_SERVICE, &format!("profile:{profile_id}:password"))
+fn profile_account(profile_id: &str) -> String {
+    format!("profile:{profile_id}:password")
+}
+
+fn ssh_account(profile_id: &str) -> String {
+    format!("profile:{profile_id}:ssh-password")
+}
+
+fn keyring_entry(account: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, account)
         .map_err(|error| format!("Failed to initialize keyring entry: {error}"))
 }
 
@@ -257,7 +565,26 @@ mod tests {
                     username: "system".to_string(),
                     schema: "APP".to_string(),
                     oracle_auth_mode: OracleAuthMode::Normal,
+                    edition: None,
+                    proxy_user: None,
+                    protocol: Default::default(),
+                    tls: None,
+                    wallet: None,
+                    tns_alias: None,
+                    ldap: None,
+                    failover_hosts: Vec::new(),
+                    failover: None,
+                    connect_descriptor: None,
+                    external_password_store: None,
+                    oracle_client_lib_dir: None,
                 }),
+                ssh_tunnel: None,
+                read_only: false,
+                environment: None,
+                startup_sql: None,
+                warm_standby: false,
+                drop_allowlist: Vec::new(),
+                reader_endpoint: None,
             },
             StoredConnectionProfile {
                 id: "profile-2".to_string(),
@@ -269,6 +596,13 @@ mod tests {
                     username: "app_user".to_string(),
                     schema: Some("public".to_string()),
                 }),
+                ssh_tunnel: None,
+                read_only: false,
+                environment: None,
+                startup_sql: None,
+                warm_standby: false,
+                drop_allowlist: Vec::new(),
+                reader_endpoint: None,
             },
         ]
     }
@@ -0,0 +1,625 @@
+use super::common::{
+    effective_query_row_limit, note_lossy_conversion, null_flags_if_any, row_matches_query_filters,
+    MAX_EXPLORER_OBJECTS,
+};
+use crate::data_export::{ExportRowSink, ExportValue};
+use crate::sql_ident;
+use crate::types::{
+    DatabaseProvider, DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry,
+    DbObjectRef, DbQueryRequest, DbQueryResult, NetworkConnectOptions,
+};
+use reqwest::blocking::{Client, Response};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+
+const DEFAULT_HTTP_PORT: u16 = 8123;
+const DEFAULT_HTTPS_PORT: u16 = 8443;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(300);
+const NULL_DISPLAY: &str = "NULL";
+// Header line with names, one with types, then one JSON array of strings per row. Reading
+// it line by line lets a query stop at the row limit without buffering the whole result.
+const RESULT_FORMAT: &str = "JSONCompactStringsEachRowWithNamesAndTypes";
+
+// The HTTP interface is stateless: each request names the database, and statements are
+// not transactional.
+pub(crate) struct ClickhouseSession {
+    client: Client,
+    base_url: String,
+    username: String,
+    password: String,
+    target_schema: String,
+}
+
+/// One result set read from the HTTP response, or `None` when the statement returned no
+/// rows (DDL, `INSERT`).
+struct ClickhouseResult {
+    columns: Vec<String>,
+    types: Vec<String>,
+    reader: Option<BufReader<Response>>,
+    written_rows: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct QuerySummary {
+    written_rows: String,
+}
+
+pub(crate) fn connect(
+    request: &NetworkConnectOptions,
+) -> Result<(ClickhouseSession, String, String), DbConnectError> {
+    let host = request.host.trim();
+    let username = request.username.trim();
+    // ClickHouse databases play the role of schemas; a schema, when given, picks the database.
+    let schema = request
+        .schema
+        .as_deref()
+        .map(str::trim)
+        .filter(|schema| !schema.is_empty())
+        .unwrap_or(request.database.trim())
+        .to_string();
+    let client = Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|error| {
+            DbConnectError::general(format!("Failed to initialize HTTP client: {error}"))
+        })?;
+    let session = ClickhouseSession {
+        client,
+        base_url: server_base_url(host, request.port),
+        username: username.to_string(),
+        password: request.password.clone(),
+        target_schema: schema,
+    };
+
+    let rows = query_text_rows(
+        &session,
+        "SELECT currentUser(), currentDatabase(), version()",
+        &[],
+    )
+    .map_err(|error| {
+        DbConnectError::general(format!(
+            "Failed to connect to ClickHouse at {}: {error}",
+            session.base_url
+        ))
+    })?;
+    let [user, database, version]: [String; 3] = rows
+        .into_iter()
+        .next()
+        .and_then(|row| row.try_into().ok())
+        .ok_or_else(|| DbConnectError::general("ClickHouse did not return session details."))?;
+
+    let display_name = format!("{user}@{host} [{database}] ClickHouse {version}");
+    let schema = session.target_schema.clone();
+    Ok((session, display_name, schema))
+}
+
+pub(crate) fn server_version(session: &ClickhouseSession) -> Result<String, String> {
+    query_text_rows(session, "SELECT version()", &[])?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| "ClickHouse did not report a version".to_string())
+}
+
+pub(crate) fn list_objects(session: &ClickhouseSession) -> Result<Vec<DbObjectEntry>, String> {
+    let sql = format!(
+        r#"
+        SELECT database,
+               multiIf(engine = 'View', 'VIEW',
+                       engine = 'MaterializedView', 'MATERIALIZED VIEW',
+                       engine = 'Dictionary', 'DICTIONARY',
+                       'TABLE') AS object_type,
+               name
+        FROM system.tables
+        WHERE database = {{schema:String}} AND NOT is_temporary
+        ORDER BY object_type, name
+        LIMIT {MAX_EXPLORER_OBJECTS}
+    "#
+    );
+    let rows = query_text_rows(
+        session,
+        sql.as_str(),
+        &[("schema", session.target_schema.as_str())],
+    )?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_type, object_name]: [String; 3] = row.try_into().ok()?;
+            Some(DbObjectEntry {
+                schema,
+                object_type,
+                object_name,
+                status: None,
+                invalid_reason: None,
+                edition_name: None,
+                created: None,
+                last_ddl_time: None,
+                temporary: false,
+                secondary: false,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &ClickhouseSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = r#"
+        SELECT database, table, name, type, if(type LIKE 'Nullable(%', 'Y', 'N')
+        FROM system.columns
+        WHERE database = {schema:String}
+        ORDER BY table, position
+    "#;
+    let rows = query_text_rows(session, sql, &[("schema", session.target_schema.as_str())])?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_name, column_name, data_type, nullable]: [String; 5] =
+                row.try_into().ok()?;
+            Some(DbObjectColumnEntry {
+                schema,
+                object_name,
+                column_name,
+                data_type,
+                nullable,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn get_object_ddl(
+    session: &ClickhouseSession,
+    request: &DbObjectRef,
+) -> Result<String, String> {
+    let schema = request.schema.trim();
+    ensure_schema_is_in_scope(schema, session)?;
+    let object_name = request.object_name.trim();
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+    let keyword = match object_type.as_str() {
+        // SHOW CREATE TABLE also covers views and materialized views.
+        "TABLE" | "VIEW" | "MATERIALIZED VIEW" => "TABLE",
+        "DICTIONARY" => "DICTIONARY",
+        _ => {
+            return Err(format!(
+                "DDL retrieval for {object_type} objects is not supported for ClickHouse."
+            ))
+        }
+    };
+
+    let sql = format!(
+        "SHOW CREATE {keyword} {}",
+        sql_ident::qualified_name(DatabaseProvider::Clickhouse, Some(schema), object_name)
+    );
+    query_text_rows(session, sql.as_str(), &[])?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| format!("{object_type} {schema}.{object_name} was not found"))
+}
+
+pub(crate) fn run_query(
+    session: &ClickhouseSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let result = execute(session, sql, &[])?;
+    if result.reader.is_none() {
+        return Ok(statement_result(result.written_rows));
+    }
+
+    let columns = result.columns.clone();
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    visit_rows(result, |row| {
+        if rows.len() >= row_limit {
+            truncated = true;
+            return Ok(false);
+        }
+        null_flags.push(row.iter().map(Option::is_none).collect());
+        rows.push(format_row(row));
+        Ok(true)
+    })?;
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &ClickhouseSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let result = execute(session, sql, &[])?;
+    if result.reader.is_none() {
+        return Err("Filtering is only available for query result sets.".to_string());
+    }
+
+    let columns = result.columns.clone();
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    visit_rows(result, |row| {
+        let nulls = row.iter().map(Option::is_none).collect();
+        let values = format_row(row);
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            return Ok(true);
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn stream_query_rows(
+    session: &ClickhouseSession,
+    sql: &str,
+    sink: &mut dyn ExportRowSink,
+) -> Result<u64, String> {
+    let result = execute(session, sql, &[])?;
+    if result.reader.is_none() {
+        return Err("Only queries can be exported".to_string());
+    }
+    sink.columns(&result.columns)?;
+
+    let types = result.types.clone();
+    let mut row_count = 0_u64;
+    visit_rows(result, |row| {
+        let values = types
+            .iter()
+            .zip(row)
+            .map(|(type_name, value)| export_value(type_name, value))
+            .collect::<Vec<_>>();
+        sink.row(&values)?;
+        row_count += 1;
+        Ok(true)
+    })?;
+
+    Ok(row_count)
+}
+
+/// Posts a statement with `params` bound to `{name:Type}` placeholders and reads the two
+/// header lines of the result, leaving the rows on the response stream.
+fn execute(
+    session: &ClickhouseSession,
+    sql: &str,
+    params: &[(&str, &str)],
+) -> Result<ClickhouseResult, String> {
+    let mut query = vec![
+        ("database".to_string(), session.target_schema.clone()),
+        ("default_format".to_string(), RESULT_FORMAT.to_string()),
+    ];
+    query.extend(
+        params
+            .iter()
+            .map(|(name, value)| (format!("param_{name}"), value.to_string())),
+    );
+
+    let response = session
+        .client
+        .post(format!("{}/", session.base_url))
+        .query(&query)
+        .header("X-ClickHouse-User", session.username.as_str())
+        .header("X-ClickHouse-Key", session.password.as_str())
+        .body(sql.trim().trim_end_matches(';').to_string())
+        .send()
+        .map_err(|error| format!("ClickHouse request failed: {error}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        let message = body.trim();
+        return Err(if message.is_empty() {
+            format!("ClickHouse returned HTTP {status}")
+        } else {
+            message.to_string()
+        });
+    }
+
+    let written_rows = response
+        .headers()
+        .get("X-ClickHouse-Summary")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| serde_json::from_str::<QuerySummary>(value).ok())
+        .and_then(|summary| summary.written_rows.parse::<u64>().ok());
+
+    let mut reader = BufReader::new(response);
+    let Some(columns) = read_row(&mut reader)? else {
+        return Ok(ClickhouseResult {
+            columns: Vec::new(),
+            types: Vec::new(),
+            reader: None,
+            written_rows,
+        });
+    };
+    let types = read_row(&mut reader)?.unwrap_or_default();
+
+    Ok(ClickhouseResult {
+        columns: columns.into_iter().map(Option::unwrap_or_default).collect(),
+        types: types.into_iter().map(Option::unwrap_or_default).collect(),
+        reader: Some(reader),
+        written_rows,
+    })
+}
+
+/// Reads the next JSON array line; `None` at the end of the stream.
+fn read_row(reader: &mut BufReader<Response>) -> Result<Option<Vec<Option<String>>>, String> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|error| format!("Failed to read ClickHouse response: {error}"))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+
+    parse_row_line(line.as_str()).map(Some)
+}
+
+fn parse_row_line(line: &str) -> Result<Vec<Option<String>>, String> {
+    let line = line.trim();
+    // Errors raised after the first block are appended to the stream as plain text.
+    if !line.starts_with('[') {
+        return Err(if line.contains("Exception") {
+            line.to_string()
+        } else {
+            format!("Unexpected ClickHouse output; remove any FORMAT clause from the query: {line}")
+        });
+    }
+    serde_json::from_str::<Vec<Option<String>>>(line)
+        .map_err(|error| format!("Failed to parse ClickHouse row: {error}"))
+}
+
+/// Calls `visit` for each remaining row until it returns `false` or the stream ends.
+/// Stopping early drops the response, which makes the server cancel the query.
+fn visit_rows(
+    mut result: ClickhouseResult,
+    mut visit: impl FnMut(Vec<Option<String>>) -> Result<bool, String>,
+) -> Result<(), String> {
+    let Some(reader) = result.reader.as_mut() else {
+        return Ok(());
+    };
+    while let Some(row) = read_row(reader)? {
+        if !visit(row)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn query_text_rows(
+    session: &ClickhouseSession,
+    sql: &str,
+    params: &[(&str, &str)],
+) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    visit_rows(execute(session, sql, params)?, |row| {
+        rows.push(row.into_iter().map(Option::unwrap_or_default).collect());
+        Ok(true)
+    })?;
+    Ok(rows)
+}
+
+fn statement_result(written_rows: Option<u64>) -> DbQueryResult {
+    match written_rows.filter(|rows| *rows > 0) {
+        Some(rows_affected) => DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: Some(rows_affected),
+            message: format!("Statement executed. {rows_affected} row(s) affected."),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        },
+        None => DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: None,
+            message: "Statement executed.".to_string(),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        },
+    }
+}
+
+fn format_row(row: Vec<Option<String>>) -> Vec<String> {
+    row.into_iter()
+        .map(|value| value.unwrap_or_else(|| NULL_DISPLAY.to_string()))
+        .collect()
+}
+
+fn export_value(type_name: &str, value: Option<String>) -> ExportValue {
+    let Some(value) = value else {
+        return ExportValue::Null;
+    };
+    let base_type = type_name
+        .strip_prefix("Nullable(")
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(type_name);
+    if ["Int", "UInt", "Float", "Decimal"]
+        .iter()
+        .any(|prefix| base_type.starts_with(prefix))
+    {
+        ExportValue::Number(value)
+    } else {
+        ExportValue::Text(value)
+    }
+}
+
+/// Accepts a bare host or a URL; `https://` switches the default port to 8443.
+fn server_base_url(host: &str, port: Option<u16>) -> String {
+    let host = host.trim().trim_end_matches('/');
+    let (scheme, authority) = match host.split_once("://") {
+        Some((scheme, authority)) => (scheme.to_ascii_lowercase(), authority),
+        None => ("http".to_string(), host),
+    };
+    let default_port = if scheme == "https" {
+        DEFAULT_HTTPS_PORT
+    } else {
+        DEFAULT_HTTP_PORT
+    };
+    let has_port = authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    if has_port {
+        format!("{scheme}://{authority}")
+    } else {
+        format!("{scheme}://{authority}:{}", port.unwrap_or(default_port))
+    }
+}
+
+fn ensure_schema_is_in_scope(schema: &str, session: &ClickhouseSession) -> Result<(), String> {
+    if schema != session.target_schema {
+        return Err(format!(
+            "Connected database is {}. Object access is limited to that database.",
+            session.target_schema
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{export_value, parse_row_line, server_base_url};
+    use crate::data_export::ExportValue;
+
+    #[test]
+    fn builds_server_urls() {
+        assert_eq!(server_base_url("ch.local", None), "http://ch.local:8123");
+        assert_eq!(
+            server_base_url("ch.local", Some(9000)),
+            "http://ch.local:9000"
+        );
+        assert_eq!(
+            server_base_url("HTTPS://ch.example.com/", None),
+            "https://ch.example.com:8443"
+        );
+        assert_eq!(
+            server_base_url("https://ch.example.com:443", Some(8443)),
+            "https://ch.example.com:443"
+        );
+    }
+
+    #[test]
+    fn parses_result_lines_and_export_types() {
+        assert_eq!(
+            parse_row_line("[\"1\",null,\"a\\tb\"]\n").expect("row"),
+            vec![Some("1".to_string()), None, Some("a\tb".to_string())]
+        );
+        assert!(
+            parse_row_line("Code: 60. DB::Exception: Table x doesn't exist")
+                .expect_err("error text")
+                .starts_with("Code: 60.")
+        );
+
+        assert!(matches!(
+            export_value("Nullable(UInt64)", Some("18446744073709551615".to_string())),
+            ExportValue::Number(_)
+        ));
+        assert!(matches!(
+            export_value("DateTime", Some("2024-03-01 00:30:00".to_string())),
+            ExportValue::Text(_)
+        ));
+        assert!(matches!(export_value("String", None), ExportValue::Null));
+    }
+}
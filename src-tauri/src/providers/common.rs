@@ -0,0 +1,250 @@
+use crate::types::{DatabaseProvider, DbConnectRequest, DbQueryRequest};
+use std::time::Duration;
+
+pub(super) const MAX_EXPLORER_OBJECTS: u32 = 5000;
+pub(super) const DEFAULT_QUERY_ROW_LIMIT: u32 = 1000;
+pub(super) const MAX_QUERY_ROW_LIMIT: u32 = 10000;
+pub(super) const DEFAULT_SCHEMA_SEARCH_LIMIT: u32 = 200;
+pub(super) const MAX_SCHEMA_SEARCH_RESULTS: u32 = 1000;
+pub(super) const MAX_DDL_SEARCH_OBJECTS: u32 = 2000;
+pub(super) const MAX_SEARCH_SNIPPET_CHARS: usize = 220;
+pub(super) const SAMPLE_TIME_LIMIT: Duration = Duration::from_secs(10);
+const DEFAULT_CONNECT_TIMEOUT_SECS: u32 = 30;
+const MAX_CONNECT_TIMEOUT_SECS: u32 = 600;
+const MAX_CONNECT_ATTEMPTS: u32 = 10;
+const MAX_RETRY_DELAY_MILLIS: u64 = 60_000;
+
+/// How long to wait for a connection and how often to try.
+pub(super) struct ConnectRetryPolicy {
+    pub(super) timeout: Duration,
+    pub(super) attempts: u32,
+    pub(super) retry_delay: Duration,
+}
+
+impl ConnectRetryPolicy {
+    pub(super) fn from_request(request: &DbConnectRequest) -> Self {
+        Self {
+            timeout: Duration::from_secs(u64::from(
+                request
+                    .connect_timeout_secs
+                    .unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS)
+                    .clamp(1, MAX_CONNECT_TIMEOUT_SECS),
+            )),
+            attempts: request
+                .connect_attempts
+                .unwrap_or(1)
+                .clamp(1, MAX_CONNECT_ATTEMPTS),
+            retry_delay: Duration::from_millis(
+                request
+                    .retry_delay_millis
+                    .unwrap_or(1000)
+                    .min(MAX_RETRY_DELAY_MILLIS),
+            ),
+        }
+    }
+}
+
+/// Rejected credentials and locked accounts are not retried, so a typo cannot lock the
+/// account through repeated failed logins.
+pub(super) fn is_retryable_connect_error(message: &str) -> bool {
+    const PERMANENT: [&str; 7] = [
+        "ORA-01017",
+        "ORA-28000",
+        "ORA-28001",
+        "ORA-01045",
+        "Access denied",
+        "authentication failed",
+        "password",
+    ];
+    let lower = message.to_ascii_lowercase();
+    !PERMANENT
+        .iter()
+        .any(|marker| lower.contains(&marker.to_ascii_lowercase()))
+}
+
+pub(super) fn effective_query_row_limit(request: &DbQueryRequest) -> usize {
+    request
+        .row_limit
+        .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
+        .clamp(1, MAX_QUERY_ROW_LIMIT) as usize
+}
+
+pub(super) fn row_matches_query_filters(
+    row: &[String],
+    normalized_global_search: &str,
+    normalized_column_filters: &[String],
+) -> bool {
+    if !normalized_global_search.is_empty()
+        && !row
+            .iter()
+            .any(|value| value.to_lowercase().contains(normalized_global_search))
+    {
+        return false;
+    }
+
+    for (column_index, normalized_filter) in normalized_column_filters.iter().enumerate() {
+        if normalized_filter.is_empty() {
+            continue;
+        }
+
+        let cell_value = row
+            .get(column_index)
+            .map(|value| value.as_str())
+            .unwrap_or_default()
+            .to_lowercase();
+        if !cell_value.contains(normalized_filter) {
+            return false;
+        }
+    }
+
+    true
+}
+
+pub(super) fn find_matching_line(text: &str, needle_upper: &str) -> Option<(u32, String)> {
+    for (idx, line) in text.lines().enumerate() {
+        if line.to_ascii_uppercase().contains(needle_upper) {
+            let line_number = (idx + 1).min(u32::MAX as usize) as u32;
+            return Some((line_number, line.trim().to_string()));
+        }
+    }
+
+    None
+}
+
+pub(super) fn truncate_for_snippet(value: &str) -> String {
+    let trimmed = value.trim();
+    if trimmed.chars().count() <= MAX_SEARCH_SNIPPET_CHARS {
+        return trimmed.to_string();
+    }
+
+    let mut snippet = String::new();
+    for (index, ch) in trimmed.chars().enumerate() {
+        if index >= MAX_SEARCH_SNIPPET_CHARS {
+            break;
+        }
+        snippet.push(ch);
+    }
+    snippet.push_str("...");
+
+    snippet
+}
+
+pub(super) fn skip_leading_sql_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    loop {
+        if let Some(line_comment) = rest.strip_prefix("--") {
+            rest = line_comment
+                .split_once('\n')
+                .map_or("", |(_, after)| after)
+                .trim_start();
+        } else if let Some(block_comment) = rest.strip_prefix("/*") {
+            rest = block_comment
+                .split_once("*/")
+                .map_or("", |(_, after)| after)
+                .trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+/// Wraps a SELECT in a subquery that stops after `rows` rows. MySQL and ClickHouse get
+/// their time limit in the SQL itself; ClickHouse returns what it has read when the limit
+/// hits. Oracle's limit is a call timeout set by the caller.
+pub(super) fn sampled_query_sql(
+    provider: DatabaseProvider,
+    sql: &str,
+    rows: usize,
+) -> Result<String, String> {
+    let body = sql.trim().trim_end_matches(';').trim_end();
+    let first_word = skip_leading_sql_comments(body)
+        .trim_start_matches('(')
+        .split(|ch: char| ch.is_whitespace() || ch == '(')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    if first_word != "SELECT" && first_word != "WITH" {
+        return Err("Sample mode only applies to SELECT queries.".to_string());
+    }
+
+    // The body goes on its own lines so a trailing line comment cannot swallow the wrapper.
+    let millis = SAMPLE_TIME_LIMIT.as_millis();
+    let seconds = SAMPLE_TIME_LIMIT.as_secs();
+    match provider {
+        DatabaseProvider::Oracle => Ok(format!(
+            "SELECT * FROM (\n{body}\n) WHERE ROWNUM <= {rows}"
+        )),
+        DatabaseProvider::Mysql => Ok(format!(
+            "SELECT /*+ MAX_EXECUTION_TIME({millis}) */ * FROM (\n{body}\n) AS clarity_sample LIMIT {rows}"
+        )),
+        DatabaseProvider::Clickhouse => Ok(format!(
+            "SELECT * FROM (\n{body}\n) LIMIT {rows} SETTINGS max_execution_time = {seconds}, timeout_overflow_mode = 'break'"
+        )),
+        DatabaseProvider::External => Err(format!(
+            "Sample mode is not supported for provider '{}' yet.",
+            provider.label()
+        )),
+        _ => Ok(format!(
+            "SELECT * FROM (\n{body}\n) AS clarity_sample LIMIT {rows}"
+        )),
+    }
+}
+
+/// Drops per-cell NULL flags when no cell is NULL, keeping such results small.
+pub(super) fn null_flags_if_any(flags: Vec<Vec<bool>>) -> Vec<Vec<bool>> {
+    if flags.iter().flatten().any(|is_null| *is_null) {
+        flags
+    } else {
+        Vec::new()
+    }
+}
+
+/// Flags results containing U+FFFD, which marks text that could not be decoded.
+pub(super) fn note_lossy_conversion(rows: &[Vec<String>], message: &mut String) -> bool {
+    let lossy = rows
+        .iter()
+        .flatten()
+        .any(|value| value.contains(char::REPLACEMENT_CHARACTER));
+    if lossy {
+        message.push_str(
+            " Some values contained bytes that are invalid in the database character set and were replaced with \u{FFFD}.",
+        );
+    }
+    lossy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_retryable_connect_error, sampled_query_sql};
+    use crate::types::DatabaseProvider;
+
+    #[test]
+    fn does_not_retry_rejected_credentials() {
+        assert!(is_retryable_connect_error(
+            "ORA-12170: TNS:Connect timeout occurred (target: //db:1521/ORCL)"
+        ));
+        assert!(!is_retryable_connect_error(
+            "ORA-01017: invalid username/password; logon denied"
+        ));
+        assert!(!is_retryable_connect_error(
+            "Access denied for user 'app'@'10.0.0.1'"
+        ));
+    }
+
+    #[test]
+    fn wraps_selects_for_sampling_per_provider() {
+        assert_eq!(
+            sampled_query_sql(DatabaseProvider::Oracle, "select * from big -- all\n;", 50)
+                .expect("oracle"),
+            "SELECT * FROM (\nselect * from big -- all\n) WHERE ROWNUM <= 50"
+        );
+        assert!(sampled_query_sql(
+            DatabaseProvider::Mysql,
+            "/* x */ with t as (select 1) select * from t",
+            5
+        )
+        .expect("mysql")
+        .starts_with("SELECT /*+ MAX_EXECUTION_TIME(10000) */ * FROM (\n"));
+        assert!(sampled_query_sql(DatabaseProvider::Duckdb, "delete from big", 5).is_err());
+    }
+}
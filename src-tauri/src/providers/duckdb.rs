@@ -0,0 +1,489 @@
+use super::common::{
+    effective_query_row_limit, note_lossy_conversion, null_flags_if_any, row_matches_query_filters,
+    MAX_EXPLORER_OBJECTS,
+};
+use crate::sql_ident;
+use crate::types::{
+    DatabaseProvider, DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry,
+    DbQueryRequest, DbQueryResult, DuckdbConnectionOptions,
+};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const DEFAULT_DUCKDB_CLI: &str = "duckdb";
+const DUCKDB_MAIN_SCHEMA: &str = "main";
+// `-ascii` output mode: unit separator between fields, record separator after each row.
+const FIELD_SEPARATOR: char = '\u{1f}';
+const RECORD_SEPARATOR: char = '\u{1e}';
+const NULL_DISPLAY: &str = "NULL";
+// Printed by the CLI for NULL cells so they can be told apart from the text "NULL".
+const NULL_MARKER: &str = "\u{1c}";
+
+// There is no DuckDB client library in the build, so statements run through the `duckdb`
+// CLI. Every call is a fresh process: files opened as views are re-declared in
+// `setup_sql` each time, and each statement commits on its own.
+pub(crate) struct DuckdbSession {
+    cli_path: String,
+    /// `None` runs against an in-memory database (Parquet and CSV files).
+    database_path: Option<String>,
+    setup_sql: String,
+}
+
+impl DuckdbSession {
+    /// Whether objects created by one statement outlive its CLI process.
+    pub(crate) fn has_database_file(&self) -> bool {
+        self.database_path.is_some()
+    }
+}
+
+/// One result set as printed by the CLI: header row plus data rows.
+#[derive(Default)]
+struct DuckdbOutput {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    null_flags: Vec<Vec<bool>>,
+    lossy: bool,
+}
+
+pub(crate) fn connect(
+    options: &DuckdbConnectionOptions,
+) -> Result<(DuckdbSession, String, String), DbConnectError> {
+    let file_path = options.file_path.trim();
+    let path = Path::new(file_path);
+    if !path.is_file() {
+        return Err(DbConnectError::general(format!(
+            "File '{file_path}' does not exist."
+        )));
+    }
+
+    let cli_path = options
+        .cli_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|cli_path| !cli_path.is_empty())
+        .unwrap_or(DEFAULT_DUCKDB_CLI)
+        .to_string();
+    let (database_path, setup_sql) = open_statements(path).map_err(DbConnectError::general)?;
+    let session = DuckdbSession {
+        cli_path,
+        database_path,
+        setup_sql,
+    };
+    execute(&session, "SELECT 1").map_err(DbConnectError::general)?;
+
+    let display_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| file_path.to_string());
+    Ok((session, display_name, DUCKDB_MAIN_SCHEMA.to_string()))
+}
+
+pub(crate) fn server_version(session: &DuckdbSession) -> Result<String, String> {
+    execute(session, "SELECT version()")?
+        .and_then(|output| output.rows.into_iter().next())
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| "DuckDB did not report a version".to_string())
+}
+
+pub(crate) fn list_objects(session: &DuckdbSession) -> Result<Vec<DbObjectEntry>, String> {
+    let sql = format!(
+        "SELECT table_schema, CASE WHEN table_type = 'VIEW' THEN 'VIEW' ELSE 'TABLE' END, table_name \
+         FROM information_schema.tables \
+         WHERE table_schema NOT IN ('information_schema', 'pg_catalog') \
+         ORDER BY 2, 1, 3 \
+         LIMIT {MAX_EXPLORER_OBJECTS}"
+    );
+    let output = execute(session, sql.as_str())?.unwrap_or_default();
+
+    Ok(output
+        .rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_type, object_name]: [String; 3] = row.try_into().ok()?;
+            Some(DbObjectEntry {
+                schema,
+                object_type,
+                object_name,
+                status: None,
+                invalid_reason: None,
+                edition_name: None,
+                created: None,
+                last_ddl_time: None,
+                temporary: false,
+                secondary: false,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &DuckdbSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = "\
+        SELECT table_schema, table_name, column_name, UPPER(data_type), \
+               CASE WHEN is_nullable = 'YES' THEN 'Y' ELSE 'N' END \
+        FROM information_schema.columns \
+        WHERE table_schema NOT IN ('information_schema', 'pg_catalog') \
+        ORDER BY table_schema, table_name, ordinal_position";
+    let output = execute(session, sql)?.unwrap_or_default();
+
+    Ok(output
+        .rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_name, column_name, data_type, nullable]: [String; 5] =
+                row.try_into().ok()?;
+            Some(DbObjectColumnEntry {
+                schema,
+                object_name,
+                column_name,
+                data_type,
+                nullable,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn run_query(
+    session: &DuckdbSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let Some(output) = execute(session, limited_query(sql, row_limit).as_str())? else {
+        return Ok(DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: None,
+            message: "Statement executed.".to_string(),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
+    };
+
+    // DML reports its row count as a one-cell `Count` result.
+    if is_dml(sql) && output.columns.len() == 1 && output.rows.len() == 1 {
+        if let Ok(rows_affected) = output.rows[0][0].parse::<u64>() {
+            return Ok(DbQueryResult {
+                columns: Vec::new(),
+                rows: Vec::new(),
+                null_flags: Vec::new(),
+                byte_lengths: Vec::new(),
+                rows_affected: Some(rows_affected),
+                message: format!("Statement executed. {rows_affected} row(s) affected."),
+                lossy_conversion: false,
+                cursor_id: None,
+                required_variables: Vec::new(),
+                ref_cursors: Vec::new(),
+            });
+        }
+    }
+
+    let mut rows = output.rows;
+    let mut null_flags = output.null_flags;
+    let truncated = rows.len() > row_limit;
+    rows.truncate(row_limit);
+    null_flags.truncate(row_limit);
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = output.lossy || note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: output.columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &DuckdbSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let output = execute(session, sql)?
+        .filter(|output| !output.columns.is_empty())
+        .ok_or_else(|| "Filtering is only available for query result sets.".to_string())?;
+
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    for (values, nulls) in output.rows.into_iter().zip(output.null_flags) {
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            continue;
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            break;
+        }
+    }
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = output.lossy || note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: output.columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+/// Database file to open and statements to run first. Parquet and CSV files are exposed
+/// as a view named after the file in an in-memory database.
+fn open_statements(path: &Path) -> Result<(Option<String>, String), String> {
+    let path_text = path.to_string_lossy().into_owned();
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let reader = match extension.as_str() {
+        "duckdb" | "ddb" | "db" => return Ok((Some(path_text), String::new())),
+        "parquet" => "read_parquet",
+        "csv" | "tsv" => "read_csv_auto",
+        _ => {
+            return Err(format!(
+                "Unsupported file type '.{extension}'. Open a .duckdb, .parquet or .csv file."
+            ))
+        }
+    };
+
+    let view_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or_else(|| "data".to_string());
+    Ok((
+        None,
+        format!(
+            "CREATE VIEW {} AS SELECT * FROM {reader}('{}');\n",
+            sql_ident::quote_identifier(DatabaseProvider::Duckdb, view_name.as_str()),
+            path_text.replace('\'', "''")
+        ),
+    ))
+}
+
+/// Runs `sql` after the session setup and returns the first result set it prints, if any.
+fn execute(session: &DuckdbSession, sql: &str) -> Result<Option<DuckdbOutput>, String> {
+    let mut command = Command::new(session.cli_path.as_str());
+    command
+        .args(["-ascii", "-header", "-bail", "-nullvalue", NULL_MARKER])
+        .arg(session.database_path.as_deref().unwrap_or(":memory:"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn().map_err(|error| {
+        format!(
+            "Failed to start the DuckDB CLI '{}': {error}. Install DuckDB or set its path on the connection.",
+            session.cli_path
+        )
+    })?;
+
+    let script = format!(
+        "{}{};\n",
+        session.setup_sql,
+        sql.trim().trim_end_matches(';')
+    );
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Failed to open DuckDB CLI input".to_string())?;
+    // Written from a thread so a large result cannot block the CLI while input is pending.
+    let writer = std::thread::spawn(move || stdin.write_all(script.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("DuckDB CLI failed: {error}"))?;
+    let _ = writer.join();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim();
+        return Err(if message.is_empty() {
+            format!("DuckDB CLI exited with {}", output.status)
+        } else {
+            message.to_string()
+        });
+    }
+
+    let (stdout, lossy) = match String::from_utf8(output.stdout) {
+        Ok(stdout) => (stdout, false),
+        Err(error) => (String::from_utf8_lossy(error.as_bytes()).into_owned(), true),
+    };
+    Ok(parse_ascii_output(stdout.as_str()).map(|output| DuckdbOutput { lossy, ..output }))
+}
+
+// A script that prints several result sets is read as one; only single-statement
+// queries are expected here.
+fn parse_ascii_output(stdout: &str) -> Option<DuckdbOutput> {
+    let mut records = stdout
+        .split(RECORD_SEPARATOR)
+        .map(|record| record.trim_start_matches(['\r', '\n']))
+        .filter(|record| !record.is_empty());
+    let columns = records
+        .next()?
+        .split(FIELD_SEPARATOR)
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let (rows, null_flags) = records
+        .map(|record| {
+            record
+                .split(FIELD_SEPARATOR)
+                .map(|value| match value {
+                    NULL_MARKER => (NULL_DISPLAY.to_string(), true),
+                    value => (value.to_string(), false),
+                })
+                .unzip()
+        })
+        .unzip();
+    Some(DuckdbOutput {
+        columns,
+        rows,
+        null_flags,
+        lossy: false,
+    })
+}
+
+// Queries are wrapped so the CLI stops after one row past the limit instead of printing
+// the whole result; other statements run unchanged.
+fn limited_query(sql: &str, row_limit: usize) -> String {
+    let sql = sql.trim().trim_end_matches(';').trim_end();
+    if matches!(
+        first_keyword(sql).as_str(),
+        "SELECT" | "WITH" | "FROM" | "VALUES" | "TABLE"
+    ) {
+        format!("SELECT * FROM (\n{sql}\n) LIMIT {}", row_limit + 1)
+    } else {
+        sql.to_string()
+    }
+}
+
+fn is_dml(sql: &str) -> bool {
+    matches!(
+        first_keyword(sql).as_str(),
+        "INSERT" | "UPDATE" | "DELETE" | "MERGE"
+    )
+}
+
+fn first_keyword(sql: &str) -> String {
+    sql.trim_start_matches(|ch: char| ch.is_whitespace() || ch == '(')
+        .split(|ch: char| !ch.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{limited_query, open_statements, parse_ascii_output};
+    use std::path::Path;
+
+    #[test]
+    fn parses_cli_ascii_output() {
+        let stdout =
+            "ID\u{1f}NOTE\u{1e}1\u{1f}two\nlines\u{1e}2\u{1f}\u{1c}\u{1e}3\u{1f}NULL\u{1e}";
+        let output = parse_ascii_output(stdout).expect("result set");
+        assert_eq!(output.columns, vec!["ID", "NOTE"]);
+        assert_eq!(
+            output.rows,
+            vec![
+                vec!["1", "two\nlines"],
+                vec!["2", "NULL"],
+                vec!["3", "NULL"]
+            ]
+        );
+        assert_eq!(
+            output.null_flags,
+            vec![vec![false, false], vec![false, true], vec![false, false]]
+        );
+        assert!(parse_ascii_output("").is_none());
+
+        assert_eq!(
+            limited_query("select 1;", 10),
+            "SELECT * FROM (\nselect 1\n) LIMIT 11"
+        );
+        assert_eq!(
+            limited_query("create table t(a int)", 10),
+            "create table t(a int)"
+        );
+    }
+
+    #[test]
+    fn exposes_data_files_as_views() {
+        let (database, setup) =
+            open_statements(Path::new("/data/o'neil sales.parquet")).expect("parquet");
+        assert!(database.is_none());
+        assert_eq!(
+            setup,
+            "CREATE VIEW \"o'neil sales\" AS SELECT * FROM read_parquet('/data/o''neil sales.parquet');\n"
+        );
+
+        let (database, setup) = open_statements(Path::new("warehouse.duckdb")).expect("duckdb");
+        assert_eq!(database.as_deref(), Some("warehouse.duckdb"));
+        assert!(setup.is_empty());
+        assert!(open_statements(Path::new("notes.txt")).is_err());
+    }
+}
@@ -0,0 +1,624 @@
+use super::common::{
+    effective_query_row_limit, note_lossy_conversion, null_flags_if_any, row_matches_query_filters,
+    MAX_QUERY_ROW_LIMIT,
+};
+use crate::profiles::app_data_file_path;
+use crate::types::{
+    DbConnectError, DbExternalProvider, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry,
+    DbObjectRef, DbQueryRequest, DbQueryResult, ExternalConnectOptions,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+/// Bumped when a method or message shape changes incompatibly; sent with `connect`.
+const PROTOCOL_VERSION: u32 = 1;
+const EXTERNAL_PROVIDER_DIRECTORY: &str = "providers";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+const NULL_DISPLAY: &str = "NULL";
+
+// Filled once at startup from the manifests in the app data `providers` directory.
+static EXTERNAL_PROVIDERS: Mutex<Vec<DbExternalProvider>> = Mutex::new(Vec::new());
+
+// Adapters are long-lived child processes speaking JSON lines over stdin/stdout (see the
+// README). Requests are strictly sequential; the cell lets the read-only registry entry
+// points (`&AppSession`) talk to the process too.
+pub(crate) struct ExternalSession {
+    process: RefCell<AdapterProcess>,
+}
+
+struct AdapterProcess {
+    child: Child,
+    stdin: ChildStdin,
+    // Fed by a reader thread so a silent adapter cannot block the session forever.
+    responses: Receiver<String>,
+    next_request_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdapterResponse {
+    id: Option<u64>,
+    #[serde(default)]
+    result: Value,
+    error: Option<AdapterError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdapterError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdapterConnectResult {
+    display_name: String,
+    schema: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdapterObject {
+    schema: String,
+    object_type: String,
+    object_name: String,
+    #[serde(default)]
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AdapterColumn {
+    schema: String,
+    object_name: String,
+    column_name: String,
+    data_type: String,
+    #[serde(default = "default_nullable")]
+    nullable: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct AdapterQueryResult {
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+    rows_affected: Option<u64>,
+    message: Option<String>,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+/// Reads every `*.json` manifest in the adapter directory and makes those providers
+/// available to `connect`. Returns one message per manifest that was skipped.
+pub(crate) fn register_providers(app: &AppHandle) -> Vec<String> {
+    let directory = match app_data_file_path(app, EXTERNAL_PROVIDER_DIRECTORY) {
+        Ok(directory) => directory,
+        Err(error) => return vec![error],
+    };
+    let (providers, warnings) = read_manifests(&directory);
+    if let Ok(mut registered) = EXTERNAL_PROVIDERS.lock() {
+        *registered = providers;
+    }
+    warnings
+}
+
+pub(crate) fn list_providers() -> Result<Vec<DbExternalProvider>, String> {
+    EXTERNAL_PROVIDERS
+        .lock()
+        .map(|providers| providers.clone())
+        .map_err(|_| "Failed to acquire external provider lock".to_string())
+}
+
+fn read_manifests(directory: &Path) -> (Vec<DbExternalProvider>, Vec<String>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        // No directory simply means no adapters are installed.
+        return (Vec::new(), Vec::new());
+    };
+
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut providers: Vec<DbExternalProvider> = Vec::new();
+    let mut warnings = Vec::new();
+    for path in paths {
+        let file_name = path.display();
+        match fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|content| parse_manifest(&content))
+        {
+            Ok(provider) if providers.iter().any(|existing| existing.id == provider.id) => {
+                warnings.push(format!(
+                    "Skipped {file_name}: provider id '{}' is already registered",
+                    provider.id
+                ));
+            }
+            Ok(provider) => providers.push(provider),
+            Err(error) => warnings.push(format!("Skipped {file_name}: {error}")),
+        }
+    }
+
+    (providers, warnings)
+}
+
+fn parse_manifest(content: &str) -> Result<DbExternalProvider, String> {
+    let mut provider = serde_json::from_str::<DbExternalProvider>(content)
+        .map_err(|error| format!("invalid manifest: {error}"))?;
+    provider.id = provider.id.trim().to_string();
+    provider.name = provider.name.trim().to_string();
+    provider.command = provider.command.trim().to_string();
+    if provider.id.is_empty() || provider.command.is_empty() {
+        return Err("manifest needs an id and a command".to_string());
+    }
+    if provider.name.is_empty() {
+        provider.name = provider.id.clone();
+    }
+    Ok(provider)
+}
+
+pub(crate) fn connect(
+    options: &ExternalConnectOptions,
+) -> Result<(ExternalSession, String, String), DbConnectError> {
+    let provider_id = options.provider_id.trim();
+    let provider = list_providers()
+        .map_err(DbConnectError::general)?
+        .into_iter()
+        .find(|provider| provider.id == provider_id)
+        .ok_or_else(|| {
+            DbConnectError::general(format!(
+                "No external provider with id '{provider_id}' is installed."
+            ))
+        })?;
+
+    let mut process = spawn_adapter(&provider).map_err(DbConnectError::general)?;
+    let result = process
+        .call(
+            "connect",
+            json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "options": options.options,
+                "password": options.password,
+            }),
+        )
+        .map_err(DbConnectError::general)?;
+    let connected = serde_json::from_value::<AdapterConnectResult>(result)
+        .map_err(|error| DbConnectError::general(invalid_result("connect", error)))?;
+
+    let session = ExternalSession {
+        process: RefCell::new(process),
+    };
+    Ok((session, connected.display_name, connected.schema))
+}
+
+pub(crate) fn list_objects(session: &ExternalSession) -> Result<Vec<DbObjectEntry>, String> {
+    let result = session
+        .process
+        .borrow_mut()
+        .call("listObjects", json!({}))?;
+    let objects = serde_json::from_value::<Vec<AdapterObject>>(result)
+        .map_err(|error| invalid_result("listObjects", error))?;
+
+    Ok(objects
+        .into_iter()
+        .map(|object| DbObjectEntry {
+            schema: object.schema,
+            object_type: object.object_type.to_uppercase(),
+            object_name: object.object_name,
+            status: object.status,
+            invalid_reason: None,
+            edition_name: None,
+            created: None,
+            last_ddl_time: None,
+            temporary: false,
+            secondary: false,
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &ExternalSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let result = session
+        .process
+        .borrow_mut()
+        .call("listObjectColumns", json!({}))?;
+    let columns = serde_json::from_value::<Vec<AdapterColumn>>(result)
+        .map_err(|error| invalid_result("listObjectColumns", error))?;
+
+    Ok(columns
+        .into_iter()
+        .map(|column| DbObjectColumnEntry {
+            schema: column.schema,
+            object_name: column.object_name,
+            column_name: column.column_name,
+            data_type: column.data_type,
+            nullable: if column.nullable { "Y" } else { "N" }.to_string(),
+        })
+        .collect())
+}
+
+pub(crate) fn get_object_ddl(
+    session: &ExternalSession,
+    request: &DbObjectRef,
+) -> Result<String, String> {
+    let result = session.process.borrow_mut().call(
+        "getObjectDdl",
+        json!({
+            "schema": request.schema,
+            "objectType": request.object_type,
+            "objectName": request.object_name,
+        }),
+    )?;
+    match result {
+        Value::String(ddl) => Ok(ddl),
+        Value::Null => Err(format!(
+            "DDL is not available for {}.{}",
+            request.schema, request.object_name
+        )),
+        other => Err(invalid_result(
+            "getObjectDdl",
+            format!("expected a string, got {other}"),
+        )),
+    }
+}
+
+pub(crate) fn run_query(
+    session: &mut ExternalSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let output = execute(session, sql, row_limit)?;
+    if output.columns.is_empty() {
+        let message = output
+            .message
+            .unwrap_or_else(|| match output.rows_affected {
+                Some(rows_affected) => {
+                    format!("Statement executed. {rows_affected} row(s) affected.")
+                }
+                None => "Statement executed.".to_string(),
+            });
+        return Ok(DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: output.rows_affected,
+            message,
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
+    }
+
+    let mut null_flags = null_flag_rows(&output.rows);
+    let mut rows = display_rows(output.rows);
+    let truncated = rows.len() > row_limit;
+    rows.truncate(row_limit);
+    null_flags.truncate(row_limit);
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: output.columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &mut ExternalSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    // Adapters do not filter, so scan the largest page the grid would ever show.
+    let output = execute(session, sql, MAX_QUERY_ROW_LIMIT as usize)?;
+    if output.columns.is_empty() {
+        return Err("Filtering is only available for query result sets.".to_string());
+    }
+
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    let row_nulls = null_flag_rows(&output.rows);
+    for (values, nulls) in display_rows(output.rows).into_iter().zip(row_nulls) {
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            continue;
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            break;
+        }
+    }
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: output.columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+fn execute(
+    session: &ExternalSession,
+    sql: &str,
+    row_limit: usize,
+) -> Result<AdapterQueryResult, String> {
+    let result = session
+        .process
+        .borrow_mut()
+        .call("runQuery", json!({ "sql": sql, "rowLimit": row_limit }))?;
+    serde_json::from_value::<AdapterQueryResult>(result)
+        .map_err(|error| invalid_result("runQuery", error))
+}
+
+fn null_flag_rows(rows: &[Vec<Value>]) -> Vec<Vec<bool>> {
+    rows.iter()
+        .map(|row| row.iter().map(Value::is_null).collect())
+        .collect()
+}
+
+/// Adapters may send any JSON scalar as a cell; the grid shows text.
+fn display_rows(rows: Vec<Vec<Value>>) -> Vec<Vec<String>> {
+    rows.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|value| match value {
+                    Value::Null => NULL_DISPLAY.to_string(),
+                    Value::String(text) => text,
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn spawn_adapter(provider: &DbExternalProvider) -> Result<AdapterProcess, String> {
+    let mut child = Command::new(&provider.command)
+        .args(&provider.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        // Adapter diagnostics go to the app's own stderr.
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|error| {
+            format!(
+                "Failed to start external provider '{}' ({}): {error}",
+                provider.name, provider.command
+            )
+        })?;
+
+    let stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "External provider stdin is unavailable".to_string())?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "External provider stdout is unavailable".to_string())?;
+
+    let (sender, responses) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(AdapterProcess {
+        child,
+        stdin,
+        responses,
+        next_request_id: 1,
+    })
+}
+
+impl AdapterProcess {
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+        let request = json!({ "id": id, "method": method, "params": params });
+        writeln!(self.stdin, "{request}")
+            .and_then(|_| self.stdin.flush())
+            .map_err(|error| format!("Failed to send request to external provider: {error}"))?;
+
+        let deadline = Instant::now() + REQUEST_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match self.responses.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!(
+                        "External provider did not answer '{method}' within {} seconds",
+                        REQUEST_TIMEOUT.as_secs()
+                    ));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    let status = self
+                        .child
+                        .try_wait()
+                        .ok()
+                        .flatten()
+                        .map(|status| format!(" ({status})"))
+                        .unwrap_or_default();
+                    return Err(format!("External provider exited{status}"));
+                }
+            };
+
+            if let Some(response) = decode_response(&line, id) {
+                return response;
+            }
+        }
+    }
+}
+
+impl Drop for AdapterProcess {
+    fn drop(&mut self) {
+        // `close` is a notification: no id, no answer expected.
+        let _ = writeln!(self.stdin, "{}", json!({ "method": "close" }));
+        let _ = self.stdin.flush();
+
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        while Instant::now() < deadline {
+            if matches!(self.child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// `None` for blank lines and answers to other requests (e.g. one that timed out earlier).
+fn decode_response(line: &str, expected_id: u64) -> Option<Result<Value, String>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    let response = match serde_json::from_str::<AdapterResponse>(line) {
+        Ok(response) => response,
+        Err(error) => {
+            return Some(Err(format!(
+                "External provider sent an invalid response: {error}"
+            )));
+        }
+    };
+    if response.id != Some(expected_id) {
+        return None;
+    }
+
+    Some(match response.error {
+        Some(error) => Err(error.message),
+        None => Ok(response.result),
+    })
+}
+
+fn invalid_result(method: &str, error: impl std::fmt::Display) -> String {
+    format!("External provider returned an invalid '{method}' result: {error}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_response, display_rows, parse_manifest};
+    use serde_json::json;
+
+    #[test]
+    fn decodes_matching_responses_and_skips_stale_ones() {
+        assert_eq!(
+            decode_response(r#"{"id":3,"result":{"schema":"main"}}"#, 3),
+            Some(Ok(json!({ "schema": "main" })))
+        );
+        assert_eq!(
+            decode_response(r#"{"id":3,"error":{"message":"no such table"}}"#, 3),
+            Some(Err("no such table".to_string()))
+        );
+        assert_eq!(decode_response(r#"{"id":2,"result":null}"#, 3), None);
+        assert_eq!(decode_response("   ", 3), None);
+        assert!(matches!(decode_response("not json", 3), Some(Err(_))));
+
+        assert_eq!(
+            display_rows(vec![vec![json!(1), json!(null), json!("a"), json!(true)]]),
+            vec![vec!["1", "NULL", "a", "true"]]
+        );
+    }
+
+    #[test]
+    fn parses_adapter_manifests() {
+        let provider = parse_manifest(
+            r#"{"id":" firebird ","name":"","command":"/opt/clarity/firebird-adapter","args":["--stdio"]}"#,
+        )
+        .expect("valid manifest");
+        assert_eq!(provider.id, "firebird");
+        assert_eq!(provider.name, "firebird");
+        assert_eq!(provider.args, vec!["--stdio"]);
+        assert_eq!(provider.dialect, None);
+
+        assert!(parse_manifest(r#"{"id":"x","name":"X","command":" "}"#).is_err());
+        assert!(parse_manifest("[]").is_err());
+    }
+}
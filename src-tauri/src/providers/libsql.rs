@@ -0,0 +1,591 @@
+use super::common::{
+    effective_query_row_limit, note_lossy_conversion, null_flags_if_any, row_matches_query_filters,
+    MAX_EXPLORER_OBJECTS,
+};
+use crate::data_export::{ExportRowSink, ExportValue};
+use crate::types::{
+    DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry, DbObjectRef,
+    DbQueryRequest, DbQueryResult, LibsqlConnectOptions,
+};
+use base64::Engine;
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+const LIBSQL_MAIN_SCHEMA: &str = "main";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(300);
+const NULL_DISPLAY: &str = "NULL";
+
+// Each request is a one-shot Hrana pipeline: the server opens a stream, runs the statement
+// in autocommit mode and closes it, so no state is kept between calls.
+pub(crate) struct LibsqlSession {
+    client: Client,
+    base_url: String,
+    auth_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipelineResponse {
+    results: Vec<PipelineResult>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum PipelineResult {
+    Ok { response: StreamResponse },
+    Error { error: StreamError },
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamResponse {
+    result: Option<StatementResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamError {
+    message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct StatementResult {
+    cols: Vec<ResultColumn>,
+    rows: Vec<Vec<HranaValue>>,
+    affected_row_count: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ResultColumn {
+    name: Option<String>,
+}
+
+/// A cell as Hrana encodes it; integers travel as strings so 64-bit values survive JSON.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum HranaValue {
+    Null,
+    Integer { value: String },
+    Float { value: f64 },
+    Text { value: String },
+    Blob { base64: String },
+}
+
+pub(crate) fn connect(
+    request: &LibsqlConnectOptions,
+) -> Result<(LibsqlSession, String, String), DbConnectError> {
+    let base_url = server_base_url(request.url.as_str());
+    let client = Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|error| {
+            DbConnectError::general(format!("Failed to initialize HTTP client: {error}"))
+        })?;
+    let session = LibsqlSession {
+        client,
+        base_url,
+        auth_token: request.auth_token.trim().to_string(),
+    };
+
+    let version = query_text_rows(&session, "SELECT sqlite_version()", &[])
+        .map_err(|error| {
+            DbConnectError::general(format!(
+                "Failed to connect to libSQL at {}: {error}",
+                session.base_url
+            ))
+        })?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .unwrap_or_default();
+
+    let host = session
+        .base_url
+        .split_once("://")
+        .map(|(_, host)| host)
+        .unwrap_or(session.base_url.as_str());
+    let display_name = format!("{host} [libSQL {version}]");
+    Ok((session, display_name, LIBSQL_MAIN_SCHEMA.to_string()))
+}
+
+/// The SQLite version the server embeds; Hrana does not expose the server's own version.
+pub(crate) fn server_version(session: &LibsqlSession) -> Result<String, String> {
+    query_text_rows(session, "SELECT sqlite_version()", &[])?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| "libSQL did not report a version".to_string())
+}
+
+pub(crate) fn list_objects(session: &LibsqlSession) -> Result<Vec<DbObjectEntry>, String> {
+    let sql = format!(
+        r#"
+        SELECT upper(type), name
+        FROM sqlite_schema
+        WHERE type IN ('table', 'view', 'index', 'trigger') AND name NOT LIKE 'sqlite\_%' ESCAPE '\'
+        ORDER BY type, name
+        LIMIT {MAX_EXPLORER_OBJECTS}
+    "#
+    );
+    let rows = query_text_rows(session, sql.as_str(), &[])?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [object_type, object_name]: [String; 2] = row.try_into().ok()?;
+            Some(DbObjectEntry {
+                schema: LIBSQL_MAIN_SCHEMA.to_string(),
+                object_type,
+                object_name,
+                status: None,
+                invalid_reason: None,
+                edition_name: None,
+                created: None,
+                last_ddl_time: None,
+                temporary: false,
+                secondary: false,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &LibsqlSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = r#"
+        SELECT m.name, p.name, p.type, CASE WHEN p."notnull" THEN 'N' ELSE 'Y' END
+        FROM sqlite_schema AS m
+        JOIN pragma_table_info(m.name) AS p
+        WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite\_%' ESCAPE '\'
+        ORDER BY m.name, p.cid
+    "#;
+    let rows = query_text_rows(session, sql, &[])?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [object_name, column_name, data_type, nullable]: [String; 4] =
+                row.try_into().ok()?;
+            Some(DbObjectColumnEntry {
+                schema: LIBSQL_MAIN_SCHEMA.to_string(),
+                object_name,
+                column_name,
+                data_type,
+                nullable,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn get_object_ddl(
+    session: &LibsqlSession,
+    request: &DbObjectRef,
+) -> Result<String, String> {
+    let schema = request.schema.trim();
+    if !schema.eq_ignore_ascii_case(LIBSQL_MAIN_SCHEMA) {
+        return Err(format!(
+            "Object access is limited to the {LIBSQL_MAIN_SCHEMA} schema."
+        ));
+    }
+    let object_name = request.object_name.trim();
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+    let object_type = request.object_type.trim().to_ascii_lowercase();
+
+    query_text_rows(
+        session,
+        "SELECT sql FROM sqlite_schema WHERE type = ? AND name = ?",
+        &[object_type.as_str(), object_name],
+    )?
+    .into_iter()
+    .next()
+    .and_then(|row| row.into_iter().next())
+    .filter(|sql| !sql.is_empty())
+    .map(|sql| format!("{sql};"))
+    .ok_or_else(|| {
+        format!(
+            "{} {schema}.{object_name} was not found",
+            object_type.to_ascii_uppercase()
+        )
+    })
+}
+
+pub(crate) fn run_query(
+    session: &LibsqlSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let result = execute(session, sql, &[])?;
+    if result.cols.is_empty() {
+        return Ok(statement_result(result.affected_row_count));
+    }
+
+    let columns = column_names(&result);
+    let truncated = result.rows.len() > row_limit;
+    let raw_rows = result.rows.into_iter().take(row_limit).collect::<Vec<_>>();
+    let null_flags = raw_rows
+        .iter()
+        .map(|row| row_null_flags(row))
+        .collect::<Vec<_>>();
+    let rows = raw_rows.into_iter().map(format_row).collect::<Vec<_>>();
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &LibsqlSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let result = execute(session, sql, &[])?;
+    if result.cols.is_empty() {
+        return Err("Filtering is only available for query result sets.".to_string());
+    }
+
+    let columns = column_names(&result);
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    for row in result.rows {
+        let nulls = row_null_flags(&row);
+        let values = format_row(row);
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            continue;
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            break;
+        }
+    }
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn stream_query_rows(
+    session: &LibsqlSession,
+    sql: &str,
+    sink: &mut dyn ExportRowSink,
+) -> Result<u64, String> {
+    let result = execute(session, sql, &[])?;
+    if result.cols.is_empty() {
+        return Err("Only queries can be exported".to_string());
+    }
+    sink.columns(&column_names(&result))?;
+
+    let mut row_count = 0_u64;
+    for row in result.rows {
+        let values = row.into_iter().map(export_value).collect::<Vec<_>>();
+        sink.row(&values)?;
+        row_count += 1;
+    }
+
+    Ok(row_count)
+}
+
+/// Runs one statement with `args` bound to `?` placeholders. Hrana returns the whole result
+/// in one response, so row limits are applied after it has been read.
+fn execute(session: &LibsqlSession, sql: &str, args: &[&str]) -> Result<StatementResult, String> {
+    let args = args
+        .iter()
+        .map(|value| serde_json::json!({ "type": "text", "value": value }))
+        .collect::<Vec<_>>();
+    let payload = serde_json::json!({
+        "requests": [
+            {
+                "type": "execute",
+                "stmt": { "sql": sql.trim(), "args": args, "want_rows": true },
+            },
+            { "type": "close" },
+        ],
+    });
+
+    let mut request = session
+        .client
+        .post(format!("{}/v2/pipeline", session.base_url))
+        .json(&payload);
+    if !session.auth_token.is_empty() {
+        request = request.bearer_auth(session.auth_token.as_str());
+    }
+    let response = request
+        .send()
+        .map_err(|error| format!("libSQL request failed: {error}"))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|error| format!("Failed to read libSQL response: {error}"))?;
+    if !status.is_success() {
+        let message = body.trim();
+        return Err(if message.is_empty() {
+            format!("libSQL returned HTTP {status}")
+        } else {
+            format!("libSQL returned HTTP {status}: {message}")
+        });
+    }
+
+    decode_pipeline_response(body.as_str())
+}
+
+fn decode_pipeline_response(body: &str) -> Result<StatementResult, String> {
+    let response = serde_json::from_str::<PipelineResponse>(body)
+        .map_err(|error| format!("Failed to parse libSQL response: {error}"))?;
+    match response.results.into_iter().next() {
+        Some(PipelineResult::Ok { response }) => Ok(response.result.unwrap_or_default()),
+        Some(PipelineResult::Error { error }) => Err(error.message),
+        None => Err("libSQL returned no result for the statement.".to_string()),
+    }
+}
+
+fn query_text_rows(
+    session: &LibsqlSession,
+    sql: &str,
+    args: &[&str],
+) -> Result<Vec<Vec<String>>, String> {
+    Ok(execute(session, sql, args)?
+        .rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|value| match value {
+                    HranaValue::Null => String::new(),
+                    value => format_value(value),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn statement_result(affected_row_count: u64) -> DbQueryResult {
+    if affected_row_count > 0 {
+        DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: Some(affected_row_count),
+            message: format!("Statement executed. {affected_row_count} row(s) affected."),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        }
+    } else {
+        DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: None,
+            message: "Statement executed.".to_string(),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        }
+    }
+}
+
+fn column_names(result: &StatementResult) -> Vec<String> {
+    result
+        .cols
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            column
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("column{}", index + 1))
+        })
+        .collect()
+}
+
+fn row_null_flags(row: &[HranaValue]) -> Vec<bool> {
+    row.iter()
+        .map(|value| matches!(value, HranaValue::Null))
+        .collect()
+}
+
+fn format_row(row: Vec<HranaValue>) -> Vec<String> {
+    row.into_iter().map(format_value).collect()
+}
+
+fn format_value(value: HranaValue) -> String {
+    match value {
+        HranaValue::Null => NULL_DISPLAY.to_string(),
+        HranaValue::Integer { value } | HranaValue::Text { value } => value,
+        HranaValue::Float { value } => value.to_string(),
+        HranaValue::Blob { base64 } => blob_hex(base64.as_str()),
+    }
+}
+
+fn export_value(value: HranaValue) -> ExportValue {
+    match value {
+        HranaValue::Null => ExportValue::Null,
+        HranaValue::Integer { value } => ExportValue::Number(value),
+        HranaValue::Float { value } => ExportValue::Number(value.to_string()),
+        HranaValue::Text { value } => ExportValue::Text(value),
+        HranaValue::Blob { base64 } => ExportValue::Text(blob_hex(base64.as_str())),
+    }
+}
+
+/// Shows blobs as `0x…` hex, like binary columns from MySQL.
+fn blob_hex(base64: &str) -> String {
+    // The server may omit padding.
+    let Ok(bytes) =
+        base64::engine::general_purpose::STANDARD_NO_PAD.decode(base64.trim_end_matches('='))
+    else {
+        return base64.to_string();
+    };
+    let mut hex = String::with_capacity(2 + bytes.len() * 2);
+    hex.push_str("0x");
+    for byte in bytes {
+        hex.push_str(&format!("{byte:02X}"));
+    }
+    hex
+}
+
+/// Accepts the `libsql://` URLs Turso hands out and maps them to HTTPS; `ws`/`wss` map to
+/// their HTTP equivalents and a bare host defaults to HTTPS.
+pub(crate) fn server_base_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let (scheme, authority) = match url.split_once("://") {
+        Some((scheme, authority)) => (scheme.to_ascii_lowercase(), authority),
+        None => ("https".to_string(), url),
+    };
+    let scheme = match scheme.as_str() {
+        "http" | "ws" => "http",
+        _ => "https",
+    };
+    format!("{scheme}://{authority}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_pipeline_response, format_row, server_base_url};
+
+    #[test]
+    fn maps_libsql_urls_to_http() {
+        assert_eq!(
+            server_base_url("libsql://db-org.turso.io/"),
+            "https://db-org.turso.io"
+        );
+        assert_eq!(
+            server_base_url("http://127.0.0.1:8080"),
+            "http://127.0.0.1:8080"
+        );
+        assert_eq!(
+            server_base_url("ws://localhost:8080"),
+            "http://localhost:8080"
+        );
+        assert_eq!(server_base_url("db.example.com"), "https://db.example.com");
+    }
+
+    #[test]
+    fn decodes_pipeline_results() {
+        let result = decode_pipeline_response(
+            r#"{"baton":null,"base_url":null,"results":[
+                {"type":"ok","response":{"type":"execute","result":{
+                    "cols":[{"name":"id","decltype":"INTEGER"},{"name":"v","decltype":null}],
+                    "rows":[[{"type":"integer","value":"9007199254740993"},{"type":"null"}],
+                            [{"type":"float","value":1.5},{"type":"blob","base64":"AKs"}]],
+                    "affected_row_count":0,"last_insert_rowid":null}}},
+                {"type":"ok","response":{"type":"close"}}]}"#,
+        )
+        .expect("result");
+        assert_eq!(result.cols.len(), 2);
+        let rows = result.rows.into_iter().map(format_row).collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["9007199254740993".to_string(), "NULL".to_string()],
+                vec!["1.5".to_string(), "0x00AB".to_string()],
+            ]
+        );
+
+        assert_eq!(
+            decode_pipeline_response(
+                r#"{"results":[{"type":"error","error":{"message":"SQLITE_ERROR: no such table: t","code":"SQLITE_ERROR"}},{"type":"error","error":{"message":"skipped"}}]}"#
+            )
+            .expect_err("error"),
+            "SQLITE_ERROR: no such table: t"
+        );
+    }
+}
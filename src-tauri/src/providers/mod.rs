@@ -1,43 +1,409 @@
+pub(crate) mod clickhouse;
+mod common;
+pub(crate) mod duckdb;
+pub(crate) mod external;
+pub(crate) mod libsql;
+pub(crate) mod mysql;
 pub(crate) mod oracle;
+pub(crate) mod snowflake;
 
+use crate::cancellation::CancelToken;
+use crate::data_export::{ExportRowSink, ExportValue};
+use crate::object_columns;
+use crate::sql_splitter::split_statements;
+use crate::ssh_tunnel::{self, SshTunnel};
 use crate::types::{
-    DatabaseProvider, DbConnectConnection, DbConnectError, DbConnectRequest,
-    DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectDdlUpdateRequest, DbObjectEntry,
-    DbObjectRef, DbQueryRequest, DbQueryResult, DbSchemaSearchRequest, DbSchemaSearchResult,
+    DatabaseProvider, DbAqBrowseRequest, DbAqBrowseResult, DbAqQueueEntry, DbCloseCursorRequest,
+    DbConnectConnection, DbConnectError, DbConnectRequest, DbConnectionProfile,
+    DbConsistentQueriesRequest, DbConsistentQueriesResult, DbContainer, DbDownloadLobRequest,
+    DbEncodingInfo, DbExternalProvider, DbFetchMoreRowsRequest, DbFilteredQueryRequest,
+    DbLobDownloadResult, DbObjectAccessStatsRequest, DbObjectAccessStatsResult,
+    DbObjectColumnEntry, DbObjectConstraint, DbObjectDdl, DbObjectDdlUpdateRequest,
+    DbObjectDependent, DbObjectEditionInfo, DbObjectEntry, DbObjectGrant, DbObjectName,
+    DbObjectRef, DbObjectStats, DbQueryRequest, DbQueryResult, DbRecentErrorsRequest,
+    DbRecentErrorsResult, DbResourceLimits, DbRowLocksRequest, DbRowLocksResult,
+    DbSchemaSearchRequest, DbSchemaSearchResult, DbServerInfo, DbSessionNls, DbSessionReconnected,
+    DbSetContainerRequest, DbSetResourceLimitsRequest, DbSetSessionNlsRequest, TimestampTzMode,
 };
+use crate::workspace::now_millis;
+use common::{is_retryable_connect_error, ConnectRetryPolicy};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(crate) struct AppSession {
     pub(crate) provider: DatabaseProvider,
     pub(crate) session: ProviderSession,
+    /// Declared after `session` so the connection closes before its tunnel.
+    ssh_tunnel: Option<SshTunnel>,
+    /// Last time the user worked with the session.
+    last_activity: Instant,
+    /// Last round-trip of any kind, including keep-alive pings.
+    last_contact: Instant,
+    /// Unix milliseconds when the session was opened.
+    connected_at: u64,
+    /// The request the session was opened with, for `db_reconnect`.
+    connect_request: Option<DbConnectRequest>,
+}
+
+impl AppSession {
+    /// Marks the session as used, postponing its next keep-alive ping.
+    pub(crate) fn touch(&mut self) {
+        self.last_activity = Instant::now();
+        self.last_contact = self.last_activity;
+    }
+
+    /// Records a keep-alive ping, which does not count as use.
+    pub(crate) fn mark_pinged(&mut self) {
+        self.last_contact = Instant::now();
+    }
+
+    pub(crate) fn idle_for(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
+    pub(crate) fn since_last_contact(&self) -> Duration {
+        self.last_contact.elapsed()
+    }
+
+    pub(crate) fn connected_at(&self) -> u64 {
+        self.connected_at
+    }
+
+    pub(crate) fn connect_request(&self) -> Option<&DbConnectRequest> {
+        self.connect_request.as_ref()
+    }
+
+    /// Adopts a connection opened ahead of time for `request`, as if it had just connected.
+    pub(crate) fn adopt(&mut self, request: &DbConnectRequest) {
+        self.connect_request = Some(request.clone());
+        self.touch();
+    }
 }
 
 pub(crate) enum ProviderSession {
-    Oracle(oracle::OracleSession),
+    Oracle(Box<oracle::OracleSession>),
+    Mysql(mysql::MysqlSession),
+    Duckdb(duckdb::DuckdbSession),
+    Snowflake(snowflake::SnowflakeSession),
+    Clickhouse(clickhouse::ClickhouseSession),
+    Libsql(libsql::LibsqlSession),
+    External(external::ExternalSession),
+}
+
+/// Interrupts the call running on a provider connection from another thread; see
+/// `CancelToken::interrupt_with`.
+pub(crate) enum QueryCancelHandle {
+    Oracle(oracle::OracleCancelHandle),
 }
 
+impl QueryCancelHandle {
+    pub(crate) fn cancel(&self) -> Result<(), String> {
+        match self {
+            QueryCancelHandle::Oracle(handle) => handle.cancel(),
+        }
+    }
+}
+
+/// A connected session with its display name and schema, or why it failed.
+type ConnectAttempt = Result<(AppSession, String, String), DbConnectError>;
+
 pub(crate) struct ProviderRegistry;
 
 impl ProviderRegistry {
+    /// Loads the external adapter manifests; called once at startup.
+    pub(crate) fn register_external_providers(app: &AppHandle) -> Vec<String> {
+        external::register_providers(app)
+    }
+
+    pub(crate) fn list_external_providers() -> Result<Vec<DbExternalProvider>, String> {
+        external::list_providers()
+    }
+
+    /// Connects, first opening the request's SSH tunnel if it has one. The tunnel is owned
+    /// by the session and closes when the session is dropped.
+    pub(crate) fn oracle_client_version(
+        oracle_client_lib_dir: Option<&str>,
+    ) -> Result<String, String> {
+        oracle::client_version(oracle_client_lib_dir)
+    }
+
+    pub(crate) fn loaded_oracle_client_dir() -> Option<Option<PathBuf>> {
+        oracle::loaded_client_dir()
+    }
+
+    /// Base URL of a profile whose provider talks HTTPS to a hosted service.
+    pub(crate) fn http_base_url(connection: &DbConnectionProfile) -> Option<String> {
+        match connection {
+            DbConnectionProfile::Snowflake(connection) => {
+                Some(snowflake::account_base_url(connection.account.as_str()))
+            }
+            DbConnectionProfile::Libsql(connection) => {
+                Some(libsql::server_base_url(connection.url.as_str()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Connects under the request's timeout and retry policy. A timed-out attempt keeps
+    /// running on its worker thread and its session is dropped once it finishes.
     pub(crate) fn connect(
         request: &DbConnectRequest,
+    ) -> Result<(AppSession, String, String), DbConnectError> {
+        let (mut session, display_name, schema) = Self::connect_with_retries(request)?;
+        if let Some(sql) = request
+            .startup_sql
+            .as_deref()
+            .filter(|sql| !sql.trim().is_empty())
+        {
+            Self::run_startup_sql(&mut session, sql).map_err(DbConnectError::general)?;
+        }
+        session.connect_request = Some(request.clone());
+        Ok((session, display_name, schema))
+    }
+
+    /// Gives a freshly connected session the settings changed on `previous` since it
+    /// connected, such as Oracle NLS formats and resource limits.
+    pub(crate) fn restore_session_settings(
+        previous: &AppSession,
+        session: &mut AppSession,
+    ) -> Result<(), String> {
+        match (&previous.session, &mut session.session) {
+            (ProviderSession::Oracle(previous), ProviderSession::Oracle(session)) => {
+                oracle::restore_session_settings(previous, session)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether `error` means the connection itself is gone rather than the statement having
+    /// failed. Providers that connect per request have no connection to lose.
+    pub(crate) fn is_connection_lost(provider: DatabaseProvider, error: &str) -> bool {
+        match provider {
+            DatabaseProvider::Oracle => oracle::is_connection_lost_error(error),
+            DatabaseProvider::Mysql => mysql::is_connection_lost_error(error),
+            _ => false,
+        }
+    }
+
+    /// Runs a profile's startup statements before the session is handed back.
+    fn run_startup_sql(session: &mut AppSession, sql: &str) -> Result<(), String> {
+        if let ProviderSession::Oracle(oracle_session) = &mut session.session {
+            return oracle::run_startup_sql(oracle_session, sql);
+        }
+        for statement in split_statements(sql, session.provider) {
+            let request = DbQueryRequest {
+                session_id: 0,
+                sql: statement.sql,
+                row_limit: Some(1),
+                binds: Vec::new(),
+                sample_mode: false,
+                binary_preview_bytes: None,
+                production_confirmation: None,
+                timeout_secs: None,
+            };
+            Self::run_query(session, &request, &CancelToken::default()).map_err(|error| {
+                format!("Startup SQL failed at line {}: {error}", statement.line)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// A timed-out attempt cannot be interrupted and keeps running in the background.
+    /// The next attempt waits on it instead of starting another, so at most one connect
+    /// (and SSH tunnel) is in flight; if it finishes after the last attempt gives up, its
+    /// session is dropped.
+    fn connect_with_retries(
+        request: &DbConnectRequest,
+    ) -> Result<(AppSession, String, String), DbConnectError> {
+        let policy = ConnectRetryPolicy::from_request(request);
+        let mut attempt = 1;
+        let mut pending = None;
+        loop {
+            let receiver = pending
+                .take()
+                .unwrap_or_else(|| Self::spawn_connect_attempt(request));
+            let error = match receiver.recv_timeout(policy.timeout) {
+                Ok(connected) => match connected {
+                    Ok(connected) => return Ok(connected),
+                    Err(error) => error,
+                },
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    pending = Some(receiver);
+                    DbConnectError::general(format!(
+                        "Timed out after {} s waiting for the {} server to accept the connection. Check the host, port and firewall, or raise the connect timeout.",
+                        policy.timeout.as_secs(),
+                        request.provider().label()
+                    ))
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    DbConnectError::general("The connect attempt stopped unexpectedly")
+                }
+            };
+            let retryable = match &error {
+                DbConnectError::General { message } => is_retryable_connect_error(message),
+                DbConnectError::OracleClientMissing { .. } => false,
+            };
+            if !retryable || attempt >= policy.attempts {
+                return Err(match error {
+                    DbConnectError::General { message } if attempt > 1 => {
+                        DbConnectError::general(format!("{message} (after {attempt} attempts)"))
+                    }
+                    error => error,
+                });
+            }
+            thread::sleep(policy.retry_delay);
+            attempt += 1;
+        }
+    }
+
+    fn spawn_connect_attempt(request: &DbConnectRequest) -> mpsc::Receiver<ConnectAttempt> {
+        let (sender, receiver) = mpsc::channel();
+        let attempt_request = request.clone();
+        thread::spawn(move || {
+            let _ = sender.send(Self::connect_once(&attempt_request));
+        });
+        receiver
+    }
+
+    fn connect_once(
+        request: &DbConnectRequest,
+    ) -> Result<(AppSession, String, String), DbConnectError> {
+        let Some(tunnel_options) = &request.ssh_tunnel else {
+            return Self::connect_direct(request);
+        };
+        let (tunnel, tunneled_request) = ssh_tunnel::open_for_request(request, tunnel_options)
+            .map_err(DbConnectError::general)?;
+        let (mut session, display_name, schema) = Self::connect_direct(&tunneled_request)?;
+        session.ssh_tunnel = Some(tunnel);
+        let display_name = format!("{display_name} via {}", tunnel_options.host.trim());
+        Ok((session, display_name, schema))
+    }
+
+    fn connect_direct(
+        request: &DbConnectRequest,
     ) -> Result<(AppSession, String, String), DbConnectError> {
         match &request.connection {
             DbConnectConnection::Oracle(connection) => {
-                let (session, display_name, schema) = oracle::connect(connection)?;
+                let (session, display_name, schema) =
+                    oracle::connect(connection, request.timestamp_tz_mode)?;
                 Ok((
                     AppSession {
                         provider: DatabaseProvider::Oracle,
-                        session: ProviderSession::Oracle(session),
+                        session: ProviderSession::Oracle(Box::new(session)),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
+            }
+            DbConnectConnection::Mysql(connection) => {
+                let (session, display_name, schema) = mysql::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::Mysql,
+                        session: ProviderSession::Mysql(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
+            }
+            DbConnectConnection::Duckdb(connection) => {
+                let (session, display_name, schema) = duckdb::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::Duckdb,
+                        session: ProviderSession::Duckdb(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
+            }
+            DbConnectConnection::Snowflake(connection) => {
+                let (session, display_name, schema) = snowflake::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::Snowflake,
+                        session: ProviderSession::Snowflake(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
+            }
+            DbConnectConnection::Clickhouse(connection) => {
+                let (session, display_name, schema) = clickhouse::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::Clickhouse,
+                        session: ProviderSession::Clickhouse(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
                     },
                     display_name,
                     schema,
                 ))
             }
-            DbConnectConnection::Postgres(_)
-            | DbConnectConnection::Mysql(_)
-            | DbConnectConnection::Sqlite(_) => {
-                Err(DbConnectError::general(not_implemented_error(request.provider())))
+            DbConnectConnection::Libsql(connection) => {
+                let (session, display_name, schema) = libsql::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::Libsql,
+                        session: ProviderSession::Libsql(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
+            }
+            DbConnectConnection::External(connection) => {
+                let (session, display_name, schema) = external::connect(connection)?;
+                Ok((
+                    AppSession {
+                        provider: DatabaseProvider::External,
+                        session: ProviderSession::External(session),
+                        ssh_tunnel: None,
+                        last_activity: Instant::now(),
+                        last_contact: Instant::now(),
+                        connect_request: None,
+                        connected_at: now_millis(),
+                    },
+                    display_name,
+                    schema,
+                ))
             }
+            DbConnectConnection::Postgres(_) | DbConnectConnection::Sqlite(_) => Err(
+                DbConnectError::general(not_implemented_error(request.provider())),
+            ),
         }
     }
 
@@ -46,6 +412,24 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::list_objects(oracle_session)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::list_objects(mysql_session)
+            }
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(duckdb_session)) => {
+                duckdb::list_objects(duckdb_session)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::list_objects(snowflake_session)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::list_objects(clickhouse_session)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::list_objects(libsql_session)
+            }
+            (DatabaseProvider::External, ProviderSession::External(external_session)) => {
+                external::list_objects(external_session)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -57,6 +441,80 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::list_object_columns(oracle_session)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::list_object_columns(mysql_session)
+            }
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(duckdb_session)) => {
+                duckdb::list_object_columns(duckdb_session)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::list_object_columns(snowflake_session)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::list_object_columns(clickhouse_session)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::list_object_columns(libsql_session)
+            }
+            (DatabaseProvider::External, ProviderSession::External(external_session)) => {
+                external::list_object_columns(external_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    /// Whether `list_columns_of` reads each object on its own. Other providers read the
+    /// whole schema, so a batch should make one call for all of its objects.
+    pub(crate) fn lists_columns_per_object(provider: DatabaseProvider) -> bool {
+        provider == DatabaseProvider::Oracle
+    }
+
+    /// Columns of the given objects, in their order.
+    pub(crate) fn list_columns_of(
+        session: &AppSession,
+        objects: &[DbObjectName],
+    ) -> Result<Vec<DbObjectColumnEntry>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_columns_of(oracle_session, objects)
+            }
+            _ => Self::list_object_columns(session)
+                .map(|columns| object_columns::select(columns, objects)),
+        }
+    }
+
+    pub(crate) fn list_constraints_of(
+        session: &AppSession,
+        objects: &[DbObjectName],
+    ) -> Result<Vec<Vec<DbObjectConstraint>>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_constraints_of(oracle_session, objects)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn table_stats_of(
+        session: &AppSession,
+        objects: &[DbObjectName],
+    ) -> Result<Vec<Option<DbObjectStats>>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::table_stats_of(oracle_session, objects)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn list_grants_of(
+        session: &AppSession,
+        objects: &[DbObjectName],
+    ) -> Result<Vec<Vec<DbObjectGrant>>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_grants_of(oracle_session, objects)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -65,10 +523,33 @@ impl ProviderRegistry {
         session: &AppSession,
         request: &DbObjectRef,
     ) -> Result<String, String> {
+        Self::get_object_ddl_detail(session, request).map(|ddl| ddl.ddl)
+    }
+
+    /// DDL plus whether it had to be reconstructed from the data dictionary.
+    pub(crate) fn get_object_ddl_detail(
+        session: &AppSession,
+        request: &DbObjectRef,
+    ) -> Result<DbObjectDdl, String> {
         match (session.provider, &session.session) {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::get_object_ddl(oracle_session, request)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::get_object_ddl(mysql_session, request).map(DbObjectDdl::generated)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::get_object_ddl(snowflake_session, request).map(DbObjectDdl::generated)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::get_object_ddl(clickhouse_session, request).map(DbObjectDdl::generated)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::get_object_ddl(libsql_session, request).map(DbObjectDdl::generated)
+            }
+            (DatabaseProvider::External, ProviderSession::External(external_session)) => {
+                external::get_object_ddl(external_session, request).map(DbObjectDdl::generated)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -85,18 +566,123 @@ impl ProviderRegistry {
         }
     }
 
+    /// Oracle statements are interrupted as soon as `cancel` fires and are held to its time
+    /// limit; other providers stop at the next call.
     pub(crate) fn run_query(
         session: &mut AppSession,
         request: &DbQueryRequest,
+        cancel: &CancelToken,
+    ) -> Result<DbQueryResult, String> {
+        if !request.binds.is_empty() && session.provider != DatabaseProvider::Oracle {
+            return Err(format!(
+                "Bind variables are not supported for provider '{}' yet.",
+                session.provider.label()
+            ));
+        }
+        cancel.check()?;
+        if request.sample_mode {
+            return Self::run_sampled_query(session, request, cancel);
+        }
+
+        let result = match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                let _interrupt = cancel.interrupt_with(oracle_interrupt(oracle_session));
+                match cancel.remaining() {
+                    Some(remaining) => oracle::run_query_with_call_timeout(
+                        oracle_session,
+                        request,
+                        call_time_limit(remaining),
+                    ),
+                    None => oracle::run_query(oracle_session, request),
+                }
+            }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::run_query(mysql_session, request)
+            }
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(duckdb_session)) => {
+                duckdb::run_query(duckdb_session, request)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::run_query(snowflake_session, request)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::run_query(clickhouse_session, request)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::run_query(libsql_session, request)
+            }
+            (DatabaseProvider::External, ProviderSession::External(external_session)) => {
+                external::run_query(external_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        };
+        result.map_err(|error| cancel.explain(error))
+    }
+
+    /// Pages through the rows a truncated query left open.
+    pub(crate) fn fetch_more_rows(
+        session: &mut AppSession,
+        request: &DbFetchMoreRowsRequest,
+        cancel: &CancelToken,
     ) -> Result<DbQueryResult, String> {
+        cancel.check()?;
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                let _interrupt = cancel.interrupt_with(oracle_interrupt(oracle_session));
+                oracle::fetch_more_rows(oracle_session, request.cursor_id, request.count)
+                    .map_err(|error| cancel.explain(error))
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn close_cursor(
+        session: &mut AppSession,
+        request: &DbCloseCursorRequest,
+    ) -> Result<bool, String> {
         match (session.provider, &mut session.session) {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
-                oracle::run_query(oracle_session, request)
+                Ok(oracle::close_cursor(oracle_session, request.cursor_id))
             }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
 
+    fn run_sampled_query(
+        session: &mut AppSession,
+        request: &DbQueryRequest,
+        cancel: &CancelToken,
+    ) -> Result<DbQueryResult, String> {
+        let rows = common::effective_query_row_limit(request);
+        let sampled = DbQueryRequest {
+            session_id: request.session_id,
+            sql: common::sampled_query_sql(session.provider, request.sql.as_str(), rows)?,
+            row_limit: request.row_limit,
+            binds: request.binds.clone(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        };
+        let mut result = match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                let _interrupt = cancel.interrupt_with(oracle_interrupt(oracle_session));
+                let time_limit = cancel
+                    .remaining()
+                    .map_or(common::SAMPLE_TIME_LIMIT, |remaining| {
+                        call_time_limit(remaining).min(common::SAMPLE_TIME_LIMIT)
+                    });
+                oracle::run_query_with_call_timeout(oracle_session, &sampled, time_limit)
+                    .map_err(|error| cancel.explain(error))
+            }
+            _ => Self::run_query(session, &sampled, cancel),
+        }?;
+        result
+            .message
+            .push_str(&format!(" Sample mode: first {rows} row(s) only."));
+        Ok(result)
+    }
+
     pub(crate) fn run_filtered_query(
         session: &mut AppSession,
         request: &DbFilteredQueryRequest,
@@ -105,6 +691,167 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::run_filtered_query(oracle_session, request)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::run_filtered_query(mysql_session, request)
+            }
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(duckdb_session)) => {
+                duckdb::run_filtered_query(duckdb_session, request)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::run_filtered_query(snowflake_session, request)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::run_filtered_query(clickhouse_session, request)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::run_filtered_query(libsql_session, request)
+            }
+            (DatabaseProvider::External, ProviderSession::External(external_session)) => {
+                external::run_filtered_query(external_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    /// Round-trips a trivial query; `None` for external adapters, whose dialect is unknown.
+    pub(crate) fn ping(session: &mut AppSession) -> Result<Option<Duration>, String> {
+        let sql = match session.provider {
+            DatabaseProvider::Oracle => "SELECT 1 FROM DUAL",
+            DatabaseProvider::External => return Ok(None),
+            _ => "SELECT 1",
+        };
+        let request = DbQueryRequest {
+            session_id: 0,
+            sql: sql.to_string(),
+            row_limit: Some(1),
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        };
+        let started = Instant::now();
+        match (session.provider, &mut session.session) {
+            // Bounded so a silently dropped connection cannot hold the session for minutes.
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::run_query_with_call_timeout(oracle_session, &request, PING_TIMEOUT)?;
+            }
+            _ => {
+                Self::run_query(session, &request, &CancelToken::default())?;
+            }
+        }
+        Ok(Some(started.elapsed()))
+    }
+
+    pub(crate) fn reconnect(
+        session: &mut AppSession,
+        password: Option<String>,
+    ) -> Result<(), String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::reconnect_session(oracle_session, password)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn take_reconnect_notice(session: &mut AppSession) -> Option<DbSessionReconnected> {
+        match &mut session.session {
+            ProviderSession::Oracle(oracle_session) => {
+                oracle::take_reconnect_notice(oracle_session)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn download_lob(
+        session: &AppSession,
+        request: &DbDownloadLobRequest,
+    ) -> Result<DbLobDownloadResult, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::download_lob(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn set_server_output(session: &AppSession, enabled: bool) -> Result<(), String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::set_server_output(oracle_session, enabled)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn read_server_output(session: &AppSession) -> Result<Vec<String>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::read_server_output(oracle_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn explain_plan(session: &mut AppSession, sql: &str) -> Result<Vec<String>, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::explain_plan(oracle_session, sql)
+            }
+            (provider, _) => Err(format!(
+                "Execution plans are not supported for {}.",
+                provider.label()
+            )),
+        }
+    }
+
+    pub(crate) fn run_consistent_queries(
+        session: &mut AppSession,
+        request: &DbConsistentQueriesRequest,
+    ) -> Result<DbConsistentQueriesResult, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::run_consistent_queries(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    /// Source of a stored program unit as it was `seconds_ago` seconds back, via flashback.
+    pub(crate) fn get_object_source_as_of(
+        session: &AppSession,
+        request: &DbObjectRef,
+        seconds_ago: u64,
+    ) -> Result<Option<String>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::get_object_source_as_of(oracle_session, request, seconds_ago)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn get_object_edition(
+        session: &AppSession,
+        request: &DbObjectRef,
+    ) -> Result<DbObjectEditionInfo, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::get_object_edition(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn list_object_dependents(
+        session: &AppSession,
+        request: &DbObjectRef,
+    ) -> Result<Vec<DbObjectDependent>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_object_dependents(oracle_session, request)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -112,10 +859,180 @@ impl ProviderRegistry {
     pub(crate) fn search_schema_text(
         session: &AppSession,
         request: &DbSchemaSearchRequest,
+        cancel: &CancelToken,
     ) -> Result<Vec<DbSchemaSearchResult>, String> {
+        cancel.check()?;
+        let result = match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                let _interrupt = cancel.interrupt_with(oracle_interrupt(oracle_session));
+                oracle::search_schema_text(oracle_session, request, cancel)
+            }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::search_schema_text(mysql_session, request)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::search_schema_text(snowflake_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        };
+        result.map_err(|error| cancel.explain(error))
+    }
+
+    pub(crate) fn list_aq_queues(session: &AppSession) -> Result<Vec<DbAqQueueEntry>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_aq_queues(oracle_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn browse_aq_messages(
+        session: &AppSession,
+        request: &DbAqBrowseRequest,
+    ) -> Result<DbAqBrowseResult, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::browse_aq_messages(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn get_recent_errors(
+        session: &AppSession,
+        request: &DbRecentErrorsRequest,
+    ) -> Result<DbRecentErrorsResult, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::get_recent_errors(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn get_row_locks(
+        session: &AppSession,
+        request: &DbRowLocksRequest,
+    ) -> Result<DbRowLocksResult, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::get_row_locks(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn get_object_access_stats(
+        session: &AppSession,
+        request: &DbObjectAccessStatsRequest,
+    ) -> Result<DbObjectAccessStatsResult, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::get_object_access_stats(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn get_encoding_info(session: &AppSession) -> Result<DbEncodingInfo, String> {
         match (session.provider, &session.session) {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
-                oracle::search_schema_text(oracle_session, request)
+                oracle::get_encoding_info(oracle_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    /// Checks `cancel` before every row, whatever the provider.
+    pub(crate) fn stream_query_rows(
+        session: &AppSession,
+        sql: &str,
+        sink: &mut dyn ExportRowSink,
+        cancel: &CancelToken,
+    ) -> Result<u64, String> {
+        cancel.check()?;
+        let sink = &mut CancellableSink { sink, cancel };
+        let result = match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                let _interrupt = cancel.interrupt_with(oracle_interrupt(oracle_session));
+                oracle::stream_query_rows(oracle_session, sql, sink)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::stream_query_rows(snowflake_session, sql, sink)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::stream_query_rows(clickhouse_session, sql, sink)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::stream_query_rows(libsql_session, sql, sink)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        };
+        result.map_err(|error| cancel.explain(error))
+    }
+
+    pub(crate) fn set_timestamp_tz_mode(
+        session: &mut AppSession,
+        mode: TimestampTzMode,
+    ) -> Result<TimestampTzMode, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                Ok(oracle::set_timestamp_tz_mode(oracle_session, mode))
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn session_nls(session: &AppSession) -> Result<DbSessionNls, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::session_nls(oracle_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn set_session_nls(
+        session: &mut AppSession,
+        request: &DbSetSessionNlsRequest,
+    ) -> Result<DbSessionNls, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::set_session_nls(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn set_resource_limits(
+        session: &mut AppSession,
+        request: &DbSetResourceLimitsRequest,
+    ) -> Result<DbResourceLimits, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::set_resource_limits(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn set_container(
+        session: &mut AppSession,
+        request: &DbSetContainerRequest,
+    ) -> Result<DbServerInfo, String> {
+        match (session.provider, &mut session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::set_container(oracle_session, request)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    pub(crate) fn list_containers(session: &AppSession) -> Result<Vec<DbContainer>, String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::list_containers(oracle_session)
             }
             (provider, _) => Err(not_implemented_error(provider)),
         }
@@ -126,6 +1043,9 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::begin_transaction(oracle_session)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::begin_transaction(mysql_session)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -135,6 +1055,9 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::commit_transaction(oracle_session)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::commit_transaction(mysql_session)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
@@ -144,7 +1067,67 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 oracle::rollback_transaction(oracle_session)
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::rollback_transaction(mysql_session)
+            }
+            (provider, _) => Err(not_implemented_error(provider)),
+        }
+    }
+
+    /// Best effort; `None` when the provider cannot tell or the lookup fails.
+    pub(crate) fn server_version(session: &AppSession) -> Option<String> {
+        let version = match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::server_version(oracle_session)
+            }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::server_version(mysql_session)
+            }
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(duckdb_session)) => {
+                duckdb::server_version(duckdb_session)
+            }
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(snowflake_session)) => {
+                snowflake::server_version(snowflake_session)
+            }
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(clickhouse_session)) => {
+                clickhouse::server_version(clickhouse_session)
+            }
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(libsql_session)) => {
+                libsql::server_version(libsql_session)
+            }
             (provider, _) => Err(not_implemented_error(provider)),
+        };
+        version
+            .ok()
+            .map(|version| version.trim().to_string())
+            .filter(|version| !version.is_empty())
+    }
+
+    /// Oracle instance serving the session; `None` for other providers or when the lookup
+    /// fails.
+    pub(crate) fn instance_name(session: &AppSession) -> Option<String> {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::instance_name(oracle_session)
+            }
+            _ => None,
+        }
+    }
+
+    /// Server and session details for the connection info panel. Best effort: parts the
+    /// provider or the account cannot report are left empty.
+    pub(crate) fn server_info(session: &AppSession) -> DbServerInfo {
+        match (session.provider, &session.session) {
+            (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
+                oracle::server_info(oracle_session)
+            }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                mysql::server_info(mysql_session)
+            }
+            _ => DbServerInfo {
+                version: Self::server_version(session),
+                ..DbServerInfo::default()
+            },
         }
     }
 
@@ -153,11 +1136,50 @@ impl ProviderRegistry {
             (DatabaseProvider::Oracle, ProviderSession::Oracle(oracle_session)) => {
                 Ok(oracle::transaction_active(oracle_session))
             }
+            (DatabaseProvider::Mysql, ProviderSession::Mysql(mysql_session)) => {
+                Ok(mysql::transaction_active(mysql_session))
+            }
+            // Each DuckDB statement runs in its own CLI process and commits on its own.
+            (DatabaseProvider::Duckdb, ProviderSession::Duckdb(_)) => Ok(false),
+            // The Snowflake SQL API autocommits every request.
+            (DatabaseProvider::Snowflake, ProviderSession::Snowflake(_)) => Ok(false),
+            // ClickHouse statements are not transactional.
+            (DatabaseProvider::Clickhouse, ProviderSession::Clickhouse(_)) => Ok(false),
+            // Each libSQL request runs on its own stream and autocommits.
+            (DatabaseProvider::Libsql, ProviderSession::Libsql(_)) => Ok(false),
+            // Protocol version 1 has no transaction methods; adapters autocommit.
+            (DatabaseProvider::External, ProviderSession::External(_)) => Ok(false),
             (provider, _) => Err(not_implemented_error(provider)),
         }
     }
 }
 
+fn oracle_interrupt(session: &oracle::OracleSession) -> QueryCancelHandle {
+    QueryCancelHandle::Oracle(oracle::cancel_handle(session))
+}
+
+/// An Oracle call timeout of zero means none, so at least a millisecond is set.
+fn call_time_limit(remaining: Duration) -> Duration {
+    remaining.max(Duration::from_millis(1))
+}
+
+/// Stops a streamed result between rows once its token is cancelled or out of time.
+struct CancellableSink<'a> {
+    sink: &'a mut dyn ExportRowSink,
+    cancel: &'a CancelToken,
+}
+
+impl ExportRowSink for CancellableSink<'_> {
+    fn columns(&mut self, columns: &[String]) -> Result<(), String> {
+        self.sink.columns(columns)
+    }
+
+    fn row(&mut self, values: &[ExportValue]) -> Result<(), String> {
+        self.cancel.check()?;
+        self.sink.row(values)
+    }
+}
+
 fn not_implemented_error(provider: DatabaseProvider) -> String {
     format!("Provider '{}' is not implemented yet.", provider.label())
 }
@@ -0,0 +1,913 @@
+use super::common::{
+    effective_query_row_limit, find_matching_line, note_lossy_conversion, null_flags_if_any,
+    row_matches_query_filters, truncate_for_snippet, DEFAULT_SCHEMA_SEARCH_LIMIT,
+    MAX_DDL_SEARCH_OBJECTS, MAX_EXPLORER_OBJECTS, MAX_SCHEMA_SEARCH_RESULTS,
+};
+use crate::sql_ident;
+use crate::types::{
+    DatabaseProvider, DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry,
+    DbObjectRef, DbQueryRequest, DbQueryResult, DbSchemaSearchRequest, DbSchemaSearchResult,
+    DbServerInfo, NetworkConnectOptions,
+};
+use mysql::consts::ColumnType;
+use mysql::prelude::Queryable;
+use mysql::{Column, Conn, Error as MysqlError, OptsBuilder, Row, Value};
+use std::cell::RefCell;
+use std::time::Duration;
+
+const DEFAULT_MYSQL_PORT: u16 = 3306;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+// Collation id MySQL reports for binary strings (BLOB, VARBINARY, BINARY).
+const BINARY_COLLATION_ID: u16 = 63;
+
+// MySQL needs `&mut Conn` even for reads; the cell keeps the read-only registry
+// entry points (`&AppSession`) usable for metadata queries.
+pub(crate) struct MysqlSession {
+    connection: RefCell<Conn>,
+    target_schema: String,
+    transaction_active: bool,
+}
+
+pub(crate) fn connect(
+    request: &NetworkConnectOptions,
+) -> Result<(MysqlSession, String, String), DbConnectError> {
+    let host = request.host.trim();
+    let port = request.port.unwrap_or(DEFAULT_MYSQL_PORT);
+    let username = request.username.trim();
+    // MySQL has no schema level below the database; a schema, when given, picks the database.
+    let schema = request
+        .schema
+        .as_deref()
+        .map(str::trim)
+        .filter(|schema| !schema.is_empty())
+        .unwrap_or(request.database.trim())
+        .to_string();
+
+    let options = OptsBuilder::new()
+        .ip_or_hostname(Some(host))
+        .tcp_port(port)
+        .user(Some(username))
+        .pass(Some(request.password.as_str()))
+        .db_name(Some(schema.as_str()))
+        .prefer_socket(false)
+        .tcp_connect_timeout(Some(CONNECT_TIMEOUT));
+    let connection = Conn::new(options).map_err(|error| {
+        DbConnectError::general(format!(
+            "Failed to connect to MySQL at {host}:{port}: {}",
+            map_mysql_error(error)
+        ))
+    })?;
+
+    let display_name = format!("{username}@{host}:{port} [{schema}]");
+    let session = MysqlSession {
+        connection: RefCell::new(connection),
+        target_schema: schema.clone(),
+        transaction_active: false,
+    };
+
+    Ok((session, display_name, schema))
+}
+
+pub(crate) fn server_version(session: &MysqlSession) -> Result<String, String> {
+    session
+        .connection
+        .borrow_mut()
+        .query_first::<String, _>("SELECT VERSION()")
+        .map_err(map_mysql_error)?
+        .ok_or_else(|| "The server did not report a version".to_string())
+}
+
+pub(crate) fn server_info(session: &MysqlSession) -> DbServerInfo {
+    type InfoRow = (
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<u64>,
+    );
+    let row = session.connection.borrow_mut().query_first::<InfoRow, _>(
+        "SELECT VERSION(), @@version_comment, @@hostname, CURRENT_USER(), DATABASE(), CONNECTION_ID()",
+    );
+    match row {
+        Ok(Some((version, banner, instance_name, current_user, current_schema, connection_id))) => {
+            DbServerInfo {
+                version,
+                banner,
+                instance_name,
+                container_name: None,
+                container_id: None,
+                current_user,
+                current_schema,
+                server_session_id: connection_id,
+                serial_number: None,
+                logon_time: None,
+                server_host: None,
+                failed_over: None,
+            }
+        }
+        _ => DbServerInfo::default(),
+    }
+}
+
+pub(crate) fn list_objects(session: &MysqlSession) -> Result<Vec<DbObjectEntry>, String> {
+    let sql = r#"
+        SELECT TABLE_SCHEMA, IF(TABLE_TYPE = 'VIEW', 'VIEW', 'TABLE'), TABLE_NAME
+        FROM information_schema.TABLES
+        WHERE TABLE_SCHEMA = ?
+          AND TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED', 'VIEW')
+        UNION ALL
+        SELECT ROUTINE_SCHEMA, ROUTINE_TYPE, ROUTINE_NAME
+        FROM information_schema.ROUTINES
+        WHERE ROUTINE_SCHEMA = ?
+          AND ROUTINE_TYPE IN ('PROCEDURE', 'FUNCTION')
+        UNION ALL
+        SELECT TRIGGER_SCHEMA, 'TRIGGER', TRIGGER_NAME
+        FROM information_schema.TRIGGERS
+        WHERE TRIGGER_SCHEMA = ?
+        ORDER BY 2, 3
+        LIMIT ?
+    "#;
+
+    let schema = session.target_schema.as_str();
+    let rows = session
+        .connection
+        .borrow_mut()
+        .exec::<(String, String, String), _, _>(sql, (schema, schema, schema, MAX_EXPLORER_OBJECTS))
+        .map_err(map_mysql_error)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(schema, object_type, object_name)| DbObjectEntry {
+            schema,
+            object_type,
+            object_name,
+            status: None,
+            invalid_reason: None,
+            edition_name: None,
+            created: None,
+            last_ddl_time: None,
+            temporary: false,
+            secondary: false,
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &MysqlSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = r#"
+        SELECT TABLE_SCHEMA, TABLE_NAME, COLUMN_NAME, UPPER(DATA_TYPE),
+               IF(IS_NULLABLE = 'YES', 'Y', 'N')
+        FROM information_schema.COLUMNS
+        WHERE TABLE_SCHEMA = ?
+        ORDER BY TABLE_NAME, ORDINAL_POSITION
+    "#;
+
+    let schema = session.target_schema.as_str();
+    let rows = session
+        .connection
+        .borrow_mut()
+        .exec::<(String, String, String, String, String), _, _>(sql, (schema,))
+        .map_err(map_mysql_error)?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(schema, object_name, column_name, data_type, nullable)| DbObjectColumnEntry {
+                schema,
+                object_name,
+                column_name,
+                data_type,
+                nullable,
+            },
+        )
+        .collect())
+}
+
+pub(crate) fn get_object_ddl(
+    session: &MysqlSession,
+    request: &DbObjectRef,
+) -> Result<String, String> {
+    let schema = request.schema.trim();
+    ensure_schema_is_in_scope(schema, session)?;
+    let object_name = request.object_name.trim();
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+
+    fetch_show_create(
+        &mut session.connection.borrow_mut(),
+        schema,
+        object_type.as_str(),
+        object_name,
+    )?
+    .ok_or_else(|| {
+        format!(
+            "The definition of {object_type} {schema}.{object_name} is not visible to the connected user."
+        )
+    })
+}
+
+pub(crate) fn search_schema_text(
+    session: &MysqlSession,
+    request: &DbSchemaSearchRequest,
+) -> Result<Vec<DbSchemaSearchResult>, String> {
+    let search_term = request.search_term.trim();
+    if search_term.is_empty() {
+        return Err("Search term is required".to_string());
+    }
+
+    let include_object_names = request.include_object_names.unwrap_or(true);
+    let include_source = request.include_source.unwrap_or(true);
+    let include_ddl = request.include_ddl.unwrap_or(true);
+    if !(include_object_names || include_source || include_ddl) {
+        return Err("Select at least one search scope".to_string());
+    }
+
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_SCHEMA_SEARCH_LIMIT)
+        .clamp(1, MAX_SCHEMA_SEARCH_RESULTS);
+    let mut matches = Vec::new();
+
+    if include_object_names {
+        search_object_names(session, search_term, limit, &mut matches)?;
+    }
+
+    if include_source {
+        search_source_text(session, search_term, limit, &mut matches)?;
+    }
+
+    if include_ddl {
+        search_ddl_text(session, search_term, limit, &mut matches)?;
+    }
+
+    Ok(matches)
+}
+
+fn search_object_names(
+    session: &MysqlSession,
+    search_term: &str,
+    limit: u32,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let remaining = (limit as usize).saturating_sub(matches.len());
+    if remaining == 0 {
+        return Ok(());
+    }
+
+    let remaining = remaining.min(MAX_SCHEMA_SEARCH_RESULTS as usize) as u32;
+    let sql = r#"
+        SELECT OBJECT_SCHEMA, OBJECT_TYPE, OBJECT_NAME
+        FROM (
+            SELECT TABLE_SCHEMA AS OBJECT_SCHEMA,
+                   IF(TABLE_TYPE = 'VIEW', 'VIEW', 'TABLE') AS OBJECT_TYPE,
+                   TABLE_NAME AS OBJECT_NAME
+            FROM information_schema.TABLES
+            WHERE TABLE_SCHEMA = ?
+              AND TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED', 'VIEW')
+            UNION ALL
+            SELECT ROUTINE_SCHEMA, ROUTINE_TYPE, ROUTINE_NAME
+            FROM information_schema.ROUTINES
+            WHERE ROUTINE_SCHEMA = ?
+              AND ROUTINE_TYPE IN ('PROCEDURE', 'FUNCTION')
+            UNION ALL
+            SELECT TRIGGER_SCHEMA, 'TRIGGER', TRIGGER_NAME
+            FROM information_schema.TRIGGERS
+            WHERE TRIGGER_SCHEMA = ?
+        ) OBJECTS
+        WHERE LOCATE(LOWER(?), LOWER(OBJECT_NAME)) > 0
+        ORDER BY OBJECT_TYPE, OBJECT_NAME
+        LIMIT ?
+    "#;
+
+    let schema = session.target_schema.as_str();
+    let rows = session
+        .connection
+        .borrow_mut()
+        .exec::<(String, String, String), _, _>(
+            sql,
+            (schema, schema, schema, search_term, remaining),
+        )
+        .map_err(map_mysql_error)?;
+
+    for (schema, object_type, object_name) in rows {
+        matches.push(DbSchemaSearchResult {
+            schema,
+            object_type,
+            snippet: truncate_for_snippet(object_name.as_str()),
+            tags: Vec::new(),
+            object_name,
+            match_scope: "object_name".to_string(),
+            line: None,
+        });
+    }
+
+    Ok(())
+}
+
+fn search_source_text(
+    session: &MysqlSession,
+    search_term: &str,
+    limit: u32,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let remaining = (limit as usize).saturating_sub(matches.len());
+    if remaining == 0 {
+        return Ok(());
+    }
+
+    let remaining = remaining.min(MAX_SCHEMA_SEARCH_RESULTS as usize) as u32;
+    let sql = r#"
+        SELECT OBJECT_SCHEMA, OBJECT_TYPE, OBJECT_NAME, SOURCE_TEXT
+        FROM (
+            SELECT ROUTINE_SCHEMA AS OBJECT_SCHEMA, ROUTINE_TYPE AS OBJECT_TYPE,
+                   ROUTINE_NAME AS OBJECT_NAME, ROUTINE_DEFINITION AS SOURCE_TEXT
+            FROM information_schema.ROUTINES
+            WHERE ROUTINE_SCHEMA = ?
+              AND ROUTINE_TYPE IN ('PROCEDURE', 'FUNCTION')
+            UNION ALL
+            SELECT TABLE_SCHEMA, 'VIEW', TABLE_NAME, VIEW_DEFINITION
+            FROM information_schema.VIEWS
+            WHERE TABLE_SCHEMA = ?
+            UNION ALL
+            SELECT TRIGGER_SCHEMA, 'TRIGGER', TRIGGER_NAME, ACTION_STATEMENT
+            FROM information_schema.TRIGGERS
+            WHERE TRIGGER_SCHEMA = ?
+        ) SOURCES
+        WHERE LOCATE(LOWER(?), LOWER(SOURCE_TEXT)) > 0
+        ORDER BY OBJECT_TYPE, OBJECT_NAME
+        LIMIT ?
+    "#;
+
+    let schema = session.target_schema.as_str();
+    let rows = session
+        .connection
+        .borrow_mut()
+        .exec::<(String, String, String, Option<String>), _, _>(
+            sql,
+            (schema, schema, schema, search_term, remaining),
+        )
+        .map_err(map_mysql_error)?;
+
+    // Routine bodies come back as one text value, so report the first matching line.
+    let needle_upper = search_term.to_ascii_uppercase();
+    for (schema, object_type, object_name, source) in rows {
+        let Some((line, snippet)) = source
+            .as_deref()
+            .and_then(|source| find_matching_line(source, needle_upper.as_str()))
+        else {
+            continue;
+        };
+        matches.push(DbSchemaSearchResult {
+            schema,
+            object_type,
+            object_name,
+            match_scope: "source".to_string(),
+            line: Some(line),
+            snippet: truncate_for_snippet(snippet.as_str()),
+            tags: Vec::new(),
+        });
+    }
+
+    Ok(())
+}
+
+fn search_ddl_text(
+    session: &MysqlSession,
+    search_term: &str,
+    limit: u32,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let remaining = (limit as usize).saturating_sub(matches.len());
+    if remaining == 0 {
+        return Ok(());
+    }
+
+    // Views, routines and triggers are covered by the source scope; only tables need
+    // their generated DDL searched.
+    let sql = r#"
+        SELECT TABLE_NAME
+        FROM information_schema.TABLES
+        WHERE TABLE_SCHEMA = ?
+          AND TABLE_TYPE IN ('BASE TABLE', 'SYSTEM VERSIONED')
+        ORDER BY TABLE_NAME
+        LIMIT ?
+    "#;
+
+    let schema = session.target_schema.as_str();
+    let table_names = session
+        .connection
+        .borrow_mut()
+        .exec::<String, _, _>(sql, (schema, MAX_DDL_SEARCH_OBJECTS))
+        .map_err(map_mysql_error)?;
+
+    let needle_upper = search_term.to_ascii_uppercase();
+    for table_name in table_names {
+        if matches.len() >= limit as usize {
+            break;
+        }
+
+        let Some(ddl) = fetch_show_create(
+            &mut session.connection.borrow_mut(),
+            schema,
+            "TABLE",
+            table_name.as_str(),
+        )?
+        else {
+            continue;
+        };
+
+        if let Some((line, snippet)) = find_matching_line(ddl.as_str(), needle_upper.as_str()) {
+            matches.push(DbSchemaSearchResult {
+                schema: schema.to_string(),
+                object_type: "TABLE".to_string(),
+                object_name: table_name,
+                match_scope: "ddl".to_string(),
+                line: Some(line),
+                snippet: truncate_for_snippet(snippet.as_str()),
+                tags: Vec::new(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn run_query(
+    session: &mut MysqlSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let transaction_control = detect_transaction_control(sql);
+    let mut result = session
+        .connection
+        .get_mut()
+        .query_iter(sql)
+        .map_err(map_mysql_error)?;
+
+    // A script can produce several result sets; the first one with columns is shown and
+    // the rest are drained when `result` is dropped.
+    let mut rows_affected = 0_u64;
+    while let Some(mut result_set) = result.iter() {
+        let column_info = result_set
+            .columns()
+            .as_ref()
+            .iter()
+            .map(|column| (column.name_str().into_owned(), is_binary_column(column)))
+            .collect::<Vec<_>>();
+        if column_info.is_empty() {
+            rows_affected += result_set.affected_rows();
+            continue;
+        }
+
+        let mut rows = Vec::new();
+        let mut null_flags = Vec::new();
+        let mut truncated = false;
+        for row_result in result_set.by_ref() {
+            if rows.len() >= row_limit {
+                truncated = true;
+                break;
+            }
+
+            let row = row_result.map_err(map_mysql_error)?;
+            let (values, nulls) = row_to_strings(row, column_info.as_slice());
+            rows.push(values);
+            null_flags.push(nulls);
+        }
+
+        let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+        if truncated {
+            message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+        }
+        let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+        return Ok(DbQueryResult {
+            columns: column_info.into_iter().map(|(name, _)| name).collect(),
+            rows,
+            null_flags: null_flags_if_any(null_flags),
+            byte_lengths: Vec::new(),
+            rows_affected: None,
+            message,
+            lossy_conversion,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
+    }
+    drop(result);
+
+    apply_transaction_control(session, transaction_control);
+    let message = match transaction_control {
+        TransactionControl::ImplicitCommit => "DDL executed.".to_string(),
+        _ => format!("Statement executed. {} row(s) affected.", rows_affected),
+    };
+
+    Ok(DbQueryResult {
+        columns: Vec::new(),
+        rows: Vec::new(),
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
+        rows_affected: Some(rows_affected),
+        message,
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &mut MysqlSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let mut result = session
+        .connection
+        .get_mut()
+        .query_iter(sql)
+        .map_err(map_mysql_error)?;
+    let Some(mut result_set) = result.iter() else {
+        return Err("Filtering is only available for query result sets.".to_string());
+    };
+    let column_info = result_set
+        .columns()
+        .as_ref()
+        .iter()
+        .map(|column| (column.name_str().into_owned(), is_binary_column(column)))
+        .collect::<Vec<_>>();
+    if column_info.is_empty() {
+        return Err("Filtering is only available for query result sets.".to_string());
+    }
+
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    for row_result in result_set.by_ref() {
+        let row = row_result.map_err(map_mysql_error)?;
+        let (values, nulls) = row_to_strings(row, column_info.as_slice());
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            continue;
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            break;
+        }
+    }
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: column_info.into_iter().map(|(name, _)| name).collect(),
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn begin_transaction(session: &mut MysqlSession) -> Result<bool, String> {
+    if !session.transaction_active {
+        session
+            .connection
+            .get_mut()
+            .query_drop("START TRANSACTION")
+            .map_err(map_mysql_error)?;
+    }
+    session.transaction_active = true;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn commit_transaction(session: &mut MysqlSession) -> Result<bool, String> {
+    if session.transaction_active {
+        session
+            .connection
+            .get_mut()
+            .query_drop("COMMIT")
+            .map_err(map_mysql_error)?;
+    }
+    session.transaction_active = false;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn rollback_transaction(session: &mut MysqlSession) -> Result<bool, String> {
+    if session.transaction_active {
+        session
+            .connection
+            .get_mut()
+            .query_drop("ROLLBACK")
+            .map_err(map_mysql_error)?;
+    }
+    session.transaction_active = false;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn transaction_active(session: &MysqlSession) -> bool {
+    session.transaction_active
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TransactionControl {
+    None,
+    Begin,
+    End,
+    ImplicitCommit,
+}
+
+// Autocommit stays on, so a transaction only exists between an explicit START TRANSACTION
+// (or BEGIN) and COMMIT/ROLLBACK. DDL commits implicitly, as in Oracle.
+fn detect_transaction_control(sql: &str) -> TransactionControl {
+    let normalized = sql.trim().trim_end_matches(';').trim();
+    let mut parts = normalized.split_whitespace();
+    let first = parts.next().unwrap_or_default().to_ascii_uppercase();
+    let second = parts.next().unwrap_or_default().to_ascii_uppercase();
+
+    match (first.as_str(), second.as_str()) {
+        ("START", "TRANSACTION") | ("BEGIN", "") | ("BEGIN", "WORK") => TransactionControl::Begin,
+        ("ROLLBACK", "TO") => TransactionControl::None,
+        ("COMMIT" | "ROLLBACK", _) => TransactionControl::End,
+        ("CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "RENAME", _) => {
+            TransactionControl::ImplicitCommit
+        }
+        _ => TransactionControl::None,
+    }
+}
+
+fn apply_transaction_control(session: &mut MysqlSession, control: TransactionControl) {
+    match control {
+        TransactionControl::Begin => session.transaction_active = true,
+        TransactionControl::End | TransactionControl::ImplicitCommit => {
+            session.transaction_active = false;
+        }
+        TransactionControl::None => {}
+    }
+}
+
+/// Runs the matching SHOW CREATE statement. Returns `None` when the server hides the
+/// definition (routines owned by other users show a NULL body without SHOW_ROUTINE).
+fn fetch_show_create(
+    connection: &mut Conn,
+    schema: &str,
+    object_type: &str,
+    object_name: &str,
+) -> Result<Option<String>, String> {
+    let definition_column = match object_type {
+        "TABLE" => "Create Table",
+        "VIEW" => "Create View",
+        "PROCEDURE" => "Create Procedure",
+        "FUNCTION" => "Create Function",
+        "TRIGGER" => "SQL Original Statement",
+        _ => {
+            return Err(format!(
+                "DDL is not available for MySQL {object_type} objects"
+            ))
+        }
+    };
+    let sql = format!(
+        "SHOW CREATE {object_type} {}.{}",
+        sql_ident::quote_identifier(DatabaseProvider::Mysql, schema),
+        sql_ident::quote_identifier(DatabaseProvider::Mysql, object_name)
+    );
+
+    let Some(row) = connection
+        .query_first::<Row, _>(sql)
+        .map_err(map_mysql_error)?
+    else {
+        return Err(format!(
+            "{object_type} {schema}.{object_name} was not found"
+        ));
+    };
+    let index = row
+        .columns_ref()
+        .iter()
+        .position(|column| column.name_str() == definition_column)
+        .ok_or_else(|| format!("SHOW CREATE {object_type} did not return a definition"))?;
+
+    Ok(match row.as_ref(index) {
+        Some(Value::Bytes(bytes)) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    })
+}
+
+/// Returns the display values of a row and which of them are NULL.
+fn row_to_strings(row: Row, column_info: &[(String, bool)]) -> (Vec<String>, Vec<bool>) {
+    row.unwrap()
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let is_binary = column_info
+                .get(index)
+                .is_some_and(|(_, is_binary)| *is_binary);
+            (
+                mysql_value_to_string(&value, is_binary),
+                matches!(value, Value::NULL),
+            )
+        })
+        .unzip()
+}
+
+fn is_binary_column(column: &Column) -> bool {
+    column.character_set() == BINARY_COLLATION_ID
+        && matches!(
+            column.column_type(),
+            ColumnType::MYSQL_TYPE_TINY_BLOB
+                | ColumnType::MYSQL_TYPE_MEDIUM_BLOB
+                | ColumnType::MYSQL_TYPE_LONG_BLOB
+                | ColumnType::MYSQL_TYPE_BLOB
+                | ColumnType::MYSQL_TYPE_VAR_STRING
+                | ColumnType::MYSQL_TYPE_STRING
+                | ColumnType::MYSQL_TYPE_GEOMETRY
+        )
+}
+
+fn mysql_value_to_string(value: &Value, is_binary: bool) -> String {
+    match value {
+        Value::NULL => "NULL".to_string(),
+        Value::Bytes(bytes) if is_binary => {
+            let mut hex = String::with_capacity(2 + bytes.len() * 2);
+            hex.push_str("0x");
+            for byte in bytes {
+                hex.push_str(&format!("{byte:02X}"));
+            }
+            hex
+        }
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Int(value) => value.to_string(),
+        Value::UInt(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Double(value) => value.to_string(),
+        Value::Date(year, month, day, hour, minute, second, micros) => {
+            let mut text =
+                format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}");
+            if *micros > 0 {
+                text.push_str(&format!(".{micros:06}"));
+            }
+            text
+        }
+        Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            let total_hours = u64::from(*days) * 24 + u64::from(*hours);
+            let mut text = format!(
+                "{}{total_hours:02}:{minutes:02}:{seconds:02}",
+                if *negative { "-" } else { "" }
+            );
+            if *micros > 0 {
+                text.push_str(&format!(".{micros:06}"));
+            }
+            text
+        }
+    }
+}
+
+fn ensure_schema_is_in_scope(schema: &str, session: &MysqlSession) -> Result<(), String> {
+    if schema != session.target_schema {
+        return Err(format!(
+            "Connected database is {}. Object access is limited to that database.",
+            session.target_schema
+        ));
+    }
+
+    Ok(())
+}
+
+fn map_mysql_error(error: MysqlError) -> String {
+    error.to_string()
+}
+
+/// Errors the `mysql` crate raises when the connection failed rather than the statement,
+/// and the server's "gone away", "lost connection" and idle-timeout disconnect codes.
+pub(crate) fn is_connection_lost_error(message: &str) -> bool {
+    ["IoError {", "CodecError {", "DriverError {", "TlsError {"]
+        .iter()
+        .any(|kind| message.contains(kind))
+        || ["ERROR 2006 ", "ERROR 2013 ", "ERROR 4031 "]
+            .iter()
+            .any(|code| message.contains(code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        detect_transaction_control, is_connection_lost_error, mysql_value_to_string,
+        TransactionControl,
+    };
+    use mysql::Value;
+
+    #[test]
+    fn formats_mysql_values_for_the_result_grid() {
+        assert_eq!(mysql_value_to_string(&Value::NULL, false), "NULL");
+        assert_eq!(
+            mysql_value_to_string(&Value::Bytes(b"Gda\xC5\x84sk".to_vec()), false),
+            "Gdańsk"
+        );
+        assert_eq!(
+            mysql_value_to_string(&Value::Bytes(vec![0x00, 0xAB]), true),
+            "0x00AB"
+        );
+        assert_eq!(
+            mysql_value_to_string(&Value::Date(2024, 2, 29, 13, 5, 9, 0), false),
+            "2024-02-29 13:05:09"
+        );
+        assert_eq!(
+            mysql_value_to_string(&Value::Time(true, 1, 2, 3, 4, 500), false),
+            "-26:03:04.000500"
+        );
+    }
+
+    #[test]
+    fn detects_mysql_transaction_boundaries() {
+        assert_eq!(
+            detect_transaction_control("start transaction;"),
+            TransactionControl::Begin
+        );
+        assert_eq!(
+            detect_transaction_control("BEGIN"),
+            TransactionControl::Begin
+        );
+        assert_eq!(
+            detect_transaction_control("rollback to savepoint a"),
+            TransactionControl::None
+        );
+        assert_eq!(
+            detect_transaction_control("COMMIT"),
+            TransactionControl::End
+        );
+        assert_eq!(
+            detect_transaction_control("alter table t add c int"),
+            TransactionControl::ImplicitCommit
+        );
+        assert_eq!(
+            detect_transaction_control("update t set c = 1"),
+            TransactionControl::None
+        );
+    }
+
+    #[test]
+    fn tells_lost_connections_from_failed_statements() {
+        assert!(is_connection_lost_error(
+            "IoError { Connection reset by peer (os error 104) }"
+        ));
+        assert!(is_connection_lost_error(
+            "DriverError { Connection to the server is closed. }"
+        ));
+        assert!(is_connection_lost_error(
+            "MySqlError { ERROR 4031 (HY000): The client was disconnected by the server because of inactivity. }"
+        ));
+        assert!(!is_connection_lost_error(
+            "MySqlError { ERROR 1146 (42S02): Table 'shop.orderz' doesn't exist }"
+        ));
+    }
+}
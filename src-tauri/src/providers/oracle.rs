@@ -1,81 +1,418 @@
+use super::common::{
+    effective_query_row_limit, find_matching_line, note_lossy_conversion, null_flags_if_any,
+    row_matches_query_filters, skip_leading_sql_comments, truncate_for_snippet,
+    DEFAULT_QUERY_ROW_LIMIT, DEFAULT_SCHEMA_SEARCH_LIMIT, MAX_DDL_SEARCH_OBJECTS,
+    MAX_EXPLORER_OBJECTS, MAX_QUERY_ROW_LIMIT, MAX_SCHEMA_SEARCH_RESULTS,
+};
+use crate::cancellation::CancelToken;
+use crate::data_export::{ExportDateTime, ExportRowSink, ExportValue};
+use crate::sql_ident;
+use crate::sql_splitter::split_statements;
+use crate::tnsnames;
 use crate::types::{
-    DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectDdlUpdateRequest,
-    DbObjectEntry, DbObjectRef, DbQueryRequest, DbQueryResult, DbSchemaSearchRequest,
-    DbSchemaSearchResult, OracleAuthMode, OracleConnectOptions,
+    DatabaseProvider, DbAqBrowseRequest, DbAqBrowseResult, DbAqMessage, DbAqQueueEntry, DbBindType,
+    DbConnectError, DbConsistentQueriesRequest, DbConsistentQueriesResult, DbConstraintType,
+    DbContainer, DbDownloadLobRequest, DbEncodingInfo, DbFilteredQueryRequest, DbLobDownloadResult,
+    DbObjectAccessStatsRequest, DbObjectAccessStatsResult, DbObjectColumnEntry, DbObjectConstraint,
+    DbObjectDdl, DbObjectDdlUpdateRequest, DbObjectDependent, DbObjectEditionInfo, DbObjectEntry,
+    DbObjectGrant, DbObjectName, DbObjectRef, DbObjectStats, DbQueryBind, DbQueryRequest,
+    DbQueryResult, DbRecentErrorsRequest, DbRecentErrorsResult, DbRefCursorResult,
+    DbResourceLimits, DbRowLockWaiter, DbRowLocksRequest, DbRowLocksResult, DbSchemaSearchRequest,
+    DbSchemaSearchResult, DbServerErrorEntry, DbServerInfo, DbSessionNls, DbSessionReconnected,
+    DbSetContainerRequest, DbSetResourceLimitsRequest, DbSetSessionNlsRequest, DbTableAccessStats,
+    DbTableLockHolder, OracleAuthMode, OracleConnectOptions, OracleFailoverOptions, OracleProtocol,
+    OracleTafType, OracleTlsOptions, TimestampTzMode,
+};
+use oracle::sql_type::{Blob, Clob, Lob, Nclob, OracleType, RefCursor, Timestamp};
+use oracle::{
+    Connection, Connector, Error as OracleError, InitParams, Privilege, ResultSet, Row, SqlValue,
+    Statement,
 };
-use oracle::{Connection, Connector, Error as OracleError, InitParams, Privilege, SqlValue};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::env;
-use std::fs;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
-
-const MAX_EXPLORER_OBJECTS: u32 = 5000;
-const DEFAULT_QUERY_ROW_LIMIT: u32 = 1000;
-const MAX_QUERY_ROW_LIMIT: u32 = 10000;
-const DEFAULT_SCHEMA_SEARCH_LIMIT: u32 = 200;
-const MAX_SCHEMA_SEARCH_RESULTS: u32 = 1000;
-const MAX_DDL_SEARCH_OBJECTS: u32 = 2000;
-const MAX_SEARCH_SNIPPET_CHARS: usize = 220;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+const EXPORT_FETCH_ARRAY_SIZE: u32 = 500;
+const DEFAULT_AQ_BROWSE_LIMIT: u32 = 100;
+const MAX_AQ_BROWSE_LIMIT: u32 = 1000;
+const MAX_AQ_PAYLOAD_CHARS: usize = 65_536;
+const DEFAULT_RECENT_ERRORS_MINUTES: u32 = 60;
+const MAX_RECENT_ERRORS_MINUTES: u32 = 7 * 24 * 60;
+const DEFAULT_RECENT_ERRORS_LIMIT: u32 = 200;
+const MAX_RECENT_ERRORS_LIMIT: u32 = 1000;
+const DEFAULT_ACCESS_STATS_LIMIT: u32 = 200;
+const MAX_ACCESS_STATS_LIMIT: u32 = 2000;
+const MAX_ACCESS_STATS_HISTORY_DAYS: u32 = 90;
+const DEFAULT_BINARY_PREVIEW_BYTES: u32 = 64;
+const MAX_BINARY_PREVIEW_BYTES: u32 = 4096;
+
+static LOADED_CLIENT_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+static LOADED_CONFIG_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
 
 pub(crate) struct OracleSession {
-    pub(crate) connection: Connection,
+    /// Shared so a running statement can be interrupted from another thread.
+    pub(crate) connection: Arc<Connection>,
     target_schema: String,
     transaction_active: bool,
+    timestamp_tz_mode: TimestampTzMode,
+    connect_options: OracleConnectOptions,
+    reconnect_notice: Option<DbSessionReconnected>,
+    /// The profile's startup SQL, run again whenever the session reconnects.
+    startup_sql: Option<String>,
+    /// NLS parameters set through `set_session_nls`, restored after a reconnect.
+    nls_overrides: Vec<(&'static str, String)>,
+    /// Consumer group chosen through `set_resource_limits`, restored after a reconnect.
+    consumer_group: Option<String>,
+    /// The group reported by the first switch, so clearing the choice can switch back.
+    original_consumer_group: Option<String>,
+    call_timeout_secs: Option<u32>,
+    /// Container chosen through `set_container`, restored after a reconnect.
+    container: Option<String>,
+    /// Rows of the last truncated query not yet sent; replaced by the next one.
+    open_cursor: Option<OpenCursor>,
+    next_cursor_id: u64,
+}
+
+/// A query result kept open so the grid can page past the first `row_limit` rows.
+struct OpenCursor {
+    id: u64,
+    columns: Vec<String>,
+    rows: ResultSet<'static, Row>,
+    /// Read ahead to learn whether more rows remain.
+    pending: Option<Row>,
+    fetched: u64,
+    timestamp_tz_target: TimestampTzTarget,
+    preview_bytes: usize,
+}
+
+struct FetchedRows {
+    rows: Vec<Vec<String>>,
+    null_flags: Vec<Vec<bool>>,
+    byte_lengths: Vec<Vec<Option<u64>>>,
+    more: bool,
+}
+
+impl OpenCursor {
+    /// Reads up to `count` rows, and one more to tell whether any remain.
+    fn fetch(&mut self, count: usize) -> Result<FetchedRows, String> {
+        let mut fetched = FetchedRows {
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            more: false,
+        };
+        while fetched.rows.len() < count {
+            let row = match self.pending.take() {
+                Some(row) => row,
+                None => match self.rows.next() {
+                    Some(row) => row.map_err(map_oracle_error)?,
+                    None => return Ok(fetched),
+                },
+            };
+            let (values, lengths) = display_row(
+                row.sql_values(),
+                &self.timestamp_tz_target,
+                self.preview_bytes,
+            );
+            fetched.rows.push(values);
+            fetched
+                .null_flags
+                .push(sql_value_null_flags(row.sql_values()));
+            fetched.byte_lengths.push(lengths);
+        }
+        self.fetched += fetched.rows.len() as u64;
+        if let Some(row) = self.rows.next() {
+            self.pending = Some(row.map_err(map_oracle_error)?);
+            fetched.more = true;
+        }
+        Ok(fetched)
+    }
 }
 
 pub(crate) fn connect(
     request: &OracleConnectOptions,
+    timestamp_tz_mode: TimestampTzMode,
 ) -> Result<(OracleSession, String, String), DbConnectError> {
-    ensure_oracle_client_initialized(request.oracle_client_lib_dir.as_deref())?;
+    let password_store = request.external_password_store.as_deref();
+    ensure_oracle_client_initialized(
+        request.oracle_client_lib_dir.as_deref(),
+        password_store.map(|store| Path::new(store.wallet_dir.trim())),
+    )?;
 
     let host = request.host.trim();
-    let port = request.port.unwrap_or(1521);
+    let port = request.port.unwrap_or(request.protocol.default_port());
     let service_name = request.service_name.trim();
     let username = request.username.trim();
     let password = request.password.as_str();
-    let schema = normalize_schema_name(&request.schema).map_err(DbConnectError::general)?;
+    let schema = sql_ident::parse_identifier(DatabaseProvider::Oracle, &request.schema, "Schema")
+        .map_err(DbConnectError::general)?;
+    let edition = match request.edition.as_deref().map(str::trim) {
+        Some(edition) if !edition.is_empty() => Some(
+            normalize_unquoted_identifier(edition, "Edition").map_err(DbConnectError::general)?,
+        ),
+        _ => None,
+    };
+    // With a password store the wallet supplies the user, as in `/@alias`.
+    let login = match password_store {
+        Some(_) => String::new(),
+        None => {
+            proxy_login(username, request.proxy_user.as_deref()).map_err(DbConnectError::general)?
+        }
+    };
 
-    let connect_string = format!("//{}:{}/{}", host, port, service_name);
+    let (target, connect_string) = match (
+        password_store.map(|store| store.alias.trim()),
+        non_empty(request.connect_descriptor.as_deref()),
+    ) {
+        (Some(alias), _) => (alias.to_string(), alias.to_string()),
+        (None, Some(descriptor)) => (descriptor_target(descriptor), descriptor.to_string()),
+        (None, None) => {
+            let mut addresses = vec![(host, port)];
+            addresses.extend(request.failover_hosts.iter().map(|address| {
+                (
+                    address.host.trim(),
+                    address.port.unwrap_or(request.protocol.default_port()),
+                )
+            }));
+            let hosts = addresses
+                .iter()
+                .map(|(host, port)| format!("{host}:{port}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            let target = match request.protocol {
+                OracleProtocol::Tcp => format!("//{hosts}/{service_name}"),
+                OracleProtocol::Tcps => format!("tcps://{hosts}/{service_name}"),
+            };
+            let connect_string = if request.protocol == OracleProtocol::Tcp
+                && addresses.len() == 1
+                && request.failover.is_none()
+            {
+                target.clone()
+            } else {
+                connect_descriptor(
+                    request.protocol,
+                    &addresses,
+                    service_name,
+                    request.tls.as_deref(),
+                    request.failover.as_deref(),
+                )
+                .map_err(DbConnectError::general)?
+            };
+            (target, connect_string)
+        }
+    };
     let connection = connect_with_mode(
-        username,
+        login.as_str(),
         password,
         connect_string.as_str(),
         request.oracle_auth_mode,
+        password_store.is_some(),
     )
-    .map_err(|error| map_connect_error(error, host, port, service_name))?;
-    let alter_schema_sql = format!("ALTER SESSION SET CURRENT_SCHEMA = {}", schema);
+    .map_err(|error| map_connect_error(error, target.as_str()))?;
+    let alter_schema_sql = format!(
+        "ALTER SESSION SET CURRENT_SCHEMA = {}",
+        sql_ident::format_identifier(DatabaseProvider::Oracle, &schema)
+    );
     connection
         .execute(alter_schema_sql.as_str(), &[])
         .map_err(|e| DbConnectError::general(map_oracle_error(e)))?;
+    if let Some(edition) = edition.as_deref() {
+        let alter_edition_sql = format!(
+            "ALTER SESSION SET EDITION = {}",
+            sql_ident::format_identifier(DatabaseProvider::Oracle, edition)
+        );
+        connection
+            .execute(alter_edition_sql.as_str(), &[])
+            .map_err(|e| DbConnectError::general(map_oracle_error(e)))?;
+    }
 
-    let display_name = format!(
+    let user = if password_store.is_some() {
+        "/"
+    } else {
+        login.as_str()
+    };
+    let mut display_name = format!(
         "{}@{} [{}]",
-        format_oracle_user_label(username, request.oracle_auth_mode),
-        connect_string,
+        format_oracle_user_label(user, request.oracle_auth_mode),
+        target,
         schema
     );
+    if let Some(edition) = edition.as_deref() {
+        display_name.push_str(&format!(" edition {edition}"));
+    }
     let session = OracleSession {
-        connection,
+        connection: Arc::new(connection),
         target_schema: schema.clone(),
         transaction_active: false,
+        timestamp_tz_mode,
+        connect_options: request.clone(),
+        reconnect_notice: None,
+        startup_sql: None,
+        nls_overrides: Vec::new(),
+        consumer_group: None,
+        original_consumer_group: None,
+        call_timeout_secs: None,
+        container: None,
+        open_cursor: None,
+        next_cursor_id: 1,
     };
 
     Ok((session, display_name, schema))
 }
 
+/// Transparent Application Failover retries about two and a half minutes, long enough for a
+/// RAC node restart.
+const DEFAULT_TAF_RETRIES: u32 = 30;
+const DEFAULT_TAF_DELAY_SECS: u32 = 5;
+
+/// Builds a full connect descriptor so TCPS and failover work without a `tnsnames.ora` or
+/// `sqlnet.ora` entry. Several addresses go in an `ADDRESS_LIST`, tried in order unless
+/// `failover` load balances them. `MY_WALLET_DIRECTORY` and `RETRY_DELAY` need Oracle
+/// Client 18c or later.
+fn connect_descriptor(
+    protocol: OracleProtocol,
+    addresses: &[(&str, u16)],
+    service_name: &str,
+    tls: Option<&OracleTlsOptions>,
+    failover: Option<&OracleFailoverOptions>,
+) -> Result<String, String> {
+    let failover = failover.cloned().unwrap_or_default();
+    let protocol_name = match protocol {
+        OracleProtocol::Tcp => "TCP",
+        OracleProtocol::Tcps => "TCPS",
+    };
+    let mut address_entries = Vec::with_capacity(addresses.len());
+    for (host, port) in addresses {
+        if host.is_empty() {
+            return Err("Failover hosts need a host name".to_string());
+        }
+        address_entries.push(format!(
+            "(ADDRESS=(PROTOCOL={protocol_name})(HOST={})(PORT={port}))",
+            quote_descriptor_value(host, "Host")?
+        ));
+    }
+    let address = if address_entries.len() == 1 {
+        address_entries.concat()
+    } else {
+        format!(
+            "(ADDRESS_LIST=(FAILOVER=ON)(LOAD_BALANCE={}){})",
+            if failover.load_balance { "ON" } else { "OFF" },
+            address_entries.concat()
+        )
+    };
+    let mut retry = String::new();
+    if let Some(count) = failover.retry_count {
+        retry.push_str(&format!("(RETRY_COUNT={count})"));
+    }
+    if let Some(delay) = failover.retry_delay_secs {
+        retry.push_str(&format!("(RETRY_DELAY={delay})"));
+    }
+    let failover_mode = failover.taf.as_ref().map_or(String::new(), |taf| {
+        format!(
+            "(FAILOVER_MODE=(TYPE={})(METHOD=BASIC)(RETRIES={})(DELAY={}))",
+            match taf.failover_type {
+                OracleTafType::Select => "SELECT",
+                OracleTafType::Session => "SESSION",
+            },
+            taf.retries.unwrap_or(DEFAULT_TAF_RETRIES),
+            taf.delay_secs.unwrap_or(DEFAULT_TAF_DELAY_SECS)
+        )
+    });
+    let connect_data = format!(
+        "(CONNECT_DATA=(SERVICE_NAME={}){failover_mode})",
+        quote_descriptor_value(service_name, "Service name")?
+    );
+    if protocol == OracleProtocol::Tcp {
+        return Ok(format!("(DESCRIPTION={retry}{address}{connect_data})"));
+    }
+
+    let mut security = Vec::new();
+    let tls = tls.cloned().unwrap_or_default();
+    let dn_match = tls.server_dn_match.unwrap_or(true);
+    security.push(format!(
+        "(SSL_SERVER_DN_MATCH={})",
+        if dn_match { "YES" } else { "NO" }
+    ));
+    if let Some(dn) = non_empty(tls.server_cert_dn.as_deref()) {
+        security.push(format!(
+            "(SSL_SERVER_CERT_DN={})",
+            quote_descriptor_value(dn, "Server certificate DN")?
+        ));
+    }
+    if let Some(wallet) = non_empty(tls.wallet_location.as_deref()) {
+        if !Path::new(wallet).is_dir() {
+            return Err(format!("Wallet location {wallet} is not a directory"));
+        }
+        security.push(format!(
+            "(MY_WALLET_DIRECTORY={})",
+            quote_descriptor_value(wallet, "Wallet location")?
+        ));
+    }
+
+    Ok(format!(
+        "(DESCRIPTION={retry}{address}{connect_data}(SECURITY={}))",
+        security.concat()
+    ))
+}
+
+/// A short label for a session opened with a raw descriptor: its first address and service.
+fn descriptor_target(descriptor: &str) -> String {
+    match tnsnames::parse_descriptor(descriptor) {
+        Ok(target) => {
+            let (host, port) = target.endpoint();
+            format!("//{host}:{port}/{}", target.service_name)
+        }
+        Err(_) => "connect descriptor".to_string(),
+    }
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Descriptor values with parentheses, `=` or spaces must be double-quoted, and a quoted
+/// value cannot itself contain a double quote.
+fn quote_descriptor_value(value: &str, label: &str) -> Result<String, String> {
+    if value.contains('"') {
+        return Err(format!("{label} cannot contain double quotes"));
+    }
+    if value
+        .chars()
+        .any(|ch| matches!(ch, '(' | ')' | '=' | ',' | '\\' | '\'') || ch.is_whitespace())
+    {
+        return Ok(format!("\"{value}\""));
+    }
+    Ok(value.to_string())
+}
+
 fn connect_with_mode(
     username: &str,
     password: &str,
     connect_string: &str,
     auth_mode: OracleAuthMode,
+    external_auth: bool,
 ) -> Result<Connection, OracleError> {
-    match auth_mode {
-        OracleAuthMode::Normal => Connection::connect(username, password, connect_string),
-        OracleAuthMode::Sysdba => {
-            let mut connector = Connector::new(username, password, connect_string);
-            connector.privilege(Privilege::Sysdba);
-            connector.connect()
+    let mut connector = Connector::new(username, password, connect_string);
+    connector.external_auth(external_auth);
+    if auth_mode == OracleAuthMode::Sysdba {
+        connector.privilege(Privilege::Sysdba);
+    }
+    connector.connect()
+}
+
+/// The `user[proxy_user]` form the Oracle client reads as proxy authentication.
+fn proxy_login(username: &str, proxy_user: Option<&str>) -> Result<String, String> {
+    match proxy_user.map(str::trim) {
+        Some(proxy_user) if !proxy_user.is_empty() => {
+            let proxy_user = normalize_unquoted_identifier(proxy_user, "Proxy user")?;
+            Ok(format!("{username}[{proxy_user}]"))
         }
+        _ => Ok(username.to_string()),
     }
 }
 
@@ -86,11 +423,98 @@ fn format_oracle_user_label(username: &str, auth_mode: OracleAuthMode) -> String
     }
 }
 
+/// The database release, e.g. `19.24.0.0.0`.
+pub(crate) fn server_version(session: &OracleSession) -> Result<String, String> {
+    let (version, _banner) = session
+        .connection
+        .server_version()
+        .map_err(map_oracle_error)?;
+    Ok(version.to_string())
+}
+
+pub(crate) fn instance_name(session: &OracleSession) -> Option<String> {
+    session
+        .connection
+        .query_row_as::<Option<String>>(
+            "SELECT SYS_CONTEXT('USERENV', 'INSTANCE_NAME') FROM DUAL",
+            &[],
+        )
+        .ok()
+        .flatten()
+}
+
+pub(crate) fn server_info(session: &OracleSession) -> DbServerInfo {
+    type ContextRow = (
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    );
+    let mut info = DbServerInfo::default();
+    if let Ok((version, banner)) = session.connection.server_version() {
+        info.version = Some(version.to_string());
+        info.banner = Some(banner);
+    }
+    if let Ok((instance_name, server_host, current_user, current_schema, sid)) = session
+        .connection
+        .query_row_as::<ContextRow>(
+        "SELECT SYS_CONTEXT('USERENV', 'INSTANCE_NAME'), SYS_CONTEXT('USERENV', 'SERVER_HOST'), \
+             SYS_CONTEXT('USERENV', 'SESSION_USER'), SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA'), \
+             SYS_CONTEXT('USERENV', 'SID') FROM DUAL",
+        &[],
+    ) {
+        info.instance_name = instance_name;
+        info.server_host = server_host;
+        info.current_user = current_user;
+        info.current_schema = current_schema;
+        info.server_session_id = sid.and_then(|sid| sid.trim().parse().ok());
+    }
+    // CON_NAME and CON_ID exist from 12c on; older servers reject them.
+    if let Ok((container_name, container_id)) = session
+        .connection
+        .query_row_as::<(Option<String>, Option<String>)>(
+            "SELECT SYS_CONTEXT('USERENV', 'CON_NAME'), SYS_CONTEXT('USERENV', 'CON_ID') FROM DUAL",
+            &[],
+        )
+    {
+        info.container_name = container_name;
+        info.container_id = container_id.and_then(|id| id.trim().parse().ok());
+    }
+    // V$SESSION needs a grant many accounts lack.
+    if let Some(sid) = info.server_session_id {
+        if let Ok((serial, logon_time, failed_over)) =
+            session
+                .connection
+                .query_row_as::<(u64, Option<String>, Option<String>)>(
+                "SELECT SERIAL#, TO_CHAR(LOGON_TIME, 'YYYY-MM-DD\"T\"HH24:MI:SS'), FAILED_OVER \
+                 FROM V$SESSION WHERE SID = :1",
+                &[&sid],
+            )
+        {
+            info.serial_number = Some(serial);
+            info.logon_time = logon_time;
+            info.failed_over = failed_over.map(|failed_over| failed_over == "YES");
+        }
+    }
+    info
+}
+
 pub(crate) fn list_objects(session: &OracleSession) -> Result<Vec<DbObjectEntry>, String> {
     let sql = r#"
-        SELECT OWNER, OBJECT_TYPE, OBJECT_NAME, STATUS
+        SELECT OWNER, OBJECT_TYPE, OBJECT_NAME, STATUS, EDITION_NAME, CREATED, LAST_DDL_TIME,
+               TEMPORARY, SECONDARY
         FROM (
-            SELECT OWNER, OBJECT_TYPE, OBJECT_NAME, STATUS
+            SELECT
+                OWNER,
+                OBJECT_TYPE,
+                OBJECT_NAME,
+                STATUS,
+                EDITION_NAME,
+                TO_CHAR(CREATED, 'YYYY-MM-DD"T"HH24:MI:SS') AS CREATED,
+                TO_CHAR(LAST_DDL_TIME, 'YYYY-MM-DD"T"HH24:MI:SS') AS LAST_DDL_TIME,
+                TEMPORARY,
+                SECONDARY
             FROM ALL_OBJECTS
             WHERE OWNER = :1
               AND OBJECT_TYPE IN (
@@ -145,6 +569,25 @@ pub(crate) fn list_objects(session: &OracleSession) -> Result<Vec<DbObjectEntry>
             object_name,
             status,
             invalid_reason,
+            edition_name: row
+                .get::<usize, Option<String>>(4)
+                .map_err(map_oracle_error)?,
+            created: row
+                .get::<usize, Option<String>>(5)
+                .map_err(map_oracle_error)?,
+            last_ddl_time: row
+                .get::<usize, Option<String>>(6)
+                .map_err(map_oracle_error)?,
+            temporary: row
+                .get::<usize, Option<String>>(7)
+                .map_err(map_oracle_error)?
+                .as_deref()
+                == Some("Y"),
+            secondary: row
+                .get::<usize, Option<String>>(8)
+                .map_err(map_oracle_error)?
+                .as_deref()
+                == Some("Y"),
         });
     }
 
@@ -237,8 +680,14 @@ fn fetch_object_compile_diagnostics(
             "TEXT".to_string(),
         ],
         rows: result_rows,
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message: String::new(),
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
@@ -272,13 +721,215 @@ pub(crate) fn list_object_columns(
     Ok(columns)
 }
 
+pub(crate) fn list_columns_of(
+    session: &OracleSession,
+    objects: &[DbObjectName],
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = r#"
+        SELECT OWNER, TABLE_NAME, COLUMN_NAME, DATA_TYPE, NULLABLE
+        FROM ALL_TAB_COLUMNS
+        WHERE OWNER = :1 AND TABLE_NAME = :2
+        ORDER BY COLUMN_ID
+    "#;
+
+    let mut columns = Vec::new();
+    for object in objects {
+        let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &object.schema);
+        ensure_schema_is_in_scope(&schema, session)?;
+        let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &object.object_name);
+        let rows = session
+            .connection
+            .query(sql, &[&schema, &object_name])
+            .map_err(map_oracle_error)?;
+        for row_result in rows {
+            let row = row_result.map_err(map_oracle_error)?;
+            columns.push(DbObjectColumnEntry {
+                schema: row.get::<usize, String>(0).map_err(map_oracle_error)?,
+                object_name: row.get::<usize, String>(1).map_err(map_oracle_error)?,
+                column_name: row.get::<usize, String>(2).map_err(map_oracle_error)?,
+                data_type: row.get::<usize, String>(3).map_err(map_oracle_error)?,
+                nullable: row.get::<usize, String>(4).map_err(map_oracle_error)?,
+            });
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Primary key, unique, foreign key and check constraints of each object, in the order of
+/// `objects`. The `NOT NULL` checks Oracle names itself are left out; columns report those.
+pub(crate) fn list_constraints_of(
+    session: &OracleSession,
+    objects: &[DbObjectName],
+) -> Result<Vec<Vec<DbObjectConstraint>>, String> {
+    let sql = r#"
+        SELECT c.CONSTRAINT_NAME, c.CONSTRAINT_TYPE, c.STATUS, r.OWNER, r.TABLE_NAME,
+               cc.COLUMN_NAME
+        FROM ALL_CONSTRAINTS c
+        LEFT JOIN ALL_CONS_COLUMNS cc
+          ON cc.OWNER = c.OWNER
+         AND cc.CONSTRAINT_NAME = c.CONSTRAINT_NAME
+        LEFT JOIN ALL_CONSTRAINTS r
+          ON r.OWNER = c.R_OWNER
+         AND r.CONSTRAINT_NAME = c.R_CONSTRAINT_NAME
+        WHERE c.OWNER = :1
+          AND c.TABLE_NAME = :2
+          AND c.CONSTRAINT_TYPE IN ('P', 'U', 'R', 'C')
+          AND NOT (c.CONSTRAINT_TYPE = 'C' AND c.GENERATED = 'GENERATED NAME')
+        ORDER BY DECODE(c.CONSTRAINT_TYPE, 'P', 1, 'U', 2, 'R', 3, 4), c.CONSTRAINT_NAME,
+                 cc.POSITION
+    "#;
+
+    let mut constraints_of = Vec::with_capacity(objects.len());
+    for object in objects {
+        let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &object.schema);
+        ensure_schema_is_in_scope(&schema, session)?;
+        let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &object.object_name);
+        let rows = session
+            .connection
+            .query(sql, &[&schema, &object_name])
+            .map_err(map_oracle_error)?;
+        let mut constraints: Vec<DbObjectConstraint> = Vec::new();
+        for row_result in rows {
+            let row = row_result.map_err(map_oracle_error)?;
+            let name = row.get::<usize, String>(0).map_err(map_oracle_error)?;
+            let column = row
+                .get::<usize, Option<String>>(5)
+                .map_err(map_oracle_error)?;
+            if let Some(constraint) = constraints.last_mut().filter(|last| last.name == name) {
+                constraint.columns.extend(column);
+                continue;
+            }
+            let constraint_type = match row
+                .get::<usize, String>(1)
+                .map_err(map_oracle_error)?
+                .as_str()
+            {
+                "P" => DbConstraintType::PrimaryKey,
+                "U" => DbConstraintType::Unique,
+                "R" => DbConstraintType::ForeignKey,
+                _ => DbConstraintType::Check,
+            };
+            let references = match (
+                row.get::<usize, Option<String>>(3)
+                    .map_err(map_oracle_error)?,
+                row.get::<usize, Option<String>>(4)
+                    .map_err(map_oracle_error)?,
+            ) {
+                (Some(owner), Some(table)) => Some(format!("{owner}.{table}")),
+                _ => None,
+            };
+            constraints.push(DbObjectConstraint {
+                name,
+                constraint_type,
+                columns: column.into_iter().collect(),
+                references,
+                enabled: row
+                    .get::<usize, Option<String>>(2)
+                    .map_err(map_oracle_error)?
+                    .as_deref()
+                    == Some("ENABLED"),
+            });
+        }
+        constraints_of.push(constraints);
+    }
+
+    Ok(constraints_of)
+}
+
+/// Optimizer statistics of each object that is a table, in the order of `objects`.
+pub(crate) fn table_stats_of(
+    session: &OracleSession,
+    objects: &[DbObjectName],
+) -> Result<Vec<Option<DbObjectStats>>, String> {
+    let sql = r#"
+        SELECT NUM_ROWS, BLOCKS, AVG_ROW_LEN,
+               TO_CHAR(LAST_ANALYZED, 'YYYY-MM-DD"T"HH24:MI:SS'), STALE_STATS
+        FROM ALL_TAB_STATISTICS
+        WHERE OWNER = :1
+          AND TABLE_NAME = :2
+          AND OBJECT_TYPE = 'TABLE'
+    "#;
+
+    let mut stats_of = Vec::with_capacity(objects.len());
+    for object in objects {
+        let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &object.schema);
+        ensure_schema_is_in_scope(&schema, session)?;
+        let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &object.object_name);
+        let mut rows = session
+            .connection
+            .query(sql, &[&schema, &object_name])
+            .map_err(map_oracle_error)?;
+        let stats = match rows.next() {
+            Some(row_result) => {
+                let row = row_result.map_err(map_oracle_error)?;
+                Some(DbObjectStats {
+                    num_rows: row.get(0).map_err(map_oracle_error)?,
+                    blocks: row.get(1).map_err(map_oracle_error)?,
+                    avg_row_len: row.get(2).map_err(map_oracle_error)?,
+                    last_analyzed: row.get(3).map_err(map_oracle_error)?,
+                    stale: row
+                        .get::<usize, Option<String>>(4)
+                        .map_err(map_oracle_error)?
+                        .as_deref()
+                        == Some("YES"),
+                })
+            }
+            None => None,
+        };
+        stats_of.push(stats);
+    }
+
+    Ok(stats_of)
+}
+
+/// Object privileges granted on each object that the session can see, in the order of
+/// `objects`.
+pub(crate) fn list_grants_of(
+    session: &OracleSession,
+    objects: &[DbObjectName],
+) -> Result<Vec<Vec<DbObjectGrant>>, String> {
+    let sql = r#"
+        SELECT GRANTEE, PRIVILEGE, GRANTABLE
+        FROM ALL_TAB_PRIVS
+        WHERE TABLE_SCHEMA = :1
+          AND TABLE_NAME = :2
+        ORDER BY GRANTEE, PRIVILEGE
+    "#;
+
+    let mut grants_of = Vec::with_capacity(objects.len());
+    for object in objects {
+        let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &object.schema);
+        ensure_schema_is_in_scope(&schema, session)?;
+        let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &object.object_name);
+        let rows = session
+            .connection
+            .query(sql, &[&schema, &object_name])
+            .map_err(map_oracle_error)?;
+        let mut grants = Vec::new();
+        for row_result in rows {
+            let row = row_result.map_err(map_oracle_error)?;
+            grants.push(DbObjectGrant {
+                grantee: row.get(0).map_err(map_oracle_error)?,
+                privilege: row.get(1).map_err(map_oracle_error)?,
+                grantable: row.get::<usize, String>(2).map_err(map_oracle_error)? == "YES",
+            });
+        }
+        grants_of.push(grants);
+    }
+
+    Ok(grants_of)
+}
+
+/// DDL from `ALL_SOURCE` or `DBMS_METADATA`. When the account may not use `DBMS_METADATA`,
+/// views and tables are rebuilt from the data dictionary and flagged as reconstructed.
 pub(crate) fn get_object_ddl(
     session: &OracleSession,
     request: &DbObjectRef,
-) -> Result<String, String> {
-    let schema = normalize_schema_name(&request.schema)?;
+) -> Result<DbObjectDdl, String> {
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
     ensure_schema_is_in_scope(&schema, session)?;
-    let object_name = request.object_name.trim().to_ascii_uppercase();
+    let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.object_name);
     let source_type = normalize_source_type(&request.object_type);
     let metadata_type = normalize_metadata_type(&request.object_type);
 
@@ -290,19 +941,128 @@ pub(crate) fn get_object_ddl(
     )
     .map_err(map_oracle_error)?
     {
-        return Ok(source_ddl);
+        return Ok(DbObjectDdl::generated(source_ddl));
     }
 
     let ddl_sql = "SELECT DBMS_METADATA.GET_DDL(:1, :2, :3) FROM DUAL";
-    session
+    match session
         .connection
         .query_row_as::<String>(ddl_sql, &[&metadata_type, &object_name, &schema])
-        .map_err(map_oracle_error)
+    {
+        Ok(ddl) => Ok(DbObjectDdl::generated(ddl)),
+        Err(error) => match fetch_dictionary_ddl(
+            &session.connection,
+            schema.as_str(),
+            metadata_type.as_str(),
+            object_name.as_str(),
+        ) {
+            Ok(Some(ddl)) => Ok(DbObjectDdl {
+                ddl,
+                reconstructed: true,
+            }),
+            _ => Err(map_oracle_error(error)),
+        },
+    }
+}
+
+/// Reports which edition the session uses and which edition the visible actual of an
+/// object belongs to. An object edition that differs from the session edition means the
+/// object is inherited from an ancestor edition.
+pub(crate) fn get_object_edition(
+    session: &OracleSession,
+    request: &DbObjectRef,
+) -> Result<DbObjectEditionInfo, String> {
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
+    ensure_schema_is_in_scope(&schema, session)?;
+    let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.object_name);
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+
+    let session_edition = session
+        .connection
+        .query_row_as::<String>(
+            "SELECT SYS_CONTEXT('USERENV', 'CURRENT_EDITION_NAME') FROM DUAL",
+            &[],
+        )
+        .map_err(map_oracle_error)?;
+    let object_edition = match session.connection.query_row_as::<Option<String>>(
+        "SELECT EDITION_NAME FROM ALL_OBJECTS WHERE OWNER = :1 AND OBJECT_NAME = :2 AND OBJECT_TYPE = :3",
+        &[&schema, &object_name, &object_type],
+    ) {
+        Ok(edition) => edition,
+        Err(error) if error.kind() == oracle::ErrorKind::NoDataFound => {
+            return Err(format!(
+                "{} {}.{} was not found",
+                object_type, schema, object_name
+            ))
+        }
+        Err(error) => return Err(map_oracle_error(error)),
+    };
+    let inherited = object_edition
+        .as_deref()
+        .is_some_and(|edition| edition != session_edition);
+
+    Ok(DbObjectEditionInfo {
+        session_edition,
+        object_edition,
+        inherited,
+    })
+}
+
+/// Objects that reference the given one (views, code, synonyms), up to the explorer limit.
+pub(crate) fn list_object_dependents(
+    session: &OracleSession,
+    request: &DbObjectRef,
+) -> Result<Vec<DbObjectDependent>, String> {
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
+    ensure_schema_is_in_scope(&schema, session)?;
+    let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.object_name);
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+    let sql = r#"
+        SELECT OWNER, TYPE, NAME
+        FROM (
+            SELECT DISTINCT OWNER, TYPE, NAME
+            FROM ALL_DEPENDENCIES
+            WHERE REFERENCED_OWNER = :1
+              AND REFERENCED_NAME = :2
+              AND REFERENCED_TYPE = :3
+              AND NOT (OWNER = :4 AND NAME = :5)
+            ORDER BY OWNER, TYPE, NAME
+        )
+        WHERE ROWNUM <= :6
+    "#;
+
+    let rows = session
+        .connection
+        .query(
+            sql,
+            &[
+                &schema,
+                &object_name,
+                &object_type,
+                &schema,
+                &object_name,
+                &MAX_EXPLORER_OBJECTS,
+            ],
+        )
+        .map_err(map_oracle_error)?;
+
+    let mut dependents = Vec::new();
+    for row_result in rows {
+        let row = row_result.map_err(map_oracle_error)?;
+        dependents.push(DbObjectDependent {
+            schema: row.get::<usize, String>(0).map_err(map_oracle_error)?,
+            object_type: row.get::<usize, String>(1).map_err(map_oracle_error)?,
+            object_name: row.get::<usize, String>(2).map_err(map_oracle_error)?,
+        });
+    }
+
+    Ok(dependents)
 }
 
 pub(crate) fn search_schema_text(
     session: &OracleSession,
     request: &DbSchemaSearchRequest,
+    cancel: &CancelToken,
 ) -> Result<Vec<DbSchemaSearchResult>, String> {
     let search_term = request.search_term.trim();
     if search_term.is_empty() {
@@ -332,7 +1092,7 @@ pub(crate) fn search_schema_text(
     }
 
     if include_ddl {
-        search_ddl_text(session, search_term.as_str(), limit, &mut matches)?;
+        search_ddl_text(session, search_term.as_str(), limit, &mut matches, cancel)?;
     }
 
     Ok(matches)
@@ -379,6 +1139,7 @@ fn search_object_names(
             match_scope: "object_name".to_string(),
             line: None,
             snippet: truncate_for_snippet(object_name.as_str()),
+            tags: Vec::new(),
         });
     }
 
@@ -440,17 +1201,20 @@ fn search_source_text(
             match_scope: "source".to_string(),
             line: Some(line),
             snippet: truncate_for_snippet(text.as_str()),
+            tags: Vec::new(),
         });
     }
 
     Ok(())
 }
 
+/// Reads DDL one object at a time, checking `cancel` in between.
 fn search_ddl_text(
     session: &OracleSession,
     search_term: &str,
     limit: u32,
     matches: &mut Vec<DbSchemaSearchResult>,
+    cancel: &CancelToken,
 ) -> Result<(), String> {
     let remaining = (limit as usize).saturating_sub(matches.len());
     if remaining == 0 {
@@ -491,6 +1255,7 @@ fn search_ddl_text(
         if matches.len() >= limit as usize {
             break;
         }
+        cancel.check()?;
 
         let row = row_result.map_err(map_oracle_error)?;
         let schema = row.get::<usize, String>(0).map_err(map_oracle_error)?;
@@ -517,6 +1282,7 @@ fn search_ddl_text(
                 match_scope: "ddl".to_string(),
                 line: Some(line),
                 snippet: truncate_for_snippet(snippet.as_str()),
+                tags: Vec::new(),
             });
         }
     }
@@ -535,9 +1301,9 @@ pub(crate) fn update_object_ddl(
     }
 
     ddl = normalize_ddl_for_execute(ddl, object_type.as_str());
-    let schema = normalize_schema_name(&request.schema)?;
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
     ensure_schema_is_in_scope(&schema, session)?;
-    let object_name = request.object_name.trim().to_ascii_uppercase();
+    let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.object_name);
 
     let mut compile_error_reported_by_oracle = false;
     if let Err(error) = session.connection.execute(ddl.as_str(), &[]) {
@@ -574,8 +1340,14 @@ pub(crate) fn update_object_ddl(
         return Ok(DbQueryResult {
             columns: Vec::new(),
             rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
             rows_affected: None,
             message,
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
         });
     }
 
@@ -606,76 +1378,424 @@ pub(crate) fn update_object_ddl(
     Ok(DbQueryResult {
         columns: diagnostics.columns,
         rows: diagnostics.rows,
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message,
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
+/// Runs a statement, reconnecting once if the connection has dropped. Only a read-only
+/// query outside a transaction is re-run; anything else may already have taken effect.
 pub(crate) fn run_query(
     session: &mut OracleSession,
     request: &DbQueryRequest,
 ) -> Result<DbQueryResult, String> {
-    let sql = request.sql.trim();
-    if sql.is_empty() {
-        return Err("Query cannot be empty".to_string());
+    let error = match execute_query(session, request) {
+        Err(error) if is_connection_lost_error(&error) => error,
+        result => return result,
+    };
+    let transaction_lost = session.transaction_active;
+    if let Err(reconnect_error) = reconnect(session) {
+        session.reconnect_notice = Some(DbSessionReconnected {
+            session_id: request.session_id,
+            reconnected: false,
+            replayed: false,
+            needs_password: is_rejected_credentials_error(&reconnect_error),
+            message: format!("The connection was lost and reconnecting failed: {reconnect_error}"),
+        });
+        return Err(format!("{error}. Reconnecting failed: {reconnect_error}"));
     }
 
-    if let Some(show_result) = try_run_show_command(session, request) {
-        return show_result;
+    let replay = !transaction_lost && is_read_only_query(&request.sql);
+    let outcome = if replay {
+        "the query was replayed."
+    } else if transaction_lost {
+        "uncommitted changes were rolled back by the server and the statement was not re-run."
+    } else {
+        "the statement was not re-run because it may already have taken effect."
+    };
+    let message = format!("The connection was lost and re-established; {outcome}");
+    session.reconnect_notice = Some(DbSessionReconnected {
+        session_id: request.session_id,
+        reconnected: true,
+        replayed: replay,
+        needs_password: false,
+        message: message.clone(),
+    });
+    if !replay {
+        return Err(format!("{error}. {message}"));
     }
 
-    let mut statement = session
-        .connection
-        .statement(sql)
-        .build()
-        .map_err(map_oracle_error)?;
-    let transaction_control = detect_transaction_control(sql);
-
-    if statement.is_query() {
-        let row_limit = request
-            .row_limit
-            .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
-            .clamp(1, MAX_QUERY_ROW_LIMIT) as usize;
-        let result_set = statement.query(&[]).map_err(map_oracle_error)?;
-        let columns = result_set
-            .column_info()
-            .iter()
-            .map(|column| column.name().to_string())
-            .collect::<Vec<_>>();
-
-        let mut rows = Vec::new();
-        let mut truncated = false;
-
-        for (index, row_result) in result_set.enumerate() {
-            if index >= row_limit {
-                truncated = true;
-                break;
-            }
+    let mut result = execute_query(session, request)?;
+    result.message.push(' ');
+    result.message.push_str(&message);
+    Ok(result)
+}
 
-            let row = row_result.map_err(map_oracle_error)?;
-            let values = row
-                .sql_values()
-                .iter()
-                .map(sql_value_to_string)
-                .collect::<Vec<_>>();
-            rows.push(values);
+/// Returns the next `count` rows of the query that opened `cursor_id`. The cursor closes
+/// once its last row is sent.
+pub(crate) fn fetch_more_rows(
+    session: &mut OracleSession,
+    cursor_id: u64,
+    count: Option<u32>,
+) -> Result<DbQueryResult, String> {
+    let count = count
+        .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
+        .clamp(1, MAX_QUERY_ROW_LIMIT) as usize;
+    let cursor = session
+        .open_cursor
+        .as_mut()
+        .filter(|cursor| cursor.id == cursor_id)
+        .ok_or_else(|| {
+            "The result was closed or replaced by a later query; run the query again".to_string()
+        })?;
+    let fetched = match cursor.fetch(count) {
+        Ok(fetched) => fetched,
+        Err(error) => {
+            session.open_cursor = None;
+            return Err(error);
         }
+    };
+    let mut message = format!(
+        "Fetched {} more row(s), {} in all.",
+        fetched.rows.len(),
+        cursor.fetched
+    );
+    let columns = cursor.columns.clone();
+    let cursor_id = if fetched.more {
+        Some(cursor.id)
+    } else {
+        session.open_cursor = None;
+        message.push_str(" No more rows.");
+        None
+    };
+    let lossy_conversion = note_lossy_conversion(&fetched.rows, &mut message);
+    Ok(DbQueryResult {
+        columns,
+        rows: fetched.rows,
+        null_flags: null_flags_if_any(fetched.null_flags),
+        byte_lengths: byte_lengths_if_any(fetched.byte_lengths),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
 
-        let mut message = format!("Query executed. Returned {} row(s).", rows.len());
-        if truncated {
-            message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+/// Closes the cursor if it is still open. Returns false if it had already closed.
+pub(crate) fn close_cursor(session: &mut OracleSession, cursor_id: u64) -> bool {
+    match &session.open_cursor {
+        Some(cursor) if cursor.id == cursor_id => {
+            session.open_cursor = None;
+            true
         }
-
-        return Ok(DbQueryResult {
-            columns,
-            rows,
-            rows_affected: None,
-            message,
-        });
+        _ => false,
+    }
+}
+
+/// Reconnects on request, for example after the user supplied a new password.
+pub(crate) fn reconnect_session(
+    session: &mut OracleSession,
+    password: Option<String>,
+) -> Result<(), String> {
+    if let Some(password) = password {
+        session.connect_options.password = password;
+    }
+    reconnect(session)
+}
+
+pub(crate) fn take_reconnect_notice(session: &mut OracleSession) -> Option<DbSessionReconnected> {
+    session.reconnect_notice.take()
+}
+
+/// Lines read per statement at most; the rest stay in the buffer.
+const MAX_SERVER_OUTPUT_LINES: usize = 10_000;
+
+/// `SET SERVEROUTPUT ON|OFF`. An unlimited buffer, as SQL*Plus uses by default.
+pub(crate) fn set_server_output(session: &OracleSession, enabled: bool) -> Result<(), String> {
+    let sql = if enabled {
+        "BEGIN DBMS_OUTPUT.ENABLE(NULL); END;"
+    } else {
+        "BEGIN DBMS_OUTPUT.DISABLE; END;"
+    };
+    session
+        .connection
+        .execute(sql, &[])
+        .map(|_| ())
+        .map_err(map_oracle_error)
+}
+
+/// Drains the lines `DBMS_OUTPUT` has buffered since the last call.
+pub(crate) fn read_server_output(session: &OracleSession) -> Result<Vec<String>, String> {
+    let mut statement = session
+        .connection
+        .statement("BEGIN DBMS_OUTPUT.GET_LINE(:1, :2); END;")
+        .build()
+        .map_err(map_oracle_error)?;
+    let mut lines = Vec::new();
+    while lines.len() < MAX_SERVER_OUTPUT_LINES {
+        statement
+            .execute(&[&OracleType::Varchar2(32767), &OracleType::Number(0, 0)])
+            .map_err(map_oracle_error)?;
+        let status: i32 = statement.bind_value(2).map_err(map_oracle_error)?;
+        if status != 0 {
+            break;
+        }
+        let line: Option<String> = statement.bind_value(1).map_err(map_oracle_error)?;
+        lines.push(line.unwrap_or_default());
+    }
+    Ok(lines)
+}
+
+const PLAN_STATEMENT_ID: &str = "CLARITY_PLAN";
+
+/// Execution plan of a statement as `DBMS_XPLAN.DISPLAY` prints it. `EXPLAIN PLAN` writes
+/// to `PLAN_TABLE`; those rows are rolled back, or deleted when a transaction is open.
+pub(crate) fn explain_plan(session: &mut OracleSession, sql: &str) -> Result<Vec<String>, String> {
+    let sql = sql.trim().trim_end_matches(';').trim_end();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+    session
+        .connection
+        .execute(
+            &format!("EXPLAIN PLAN SET STATEMENT_ID = '{PLAN_STATEMENT_ID}' FOR {sql}"),
+            &[],
+        )
+        .map_err(map_oracle_error)?;
+
+    let lines = read_plan_lines(&session.connection);
+    let cleanup = if session.transaction_active {
+        session
+            .connection
+            .execute(
+                "DELETE FROM PLAN_TABLE WHERE STATEMENT_ID = :1",
+                &[&PLAN_STATEMENT_ID],
+            )
+            .map(|_| ())
+    } else {
+        session.connection.rollback()
+    };
+    let lines = lines.map_err(map_oracle_error)?;
+    cleanup.map_err(map_oracle_error)?;
+    Ok(lines)
+}
+
+fn read_plan_lines(connection: &Connection) -> Result<Vec<String>, OracleError> {
+    let rows = connection.query(
+        "SELECT PLAN_TABLE_OUTPUT FROM TABLE(DBMS_XPLAN.DISPLAY(NULL, :1, 'TYPICAL'))",
+        &[&PLAN_STATEMENT_ID],
+    )?;
+    let mut lines = Vec::new();
+    for row_result in rows {
+        lines.push(
+            row_result?
+                .get::<usize, Option<String>>(0)?
+                .unwrap_or_default(),
+        );
+    }
+    Ok(lines)
+}
+
+/// Runs every query inside one read-only transaction so they all see the same snapshot.
+pub(crate) fn run_consistent_queries(
+    session: &mut OracleSession,
+    request: &DbConsistentQueriesRequest,
+) -> Result<DbConsistentQueriesResult, String> {
+    if session.transaction_active {
+        return Err(
+            "Commit or roll back the open transaction before running a consistent read."
+                .to_string(),
+        );
+    }
+    if request.queries.iter().all(|sql| sql.trim().is_empty()) {
+        return Err("At least one query is required".to_string());
+    }
+    if let Some(sql) = request
+        .queries
+        .iter()
+        .find(|sql| !sql.trim().is_empty() && !is_read_only_query(sql))
+    {
+        return Err(format!(
+            "Only SELECT queries can run in a consistent read: {}",
+            sql.trim()
+        ));
+    }
+
+    session
+        .connection
+        .execute("SET TRANSACTION READ ONLY", &[])
+        .map_err(map_oracle_error)?;
+    let outcome = read_consistent_queries(session, request);
+    // A read-only transaction holds no changes; rolling back just releases the snapshot.
+    let released = session.connection.rollback().map_err(map_oracle_error);
+    let result = outcome?;
+    released?;
+    Ok(result)
+}
+
+fn read_consistent_queries(
+    session: &mut OracleSession,
+    request: &DbConsistentQueriesRequest,
+) -> Result<DbConsistentQueriesResult, String> {
+    let scn = current_scn(session);
+    let mut results = Vec::with_capacity(request.queries.len());
+    for sql in request.queries.iter().filter(|sql| !sql.trim().is_empty()) {
+        let query = DbQueryRequest {
+            session_id: request.session_id,
+            sql: sql.clone(),
+            row_limit: request.row_limit,
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        };
+        let mut result = execute_query(session, &query)?;
+        match scn.as_deref() {
+            Some(scn) => result
+                .message
+                .push_str(&format!(" Read consistent at SCN {scn}.")),
+            None => result
+                .message
+                .push_str(" Read consistent with the other queries."),
+        }
+        results.push(result);
+    }
+    Ok(DbConsistentQueriesResult { scn, results })
+}
+
+/// Tries `DBMS_FLASHBACK` first and `V$DATABASE` second; both need grants many users lack.
+fn current_scn(session: &OracleSession) -> Option<String> {
+    [
+        "SELECT TO_CHAR(DBMS_FLASHBACK.GET_SYSTEM_CHANGE_NUMBER) FROM DUAL",
+        "SELECT TO_CHAR(CURRENT_SCN) FROM V$DATABASE",
+    ]
+    .iter()
+    .find_map(|sql| {
+        session
+            .connection
+            .query_row_as::<Option<String>>(sql, &[])
+            .ok()
+            .flatten()
+    })
+}
+
+/// Runs a query with a call timeout on every round-trip, restoring the previous timeout
+/// afterwards.
+pub(crate) fn run_query_with_call_timeout(
+    session: &mut OracleSession,
+    request: &DbQueryRequest,
+    timeout: Duration,
+) -> Result<DbQueryResult, String> {
+    let previous = session
+        .connection
+        .call_timeout()
+        .map_err(map_oracle_error)?;
+    session
+        .connection
+        .set_call_timeout(Some(timeout))
+        .map_err(map_oracle_error)?;
+    let result = execute_query(session, request);
+    let _ = session.connection.set_call_timeout(previous);
+    result.map_err(|error| {
+        if error.contains("DPI-1067") {
+            format!(
+                "The query did not finish within {} seconds.",
+                timeout.as_secs()
+            )
+        } else {
+            error
+        }
+    })
+}
+
+fn execute_query(
+    session: &mut OracleSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    if let Some(show_result) = try_run_show_command(session, request) {
+        return show_result;
+    }
+
+    let mut statement = session
+        .connection
+        .statement(sql)
+        .build()
+        .map_err(map_oracle_error)?;
+    bind_named_values(&mut statement, &request.binds)?;
+    let transaction_control = detect_transaction_control(sql);
+
+    if statement.is_query() {
+        let row_limit = request
+            .row_limit
+            .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
+            .clamp(1, MAX_QUERY_ROW_LIMIT) as usize;
+        let timestamp_tz_target = resolve_timestamp_tz_target(session)?;
+        let preview_bytes = request
+            .binary_preview_bytes
+            .unwrap_or(DEFAULT_BINARY_PREVIEW_BYTES)
+            .clamp(1, MAX_BINARY_PREVIEW_BYTES) as usize;
+        let result_set = statement
+            .into_result_set::<Row>(&[])
+            .map_err(map_oracle_error)?;
+        let columns = result_set
+            .column_info()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        let mut cursor = OpenCursor {
+            id: 0,
+            columns: columns.clone(),
+            rows: result_set,
+            pending: None,
+            fetched: 0,
+            timestamp_tz_target,
+            preview_bytes,
+        };
+        let fetched = cursor.fetch(row_limit)?;
+
+        let mut message = format!("Query executed. Returned {} row(s).", fetched.rows.len());
+        let mut cursor_id = None;
+        if fetched.more {
+            message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+            cursor.id = session.next_cursor_id;
+            session.next_cursor_id += 1;
+            cursor_id = Some(cursor.id);
+            session.open_cursor = Some(cursor);
+        }
+        let lossy_conversion = note_lossy_conversion(&fetched.rows, &mut message);
+
+        return Ok(DbQueryResult {
+            columns,
+            rows: fetched.rows,
+            null_flags: null_flags_if_any(fetched.null_flags),
+            byte_lengths: byte_lengths_if_any(fetched.byte_lengths),
+            rows_affected: None,
+            message,
+            lossy_conversion,
+            cursor_id,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
     }
 
     statement.execute(&[]).map_err(map_oracle_error)?;
     let rows_affected = statement.row_count().map_err(map_oracle_error)?;
+    // Read before committing, which would close cursors over rows locked FOR UPDATE.
+    let ref_cursors = read_ref_cursors(session, &statement, request)?;
 
     if statement.is_dml() || statement.is_plsql() {
         if !session.transaction_active {
@@ -688,7 +1808,7 @@ pub(crate) fn run_query(
         apply_transaction_control(session, transaction_control);
     }
 
-    let message = if statement.is_dml() {
+    let mut message = if statement.is_dml() {
         format!("Statement executed. {} row(s) affected.", rows_affected)
     } else if statement.is_ddl() {
         "DDL executed.".to_string()
@@ -697,15 +1817,254 @@ pub(crate) fn run_query(
     } else {
         "Statement executed.".to_string()
     };
+    message.push_str(&describe_ref_cursors(&ref_cursors));
+    let lossy_conversion = ref_cursors
+        .iter()
+        .any(|cursor| note_lossy_conversion(&cursor.rows, &mut message));
 
     Ok(DbQueryResult {
         columns: Vec::new(),
         rows: Vec::new(),
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: Some(rows_affected),
         message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors,
+    })
+}
+
+/// Binds every placeholder in the statement by name (case-insensitively); values for names
+/// the statement does not use are ignored, so one parameter set can serve related queries.
+/// A statement with placeholders but no values fails listing every missing name.
+fn bind_named_values(statement: &mut Statement, binds: &[DbQueryBind]) -> Result<(), String> {
+    let names = statement
+        .bind_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let mut missing = Vec::new();
+    for name in names {
+        match find_bind(binds, &name) {
+            Some(bind) => bind_value(statement, &name, bind)?,
+            None => missing.push(format!(":{name}")),
+        }
+    }
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "No value given for bind variable(s) {}",
+            missing.join(", ")
+        ))
+    }
+}
+
+fn find_bind<'a>(binds: &'a [DbQueryBind], name: &str) -> Option<&'a DbQueryBind> {
+    binds.iter().find(|bind| {
+        bind.name
+            .trim()
+            .trim_start_matches(':')
+            .eq_ignore_ascii_case(name)
+    })
+}
+
+/// Reads the cursors a PL/SQL call returned through REF CURSOR binds, then the implicit
+/// results it returned with `DBMS_SQL.RETURN_RESULT`, each up to the row limit. A cursor
+/// the call left unopened is skipped.
+fn read_ref_cursors(
+    session: &OracleSession,
+    statement: &Statement,
+    request: &DbQueryRequest,
+) -> Result<Vec<DbRefCursorResult>, String> {
+    let names = statement
+        .bind_names()
+        .into_iter()
+        .filter(|name| {
+            find_bind(&request.binds, name)
+                .is_some_and(|bind| bind.value_type == DbBindType::RefCursor)
+        })
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if names.is_empty() && !statement.is_plsql() {
+        return Ok(Vec::new());
+    }
+
+    let row_limit = request
+        .row_limit
+        .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
+        .clamp(1, MAX_QUERY_ROW_LIMIT) as usize;
+    let timestamp_tz_target = resolve_timestamp_tz_target(session)?;
+    let preview_bytes = request
+        .binary_preview_bytes
+        .unwrap_or(DEFAULT_BINARY_PREVIEW_BYTES)
+        .clamp(1, MAX_BINARY_PREVIEW_BYTES) as usize;
+    let read = |cursor: &mut RefCursor, name: String, implicit: bool| {
+        read_cursor(
+            cursor,
+            name,
+            implicit,
+            row_limit,
+            &timestamp_tz_target,
+            preview_bytes,
+        )
+    };
+    let mut results = Vec::new();
+    for name in names {
+        let Some(mut cursor) = statement
+            .bind_value::<&str, Option<RefCursor>>(name.as_str())
+            .map_err(map_oracle_error)?
+        else {
+            continue;
+        };
+        results.push(read(&mut cursor, name, false)?);
+    }
+    if statement.is_plsql() {
+        let mut position = 1;
+        while let Some(mut cursor) = statement.implicit_result().map_err(map_oracle_error)? {
+            results.push(read(&mut cursor, position.to_string(), true)?);
+            position += 1;
+        }
+    }
+    Ok(results)
+}
+
+fn read_cursor(
+    cursor: &mut RefCursor,
+    name: String,
+    implicit: bool,
+    row_limit: usize,
+    timestamp_tz_target: &TimestampTzTarget,
+    preview_bytes: usize,
+) -> Result<DbRefCursorResult, String> {
+    let result_set = cursor.query().map_err(map_oracle_error)?;
+    let columns = result_set
+        .column_info()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect::<Vec<_>>();
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut byte_lengths = Vec::new();
+    let mut truncated = false;
+    for row in result_set {
+        if rows.len() == row_limit {
+            truncated = true;
+            break;
+        }
+        let row = row.map_err(map_oracle_error)?;
+        let (values, lengths) = display_row(row.sql_values(), timestamp_tz_target, preview_bytes);
+        rows.push(values);
+        null_flags.push(sql_value_null_flags(row.sql_values()));
+        byte_lengths.push(lengths);
+    }
+    Ok(DbRefCursorResult {
+        name,
+        implicit,
+        columns,
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: byte_lengths_if_any(byte_lengths),
+        truncated,
     })
 }
 
+/// Message suffix listing the cursors a call returned, e.g. ` Cursor :rc returned 3 row(s).`
+fn describe_ref_cursors(ref_cursors: &[DbRefCursorResult]) -> String {
+    ref_cursors
+        .iter()
+        .map(|cursor| {
+            format!(
+                " {} returned {}{} row(s).",
+                if cursor.implicit {
+                    format!("Implicit result {}", cursor.name)
+                } else {
+                    format!("Cursor :{}", cursor.name)
+                },
+                if cursor.truncated { "the first " } else { "" },
+                cursor.rows.len()
+            )
+        })
+        .collect()
+}
+
+fn bind_value(statement: &mut Statement, name: &str, bind: &DbQueryBind) -> Result<(), String> {
+    // Oracle reads an empty string as NULL; typed values follow suit.
+    let value = bind
+        .value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let result = match bind.value_type {
+        DbBindType::Text => statement.bind(name, &bind.value),
+        DbBindType::Number => {
+            if let Some(value) = value {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| format!("Bind variable :{name} is not a number: {value}"))?;
+            }
+            let number = value.map(str::to_string);
+            statement.bind(name, &(&number, &OracleType::Number(0, -127)))
+        }
+        DbBindType::Date | DbBindType::Timestamp => {
+            let timestamp = value
+                .map(parse_bind_timestamp)
+                .transpose()
+                .map_err(|error| format!("Bind variable :{name}: {error}"))?;
+            let oracle_type = match bind.value_type {
+                DbBindType::Date => OracleType::Date,
+                _ => OracleType::Timestamp(9),
+            };
+            statement.bind(name, &(&timestamp, &oracle_type))
+        }
+        DbBindType::RefCursor => statement.bind(name, &OracleType::RefCursor),
+    };
+    result.map_err(map_oracle_error)
+}
+
+/// Reads `YYYY-MM-DD`, optionally followed by a space or `T` and `HH:MI[:SS[.FF]]`.
+fn parse_bind_timestamp(value: &str) -> Result<Timestamp, String> {
+    let invalid = || format!("'{value}' is not a date; use YYYY-MM-DD HH:MI:SS");
+    let (date, time) = match value.split_once([' ', 'T']) {
+        Some((date, time)) => (date, time.trim()),
+        None => (value, ""),
+    };
+    let mut date_parts = date.split('-').map(|part| part.parse::<u32>().ok());
+    let (Some(Some(year)), Some(Some(month)), Some(Some(day)), None) = (
+        date_parts.next(),
+        date_parts.next(),
+        date_parts.next(),
+        date_parts.next(),
+    ) else {
+        return Err(invalid());
+    };
+
+    let (clock, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut clock_parts = clock
+        .split(':')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<u32>().map_err(|_| invalid()));
+    let hour = clock_parts.next().transpose()?.unwrap_or(0);
+    let minute = clock_parts.next().transpose()?.unwrap_or(0);
+    let second = clock_parts.next().transpose()?.unwrap_or(0);
+    if clock_parts.next().is_some()
+        || fraction.len() > 9
+        || !fraction.chars().all(|ch| ch.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let nanosecond = format!("{fraction:0<9}")
+        .parse::<u32>()
+        .map_err(|_| invalid())?;
+    if month == 0 || month > 12 || day == 0 || day > 31 || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    Timestamp::new(year as i32, month, day, hour, minute, second, nanosecond)
+        .map_err(|error| error.to_string())
+}
+
 pub(crate) fn run_filtered_query(
     session: &mut OracleSession,
     request: &DbFilteredQueryRequest,
@@ -719,6 +2078,11 @@ pub(crate) fn run_filtered_query(
         session_id: request.session_id,
         sql: request.sql.clone(),
         row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
     };
     let row_limit = effective_query_row_limit(&query_request);
 
@@ -769,6 +2133,7 @@ pub(crate) fn run_filtered_query(
         return Err("Filtering is only available for query result sets.".to_string());
     }
 
+    let timestamp_tz_target = resolve_timestamp_tz_target(session)?;
     let result_set = statement.query(&[]).map_err(map_oracle_error)?;
     let columns = result_set
         .column_info()
@@ -777,15 +2142,17 @@ pub(crate) fn run_filtered_query(
         .collect::<Vec<_>>();
 
     let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut byte_lengths = Vec::new();
     let mut truncated = false;
 
     for row_result in result_set {
         let row = row_result.map_err(map_oracle_error)?;
-        let values = row
-            .sql_values()
-            .iter()
-            .map(sql_value_to_string)
-            .collect::<Vec<_>>();
+        let (values, lengths) = display_row(
+            row.sql_values(),
+            &timestamp_tz_target,
+            DEFAULT_BINARY_PREVIEW_BYTES as usize,
+        );
         if !row_matches_query_filters(
             values.as_slice(),
             normalized_global_search.as_str(),
@@ -795,6 +2162,8 @@ pub(crate) fn run_filtered_query(
         }
 
         rows.push(values);
+        null_flags.push(sql_value_null_flags(row.sql_values()));
+        byte_lengths.push(lengths);
         if rows.len() >= row_limit {
             truncated = true;
             break;
@@ -805,48 +2174,1403 @@ pub(crate) fn run_filtered_query(
     if truncated {
         message.push_str(&format!(" Results truncated at {} rows.", row_limit));
     }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
 
     Ok(DbQueryResult {
         columns,
         rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: byte_lengths_if_any(byte_lengths),
         rows_affected: None,
         message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
-pub(crate) fn begin_transaction(session: &mut OracleSession) -> Result<bool, String> {
-    session.transaction_active = true;
-    Ok(session.transaction_active)
-}
+/// Saves the full content of one cell, such as a BLOB, CLOB or RAW value, to a file.
+pub(crate) fn download_lob(
+    session: &OracleSession,
+    request: &DbDownloadLobRequest,
+) -> Result<DbLobDownloadResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+    let file_path = request.file_path.trim();
+    if file_path.is_empty() {
+        return Err("Destination file is required".to_string());
+    }
 
-pub(crate) fn commit_transaction(session: &mut OracleSession) -> Result<bool, String> {
-    if session.transaction_active {
-        session.connection.commit().map_err(map_oracle_error)?;
+    let mut statement = session
+        .connection
+        .statement(sql)
+        .build()
+        .map_err(map_oracle_error)?;
+    if !statement.is_query() {
+        return Err("Only query results can be downloaded.".to_string());
+    }
+    let row = statement
+        .query(&[])
+        .map_err(map_oracle_error)?
+        .nth(request.row_index as usize)
+        .ok_or_else(|| "The query no longer returns that row; run it again and retry.".to_string())?
+        .map_err(map_oracle_error)?;
+    let value = row
+        .sql_values()
+        .get(request.column_index as usize)
+        .ok_or_else(|| format!("The result has no column {}", request.column_index + 1))?;
+    if value.is_null().unwrap_or(false) {
+        return Err("The value is NULL; there is nothing to save.".to_string());
     }
-    session.transaction_active = false;
-    Ok(session.transaction_active)
-}
 
-pub(crate) fn rollback_transaction(session: &mut OracleSession) -> Result<bool, String> {
-    if session.transaction_active {
-        session.connection.rollback().map_err(map_oracle_error)?;
+    let mut file = File::create(file_path)
+        .map_err(|error| format!("Failed to create '{file_path}': {error}"))?;
+    let byte_length = match value.oracle_type().map_err(map_oracle_error)? {
+        OracleType::BLOB => copy_lob(value.get::<Blob>(), &mut file),
+        OracleType::CLOB => copy_lob(value.get::<Clob>(), &mut file),
+        OracleType::NCLOB => copy_lob(value.get::<Nclob>(), &mut file),
+        OracleType::Raw(_) | OracleType::LongRaw => {
+            let bytes = value.get::<Vec<u8>>().map_err(map_oracle_error)?;
+            file.write_all(&bytes)
+                .map(|_| bytes.len() as u64)
+                .map_err(|error| error.to_string())
+        }
+        _ => {
+            let text = sql_value_to_string(value, &TimestampTzTarget::Original);
+            file.write_all(text.as_bytes())
+                .map(|_| text.len() as u64)
+                .map_err(|error| error.to_string())
+        }
     }
-    session.transaction_active = false;
-    Ok(session.transaction_active)
+    .map_err(|error| format!("Failed to write '{file_path}': {error}"))?;
+
+    Ok(DbLobDownloadResult {
+        file_path: file_path.to_string(),
+        byte_length,
+        message: format!("Saved {byte_length} byte(s) to {file_path}."),
+    })
 }
 
-pub(crate) fn transaction_active(session: &OracleSession) -> bool {
-    session.transaction_active
+fn copy_lob<L: Read>(lob: oracle::Result<L>, file: &mut File) -> Result<u64, String> {
+    let mut lob = lob.map_err(map_oracle_error)?;
+    io::copy(&mut lob, file).map_err(|error| error.to_string())
 }
 
-#[derive(Clone, Copy)]
-enum TransactionControl {
-    None,
-    Commit,
-    Rollback,
-    RollbackToSavepoint,
-    Savepoint,
-    SetTransaction,
+/// Streams every row of a query to `sink` without the interactive row limit.
+pub(crate) fn stream_query_rows(
+    session: &OracleSession,
+    sql: &str,
+    sink: &mut dyn ExportRowSink,
+) -> Result<u64, String> {
+    let mut statement = session
+        .connection
+        .statement(sql)
+        .fetch_array_size(EXPORT_FETCH_ARRAY_SIZE)
+        .build()
+        .map_err(map_oracle_error)?;
+    if !statement.is_query() {
+        return Err("Only queries can be exported".to_string());
+    }
+
+    let timestamp_tz_target = resolve_timestamp_tz_target(session)?;
+    let result_set = statement.query(&[]).map_err(map_oracle_error)?;
+    let columns = result_set
+        .column_info()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect::<Vec<_>>();
+    sink.columns(&columns)?;
+
+    let mut row_count = 0_u64;
+    for row_result in result_set {
+        let row = row_result.map_err(map_oracle_error)?;
+        let values = row
+            .sql_values()
+            .iter()
+            .map(|value| sql_value_to_export_value(value, &timestamp_tz_target))
+            .collect::<Vec<_>>();
+        sink.row(&values)?;
+        row_count += 1;
+    }
+
+    Ok(row_count)
+}
+
+pub(crate) fn list_aq_queues(session: &OracleSession) -> Result<Vec<DbAqQueueEntry>, String> {
+    let sql = r#"
+        SELECT Q.OWNER, Q.NAME, Q.QUEUE_TABLE, Q.QUEUE_TYPE,
+               NVL(T.OBJECT_TYPE, T.TYPE), TRIM(Q.ENQUEUE_ENABLED), TRIM(Q.DEQUEUE_ENABLED),
+               Q.MAX_RETRIES, Q.USER_COMMENT
+        FROM ALL_QUEUES Q
+        JOIN ALL_QUEUE_TABLES T
+          ON T.OWNER = Q.OWNER
+         AND T.QUEUE_TABLE = Q.QUEUE_TABLE
+        WHERE Q.OWNER = :1
+        ORDER BY Q.NAME
+    "#;
+
+    let rows = session
+        .connection
+        .query(sql, &[&session.target_schema])
+        .map_err(map_oracle_error)?;
+
+    let mut queues = Vec::new();
+    for row_result in rows {
+        let row = row_result.map_err(map_oracle_error)?;
+        let enqueue_enabled = row
+            .get::<usize, Option<String>>(5)
+            .map_err(map_oracle_error)?;
+        let dequeue_enabled = row
+            .get::<usize, Option<String>>(6)
+            .map_err(map_oracle_error)?;
+        queues.push(DbAqQueueEntry {
+            schema: row.get::<usize, String>(0).map_err(map_oracle_error)?,
+            queue_name: row.get::<usize, String>(1).map_err(map_oracle_error)?,
+            queue_table: row.get::<usize, String>(2).map_err(map_oracle_error)?,
+            queue_type: row
+                .get::<usize, Option<String>>(3)
+                .map_err(map_oracle_error)?
+                .unwrap_or_default(),
+            payload_type: row
+                .get::<usize, Option<String>>(4)
+                .map_err(map_oracle_error)?
+                .unwrap_or_default(),
+            enqueue_enabled: enqueue_enabled.as_deref() == Some("YES"),
+            dequeue_enabled: dequeue_enabled.as_deref() == Some("YES"),
+            max_retries: row.get::<usize, Option<i64>>(7).map_err(map_oracle_error)?,
+            comment: row
+                .get::<usize, Option<String>>(8)
+                .map_err(map_oracle_error)?,
+        });
+    }
+
+    Ok(queues)
+}
+
+/// Peeks at the messages of a queue through its AQ$ view, which never dequeues or
+/// locks anything.
+pub(crate) fn browse_aq_messages(
+    session: &OracleSession,
+    request: &DbAqBrowseRequest,
+) -> Result<DbAqBrowseResult, String> {
+    let queue_name = request.queue_name.trim();
+    if queue_name.is_empty() {
+        return Err("Queue name is required".to_string());
+    }
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_AQ_BROWSE_LIMIT)
+        .clamp(1, MAX_AQ_BROWSE_LIMIT);
+
+    let queue_sql = r#"
+        SELECT Q.NAME, Q.QUEUE_TABLE, T.TYPE, T.OBJECT_TYPE
+        FROM ALL_QUEUES Q
+        JOIN ALL_QUEUE_TABLES T
+          ON T.OWNER = Q.OWNER
+         AND T.QUEUE_TABLE = Q.QUEUE_TABLE
+        WHERE Q.OWNER = :1
+          AND Q.NAME IN (:2, UPPER(:3))
+    "#;
+    let (queue_name, queue_table, storage_type, object_type) = match session
+        .connection
+        .query_row_as::<(String, String, Option<String>, Option<String>)>(
+            queue_sql,
+            &[&session.target_schema, &queue_name, &queue_name],
+        ) {
+        Ok(queue) => queue,
+        Err(error) if error.kind() == oracle::ErrorKind::NoDataFound => {
+            return Err(format!(
+                "Queue '{}' was not found in schema {}",
+                queue_name, session.target_schema
+            ))
+        }
+        Err(error) => return Err(map_oracle_error(error)),
+    };
+    let payload_type = object_type
+        .clone()
+        .or_else(|| storage_type.clone())
+        .unwrap_or_default();
+    let view_name = sql_ident::qualified_name(
+        DatabaseProvider::Oracle,
+        Some(&session.target_schema),
+        &format!("AQ${queue_table}"),
+    );
+    let payload_expression = aq_payload_expression(storage_type.as_deref(), object_type.as_deref());
+
+    let (messages, payload_note) =
+        match query_aq_messages(session, &view_name, payload_expression, &queue_name, limit) {
+            Ok(messages) => (messages, None),
+            // Some payload types (ANYDATA, opaque objects) cannot be converted in SQL;
+            // still show the message headers.
+            Err(_) if payload_expression != "NULL" => (
+                query_aq_messages(session, &view_name, "NULL", &queue_name, limit)?,
+                Some(format!(
+                    "Payloads of type {payload_type} cannot be rendered."
+                )),
+            ),
+            Err(error) => return Err(error),
+        };
+
+    let mut message = format!("Browsed {} message(s) in {}.", messages.len(), queue_name);
+    if messages.len() as u32 >= limit {
+        message.push_str(&format!(" Results limited to {limit} messages."));
+    }
+    if let Some(note) = payload_note {
+        message.push(' ');
+        message.push_str(&note);
+    }
+
+    Ok(DbAqBrowseResult {
+        queue_name,
+        payload_type,
+        messages,
+        message,
+    })
+}
+
+fn query_aq_messages(
+    session: &OracleSession,
+    view_name: &str,
+    payload_expression: &str,
+    queue_name: &str,
+    limit: u32,
+) -> Result<Vec<DbAqMessage>, String> {
+    let sql = format!(
+        r#"
+        SELECT MSG_ID, CORR_ID, MSG_PRIORITY, MSG_STATE, ENQ_TIME, RETRY_COUNT,
+               EXCEPTION_QUEUE, PAYLOAD
+        FROM (
+            SELECT RAWTOHEX(Q.MSG_ID) AS MSG_ID, Q.CORR_ID, Q.MSG_PRIORITY, Q.MSG_STATE,
+                   TO_CHAR(Q.ENQ_TIME, 'YYYY-MM-DD HH24:MI:SS') AS ENQ_TIME,
+                   Q.RETRY_COUNT, Q.EXCEPTION_QUEUE, {payload_expression} AS PAYLOAD
+            FROM {view_name} Q
+            WHERE Q.QUEUE = :1
+            ORDER BY Q.ENQ_TIME, Q.MSG_ID
+        )
+        WHERE ROWNUM <= :2
+    "#
+    );
+
+    let rows = session
+        .connection
+        .query(sql.as_str(), &[&queue_name, &limit])
+        .map_err(map_oracle_error)?;
+
+    let mut messages = Vec::new();
+    for row_result in rows {
+        let row = row_result.map_err(map_oracle_error)?;
+        let payload = row
+            .get::<usize, Option<String>>(7)
+            .map_err(map_oracle_error)?;
+        let payload_format = payload
+            .as_deref()
+            .map_or("none", detect_payload_format)
+            .to_string();
+        let payload_truncated = payload
+            .as_ref()
+            .is_some_and(|payload| payload.chars().count() > MAX_AQ_PAYLOAD_CHARS);
+        let payload = payload.map(|payload| {
+            if payload_truncated {
+                payload.chars().take(MAX_AQ_PAYLOAD_CHARS).collect()
+            } else {
+                payload
+            }
+        });
+
+        messages.push(DbAqMessage {
+            message_id: row.get::<usize, String>(0).map_err(map_oracle_error)?,
+            correlation_id: row
+                .get::<usize, Option<String>>(1)
+                .map_err(map_oracle_error)?,
+            priority: row.get::<usize, Option<i64>>(2).map_err(map_oracle_error)?,
+            state: row
+                .get::<usize, Option<String>>(3)
+                .map_err(map_oracle_error)?
+                .unwrap_or_default(),
+            enqueued_at: row
+                .get::<usize, Option<String>>(4)
+                .map_err(map_oracle_error)?,
+            retry_count: row.get::<usize, Option<i64>>(5).map_err(map_oracle_error)?,
+            exception_queue: row
+                .get::<usize, Option<String>>(6)
+                .map_err(map_oracle_error)?,
+            payload,
+            payload_format,
+            payload_truncated,
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Returns a SQL expression that renders an AQ$ view's USER_DATA column as text.
+fn aq_payload_expression(storage_type: Option<&str>, object_type: Option<&str>) -> &'static str {
+    let object_type = object_type.unwrap_or_default().to_ascii_uppercase();
+    let storage_type = storage_type.unwrap_or_default().to_ascii_uppercase();
+
+    if object_type.ends_with("XMLTYPE") || storage_type == "XMLTYPE" {
+        "Q.USER_DATA.GETCLOBVAL()"
+    } else if object_type.ends_with("AQ$_JMS_TEXT_MESSAGE") {
+        "NVL(TO_CLOB(Q.USER_DATA.TEXT_VC), Q.USER_DATA.TEXT_LOB)"
+    } else if object_type == "JSON" || storage_type == "JSON" {
+        "JSON_SERIALIZE(Q.USER_DATA RETURNING CLOB)"
+    } else if storage_type == "RAW" {
+        "TO_CLOB(UTL_RAW.CAST_TO_VARCHAR2(UTL_RAW.SUBSTR(Q.USER_DATA, 1, LEAST(UTL_RAW.LENGTH(Q.USER_DATA), 2000))))"
+    } else if object_type.ends_with("ANYDATA") {
+        "NULL"
+    } else if storage_type == "OBJECT" {
+        "XMLTYPE(Q.USER_DATA).GETCLOBVAL()"
+    } else {
+        "NULL"
+    }
+}
+
+fn detect_payload_format(payload: &str) -> &'static str {
+    match payload.trim_start().chars().next() {
+        Some('{') | Some('[') => "json",
+        Some('<') => "xml",
+        _ => "text",
+    }
+}
+
+/// Reads recent server-side errors from the alert log (V$DIAG_ALERT_EXT), falling back
+/// to DBA_ALERT_HISTORY when the alert log view is not accessible.
+pub(crate) fn get_recent_errors(
+    session: &OracleSession,
+    request: &DbRecentErrorsRequest,
+) -> Result<DbRecentErrorsResult, String> {
+    let since_minutes = request
+        .since_minutes
+        .unwrap_or(DEFAULT_RECENT_ERRORS_MINUTES)
+        .clamp(1, MAX_RECENT_ERRORS_MINUTES);
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_RECENT_ERRORS_LIMIT)
+        .clamp(1, MAX_RECENT_ERRORS_LIMIT);
+    let error_code = match request.error_code.as_deref().map(str::trim) {
+        Some(code) if !code.is_empty() => Some(normalize_ora_error_code(code)?),
+        _ => None,
+    };
+
+    let alert_log_sql = r#"
+        SELECT LOGGED_AT, MESSAGE_TEXT, MESSAGE_LEVEL, MODULE_ID
+        FROM (
+            SELECT TO_CHAR(ORIGINATING_TIMESTAMP, 'YYYY-MM-DD HH24:MI:SS.FF3 TZH:TZM') AS LOGGED_AT,
+                   MESSAGE_TEXT, MESSAGE_LEVEL, MODULE_ID
+            FROM V$DIAG_ALERT_EXT
+            WHERE ORIGINATING_TIMESTAMP >= SYSTIMESTAMP - NUMTODSINTERVAL(:1, 'MINUTE')
+              AND INSTR(MESSAGE_TEXT, :2) > 0
+            ORDER BY ORIGINATING_TIMESTAMP DESC
+        )
+        WHERE ROWNUM <= :3
+    "#;
+    let alert_log_needle = error_code.clone().unwrap_or_else(|| "ORA-".to_string());
+    let alert_log_error = match query_server_errors(
+        session,
+        alert_log_sql,
+        since_minutes,
+        Some(alert_log_needle),
+        limit,
+    ) {
+        Ok(entries) => {
+            return Ok(recent_errors_result(
+                "alertLog",
+                entries,
+                since_minutes,
+                limit,
+            ))
+        }
+        Err(error) => error,
+    };
+
+    // The alert history also holds threshold alerts, so only filter when asked to.
+    let alert_history_sql = r#"
+        SELECT LOGGED_AT, REASON, MESSAGE_LEVEL, MODULE_ID
+        FROM (
+            SELECT TO_CHAR(CREATION_TIME, 'YYYY-MM-DD HH24:MI:SS.FF3 TZH:TZM') AS LOGGED_AT,
+                   REASON, MESSAGE_LEVEL, MODULE_ID
+            FROM DBA_ALERT_HISTORY
+            WHERE CREATION_TIME >= SYSTIMESTAMP - NUMTODSINTERVAL(:1, 'MINUTE')
+              AND NVL(INSTR(REASON, :2), 1) > 0
+            ORDER BY CREATION_TIME DESC
+        )
+        WHERE ROWNUM <= :3
+    "#;
+    match query_server_errors(session, alert_history_sql, since_minutes, error_code, limit) {
+        Ok(entries) => Ok(recent_errors_result(
+            "alertHistory",
+            entries,
+            since_minutes,
+            limit,
+        )),
+        Err(history_error) => Err(format!(
+            "Unable to read server errors. V$DIAG_ALERT_EXT: {alert_log_error}. DBA_ALERT_HISTORY: {history_error}"
+        )),
+    }
+}
+
+fn query_server_errors(
+    session: &OracleSession,
+    sql: &str,
+    since_minutes: u32,
+    needle: Option<String>,
+    limit: u32,
+) -> Result<Vec<DbServerErrorEntry>, String> {
+    let rows = session
+        .connection
+        .query(sql, &[&since_minutes, &needle, &limit])
+        .map_err(map_oracle_error)?;
+
+    let mut entries = Vec::new();
+    for row_result in rows {
+        let row = row_result.map_err(map_oracle_error)?;
+        let message = row
+            .get::<usize, Option<String>>(1)
+            .map_err(map_oracle_error)?
+            .unwrap_or_default();
+        entries.push(DbServerErrorEntry {
+            timestamp: row
+                .get::<usize, Option<String>>(0)
+                .map_err(map_oracle_error)?
+                .unwrap_or_default(),
+            error_code: extract_ora_error_code(message.as_str()),
+            message: message.trim_end().to_string(),
+            level: row.get::<usize, Option<i64>>(2).map_err(map_oracle_error)?,
+            module: row
+                .get::<usize, Option<String>>(3)
+                .map_err(map_oracle_error)?,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn recent_errors_result(
+    source: &str,
+    entries: Vec<DbServerErrorEntry>,
+    since_minutes: u32,
+    limit: u32,
+) -> DbRecentErrorsResult {
+    let source_label = if source == "alertLog" {
+        "alert log"
+    } else {
+        "alert history"
+    };
+    let mut message = format!(
+        "Found {} {} entr{} in the last {} minute(s).",
+        entries.len(),
+        source_label,
+        if entries.len() == 1 { "y" } else { "ies" },
+        since_minutes
+    );
+    if entries.len() as u32 >= limit {
+        message.push_str(&format!(" Results limited to {limit} entries."));
+    }
+
+    DbRecentErrorsResult {
+        source: source.to_string(),
+        entries,
+        message,
+    }
+}
+
+/// Accepts `ORA-1555`, `ora-01555` or `1555` and returns `ORA-01555`.
+fn normalize_ora_error_code(code: &str) -> Result<String, String> {
+    let upper = code.trim().to_ascii_uppercase();
+    let digits = upper.strip_prefix("ORA-").unwrap_or(upper.as_str());
+    if digits.is_empty() || digits.len() > 5 || !digits.chars().all(|ch| ch.is_ascii_digit()) {
+        return Err(format!("'{code}' is not a valid ORA- error code"));
+    }
+    Ok(format!("ORA-{digits:0>5}"))
+}
+
+fn extract_ora_error_code(message: &str) -> Option<String> {
+    message.match_indices("ORA-").find_map(|(index, _)| {
+        let digits = message.get(index + 4..index + 9)?;
+        digits
+            .chars()
+            .all(|ch| ch.is_ascii_digit())
+            .then(|| format!("ORA-{digits}"))
+    })
+}
+
+/// Lists sessions holding DML locks on a table and sessions waiting on one of its
+/// rows. Waiting rows are identified by rebuilding their ROWID from V$SESSION.
+pub(crate) fn get_row_locks(
+    session: &OracleSession,
+    request: &DbRowLocksRequest,
+) -> Result<DbRowLocksResult, String> {
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
+    ensure_schema_is_in_scope(&schema, session)?;
+    let table_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.table_name);
+    if table_name.is_empty() {
+        return Err("Table name is required".to_string());
+    }
+
+    let waiters =
+        fetch_row_lock_waiters(session, &schema, &table_name).map_err(map_lock_view_error)?;
+    let holders = fetch_table_lock_holders(session, &schema, &table_name)
+        .map_err(map_lock_view_error)?
+        .into_iter()
+        .map(|mut holder| {
+            holder.blocked_sessions = waiters
+                .iter()
+                .filter(|waiter| waiter.blocking_sid == Some(holder.sid))
+                .count() as u32;
+            holder
+        })
+        .collect::<Vec<_>>();
+
+    let message = if holders.is_empty() {
+        format!("No sessions hold locks on {schema}.{table_name}.")
+    } else {
+        format!(
+            "{} session(s) hold locks on {}.{}; {} session(s) are waiting for a row.",
+            holders.len(),
+            schema,
+            table_name,
+            waiters.len()
+        )
+    };
+
+    Ok(DbRowLocksResult {
+        schema,
+        table_name,
+        holders,
+        waiters,
+        message,
+    })
+}
+
+fn fetch_table_lock_holders(
+    session: &OracleSession,
+    schema: &str,
+    table_name: &str,
+) -> Result<Vec<DbTableLockHolder>, OracleError> {
+    let sql = r#"
+        SELECT S.SID, S.SERIAL#, S.USERNAME, S.OSUSER, S.MACHINE, S.PROGRAM, S.STATUS,
+               L.LOCKED_MODE, S.SQL_ID
+        FROM V$LOCKED_OBJECT L
+        JOIN ALL_OBJECTS O
+          ON O.OBJECT_ID = L.OBJECT_ID
+        JOIN V$SESSION S
+          ON S.SID = L.SESSION_ID
+        WHERE O.OWNER = :1
+          AND O.OBJECT_NAME = :2
+        ORDER BY S.SID
+    "#;
+
+    let rows = session.connection.query(sql, &[&schema, &table_name])?;
+    let mut holders = Vec::new();
+    for row_result in rows {
+        let row = row_result?;
+        holders.push(DbTableLockHolder {
+            sid: row.get::<usize, i64>(0)?,
+            serial: row.get::<usize, i64>(1)?,
+            username: row.get::<usize, Option<String>>(2)?,
+            os_user: row.get::<usize, Option<String>>(3)?,
+            machine: row.get::<usize, Option<String>>(4)?,
+            program: row.get::<usize, Option<String>>(5)?,
+            status: row.get::<usize, Option<String>>(6)?,
+            lock_mode: describe_lock_mode(row.get::<usize, Option<i64>>(7)?.unwrap_or(0)),
+            sql_id: row.get::<usize, Option<String>>(8)?,
+            blocked_sessions: 0,
+        });
+    }
+
+    Ok(holders)
+}
+
+fn fetch_row_lock_waiters(
+    session: &OracleSession,
+    schema: &str,
+    table_name: &str,
+) -> Result<Vec<DbRowLockWaiter>, OracleError> {
+    // ROW_WAIT_* columns are only meaningful while the session waits on a row lock.
+    // ROW_WAIT_OBJ# is an OBJECT_ID, and ROW_WAIT_FILE# an absolute file number that
+    // extended ROWIDs store as the relative number from V$DATAFILE.
+    let sql = r#"
+        SELECT W.SID, W.SERIAL#, W.USERNAME, W.BLOCKING_SESSION, W.EVENT, W.SECONDS_IN_WAIT,
+               CASE
+                   WHEN W.ROW_WAIT_ROW# >= 0 AND O.DATA_OBJECT_ID IS NOT NULL
+                        AND F.RFILE# IS NOT NULL THEN
+                       ROWIDTOCHAR(DBMS_ROWID.ROWID_CREATE(
+                           1, O.DATA_OBJECT_ID, F.RFILE#, W.ROW_WAIT_BLOCK#, W.ROW_WAIT_ROW#
+                       ))
+               END
+        FROM V$SESSION W
+        JOIN ALL_OBJECTS O
+          ON O.OBJECT_ID = W.ROW_WAIT_OBJ#
+        LEFT JOIN V$DATAFILE F
+          ON F.FILE# = W.ROW_WAIT_FILE#
+        WHERE O.OWNER = :1
+          AND O.OBJECT_NAME = :2
+          AND W.BLOCKING_SESSION IS NOT NULL
+        ORDER BY W.SECONDS_IN_WAIT DESC, W.SID
+    "#;
+
+    let rows = session.connection.query(sql, &[&schema, &table_name])?;
+    let mut waiters = Vec::new();
+    for row_result in rows {
+        let row = row_result?;
+        waiters.push(DbRowLockWaiter {
+            sid: row.get::<usize, i64>(0)?,
+            serial: row.get::<usize, i64>(1)?,
+            username: row.get::<usize, Option<String>>(2)?,
+            blocking_sid: row.get::<usize, Option<i64>>(3)?,
+            wait_event: row.get::<usize, Option<String>>(4)?,
+            seconds_waiting: row.get::<usize, Option<i64>>(5)?,
+            row_id: row.get::<usize, Option<String>>(6)?,
+        });
+    }
+
+    Ok(waiters)
+}
+
+fn map_lock_view_error(error: OracleError) -> String {
+    let message = map_oracle_error(error);
+    if message.contains("ORA-00942") {
+        format!(
+            "{message}. Lock inspection needs SELECT access to V$LOCKED_OBJECT, V$SESSION and V$DATAFILE (for example through SELECT_CATALOG_ROLE)."
+        )
+    } else {
+        message
+    }
+}
+
+fn describe_lock_mode(mode: i64) -> String {
+    match mode {
+        0 => "None",
+        1 => "Null",
+        2 => "Row Share (SS)",
+        3 => "Row Exclusive (SX)",
+        4 => "Share (S)",
+        5 => "Share Row Exclusive (SSX)",
+        6 => "Exclusive (X)",
+        _ => return format!("Unknown ({mode})"),
+    }
+    .to_string()
+}
+
+/// Ranks the current schema's tables by read and write activity, counting their indexes
+/// too. Reads the AWR history (DBA_HIST_SEG_STAT) when asked for and the Diagnostics Pack
+/// is enabled, otherwise V$SEGMENT_STATISTICS, which covers the time since instance startup.
+pub(crate) fn get_object_access_stats(
+    session: &OracleSession,
+    request: &DbObjectAccessStatsRequest,
+) -> Result<DbObjectAccessStatsResult, String> {
+    let schema = session.target_schema.clone();
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_ACCESS_STATS_LIMIT)
+        .clamp(1, MAX_ACCESS_STATS_LIMIT);
+
+    let mut notes = Vec::new();
+    if let Some(days) = request.history_days {
+        let days = days.clamp(1, MAX_ACCESS_STATS_HISTORY_DAYS);
+        if diagnostics_pack_enabled(session) {
+            let history_sql = r#"
+                SELECT NVL(I.TABLE_NAME, O.OBJECT_NAME) AS TABLE_NAME,
+                       SUM(S.LOGICAL_READS_DELTA) AS LOGICAL_READS,
+                       SUM(S.PHYSICAL_READS_DELTA) AS PHYSICAL_READS,
+                       SUM(S.PHYSICAL_WRITES_DELTA) AS PHYSICAL_WRITES,
+                       SUM(S.DB_BLOCK_CHANGES_DELTA) AS BLOCK_CHANGES,
+                       SUM(CASE WHEN O.OBJECT_TYPE LIKE 'TABLE%' THEN S.TABLE_SCANS_DELTA ELSE 0 END) AS TABLE_SCANS
+                FROM DBA_HIST_SEG_STAT S
+                JOIN DBA_HIST_SEG_STAT_OBJ O
+                  ON O.DBID = S.DBID AND O.TS# = S.TS# AND O.OBJ# = S.OBJ# AND O.DATAOBJ# = S.DATAOBJ#
+                JOIN DBA_HIST_SNAPSHOT N
+                  ON N.DBID = S.DBID AND N.INSTANCE_NUMBER = S.INSTANCE_NUMBER AND N.SNAP_ID = S.SNAP_ID
+                LEFT JOIN ALL_INDEXES I
+                  ON O.OBJECT_TYPE LIKE 'INDEX%' AND I.OWNER = O.OWNER AND I.INDEX_NAME = O.OBJECT_NAME
+                WHERE O.OWNER = :owner
+                  AND N.BEGIN_INTERVAL_TIME >= SYSTIMESTAMP - NUMTODSINTERVAL(:days, 'DAY')
+                GROUP BY NVL(I.TABLE_NAME, O.OBJECT_NAME)
+            "#;
+            match fetch_table_access_stats(
+                session,
+                history_sql,
+                &[("owner", &schema), ("days", &days)],
+            ) {
+                Ok(tables) => {
+                    let since = format!("the last {days} day(s)");
+                    return Ok(access_stats_result(
+                        schema,
+                        "history",
+                        Some(since),
+                        tables,
+                        limit,
+                        notes,
+                    ));
+                }
+                Err(error) => notes.push(format!(
+                    "The AWR history could not be read ({}), so these are the counters since instance startup.",
+                    map_oracle_error(error)
+                )),
+            }
+        } else {
+            notes.push(
+                "The Diagnostics Pack is not enabled (CONTROL_MANAGEMENT_PACK_ACCESS), so these are the counters since instance startup."
+                    .to_string(),
+            );
+        }
+    }
+
+    let segment_sql = r#"
+        SELECT NVL(I.TABLE_NAME, S.OBJECT_NAME) AS TABLE_NAME,
+               SUM(CASE WHEN S.STATISTIC_NAME = 'logical reads' THEN S.VALUE ELSE 0 END) AS LOGICAL_READS,
+               SUM(CASE WHEN S.STATISTIC_NAME = 'physical reads' THEN S.VALUE ELSE 0 END) AS PHYSICAL_READS,
+               SUM(CASE WHEN S.STATISTIC_NAME = 'physical writes' THEN S.VALUE ELSE 0 END) AS PHYSICAL_WRITES,
+               SUM(CASE WHEN S.STATISTIC_NAME = 'db block changes' THEN S.VALUE ELSE 0 END) AS BLOCK_CHANGES,
+               SUM(CASE WHEN S.STATISTIC_NAME = 'segment scans' AND S.OBJECT_TYPE LIKE 'TABLE%' THEN S.VALUE ELSE 0 END) AS TABLE_SCANS
+        FROM V$SEGMENT_STATISTICS S
+        LEFT JOIN ALL_INDEXES I
+          ON S.OBJECT_TYPE LIKE 'INDEX%' AND I.OWNER = S.OWNER AND I.INDEX_NAME = S.OBJECT_NAME
+        WHERE S.OWNER = :owner
+          AND S.STATISTIC_NAME IN ('logical reads', 'physical reads', 'physical writes', 'db block changes', 'segment scans')
+        GROUP BY NVL(I.TABLE_NAME, S.OBJECT_NAME)
+    "#;
+    let tables = fetch_table_access_stats(session, segment_sql, &[("owner", &schema)]).map_err(|error| {
+        let message = map_oracle_error(error);
+        if message.contains("ORA-00942") {
+            format!(
+                "{message}. Access statistics need SELECT access to V$SEGMENT_STATISTICS (for example through SELECT_CATALOG_ROLE)."
+            )
+        } else {
+            message
+        }
+    })?;
+    // Reading the startup time needs V$INSTANCE access, which the statistics do not.
+    let since = session
+        .connection
+        .query_row_as::<String>(
+            "SELECT TO_CHAR(STARTUP_TIME, 'YYYY-MM-DD HH24:MI:SS') FROM V$INSTANCE",
+            &[],
+        )
+        .ok();
+    Ok(access_stats_result(
+        schema,
+        "segmentStatistics",
+        since,
+        tables,
+        limit,
+        notes,
+    ))
+}
+
+fn diagnostics_pack_enabled(session: &OracleSession) -> bool {
+    session
+        .connection
+        .query_row_as::<Option<String>>(
+            "SELECT VALUE FROM V$PARAMETER WHERE NAME = 'control_management_pack_access'",
+            &[],
+        )
+        .ok()
+        .flatten()
+        .is_some_and(|value| value.to_ascii_uppercase().contains("DIAGNOSTIC"))
+}
+
+/// Lists every table in the schema with the activity `stats_sql` reports for it. Binds are
+/// named so `:owner` can appear in both queries.
+fn fetch_table_access_stats(
+    session: &OracleSession,
+    stats_sql: &str,
+    params: &[(&str, &dyn oracle::sql_type::ToSql)],
+) -> Result<Vec<DbTableAccessStats>, OracleError> {
+    let sql = format!(
+        r#"
+        SELECT T.TABLE_NAME, NVL(X.LOGICAL_READS, 0), NVL(X.PHYSICAL_READS, 0),
+               NVL(X.PHYSICAL_WRITES, 0), NVL(X.BLOCK_CHANGES, 0), NVL(X.TABLE_SCANS, 0)
+        FROM ALL_TABLES T
+        LEFT JOIN ({stats_sql}) X
+          ON X.TABLE_NAME = T.TABLE_NAME
+        WHERE T.OWNER = :owner
+          AND T.DROPPED = 'NO'
+        "#
+    );
+    let rows = session.connection.query_named(sql.as_str(), params)?;
+    let mut tables = Vec::new();
+    for row_result in rows {
+        let row = row_result?;
+        tables.push(DbTableAccessStats {
+            table_name: row.get::<usize, String>(0)?,
+            logical_reads: row.get::<usize, u64>(1)?,
+            physical_reads: row.get::<usize, u64>(2)?,
+            physical_writes: row.get::<usize, u64>(3)?,
+            block_changes: row.get::<usize, u64>(4)?,
+            table_scans: row.get::<usize, u64>(5)?,
+            read_rank: None,
+            write_rank: None,
+        });
+    }
+    Ok(tables)
+}
+
+fn access_stats_result(
+    schema: String,
+    source: &str,
+    since: Option<String>,
+    tables: Vec<DbTableAccessStats>,
+    limit: u32,
+    notes: Vec<String>,
+) -> DbObjectAccessStatsResult {
+    let mut tables = rank_table_activity(tables);
+    let idle_tables = tables
+        .iter()
+        .filter(|table| table.read_rank.is_none() && table.write_rank.is_none())
+        .count() as u32;
+    let active_tables = tables.len() as u32 - idle_tables;
+    let mut message = format!(
+        "{active_tables} of {} table(s) in {schema} were used; {idle_tables} show no activity.",
+        tables.len()
+    );
+    if tables.len() > limit as usize {
+        tables.truncate(limit as usize);
+        message.push_str(&format!(" Showing the busiest {limit}."));
+    }
+    for note in notes {
+        message.push(' ');
+        message.push_str(&note);
+    }
+
+    DbObjectAccessStatsResult {
+        schema,
+        source: source.to_string(),
+        since,
+        tables,
+        idle_tables,
+        message,
+    }
+}
+
+/// Sets the read and write ranks and sorts the busiest tables first. Reads are logical
+/// reads, which include those served from the buffer cache; writes are block changes.
+fn rank_table_activity(mut tables: Vec<DbTableAccessStats>) -> Vec<DbTableAccessStats> {
+    let ranks = |key: fn(&DbTableAccessStats) -> u64, tables: &[DbTableAccessStats]| {
+        let mut order = (0..tables.len())
+            .filter(|&index| key(&tables[index]) > 0)
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&index| std::cmp::Reverse(key(&tables[index])));
+        let mut ranks = vec![None; tables.len()];
+        for (rank, index) in order.into_iter().enumerate() {
+            ranks[index] = Some(rank as u32 + 1);
+        }
+        ranks
+    };
+    let read_ranks = ranks(|table| table.logical_reads, &tables);
+    let write_ranks = ranks(|table| table.block_changes, &tables);
+    for ((table, read_rank), write_rank) in tables.iter_mut().zip(read_ranks).zip(write_ranks) {
+        table.read_rank = read_rank;
+        table.write_rank = write_rank;
+    }
+
+    tables.sort_by(|left, right| {
+        (right.logical_reads + right.block_changes)
+            .cmp(&(left.logical_reads + left.block_changes))
+            .then_with(|| left.table_name.cmp(&right.table_name))
+    });
+    tables
+}
+
+/// Reports database and client character sets. The client side is always UTF-8 because
+/// the Oracle client is initialized with a UTF-8 encoding regardless of NLS_LANG.
+pub(crate) fn get_encoding_info(session: &OracleSession) -> Result<DbEncodingInfo, String> {
+    let sql = r#"
+        SELECT
+            MAX(CASE WHEN PARAMETER = 'NLS_CHARACTERSET' THEN VALUE END),
+            MAX(CASE WHEN PARAMETER = 'NLS_NCHAR_CHARACTERSET' THEN VALUE END),
+            (SELECT VALUE FROM NLS_SESSION_PARAMETERS WHERE PARAMETER = 'NLS_LENGTH_SEMANTICS')
+        FROM NLS_DATABASE_PARAMETERS
+    "#;
+    let (database_charset, national_charset, length_semantics) = session
+        .connection
+        .query_row_as::<(Option<String>, Option<String>, Option<String>)>(sql, &[])
+        .map_err(map_oracle_error)?;
+    let database_charset = database_charset.unwrap_or_default();
+    let length_semantics = length_semantics.unwrap_or_else(|| "BYTE".to_string());
+    let nls_lang = env::var("NLS_LANG")
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+
+    Ok(DbEncodingInfo {
+        warnings: encoding_warnings(
+            database_charset.as_str(),
+            length_semantics.as_str(),
+            nls_lang.as_deref(),
+        ),
+        database_charset,
+        national_charset: national_charset.unwrap_or_default(),
+        client_charset: "AL32UTF8".to_string(),
+        nls_lang,
+        length_semantics,
+    })
+}
+
+fn encoding_warnings(
+    database_charset: &str,
+    length_semantics: &str,
+    nls_lang: Option<&str>,
+) -> Vec<String> {
+    let charset = database_charset.trim().to_ascii_uppercase();
+    let is_unicode = matches!(charset.as_str(), "AL32UTF8" | "UTF8" | "AL16UTF16");
+    let mut warnings = Vec::new();
+
+    if !charset.is_empty() && !is_unicode {
+        warnings.push(format!(
+            "The database character set {charset} is not Unicode. Characters it cannot represent are stored as '?' when written from this client."
+        ));
+        if matches!(
+            charset.as_str(),
+            "US7ASCII" | "WE8ISO8859P1" | "WE8MSWIN1252"
+        ) {
+            warnings.push(format!(
+                "Data written by clients whose NLS_LANG did not match {charset} (for example Central European text) may have been stored unconverted and will appear garbled."
+            ));
+        }
+    }
+
+    if charset.contains("UTF8") && length_semantics.eq_ignore_ascii_case("BYTE") {
+        warnings.push(
+            "Length semantics are BYTE, so VARCHAR2(n) limits count bytes and multi-byte characters reduce the usable length."
+                .to_string(),
+        );
+    }
+
+    if let Some(client_charset) = nls_lang.and_then(|value| value.rsplit_once('.')) {
+        if !client_charset.1.eq_ignore_ascii_case("AL32UTF8") {
+            warnings.push(format!(
+                "NLS_LANG requests {}, but Clarity always exchanges data as AL32UTF8; other tools using this NLS_LANG may convert differently.",
+                client_charset.1
+            ));
+        }
+    }
+
+    warnings
+}
+
+pub(crate) fn set_timestamp_tz_mode(
+    session: &mut OracleSession,
+    mode: TimestampTzMode,
+) -> TimestampTzMode {
+    session.timestamp_tz_mode = mode;
+    session.timestamp_tz_mode
+}
+
+pub(crate) fn session_nls(session: &OracleSession) -> Result<DbSessionNls, String> {
+    let rows = session
+        .connection
+        .query_as::<(String, Option<String>)>(
+            "SELECT PARAMETER, VALUE FROM NLS_SESSION_PARAMETERS \
+             WHERE PARAMETER IN ('NLS_DATE_FORMAT', 'NLS_TIMESTAMP_FORMAT', 'NLS_NUMERIC_CHARACTERS')",
+            &[],
+        )
+        .map_err(map_oracle_error)?;
+    let mut nls = DbSessionNls {
+        date_format: String::new(),
+        timestamp_format: String::new(),
+        numeric_characters: String::new(),
+    };
+    for row_result in rows {
+        let (parameter, value) = row_result.map_err(map_oracle_error)?;
+        let value = value.unwrap_or_default();
+        match parameter.as_str() {
+            "NLS_DATE_FORMAT" => nls.date_format = value,
+            "NLS_TIMESTAMP_FORMAT" => nls.timestamp_format = value,
+            "NLS_NUMERIC_CHARACTERS" => nls.numeric_characters = value,
+            _ => {}
+        }
+    }
+    Ok(nls)
+}
+
+/// Applies the requested NLS parameters with `ALTER SESSION` and keeps them so a
+/// reconnect restores them. Returns the values now in effect.
+pub(crate) fn set_session_nls(
+    session: &mut OracleSession,
+    request: &DbSetSessionNlsRequest,
+) -> Result<DbSessionNls, String> {
+    for (parameter, value) in nls_changes(request)? {
+        session
+            .connection
+            .execute(&alter_session_nls_sql(parameter, &value), &[])
+            .map_err(|error| format!("Failed to set {parameter}: {}", map_oracle_error(error)))?;
+        session
+            .nls_overrides
+            .retain(|(existing, _)| *existing != parameter);
+        session.nls_overrides.push((parameter, value));
+    }
+    session_nls(session)
+}
+
+fn nls_changes(request: &DbSetSessionNlsRequest) -> Result<Vec<(&'static str, String)>, String> {
+    let mut changes = Vec::new();
+    for (parameter, value) in [
+        ("NLS_DATE_FORMAT", request.date_format.as_deref()),
+        ("NLS_TIMESTAMP_FORMAT", request.timestamp_format.as_deref()),
+        (
+            "NLS_NUMERIC_CHARACTERS",
+            request.numeric_characters.as_deref(),
+        ),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let value = if parameter == "NLS_NUMERIC_CHARACTERS" {
+            value.to_string()
+        } else {
+            value.trim().to_string()
+        };
+        if value.is_empty() {
+            return Err(format!("{parameter} cannot be empty"));
+        }
+        if parameter == "NLS_NUMERIC_CHARACTERS" {
+            let characters: Vec<char> = value.chars().collect();
+            let valid = characters.len() == 2
+                && characters[0] != characters[1]
+                && characters
+                    .iter()
+                    .all(|c| !c.is_ascii_digit() && !matches!(c, '+' | '-' | '<' | '>'));
+            if !valid {
+                return Err(
+                    "NLS_NUMERIC_CHARACTERS needs two different characters, the decimal then the group separator, neither a digit nor + - < >"
+                        .to_string(),
+                );
+            }
+        }
+        changes.push((parameter, value));
+    }
+    if changes.is_empty() {
+        return Err("No NLS parameters to set".to_string());
+    }
+    Ok(changes)
+}
+
+const MAX_CALL_TIMEOUT_SECS: u32 = 24 * 60 * 60;
+
+/// Switches the Resource Manager consumer group and sets the call timeout. The session
+/// keeps both so a reconnect restores them.
+pub(crate) fn set_resource_limits(
+    session: &mut OracleSession,
+    request: &DbSetResourceLimitsRequest,
+) -> Result<DbResourceLimits, String> {
+    let consumer_group = match request.consumer_group.as_deref().map(str::trim) {
+        Some(group) if !group.is_empty() => Some(sql_ident::parse_identifier(
+            DatabaseProvider::Oracle,
+            group,
+            "Consumer group",
+        )?),
+        _ => None,
+    };
+    if let Some(seconds) = request.call_timeout_secs {
+        if seconds == 0 || seconds > MAX_CALL_TIMEOUT_SECS {
+            return Err(format!(
+                "Call timeout must be between 1 and {MAX_CALL_TIMEOUT_SECS} seconds"
+            ));
+        }
+    }
+
+    if consumer_group != session.consumer_group {
+        let target = consumer_group
+            .clone()
+            .or_else(|| session.original_consumer_group.clone());
+        if let Some(target) = target {
+            let previous = switch_consumer_group(&session.connection, &target)?;
+            if session.original_consumer_group.is_none() {
+                session.original_consumer_group = previous;
+            }
+        }
+        session.consumer_group = consumer_group;
+    }
+
+    session
+        .connection
+        .set_call_timeout(request.call_timeout_secs.map(call_timeout_duration))
+        .map_err(map_oracle_error)?;
+    session.call_timeout_secs = request.call_timeout_secs;
+
+    Ok(DbResourceLimits {
+        consumer_group: session.consumer_group.clone(),
+        original_consumer_group: session.original_consumer_group.clone(),
+        call_timeout_secs: session.call_timeout_secs,
+    })
+}
+
+/// Moves the session into another container of a multitenant database with
+/// `ALTER SESSION SET CONTAINER`, which needs the `SET CONTAINER` privilege there. The
+/// session keeps the container so a reconnect returns to it.
+pub(crate) fn set_container(
+    session: &mut OracleSession,
+    request: &DbSetContainerRequest,
+) -> Result<DbServerInfo, String> {
+    let container =
+        sql_ident::parse_identifier(DatabaseProvider::Oracle, &request.container, "Container")?;
+    if session.transaction_active {
+        return Err("Commit or roll back the open transaction before switching containers".into());
+    }
+    switch_container(&session.connection, &container)?;
+    // The switch resets the current schema; keep the explorer's schema where it exists.
+    let _ = restore_current_schema(session);
+    session.container = Some(container);
+    Ok(server_info(session))
+}
+
+fn switch_container(connection: &Connection, container: &str) -> Result<(), String> {
+    connection
+        .execute(
+            &format!(
+                "ALTER SESSION SET CONTAINER = {}",
+                sql_ident::format_identifier(DatabaseProvider::Oracle, container)
+            ),
+            &[],
+        )
+        .map(|_| ())
+        .map_err(|error| {
+            format!(
+                "Failed to switch to container {container}: {}",
+                map_oracle_error(error)
+            )
+        })
+}
+
+fn restore_current_schema(session: &OracleSession) -> Result<(), String> {
+    session
+        .connection
+        .execute(
+            &format!(
+                "ALTER SESSION SET CURRENT_SCHEMA = {}",
+                sql_ident::format_identifier(DatabaseProvider::Oracle, &session.target_schema)
+            ),
+            &[],
+        )
+        .map(|_| ())
+        .map_err(map_oracle_error)
+}
+
+/// Interrupts whatever the session's connection is running, from another thread.
+pub(crate) struct OracleCancelHandle(Arc<Connection>);
+
+impl OracleCancelHandle {
+    /// Sends a break; the running call then fails with ORA-01013 and the connection stays
+    /// usable. Statement-level rollback undoes a cancelled DML statement only.
+    pub(crate) fn cancel(&self) -> Result<(), String> {
+        self.0.break_execution().map_err(map_oracle_error)
+    }
+}
+
+pub(crate) fn cancel_handle(session: &OracleSession) -> OracleCancelHandle {
+    OracleCancelHandle(Arc::clone(&session.connection))
+}
+
+/// Containers the session can see: every PDB from the root with `V$CONTAINERS` access,
+/// only the current one from inside a PDB.
+pub(crate) fn list_containers(session: &OracleSession) -> Result<Vec<DbContainer>, String> {
+    let rows = session
+        .connection
+        .query_as::<(u64, String, Option<String>, Option<String>)>(
+            "SELECT CON_ID, NAME, OPEN_MODE, RESTRICTED FROM V$CONTAINERS ORDER BY CON_ID",
+            &[],
+        )
+        .map_err(|error| {
+            format!(
+                "Failed to list containers (needs SELECT on V$CONTAINERS): {}",
+                map_oracle_error(error)
+            )
+        })?;
+    rows.map(|row| {
+        let (container_id, name, open_mode, restricted) = row.map_err(map_oracle_error)?;
+        Ok(DbContainer {
+            container_id,
+            name,
+            open_mode,
+            restricted: restricted.as_deref() == Some("YES"),
+        })
+    })
+    .collect()
+}
+
+/// Returns the group the session was in before the switch.
+fn switch_consumer_group(connection: &Connection, group: &str) -> Result<Option<String>, String> {
+    let mut statement = connection
+        .statement("BEGIN DBMS_SESSION.SWITCH_CURRENT_CONSUMER_GROUP(:1, :2, FALSE); END;")
+        .build()
+        .map_err(map_oracle_error)?;
+    statement
+        .execute(&[&group, &OracleType::Varchar2(128)])
+        .map_err(|error| {
+            format!(
+                "Failed to switch to consumer group {group}: {}",
+                map_oracle_error(error)
+            )
+        })?;
+    statement.bind_value(2).map_err(map_oracle_error)
+}
+
+fn call_timeout_duration(seconds: u32) -> Duration {
+    Duration::from_secs(u64::from(seconds))
+}
+
+fn alter_session_nls_sql(parameter: &str, value: &str) -> String {
+    format!(
+        "ALTER SESSION SET {parameter} = '{}'",
+        value.replace('\'', "''")
+    )
+}
+
+pub(crate) fn begin_transaction(session: &mut OracleSession) -> Result<bool, String> {
+    session.transaction_active = true;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn commit_transaction(session: &mut OracleSession) -> Result<bool, String> {
+    if session.transaction_active {
+        session.connection.commit().map_err(map_oracle_error)?;
+    }
+    session.transaction_active = false;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn rollback_transaction(session: &mut OracleSession) -> Result<bool, String> {
+    if session.transaction_active {
+        session.connection.rollback().map_err(map_oracle_error)?;
+    }
+    session.transaction_active = false;
+    Ok(session.transaction_active)
+}
+
+pub(crate) fn transaction_active(session: &OracleSession) -> bool {
+    session.transaction_active
+}
+
+#[derive(Clone, Copy)]
+enum TransactionControl {
+    None,
+    Commit,
+    Rollback,
+    RollbackToSavepoint,
+    Savepoint,
+    SetTransaction,
+}
+
+/// Replaces a dropped connection with a fresh one using the original connect options,
+/// restoring the schema and edition. Any uncommitted work is already gone at this point.
+fn reconnect(session: &mut OracleSession) -> Result<(), String> {
+    let (replacement, _, _) = connect(&session.connect_options, session.timestamp_tz_mode)
+        .map_err(DbConnectError::into_message)?;
+    session.connection = replacement.connection;
+    session.transaction_active = false;
+    session.open_cursor = None;
+    if let Some(sql) = session.startup_sql.as_deref() {
+        execute_startup_sql(&session.connection, sql)?;
+    }
+    apply_session_settings(session)
+}
+
+/// Copies the settings changed on `previous` through commands onto a new session and
+/// applies them to its connection.
+pub(crate) fn restore_session_settings(
+    previous: &OracleSession,
+    session: &mut OracleSession,
+) -> Result<(), String> {
+    session.timestamp_tz_mode = previous.timestamp_tz_mode;
+    session.nls_overrides = previous.nls_overrides.clone();
+    session.consumer_group = previous.consumer_group.clone();
+    session.original_consumer_group = previous.original_consumer_group.clone();
+    session.call_timeout_secs = previous.call_timeout_secs;
+    session.container = previous.container.clone();
+    apply_session_settings(session)
+}
+
+fn apply_session_settings(session: &OracleSession) -> Result<(), String> {
+    // First, as switching containers resets the other session settings.
+    if let Some(container) = session.container.as_deref() {
+        switch_container(&session.connection, container)?;
+        restore_current_schema(session)?;
+    }
+    for (parameter, value) in &session.nls_overrides {
+        session
+            .connection
+            .execute(&alter_session_nls_sql(parameter, value), &[])
+            .map_err(map_oracle_error)?;
+    }
+    if let Some(group) = session.consumer_group.as_deref() {
+        switch_consumer_group(&session.connection, group)?;
+    }
+    session
+        .connection
+        .set_call_timeout(session.call_timeout_secs.map(call_timeout_duration))
+        .map_err(map_oracle_error)?;
+    Ok(())
+}
+
+/// Runs the profile's startup statements and keeps them for later reconnects.
+pub(crate) fn run_startup_sql(session: &mut OracleSession, sql: &str) -> Result<(), String> {
+    execute_startup_sql(&session.connection, sql)?;
+    session.startup_sql = Some(sql.to_string());
+    Ok(())
+}
+
+fn execute_startup_sql(connection: &Connection, sql: &str) -> Result<(), String> {
+    for statement in split_statements(sql, DatabaseProvider::Oracle) {
+        connection.execute(&statement.sql, &[]).map_err(|error| {
+            format!(
+                "Startup SQL failed at line {}: {}",
+                statement.line,
+                map_oracle_error(error)
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn is_rejected_credentials_error(message: &str) -> bool {
+    ["ORA-01017", "ORA-28001", "ORA-28000"]
+        .iter()
+        .any(|code| message.contains(code))
+}
+
+pub(crate) fn is_connection_lost_error(message: &str) -> bool {
+    const CONNECTION_LOST_CODES: [&str; 12] = [
+        "ORA-01012",
+        "ORA-02396",
+        "ORA-03113",
+        "ORA-03114",
+        "ORA-03135",
+        "ORA-12170",
+        "ORA-12537",
+        "ORA-12547",
+        "ORA-12570",
+        "ORA-12571",
+        "DPI-1010",
+        "DPI-1080",
+    ];
+    CONNECTION_LOST_CODES
+        .iter()
+        .any(|code| message.contains(code))
+}
+
+/// Only plain queries are replayed; anything that could change data or take locks is not.
+fn is_read_only_query(sql: &str) -> bool {
+    let sql = skip_leading_sql_comments(sql).trim_start_matches('(');
+    let first_word = sql
+        .split(|ch: char| ch.is_whitespace() || ch == '(')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    if first_word != "SELECT" && first_word != "WITH" {
+        return false;
+    }
+
+    let upper = sql.to_ascii_uppercase();
+    let words = upper.split_whitespace().collect::<Vec<_>>();
+    !words.windows(2).any(|pair| {
+        matches!(
+            pair,
+            ["FOR", "UPDATE"] | ["WITH", "FUNCTION"] | ["WITH", "PROCEDURE"]
+        )
+    })
 }
 
 fn detect_transaction_control(sql: &str) -> TransactionControl {
@@ -962,8 +3686,14 @@ fn run_show_con_name(session: &OracleSession) -> Result<DbQueryResult, String> {
     Ok(DbQueryResult {
         columns: vec!["CON_NAME".to_string()],
         rows: vec![vec![con_name]],
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message: "SHOW CON_NAME executed.".to_string(),
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
@@ -978,8 +3708,14 @@ fn run_show_user(session: &OracleSession) -> Result<DbQueryResult, String> {
     Ok(DbQueryResult {
         columns: vec!["USER".to_string()],
         rows: vec![vec![user_name]],
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message: "SHOW USER executed.".to_string(),
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
@@ -1012,7 +3748,7 @@ fn run_show_pdbs(session: &OracleSession, row_limit: usize) -> Result<DbQueryRes
         let values = row
             .sql_values()
             .iter()
-            .map(sql_value_to_string)
+            .map(|value| sql_value_to_string(value, &TimestampTzTarget::Original))
             .collect::<Vec<_>>();
         rows.push(values);
     }
@@ -1025,8 +3761,14 @@ fn run_show_pdbs(session: &OracleSession, row_limit: usize) -> Result<DbQueryRes
     Ok(DbQueryResult {
         columns,
         rows,
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message,
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
@@ -1065,7 +3807,7 @@ fn run_show_parameter(
         let values = row
             .sql_values()
             .iter()
-            .map(sql_value_to_string)
+            .map(|value| sql_value_to_string(value, &TimestampTzTarget::Original))
             .collect::<Vec<_>>();
         rows.push(values);
     }
@@ -1078,8 +3820,14 @@ fn run_show_parameter(
     Ok(DbQueryResult {
         columns,
         rows,
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
         rows_affected: None,
         message,
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
     })
 }
 
@@ -1096,57 +3844,19 @@ fn normalize_show_parameter_filter(filter: &str) -> String {
     format!("%{}%", normalized)
 }
 
-fn effective_query_row_limit(request: &DbQueryRequest) -> usize {
-    request
-        .row_limit
-        .unwrap_or(DEFAULT_QUERY_ROW_LIMIT)
-        .clamp(1, MAX_QUERY_ROW_LIMIT) as usize
-}
-
-fn row_matches_query_filters(
-    row: &[String],
-    normalized_global_search: &str,
-    normalized_column_filters: &[String],
-) -> bool {
-    if !normalized_global_search.is_empty()
-        && !row
-            .iter()
-            .any(|value| value.to_lowercase().contains(normalized_global_search))
-    {
-        return false;
-    }
-
-    for (column_index, normalized_filter) in normalized_column_filters.iter().enumerate() {
-        if normalized_filter.is_empty() {
-            continue;
-        }
-
-        let cell_value = row
-            .get(column_index)
-            .map(|value| value.as_str())
-            .unwrap_or_default()
-            .to_lowercase();
-        if !cell_value.contains(normalized_filter) {
-            return false;
-        }
-    }
-
-    true
-}
-
-fn normalize_schema_name(schema: &str) -> Result<String, String> {
-    let normalized = schema.trim().to_ascii_uppercase();
+fn normalize_unquoted_identifier(value: &str, label: &str) -> Result<String, String> {
+    let normalized = value.trim().to_ascii_uppercase();
     if normalized.is_empty() {
-        return Err("Schema is required".to_string());
+        return Err(format!("{label} is required"));
     }
 
     if !normalized
         .chars()
         .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$' || ch == '#')
     {
-        return Err(
-            "Schema must use unquoted Oracle identifier characters: A-Z, 0-9, _, $, #".to_string(),
-        );
+        return Err(format!(
+            "{label} must use unquoted Oracle identifier characters: A-Z, 0-9, _, $, #"
+        ));
     }
 
     Ok(normalized)
@@ -1167,7 +3877,7 @@ fn map_oracle_error(error: OracleError) -> String {
     error.to_string()
 }
 
-fn map_connect_error(error: OracleError, host: &str, port: u16, service_name: &str) -> DbConnectError {
+fn map_connect_error(error: OracleError, target: &str) -> DbConnectError {
     let base = error.to_string();
 
     if base.contains("DPI-1047") {
@@ -1180,7 +3890,7 @@ fn map_connect_error(error: OracleError, host: &str, port: u16, service_name: &s
     }
 
     DbConnectError::General {
-        message: format!("{} (target: //{}:{}/{})", base, host, port, service_name),
+        message: format!("{} (target: {})", base, target),
     }
 }
 
@@ -1223,7 +3933,42 @@ fn fetch_source_ddl(
     "#;
 
     let rows = connection.query(sql, &[&schema, &object_type, &object_name])?;
+    collect_source_text(rows)
+}
+
+/// Source of a stored program unit as it was `seconds_ago` seconds back, read with a
+/// flashback query on `ALL_SOURCE`. `None` for object types without source text, or when
+/// the unit did not exist then. Needs `FLASHBACK` on the dictionary (e.g. `FLASHBACK ANY
+/// TABLE`) and enough undo retention.
+pub(crate) fn get_object_source_as_of(
+    session: &OracleSession,
+    request: &DbObjectRef,
+    seconds_ago: u64,
+) -> Result<Option<String>, String> {
+    let schema = sql_ident::stored_name(DatabaseProvider::Oracle, &request.schema);
+    ensure_schema_is_in_scope(&schema, session)?;
+    let object_name = sql_ident::stored_name(DatabaseProvider::Oracle, &request.object_name);
+    let source_type = normalize_source_type(&request.object_type);
+    if !is_source_supported(&source_type) {
+        return Ok(None);
+    }
+
+    let sql = r#"
+        SELECT TEXT
+        FROM ALL_SOURCE AS OF TIMESTAMP SYSTIMESTAMP - NUMTODSINTERVAL(:1, 'SECOND')
+        WHERE OWNER = :2
+          AND TYPE = :3
+          AND NAME = :4
+        ORDER BY LINE
+    "#;
+    let rows = session
+        .connection
+        .query(sql, &[&seconds_ago, &schema, &source_type, &object_name])
+        .map_err(map_oracle_error)?;
+    collect_source_text(rows).map_err(map_oracle_error)
+}
 
+fn collect_source_text(rows: ResultSet<'_, Row>) -> Result<Option<String>, OracleError> {
     let mut ddl = String::new();
     for row_result in rows {
         let row = row_result?;
@@ -1258,43 +4003,525 @@ fn fetch_object_ddl_for_search(
     let ddl_sql = "SELECT DBMS_METADATA.GET_DDL(:1, :2, :3) FROM DUAL";
     match connection.query_row_as::<String>(ddl_sql, &[&metadata_type, &object_name, &schema]) {
         Ok(ddl) => Ok(Some(ddl)),
-        Err(_) => Ok(None),
+        Err(_) => Ok(
+            fetch_dictionary_ddl(connection, schema, metadata_type.as_str(), object_name)
+                .unwrap_or(None),
+        ),
     }
 }
 
-fn find_matching_line(text: &str, needle_upper: &str) -> Option<(u32, String)> {
-    for (idx, line) in text.lines().enumerate() {
-        if line.to_ascii_uppercase().contains(needle_upper) {
-            let line_number = (idx + 1).min(u32::MAX as usize) as u32;
-            return Some((line_number, line.trim().to_string()));
+const RECONSTRUCTED_DDL_NOTE: &str =
+    "-- Reconstructed from the data dictionary; DBMS_METADATA is not available to this account.\n";
+
+/// Rebuilds view and table DDL from `ALL_VIEWS` and `ALL_TAB_COLUMNS`, for accounts that
+/// may not call `DBMS_METADATA`. Tables get their columns and primary key only.
+fn fetch_dictionary_ddl(
+    connection: &Connection,
+    schema: &str,
+    metadata_type: &str,
+    object_name: &str,
+) -> Result<Option<String>, OracleError> {
+    match metadata_type {
+        "VIEW" => {
+            let text = match connection.query_row_as::<Option<String>>(
+                "SELECT TEXT FROM ALL_VIEWS WHERE OWNER = :1 AND VIEW_NAME = :2",
+                &[&schema, &object_name],
+            ) {
+                Ok(text) => text,
+                Err(error) if error.kind() == oracle::ErrorKind::NoDataFound => None,
+                Err(error) => return Err(error),
+            };
+            Ok(text.map(|text| {
+                format!(
+                    "{RECONSTRUCTED_DDL_NOTE}CREATE OR REPLACE VIEW {}.{} AS\n{};\n",
+                    sql_ident::quote_identifier(DatabaseProvider::Oracle, schema),
+                    sql_ident::quote_identifier(DatabaseProvider::Oracle, object_name),
+                    text.trim_end()
+                )
+            }))
+        }
+        "TABLE" => {
+            let rows = connection.query(
+                r#"
+                SELECT COLUMN_NAME, DATA_TYPE, DATA_LENGTH, CHAR_LENGTH, CHAR_USED,
+                       DATA_PRECISION, DATA_SCALE, NULLABLE, DATA_DEFAULT
+                FROM ALL_TAB_COLUMNS
+                WHERE OWNER = :1
+                  AND TABLE_NAME = :2
+                ORDER BY COLUMN_ID
+                "#,
+                &[&schema, &object_name],
+            )?;
+            let mut columns = Vec::new();
+            for row_result in rows {
+                let row = row_result?;
+                let data_type = column_type_sql(
+                    &row.get::<usize, String>(1)?,
+                    row.get::<usize, Option<u32>>(2)?,
+                    row.get::<usize, Option<u32>>(3)?,
+                    row.get::<usize, Option<String>>(4)?.as_deref(),
+                    row.get::<usize, Option<u32>>(5)?,
+                    row.get::<usize, Option<i32>>(6)?,
+                );
+                columns.push(ReconstructedColumn {
+                    name: row.get(0)?,
+                    data_type,
+                    nullable: row.get::<usize, Option<String>>(7)?.as_deref() != Some("N"),
+                    default: row.get(8)?,
+                });
+            }
+            if columns.is_empty() {
+                return Ok(None);
+            }
+
+            let key_rows = connection.query(
+                r#"
+                SELECT cc.COLUMN_NAME
+                FROM ALL_CONSTRAINTS c
+                JOIN ALL_CONS_COLUMNS cc
+                  ON cc.OWNER = c.OWNER
+                 AND cc.CONSTRAINT_NAME = c.CONSTRAINT_NAME
+                WHERE c.OWNER = :1
+                  AND c.TABLE_NAME = :2
+                  AND c.CONSTRAINT_TYPE = 'P'
+                ORDER BY cc.POSITION
+                "#,
+                &[&schema, &object_name],
+            )?;
+            let mut primary_key = Vec::new();
+            for row_result in key_rows {
+                primary_key.push(row_result?.get::<usize, String>(0)?);
+            }
+            Ok(Some(reconstruct_table_ddl(
+                schema,
+                object_name,
+                &columns,
+                &primary_key,
+            )))
         }
+        _ => Ok(None),
     }
+}
 
-    None
+struct ReconstructedColumn {
+    name: String,
+    data_type: String,
+    nullable: bool,
+    default: Option<String>,
+}
+
+fn reconstruct_table_ddl(
+    schema: &str,
+    table_name: &str,
+    columns: &[ReconstructedColumn],
+    primary_key: &[String],
+) -> String {
+    let quote =
+        |identifier: &str| sql_ident::quote_identifier(DatabaseProvider::Oracle, identifier);
+    let mut lines = columns
+        .iter()
+        .map(|column| {
+            let mut line = format!("  {} {}", quote(&column.name), column.data_type);
+            if let Some(default) = column
+                .default
+                .as_deref()
+                .map(str::trim)
+                .filter(|default| !default.is_empty())
+            {
+                line.push_str(" DEFAULT ");
+                line.push_str(default);
+            }
+            if !column.nullable {
+                line.push_str(" NOT NULL");
+            }
+            line
+        })
+        .collect::<Vec<_>>();
+    if !primary_key.is_empty() {
+        lines.push(format!(
+            "  PRIMARY KEY ({})",
+            primary_key
+                .iter()
+                .map(|column| quote(column))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    format!(
+        "{RECONSTRUCTED_DDL_NOTE}CREATE TABLE {}.{} (\n{}\n);\n",
+        quote(schema),
+        quote(table_name),
+        lines.join(",\n")
+    )
 }
 
-fn truncate_for_snippet(value: &str) -> String {
-    let trimmed = value.trim();
-    if trimmed.chars().count() <= MAX_SEARCH_SNIPPET_CHARS {
-        return trimmed.to_string();
+/// Column type as written in DDL, from the `ALL_TAB_COLUMNS` fields.
+fn column_type_sql(
+    data_type: &str,
+    data_length: Option<u32>,
+    char_length: Option<u32>,
+    char_used: Option<&str>,
+    precision: Option<u32>,
+    scale: Option<i32>,
+) -> String {
+    match data_type {
+        "VARCHAR2" | "CHAR" => match (char_used, char_length, data_length) {
+            (Some("C"), Some(length), _) => format!("{data_type}({length} CHAR)"),
+            (_, _, Some(length)) => format!("{data_type}({length} BYTE)"),
+            _ => data_type.to_string(),
+        },
+        "NVARCHAR2" | "NCHAR" => match char_length {
+            Some(length) => format!("{data_type}({length})"),
+            None => data_type.to_string(),
+        },
+        "RAW" => match data_length {
+            Some(length) => format!("RAW({length})"),
+            None => data_type.to_string(),
+        },
+        "NUMBER" => match (precision, scale) {
+            (Some(precision), Some(scale)) if scale != 0 => format!("NUMBER({precision},{scale})"),
+            (Some(precision), _) => format!("NUMBER({precision})"),
+            (None, Some(0)) => "NUMBER(*,0)".to_string(),
+            _ => "NUMBER".to_string(),
+        },
+        "FLOAT" => match precision {
+            Some(precision) => format!("FLOAT({precision})"),
+            None => data_type.to_string(),
+        },
+        _ => data_type.to_string(),
     }
+}
 
-    let mut snippet = String::new();
-    for (index, ch) in trimmed.chars().enumerate() {
-        if index >= MAX_SEARCH_SNIPPET_CHARS {
-            break;
+/// Display text of a row, plus the full byte length of its binary cells.
+fn display_row(
+    values: &[SqlValue<'_>],
+    timestamp_tz_target: &TimestampTzTarget,
+    preview_bytes: usize,
+) -> (Vec<String>, Vec<Option<u64>>) {
+    values
+        .iter()
+        .map(|value| match binary_preview(value, preview_bytes) {
+            Some((preview, length)) => (preview, Some(length)),
+            None => (sql_value_to_string(value, timestamp_tz_target), None),
+        })
+        .unzip()
+}
+
+fn byte_lengths_if_any(lengths: Vec<Vec<Option<u64>>>) -> Vec<Vec<Option<u64>>> {
+    if lengths.iter().flatten().any(Option::is_some) {
+        lengths
+    } else {
+        Vec::new()
+    }
+}
+
+/// Hex preview and full length of a non-NULL RAW, LONG RAW or BLOB value. Only the
+/// previewed part of a BLOB is read.
+fn binary_preview(value: &SqlValue<'_>, preview_bytes: usize) -> Option<(String, u64)> {
+    match value.oracle_type().ok()? {
+        OracleType::Raw(_) | OracleType::LongRaw => {
+            let bytes = value.get::<Option<Vec<u8>>>().ok()??;
+            let length = bytes.len() as u64;
+            Some((hex_preview(&bytes, length, preview_bytes), length))
         }
-        snippet.push(ch);
+        OracleType::BLOB => {
+            let blob = value.get::<Option<Blob>>().ok()??;
+            let length = blob.size().ok()?;
+            let mut head = Vec::new();
+            blob.take(preview_bytes as u64)
+                .read_to_end(&mut head)
+                .ok()?;
+            Some((hex_preview(&head, length, preview_bytes), length))
+        }
+        _ => None,
+    }
+}
+
+fn hex_preview(bytes: &[u8], length: u64, preview_bytes: usize) -> String {
+    let mut text = bytes
+        .iter()
+        .take(preview_bytes)
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<String>();
+    if length > preview_bytes as u64 {
+        text.push_str(&format!("... ({length} bytes)"));
     }
-    snippet.push_str("...");
+    text
+}
 
-    snippet
+fn sql_value_null_flags(values: &[SqlValue<'_>]) -> Vec<bool> {
+    values
+        .iter()
+        .map(|value| value.is_null().unwrap_or(false))
+        .collect()
 }
 
-fn sql_value_to_string(value: &SqlValue<'_>) -> String {
+/// Invalid bytes in character and LOB columns are shown as U+FFFD, which
+/// `note_lossy_conversion` flags.
+fn sql_value_to_string(value: &SqlValue<'_>, timestamp_tz_target: &TimestampTzTarget) -> String {
+    if matches!(
+        value.oracle_type(),
+        Ok(OracleType::Varchar2(_)
+            | OracleType::NVarchar2(_)
+            | OracleType::Char(_)
+            | OracleType::NChar(_)
+            | OracleType::Long
+            | OracleType::CLOB
+            | OracleType::NCLOB)
+    ) {
+        return match value.get::<Option<String>>() {
+            Ok(Some(text)) => text,
+            Ok(None) => "NULL".to_string(),
+            // Invalid byte sequences fail strict UTF-8 decoding; show them replaced instead.
+            Err(_) => read_lob_lossy(value).unwrap_or_else(|| value.to_string()),
+        };
+    }
+
+    if matches!(value.oracle_type(), Ok(OracleType::TimestampTZ(_))) {
+        if let Ok(Some(timestamp)) = value.get::<Option<Timestamp>>() {
+            if let Some(shifted) = timestamp_tz_target.shift(&timestamp) {
+                return shifted.to_string();
+            }
+        }
+    }
+
     value.to_string()
 }
 
+fn sql_value_to_export_value(
+    value: &SqlValue<'_>,
+    timestamp_tz_target: &TimestampTzTarget,
+) -> ExportValue {
+    if value.is_null().unwrap_or(false) {
+        return ExportValue::Null;
+    }
+
+    match value.oracle_type() {
+        Ok(
+            OracleType::Number(_, _)
+            | OracleType::Float(_)
+            | OracleType::BinaryFloat
+            | OracleType::BinaryDouble
+            | OracleType::Int64
+            | OracleType::UInt64,
+        ) => ExportValue::Number(value.to_string()),
+        Ok(
+            OracleType::Date
+            | OracleType::Timestamp(_)
+            | OracleType::TimestampTZ(_)
+            | OracleType::TimestampLTZ(_),
+        ) => {
+            let display = sql_value_to_string(value, timestamp_tz_target);
+            let timestamp = match value.get::<Timestamp>() {
+                Ok(timestamp) => timestamp,
+                Err(_) => return ExportValue::Text(display),
+            };
+            let timestamp = match value.oracle_type() {
+                Ok(OracleType::TimestampTZ(_)) => {
+                    timestamp_tz_target.shift(&timestamp).unwrap_or(timestamp)
+                }
+                _ => timestamp,
+            };
+
+            ExportValue::DateTime(ExportDateTime {
+                year: timestamp.year(),
+                month: timestamp.month(),
+                day: timestamp.day(),
+                hour: timestamp.hour(),
+                minute: timestamp.minute(),
+                second: timestamp.second(),
+                nanosecond: timestamp.nanosecond(),
+                offset_seconds: timestamp.with_tz().then(|| timestamp.tz_offset()),
+                display,
+            })
+        }
+        _ => ExportValue::Text(value.to_string()),
+    }
+}
+
+fn read_lob_lossy(value: &SqlValue<'_>) -> Option<String> {
+    let mut bytes = Vec::new();
+    match value.oracle_type().ok()? {
+        OracleType::CLOB => value.get::<Clob>().ok()?.read_to_end(&mut bytes).ok()?,
+        OracleType::NCLOB => value.get::<Nclob>().ok()?.read_to_end(&mut bytes).ok()?,
+        _ => return None,
+    };
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Where TIMESTAMP WITH TIME ZONE values are shifted before they are shown or exported.
+enum TimestampTzTarget {
+    Original,
+    Offset(i32),
+    /// A region-based session zone, whose offset depends on the value because of DST.
+    SessionZone {
+        connection: Arc<Connection>,
+        offsets: RefCell<ZoneOffsetCache>,
+    },
+}
+
+impl TimestampTzTarget {
+    fn shift(&self, timestamp: &Timestamp) -> Option<Timestamp> {
+        match self {
+            Self::Original => None,
+            Self::Offset(offset) => shift_timestamp_to_offset(timestamp, *offset),
+            Self::SessionZone {
+                connection,
+                offsets,
+            } => {
+                let offset = offsets
+                    .borrow_mut()
+                    .offset_at(utc_seconds(timestamp), |utc| {
+                        session_zone_offset_at(connection, utc)
+                    })?;
+                shift_timestamp_to_offset(timestamp, offset)
+            }
+        }
+    }
+}
+
+/// Session zone offsets of whole UTC days, so most values need no round trip.
+#[derive(Default)]
+struct ZoneOffsetCache {
+    /// `None` for days with a DST transition, whose values are looked up one by one.
+    days: HashMap<i64, Option<i32>>,
+}
+
+impl ZoneOffsetCache {
+    fn offset_at(
+        &mut self,
+        utc_seconds: i64,
+        mut lookup: impl FnMut(i64) -> Option<i32>,
+    ) -> Option<i32> {
+        let day = utc_seconds.div_euclid(86_400);
+        let whole_day = match self.days.get(&day) {
+            Some(offset) => *offset,
+            None => {
+                let start = lookup(day * 86_400)?;
+                let end = lookup(day * 86_400 + 86_399)?;
+                let offset = (start == end).then_some(start);
+                self.days.insert(day, offset);
+                offset
+            }
+        };
+        whole_day.or_else(|| lookup(utc_seconds))
+    }
+}
+
+/// Picks the target for the session's `TimestampTzMode`, querying its zone in Session mode.
+fn resolve_timestamp_tz_target(session: &OracleSession) -> Result<TimestampTzTarget, String> {
+    match session.timestamp_tz_mode {
+        TimestampTzMode::Original => Ok(TimestampTzTarget::Original),
+        TimestampTzMode::Utc => Ok(TimestampTzTarget::Offset(0)),
+        TimestampTzMode::Session => {
+            let zone = session
+                .connection
+                .query_row_as::<String>("SELECT SESSIONTIMEZONE FROM DUAL", &[])
+                .map_err(map_oracle_error)?;
+            Ok(match parse_fixed_offset(&zone) {
+                Some(offset) => TimestampTzTarget::Offset(offset),
+                None => TimestampTzTarget::SessionZone {
+                    connection: Arc::clone(&session.connection),
+                    offsets: RefCell::new(ZoneOffsetCache::default()),
+                },
+            })
+        }
+    }
+}
+
+/// Seconds east of UTC for a `[+|-]HH:MM` zone; `None` for region names.
+fn parse_fixed_offset(zone: &str) -> Option<i32> {
+    let zone = zone.trim();
+    let (sign, rest) = match zone.as_bytes().first()? {
+        b'+' => (1, &zone[1..]),
+        b'-' => (-1, &zone[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    let hours = hours.parse::<i32>().ok()?;
+    let minutes = minutes.parse::<i32>().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Offset of the session time zone at an instant given in seconds since the epoch.
+fn session_zone_offset_at(connection: &Connection, utc_seconds: i64) -> Option<i32> {
+    let days = utc_seconds.div_euclid(86_400);
+    let seconds_of_day = utc_seconds.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let instant = Timestamp::new(
+        i32::try_from(year).ok()?,
+        month,
+        day,
+        (seconds_of_day / 3600) as u32,
+        (seconds_of_day % 3600 / 60) as u32,
+        (seconds_of_day % 60) as u32,
+        0,
+    )
+    .ok()?;
+    let sql = "SELECT EXTRACT(TIMEZONE_HOUR FROM t), EXTRACT(TIMEZONE_MINUTE FROM t) FROM (SELECT FROM_TZ(CAST(:1 AS TIMESTAMP), 'UTC') AT TIME ZONE SESSIONTIMEZONE AS t FROM DUAL)";
+    let (hours, minutes) = connection
+        .query_row_as::<(i32, i32)>(sql, &[&instant])
+        .ok()?;
+    Some(hours * 3600 + minutes * 60)
+}
+
+fn utc_seconds(timestamp: &Timestamp) -> i64 {
+    days_from_civil(timestamp.year(), timestamp.month(), timestamp.day()) * 86_400
+        + i64::from(timestamp.hour()) * 3600
+        + i64::from(timestamp.minute()) * 60
+        + i64::from(timestamp.second())
+        - i64::from(timestamp.tz_offset())
+}
+
+fn shift_timestamp_to_offset(timestamp: &Timestamp, target_offset: i32) -> Option<Timestamp> {
+    let target_seconds = utc_seconds(timestamp) + i64::from(target_offset);
+    let days = target_seconds.div_euclid(86_400);
+    let seconds_of_day = target_seconds.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    Timestamp::new(
+        i32::try_from(year).ok()?,
+        month,
+        day,
+        (seconds_of_day / 3600) as u32,
+        (seconds_of_day % 3600 / 60) as u32,
+        (seconds_of_day % 60) as u32,
+        timestamp.nanosecond(),
+    )
+    .and_then(|value| value.and_tz_offset(target_offset))
+    .and_then(|value| value.and_prec(timestamp.precision()))
+    .ok()
+}
+
+// Proleptic Gregorian day arithmetic (days relative to 1970-01-01).
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
 fn normalize_ddl_for_execute(ddl: String, object_type: &str) -> String {
     let mut lines = ddl.lines().map(str::to_string).collect::<Vec<_>>();
 
@@ -1343,20 +4570,44 @@ fn with_create_or_replace_prefix(ddl: String, object_type: &str) -> String {
     format!("create or replace {trimmed_start}")
 }
 
+/// `config_dir` is a password store's wallet directory. Like the libraries, the client
+/// reads its configuration directory once per process.
 fn ensure_oracle_client_initialized(
     oracle_client_lib_dir_override: Option<&str>,
+    config_dir: Option<&Path>,
 ) -> Result<(), DbConnectError> {
     let normalized_override = oracle_client_lib_dir_override
         .map(str::trim)
         .filter(|path| !path.is_empty())
         .map(PathBuf::from);
 
-    if let Some(path) = normalized_override.as_ref() {
-        env::set_var("ORACLE_CLIENT_LIB_DIR", path);
-    }
-
+    // The client libraries are loaded once per process and cannot be swapped afterwards.
     if InitParams::is_initialized() {
-        return Ok(());
+        if let Some(requested) = config_dir {
+            let loaded = LOADED_CONFIG_DIR.get().cloned().flatten();
+            if loaded.as_deref() != Some(requested) {
+                return Err(DbConnectError::general(format!(
+                    "Oracle Client already reads its configuration from {}; restart Clarity to use the wallet in {}.",
+                    loaded
+                        .map(|dir| dir.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "the default location".to_string()),
+                    requested.display()
+                )));
+            }
+        }
+        return match (normalized_override, LOADED_CLIENT_DIR.get()) {
+            (Some(requested), Some(loaded)) if loaded.as_ref() != Some(&requested) => {
+                Err(DbConnectError::general(format!(
+                    "Oracle Client from {} is already loaded; restart Clarity to use {}.",
+                    loaded
+                        .as_ref()
+                        .map(|dir| dir.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "the default library path".to_string()),
+                    requested.to_string_lossy()
+                )))
+            }
+            _ => Ok(()),
+        };
     }
 
     let mut params = InitParams::new();
@@ -1371,18 +4622,21 @@ fn ensure_oracle_client_initialized(
     }
 
     if let Some(dir) = chosen_lib_dir.as_ref() {
-        env::set_var("ORACLE_CLIENT_LIB_DIR", dir);
         params
             .oracle_client_lib_dir(dir)
             .map_err(|e| DbConnectError::general(map_oracle_error(e)))?;
     }
 
-    if let Some(tns_admin) = env::var_os("TNS_ADMIN") {
+    let chosen_config_dir = config_dir
+        .map(Path::to_path_buf)
+        .or_else(|| env::var_os("TNS_ADMIN").map(PathBuf::from));
+    if let Some(dir) = chosen_config_dir.as_ref() {
         params
-            .oracle_client_config_dir(tns_admin)
+            .oracle_client_config_dir(dir)
             .map_err(|e| DbConnectError::general(map_oracle_error(e)))?;
     }
 
+    let loaded_dir = chosen_lib_dir.clone();
     params.init().map_err(|error| {
         let base = error.to_string();
         if base.contains("DPI-1047") {
@@ -1402,10 +4656,30 @@ fn ensure_oracle_client_initialized(
 
         DbConnectError::general(base)
     })?;
+    let _ = LOADED_CLIENT_DIR.set(loaded_dir);
+    let _ = LOADED_CONFIG_DIR.set(chosen_config_dir);
 
     Ok(())
 }
 
+/// Directory the client libraries were loaded from, once initialized; `Some(None)` means
+/// the system library path.
+pub(crate) fn loaded_client_dir() -> Option<Option<PathBuf>> {
+    LOADED_CLIENT_DIR.get().cloned()
+}
+
+/// Initializes the client libraries the way connecting does and reports their version.
+pub(crate) fn client_version(oracle_client_lib_dir: Option<&str>) -> Result<String, String> {
+    ensure_oracle_client_initialized(oracle_client_lib_dir, None).map_err(|error| match error {
+        DbConnectError::OracleClientMissing { message } | DbConnectError::General { message } => {
+            message
+        }
+    })?;
+    oracle::Version::client()
+        .map(|version| version.to_string())
+        .map_err(map_oracle_error)
+}
+
 fn detect_macos_instant_client_dir() -> Option<PathBuf> {
     let candidates = [
         Path::new("/opt/homebrew/lib"),
@@ -1462,3 +4736,435 @@ fn contains_libclntsh(dir: &Path) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        alter_session_nls_sql, aq_payload_expression, civil_from_days, column_type_sql,
+        connect_descriptor, days_from_civil, describe_lock_mode, describe_ref_cursors,
+        detect_payload_format, encoding_warnings, extract_ora_error_code, hex_preview,
+        is_connection_lost_error, is_read_only_query, nls_changes, normalize_ora_error_code,
+        normalize_unquoted_identifier, note_lossy_conversion, parse_bind_timestamp,
+        parse_fixed_offset, proxy_login, rank_table_activity, reconstruct_table_ddl,
+        shift_timestamp_to_offset, ReconstructedColumn, ZoneOffsetCache,
+    };
+    use crate::types::{
+        DbRefCursorResult, DbSetSessionNlsRequest, DbTableAccessStats, OracleFailoverOptions,
+        OracleProtocol, OracleTafOptions, OracleTlsOptions,
+    };
+    use oracle::sql_type::Timestamp;
+
+    #[test]
+    fn nls_changes_validate_and_quote_values() {
+        let request = |date: Option<&str>, numeric: Option<&str>| DbSetSessionNlsRequest {
+            session_id: 1,
+            date_format: date.map(str::to_string),
+            timestamp_format: None,
+            numeric_characters: numeric.map(str::to_string),
+        };
+
+        let changes = nls_changes(&request(Some(" YYYY-MM-DD\"T\"HH24:MI "), Some(", "))).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ("NLS_DATE_FORMAT", "YYYY-MM-DD\"T\"HH24:MI".to_string()),
+                ("NLS_NUMERIC_CHARACTERS", ", ".to_string()),
+            ]
+        );
+        assert_eq!(
+            alter_session_nls_sql("NLS_DATE_FORMAT", "DD 'x'"),
+            "ALTER SESSION SET NLS_DATE_FORMAT = 'DD ''x'''"
+        );
+        assert!(nls_changes(&request(None, None)).is_err());
+        assert!(nls_changes(&request(Some("  "), None)).is_err());
+        assert!(nls_changes(&request(None, Some(",,"))).is_err());
+        assert!(nls_changes(&request(None, Some("1."))).is_err());
+        assert!(nls_changes(&request(None, Some(".,;"))).is_err());
+    }
+
+    #[test]
+    fn civil_day_arithmetic_round_trips() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        for days in [-800_000, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year as i32, month, day), days);
+        }
+    }
+
+    #[test]
+    fn shifts_timestamp_with_time_zone_across_day_boundaries() {
+        let original = Timestamp::new(2024, 3, 1, 1, 30, 0, 250_000_000)
+            .and_then(|value| value.and_tz_hm_offset(2, 0))
+            .and_then(|value| value.and_prec(3))
+            .expect("valid timestamp");
+
+        let utc = shift_timestamp_to_offset(&original, 0).expect("shift to UTC");
+        assert_eq!(utc.to_string(), "2024-02-29 23:30:00.250 +00:00");
+
+        let new_york = shift_timestamp_to_offset(&original, -5 * 3600).expect("shift to -05:00");
+        assert_eq!(new_york.to_string(), "2024-02-29 18:30:00.250 -05:00");
+
+        let india = shift_timestamp_to_offset(&utc, 5 * 3600 + 30 * 60).expect("shift to +05:30");
+        assert_eq!(india.to_string(), "2024-03-01 05:00:00.250 +05:30");
+    }
+
+    #[test]
+    fn tells_fixed_session_offsets_from_region_names() {
+        assert_eq!(parse_fixed_offset("+05:30"), Some(5 * 3600 + 30 * 60));
+        assert_eq!(parse_fixed_offset("-08:00"), Some(-8 * 3600));
+        assert_eq!(parse_fixed_offset("Europe/Berlin"), None);
+    }
+
+    #[test]
+    fn looks_up_session_zone_offsets_per_value_on_dst_days() {
+        // Europe/Berlin: +01:00 until 2024-03-31 01:00 UTC, +02:00 after.
+        let transition = days_from_civil(2024, 3, 31) * 86_400 + 3600;
+        let berlin = |utc: i64| Some(if utc < transition { 3600 } else { 7200 });
+        let mut lookups = 0;
+        let mut cache = ZoneOffsetCache::default();
+        let mut offset_at = |utc: i64| {
+            cache.offset_at(utc, |at| {
+                lookups += 1;
+                berlin(at)
+            })
+        };
+
+        let winter = days_from_civil(2024, 3, 30) * 86_400;
+        assert_eq!(offset_at(winter + 600), Some(3600));
+        assert_eq!(offset_at(winter + 7200), Some(3600));
+        assert_eq!(offset_at(transition - 60), Some(3600));
+        assert_eq!(offset_at(transition + 60), Some(7200));
+        // Two lookups per day, plus one per value on the transition day.
+        assert_eq!(lookups, 6);
+    }
+
+    #[test]
+    fn picks_aq_payload_rendering_by_queue_type() {
+        assert_eq!(
+            aq_payload_expression(Some("OBJECT"), Some("SYS.XMLTYPE")),
+            "Q.USER_DATA.GETCLOBVAL()"
+        );
+        assert!(
+            aq_payload_expression(Some("OBJECT"), Some("SYS.AQ$_JMS_TEXT_MESSAGE"))
+                .contains("TEXT_LOB")
+        );
+        assert!(aq_payload_expression(Some("RAW"), None).starts_with("TO_CLOB(UTL_RAW"));
+        assert_eq!(
+            aq_payload_expression(Some("OBJECT"), Some("APP.ORDER_EVENT_T")),
+            "XMLTYPE(Q.USER_DATA).GETCLOBVAL()"
+        );
+        assert_eq!(
+            aq_payload_expression(Some("OBJECT"), Some("SYS.ANYDATA")),
+            "NULL"
+        );
+
+        assert_eq!(detect_payload_format("  {\"id\": 1}"), "json");
+        assert_eq!(detect_payload_format("<order/>"), "xml");
+        assert_eq!(detect_payload_format("plain"), "text");
+    }
+
+    #[test]
+    fn normalizes_and_extracts_ora_error_codes() {
+        assert_eq!(normalize_ora_error_code("1555").as_deref(), Ok("ORA-01555"));
+        assert_eq!(
+            normalize_ora_error_code(" ora-00060 ").as_deref(),
+            Ok("ORA-00060")
+        );
+        assert!(normalize_ora_error_code("ORA-").is_err());
+        assert!(normalize_ora_error_code("ORA-1555x").is_err());
+
+        assert_eq!(
+            extract_ora_error_code("Errors in file x.trc:\nORA-01555: snapshot too old").as_deref(),
+            Some("ORA-01555")
+        );
+        assert_eq!(
+            extract_ora_error_code("ORA-1 ORA-00600: internal"),
+            Some("ORA-00600".to_string())
+        );
+        assert_eq!(extract_ora_error_code("Thread 1 advanced"), None);
+    }
+
+    #[test]
+    fn validates_edition_names_as_unquoted_identifiers() {
+        assert_eq!(
+            normalize_unquoted_identifier(" release_42 ", "Edition").as_deref(),
+            Ok("RELEASE_42")
+        );
+        assert_eq!(
+            normalize_unquoted_identifier("v2; DROP", "Edition"),
+            Err(
+                "Edition must use unquoted Oracle identifier characters: A-Z, 0-9, _, $, #"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn describes_locked_object_modes() {
+        assert_eq!(describe_lock_mode(3), "Row Exclusive (SX)");
+        assert_eq!(describe_lock_mode(6), "Exclusive (X)");
+        assert_eq!(describe_lock_mode(9), "Unknown (9)");
+    }
+
+    #[test]
+    fn replays_only_read_only_queries_after_connection_loss() {
+        assert!(is_read_only_query(
+            "-- report\n/* v2 */ SELECT * FROM orders"
+        ));
+        assert!(is_read_only_query(
+            "with t as (select 1 x from dual) select * from t"
+        ));
+        assert!(is_read_only_query("(SELECT 1 FROM dual)"));
+        assert!(!is_read_only_query("SELECT * FROM orders FOR UPDATE"));
+        assert!(!is_read_only_query("UPDATE orders SET status = 'X'"));
+        assert!(!is_read_only_query(
+            "WITH FUNCTION f RETURN NUMBER IS BEGIN RETURN 1; END; SELECT f FROM dual"
+        ));
+
+        assert!(is_connection_lost_error(
+            "ORA-03113: end-of-file on communication channel"
+        ));
+        assert!(is_connection_lost_error(
+            "DPI-1080: connection was closed by ORA-3113"
+        ));
+        assert!(is_connection_lost_error(
+            "ORA-12170: TNS:Connect timeout occurred"
+        ));
+        assert!(!is_connection_lost_error(
+            "ORA-00942: table or view does not exist"
+        ));
+    }
+
+    #[test]
+    fn warns_about_lossy_database_character_sets() {
+        let warnings =
+            encoding_warnings("WE8ISO8859P1", "BYTE", Some("POLISH_POLAND.EE8MSWIN1250"));
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("not Unicode"));
+        assert!(warnings[2].contains("EE8MSWIN1250"));
+
+        let warnings = encoding_warnings("AL32UTF8", "BYTE", Some("AMERICAN_AMERICA.AL32UTF8"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("BYTE"));
+        assert!(encoding_warnings("AL32UTF8", "CHAR", None).is_empty());
+
+        let mut message = String::new();
+        assert!(note_lossy_conversion(
+            &[vec!["Gda\u{FFFD}sk".to_string()]],
+            &mut message
+        ));
+        assert!(!message.is_empty());
+    }
+
+    #[test]
+    fn builds_tcps_connect_descriptors() {
+        assert_eq!(
+            connect_descriptor(
+                OracleProtocol::Tcps,
+                &[("db.example.com", 2484)],
+                "ORCLPDB1",
+                None,
+                None
+            )
+            .expect("descriptor"),
+            "(DESCRIPTION=(ADDRESS=(PROTOCOL=TCPS)(HOST=db.example.com)(PORT=2484))(CONNECT_DATA=(SERVICE_NAME=ORCLPDB1))(SECURITY=(SSL_SERVER_DN_MATCH=YES)))"
+        );
+        assert_eq!(
+            connect_descriptor(
+                OracleProtocol::Tcp,
+                &[("rac1", 1521), ("rac2", 1522)],
+                "SALES",
+                None,
+                None
+            )
+            .expect("descriptor"),
+            "(DESCRIPTION=(ADDRESS_LIST=(FAILOVER=ON)(LOAD_BALANCE=OFF)(ADDRESS=(PROTOCOL=TCP)(HOST=rac1)(PORT=1521))(ADDRESS=(PROTOCOL=TCP)(HOST=rac2)(PORT=1522)))(CONNECT_DATA=(SERVICE_NAME=SALES)))"
+        );
+
+        let wallet = std::env::temp_dir();
+        let tls = OracleTlsOptions {
+            wallet_location: Some(wallet.to_string_lossy().to_string()),
+            server_cert_dn: Some("CN=db, O=Example".to_string()),
+            server_dn_match: Some(false),
+        };
+        let descriptor = connect_descriptor(
+            OracleProtocol::Tcps,
+            &[("db", 1522)],
+            "svc",
+            Some(&tls),
+            None,
+        )
+        .expect("descriptor");
+        assert!(descriptor.contains("(SSL_SERVER_DN_MATCH=NO)"));
+        assert!(descriptor.contains("(SSL_SERVER_CERT_DN=\"CN=db, O=Example\")"));
+        assert!(descriptor.contains("(MY_WALLET_DIRECTORY="));
+
+        let missing_wallet = OracleTlsOptions {
+            wallet_location: Some("/definitely/not/a/wallet".to_string()),
+            ..OracleTlsOptions::default()
+        };
+        assert!(connect_descriptor(
+            OracleProtocol::Tcps,
+            &[("db", 1522)],
+            "svc",
+            Some(&missing_wallet),
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn adds_load_balancing_retries_and_taf_to_connect_descriptors() {
+        let failover = OracleFailoverOptions {
+            load_balance: true,
+            retry_count: Some(3),
+            retry_delay_secs: Some(10),
+            taf: Some(OracleTafOptions::default()),
+        };
+        assert_eq!(
+            connect_descriptor(
+                OracleProtocol::Tcp,
+                &[("rac1", 1521), ("rac2", 1521)],
+                "SALES",
+                None,
+                Some(&failover)
+            )
+            .expect("descriptor"),
+            "(DESCRIPTION=(RETRY_COUNT=3)(RETRY_DELAY=10)(ADDRESS_LIST=(FAILOVER=ON)(LOAD_BALANCE=ON)(ADDRESS=(PROTOCOL=TCP)(HOST=rac1)(PORT=1521))(ADDRESS=(PROTOCOL=TCP)(HOST=rac2)(PORT=1521)))(CONNECT_DATA=(SERVICE_NAME=SALES)(FAILOVER_MODE=(TYPE=SELECT)(METHOD=BASIC)(RETRIES=30)(DELAY=5))))"
+        );
+    }
+
+    #[test]
+    fn builds_proxy_logins() {
+        assert_eq!(proxy_login("appuser", None).expect("login"), "appuser");
+        assert_eq!(
+            proxy_login("appuser", Some("  ")).expect("login"),
+            "appuser"
+        );
+        assert_eq!(
+            proxy_login("appuser", Some(" hr_owner ")).expect("login"),
+            "appuser[HR_OWNER]"
+        );
+        assert!(proxy_login("appuser", Some("hr]owner")).is_err());
+    }
+
+    #[test]
+    fn hex_preview_marks_truncated_values() {
+        assert_eq!(hex_preview(&[0x0a, 0xff], 2, 4), "0AFF");
+        assert_eq!(
+            hex_preview(&[0xde, 0xad, 0xbe, 0xef], 4096, 2),
+            "DEAD... (4096 bytes)"
+        );
+    }
+
+    #[test]
+    fn reconstructs_table_ddl_from_dictionary_columns() {
+        assert_eq!(
+            column_type_sql("VARCHAR2", Some(160), Some(40), Some("C"), None, None),
+            "VARCHAR2(40 CHAR)"
+        );
+        assert_eq!(
+            column_type_sql("NUMBER", Some(22), None, None, Some(10), Some(2)),
+            "NUMBER(10,2)"
+        );
+        assert_eq!(
+            column_type_sql("NUMBER", Some(22), None, None, None, Some(0)),
+            "NUMBER(*,0)"
+        );
+
+        let columns = [
+            ReconstructedColumn {
+                name: "ID".to_string(),
+                data_type: "NUMBER(*,0)".to_string(),
+                nullable: false,
+                default: None,
+            },
+            ReconstructedColumn {
+                name: "STATUS".to_string(),
+                data_type: "VARCHAR2(10 BYTE)".to_string(),
+                nullable: true,
+                default: Some("'NEW' ".to_string()),
+            },
+        ];
+        let ddl = reconstruct_table_ddl("HR", "ORDERS", &columns, &["ID".to_string()]);
+        assert!(ddl.starts_with("-- Reconstructed"));
+        assert!(ddl.ends_with(
+            "CREATE TABLE \"HR\".\"ORDERS\" (\n  \"ID\" NUMBER(*,0) NOT NULL,\n  \"STATUS\" VARCHAR2(10 BYTE) DEFAULT 'NEW',\n  PRIMARY KEY (\"ID\")\n);\n"
+        ));
+    }
+
+    #[test]
+    fn ranks_tables_by_reads_and_changes() {
+        let table = |name: &str, logical_reads: u64, block_changes: u64| DbTableAccessStats {
+            table_name: name.to_string(),
+            logical_reads,
+            physical_reads: 0,
+            physical_writes: 0,
+            block_changes,
+            table_scans: 0,
+            read_rank: None,
+            write_rank: None,
+        };
+        let ranked = rank_table_activity(vec![
+            table("ARCHIVE_2019", 0, 0),
+            table("ORDERS", 500, 40),
+            table("AUDIT_LOG", 10, 900),
+        ]);
+        let summary = ranked
+            .iter()
+            .map(|table| (table.table_name.as_str(), table.read_rank, table.write_rank))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                ("AUDIT_LOG", Some(2), Some(1)),
+                ("ORDERS", Some(1), Some(2)),
+                ("ARCHIVE_2019", None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_bind_dates_and_timestamps() {
+        let timestamp = parse_bind_timestamp("2026-03-07T14:05:09.25").unwrap();
+        assert_eq!(
+            (timestamp.year(), timestamp.month(), timestamp.day()),
+            (2026, 3, 7)
+        );
+        assert_eq!(
+            (timestamp.hour(), timestamp.minute(), timestamp.second()),
+            (14, 5, 9)
+        );
+        assert_eq!(timestamp.nanosecond(), 250_000_000);
+        assert_eq!(parse_bind_timestamp("2026-03-07").unwrap().hour(), 0);
+        assert!(parse_bind_timestamp("07.03.2026").is_err());
+        assert!(parse_bind_timestamp("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn describes_returned_ref_cursors() {
+        let cursor = |name: &str, rows: usize, truncated| DbRefCursorResult {
+            name: name.to_string(),
+            implicit: false,
+            columns: vec!["ID".to_string()],
+            rows: vec![vec!["1".to_string()]; rows],
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            truncated,
+        };
+        assert_eq!(describe_ref_cursors(&[]), "");
+        assert_eq!(
+            describe_ref_cursors(&[cursor("orders", 2, false), cursor("lines", 500, true)]),
+            " Cursor :orders returned 2 row(s). Cursor :lines returned the first 500 row(s)."
+        );
+        let implicit = DbRefCursorResult {
+            implicit: true,
+            ..cursor("1", 3, false)
+        };
+        assert_eq!(
+            describe_ref_cursors(&[implicit]),
+            " Implicit result 1 returned 3 row(s)."
+        );
+    }
+}
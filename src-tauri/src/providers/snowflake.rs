@@ -0,0 +1,1126 @@
+use super::common::{
+    effective_query_row_limit, find_matching_line, note_lossy_conversion, null_flags_if_any,
+    row_matches_query_filters, truncate_for_snippet, DEFAULT_SCHEMA_SEARCH_LIMIT,
+    MAX_EXPLORER_OBJECTS, MAX_SCHEMA_SEARCH_RESULTS,
+};
+use crate::data_export::{ExportDateTime, ExportRowSink, ExportValue};
+use crate::sql_ident;
+use crate::types::{
+    DatabaseProvider, DbConnectError, DbFilteredQueryRequest, DbObjectColumnEntry, DbObjectEntry,
+    DbObjectRef, DbQueryRequest, DbQueryResult, DbSchemaSearchRequest, DbSchemaSearchResult,
+    SnowflakeConnectOptions, SnowflakeTokenType,
+};
+use reqwest::blocking::{Client, Response};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+
+const DEFAULT_SNOWFLAKE_SCHEMA: &str = "PUBLIC";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+const STATEMENT_TIMEOUT_SECONDS: u64 = 600;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const NULL_DISPLAY: &str = "NULL";
+// GET_DDL('SCHEMA', ...) prefixes every object with one of these.
+const DDL_SCRIPT_OBJECT_PREFIX: &str = "CREATE OR REPLACE ";
+const DDL_SCRIPT_MODIFIERS: [&str; 5] = [
+    "SECURE",
+    "TRANSIENT",
+    "TEMPORARY",
+    "RECURSIVE",
+    "MATERIALIZED",
+];
+
+// The SQL API is stateless: every request carries the database, schema, warehouse and
+// role, and each statement commits on its own.
+pub(crate) struct SnowflakeSession {
+    client: Client,
+    base_url: String,
+    token: String,
+    token_type: SnowflakeTokenType,
+    database: String,
+    target_schema: String,
+    warehouse: Option<String>,
+    role: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct StatementResponse {
+    statement_handle: Option<String>,
+    statement_status_url: Option<String>,
+    message: Option<String>,
+    result_set_meta_data: Option<ResultSetMetaData>,
+    data: Vec<Vec<Option<String>>>,
+    stats: Option<StatementStats>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ResultSetMetaData {
+    row_type: Vec<SnowflakeColumn>,
+    partition_info: Vec<serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+struct SnowflakeColumn {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    scale: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct StatementStats {
+    num_rows_inserted: u64,
+    num_rows_updated: u64,
+    num_rows_deleted: u64,
+}
+
+pub(crate) fn connect(
+    request: &SnowflakeConnectOptions,
+) -> Result<(SnowflakeSession, String, String), DbConnectError> {
+    let account = request.account.trim();
+    let client = Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|error| {
+            DbConnectError::general(format!("Failed to initialize HTTP client: {error}"))
+        })?;
+    let optional = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let mut session = SnowflakeSession {
+        client,
+        base_url: account_base_url(account),
+        token: request.token.trim().to_string(),
+        token_type: request.token_type,
+        database: request.database.trim().to_string(),
+        target_schema: optional(&request.schema)
+            .unwrap_or_else(|| DEFAULT_SNOWFLAKE_SCHEMA.to_string()),
+        warehouse: optional(&request.warehouse),
+        role: optional(&request.role),
+    };
+
+    // Use the names as Snowflake resolved them so INFORMATION_SCHEMA filters match.
+    let response = execute(
+        &session,
+        "SELECT CURRENT_USER(), CURRENT_DATABASE(), CURRENT_SCHEMA()",
+        &[],
+    )
+    .map_err(|error| {
+        DbConnectError::general(format!(
+            "Failed to connect to Snowflake account {account}: {error}"
+        ))
+    })?;
+    let [user, database, schema]: [Option<String>; 3] = response
+        .data
+        .into_iter()
+        .next()
+        .and_then(|row| row.try_into().ok())
+        .ok_or_else(|| DbConnectError::general("Snowflake did not return session details."))?;
+    let (Some(database), Some(schema)) = (database, schema) else {
+        return Err(DbConnectError::general(format!(
+            "Database {} or schema {} does not exist or is not accessible with this role.",
+            session.database, session.target_schema
+        )));
+    };
+    session.database = database;
+    session.target_schema = schema;
+
+    let display_name = format!(
+        "{}@{account} [{}.{}]",
+        user.unwrap_or_else(|| request.username.trim().to_string()),
+        session.database,
+        session.target_schema
+    );
+    let schema = session.target_schema.clone();
+    Ok((session, display_name, schema))
+}
+
+pub(crate) fn server_version(session: &SnowflakeSession) -> Result<String, String> {
+    query_text_rows(session, "SELECT CURRENT_VERSION()", &[])?
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| "Snowflake did not report a version".to_string())
+}
+
+pub(crate) fn list_objects(session: &SnowflakeSession) -> Result<Vec<DbObjectEntry>, String> {
+    let sql = format!(
+        r#"
+        SELECT TABLE_SCHEMA,
+               CASE WHEN TABLE_TYPE IN ('VIEW', 'MATERIALIZED VIEW') THEN 'VIEW' ELSE 'TABLE' END,
+               TABLE_NAME
+        FROM INFORMATION_SCHEMA.TABLES
+        WHERE TABLE_SCHEMA = ?
+        UNION ALL
+        SELECT SEQUENCE_SCHEMA, 'SEQUENCE', SEQUENCE_NAME
+        FROM INFORMATION_SCHEMA.SEQUENCES
+        WHERE SEQUENCE_SCHEMA = ?
+        ORDER BY 2, 3
+        LIMIT {MAX_EXPLORER_OBJECTS}
+    "#
+    );
+    let schema = session.target_schema.as_str();
+    let rows = query_text_rows(session, sql.as_str(), &[schema, schema])?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_type, object_name]: [String; 3] = row.try_into().ok()?;
+            Some(DbObjectEntry {
+                schema,
+                object_type,
+                object_name,
+                status: None,
+                invalid_reason: None,
+                edition_name: None,
+                created: None,
+                last_ddl_time: None,
+                temporary: false,
+                secondary: false,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn list_object_columns(
+    session: &SnowflakeSession,
+) -> Result<Vec<DbObjectColumnEntry>, String> {
+    let sql = r#"
+        SELECT TABLE_SCHEMA, TABLE_NAME, COLUMN_NAME, DATA_TYPE,
+               IFF(IS_NULLABLE = 'YES', 'Y', 'N')
+        FROM INFORMATION_SCHEMA.COLUMNS
+        WHERE TABLE_SCHEMA = ?
+        ORDER BY TABLE_NAME, ORDINAL_POSITION
+    "#;
+    let rows = query_text_rows(session, sql, &[session.target_schema.as_str()])?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let [schema, object_name, column_name, data_type, nullable]: [String; 5] =
+                row.try_into().ok()?;
+            Some(DbObjectColumnEntry {
+                schema,
+                object_name,
+                column_name,
+                data_type,
+                nullable,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn get_object_ddl(
+    session: &SnowflakeSession,
+    request: &DbObjectRef,
+) -> Result<String, String> {
+    let schema = request.schema.trim();
+    ensure_schema_is_in_scope(schema, session)?;
+    let object_name = request.object_name.trim();
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+    if !matches!(object_type.as_str(), "TABLE" | "VIEW" | "SEQUENCE") {
+        return Err(format!(
+            "DDL retrieval for {object_type} objects is not supported for Snowflake."
+        ));
+    }
+
+    let qualified_name = qualified_object_name(session, object_name);
+    let rows = query_text_rows(
+        session,
+        "SELECT GET_DDL(?, ?)",
+        &[object_type.as_str(), qualified_name.as_str()],
+    )?;
+    rows.into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .ok_or_else(|| format!("{object_type} {schema}.{object_name} was not found"))
+}
+
+pub(crate) fn search_schema_text(
+    session: &SnowflakeSession,
+    request: &DbSchemaSearchRequest,
+) -> Result<Vec<DbSchemaSearchResult>, String> {
+    let search_term = request.search_term.trim();
+    if search_term.is_empty() {
+        return Err("Search term is required".to_string());
+    }
+
+    let include_object_names = request.include_object_names.unwrap_or(true);
+    let include_source = request.include_source.unwrap_or(true);
+    let include_ddl = request.include_ddl.unwrap_or(true);
+    if !(include_object_names || include_source || include_ddl) {
+        return Err("Select at least one search scope".to_string());
+    }
+
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_SCHEMA_SEARCH_LIMIT)
+        .clamp(1, MAX_SCHEMA_SEARCH_RESULTS) as usize;
+    let mut matches = Vec::new();
+
+    if include_object_names {
+        search_object_names(session, search_term, limit, &mut matches)?;
+    }
+
+    if include_source {
+        search_source_text(session, search_term, limit, &mut matches)?;
+    }
+
+    if include_ddl {
+        search_ddl_text(session, search_term, limit, &mut matches)?;
+    }
+
+    Ok(matches)
+}
+
+fn search_object_names(
+    session: &SnowflakeSession,
+    search_term: &str,
+    limit: usize,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let remaining = limit.saturating_sub(matches.len());
+    if remaining == 0 {
+        return Ok(());
+    }
+
+    let sql = format!(
+        r#"
+        SELECT OBJECT_SCHEMA, OBJECT_TYPE, OBJECT_NAME
+        FROM (
+            SELECT TABLE_SCHEMA AS OBJECT_SCHEMA,
+                   CASE WHEN TABLE_TYPE IN ('VIEW', 'MATERIALIZED VIEW') THEN 'VIEW' ELSE 'TABLE' END AS OBJECT_TYPE,
+                   TABLE_NAME AS OBJECT_NAME
+            FROM INFORMATION_SCHEMA.TABLES
+            WHERE TABLE_SCHEMA = ?
+            UNION ALL
+            SELECT SEQUENCE_SCHEMA, 'SEQUENCE', SEQUENCE_NAME
+            FROM INFORMATION_SCHEMA.SEQUENCES
+            WHERE SEQUENCE_SCHEMA = ?
+        )
+        WHERE CONTAINS(LOWER(OBJECT_NAME), LOWER(?))
+        ORDER BY OBJECT_TYPE, OBJECT_NAME
+        LIMIT {remaining}
+    "#
+    );
+    let schema = session.target_schema.as_str();
+    let rows = query_text_rows(session, sql.as_str(), &[schema, schema, search_term])?;
+
+    for row in rows {
+        let Ok([schema, object_type, object_name]) = <[String; 3]>::try_from(row) else {
+            continue;
+        };
+        matches.push(DbSchemaSearchResult {
+            schema,
+            object_type,
+            snippet: truncate_for_snippet(object_name.as_str()),
+            tags: Vec::new(),
+            object_name,
+            match_scope: "object_name".to_string(),
+            line: None,
+        });
+    }
+
+    Ok(())
+}
+
+fn search_source_text(
+    session: &SnowflakeSession,
+    search_term: &str,
+    limit: usize,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let remaining = limit.saturating_sub(matches.len());
+    if remaining == 0 {
+        return Ok(());
+    }
+
+    let sql = format!(
+        r#"
+        SELECT OBJECT_SCHEMA, OBJECT_TYPE, OBJECT_NAME, SOURCE_TEXT
+        FROM (
+            SELECT TABLE_SCHEMA AS OBJECT_SCHEMA, 'VIEW' AS OBJECT_TYPE,
+                   TABLE_NAME AS OBJECT_NAME, VIEW_DEFINITION AS SOURCE_TEXT
+            FROM INFORMATION_SCHEMA.VIEWS
+            WHERE TABLE_SCHEMA = ?
+            UNION ALL
+            SELECT FUNCTION_SCHEMA, 'FUNCTION', FUNCTION_NAME, FUNCTION_DEFINITION
+            FROM INFORMATION_SCHEMA.FUNCTIONS
+            WHERE FUNCTION_SCHEMA = ?
+            UNION ALL
+            SELECT PROCEDURE_SCHEMA, 'PROCEDURE', PROCEDURE_NAME, PROCEDURE_DEFINITION
+            FROM INFORMATION_SCHEMA.PROCEDURES
+            WHERE PROCEDURE_SCHEMA = ?
+        )
+        WHERE CONTAINS(LOWER(SOURCE_TEXT), LOWER(?))
+        ORDER BY OBJECT_TYPE, OBJECT_NAME
+        LIMIT {remaining}
+    "#
+    );
+    let schema = session.target_schema.as_str();
+    let response = execute(
+        session,
+        sql.as_str(),
+        &[schema, schema, schema, search_term],
+    )?;
+
+    // Definitions come back as one text value, so report the first matching line.
+    let needle_upper = search_term.to_ascii_uppercase();
+    visit_rows(session, response, |row| {
+        let mut values = row.into_iter();
+        let (Some(Some(schema)), Some(Some(object_type)), Some(Some(object_name)), Some(source)) =
+            (values.next(), values.next(), values.next(), values.next())
+        else {
+            return Ok(true);
+        };
+        if let Some((line, snippet)) = source
+            .as_deref()
+            .and_then(|source| find_matching_line(source, needle_upper.as_str()))
+        {
+            matches.push(DbSchemaSearchResult {
+                schema,
+                object_type,
+                object_name,
+                match_scope: "source".to_string(),
+                line: Some(line),
+                snippet: truncate_for_snippet(snippet.as_str()),
+                tags: Vec::new(),
+            });
+        }
+        Ok(true)
+    })
+}
+
+fn search_ddl_text(
+    session: &SnowflakeSession,
+    search_term: &str,
+    limit: usize,
+    matches: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    if matches.len() >= limit {
+        return Ok(());
+    }
+
+    // One GET_DDL call returns the whole schema as a script, instead of a round trip
+    // per object.
+    let qualified_schema = format!(
+        "{}.{}",
+        sql_ident::quote_identifier(DatabaseProvider::Snowflake, session.database.as_str()),
+        sql_ident::quote_identifier(DatabaseProvider::Snowflake, session.target_schema.as_str())
+    );
+    let script = query_text_rows(
+        session,
+        "SELECT GET_DDL('SCHEMA', ?)",
+        &[qualified_schema.as_str()],
+    )?
+    .into_iter()
+    .next()
+    .and_then(|row| row.into_iter().next())
+    .unwrap_or_default();
+
+    let needle_upper = search_term.to_ascii_uppercase();
+    for (object_type, object_name, ddl) in split_schema_ddl_script(script.as_str()) {
+        if matches.len() >= limit {
+            break;
+        }
+        if let Some((line, snippet)) = find_matching_line(ddl, needle_upper.as_str()) {
+            matches.push(DbSchemaSearchResult {
+                schema: session.target_schema.clone(),
+                object_type,
+                object_name,
+                match_scope: "ddl".to_string(),
+                line: Some(line),
+                snippet: truncate_for_snippet(snippet.as_str()),
+                tags: Vec::new(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn run_query(
+    session: &SnowflakeSession,
+    request: &DbQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let row_limit = effective_query_row_limit(request);
+    let mut response = execute(session, sql, &[])?;
+
+    if let Some(stats) = response.stats.take() {
+        let rows_affected =
+            stats.num_rows_inserted + stats.num_rows_updated + stats.num_rows_deleted;
+        return Ok(DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: Some(rows_affected),
+            message: format!("Statement executed. {rows_affected} row(s) affected."),
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
+    }
+
+    let columns = response_columns(&response);
+    // DDL and session commands answer with a single `status` cell.
+    if columns.len() == 1 && columns[0].name == "status" && response.data.len() == 1 {
+        let status = response.data[0][0].clone().unwrap_or_default();
+        return Ok(DbQueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            null_flags: Vec::new(),
+            byte_lengths: Vec::new(),
+            rows_affected: None,
+            message: if status.is_empty() {
+                "Statement executed.".to_string()
+            } else {
+                status
+            },
+            lossy_conversion: false,
+            cursor_id: None,
+            required_variables: Vec::new(),
+            ref_cursors: Vec::new(),
+        });
+    }
+
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    visit_rows(session, response, |row| {
+        if rows.len() >= row_limit {
+            truncated = true;
+            return Ok(false);
+        }
+        null_flags.push(row.iter().map(Option::is_none).collect());
+        rows.push(format_row(columns.as_slice(), row));
+        Ok(true)
+    })?;
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: columns.into_iter().map(|column| column.name).collect(),
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn run_filtered_query(
+    session: &SnowflakeSession,
+    request: &DbFilteredQueryRequest,
+) -> Result<DbQueryResult, String> {
+    let sql = request.sql.trim();
+    if sql.is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let query_request = DbQueryRequest {
+        session_id: request.session_id,
+        sql: request.sql.clone(),
+        row_limit: request.row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    };
+    let row_limit = effective_query_row_limit(&query_request);
+
+    let normalized_global_search = request
+        .global_search
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase();
+    let normalized_column_filters = request
+        .column_filters
+        .as_ref()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|value| value.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let response = execute(session, sql, &[])?;
+    let columns = response_columns(&response);
+    if columns.is_empty() || response.stats.is_some() {
+        return Err("Filtering is only available for query result sets.".to_string());
+    }
+
+    let mut rows = Vec::new();
+    let mut null_flags = Vec::new();
+    let mut truncated = false;
+    visit_rows(session, response, |row| {
+        let nulls = row.iter().map(Option::is_none).collect();
+        let values = format_row(columns.as_slice(), row);
+        if !row_matches_query_filters(
+            values.as_slice(),
+            normalized_global_search.as_str(),
+            normalized_column_filters.as_slice(),
+        ) {
+            return Ok(true);
+        }
+
+        rows.push(values);
+        null_flags.push(nulls);
+        if rows.len() >= row_limit {
+            truncated = true;
+            return Ok(false);
+        }
+        Ok(true)
+    })?;
+
+    let mut message = format!("Query executed. Returned {} row(s).", rows.len());
+    if truncated {
+        message.push_str(&format!(" Results truncated at {} rows.", row_limit));
+    }
+    let lossy_conversion = note_lossy_conversion(&rows, &mut message);
+
+    Ok(DbQueryResult {
+        columns: columns.into_iter().map(|column| column.name).collect(),
+        rows,
+        null_flags: null_flags_if_any(null_flags),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+pub(crate) fn stream_query_rows(
+    session: &SnowflakeSession,
+    sql: &str,
+    sink: &mut dyn ExportRowSink,
+) -> Result<u64, String> {
+    let response = execute(session, sql, &[])?;
+    let columns = response_columns(&response);
+    if columns.is_empty() || response.stats.is_some() {
+        return Err("Only queries can be exported".to_string());
+    }
+    sink.columns(
+        &columns
+            .iter()
+            .map(|column| column.name.clone())
+            .collect::<Vec<_>>(),
+    )?;
+
+    let mut row_count = 0_u64;
+    visit_rows(session, response, |row| {
+        let values = columns
+            .iter()
+            .zip(row)
+            .map(|(column, value)| export_value(column, value))
+            .collect::<Vec<_>>();
+        sink.row(&values)?;
+        row_count += 1;
+        Ok(true)
+    })?;
+
+    Ok(row_count)
+}
+
+/// Submits a statement and waits for it to finish. Statements that outlast the HTTP call
+/// come back as `202 Accepted` and are polled through their status URL.
+fn execute(
+    session: &SnowflakeSession,
+    sql: &str,
+    bindings: &[&str],
+) -> Result<StatementResponse, String> {
+    let bindings = bindings
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            (
+                (index + 1).to_string(),
+                serde_json::json!({ "type": "TEXT", "value": value }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    let mut payload = serde_json::json!({
+        "statement": sql,
+        "timeout": STATEMENT_TIMEOUT_SECONDS,
+        "database": session.database,
+        "schema": session.target_schema,
+        "bindings": bindings,
+    });
+    if let Some(warehouse) = &session.warehouse {
+        payload["warehouse"] = serde_json::json!(warehouse);
+    }
+    if let Some(role) = &session.role {
+        payload["role"] = serde_json::json!(role);
+    }
+
+    let response = authorized(
+        session,
+        session
+            .client
+            .post(format!("{}/api/v2/statements", session.base_url)),
+    )
+    .json(&payload)
+    .send()
+    .map_err(|error| format!("Snowflake request failed: {error}"))?;
+    let mut statement = read_statement_response(response)?;
+
+    let started = Instant::now();
+    while statement.result_set_meta_data.is_none() {
+        let Some(status_url) = statement.statement_status_url.clone() else {
+            break;
+        };
+        if started.elapsed() > Duration::from_secs(STATEMENT_TIMEOUT_SECONDS) {
+            return Err("Timed out waiting for the Snowflake statement to finish.".to_string());
+        }
+        std::thread::sleep(POLL_INTERVAL);
+        let response = authorized(
+            session,
+            session
+                .client
+                .get(format!("{}{status_url}", session.base_url)),
+        )
+        .send()
+        .map_err(|error| format!("Snowflake request failed: {error}"))?;
+        statement = read_statement_response(response)?;
+    }
+
+    Ok(statement)
+}
+
+fn authorized(
+    session: &SnowflakeSession,
+    request: reqwest::blocking::RequestBuilder,
+) -> reqwest::blocking::RequestBuilder {
+    let token_type = match session.token_type {
+        SnowflakeTokenType::ProgrammaticAccessToken => "PROGRAMMATIC_ACCESS_TOKEN",
+        SnowflakeTokenType::Oauth => "OAUTH",
+    };
+    request
+        .bearer_auth(session.token.as_str())
+        .header("X-Snowflake-Authorization-Token-Type", token_type)
+        .header("Accept", "application/json")
+}
+
+fn read_statement_response(response: Response) -> Result<StatementResponse, String> {
+    let status = response.status();
+    let body = response
+        .text()
+        .map_err(|error| format!("Failed to read Snowflake response: {error}"))?;
+    let parsed = serde_json::from_str::<StatementResponse>(body.as_str());
+
+    match status {
+        StatusCode::OK | StatusCode::ACCEPTED => {
+            parsed.map_err(|error| format!("Failed to parse Snowflake response: {error}"))
+        }
+        _ => {
+            let detail = parsed
+                .ok()
+                .and_then(|statement| statement.message)
+                .unwrap_or_else(|| body.trim().chars().take(350).collect());
+            Err(format!("Snowflake returned {status}: {detail}"))
+        }
+    }
+}
+
+/// Feeds rows to `visit` across all result partitions, fetching later partitions only
+/// while `visit` keeps returning `true`.
+fn visit_rows(
+    session: &SnowflakeSession,
+    response: StatementResponse,
+    mut visit: impl FnMut(Vec<Option<String>>) -> Result<bool, String>,
+) -> Result<(), String> {
+    let partition_count = response
+        .result_set_meta_data
+        .as_ref()
+        .map(|metadata| metadata.partition_info.len())
+        .unwrap_or(0);
+    for row in response.data {
+        if !visit(row)? {
+            return Ok(());
+        }
+    }
+
+    let Some(handle) = response.statement_handle else {
+        return Ok(());
+    };
+    for partition in 1..partition_count {
+        let response = authorized(
+            session,
+            session.client.get(format!(
+                "{}/api/v2/statements/{handle}?partition={partition}",
+                session.base_url
+            )),
+        )
+        .send()
+        .map_err(|error| format!("Snowflake request failed: {error}"))?;
+        for row in read_statement_response(response)?.data {
+            if !visit(row)? {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Metadata queries return plain text columns; NULLs become empty strings.
+fn query_text_rows(
+    session: &SnowflakeSession,
+    sql: &str,
+    bindings: &[&str],
+) -> Result<Vec<Vec<String>>, String> {
+    let response = execute(session, sql, bindings)?;
+    let mut rows = Vec::new();
+    visit_rows(session, response, |row| {
+        rows.push(row.into_iter().map(Option::unwrap_or_default).collect());
+        Ok(true)
+    })?;
+    Ok(rows)
+}
+
+fn response_columns(response: &StatementResponse) -> Vec<SnowflakeColumn> {
+    response
+        .result_set_meta_data
+        .as_ref()
+        .map(|metadata| metadata.row_type.clone())
+        .unwrap_or_default()
+}
+
+fn format_row(columns: &[SnowflakeColumn], row: Vec<Option<String>>) -> Vec<String> {
+    columns
+        .iter()
+        .zip(row)
+        .map(|(column, value)| match value {
+            Some(value) => format_value(column, value.as_str()),
+            None => NULL_DISPLAY.to_string(),
+        })
+        .collect()
+}
+
+/// Renders the SQL API's `jsonv2` encoding for display. Dates arrive as days since the
+/// epoch, times and timestamps as epoch seconds with a fraction, and TIMESTAMP_TZ with an
+/// extra offset field (minutes + 1440).
+fn format_value(column: &SnowflakeColumn, raw: &str) -> String {
+    parse_temporal(column, raw)
+        .map(|temporal| temporal.display)
+        .unwrap_or_else(|| raw.to_string())
+}
+
+fn export_value(column: &SnowflakeColumn, raw: Option<String>) -> ExportValue {
+    let Some(raw) = raw else {
+        return ExportValue::Null;
+    };
+    if matches!(column.type_name.as_str(), "fixed" | "real") {
+        return ExportValue::Number(raw);
+    }
+    match parse_temporal(column, raw.as_str()) {
+        Some(temporal) => ExportValue::DateTime(temporal),
+        None => ExportValue::Text(raw),
+    }
+}
+
+fn parse_temporal(column: &SnowflakeColumn, raw: &str) -> Option<ExportDateTime> {
+    let fraction_digits = column.scale.unwrap_or(9).min(9) as usize;
+    match column.type_name.as_str() {
+        "date" => {
+            let days = raw.trim().parse::<i64>().ok()?;
+            let (year, month, day) = civil_from_days(days);
+            Some(ExportDateTime {
+                year,
+                month,
+                day,
+                hour: 0,
+                minute: 0,
+                second: 0,
+                nanosecond: 0,
+                offset_seconds: None,
+                display: format!("{year:04}-{month:02}-{day:02}"),
+            })
+        }
+        "time" => {
+            let nanos = parse_epoch_nanos(raw)?;
+            let (_, hour, minute, second, nanosecond) = split_epoch_nanos(nanos);
+            Some(ExportDateTime {
+                year: 1970,
+                month: 1,
+                day: 1,
+                hour,
+                minute,
+                second,
+                nanosecond,
+                offset_seconds: None,
+                display: format!(
+                    "{hour:02}:{minute:02}:{second:02}{}",
+                    fraction_suffix(nanosecond, fraction_digits)
+                ),
+            })
+        }
+        "timestamp_ntz" | "timestamp_ltz" | "timestamp_tz" => {
+            let mut parts = raw.split_whitespace();
+            let mut nanos = parse_epoch_nanos(parts.next()?)?;
+            let offset_seconds = match (column.type_name.as_str(), parts.next()) {
+                ("timestamp_tz", Some(offset)) => Some((offset.parse::<i32>().ok()? - 1440) * 60),
+                // LTZ values are shown in UTC; the API does not report the session zone.
+                ("timestamp_ltz", _) => Some(0),
+                _ => None,
+            };
+            nanos += i128::from(offset_seconds.unwrap_or(0)) * 1_000_000_000;
+            let (days, hour, minute, second, nanosecond) = split_epoch_nanos(nanos);
+            let (year, month, day) = civil_from_days(days);
+            let mut display = format!(
+                "{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02}{}",
+                fraction_suffix(nanosecond, fraction_digits)
+            );
+            if let Some(offset_seconds) = offset_seconds {
+                let sign = if offset_seconds < 0 { '-' } else { '+' };
+                let offset_minutes = offset_seconds.abs() / 60;
+                display.push_str(&format!(
+                    " {sign}{:02}:{:02}",
+                    offset_minutes / 60,
+                    offset_minutes % 60
+                ));
+            }
+            Some(ExportDateTime {
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                nanosecond,
+                offset_seconds,
+                display,
+            })
+        }
+        _ => None,
+    }
+}
+
+// "1616173619.123000000" or "-5.5" to nanoseconds since the epoch.
+fn parse_epoch_nanos(raw: &str) -> Option<i128> {
+    let raw = raw.trim();
+    let (negative, unsigned) = match raw.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, raw),
+    };
+    let (seconds, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    if fraction.len() > 9 || !fraction.chars().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    let seconds = seconds.parse::<i128>().ok()?;
+    let fraction = format!("{fraction:0<9}").parse::<i128>().ok()?;
+    let nanos = seconds * 1_000_000_000 + fraction;
+    Some(if negative { -nanos } else { nanos })
+}
+
+fn split_epoch_nanos(nanos: i128) -> (i64, u32, u32, u32, u32) {
+    const NANOS_PER_DAY: i128 = 86_400 * 1_000_000_000;
+    let days = nanos.div_euclid(NANOS_PER_DAY) as i64;
+    let within_day = nanos.rem_euclid(NANOS_PER_DAY);
+    let seconds = (within_day / 1_000_000_000) as u32;
+    (
+        days,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        (within_day % 1_000_000_000) as u32,
+    )
+}
+
+// Days since 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+fn fraction_suffix(nanosecond: u32, digits: usize) -> String {
+    if digits == 0 {
+        return String::new();
+    }
+    format!(".{}", &format!("{nanosecond:09}")[..digits])
+}
+
+/// Splits a `GET_DDL('SCHEMA', ...)` script into `(object type, name, ddl)` per object.
+fn split_schema_ddl_script(script: &str) -> Vec<(String, String, &str)> {
+    let mut objects: Vec<(String, String, usize)> = Vec::new();
+    let mut offset = 0;
+    for line in script.split_inclusive('\n') {
+        let upper = line.trim_start().to_ascii_uppercase();
+        if let Some(rest) = upper.strip_prefix(DDL_SCRIPT_OBJECT_PREFIX) {
+            let mut words = rest
+                .split_whitespace()
+                .skip_while(|word| DDL_SCRIPT_MODIFIERS.contains(word));
+            let header = line.trim_start()[DDL_SCRIPT_OBJECT_PREFIX.len()..].to_string();
+            if let (Some(object_type), Some(_)) = (words.next(), words.next()) {
+                // The name keeps its original case; it is the word after the type.
+                let name = header
+                    .split_whitespace()
+                    .skip_while(|word| !word.eq_ignore_ascii_case(object_type))
+                    .nth(1)
+                    .unwrap_or_default()
+                    .split('(')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_matches('"')
+                    .to_string();
+                if object_type != "SCHEMA" && !name.is_empty() {
+                    objects.push((object_type.to_string(), name, offset));
+                }
+            }
+        }
+        offset += line.len();
+    }
+
+    let mut ends = objects
+        .iter()
+        .skip(1)
+        .map(|(_, _, start)| *start)
+        .collect::<Vec<_>>();
+    ends.push(script.len());
+    objects
+        .into_iter()
+        .zip(ends)
+        .map(|((object_type, name, start), end)| (object_type, name, &script[start..end]))
+        .collect()
+}
+
+pub(crate) fn account_base_url(account: &str) -> String {
+    let account = account.trim().trim_end_matches('/');
+    if account.contains("://") {
+        account.to_string()
+    } else if account
+        .to_ascii_lowercase()
+        .ends_with(".snowflakecomputing.com")
+    {
+        format!("https://{account}")
+    } else {
+        format!("https://{account}.snowflakecomputing.com")
+    }
+}
+
+fn qualified_object_name(session: &SnowflakeSession, object_name: &str) -> String {
+    [
+        session.database.as_str(),
+        session.target_schema.as_str(),
+        object_name,
+    ]
+    .iter()
+    .map(|part| sql_ident::quote_identifier(DatabaseProvider::Snowflake, part))
+    .collect::<Vec<_>>()
+    .join(".")
+}
+
+fn ensure_schema_is_in_scope(schema: &str, session: &SnowflakeSession) -> Result<(), String> {
+    if schema != session.target_schema {
+        return Err(format!(
+            "Connected schema is {}. Object access is limited to that schema.",
+            session.target_schema
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{account_base_url, format_value, split_schema_ddl_script, SnowflakeColumn};
+
+    fn column(type_name: &str, scale: Option<u32>) -> SnowflakeColumn {
+        SnowflakeColumn {
+            name: "C".to_string(),
+            type_name: type_name.to_string(),
+            scale,
+        }
+    }
+
+    #[test]
+    fn formats_jsonv2_temporal_values() {
+        assert_eq!(format_value(&column("date", None), "19723"), "2024-01-01");
+        assert_eq!(format_value(&column("date", None), "-1"), "1969-12-31");
+        assert_eq!(
+            format_value(&column("time", Some(3)), "45296.789000000"),
+            "12:34:56.789"
+        );
+        assert_eq!(
+            format_value(&column("timestamp_ntz", Some(0)), "1709253000.000000000"),
+            "2024-03-01 00:30:00"
+        );
+        assert_eq!(
+            format_value(
+                &column("timestamp_tz", Some(3)),
+                "1709253000.250000000 1560"
+            ),
+            "2024-03-01 02:30:00.250 +02:00"
+        );
+        assert_eq!(format_value(&column("fixed", Some(2)), "12.50"), "12.50");
+    }
+
+    #[test]
+    fn splits_schema_ddl_scripts_and_builds_account_urls() {
+        let script = "create or replace schema PUBLIC;\n\
+                      create or replace TRANSIENT TABLE ORDERS (\n\tID NUMBER(38,0),\n\tSTATUS VARCHAR\n);\n\
+                      create or replace view V_OPEN(ID) as select id from orders where status = 'OPEN';\n";
+        let objects = split_schema_ddl_script(script);
+        assert_eq!(objects.len(), 2);
+        assert_eq!(
+            (objects[0].0.as_str(), objects[0].1.as_str()),
+            ("TABLE", "ORDERS")
+        );
+        assert!(objects[0].2.contains("STATUS VARCHAR"));
+        assert_eq!(
+            (objects[1].0.as_str(), objects[1].1.as_str()),
+            ("VIEW", "V_OPEN")
+        );
+
+        assert_eq!(
+            account_base_url("myorg-acct"),
+            "https://myorg-acct.snowflakecomputing.com"
+        );
+        assert_eq!(
+            account_base_url("xy12345.eu-west-1.snowflakecomputing.com/"),
+            "https://xy12345.eu-west-1.snowflakecomputing.com"
+        );
+    }
+}
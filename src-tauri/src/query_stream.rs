@@ -0,0 +1,184 @@
+use crate::cancellation::CancelToken;
+use crate::data_export::{ExportRowSink, ExportValue};
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_safety;
+use crate::types::{DbQueryRowsEvent, DbQueryStreamSummary, DbStreamQueryRequest};
+
+const DEFAULT_BATCH_SIZE: u32 = 1_000;
+const MAX_BATCH_SIZE: u32 = 10_000;
+
+/// Runs a SELECT and hands its rows to `emit` in batches, followed by a summary, so a large
+/// result never travels as one payload. A failure after some rows were sent still ends
+/// with a summary carrying the error.
+pub(crate) fn stream_query(
+    session: &AppSession,
+    request: &DbStreamQueryRequest,
+    cancel: &CancelToken,
+    emit: &mut dyn FnMut(DbQueryRowsEvent),
+) -> Result<DbQueryStreamSummary, String> {
+    let sql = request.sql.trim();
+    if !sql_safety::is_select_query(sql) {
+        return Err("Only SELECT queries can be streamed.".to_string());
+    }
+    let batch_size = request
+        .batch_size
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+        .clamp(1, MAX_BATCH_SIZE) as usize;
+
+    let mut sink = BatchSink::new(request.stream_id.clone(), batch_size, emit);
+    let result = ProviderRegistry::stream_query_rows(session, sql, &mut sink, cancel);
+    sink.flush();
+    let summary = sink.summary(result);
+    (sink.emit)(DbQueryRowsEvent::Summary(summary.clone()));
+    Ok(summary)
+}
+
+struct BatchSink<'a> {
+    stream_id: String,
+    batch_size: usize,
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+    null_flags: Vec<Vec<bool>>,
+    has_nulls: bool,
+    row_count: u64,
+    batch_count: u64,
+    emit: &'a mut dyn FnMut(DbQueryRowsEvent),
+}
+
+impl<'a> BatchSink<'a> {
+    fn new(
+        stream_id: String,
+        batch_size: usize,
+        emit: &'a mut dyn FnMut(DbQueryRowsEvent),
+    ) -> Self {
+        Self {
+            stream_id,
+            batch_size,
+            columns: Vec::new(),
+            rows: Vec::with_capacity(batch_size),
+            null_flags: Vec::with_capacity(batch_size),
+            has_nulls: false,
+            row_count: 0,
+            batch_count: 0,
+            emit,
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let rows = std::mem::replace(&mut self.rows, Vec::with_capacity(self.batch_size));
+        let null_flags =
+            std::mem::replace(&mut self.null_flags, Vec::with_capacity(self.batch_size));
+        (self.emit)(DbQueryRowsEvent::Rows {
+            stream_id: self.stream_id.clone(),
+            batch_index: self.batch_count,
+            columns: (self.batch_count == 0).then(|| self.columns.clone()),
+            rows,
+            null_flags: if self.has_nulls {
+                null_flags
+            } else {
+                Vec::new()
+            },
+        });
+        self.has_nulls = false;
+        self.batch_count += 1;
+    }
+
+    fn summary(&self, result: Result<u64, String>) -> DbQueryStreamSummary {
+        let row_count = self.row_count;
+        let (message, error) = match result {
+            Ok(_) => (format!("{row_count} row(s) fetched."), None),
+            Err(error) => (format!("Stopped after {row_count} row(s)."), Some(error)),
+        };
+        DbQueryStreamSummary {
+            stream_id: self.stream_id.clone(),
+            columns: self.columns.clone(),
+            row_count,
+            batch_count: self.batch_count,
+            message,
+            error,
+        }
+    }
+}
+
+impl ExportRowSink for BatchSink<'_> {
+    fn columns(&mut self, columns: &[String]) -> Result<(), String> {
+        self.columns = columns.to_vec();
+        Ok(())
+    }
+
+    fn row(&mut self, values: &[ExportValue]) -> Result<(), String> {
+        let mut nulls = Vec::with_capacity(values.len());
+        let row = values
+            .iter()
+            .map(|value| {
+                nulls.push(matches!(value, ExportValue::Null));
+                match value {
+                    ExportValue::Null => String::new(),
+                    ExportValue::Text(text) | ExportValue::Number(text) => text.clone(),
+                    ExportValue::DateTime(date_time) => date_time.display.clone(),
+                }
+            })
+            .collect();
+        self.has_nulls |= nulls.contains(&true);
+        self.rows.push(row);
+        self.null_flags.push(nulls);
+        self.row_count += 1;
+        if self.rows.len() >= self.batch_size {
+            self.flush();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchSink;
+    use crate::data_export::{ExportRowSink, ExportValue};
+    use crate::types::DbQueryRowsEvent;
+
+    #[test]
+    fn sends_rows_in_batches_with_columns_first() {
+        let mut events = Vec::new();
+        let mut emit = |event| events.push(event);
+        let mut sink = BatchSink::new("s1".to_string(), 2, &mut emit);
+        sink.columns(&["ID".to_string(), "NAME".to_string()])
+            .unwrap();
+        for id in 1..=3 {
+            let name = if id == 2 {
+                ExportValue::Null
+            } else {
+                ExportValue::Text(format!("n{id}"))
+            };
+            sink.row(&[ExportValue::Number(id.to_string()), name])
+                .unwrap();
+        }
+        sink.flush();
+        let summary = sink.summary(Err("ORA-01013".to_string()));
+        assert_eq!(summary.batch_count, 2);
+        assert_eq!(summary.row_count, 3);
+        assert_eq!(summary.message, "Stopped after 3 row(s).");
+
+        let batches = events
+            .iter()
+            .map(|event| match event {
+                DbQueryRowsEvent::Rows {
+                    batch_index,
+                    columns,
+                    rows,
+                    null_flags,
+                    ..
+                } => (
+                    *batch_index,
+                    columns.is_some(),
+                    rows.len(),
+                    null_flags.len(),
+                ),
+                DbQueryRowsEvent::Summary(_) => panic!("unexpected summary"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![(0, true, 2, 2), (1, false, 1, 0)]);
+    }
+}
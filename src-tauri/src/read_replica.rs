@@ -0,0 +1,306 @@
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_safety;
+use crate::state::{lock_session, SharedSession};
+use crate::types::{DbConnectConnection, DbConnectRequest, DbSqlStatement};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Read replica connections of sessions opened with a reader endpoint. A query that only
+/// reads runs on the replica while nothing is pending on the primary connection; anything
+/// else stays on the primary. The replica is connected the first time a query is sent to it.
+#[derive(Default)]
+pub(crate) struct ReadReplicaRegistry {
+    replicas: Mutex<HashMap<u64, ReadReplica>>,
+}
+
+struct ReadReplica {
+    request: DbConnectRequest,
+    /// `host:port` shown in result messages.
+    endpoint: String,
+    connection: Option<SharedSession>,
+    /// Cursor of the last query that ran on the replica, paged through there until the
+    /// primary connection opens one.
+    cursor_id: Option<u64>,
+}
+
+/// Where a query ran, for its result message.
+pub(crate) enum Routing {
+    /// The session has no reader endpoint.
+    Primary,
+    /// The session has one, but the query ran on the primary for this reason.
+    KeptOnPrimary(String),
+    Replica(String),
+}
+
+impl Routing {
+    pub(crate) fn describe(&self) -> Option<String> {
+        match self {
+            Routing::Primary => None,
+            Routing::KeptOnPrimary(reason) => Some(format!("Ran on the primary: {reason}.")),
+            Routing::Replica(endpoint) => Some(format!("Ran on reader endpoint {endpoint}.")),
+        }
+    }
+}
+
+impl ReadReplicaRegistry {
+    /// Remembers how to reach the replica of a new session whose request has a reader
+    /// endpoint.
+    pub(crate) fn register(&self, session_id: u64, request: &DbConnectRequest) {
+        let Some((request, endpoint)) = replica_request(request) else {
+            return;
+        };
+        if let Ok(mut replicas) = self.replicas.lock() {
+            replicas.insert(
+                session_id,
+                ReadReplica {
+                    request,
+                    endpoint,
+                    connection: None,
+                    cursor_id: None,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn remove(&self, session_id: u64) {
+        if let Ok(mut replicas) = self.replicas.lock() {
+            replicas.remove(&session_id);
+        }
+    }
+
+    /// Closes the replica connection after the primary's session settings changed; the next
+    /// routed query reconnects and copies them again.
+    pub(crate) fn reset(&self, session_id: u64) {
+        if let Ok(mut replicas) = self.replicas.lock() {
+            if let Some(replica) = replicas.get_mut(&session_id) {
+                replica.connection = None;
+                replica.cursor_id = None;
+            }
+        }
+    }
+
+    /// Runs `run` on the replica when `statements` may go there, returning where it ran.
+    /// Without a result the caller runs the query on the primary: it must stay there, or the
+    /// replica could not be reached or failed the query and was dropped.
+    pub(crate) fn route<T>(
+        &self,
+        session_id: u64,
+        primary: &AppSession,
+        statements: &[DbSqlStatement],
+        run: impl FnOnce(&mut AppSession) -> Result<T, String>,
+    ) -> (Routing, Option<T>) {
+        let Some((request, endpoint, connection)) = self.lookup(session_id) else {
+            return (Routing::Primary, None);
+        };
+        let transaction_active = ProviderRegistry::transaction_active(primary).unwrap_or(true);
+        if let Some(reason) = primary_reason(statements, transaction_active) {
+            return (Routing::KeptOnPrimary(reason.to_string()), None);
+        }
+
+        let connection = match connection {
+            Some(connection) => connection,
+            None => match self.connect(session_id, primary, &request) {
+                Ok(connection) => connection,
+                Err(error) => {
+                    return (
+                        Routing::KeptOnPrimary(format!(
+                            "reader endpoint {endpoint} could not be reached ({error})"
+                        )),
+                        None,
+                    )
+                }
+            },
+        };
+        let result = match lock_session(&connection) {
+            Ok(mut replica) => run(&mut replica),
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(result) => (Routing::Replica(endpoint), Some(result)),
+            Err(error) => {
+                self.reset(session_id);
+                (
+                    Routing::KeptOnPrimary(format!("reader endpoint {endpoint} failed ({error})")),
+                    None,
+                )
+            }
+        }
+    }
+
+    /// Notes which connection holds the session's pageable cursor.
+    pub(crate) fn set_cursor(&self, session_id: u64, routing: &Routing, cursor_id: Option<u64>) {
+        if let Ok(mut replicas) = self.replicas.lock() {
+            if let Some(replica) = replicas.get_mut(&session_id) {
+                replica.cursor_id = match routing {
+                    Routing::Replica(_) => cursor_id,
+                    _ => None,
+                };
+            }
+        }
+    }
+
+    /// The replica connection when it holds `cursor_id`, for paging and closing.
+    pub(crate) fn cursor_connection(
+        &self,
+        session_id: u64,
+        cursor_id: u64,
+    ) -> Option<SharedSession> {
+        let replicas = self.replicas.lock().ok()?;
+        let replica = replicas.get(&session_id)?;
+        if replica.cursor_id != Some(cursor_id) {
+            return None;
+        }
+        replica.connection.clone()
+    }
+
+    fn lookup(&self, session_id: u64) -> Option<(DbConnectRequest, String, Option<SharedSession>)> {
+        let replicas = self.replicas.lock().ok()?;
+        let replica = replicas.get(&session_id)?;
+        Some((
+            replica.request.clone(),
+            replica.endpoint.clone(),
+            replica.connection.clone(),
+        ))
+    }
+
+    fn connect(
+        &self,
+        session_id: u64,
+        primary: &AppSession,
+        request: &DbConnectRequest,
+    ) -> Result<SharedSession, String> {
+        let (mut session, _, _) =
+            ProviderRegistry::connect(request).map_err(|error| error.into_message())?;
+        ProviderRegistry::restore_session_settings(primary, &mut session)?;
+        let connection = Arc::new(Mutex::new(session));
+        let mut replicas = self
+            .replicas
+            .lock()
+            .map_err(|_| "Failed to acquire read replica lock".to_string())?;
+        if let Some(replica) = replicas.get_mut(&session_id) {
+            replica.connection = Some(connection.clone());
+        }
+        Ok(connection)
+    }
+}
+
+/// Why statements must run on the primary connection, or `None` when the replica can run
+/// them.
+fn primary_reason(statements: &[DbSqlStatement], transaction_active: bool) -> Option<&'static str> {
+    match statements {
+        [statement] if !sql_safety::is_read_only_query(&statement.sql) => {
+            Some("the statement is not a read-only query")
+        }
+        [_] if transaction_active => {
+            Some("a transaction is open, and the replica would not see its changes")
+        }
+        [_] => None,
+        _ => Some("several statements run together"),
+    }
+}
+
+/// The session's request with the reader endpoint in place of the primary's address.
+fn replica_request(request: &DbConnectRequest) -> Option<(DbConnectRequest, String)> {
+    let reader = request
+        .reader_endpoint
+        .as_ref()
+        .filter(|reader| !reader.host.trim().is_empty())?;
+    let host = reader.host.trim().to_string();
+    let mut replica = request.clone();
+    replica.reader_endpoint = None;
+    // The standby connection belongs to the primary.
+    replica.profile_id = None;
+    let port = match &mut replica.connection {
+        DbConnectConnection::Oracle(connection) => {
+            connection.host = host.clone();
+            connection.port = reader.port.or(connection.port);
+            if let Some(service_name) = reader
+                .service_name
+                .as_deref()
+                .map(str::trim)
+                .filter(|service_name| !service_name.is_empty())
+            {
+                connection.service_name = service_name.to_string();
+            }
+            connection.failover_hosts.clear();
+            Some(
+                connection
+                    .port
+                    .unwrap_or(connection.protocol.default_port()),
+            )
+        }
+        DbConnectConnection::Postgres(connection)
+        | DbConnectConnection::Mysql(connection)
+        | DbConnectConnection::Clickhouse(connection) => {
+            connection.host = host.clone();
+            connection.port = reader.port.or(connection.port);
+            connection.port
+        }
+        _ => return None,
+    };
+    let endpoint = match port {
+        Some(port) => format!("{host}:{port}"),
+        None => host,
+    };
+    Some((replica, endpoint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{primary_reason, replica_request};
+    use crate::types::{
+        DbConnectConnection, DbConnectRequest, DbReaderEndpoint, DbSqlStatement,
+        NetworkConnectOptions,
+    };
+
+    fn statement(sql: &str) -> DbSqlStatement {
+        DbSqlStatement {
+            sql: sql.to_string(),
+            line: 1,
+        }
+    }
+
+    #[test]
+    fn only_single_read_only_queries_outside_a_transaction_go_to_the_replica() {
+        let select = statement("SELECT * FROM orders");
+        assert_eq!(primary_reason(std::slice::from_ref(&select), false), None);
+        assert!(primary_reason(std::slice::from_ref(&select), true).is_some());
+        assert!(primary_reason(&[statement("DELETE FROM orders WHERE id = 1")], false).is_some());
+        assert!(primary_reason(&[select.clone(), select], false).is_some());
+    }
+
+    #[test]
+    fn replica_request_points_at_the_reader_endpoint() {
+        let request: DbConnectRequest = serde_json::from_value(serde_json::json!({
+            "provider": "mysql",
+            "connection": {
+                "host": "db-primary",
+                "port": 3306,
+                "database": "shop",
+                "username": "app",
+                "password": "secret",
+            },
+            "profileId": "profile-1",
+        }))
+        .unwrap();
+        assert!(replica_request(&request).is_none());
+
+        let request = DbConnectRequest {
+            reader_endpoint: Some(DbReaderEndpoint {
+                host: " db-replica ".to_string(),
+                port: None,
+                service_name: None,
+            }),
+            ..request
+        };
+        let (replica, endpoint) = replica_request(&request).unwrap();
+        assert_eq!(endpoint, "db-replica:3306");
+        assert!(replica.reader_endpoint.is_none() && replica.profile_id.is_none());
+        let DbConnectConnection::Mysql(NetworkConnectOptions { host, password, .. }) =
+            replica.connection
+        else {
+            panic!("expected a MySQL request");
+        };
+        assert_eq!((host.as_str(), password.as_str()), ("db-replica", "secret"));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::providers::ProviderRegistry;
+use crate::state::{lock_session, SessionRegistry};
+use crate::types::{DbExportResultReportRequest, DbQueryResult, DbResultReport};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+const REPORT_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;margin:24px;color:#1f2328;background:#fff}\
+h1{font-size:20px;margin:0 0 12px}\
+h2{font-size:15px;margin:24px 0 8px}\
+dl{display:grid;grid-template-columns:max-content auto;gap:4px 16px;margin:0;font-size:13px}\
+dt{color:#59636e}dd{margin:0}\
+pre{background:#f6f8fa;border:1px solid #d1d9e0;border-radius:6px;padding:12px;overflow:auto;font-size:12px}\
+table{border-collapse:collapse;font-size:12px}\
+th,td{border:1px solid #d1d9e0;padding:4px 8px;text-align:left;vertical-align:top;white-space:pre-wrap}\
+th{background:#f6f8fa;position:sticky;top:0}\
+tr:nth-child(even) td{background:#fbfcfd}\
+td.null{color:#8c959f;font-style:italic}\
+p.note{color:#59636e;font-size:13px}";
+
+pub(crate) async fn export_result_report(
+    request: DbExportResultReportRequest,
+    sessions: SessionRegistry,
+) -> Result<DbResultReport, String> {
+    tauri::async_runtime::spawn_blocking(move || export_result_report_blocking(request, &sessions))
+        .await
+        .map_err(|error| format!("Report export task failed: {error}"))?
+}
+
+/// Writes a query's result, its SQL and run details as one HTML file with inline styles,
+/// so it can be attached to a ticket and opened anywhere.
+fn export_result_report_blocking(
+    request: DbExportResultReportRequest,
+    sessions: &SessionRegistry,
+) -> Result<DbResultReport, String> {
+    let file_path = request.file_path.trim();
+    if file_path.is_empty() {
+        return Err("Destination file is required".to_string());
+    }
+    let path = PathBuf::from(file_path);
+
+    let session = sessions.get(request.session_id)?;
+    let (provider, plan) = {
+        let mut session = lock_session(&session)?;
+        let plan = if request.include_plan {
+            Some(ProviderRegistry::explain_plan(&mut session, &request.sql))
+        } else {
+            None
+        };
+        (session.provider, plan)
+    };
+
+    let html = render_report(&request, provider.label(), plan.as_ref());
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!("Failed to create directory '{}': {error}", parent.display())
+        })?;
+    }
+    fs::write(&path, html)
+        .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+
+    let row_count = request.result.rows.len();
+    let plan_included = matches!(plan, Some(Ok(_)));
+    let message = match &plan {
+        Some(Err(error)) => format!(
+            "Saved report with {row_count} row(s) to {}; the plan could not be included: {error}",
+            path.display()
+        ),
+        _ => format!("Saved report with {row_count} row(s) to {}", path.display()),
+    };
+    Ok(DbResultReport {
+        file_path: path.to_string_lossy().into_owned(),
+        row_count,
+        plan_included,
+        message,
+    })
+}
+
+fn render_report(
+    request: &DbExportResultReportRequest,
+    provider_label: &str,
+    plan: Option<&Result<Vec<String>, String>>,
+) -> String {
+    let title = request
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("Query result");
+    let result = &request.result;
+
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{REPORT_STYLE}</style>\n</head>\n<body>\n<h1>{}</h1>\n<dl>\n",
+        escape_html(title),
+        escape_html(title)
+    );
+    let mut detail = |label: &str, value: &str| {
+        let _ = writeln!(
+            html,
+            "<dt>{}</dt><dd>{}</dd>",
+            escape_html(label),
+            escape_html(value)
+        );
+    };
+    if let Some(label) = request
+        .connection_label
+        .as_deref()
+        .filter(|label| !label.trim().is_empty())
+    {
+        detail("Connection", label);
+    }
+    detail("Provider", provider_label);
+    if let Some(executed_at) = request
+        .executed_at
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        detail("Executed", executed_at);
+    }
+    if let Some(elapsed) = request.elapsed_millis {
+        detail("Elapsed", &format!("{elapsed} ms"));
+    }
+    detail("Rows", &result.rows.len().to_string());
+    if !result.message.trim().is_empty() {
+        detail("Message", &result.message);
+    }
+    html.push_str("</dl>\n<h2>SQL</h2>\n");
+    let _ = writeln!(html, "<pre>{}</pre>", escape_html(request.sql.trim()));
+
+    html.push_str("<h2>Result</h2>\n");
+    if result.columns.is_empty() {
+        html.push_str("<p class=\"note\">The statement returned no columns.</p>\n");
+    } else {
+        html.push_str("<table>\n<thead><tr>");
+        for column in &result.columns {
+            let _ = write!(html, "<th>{}</th>", escape_html(column));
+        }
+        html.push_str("</tr></thead>\n<tbody>\n");
+        for (row_index, row) in result.rows.iter().enumerate() {
+            html.push_str("<tr>");
+            for (column_index, cell) in row.iter().enumerate() {
+                if is_null(result, row_index, column_index) {
+                    html.push_str("<td class=\"null\">NULL</td>");
+                } else {
+                    let _ = write!(html, "<td>{}</td>", escape_html(cell));
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    match plan {
+        Some(Ok(lines)) => {
+            html.push_str("<h2>Execution plan</h2>\n");
+            let _ = writeln!(html, "<pre>{}</pre>", escape_html(&lines.join("\n")));
+        }
+        Some(Err(error)) => {
+            html.push_str("<h2>Execution plan</h2>\n");
+            let _ = writeln!(
+                html,
+                "<p class=\"note\">Plan unavailable: {}</p>",
+                escape_html(error)
+            );
+        }
+        None => {}
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn is_null(result: &DbQueryResult, row_index: usize, column_index: usize) -> bool {
+    result
+        .null_flags
+        .get(row_index)
+        .and_then(|flags| flags.get(column_index))
+        .copied()
+        .unwrap_or(false)
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_escaped_cells_nulls_and_plan_errors() {
+        let request = DbExportResultReportRequest {
+            session_id: 1,
+            file_path: "report.html".to_string(),
+            title: None,
+            sql: "SELECT name, note FROM items WHERE id < 3".to_string(),
+            result: DbQueryResult {
+                columns: vec!["NAME".to_string(), "NOTE".to_string()],
+                rows: vec![
+                    vec!["<b>bold</b>".to_string(), String::new()],
+                    vec!["Tom & Jerry".to_string(), "ok".to_string()],
+                ],
+                null_flags: vec![vec![false, true], vec![false, false]],
+                byte_lengths: Vec::new(),
+                rows_affected: None,
+                message: String::new(),
+                lossy_conversion: false,
+                cursor_id: None,
+                required_variables: Vec::new(),
+                ref_cursors: Vec::new(),
+            },
+            connection_label: Some("Prod \"EU\"".to_string()),
+            elapsed_millis: Some(42),
+            executed_at: None,
+            include_plan: true,
+        };
+
+        let html = render_report(
+            &request,
+            "oracle",
+            Some(&Err("ORA-00942: table or view does not exist".to_string())),
+        );
+
+        assert!(html.contains("<title>Query result</title>"));
+        assert!(html.contains("<dd>Prod &quot;EU&quot;</dd>"));
+        assert!(html.contains("<dd>42 ms</dd>"));
+        assert!(html.contains("SELECT name, note FROM items WHERE id &lt; 3"));
+        assert!(html.contains("<td>&lt;b&gt;bold&lt;/b&gt;</td><td class=\"null\">NULL</td>"));
+        assert!(html.contains("<td>Tom &amp; Jerry</td><td>ok</td>"));
+        assert!(html.contains("Plan unavailable: ORA-00942"));
+        assert!(!html.contains("Executed"));
+    }
+}
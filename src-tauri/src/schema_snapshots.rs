@@ -0,0 +1,488 @@
+use crate::files::{
+    normalize_export_file_content, normalize_export_object_type_dir_name, sanitize_export_file_stem,
+};
+use crate::jobs::{start_job, JobRegistry, JobReporter};
+use crate::profiles::{self, app_data_file_path};
+use crate::providers::ProviderRegistry;
+use crate::types::{
+    DbConnectError, DbJobKind, DbJobStatus, DbObjectRef, DbSaveSchemaSnapshotScheduleRequest,
+    DbSchemaSnapshotSchedule,
+};
+use crate::workspace::now_millis;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tauri::AppHandle;
+
+const SCHEDULES_STORE_FILE: &str = "schema-snapshots.json";
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+const MAX_INTERVAL_HOURS: u32 = 24 * 31;
+const TICK: Duration = Duration::from_secs(60);
+const HOUR_MILLIS: u64 = 60 * 60 * 1000;
+
+static SCHEDULES_STORE_LOCK: Mutex<()> = Mutex::new(());
+/// Schedules with a snapshot in progress, so a slow export is not started twice.
+static RUNNING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct SnapshotCounts {
+    added: usize,
+    changed: usize,
+    unchanged: usize,
+    removed: usize,
+}
+
+/// Starts the background task that runs due snapshot schedules.
+pub(crate) fn spawn(app: AppHandle, jobs: Arc<JobRegistry>) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK);
+        let Ok(schedules) = list_schedules(&app) else {
+            continue;
+        };
+        let now = now_millis();
+        for schedule in schedules
+            .into_iter()
+            .filter(|schedule| is_due(schedule, now))
+        {
+            // Failures are recorded on the schedule and in the job list.
+            let _ = start_snapshot(&app, jobs.clone(), schedule);
+        }
+    });
+}
+
+pub(crate) fn list_schedules(app: &AppHandle) -> Result<Vec<DbSchemaSnapshotSchedule>, String> {
+    let _guard = lock_store()?;
+    read_schedules(app)
+}
+
+pub(crate) fn save_schedule(
+    app: &AppHandle,
+    request: DbSaveSchemaSnapshotScheduleRequest,
+) -> Result<DbSchemaSnapshotSchedule, String> {
+    let profile_id = request.profile_id.trim().to_string();
+    if !profiles::read_profiles(app)?
+        .iter()
+        .any(|profile| profile.id == profile_id)
+    {
+        return Err("Profile not found".to_string());
+    }
+    let destination_directory = request.destination_directory.trim().to_string();
+    if destination_directory.is_empty() {
+        return Err("Destination directory is required".to_string());
+    }
+    let interval_hours = request
+        .interval_hours
+        .unwrap_or(DEFAULT_INTERVAL_HOURS)
+        .clamp(1, MAX_INTERVAL_HOURS);
+
+    update_schedules(app, |schedules| {
+        let id = request
+            .id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("snapshot-{}", now_millis()));
+        let existing = schedules.iter().position(|schedule| schedule.id == id);
+        let previous = existing.map(|index| schedules[index].clone());
+        let schedule = DbSchemaSnapshotSchedule {
+            id,
+            profile_id,
+            destination_directory,
+            interval_hours,
+            git_commit: request.git_commit,
+            enabled: request.enabled,
+            last_run_at: previous.as_ref().and_then(|previous| previous.last_run_at),
+            last_error: previous
+                .as_ref()
+                .and_then(|previous| previous.last_error.clone()),
+            last_message: previous.and_then(|previous| previous.last_message),
+        };
+        match existing {
+            Some(index) => schedules[index] = schedule.clone(),
+            None => schedules.push(schedule.clone()),
+        }
+        Ok(schedule)
+    })
+}
+
+pub(crate) fn delete_schedule(app: &AppHandle, id: &str) -> Result<(), String> {
+    update_schedules(app, |schedules| {
+        let before = schedules.len();
+        schedules.retain(|schedule| schedule.id != id.trim());
+        if schedules.len() == before {
+            return Err("Snapshot schedule not found".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// Drops the schedules of a deleted profile.
+pub(crate) fn remove_profile_schedules(app: &AppHandle, profile_id: &str) -> Result<(), String> {
+    update_schedules(app, |schedules| {
+        schedules.retain(|schedule| schedule.profile_id != profile_id);
+        Ok(())
+    })
+}
+
+/// Runs a schedule now, regardless of when it is next due.
+pub(crate) fn run_schedule_now(
+    app: &AppHandle,
+    jobs: Arc<JobRegistry>,
+    id: &str,
+) -> Result<DbJobStatus, String> {
+    let schedule = list_schedules(app)?
+        .into_iter()
+        .find(|schedule| schedule.id == id.trim())
+        .ok_or_else(|| "Snapshot schedule not found".to_string())?;
+    start_snapshot(app, jobs, schedule)
+}
+
+fn start_snapshot(
+    app: &AppHandle,
+    jobs: Arc<JobRegistry>,
+    schedule: DbSchemaSnapshotSchedule,
+) -> Result<DbJobStatus, String> {
+    {
+        let mut running = RUNNING
+            .lock()
+            .map_err(|_| "Failed to acquire snapshot lock".to_string())?;
+        if running.contains(&schedule.id) {
+            return Err("A snapshot for this schedule is already running".to_string());
+        }
+        running.push(schedule.id.clone());
+    }
+    // Recorded up front so the scheduler does not pick the schedule again while it runs.
+    record_run(app, &schedule.id, Ok(None))?;
+
+    let label = format!("Schema snapshot to {}", schedule.destination_directory);
+    let job_app = app.clone();
+    start_job(
+        jobs,
+        app.clone(),
+        DbJobKind::SchemaSnapshot,
+        label,
+        move |reporter| {
+            let result = run_snapshot(&job_app, &schedule, reporter);
+            let _ = record_run(&job_app, &schedule.id, result.as_ref().map(Some));
+            if let Ok(mut running) = RUNNING.lock() {
+                running.retain(|id| *id != schedule.id);
+            }
+            result
+        },
+    )
+}
+
+fn run_snapshot(
+    app: &AppHandle,
+    schedule: &DbSchemaSnapshotSchedule,
+    reporter: &JobReporter,
+) -> Result<String, String> {
+    let profile = profiles::read_profiles(app)?
+        .into_iter()
+        .find(|profile| profile.id == schedule.profile_id)
+        .ok_or_else(|| "Profile not found".to_string())?;
+    let request = profiles::profile_connect_request(app, &profile)?;
+    let (session, _, _) = ProviderRegistry::connect(&request).map_err(|error| match error {
+        DbConnectError::OracleClientMissing { message } | DbConnectError::General { message } => {
+            message
+        }
+    })?;
+
+    let objects = ProviderRegistry::list_objects(&session)?;
+    let total = objects.len();
+    let mut files = Vec::with_capacity(total);
+    let mut used_paths = HashSet::new();
+    let mut warnings = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        reporter.cancel_token().check()?;
+        let object_ref = DbObjectRef {
+            session_id: 0,
+            schema: object.schema.clone(),
+            object_type: object.object_type.clone(),
+            object_name: object.object_name.clone(),
+        };
+        match ProviderRegistry::get_object_ddl(&session, &object_ref) {
+            Ok(ddl) => {
+                let path = unique_relative_path(
+                    &mut used_paths,
+                    &normalize_export_object_type_dir_name(&object.object_type),
+                    &sanitize_export_file_stem(&object.object_name),
+                );
+                files.push((path, normalize_export_file_content(&ddl)));
+            }
+            Err(error) => warnings.push(format!(
+                "{} {}.{}: {error}",
+                object.object_type, object.schema, object.object_name
+            )),
+        }
+        reporter.progress(
+            (index + 1) as u64,
+            &format!("{} of {total} object(s) read.", index + 1),
+        );
+    }
+    drop(session);
+
+    let destination = PathBuf::from(&schedule.destination_directory);
+    let counts = sync_snapshot_files(&destination, &files)?;
+    let mut message = format!(
+        "Snapshot of {}: {} added, {} changed, {} removed, {} unchanged.",
+        profile.name, counts.added, counts.changed, counts.removed, counts.unchanged
+    );
+    if schedule.git_commit {
+        let committed = git_commit(
+            &destination,
+            &format!("Schema snapshot of {}", profile.name),
+        )?;
+        message.push_str(if committed {
+            " Committed to git."
+        } else {
+            " Nothing to commit."
+        });
+    }
+    if let Some(first) = warnings.first() {
+        message.push_str(&format!(
+            " {} object(s) skipped; first: {first}",
+            warnings.len()
+        ));
+    }
+    Ok(message)
+}
+
+/// Writes only files whose content changed and removes `.sql` files of objects that no
+/// longer exist, so the directory (and its git history) reflects real DDL changes.
+fn sync_snapshot_files(
+    destination: &Path,
+    files: &[(PathBuf, String)],
+) -> Result<SnapshotCounts, String> {
+    fs::create_dir_all(destination)
+        .map_err(|error| format!("Failed to create snapshot directory: {error}"))?;
+    let mut counts = SnapshotCounts::default();
+    for (relative, content) in files {
+        let path = destination.join(relative);
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == *content => {
+                counts.unchanged += 1;
+                continue;
+            }
+            Ok(_) => counts.changed += 1,
+            Err(_) => counts.added += 1,
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| {
+                format!("Failed to create directory '{}': {error}", parent.display())
+            })?;
+        }
+        fs::write(&path, content)
+            .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+    }
+
+    let expected = files
+        .iter()
+        .map(|(relative, _)| destination.join(relative))
+        .collect::<HashSet<_>>();
+    let entries = fs::read_dir(destination)
+        .map_err(|error| format!("Failed to read snapshot directory: {error}"))?;
+    for directory in entries.flatten().map(|entry| entry.path()) {
+        let is_hidden = directory
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if !directory.is_dir() || is_hidden {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(&directory) else {
+            continue;
+        };
+        for file in files.flatten().map(|entry| entry.path()) {
+            let is_sql = file.extension().is_some_and(|extension| extension == "sql");
+            if is_sql && !expected.contains(&file) && fs::remove_file(&file).is_ok() {
+                counts.removed += 1;
+            }
+        }
+    }
+    Ok(counts)
+}
+
+/// Names differing only in case (`orders` and `ORDERS`) get separate files, so a snapshot
+/// also works on case-insensitive file systems.
+fn unique_relative_path(used: &mut HashSet<String>, directory: &str, stem: &str) -> PathBuf {
+    let mut candidate = Path::new(directory).join(format!("{stem}.sql"));
+    let mut index = 2;
+    while !used.insert(candidate.to_string_lossy().to_lowercase()) {
+        candidate = Path::new(directory).join(format!("{stem}_{index}.sql"));
+        index += 1;
+    }
+    candidate
+}
+
+/// Commits every change in `directory`, initializing a repository on first use. Returns
+/// whether anything was committed.
+fn git_commit(directory: &Path, message: &str) -> Result<bool, String> {
+    if !directory.join(".git").exists() {
+        run_git(directory, &["init"])?;
+    }
+    run_git(directory, &["add", "-A"])?;
+    if run_git(directory, &["status", "--porcelain"])?
+        .trim()
+        .is_empty()
+    {
+        return Ok(false);
+    }
+    run_git(directory, &["commit", "-m", message])?;
+    Ok(true)
+}
+
+fn run_git(directory: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .map_err(|error| format!("Failed to run git: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn is_due(schedule: &DbSchemaSnapshotSchedule, now: u64) -> bool {
+    schedule.enabled
+        && schedule.last_run_at.is_none_or(|last_run_at| {
+            now.saturating_sub(last_run_at) >= u64::from(schedule.interval_hours) * HOUR_MILLIS
+        })
+}
+
+/// `Ok(None)` marks the start of a run; a finished run stores its message or error.
+fn record_run(
+    app: &AppHandle,
+    id: &str,
+    result: Result<Option<&String>, &String>,
+) -> Result<(), String> {
+    update_schedules(app, |schedules| {
+        if let Some(schedule) = schedules.iter_mut().find(|schedule| schedule.id == id) {
+            match result {
+                Ok(None) => schedule.last_run_at = Some(now_millis()),
+                Ok(Some(message)) => {
+                    schedule.last_message = Some(message.clone());
+                    schedule.last_error = None;
+                }
+                Err(error) => schedule.last_error = Some(error.clone()),
+            }
+        }
+        Ok(())
+    })
+}
+
+fn update_schedules<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut Vec<DbSchemaSnapshotSchedule>) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = lock_store()?;
+    let mut schedules = read_schedules(app)?;
+    let value = f(&mut schedules)?;
+    let path = app_data_file_path(app, SCHEDULES_STORE_FILE)?;
+    let payload = serde_json::to_string_pretty(&schedules)
+        .map_err(|error| format!("Failed to serialize snapshot schedules: {error}"))?;
+    fs::write(path, payload)
+        .map_err(|error| format!("Failed to write snapshot schedules: {error}"))?;
+    Ok(value)
+}
+
+fn read_schedules(app: &AppHandle) -> Result<Vec<DbSchemaSnapshotSchedule>, String> {
+    let path = app_data_file_path(app, SCHEDULES_STORE_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read snapshot schedules: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&content)
+        .map_err(|error| format!("Failed to parse snapshot schedules: {error}"))
+}
+
+fn lock_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    SCHEDULES_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire snapshot schedule lock".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_due, sync_snapshot_files, SnapshotCounts, HOUR_MILLIS};
+    use crate::types::DbSchemaSnapshotSchedule;
+    use std::fs;
+    use std::path::PathBuf;
+
+    #[test]
+    fn rewrites_only_changed_files_and_removes_dropped_objects() {
+        let directory =
+            std::env::temp_dir().join(format!("clarity_schema_snapshot_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let file = |path: &str, content: &str| (PathBuf::from(path), content.to_string());
+
+        let first = vec![
+            file("table/ORDERS.sql", "CREATE TABLE orders (id NUMBER);\n"),
+            file(
+                "view/OLD_ORDERS.sql",
+                "CREATE VIEW old_orders AS SELECT 1;\n",
+            ),
+        ];
+        assert_eq!(
+            sync_snapshot_files(&directory, &first).expect("first run"),
+            SnapshotCounts {
+                added: 2,
+                ..SnapshotCounts::default()
+            }
+        );
+
+        let second = vec![
+            file(
+                "table/ORDERS.sql",
+                "CREATE TABLE orders (id NUMBER, note VARCHAR2(10));\n",
+            ),
+            file("table/ITEMS.sql", "CREATE TABLE items (id NUMBER);\n"),
+        ];
+        let counts = sync_snapshot_files(&directory, &second).expect("second run");
+        let _ = fs::remove_dir_all(&directory);
+        assert_eq!(
+            counts,
+            SnapshotCounts {
+                added: 1,
+                changed: 1,
+                unchanged: 0,
+                removed: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn schedules_are_due_after_their_interval() {
+        let mut schedule = DbSchemaSnapshotSchedule {
+            id: "snapshot-1".to_string(),
+            profile_id: "profile-1".to_string(),
+            destination_directory: "/tmp/ddl".to_string(),
+            interval_hours: 24,
+            git_commit: false,
+            enabled: true,
+            last_run_at: None,
+            last_error: None,
+            last_message: None,
+        };
+        assert!(is_due(&schedule, 0));
+        schedule.last_run_at = Some(1_000);
+        assert!(!is_due(&schedule, 1_000 + 23 * HOUR_MILLIS));
+        assert!(is_due(&schedule, 1_000 + 24 * HOUR_MILLIS));
+        schedule.enabled = false;
+        assert!(!is_due(&schedule, 1_000 + 48 * HOUR_MILLIS));
+    }
+}
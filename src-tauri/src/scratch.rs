@@ -0,0 +1,222 @@
+use crate::cancellation::CancelToken;
+use crate::providers::{AppSession, ProviderRegistry, ProviderSession};
+use crate::sql_ident;
+use crate::sql_safety;
+use crate::types::{
+    DatabaseProvider, DbCreateScratchTableRequest, DbQueryRequest, DbScratchTable,
+    DbScratchTableKind, DbScratchTableRef, DbSqlStatement,
+};
+use crate::workspace::now_millis;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const SCRATCH_TABLE_PREFIX: &str = "clarity_scratch_";
+
+/// Scratch tables created through the app, per session, so they can be dropped on disconnect.
+#[derive(Default)]
+pub(crate) struct ScratchTableRegistry {
+    entries: Mutex<HashMap<u64, Vec<DbScratchTable>>>,
+}
+
+impl ScratchTableRegistry {
+    pub(crate) fn list(&self, session_id: u64) -> Result<Vec<DbScratchTable>, String> {
+        let entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Failed to acquire scratch table lock".to_string())?;
+        Ok(entries.get(&session_id).cloned().unwrap_or_default())
+    }
+
+    fn find(&self, session_id: u64, table_name: &str) -> Result<Option<DbScratchTable>, String> {
+        Ok(self
+            .list(session_id)?
+            .into_iter()
+            .find(|table| table.table_name.eq_ignore_ascii_case(table_name)))
+    }
+
+    fn insert(&self, session_id: u64, table: DbScratchTable) -> Result<(), String> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| "Failed to acquire scratch table lock".to_string())?;
+        entries.entry(session_id).or_default().push(table);
+        Ok(())
+    }
+
+    fn remove(&self, session_id: u64, table_name: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(tables) = entries.get_mut(&session_id) {
+                tables.retain(|table| !table.table_name.eq_ignore_ascii_case(table_name));
+            }
+        }
+    }
+
+    fn take(&self, session_id: u64) -> Vec<DbScratchTable> {
+        self.entries
+            .lock()
+            .ok()
+            .and_then(|mut entries| entries.remove(&session_id))
+            .unwrap_or_default()
+    }
+}
+
+/// Creates a scratch table from `request.query`, using the provider's session-scoped table
+/// kind where there is one. On Oracle the `CREATE` is DDL and commits any open transaction.
+pub(crate) fn create_scratch_table(
+    session: &mut AppSession,
+    registry: &ScratchTableRegistry,
+    request: &DbCreateScratchTableRequest,
+) -> Result<DbScratchTable, String> {
+    let provider = session.provider;
+    let kind = scratch_table_kind(session)?;
+    let table_name = sql_ident::normalize_unquoted(
+        provider,
+        &format!(
+            "{SCRATCH_TABLE_PREFIX}{}",
+            request.name.trim().to_lowercase()
+        ),
+    );
+    if registry.find(request.session_id, &table_name)?.is_some() {
+        return Err(format!(
+            "Scratch table {table_name} already exists in this session"
+        ));
+    }
+
+    let identifier = sql_ident::format_identifier(provider, &table_name);
+    let query = request.query.trim().trim_end_matches(';');
+    let sql = match kind {
+        DbScratchTableKind::GlobalTemporary => {
+            format!("CREATE GLOBAL TEMPORARY TABLE {identifier} ON COMMIT PRESERVE ROWS AS {query}")
+        }
+        DbScratchTableKind::Temporary => format!("CREATE TEMPORARY TABLE {identifier} AS {query}"),
+        DbScratchTableKind::Prefixed if provider == DatabaseProvider::Snowflake => {
+            // Transient tables skip Fail-safe storage, which scratch data does not need.
+            format!("CREATE TRANSIENT TABLE {identifier} AS {query}")
+        }
+        DbScratchTableKind::Prefixed if provider == DatabaseProvider::Clickhouse => {
+            format!("CREATE TABLE {identifier} ENGINE = Memory AS {query}")
+        }
+        DbScratchTableKind::Prefixed => format!("CREATE TABLE {identifier} AS {query}"),
+    };
+    let statement = DbSqlStatement { sql, line: 1 };
+    sql_safety::check_production(
+        request.session_id,
+        session,
+        std::slice::from_ref(&statement),
+        request.production_confirmation.as_deref(),
+    )?;
+    run_statement(session, request.session_id, statement.sql)?;
+
+    let table = DbScratchTable {
+        table_name,
+        kind,
+        created_at: now_millis(),
+    };
+    registry.insert(request.session_id, table.clone())?;
+    Ok(table)
+}
+
+pub(crate) fn drop_scratch_table(
+    session: &mut AppSession,
+    registry: &ScratchTableRegistry,
+    request: &DbScratchTableRef,
+) -> Result<(), String> {
+    let table = registry
+        .find(request.session_id, request.table_name.trim())?
+        .ok_or_else(|| {
+            format!(
+                "{} is not a scratch table of this session",
+                request.table_name
+            )
+        })?;
+    drop_table(session, request.session_id, &table)?;
+    registry.remove(request.session_id, &table.table_name);
+    Ok(())
+}
+
+/// Best-effort cleanup before a session is closed; returns one message per table that could
+/// not be dropped.
+pub(crate) fn drop_all_scratch_tables(
+    session: &mut AppSession,
+    registry: &ScratchTableRegistry,
+    session_id: u64,
+) -> Vec<String> {
+    registry
+        .take(session_id)
+        .iter()
+        .filter_map(|table| {
+            drop_table(session, session_id, table)
+                .err()
+                .map(|error| format!("Could not drop {}: {error}", table.table_name))
+        })
+        .collect()
+}
+
+fn scratch_table_kind(session: &AppSession) -> Result<DbScratchTableKind, String> {
+    match &session.session {
+        ProviderSession::Oracle(_) => Ok(DbScratchTableKind::GlobalTemporary),
+        ProviderSession::Mysql(_) => Ok(DbScratchTableKind::Temporary),
+        ProviderSession::Duckdb(duckdb_session) if duckdb_session.has_database_file() => {
+            Ok(DbScratchTableKind::Prefixed)
+        }
+        // Every DuckDB statement runs in a fresh in-memory CLI process.
+        ProviderSession::Duckdb(_) => {
+            Err("Scratch tables need a DuckDB database file, not a Parquet or CSV file".to_string())
+        }
+        ProviderSession::Snowflake(_)
+        | ProviderSession::Clickhouse(_)
+        // Temporary tables would not outlive the one-shot libSQL stream that created them.
+        | ProviderSession::Libsql(_)
+        | ProviderSession::External(_) => Ok(DbScratchTableKind::Prefixed),
+    }
+}
+
+/// Only drops tables this session created through `create_scratch_table`, which passed the
+/// production guard then, so the drop is not guarded again; the guard would refuse it on a
+/// production session and leave the table behind when the session closes.
+fn drop_table(
+    session: &mut AppSession,
+    session_id: u64,
+    table: &DbScratchTable,
+) -> Result<(), String> {
+    let identifier = sql_ident::format_identifier(session.provider, &table.table_name);
+    match table.kind {
+        DbScratchTableKind::GlobalTemporary => {
+            // Oracle refuses to drop a temporary table the session still holds rows in.
+            run_statement(session, session_id, format!("TRUNCATE TABLE {identifier}"))?;
+            run_statement(
+                session,
+                session_id,
+                format!("DROP TABLE {identifier} PURGE"),
+            )
+        }
+        DbScratchTableKind::Temporary => run_statement(
+            session,
+            session_id,
+            format!("DROP TEMPORARY TABLE IF EXISTS {identifier}"),
+        ),
+        DbScratchTableKind::Prefixed => run_statement(
+            session,
+            session_id,
+            format!("DROP TABLE IF EXISTS {identifier}"),
+        ),
+    }
+}
+
+fn run_statement(session: &mut AppSession, session_id: u64, sql: String) -> Result<(), String> {
+    ProviderRegistry::run_query(
+        session,
+        &DbQueryRequest {
+            session_id,
+            sql,
+            row_limit: None,
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        },
+        &CancelToken::default(),
+    )
+    .map(|_| ())
+}
@@ -0,0 +1,551 @@
+use crate::cancellation::CancelToken;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_ident;
+use crate::sql_safety;
+use crate::types::{
+    DbDetectSensitiveColumnsRequest, DbObjectColumnEntry, DbQueryRequest, DbSensitiveColumnFinding,
+    DbSensitiveColumnsReport, DbSensitiveDataCategory, DbSensitiveDataConfidence, DbSqlStatement,
+};
+use std::collections::HashMap;
+
+const DEFAULT_SAMPLE_ROWS: u32 = 100;
+const MAX_SAMPLE_ROWS: u32 = 1000;
+
+/// Column-name tokens that only count as a hint when they are a whole word (`ssn` also
+/// appears inside `classname`).
+const NAME_TOKEN_HINTS: &[(&str, DbSensitiveDataCategory)] = &[
+    ("email", DbSensitiveDataCategory::Email),
+    ("mail", DbSensitiveDataCategory::Email),
+    ("phone", DbSensitiveDataCategory::Phone),
+    ("mobile", DbSensitiveDataCategory::Phone),
+    ("msisdn", DbSensitiveDataCategory::Phone),
+    ("fax", DbSensitiveDataCategory::Phone),
+    ("ssn", DbSensitiveDataCategory::NationalId),
+    ("sin", DbSensitiveDataCategory::NationalId),
+    ("nino", DbSensitiveDataCategory::NationalId),
+    ("tin", DbSensitiveDataCategory::NationalId),
+    ("passport", DbSensitiveDataCategory::NationalId),
+    ("pan", DbSensitiveDataCategory::CardNumber),
+    ("cvv", DbSensitiveDataCategory::CardNumber),
+    ("iban", DbSensitiveDataCategory::BankAccount),
+    ("bic", DbSensitiveDataCategory::BankAccount),
+    ("dob", DbSensitiveDataCategory::DateOfBirth),
+    ("birthday", DbSensitiveDataCategory::DateOfBirth),
+    ("surname", DbSensitiveDataCategory::PersonName),
+    ("street", DbSensitiveDataCategory::PostalAddress),
+    ("zip", DbSensitiveDataCategory::PostalAddress),
+];
+
+/// Substrings of the column name with separators removed, so `EMAIL_ADDRESS`,
+/// `emailAddress` and `email-address` all match.
+const NAME_SUBSTRING_HINTS: &[(&str, DbSensitiveDataCategory)] = &[
+    ("email", DbSensitiveDataCategory::Email),
+    ("phonenumber", DbSensitiveDataCategory::Phone),
+    ("telephone", DbSensitiveDataCategory::Phone),
+    ("socialsecurity", DbSensitiveDataCategory::NationalId),
+    ("nationalid", DbSensitiveDataCategory::NationalId),
+    ("nationalinsurance", DbSensitiveDataCategory::NationalId),
+    ("taxnumber", DbSensitiveDataCategory::NationalId),
+    ("taxidentification", DbSensitiveDataCategory::NationalId),
+    ("passportnumber", DbSensitiveDataCategory::NationalId),
+    ("driverslicense", DbSensitiveDataCategory::NationalId),
+    ("driverlicense", DbSensitiveDataCategory::NationalId),
+    ("creditcard", DbSensitiveDataCategory::CardNumber),
+    ("cardnumber", DbSensitiveDataCategory::CardNumber),
+    ("cardno", DbSensitiveDataCategory::CardNumber),
+    ("iban", DbSensitiveDataCategory::BankAccount),
+    ("bankaccount", DbSensitiveDataCategory::BankAccount),
+    ("dateofbirth", DbSensitiveDataCategory::DateOfBirth),
+    ("birthdate", DbSensitiveDataCategory::DateOfBirth),
+    ("firstname", DbSensitiveDataCategory::PersonName),
+    ("lastname", DbSensitiveDataCategory::PersonName),
+    ("fullname", DbSensitiveDataCategory::PersonName),
+    ("givenname", DbSensitiveDataCategory::PersonName),
+    ("familyname", DbSensitiveDataCategory::PersonName),
+    ("streetaddress", DbSensitiveDataCategory::PostalAddress),
+    ("postcode", DbSensitiveDataCategory::PostalAddress),
+    ("postalcode", DbSensitiveDataCategory::PostalAddress),
+    ("zipcode", DbSensitiveDataCategory::PostalAddress),
+];
+
+/// Categories that can be recognized from a value alone. Names, addresses and birth dates
+/// look like any other text or date, so they are only reported from column names.
+const VALUE_CATEGORIES: &[DbSensitiveDataCategory] = &[
+    DbSensitiveDataCategory::Email,
+    DbSensitiveDataCategory::Phone,
+    DbSensitiveDataCategory::NationalId,
+    DbSensitiveDataCategory::CardNumber,
+    DbSensitiveDataCategory::BankAccount,
+];
+
+/// Scans the session's tables and views for columns that look like personal data, from the
+/// column names and from a sample of each object's rows. Sampled values are only inspected
+/// locally and are not part of the report.
+pub(crate) fn detect_sensitive_columns(
+    session: &mut AppSession,
+    request: &DbDetectSensitiveColumnsRequest,
+) -> Result<DbSensitiveColumnsReport, String> {
+    let sample_rows = request
+        .sample_rows
+        .unwrap_or(DEFAULT_SAMPLE_ROWS)
+        .min(MAX_SAMPLE_ROWS);
+    let requested_objects = request
+        .object_names
+        .iter()
+        .map(|name| name.trim().to_uppercase())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut objects = ProviderRegistry::list_objects(session)?
+        .into_iter()
+        .filter(|object| matches!(object.object_type.as_str(), "TABLE" | "VIEW"))
+        .filter(|object| {
+            requested_objects.is_empty()
+                || requested_objects.contains(&object.object_name.to_uppercase())
+        })
+        .map(|object| ((object.schema, object.object_name), Vec::new()))
+        .collect::<Vec<(_, Vec<DbObjectColumnEntry>)>>();
+    let positions = objects
+        .iter()
+        .enumerate()
+        .map(|(index, (key, _))| (key.clone(), index))
+        .collect::<HashMap<_, _>>();
+    for column in ProviderRegistry::list_object_columns(session)? {
+        let key = (column.schema.clone(), column.object_name.clone());
+        if let Some(index) = positions.get(&key) {
+            objects[*index].1.push(column);
+        }
+    }
+
+    let mut report = DbSensitiveColumnsReport {
+        findings: Vec::new(),
+        scanned_objects: 0,
+        scanned_columns: 0,
+        warnings: Vec::new(),
+    };
+    for ((schema, object_name), columns) in objects {
+        if columns.is_empty() {
+            continue;
+        }
+        report.scanned_objects += 1;
+        report.scanned_columns += columns.len() as u32;
+
+        let samples = if sample_rows == 0 {
+            None
+        } else {
+            match sample_column_values(
+                session,
+                request.session_id,
+                &schema,
+                &object_name,
+                &columns,
+                sample_rows,
+            ) {
+                Ok(samples) => Some(samples),
+                Err(error) => {
+                    report
+                        .warnings
+                        .push(format!("Could not sample {schema}.{object_name}: {error}"));
+                    None
+                }
+            }
+        };
+
+        for (index, column) in columns.iter().enumerate() {
+            let values = samples
+                .as_ref()
+                .and_then(|samples| samples.get(index))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            report.findings.extend(classify_column(column, values));
+        }
+    }
+
+    report.findings.sort_by(|left, right| {
+        right
+            .confidence
+            .cmp(&left.confidence)
+            .then_with(|| left.schema.cmp(&right.schema))
+            .then_with(|| left.object_name.cmp(&right.object_name))
+            .then_with(|| left.column_name.cmp(&right.column_name))
+    });
+    Ok(report)
+}
+
+/// Non-NULL sampled values per column, in the order of `columns`.
+fn sample_column_values(
+    session: &mut AppSession,
+    session_id: u64,
+    schema: &str,
+    object_name: &str,
+    columns: &[DbObjectColumnEntry],
+    sample_rows: u32,
+) -> Result<Vec<Vec<String>>, String> {
+    let provider = session.provider;
+    let select_list = columns
+        .iter()
+        .map(|column| sql_ident::quote_identifier(provider, &column.column_name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        "SELECT {select_list} FROM {}",
+        sql_ident::qualified_name(provider, Some(schema), object_name)
+    );
+    let statement = DbSqlStatement { sql, line: 1 };
+    sql_safety::check_production(session_id, session, std::slice::from_ref(&statement), None)?;
+    let result = ProviderRegistry::run_query(
+        session,
+        &DbQueryRequest {
+            session_id,
+            sql: statement.sql,
+            row_limit: Some(sample_rows),
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        },
+        &CancelToken::default(),
+    )?;
+
+    let mut samples = vec![Vec::new(); columns.len()];
+    for row in result.rows {
+        for (index, value) in row.into_iter().enumerate().take(columns.len()) {
+            let value = value.trim();
+            if !value.is_empty() && value != "NULL" {
+                samples[index].push(value.to_string());
+            }
+        }
+    }
+    Ok(samples)
+}
+
+fn classify_column(
+    column: &DbObjectColumnEntry,
+    values: &[String],
+) -> Vec<DbSensitiveColumnFinding> {
+    let name_categories = column_name_categories(&column.column_name);
+    let mut categories = name_categories.clone();
+    for category in VALUE_CATEGORIES {
+        if !categories.contains(category) {
+            categories.push(*category);
+        }
+    }
+
+    categories
+        .into_iter()
+        .filter_map(|category| {
+            let matched_column_name = name_categories.contains(&category);
+            let matching_values = values
+                .iter()
+                .filter(|value| value_matches_category(category, value))
+                .count() as u32;
+            let confidence =
+                finding_confidence(matched_column_name, matching_values, values.len() as u32)?;
+            Some(DbSensitiveColumnFinding {
+                schema: column.schema.clone(),
+                object_name: column.object_name.clone(),
+                column_name: column.column_name.clone(),
+                data_type: column.data_type.clone(),
+                category,
+                confidence,
+                matched_column_name,
+                sampled_values: values.len() as u32,
+                matching_values,
+            })
+        })
+        .collect()
+}
+
+fn column_name_categories(column_name: &str) -> Vec<DbSensitiveDataCategory> {
+    let lowered = column_name.to_lowercase();
+    let compact = lowered
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>();
+    let tokens = lowered
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut categories = Vec::new();
+    let token_hits = NAME_TOKEN_HINTS
+        .iter()
+        .filter(|(hint, _)| tokens.contains(hint));
+    let substring_hits = NAME_SUBSTRING_HINTS
+        .iter()
+        .filter(|(hint, _)| compact.contains(hint));
+    for (_, category) in token_hits.chain(substring_hits) {
+        if !categories.contains(category) {
+            categories.push(*category);
+        }
+    }
+    categories
+}
+
+/// `None` when neither the name nor enough sampled values point at the category. A name hint
+/// alone is `medium`; values that mostly disagree with the name drop it to `low`.
+fn finding_confidence(
+    matched_column_name: bool,
+    matching_values: u32,
+    sampled_values: u32,
+) -> Option<DbSensitiveDataConfidence> {
+    let ratio = if sampled_values == 0 {
+        0.0
+    } else {
+        f64::from(matching_values) / f64::from(sampled_values)
+    };
+
+    match (matched_column_name, sampled_values) {
+        (true, 0) => Some(DbSensitiveDataConfidence::Medium),
+        (true, _) if ratio >= 0.3 => Some(DbSensitiveDataConfidence::High),
+        (true, _) if matching_values > 0 => Some(DbSensitiveDataConfidence::Medium),
+        (true, _) => Some(DbSensitiveDataConfidence::Low),
+        (false, _) if ratio >= 0.8 => Some(DbSensitiveDataConfidence::High),
+        (false, _) if ratio >= 0.3 => Some(DbSensitiveDataConfidence::Medium),
+        // A handful of matches in free text is still worth a look.
+        (false, _) if matching_values > 0 => Some(DbSensitiveDataConfidence::Low),
+        (false, _) => None,
+    }
+}
+
+/// Whole-value checks; a value that merely contains an e-mail address inside longer text does
+/// not count.
+fn value_matches_category(category: DbSensitiveDataCategory, value: &str) -> bool {
+    match category {
+        DbSensitiveDataCategory::Email => is_email(value),
+        DbSensitiveDataCategory::Phone => is_international_phone(value),
+        DbSensitiveDataCategory::NationalId => is_us_ssn(value) || is_uk_nino(value),
+        DbSensitiveDataCategory::CardNumber => is_card_number(value),
+        DbSensitiveDataCategory::BankAccount => is_iban(value),
+        DbSensitiveDataCategory::DateOfBirth
+        | DbSensitiveDataCategory::PersonName
+        | DbSensitiveDataCategory::PostalAddress => false,
+    }
+}
+
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    if local.is_empty()
+        || local.len() > 64
+        || !local
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '%' | '+' | '-'))
+    {
+        return false;
+    }
+
+    let labels = domain.split('.').collect::<Vec<_>>();
+    let Some(top_level) = labels.last() else {
+        return false;
+    };
+    labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '-')
+        })
+        && top_level.len() >= 2
+        && top_level.chars().all(|ch| ch.is_ascii_alphabetic())
+}
+
+/// E.164 style numbers (`+` and 8-15 digits) with the usual separators; national formats are
+/// too close to other identifiers to tell apart.
+fn is_international_phone(value: &str) -> bool {
+    let Some(rest) = value.strip_prefix('+') else {
+        return false;
+    };
+    let digits = rest.chars().filter(char::is_ascii_digit).count();
+    (8..=15).contains(&digits)
+        && rest
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || matches!(ch, ' ' | '-' | '(' | ')' | '.'))
+}
+
+/// US social security number as `AAA-GG-SSSS`, excluding the ranges that are never issued.
+fn is_us_ssn(value: &str) -> bool {
+    let parts = value.split('-').collect::<Vec<_>>();
+    let [area, group, serial] = parts.as_slice() else {
+        return false;
+    };
+    let all_digits =
+        |part: &str, len: usize| part.len() == len && part.chars().all(|ch| ch.is_ascii_digit());
+    all_digits(area, 3)
+        && all_digits(group, 2)
+        && all_digits(serial, 4)
+        && *area != "000"
+        && *area != "666"
+        && !area.starts_with('9')
+        && *group != "00"
+        && *serial != "0000"
+}
+
+/// UK National Insurance number: two letters, six digits and a suffix `A`-`D`.
+fn is_uk_nino(value: &str) -> bool {
+    let compact = value.chars().filter(|ch| *ch != ' ').collect::<Vec<_>>();
+    compact.len() == 9
+        && compact[..2].iter().all(char::is_ascii_uppercase)
+        && compact[2..8].iter().all(char::is_ascii_digit)
+        && matches!(compact[8], 'A'..='D')
+}
+
+/// 13-19 digit numbers (optionally grouped with spaces or dashes) that pass the Luhn check.
+fn is_card_number(value: &str) -> bool {
+    if !value
+        .chars()
+        .all(|ch| ch.is_ascii_digit() || matches!(ch, ' ' | '-'))
+    {
+        return false;
+    }
+    let digits = value
+        .chars()
+        .filter_map(|ch| ch.to_digit(10))
+        .collect::<Vec<_>>();
+    if !(13..=19).contains(&digits.len())
+        || !(2..=6).contains(&digits[0])
+        || digits.iter().all(|digit| *digit == digits[0])
+    {
+        return false;
+    }
+
+    let checksum = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                *digit
+            }
+        })
+        .sum::<u32>();
+    checksum % 10 == 0
+}
+
+/// IBAN with a valid ISO 7064 mod-97 check digit.
+fn is_iban(value: &str) -> bool {
+    let compact = value
+        .chars()
+        .filter(|ch| *ch != ' ')
+        .map(|ch| ch.to_ascii_uppercase())
+        .collect::<Vec<_>>();
+    if !(15..=34).contains(&compact.len())
+        || !compact[..2].iter().all(char::is_ascii_uppercase)
+        || !compact[2..4].iter().all(char::is_ascii_digit)
+        || !compact.iter().all(char::is_ascii_alphanumeric)
+    {
+        return false;
+    }
+
+    let mut remainder = 0u32;
+    for ch in compact[4..].iter().chain(&compact[..4]) {
+        let Some(value) = ch.to_digit(36) else {
+            return false;
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify_column, column_name_categories, value_matches_category};
+    use crate::types::{DbObjectColumnEntry, DbSensitiveDataCategory, DbSensitiveDataConfidence};
+
+    #[test]
+    fn recognizes_sensitive_values() {
+        let matches = |category, value| value_matches_category(category, value);
+
+        assert!(matches(
+            DbSensitiveDataCategory::Email,
+            "jane.doe+crm@example.co.uk"
+        ));
+        assert!(!matches(
+            DbSensitiveDataCategory::Email,
+            "contact jane@example.com"
+        ));
+        assert!(matches(
+            DbSensitiveDataCategory::CardNumber,
+            "4111 1111 1111 1111"
+        ));
+        assert!(!matches(
+            DbSensitiveDataCategory::CardNumber,
+            "4111 1111 1111 1112"
+        ));
+        assert!(!matches(
+            DbSensitiveDataCategory::CardNumber,
+            "1000000000000007"
+        ));
+        assert!(matches(DbSensitiveDataCategory::NationalId, "123-45-6789"));
+        assert!(!matches(DbSensitiveDataCategory::NationalId, "666-45-6789"));
+        assert!(matches(
+            DbSensitiveDataCategory::NationalId,
+            "QQ 12 34 56 C"
+        ));
+        assert!(matches(
+            DbSensitiveDataCategory::BankAccount,
+            "GB82 WEST 1234 5698 7654 32"
+        ));
+        assert!(!matches(
+            DbSensitiveDataCategory::BankAccount,
+            "GB83 WEST 1234 5698 7654 32"
+        ));
+        assert!(matches(DbSensitiveDataCategory::Phone, "+44 20 7946 0958"));
+        assert!(!matches(DbSensitiveDataCategory::Phone, "2024-03-01"));
+    }
+
+    #[test]
+    fn combines_column_names_with_sampled_values() {
+        assert_eq!(
+            column_name_categories("CUSTOMER_EMAIL_ADDRESS"),
+            vec![DbSensitiveDataCategory::Email]
+        );
+        assert_eq!(
+            column_name_categories("dateOfBirth"),
+            vec![DbSensitiveDataCategory::DateOfBirth]
+        );
+        assert!(column_name_categories("CLASSNAME").is_empty());
+
+        let column = |name: &str| DbObjectColumnEntry {
+            schema: "APP".to_string(),
+            object_name: "CUSTOMERS".to_string(),
+            column_name: name.to_string(),
+            data_type: "VARCHAR2".to_string(),
+            nullable: "Y".to_string(),
+        };
+        let values = |items: &[&str]| {
+            items
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let findings = classify_column(
+            &column("NOTES"),
+            &values(&["4111111111111111", "5500 0000 0000 0004", "n/a"]),
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].category, DbSensitiveDataCategory::CardNumber);
+        assert_eq!(findings[0].confidence, DbSensitiveDataConfidence::Medium);
+        assert_eq!(findings[0].matching_values, 2);
+
+        let findings = classify_column(&column("EMAIL"), &values(&["unknown", "none"]));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].confidence, DbSensitiveDataConfidence::Low);
+
+        assert!(classify_column(&column("STATUS"), &values(&["OPEN", "CLOSED"])).is_empty());
+    }
+}
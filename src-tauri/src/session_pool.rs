@@ -0,0 +1,200 @@
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::state::SharedSession;
+use crate::types::{DatabaseProvider, DbConnectRequest};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+pub(crate) const DEFAULT_POOL_SIZE: u32 = 2;
+pub(crate) const MAX_POOL_SIZE: u32 = 8;
+
+/// Extra connections opened next to each session's primary connection, so explorer and
+/// schema search reads do not wait behind a long-running query. Queries, scripts and
+/// transactions always stay on the primary connection.
+#[derive(Default)]
+pub(crate) struct SessionPoolRegistry {
+    pools: Mutex<HashMap<u64, SessionPool>>,
+}
+
+struct SessionPool {
+    request: DbConnectRequest,
+    /// Total connections including the primary one.
+    size: u32,
+    connections: Vec<SharedSession>,
+    opening: u32,
+}
+
+/// Pool usage of one session. `size` and `open` count the primary connection; `in_use`
+/// counts pooled connections running a read.
+pub(crate) struct PoolUsage {
+    pub(crate) size: u32,
+    pub(crate) open: u32,
+    pub(crate) in_use: u32,
+}
+
+impl SessionPoolRegistry {
+    /// Remembers how to open extra connections for a new session. Connections are opened
+    /// lazily, the first time every existing one is busy.
+    pub(crate) fn register(&self, session_id: u64, request: &DbConnectRequest) {
+        let size = pool_size(request.provider(), request.pool_size);
+        if size <= 1 {
+            return;
+        }
+        if let Ok(mut pools) = self.pools.lock() {
+            pools.insert(
+                session_id,
+                SessionPool {
+                    request: request.clone(),
+                    size,
+                    connections: Vec::new(),
+                    opening: 0,
+                },
+            );
+        }
+    }
+
+    pub(crate) fn remove(&self, session_id: u64) {
+        if let Ok(mut pools) = self.pools.lock() {
+            pools.remove(&session_id);
+        }
+    }
+
+    /// Drops the pooled connections after the primary's session settings changed; the next
+    /// reads open new ones with the new settings. Reads still running finish first.
+    pub(crate) fn reset(&self, session_id: u64) {
+        if let Ok(mut pools) = self.pools.lock() {
+            if let Some(pool) = pools.get_mut(&session_id) {
+                pool.connections.clear();
+            }
+        }
+    }
+
+    /// Runs a read on an idle pooled connection. `None` means the caller should use
+    /// `primary`: the session has no pool, every connection is busy, or the pooled connection
+    /// was lost (it is then discarded, and the read is repeated on the primary). Other errors
+    /// are the read's own and are returned as they are.
+    pub(crate) fn with_connection<T>(
+        &self,
+        session_id: u64,
+        primary: &SharedSession,
+        f: &impl Fn(&AppSession) -> Result<T, String>,
+    ) -> Option<Result<T, String>> {
+        let connection = self.checkout(session_id, primary)?;
+        let (result, provider) = {
+            let session = connection.try_lock().ok()?;
+            (f(&session), session.provider)
+        };
+        if let Err(error) = &result {
+            if ProviderRegistry::is_connection_lost(provider, error) {
+                self.discard(session_id, &connection);
+                return None;
+            }
+        }
+        Some(result)
+    }
+
+    pub(crate) fn usage(&self, session_id: u64, provider: DatabaseProvider) -> PoolUsage {
+        let pools = self.pools.lock().ok();
+        match pools.as_ref().and_then(|pools| pools.get(&session_id)) {
+            Some(pool) => PoolUsage {
+                size: pool.size,
+                open: 1 + pool.connections.len() as u32,
+                in_use: pool
+                    .connections
+                    .iter()
+                    .filter(|connection| connection.try_lock().is_err())
+                    .count() as u32,
+            },
+            None => PoolUsage {
+                size: pool_size(provider, Some(1)),
+                open: 1,
+                in_use: 0,
+            },
+        }
+    }
+
+    fn checkout(&self, session_id: u64, primary: &SharedSession) -> Option<SharedSession> {
+        let request = {
+            let mut pools = self.pools.lock().ok()?;
+            let pool = pools.get_mut(&session_id)?;
+            if let Some(idle) = pool
+                .connections
+                .iter()
+                .find(|connection| connection.try_lock().is_ok())
+            {
+                return Some(idle.clone());
+            }
+            if 1 + pool.connections.len() as u32 + pool.opening >= pool.size {
+                return None;
+            }
+            pool.opening += 1;
+            pool.request.clone()
+        };
+
+        // Connecting happens outside the lock; other reads fall back to the primary meanwhile.
+        let opened = ProviderRegistry::connect(&request)
+            .ok()
+            .and_then(|(mut session, _, _)| {
+                self.restore_settings(session_id, primary, &mut session)?;
+                Some(Arc::new(Mutex::new(session)))
+            });
+        let mut pools = self.pools.lock().ok()?;
+        let pool = pools.get_mut(&session_id)?;
+        pool.opening -= 1;
+        let opened = opened?;
+        pool.connections.push(opened.clone());
+        Some(opened)
+    }
+
+    /// Gives a new connection the settings changed on the primary since it connected, such
+    /// as NLS formats or the container. They are read from the primary, or from an idle
+    /// pooled connection (which got them the same way) while the primary is busy; when
+    /// neither is free the connection is not used.
+    fn restore_settings(
+        &self,
+        session_id: u64,
+        primary: &SharedSession,
+        session: &mut AppSession,
+    ) -> Option<()> {
+        let peers = {
+            let pools = self.pools.lock().ok()?;
+            pools.get(&session_id)?.connections.clone()
+        };
+        let source = std::iter::once(primary)
+            .chain(&peers)
+            .find_map(|candidate| candidate.try_lock().ok())?;
+        ProviderRegistry::restore_session_settings(&source, session).ok()
+    }
+
+    fn discard(&self, session_id: u64, connection: &SharedSession) {
+        if let Ok(mut pools) = self.pools.lock() {
+            if let Some(pool) = pools.get_mut(&session_id) {
+                pool.connections
+                    .retain(|candidate| !Arc::ptr_eq(candidate, connection));
+            }
+        }
+    }
+}
+
+/// External adapters run one process per connection, so they are not pooled.
+fn pool_size(provider: DatabaseProvider, requested: Option<u32>) -> u32 {
+    if provider == DatabaseProvider::External {
+        return 1;
+    }
+    requested
+        .unwrap_or(DEFAULT_POOL_SIZE)
+        .clamp(1, MAX_POOL_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pool_size, DEFAULT_POOL_SIZE, MAX_POOL_SIZE};
+    use crate::types::DatabaseProvider;
+
+    #[test]
+    fn pool_size_is_clamped_and_external_adapters_are_not_pooled() {
+        assert_eq!(pool_size(DatabaseProvider::Oracle, None), DEFAULT_POOL_SIZE);
+        assert_eq!(pool_size(DatabaseProvider::Postgres, Some(0)), 1);
+        assert_eq!(pool_size(DatabaseProvider::Mysql, Some(50)), MAX_POOL_SIZE);
+        assert_eq!(pool_size(DatabaseProvider::External, Some(4)), 1);
+    }
+}
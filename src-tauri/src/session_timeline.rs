@@ -0,0 +1,201 @@
+use crate::types::{
+    DbExportSessionTimelineRequest, DbSessionTimeline, DbSessionTimelineEvent,
+    DbSessionTimelineEventKind, DbSessionTimelineExport,
+};
+use crate::workspace::now_millis;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const MAX_TIMELINE_EVENTS: usize = 2_000;
+
+/// What was done on each session, in order, kept in memory for the life of the app.
+/// Timelines outlive their sessions so they can still be read and exported after a
+/// disconnect.
+#[derive(Default)]
+pub(crate) struct SessionTimelineRegistry {
+    timelines: Mutex<HashMap<u64, SessionTimeline>>,
+}
+
+#[derive(Default)]
+struct SessionTimeline {
+    label: String,
+    dropped_events: u64,
+    events: VecDeque<DbSessionTimelineEvent>,
+}
+
+impl SessionTimelineRegistry {
+    /// Starts a session's timeline with its connect event.
+    pub(crate) fn start(&self, session_id: u64, label: &str) {
+        if let Ok(mut timelines) = self.timelines.lock() {
+            timelines.entry(session_id).or_default().label = label.to_string();
+        }
+        self.record(
+            session_id,
+            event(
+                DbSessionTimelineEventKind::Connect,
+                format!("Connected to {label}"),
+            ),
+        );
+    }
+
+    pub(crate) fn record(&self, session_id: u64, event: DbSessionTimelineEvent) {
+        let Ok(mut timelines) = self.timelines.lock() else {
+            return;
+        };
+        let timeline = timelines.entry(session_id).or_default();
+        if timeline.events.len() >= MAX_TIMELINE_EVENTS {
+            timeline.events.pop_front();
+            timeline.dropped_events += 1;
+        }
+        timeline.events.push_back(event);
+    }
+
+    pub(crate) fn get(&self, session_id: u64) -> Result<DbSessionTimeline, String> {
+        let timelines = self
+            .timelines
+            .lock()
+            .map_err(|_| "Failed to acquire session timeline lock".to_string())?;
+        let timeline = timelines
+            .get(&session_id)
+            .ok_or_else(|| "No timeline was recorded for this session".to_string())?;
+        Ok(DbSessionTimeline {
+            session_id,
+            label: timeline.label.clone(),
+            dropped_events: timeline.dropped_events,
+            events: timeline.events.iter().cloned().collect(),
+        })
+    }
+
+    /// Every timeline with at most its last `limit` events, in session order.
+    pub(crate) fn recent(&self, limit: usize) -> Vec<DbSessionTimeline> {
+        let Ok(timelines) = self.timelines.lock() else {
+            return Vec::new();
+        };
+        let mut recent = timelines
+            .iter()
+            .map(|(session_id, timeline)| {
+                let skip = timeline.events.len().saturating_sub(limit);
+                DbSessionTimeline {
+                    session_id: *session_id,
+                    label: timeline.label.clone(),
+                    dropped_events: timeline.dropped_events + skip as u64,
+                    events: timeline.events.iter().skip(skip).cloned().collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        recent.sort_by_key(|timeline| timeline.session_id);
+        recent
+    }
+
+    /// Writes the timeline as JSON so it can be attached to a postmortem.
+    pub(crate) fn export(
+        &self,
+        request: &DbExportSessionTimelineRequest,
+    ) -> Result<DbSessionTimelineExport, String> {
+        let file_path = request.file_path.trim();
+        if file_path.is_empty() {
+            return Err("Destination file is required".to_string());
+        }
+        let path = PathBuf::from(file_path);
+        let timeline = self.get(request.session_id)?;
+
+        let payload = serde_json::to_string_pretty(&timeline)
+            .map_err(|error| format!("Failed to serialize session timeline: {error}"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|error| {
+                format!("Failed to create directory '{}': {error}", parent.display())
+            })?;
+        }
+        fs::write(&path, payload)
+            .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+
+        let event_count = timeline.events.len();
+        Ok(DbSessionTimelineExport {
+            file_path: path.to_string_lossy().into_owned(),
+            event_count,
+            message: format!(
+                "Saved {event_count} timeline event(s) to {}",
+                path.display()
+            ),
+        })
+    }
+}
+
+pub(crate) fn event(kind: DbSessionTimelineEventKind, summary: String) -> DbSessionTimelineEvent {
+    DbSessionTimelineEvent {
+        at: now_millis(),
+        kind,
+        summary,
+        sql: None,
+        succeeded: true,
+        error: None,
+        elapsed_millis: None,
+    }
+}
+
+/// An event for work that produced `outcome`; `message` describes a success.
+pub(crate) fn outcome_event<T>(
+    kind: DbSessionTimelineEventKind,
+    outcome: &Result<T, String>,
+    message: impl FnOnce(&T) -> String,
+) -> DbSessionTimelineEvent {
+    match outcome {
+        Ok(value) => event(kind, message(value)),
+        Err(error) => DbSessionTimelineEvent {
+            succeeded: false,
+            error: Some(error.clone()),
+            ..event(kind, "Failed".to_string())
+        },
+    }
+}
+
+/// Like [`outcome_event`], also keeping the SQL that ran and how long it took.
+pub(crate) fn sql_event<T>(
+    kind: DbSessionTimelineEventKind,
+    sql: &str,
+    outcome: &Result<T, String>,
+    message: impl FnOnce(&T) -> String,
+    elapsed: Duration,
+) -> DbSessionTimelineEvent {
+    let mut event = outcome_event(kind, outcome, message);
+    event.sql = Some(sql.trim().to_string()).filter(|sql| !sql.is_empty());
+    event.elapsed_millis = Some(elapsed.as_millis() as u64);
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_events_in_order_and_drops_the_oldest_past_the_limit() {
+        let registry = SessionTimelineRegistry::default();
+        registry.start(7, "HR@prod");
+        for index in 0..MAX_TIMELINE_EVENTS {
+            registry.record(
+                7,
+                sql_event(
+                    DbSessionTimelineEventKind::Statement,
+                    &format!(" SELECT {index} FROM dual "),
+                    &Err::<(), _>("ORA-00942".to_string()),
+                    |_| String::new(),
+                    Duration::from_millis(3),
+                ),
+            );
+        }
+
+        let timeline = registry.get(7).unwrap();
+        assert_eq!(timeline.label, "HR@prod");
+        assert_eq!(timeline.dropped_events, 1);
+        assert_eq!(timeline.events.len(), MAX_TIMELINE_EVENTS);
+        let first = &timeline.events[0];
+        assert_eq!(first.sql.as_deref(), Some("SELECT 0 FROM dual"));
+        assert!(!first.succeeded);
+        assert_eq!(first.error.as_deref(), Some("ORA-00942"));
+        assert_eq!(first.elapsed_millis, Some(3));
+        assert!(registry.get(8).is_err());
+    }
+}
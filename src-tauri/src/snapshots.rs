@@ -0,0 +1,252 @@
+use crate::profiles::app_data_file_path;
+use crate::types::{DbResultSnapshot, DbResultSnapshotSummary, DbSaveResultSnapshotRequest};
+use crate::workspace::now_millis;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+// Kept apart from workspace.json, which is rewritten on every editor save.
+const RESULT_SNAPSHOT_STORE_FILE: &str = "result_snapshots.json";
+const MAX_SNAPSHOT_CELLS: usize = 500_000;
+
+static RESULT_SNAPSHOT_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct ResultSnapshotStore {
+    snapshots: Vec<DbResultSnapshot>,
+}
+
+pub(crate) fn save_result_snapshot(
+    app: &AppHandle,
+    request: DbSaveResultSnapshotRequest,
+) -> Result<DbResultSnapshotSummary, String> {
+    update_store(app, |store| {
+        let snapshot = insert_snapshot(store, request, now_millis())?;
+        Ok(summarize(snapshot))
+    })
+}
+
+/// Newest first.
+pub(crate) fn list_result_snapshots(
+    app: &AppHandle,
+) -> Result<Vec<DbResultSnapshotSummary>, String> {
+    let store = read_store(app)?;
+    Ok(store.snapshots.iter().rev().map(summarize).collect())
+}
+
+pub(crate) fn get_result_snapshot(
+    app: &AppHandle,
+    snapshot_id: &str,
+) -> Result<DbResultSnapshot, String> {
+    let snapshot_id = snapshot_id.trim();
+    read_store(app)?
+        .snapshots
+        .into_iter()
+        .find(|snapshot| snapshot.id == snapshot_id)
+        .ok_or_else(|| "Result snapshot not found".to_string())
+}
+
+pub(crate) fn delete_result_snapshot(app: &AppHandle, snapshot_id: &str) -> Result<(), String> {
+    let snapshot_id = snapshot_id.trim();
+    if snapshot_id.is_empty() {
+        return Err("Snapshot id is required".to_string());
+    }
+
+    update_store(app, |store| {
+        let before = store.snapshots.len();
+        store
+            .snapshots
+            .retain(|snapshot| snapshot.id != snapshot_id);
+        if store.snapshots.len() == before {
+            return Err("Result snapshot not found".to_string());
+        }
+        Ok(())
+    })
+}
+
+fn insert_snapshot(
+    store: &mut ResultSnapshotStore,
+    request: DbSaveResultSnapshotRequest,
+    created_at: u64,
+) -> Result<&DbResultSnapshot, String> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Snapshot name is required".to_string());
+    }
+    if request.columns.is_empty() {
+        return Err("Snapshot has no columns".to_string());
+    }
+    if request
+        .rows
+        .iter()
+        .any(|row| row.len() != request.columns.len())
+    {
+        return Err("Every snapshot row must have one value per column".to_string());
+    }
+    if request.rows.len() * request.columns.len() > MAX_SNAPSHOT_CELLS {
+        return Err(format!(
+            "Snapshot is too large to save (limit is {MAX_SNAPSHOT_CELLS} cells)"
+        ));
+    }
+
+    let optional = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    store.snapshots.push(DbResultSnapshot {
+        id: next_snapshot_id(store, created_at),
+        name,
+        notes: optional(request.notes),
+        sql: optional(request.sql),
+        source: optional(request.source),
+        columns: request.columns,
+        rows: request.rows,
+        created_at,
+    });
+    Ok(&store.snapshots[store.snapshots.len() - 1])
+}
+
+fn summarize(snapshot: &DbResultSnapshot) -> DbResultSnapshotSummary {
+    DbResultSnapshotSummary {
+        id: snapshot.id.clone(),
+        name: snapshot.name.clone(),
+        notes: snapshot.notes.clone(),
+        sql: snapshot.sql.clone(),
+        source: snapshot.source.clone(),
+        column_count: snapshot.columns.len() as u32,
+        row_count: snapshot.rows.len() as u32,
+        created_at: snapshot.created_at,
+    }
+}
+
+fn next_snapshot_id(store: &ResultSnapshotStore, seed: u64) -> String {
+    let mut candidate = format!("snapshot-{seed}");
+    let mut suffix = 2;
+    while store
+        .snapshots
+        .iter()
+        .any(|snapshot| snapshot.id == candidate)
+    {
+        candidate = format!("snapshot-{seed}-{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+fn read_store(app: &AppHandle) -> Result<ResultSnapshotStore, String> {
+    let path = app_data_file_path(app, RESULT_SNAPSHOT_STORE_FILE)?;
+    let _guard = lock_store()?;
+    read_store_from_path(path.as_path())
+}
+
+fn update_store<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut ResultSnapshotStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = app_data_file_path(app, RESULT_SNAPSHOT_STORE_FILE)?;
+    let _guard = lock_store()?;
+    let mut store = read_store_from_path(path.as_path())?;
+    let value = f(&mut store)?;
+    write_store_to_path(path.as_path(), &store)?;
+    Ok(value)
+}
+
+fn lock_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    RESULT_SNAPSHOT_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire result snapshot store lock".to_string())
+}
+
+fn read_store_from_path(path: &Path) -> Result<ResultSnapshotStore, String> {
+    if !path.exists() {
+        return Ok(ResultSnapshotStore::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read result snapshot file: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(ResultSnapshotStore::default());
+    }
+
+    serde_json::from_str::<ResultSnapshotStore>(&content)
+        .map_err(|error| format!("Failed to parse result snapshot file: {error}"))
+}
+
+fn write_store_to_path(path: &Path, store: &ResultSnapshotStore) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create app data directory: {error}"))?;
+    }
+
+    // Compact JSON: snapshots can hold many thousands of rows.
+    let payload = serde_json::to_string(store)
+        .map_err(|error| format!("Failed to serialize result snapshots: {error}"))?;
+    fs::write(path, payload)
+        .map_err(|error| format!("Failed to write result snapshot file: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{insert_snapshot, read_store_from_path, write_store_to_path, ResultSnapshotStore};
+    use crate::types::DbSaveResultSnapshotRequest;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn request(name: &str, rows: Vec<Vec<&str>>) -> DbSaveResultSnapshotRequest {
+        DbSaveResultSnapshotRequest {
+            name: name.to_string(),
+            notes: Some("  ".to_string()),
+            sql: Some("SELECT id, status FROM orders WHERE status = 'BAD'".to_string()),
+            source: Some("prod".to_string()),
+            columns: vec!["ID".to_string(), "STATUS".to_string()],
+            rows: rows
+                .into_iter()
+                .map(|row| row.into_iter().map(str::to_string).collect())
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn saves_snapshots_and_round_trips_them() {
+        let mut store = ResultSnapshotStore::default();
+        let first = insert_snapshot(&mut store, request(" Bad rows ", vec![vec!["1", "BAD"]]), 7)
+            .expect("valid snapshot")
+            .clone();
+        assert_eq!(first.id, "snapshot-7");
+        assert_eq!(first.name, "Bad rows");
+        assert_eq!(first.notes, None);
+
+        let second = insert_snapshot(&mut store, request("Again", vec![]), 7).expect("empty rows");
+        assert_eq!(second.id, "snapshot-7-2");
+
+        assert_eq!(
+            insert_snapshot(&mut store, request("Ragged", vec![vec!["1"]]), 8).map(|_| ()),
+            Err("Every snapshot row must have one value per column".to_string())
+        );
+
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock should be after unix epoch")
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "clarity_snapshot_tests_{}_{unique}",
+            std::process::id()
+        ));
+        let path = dir.join("result_snapshots.json");
+        assert!(read_store_from_path(&path)
+            .expect("missing file")
+            .snapshots
+            .is_empty());
+        write_store_to_path(&path, &store).expect("write");
+        let restored = read_store_from_path(&path).expect("read");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(restored.snapshots.len(), 2);
+        assert_eq!(restored.snapshots[0].rows, vec![vec!["1", "BAD"]]);
+        assert_eq!(restored.snapshots[0].source.as_deref(), Some("prod"));
+    }
+}
@@ -0,0 +1,399 @@
+use crate::types::DatabaseProvider;
+
+// Deliberately short lists: words that commonly collide with real column and table names.
+const ORACLE_RESERVED_WORDS: &str = "\
+    ACCESS ADD ALL ALTER AND ANY AS ASC AUDIT BETWEEN BY CHAR CHECK CLUSTER COLUMN COMMENT \
+    COMPRESS CONNECT CREATE CURRENT DATE DECIMAL DEFAULT DELETE DESC DISTINCT DROP ELSE \
+    EXCLUSIVE EXISTS FILE FLOAT FOR FROM GRANT GROUP HAVING IDENTIFIED IMMEDIATE IN INCREMENT \
+    INDEX INITIAL INSERT INTEGER INTERSECT INTO IS LEVEL LIKE LOCK LONG MAXEXTENTS MINUS MODE \
+    MODIFY NOAUDIT NOCOMPRESS NOT NOWAIT NULL NUMBER OF OFFLINE ON ONLINE OPTION OR ORDER \
+    PCTFREE PRIOR PUBLIC RAW RENAME RESOURCE REVOKE ROW ROWID ROWNUM ROWS SELECT SESSION SET \
+    SHARE SIZE SMALLINT START SYNONYM SYSDATE TABLE THEN TO TRIGGER UID UNION UNIQUE UPDATE \
+    USER VALIDATE VALUES VARCHAR VARCHAR2 VIEW WHENEVER WHERE WITH";
+
+const POSTGRES_RESERVED_WORDS: &str = "\
+    all analyse analyze and any array as asc asymmetric both case cast check collate column \
+    constraint create current_date current_role current_time current_timestamp current_user \
+    default deferrable desc distinct do else end except false fetch for foreign from grant \
+    group having in initially intersect into lateral leading limit localtime localtimestamp \
+    not null offset on only or order placing primary references returning select session_user \
+    some symmetric table then to trailing true union unique user using variadic when where \
+    window with";
+
+const MYSQL_RESERVED_WORDS: &str = "\
+    ADD ALL ALTER AND AS ASC BETWEEN BY CASE CHECK COLUMN CONDITION CONSTRAINT CREATE CROSS \
+    DATABASE DEFAULT DELETE DESC DISTINCT DROP ELSE EXISTS FALSE FOR FOREIGN FROM GROUP HAVING \
+    IN INDEX INSERT INTERVAL INTO IS JOIN KEY KEYS LEFT LIKE LIMIT LOCK NOT NULL ON OR ORDER \
+    PRIMARY RANGE READ REFERENCES RENAME REPLACE RIGHT ROW ROWS SELECT SET SHOW TABLE THEN TO \
+    TRUE UNION UNIQUE UPDATE USAGE USE USING VALUES WHEN WHERE WITH WRITE";
+
+const SNOWFLAKE_RESERVED_WORDS: &str = "\
+    ALL ALTER AND ANY AS BETWEEN BY CASE CAST CHECK COLUMN CONNECT CONSTRAINT CREATE CROSS \
+    CURRENT CURRENT_DATE CURRENT_TIME CURRENT_TIMESTAMP CURRENT_USER DELETE DISTINCT DROP ELSE \
+    EXISTS FALSE FOLLOWING FOR FROM FULL GRANT GROUP HAVING ILIKE IN INCREMENT INNER INSERT \
+    INTERSECT INTO IS JOIN LATERAL LEFT LIKE LOCALTIME LOCALTIMESTAMP MINUS NATURAL NOT NULL \
+    OF ON OR ORDER QUALIFY REGEXP REVOKE RIGHT RLIKE ROW ROWS SAMPLE SELECT SET SOME START \
+    TABLE TABLESAMPLE THEN TO TRIGGER TRUE TRY_CAST UNION UNIQUE UPDATE USING VALUES VIEW \
+    WHEN WHENEVER WHERE WITH";
+
+// ClickHouse reserves few words outright; these are the ones that end a column or table
+// reference.
+const CLICKHOUSE_RESERVED_WORDS: &str = "\
+    ALL ANY ARRAY AS ASC BETWEEN BY CASE CROSS DESC DISTINCT ELSE END EXCEPT FINAL FORMAT \
+    FROM FULL GLOBAL GROUP HAVING IN INNER INTERSECT INTO JOIN LEFT LIKE LIMIT NOT NULL OFFSET \
+    ON OR ORDER PREWHERE RIGHT SAMPLE SELECT SETTINGS THEN UNION USING WHEN WHERE WITH";
+
+const SQLITE_RESERVED_WORDS: &str = "\
+    ADD ALL ALTER AND AS AUTOINCREMENT BETWEEN CASE CHECK COLLATE COMMIT CONSTRAINT CREATE \
+    DEFAULT DEFERRABLE DELETE DISTINCT DROP ELSE ESCAPE EXCEPT EXISTS FOREIGN FROM GROUP \
+    HAVING IN INDEX INSERT INTERSECT INTO IS ISNULL JOIN LIMIT NOT NOTNULL NULL ON OR ORDER \
+    PRIMARY REFERENCES SELECT SET TABLE THEN TO TRANSACTION UNION UNIQUE UPDATE USING VALUES \
+    WHEN WHERE";
+
+/// Folds an unquoted identifier the way the provider does: Oracle and Snowflake store it upper-case,
+/// Postgres lower-case, and MySQL/SQLite/DuckDB/ClickHouse keep it as written.
+pub(crate) fn normalize_unquoted(provider: DatabaseProvider, identifier: &str) -> String {
+    let identifier = identifier.trim();
+    match provider {
+        DatabaseProvider::Oracle | DatabaseProvider::Snowflake => identifier.to_uppercase(),
+        DatabaseProvider::Postgres => identifier.to_lowercase(),
+        DatabaseProvider::Mysql
+        | DatabaseProvider::Sqlite
+        | DatabaseProvider::Libsql
+        | DatabaseProvider::Duckdb
+        | DatabaseProvider::Clickhouse
+        | DatabaseProvider::External => identifier.to_string(),
+    }
+}
+
+/// Reads an identifier typed by the user into the name the database stores. A name in the
+/// provider's quote characters is taken literally, with doubled quotes unescaped; anything
+/// else is folded like an unquoted identifier.
+pub(crate) fn parse_identifier(
+    provider: DatabaseProvider,
+    input: &str,
+    label: &str,
+) -> Result<String, String> {
+    let input = input.trim();
+    let name = match unquote(provider, input) {
+        Some(name) => name,
+        None => normalize_unquoted(provider, input),
+    };
+    if name.is_empty() {
+        return Err(format!("{label} is required"));
+    }
+    if let Some(limit) = max_identifier_bytes(provider) {
+        if name.len() > limit {
+            return Err(format!("{label} is longer than {limit} bytes"));
+        }
+    }
+    Ok(name)
+}
+
+/// A name taken from the data dictionary (explorer entries, search results) is already
+/// stored form: it is used exactly as given, except that a quoted name is unquoted.
+pub(crate) fn stored_name(provider: DatabaseProvider, name: &str) -> String {
+    unquote(provider, name.trim()).unwrap_or_else(|| name.trim().to_string())
+}
+
+/// Longest identifier the provider accepts, in bytes, where it has a fixed limit.
+pub(crate) fn max_identifier_bytes(provider: DatabaseProvider) -> Option<usize> {
+    match provider {
+        DatabaseProvider::Oracle => Some(128),
+        DatabaseProvider::Postgres => Some(63),
+        DatabaseProvider::Mysql => Some(64),
+        DatabaseProvider::Snowflake => Some(255),
+        DatabaseProvider::Sqlite
+        | DatabaseProvider::Libsql
+        | DatabaseProvider::Duckdb
+        | DatabaseProvider::Clickhouse
+        | DatabaseProvider::External => None,
+    }
+}
+
+fn unquote(provider: DatabaseProvider, input: &str) -> Option<String> {
+    let quote = match provider {
+        DatabaseProvider::Mysql | DatabaseProvider::Clickhouse => '`',
+        _ => '"',
+    };
+    let inner = input.strip_prefix(quote)?.strip_suffix(quote)?;
+    let doubled = format!("{quote}{quote}");
+    Some(inner.replace(&doubled, &quote.to_string()))
+}
+
+/// Always quotes an identifier, escaping embedded quote characters.
+pub(crate) fn quote_identifier(provider: DatabaseProvider, identifier: &str) -> String {
+    match provider {
+        DatabaseProvider::Mysql => format!("`{}`", identifier.replace('`', "``")),
+        // ClickHouse escapes inside quoted identifiers with a backslash, as in strings.
+        DatabaseProvider::Clickhouse => {
+            format!("`{}`", identifier.replace('\\', "\\\\").replace('`', "\\`"))
+        }
+        DatabaseProvider::Oracle
+        | DatabaseProvider::Postgres
+        | DatabaseProvider::Sqlite
+        | DatabaseProvider::Libsql
+        | DatabaseProvider::Duckdb
+        | DatabaseProvider::Snowflake
+        // Adapters are assumed to follow standard SQL quoting.
+        | DatabaseProvider::External => {
+            format!("\"{}\"", identifier.replace('"', "\"\""))
+        }
+    }
+}
+
+/// Renders a stored identifier for generated SQL, quoting only when the provider would
+/// otherwise change its case or reject it.
+pub(crate) fn format_identifier(provider: DatabaseProvider, identifier: &str) -> String {
+    if needs_quoting(provider, identifier) {
+        quote_identifier(provider, identifier)
+    } else {
+        identifier.to_string()
+    }
+}
+
+/// The alias after a derived table, `(SELECT ...) alias`.
+pub(crate) fn derived_table_alias(provider: DatabaseProvider, alias: &str) -> String {
+    // Oracle rejects AS before a table alias.
+    match provider {
+        DatabaseProvider::Oracle => format!(" {alias}"),
+        _ => format!(" AS {alias}"),
+    }
+}
+
+/// Renders `schema.name`, or just `name` when no schema is given.
+pub(crate) fn qualified_name(
+    provider: DatabaseProvider,
+    schema: Option<&str>,
+    name: &str,
+) -> String {
+    match schema.map(str::trim).filter(|schema| !schema.is_empty()) {
+        Some(schema) => format!(
+            "{}.{}",
+            format_identifier(provider, schema),
+            format_identifier(provider, name)
+        ),
+        None => format_identifier(provider, name),
+    }
+}
+
+fn needs_quoting(provider: DatabaseProvider, identifier: &str) -> bool {
+    let Some(first) = identifier.chars().next() else {
+        return true;
+    };
+
+    let shape_is_plain = match provider {
+        DatabaseProvider::Oracle => {
+            first.is_ascii_uppercase()
+                && identifier.chars().all(|ch| {
+                    ch.is_ascii_uppercase() || ch.is_ascii_digit() || matches!(ch, '_' | '$' | '#')
+                })
+        }
+        DatabaseProvider::Snowflake => {
+            (first.is_ascii_uppercase() || first == '_')
+                && identifier.chars().all(|ch| {
+                    ch.is_ascii_uppercase() || ch.is_ascii_digit() || matches!(ch, '_' | '$')
+                })
+        }
+        DatabaseProvider::Postgres => {
+            (first.is_ascii_lowercase() || first == '_')
+                && identifier.chars().all(|ch| {
+                    ch.is_ascii_lowercase() || ch.is_ascii_digit() || matches!(ch, '_' | '$')
+                })
+        }
+        DatabaseProvider::Mysql => {
+            identifier
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '$'))
+                && !identifier.chars().all(|ch| ch.is_ascii_digit())
+        }
+        DatabaseProvider::Sqlite
+        | DatabaseProvider::Libsql
+        | DatabaseProvider::Duckdb
+        | DatabaseProvider::Clickhouse
+        | DatabaseProvider::External => {
+            (first.is_ascii_alphabetic() || first == '_')
+                && identifier
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        }
+    };
+
+    !shape_is_plain || is_reserved_word(provider, identifier)
+}
+
+fn is_reserved_word(provider: DatabaseProvider, identifier: &str) -> bool {
+    let words = match provider {
+        DatabaseProvider::Oracle => ORACLE_RESERVED_WORDS,
+        DatabaseProvider::Snowflake => SNOWFLAKE_RESERVED_WORDS,
+        // DuckDB's parser is derived from Postgres and reserves the same core words.
+        DatabaseProvider::Postgres | DatabaseProvider::Duckdb => POSTGRES_RESERVED_WORDS,
+        // The Postgres list is close to the SQL standard's reserved words.
+        DatabaseProvider::External => POSTGRES_RESERVED_WORDS,
+        DatabaseProvider::Mysql => MYSQL_RESERVED_WORDS,
+        DatabaseProvider::Sqlite | DatabaseProvider::Libsql => SQLITE_RESERVED_WORDS,
+        DatabaseProvider::Clickhouse => CLICKHOUSE_RESERVED_WORDS,
+    };
+    words
+        .split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case(identifier))
+}
+
+/// Case-insensitive match where `*` and `%` stand for any run of characters and `?` for one.
+pub(crate) fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_uppercase().chars().collect::<Vec<_>>();
+    let name = name.to_uppercase().chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*' | '%') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| matches!(c, '*' | '%'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        format_identifier, matches_pattern, normalize_unquoted, parse_identifier, qualified_name,
+        quote_identifier, stored_name,
+    };
+    use crate::types::DatabaseProvider;
+
+    #[test]
+    fn formats_identifiers_per_provider_casing_rules() {
+        assert_eq!(
+            format_identifier(DatabaseProvider::Oracle, "ORDERS"),
+            "ORDERS"
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Oracle, "Orders"),
+            "\"Orders\""
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Oracle, "LEVEL"),
+            "\"LEVEL\""
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Postgres, "orders"),
+            "orders"
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Postgres, "ORDERS"),
+            "\"ORDERS\""
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Postgres, "user"),
+            "\"user\""
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Mysql, "Orders"),
+            "Orders"
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Mysql, "order items"),
+            "`order items`"
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Sqlite, "2024_data"),
+            "\"2024_data\""
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Snowflake, "ORDERS"),
+            "ORDERS"
+        );
+        assert_eq!(
+            format_identifier(DatabaseProvider::Snowflake, "QUALIFY"),
+            "\"QUALIFY\""
+        );
+
+        assert_eq!(
+            normalize_unquoted(DatabaseProvider::Oracle, " orders "),
+            "ORDERS"
+        );
+        assert_eq!(
+            normalize_unquoted(DatabaseProvider::Postgres, "Orders"),
+            "orders"
+        );
+    }
+
+    #[test]
+    fn quotes_and_qualifies_identifiers() {
+        assert_eq!(
+            quote_identifier(DatabaseProvider::Oracle, "A\"B"),
+            "\"A\"\"B\""
+        );
+        assert_eq!(quote_identifier(DatabaseProvider::Mysql, "a`b"), "`a``b`");
+        assert_eq!(
+            quote_identifier(DatabaseProvider::Clickhouse, "a`b"),
+            "`a\\`b`"
+        );
+        assert_eq!(
+            qualified_name(DatabaseProvider::Oracle, Some("APP"), "Order Lines"),
+            "APP.\"Order Lines\""
+        );
+        assert_eq!(
+            qualified_name(DatabaseProvider::Postgres, Some(" "), "t"),
+            "t"
+        );
+    }
+
+    #[test]
+    fn parses_quoted_and_long_identifiers() {
+        assert_eq!(
+            parse_identifier(DatabaseProvider::Oracle, " hr ", "Schema").as_deref(),
+            Ok("HR")
+        );
+        assert_eq!(
+            parse_identifier(
+                DatabaseProvider::Oracle,
+                "\"Order \"\"Lines\"\"\"",
+                "Schema"
+            )
+            .as_deref(),
+            Ok("Order \"Lines\"")
+        );
+        assert_eq!(
+            parse_identifier(DatabaseProvider::Mysql, "`sales data`", "Table").as_deref(),
+            Ok("sales data")
+        );
+        assert!(parse_identifier(DatabaseProvider::Oracle, "\"\"", "Schema").is_err());
+        let long_name = "A".repeat(128);
+        assert!(parse_identifier(DatabaseProvider::Oracle, &long_name, "Schema").is_ok());
+        assert!(
+            parse_identifier(DatabaseProvider::Oracle, &format!("{long_name}B"), "Schema").is_err()
+        );
+
+        assert_eq!(stored_name(DatabaseProvider::Oracle, "orders"), "orders");
+        assert_eq!(
+            stored_name(DatabaseProvider::Oracle, "\"orders\""),
+            "orders"
+        );
+    }
+
+    #[test]
+    fn matches_wildcard_patterns_case_insensitively() {
+        assert!(matches_pattern("v_sales*", "V_SALES_BY_REGION"));
+        assert!(matches_pattern("%ORDER%", "OPEN_ORDERS"));
+        assert!(matches_pattern("ORDERS_?", "ORDERS_1"));
+        assert!(!matches_pattern("ORDERS_?", "ORDERS_10"));
+        assert!(!matches_pattern("V_*", "SALES"));
+        assert!(matches_pattern("*", "ANYTHING"));
+    }
+}
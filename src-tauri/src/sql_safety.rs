@@ -0,0 +1,693 @@
+use crate::providers::AppSession;
+use crate::sql_ident;
+use crate::sql_splitter::split_statements;
+use crate::types::{DatabaseProvider, DbConnectRequest, DbSqlStatement};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const MAX_LISTED_STATEMENTS: usize = 5;
+
+/// Environment tags that turn on the production guard.
+const PRODUCTION_ENVIRONMENTS: [&str; 2] = ["prod", "production"];
+
+/// Words between `DROP`/`TRUNCATE`/`PURGE`/`ALTER` and the name of the object.
+const OBJECT_KEYWORDS: [&str; 24] = [
+    "BODY",
+    "CLUSTER",
+    "DATABASE",
+    "DIRECTORY",
+    "EXISTS",
+    "FUNCTION",
+    "GLOBAL",
+    "IF",
+    "INDEX",
+    "LINK",
+    "MATERIALIZED",
+    "PACKAGE",
+    "PROCEDURE",
+    "PUBLIC",
+    "ROLE",
+    "SCHEMA",
+    "SEQUENCE",
+    "SYNONYM",
+    "TABLE",
+    "TABLESPACE",
+    "TEMPORARY",
+    "TRIGGER",
+    "TYPE",
+    "VIEW",
+];
+
+/// Words that may come between `CREATE`/`ALTER`/`DROP`/`TRUNCATE` and the object type.
+const DDL_MODIFIERS: [&str; 14] = [
+    "BITMAP",
+    "EDITIONABLE",
+    "EXISTS",
+    "FORCE",
+    "GLOBAL",
+    "IF",
+    "NOFORCE",
+    "NONEDITIONABLE",
+    "NOT",
+    "OR",
+    "PUBLIC",
+    "REPLACE",
+    "TEMPORARY",
+    "UNIQUE",
+];
+
+/// Words that make up the type of the object a DDL statement names.
+const DDL_OBJECT_TYPES: [&str; 18] = [
+    "BODY",
+    "CLUSTER",
+    "DATABASE",
+    "DIRECTORY",
+    "FUNCTION",
+    "INDEX",
+    "LINK",
+    "MATERIALIZED",
+    "PACKAGE",
+    "PROCEDURE",
+    "ROLE",
+    "SEQUENCE",
+    "SYNONYM",
+    "TABLE",
+    "TABLESPACE",
+    "TRIGGER",
+    "TYPE",
+    "VIEW",
+];
+
+/// Common keywords, which `identifiers` leaves out.
+const SQL_KEYWORDS: [&str; 58] = [
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "COUNT",
+    "CROSS",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "ELSE",
+    "END",
+    "EXISTS",
+    "FETCH",
+    "FIRST",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "MAX",
+    "MIN",
+    "NOT",
+    "NULL",
+    "NULLS",
+    "OFFSET",
+    "ON",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "OVER",
+    "PARTITION",
+    "RIGHT",
+    "ROW",
+    "ROWS",
+    "SELECT",
+    "SET",
+    "SUM",
+    "THEN",
+    "TOP",
+    "UNION",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+/// The object a DDL statement creates, changes or removes.
+#[derive(Debug, PartialEq)]
+pub(crate) struct DdlTarget {
+    /// `CREATE`, `ALTER`, `DROP` or `TRUNCATE`.
+    pub(crate) action: String,
+    /// Upper-cased, such as `PACKAGE BODY`.
+    pub(crate) object_type: String,
+    pub(crate) schema: Option<String>,
+    pub(crate) object_name: String,
+}
+
+/// Why a statement destroys data or objects, or `None` when it is not considered destructive.
+pub(crate) fn destructive_reason(sql: &str) -> Option<&'static str> {
+    let words = significant_words(sql);
+    let first = words.first()?.as_str();
+    let has = |word: &str| words.iter().any(|candidate| candidate == word);
+    match first {
+        "DROP" => Some("DROP removes the object and its data"),
+        "TRUNCATE" => Some("TRUNCATE deletes every row"),
+        "PURGE" => Some("PURGE permanently removes dropped objects"),
+        "DELETE" if !has("WHERE") => Some("DELETE without WHERE removes every row"),
+        "UPDATE" if !has("WHERE") => Some("UPDATE without WHERE changes every row"),
+        "ALTER" if has("DROP") || has("TRUNCATE") => {
+            Some("ALTER ... DROP removes part of the object")
+        }
+        _ => None,
+    }
+}
+
+/// True when the statement is a query (`SELECT` or `WITH`), ignoring leading comments.
+pub(crate) fn is_select_query(sql: &str) -> bool {
+    matches!(
+        significant_words(sql).first().map(String::as_str),
+        Some("SELECT" | "WITH")
+    )
+}
+
+/// True when a query only reads, so a read replica can run it: no `FOR UPDATE` or other
+/// locking clause, no `SELECT ... INTO`, no data-changing `WITH` and no sequence `NEXTVAL`.
+pub(crate) fn is_read_only_query(sql: &str) -> bool {
+    is_select_query(sql)
+        && !significant_words(sql).iter().any(|word| {
+            matches!(
+                word.as_str(),
+                "INTO"
+                    | "INSERT"
+                    | "UPDATE"
+                    | "DELETE"
+                    | "MERGE"
+                    | "SHARE"
+                    | "LOCK"
+                    | "NEXTVAL"
+                    | "FUNCTION"
+                    | "PROCEDURE"
+            )
+        })
+}
+
+/// Splits SQL from the editor, scripts, `.sql` files or snippets and refuses it before
+/// anything executes if a statement is destructive and the caller has not confirmed with
+/// `allow_destructive`.
+pub(crate) fn split_confirmed(
+    sql: &str,
+    provider: DatabaseProvider,
+    allow_destructive: bool,
+) -> Result<Vec<DbSqlStatement>, String> {
+    confirm_destructive(split_statements(sql, provider), allow_destructive)
+}
+
+/// The check behind `split_confirmed`, for statements that were already split, such as an
+/// Oracle script after its `@file` includes were expanded.
+pub(crate) fn confirm_destructive(
+    statements: Vec<DbSqlStatement>,
+    allow_destructive: bool,
+) -> Result<Vec<DbSqlStatement>, String> {
+    if allow_destructive {
+        return Ok(statements);
+    }
+
+    let flagged = statements
+        .iter()
+        .filter_map(|statement| {
+            destructive_reason(&statement.sql)
+                .map(|reason| format!("line {}: {reason}", statement.line))
+        })
+        .collect::<Vec<_>>();
+    if flagged.is_empty() {
+        return Ok(statements);
+    }
+
+    Err(format!(
+        "Nothing was run: {} destructive statement(s) need confirmation ({}). Run again with allowDestructive to proceed.",
+        flagged.len(),
+        list_flagged(&flagged)
+    ))
+}
+
+pub(crate) fn is_production(environment: Option<&str>) -> bool {
+    environment.is_some_and(|environment| {
+        PRODUCTION_ENVIRONMENTS
+            .iter()
+            .any(|tag| environment.trim().eq_ignore_ascii_case(tag))
+    })
+}
+
+/// The production guard, for sessions whose profile is tagged `prod` or `production`; other
+/// sessions pass. DROP, TRUNCATE and PURGE are refused outright unless the object is on the
+/// profile's drop allowlist. Any other statement that changes data or objects needs
+/// `confirmation`: the token named in the refusal, which only confirms these statements on
+/// this session. This is on top of `allow_destructive`, not instead of it.
+pub(crate) fn check_production(
+    session_id: u64,
+    session: &AppSession,
+    statements: &[DbSqlStatement],
+    confirmation: Option<&str>,
+) -> Result<(), String> {
+    check_production_for(
+        session_id,
+        session.connect_request(),
+        statements,
+        confirmation,
+    )
+}
+
+/// `check_production` against the request the session was opened with.
+pub(crate) fn check_production_for(
+    session_id: u64,
+    request: Option<&DbConnectRequest>,
+    statements: &[DbSqlStatement],
+    confirmation: Option<&str>,
+) -> Result<(), String> {
+    let Some(request) = request else {
+        return Ok(());
+    };
+    if !is_production(request.environment.as_deref()) {
+        return Ok(());
+    }
+    check_production_statements(
+        session_id,
+        statements,
+        &request.drop_allowlist,
+        confirmation,
+    )
+}
+
+fn check_production_statements(
+    session_id: u64,
+    statements: &[DbSqlStatement],
+    drop_allowlist: &[String],
+    confirmation: Option<&str>,
+) -> Result<(), String> {
+    let refused = statements
+        .iter()
+        .filter_map(|statement| match removed_object(&statement.sql)? {
+            Some(name) if allowlisted(&name, drop_allowlist) => None,
+            Some(name) => Some(format!("line {}: {name}", statement.line)),
+            None => Some(format!("line {}", statement.line)),
+        })
+        .collect::<Vec<_>>();
+    if !refused.is_empty() {
+        return Err(format!(
+            "Nothing was run: DROP and TRUNCATE are refused on a production session unless the object is on the profile's drop allowlist ({}).",
+            list_flagged(&refused)
+        ));
+    }
+
+    let changing = statements
+        .iter()
+        .filter(|statement| changes_data_or_objects(&statement.sql))
+        .map(|statement| format!("line {}", statement.line))
+        .collect::<Vec<_>>();
+    if changing.is_empty() {
+        return Ok(());
+    }
+    let token = confirmation_token(session_id, statements);
+    if confirmation.map(str::trim) == Some(token.as_str()) {
+        return Ok(());
+    }
+    Err(format!(
+        "Nothing was run: this is a production session and {} statement(s) change data or objects ({}). Run again with productionConfirmation \"{token}\" to proceed.",
+        changing.len(),
+        list_flagged(&changing)
+    ))
+}
+
+/// Statements other than queries and session settings.
+fn changes_data_or_objects(sql: &str) -> bool {
+    let words = significant_words(sql);
+    let second = words.get(1).map(String::as_str);
+    match words.first().map(String::as_str) {
+        Some("ALTER") => second != Some("SESSION"),
+        // A PL/SQL block, not Postgres' BEGIN [TRANSACTION | WORK].
+        Some("BEGIN") => !matches!(second, None | Some("TRANSACTION" | "WORK")),
+        Some(
+            "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "UPSERT" | "REPLACE" | "CREATE" | "DROP"
+            | "TRUNCATE" | "RENAME" | "GRANT" | "REVOKE" | "COMMENT" | "PURGE" | "FLASHBACK"
+            | "CALL" | "EXEC" | "EXECUTE" | "DECLARE" | "COPY",
+        ) => true,
+        _ => false,
+    }
+}
+
+/// The object a DROP, TRUNCATE or PURGE removes, or an ALTER ... DROP changes, as `NAME` or
+/// `SCHEMA.NAME`; `None` for other statements and `Some(None)` when no name can be read.
+fn removed_object(sql: &str) -> Option<Option<String>> {
+    let tokens = tokens(sql);
+    let has_word = |word: &str| {
+        tokens
+            .iter()
+            .any(|token| matches!(token, Token::Word(candidate) if candidate == word))
+    };
+    match tokens.first() {
+        Some(Token::Word(first)) if matches!(first.as_str(), "DROP" | "TRUNCATE" | "PURGE") => {}
+        Some(Token::Word(first))
+            if first == "ALTER" && (has_word("DROP") || has_word("TRUNCATE")) => {}
+        _ => return None,
+    }
+
+    let mut rest = tokens[1..].iter().skip_while(
+        |token| matches!(token, Token::Word(word) if OBJECT_KEYWORDS.contains(&word.as_str())),
+    );
+    let name = match rest.next() {
+        Some(Token::Word(name) | Token::Quoted(name)) => name.clone(),
+        _ => return Some(None),
+    };
+    match (rest.next(), rest.next()) {
+        (Some(Token::Symbol('.')), Some(Token::Word(object) | Token::Quoted(object))) => {
+            Some(Some(format!("{name}.{object}")))
+        }
+        _ => Some(Some(name)),
+    }
+}
+
+/// Patterns may name the object alone or with its schema.
+fn allowlisted(name: &str, drop_allowlist: &[String]) -> bool {
+    let object = name.rsplit('.').next().unwrap_or(name);
+    drop_allowlist.iter().any(|pattern| {
+        sql_ident::matches_pattern(pattern.trim(), name)
+            || sql_ident::matches_pattern(pattern.trim(), object)
+    })
+}
+
+/// Ties a confirmation to these statements on this session, so it cannot be reused for
+/// different SQL.
+fn confirmation_token(session_id: u64, statements: &[DbSqlStatement]) -> String {
+    let mut hasher = DefaultHasher::new();
+    session_id.hash(&mut hasher);
+    for statement in statements {
+        statement.sql.trim().hash(&mut hasher);
+    }
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+fn list_flagged(flagged: &[String]) -> String {
+    let mut listed = flagged
+        .iter()
+        .take(MAX_LISTED_STATEMENTS)
+        .cloned()
+        .collect::<Vec<_>>();
+    if flagged.len() > MAX_LISTED_STATEMENTS {
+        listed.push(format!(
+            "and {} more",
+            flagged.len() - MAX_LISTED_STATEMENTS
+        ));
+    }
+    listed.join("; ")
+}
+
+enum Token {
+    /// Upper-cased.
+    Word(String),
+    /// A quoted identifier's name, as written.
+    Quoted(String),
+    Symbol(char),
+}
+
+/// Tokens of `sql` outside comments and string literals.
+fn tokens(sql: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch.is_alphanumeric() || ch == '_' || ch == '$' || ch == '#' {
+            current.push(ch.to_ascii_uppercase());
+            continue;
+        }
+        if !current.is_empty() {
+            tokens.push(Token::Word(std::mem::take(&mut current)));
+        }
+        match ch {
+            '-' if chars.peek() == Some(&'-') => {
+                read_until(&mut chars, "\n");
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                read_until(&mut chars, "*/");
+            }
+            '\'' => {
+                read_until(&mut chars, "'");
+            }
+            '"' | '`' => tokens.push(Token::Quoted(read_until(&mut chars, &ch.to_string()))),
+            _ if ch.is_whitespace() => {}
+            _ => tokens.push(Token::Symbol(ch)),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(Token::Word(current));
+    }
+    tokens
+}
+
+/// The object `sql` changes, or `None` when it is not DDL on a named object. `ALTER SESSION`
+/// and `ALTER SYSTEM` are not.
+pub(crate) fn ddl_target(sql: &str) -> Option<DdlTarget> {
+    let tokens = tokens(sql);
+    let action = match tokens.first() {
+        Some(Token::Word(word))
+            if matches!(word.as_str(), "CREATE" | "ALTER" | "DROP" | "TRUNCATE") =>
+        {
+            word.clone()
+        }
+        _ => return None,
+    };
+    let mut rest = tokens[1..]
+        .iter()
+        .skip_while(
+            |token| matches!(token, Token::Word(word) if DDL_MODIFIERS.contains(&word.as_str())),
+        )
+        .peekable();
+    let mut type_words = Vec::new();
+    while let Some(Token::Word(word)) = rest.peek() {
+        if !DDL_OBJECT_TYPES.contains(&word.as_str()) {
+            break;
+        }
+        type_words.push(word.as_str());
+        rest.next();
+    }
+    // `DROP TABLE IF EXISTS name`
+    let mut rest = rest.skip_while(
+        |token| matches!(token, Token::Word(word) if DDL_MODIFIERS.contains(&word.as_str())),
+    );
+    if type_words.is_empty() {
+        return None;
+    }
+    let name = match rest.next() {
+        Some(Token::Word(name) | Token::Quoted(name)) => name.clone(),
+        _ => return None,
+    };
+    let (schema, object_name) = match (rest.next(), rest.next()) {
+        (Some(Token::Symbol('.')), Some(Token::Word(object) | Token::Quoted(object))) => {
+            (Some(name), object.clone())
+        }
+        _ => (None, name),
+    };
+    Some(DdlTarget {
+        action,
+        object_type: type_words.join(" "),
+        schema,
+        object_name,
+    })
+}
+
+/// Names `sql` refers to, each once: unquoted words upper-cased, outside keywords and
+/// numbers, and quoted identifiers as written. Both parts of `schema.object` count.
+pub(crate) fn identifiers(sql: &str) -> Vec<String> {
+    let mut names = Vec::<String>::new();
+    for token in tokens(sql) {
+        let name = match token {
+            Token::Word(word)
+                if !SQL_KEYWORDS.contains(&word.as_str())
+                    && !word.starts_with(|ch: char| ch.is_ascii_digit()) =>
+            {
+                word
+            }
+            Token::Quoted(name) if !name.is_empty() => name,
+            _ => continue,
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Names of the `:name` placeholders in `sql`, upper-cased like Oracle reports them, each
+/// once in order of appearance. DDL has none: `:new` and `:old` in a trigger body are not
+/// binds.
+pub(crate) fn bind_names(sql: &str) -> Vec<String> {
+    let tokens = tokens(sql);
+    let is_ddl = matches!(
+        tokens.first(),
+        Some(Token::Word(word)) if matches!(word.as_str(), "CREATE" | "ALTER" | "DROP")
+    );
+    if is_ddl {
+        return Vec::new();
+    }
+    let mut names = Vec::<String>::new();
+    for pair in tokens.windows(2) {
+        if let [Token::Symbol(':'), Token::Word(name) | Token::Quoted(name)] = pair {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+    }
+    names
+}
+
+/// Consumes `chars` through `terminator`, returning what came before it.
+fn read_until(chars: &mut impl Iterator<Item = char>, terminator: &str) -> String {
+    let mut text = String::new();
+    for ch in chars.by_ref() {
+        text.push(ch);
+        if text.ends_with(terminator) {
+            text.truncate(text.len() - terminator.len());
+            break;
+        }
+    }
+    text
+}
+
+/// Upper-cased words of `sql` outside comments, string literals and quoted identifiers.
+fn significant_words(sql: &str) -> Vec<String> {
+    tokens(sql)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Word(word) => Some(word),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bind_names, check_production_statements, ddl_target, destructive_reason, identifiers,
+        is_read_only_query, removed_object, split_confirmed, DdlTarget,
+    };
+    use crate::sql_splitter::split_statements;
+    use crate::types::DatabaseProvider;
+
+    #[test]
+    fn flags_statements_that_destroy_data() {
+        assert!(destructive_reason("-- cleanup\nDROP TABLE orders").is_some());
+        assert!(destructive_reason("delete from orders").is_some());
+        assert!(destructive_reason("ALTER TABLE orders DROP COLUMN note").is_some());
+        assert!(destructive_reason("DELETE FROM orders WHERE id = 1").is_none());
+        assert!(destructive_reason("UPDATE t SET note = 'no where here' WHERE id = 2").is_none());
+        assert!(destructive_reason("UPDATE t SET note = 'where'").is_some());
+        assert!(destructive_reason("SELECT * FROM drop_log").is_none());
+    }
+
+    #[test]
+    fn tells_read_only_queries_apart() {
+        assert!(is_read_only_query(
+            "-- report\nSELECT * FROM orders WHERE note = 'update'"
+        ));
+        assert!(is_read_only_query(
+            "WITH t AS (SELECT 1 FROM dual) SELECT * FROM t"
+        ));
+        assert!(!is_read_only_query("SELECT * FROM orders FOR UPDATE"));
+        assert!(!is_read_only_query("SELECT * INTO orders_copy FROM orders"));
+        assert!(!is_read_only_query("SELECT orders_seq.NEXTVAL FROM dual"));
+        assert!(!is_read_only_query(
+            "WITH gone AS (DELETE FROM orders RETURNING *) SELECT * FROM gone"
+        ));
+        assert!(!is_read_only_query("UPDATE orders SET note = NULL"));
+    }
+
+    #[test]
+    fn refuses_whole_script_until_confirmed() {
+        let sql = "INSERT INTO t VALUES (1);\nTRUNCATE TABLE t;";
+        let error = split_confirmed(sql, DatabaseProvider::Postgres, false).unwrap_err();
+        assert!(error.contains("line 2: TRUNCATE"), "{error}");
+        assert_eq!(
+            split_confirmed(sql, DatabaseProvider::Postgres, true)
+                .expect("confirmed")
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn reads_the_object_a_statement_removes() {
+        assert_eq!(
+            removed_object("DROP TABLE IF EXISTS app.\"Staging\""),
+            Some(Some("APP.Staging".to_string()))
+        );
+        assert_eq!(
+            removed_object("alter table orders drop column note"),
+            Some(Some("ORDERS".to_string()))
+        );
+        assert_eq!(removed_object("DELETE FROM orders"), None);
+    }
+
+    #[test]
+    fn production_needs_a_token_and_refuses_drops_off_the_allowlist() {
+        let allowlist = vec!["TMP_*".to_string()];
+        let drop = split_statements("DROP TABLE orders", DatabaseProvider::Oracle);
+        let error = check_production_statements(7, &drop, &allowlist, None).unwrap_err();
+        assert!(error.contains("line 1: ORDERS"), "{error}");
+
+        let select = split_statements("SELECT * FROM orders", DatabaseProvider::Oracle);
+        assert!(check_production_statements(7, &select, &allowlist, None).is_ok());
+
+        let sql = "UPDATE orders SET note = 'x' WHERE id = 1;\nDROP TABLE app.tmp_load;";
+        let statements = split_statements(sql, DatabaseProvider::Oracle);
+        let error = check_production_statements(7, &statements, &allowlist, None).unwrap_err();
+        let token = error.split('"').nth(1).expect("token").to_string();
+        assert!(check_production_statements(7, &statements, &allowlist, Some(&token)).is_ok());
+        assert!(check_production_statements(8, &statements, &allowlist, Some(&token)).is_err());
+    }
+
+    #[test]
+    fn finds_bind_names_outside_literals_and_assignments() {
+        let sql = "SELECT :id, ':skip' FROM t -- :comment\nWHERE a = :Id OR b = :name\n\
+                   BEGIN x := 1; END;";
+        assert_eq!(bind_names(sql), vec!["ID", "NAME"]);
+        assert!(bind_names(
+            "CREATE TRIGGER t BEFORE INSERT ON x FOR EACH ROW BEGIN :new.id := 1; END;"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn finds_the_object_a_ddl_statement_changes() {
+        assert_eq!(
+            ddl_target("-- v2\nCREATE OR REPLACE EDITIONABLE PACKAGE BODY hr.\"Pkg\" AS END;"),
+            Some(DdlTarget {
+                action: "CREATE".to_string(),
+                object_type: "PACKAGE BODY".to_string(),
+                schema: Some("HR".to_string()),
+                object_name: "Pkg".to_string(),
+            })
+        );
+        let drop = ddl_target("drop table if exists orders").unwrap();
+        assert_eq!((drop.object_type.as_str(), drop.schema), ("TABLE", None));
+        assert_eq!(drop.object_name, "ORDERS");
+        assert!(ddl_target("ALTER SESSION SET current_schema = hr").is_none());
+        assert!(ddl_target("SELECT * FROM orders").is_none());
+    }
+
+    #[test]
+    fn lists_identifiers_without_keywords_or_literals() {
+        let sql = "SELECT o.order_id, \"Note\" FROM hr.orders o -- customers\n\
+                   WHERE o.status = 'OPEN' AND o.total > 10 ORDER BY o.order_id";
+        assert_eq!(
+            identifiers(sql),
+            vec!["O", "ORDER_ID", "Note", "HR", "ORDERS", "STATUS", "TOTAL"]
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use crate::cancellation::CancelToken;
+use crate::ddl_changelog;
+use crate::jobs::{start_job, JobRegistry};
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_safety::{check_production, confirm_destructive};
+use crate::sql_splitter::split_statements;
+use crate::sqlplus::{self, Directive, OnSqlError};
+use crate::state::{lock_session, SessionRegistry};
+use crate::types::{
+    DatabaseProvider, DbJobKind, DbJobStatus, DbQueryRequest, DbQueryResult, DbRunScriptRequest,
+    DbScriptResult, DbScriptStatementResult, DbSqlFileImportRequest, DbSqlStatement,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+/// Runs every statement of a script in order on one session, recording the DDL it applies
+/// in the profile's changelog.
+pub(crate) fn run_script(
+    app: &AppHandle,
+    session: &mut AppSession,
+    request: &DbRunScriptRequest,
+    cancel: &CancelToken,
+) -> Result<DbScriptResult, String> {
+    let statements = prepare_statements(
+        &request.sql,
+        session.provider,
+        request.allow_destructive,
+        request.base_directory.as_deref().map(Path::new),
+        &request.substitutions,
+    )?;
+    check_production(
+        request.session_id,
+        session,
+        &statements,
+        request.production_confirmation.as_deref(),
+    )?;
+    let pending = ddl_changelog::pending_changes(session, request.session_id, &statements);
+    let total_statements = statements.len() as u32;
+    let mut results = Vec::with_capacity(statements.len());
+    let mut failed_statements = 0;
+    run_statements(
+        session,
+        request.session_id,
+        statements,
+        request.row_limit,
+        cancel,
+        |statement, outcome, output| {
+            let (result, error) = match outcome {
+                Ok(result) => (Some(result), None),
+                Err(error) => {
+                    failed_statements += 1;
+                    (None, Some(error))
+                }
+            };
+            let failed = error.is_some();
+            results.push(DbScriptStatementResult {
+                sql: statement.sql,
+                line: statement.line,
+                result,
+                error,
+                output,
+            });
+            !(failed && request.stop_on_error)
+        },
+    );
+    let _ = ddl_changelog::record_changes(app, session, pending, |line, sql| {
+        results
+            .iter()
+            .find(|statement| statement.line == line && statement.sql.trim() == sql)
+            .map(|statement| statement.error.clone())
+    });
+
+    Ok(DbScriptResult {
+        statements: results,
+        total_statements,
+        failed_statements,
+    })
+}
+
+/// Runs a `.sql` file as a background job, reporting the number of statements executed.
+pub(crate) fn start_sql_file_import_job(
+    request: DbSqlFileImportRequest,
+    sessions: SessionRegistry,
+    jobs: Arc<JobRegistry>,
+    app: AppHandle,
+) -> Result<DbJobStatus, String> {
+    let file_path = request.file_path.trim().to_string();
+    if file_path.is_empty() {
+        return Err("A SQL file is required".to_string());
+    }
+
+    let label = format!("Run {file_path}");
+    let changelog_app = app.clone();
+    start_job(
+        jobs,
+        app,
+        DbJobKind::SqlFileImport,
+        label,
+        move |reporter| {
+            let sql = fs::read_to_string(&file_path)
+                .map_err(|error| format!("Failed to read {file_path}: {error}"))?;
+            let session = sessions.get(request.session_id)?;
+            let mut session = lock_session(&session)?;
+            let session = &mut *session;
+
+            let statements = prepare_statements(
+                &sql,
+                session.provider,
+                request.allow_destructive,
+                Path::new(&file_path).parent(),
+                &HashMap::new(),
+            )?;
+            check_production(
+                request.session_id,
+                session,
+                &statements,
+                request.production_confirmation.as_deref(),
+            )?;
+            let pending = ddl_changelog::pending_changes(session, request.session_id, &statements);
+            let total = statements.len();
+            let mut executed = 0u64;
+            let mut errors = Vec::new();
+            let mut outcomes = Vec::new();
+            run_statements(
+                session,
+                request.session_id,
+                statements,
+                Some(1),
+                reporter.cancel_token(),
+                |statement, outcome, _| {
+                    executed += 1;
+                    let error = outcome.err();
+                    if let Some(error) = &error {
+                        errors.push(format!("Line {}: {error}", statement.line));
+                    }
+                    outcomes.push((statement.line, statement.sql, error));
+                    reporter.progress(
+                        executed,
+                        &format!("{executed} of {total} statement(s) executed."),
+                    );
+                    errors.is_empty() || !request.stop_on_error
+                },
+            );
+            let _ = ddl_changelog::record_changes(&changelog_app, session, pending, |line, sql| {
+                outcomes
+                    .iter()
+                    .find(|(ran_line, ran_sql, _)| *ran_line == line && ran_sql.trim() == sql)
+                    .map(|(_, _, error)| error.clone())
+            });
+
+            match errors.first() {
+                Some(first) if request.stop_on_error => Err(format!(
+                    "Stopped after {executed} of {total} statement(s). {first}"
+                )),
+                Some(first) => Err(format!(
+                    "{executed} statement(s) executed, {} failed. First failure: {first}",
+                    errors.len()
+                )),
+                None => Ok(format!("{executed} statement(s) executed.")),
+            }
+        },
+    )
+}
+
+/// Splits a script and refuses it if it is destructive and unconfirmed. Oracle scripts are
+/// first prepared like SQL*Plus would (see `sqlplus::prepare`), so included files are
+/// checked too.
+fn prepare_statements(
+    sql: &str,
+    provider: DatabaseProvider,
+    allow_destructive: bool,
+    base_directory: Option<&Path>,
+    substitutions: &HashMap<String, String>,
+) -> Result<Vec<DbSqlStatement>, String> {
+    let mut statements = split_statements(sql, provider);
+    if provider == DatabaseProvider::Oracle {
+        statements = sqlplus::prepare(statements, base_directory, substitutions)?;
+    }
+    confirm_destructive(statements, allow_destructive)
+}
+
+/// Executes statements until `on_result` returns false or `cancel` stops the run. The
+/// statement the run stopped at reports why; the rest are not run. On Oracle, SQL*Plus
+/// commands are applied here: `WHENEVER SQLERROR` decides instead of `on_result` whether
+/// a failure stops the run, and with `SET SERVEROUTPUT ON` each statement's `DBMS_OUTPUT`
+/// lines are passed along with its outcome.
+fn run_statements(
+    session: &mut AppSession,
+    session_id: u64,
+    statements: Vec<DbSqlStatement>,
+    row_limit: Option<u32>,
+    cancel: &CancelToken,
+    mut on_result: impl FnMut(DbSqlStatement, Result<DbQueryResult, String>, Vec<String>) -> bool,
+) {
+    let applies_sqlplus = session.provider == DatabaseProvider::Oracle;
+    let mut server_output = false;
+    let mut on_sql_error: Option<OnSqlError> = None;
+    for statement in statements {
+        if let Some(directive) = applies_sqlplus
+            .then(|| sqlplus::directive(&statement.sql))
+            .flatten()
+        {
+            let exit = matches!(directive, Directive::Exit(_));
+            let outcome = cancel.check().and_then(|()| {
+                apply_directive(
+                    session,
+                    session_id,
+                    directive,
+                    &mut server_output,
+                    &mut on_sql_error,
+                    cancel,
+                )
+            });
+            if !on_result(statement, outcome, Vec::new()) || exit || cancel.check().is_err() {
+                break;
+            }
+            continue;
+        }
+
+        let outcome = cancel.check().and_then(|()| {
+            let request = query_request(session_id, statement.sql.clone(), row_limit);
+            ProviderRegistry::run_query(session, &request, cancel)
+        });
+        let output = if server_output {
+            ProviderRegistry::read_server_output(session).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let failed = outcome.is_err();
+        let keep_going = on_result(statement, outcome, output);
+        let keep_going = match on_sql_error.filter(|_| failed) {
+            Some(on_sql_error) => {
+                if let Some(sql) = on_sql_error.end_transaction {
+                    let request = query_request(session_id, sql.to_string(), None);
+                    let _ = ProviderRegistry::run_query(session, &request, cancel);
+                }
+                !on_sql_error.exit
+            }
+            None => keep_going,
+        };
+        if !keep_going || cancel.check().is_err() {
+            break;
+        }
+    }
+}
+
+fn apply_directive(
+    session: &mut AppSession,
+    session_id: u64,
+    directive: Directive,
+    server_output: &mut bool,
+    on_sql_error: &mut Option<OnSqlError>,
+    cancel: &CancelToken,
+) -> Result<DbQueryResult, String> {
+    let message = match directive {
+        Directive::ServerOutput(enabled) => {
+            ProviderRegistry::set_server_output(session, enabled)?;
+            *server_output = enabled;
+            format!("Server output {}.", if enabled { "on" } else { "off" })
+        }
+        Directive::WheneverSqlError(action) => {
+            *on_sql_error = Some(action);
+            format!(
+                "A failed statement will {}{}.",
+                if action.exit {
+                    "stop the script"
+                } else {
+                    "not stop the script"
+                },
+                action
+                    .end_transaction
+                    .map(|sql| format!(" after {sql}"))
+                    .unwrap_or_default()
+            )
+        }
+        Directive::Prompt(text) => text,
+        Directive::Exit(end_transaction) => {
+            if let Some(sql) = end_transaction {
+                let request = query_request(session_id, sql.to_string(), None);
+                ProviderRegistry::run_query(session, &request, cancel)?;
+            }
+            "Script exited.".to_string()
+        }
+        Directive::Applied => "Applied before the script ran.".to_string(),
+        Directive::Ignored => "SQL*Plus command ignored.".to_string(),
+    };
+    Ok(DbQueryResult {
+        columns: Vec::new(),
+        rows: Vec::new(),
+        null_flags: Vec::new(),
+        byte_lengths: Vec::new(),
+        rows_affected: None,
+        message,
+        lossy_conversion: false,
+        cursor_id: None,
+        required_variables: Vec::new(),
+        ref_cursors: Vec::new(),
+    })
+}
+
+fn query_request(session_id: u64, sql: String, row_limit: Option<u32>) -> DbQueryRequest {
+    DbQueryRequest {
+        session_id,
+        sql,
+        row_limit,
+        binds: Vec::new(),
+        sample_mode: false,
+        binary_preview_bytes: None,
+        production_confirmation: None,
+        timeout_secs: None,
+    }
+}
@@ -0,0 +1,507 @@
+use crate::sqlplus;
+use crate::types::{DatabaseProvider, DbSqlStatement};
+
+/// How many leading keywords are kept to recognise PL/SQL units; enough for
+/// `CREATE OR REPLACE NONEDITIONABLE PACKAGE BODY`.
+const LEADING_WORDS: usize = 6;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DollarQuotes {
+    None,
+    /// Snowflake only knows `$$ ... $$`.
+    Untagged,
+    /// Postgres and DuckDB also accept `$tag$ ... $tag$`.
+    Tagged,
+}
+
+/// The lexical rules that decide where one statement ends and the next begins.
+struct Dialect {
+    /// Oracle: `q'[...]'` literals, PL/SQL units that end at a `/` line, and `/` as a
+    /// terminator for plain statements.
+    oracle: bool,
+    /// SQLite: `CREATE TRIGGER ... BEGIN ... END;` bodies contain semicolons.
+    trigger_bodies: bool,
+    dollar_quotes: DollarQuotes,
+    backtick_identifiers: bool,
+    backslash_escapes: bool,
+    hash_comments: bool,
+    /// MySQL client `DELIMITER` lines.
+    delimiter_command: bool,
+}
+
+impl Dialect {
+    fn for_provider(provider: DatabaseProvider) -> Self {
+        let mut dialect = Self {
+            oracle: false,
+            trigger_bodies: false,
+            dollar_quotes: DollarQuotes::None,
+            backtick_identifiers: false,
+            backslash_escapes: false,
+            hash_comments: false,
+            delimiter_command: false,
+        };
+        match provider {
+            DatabaseProvider::Oracle => dialect.oracle = true,
+            DatabaseProvider::Postgres | DatabaseProvider::Duckdb => {
+                dialect.dollar_quotes = DollarQuotes::Tagged;
+            }
+            DatabaseProvider::Snowflake => dialect.dollar_quotes = DollarQuotes::Untagged,
+            DatabaseProvider::Mysql => {
+                dialect.backtick_identifiers = true;
+                dialect.backslash_escapes = true;
+                dialect.hash_comments = true;
+                dialect.delimiter_command = true;
+            }
+            DatabaseProvider::Clickhouse => {
+                dialect.backtick_identifiers = true;
+                dialect.backslash_escapes = true;
+            }
+            DatabaseProvider::Sqlite | DatabaseProvider::Libsql => {
+                dialect.backtick_identifiers = true;
+                dialect.trigger_bodies = true;
+            }
+            DatabaseProvider::External => {}
+        }
+        dialect
+    }
+}
+
+/// Splits a script into the statements a driver can execute one at a time.
+///
+/// Plain statements are returned without their terminator. PL/SQL units keep their final
+/// `END;` since Oracle needs it, and run until a line holding only `/`. Comments between
+/// statements are dropped; comments inside a statement are kept.
+pub(crate) fn split_statements(sql: &str, provider: DatabaseProvider) -> Vec<DbSqlStatement> {
+    Splitter {
+        sql,
+        bytes: sql.as_bytes(),
+        dialect: Dialect::for_provider(provider),
+        delimiter: ";".to_string(),
+        statements: Vec::new(),
+        start: None,
+        words: Vec::new(),
+        block_depth: 0,
+        saw_begin: false,
+        line: 1,
+        counted_to: 0,
+    }
+    .run()
+}
+
+struct Splitter<'a> {
+    sql: &'a str,
+    bytes: &'a [u8],
+    dialect: Dialect,
+    delimiter: String,
+    statements: Vec<DbSqlStatement>,
+    /// Byte offset of the first token of the statement being collected.
+    start: Option<usize>,
+    words: Vec<String>,
+    /// `BEGIN`/`CASE` minus `END` inside a SQLite trigger.
+    block_depth: i32,
+    saw_begin: bool,
+    line: u32,
+    counted_to: usize,
+}
+
+impl Splitter<'_> {
+    fn run(mut self) -> Vec<DbSqlStatement> {
+        let mut index = 0;
+        while index < self.bytes.len() {
+            let byte = self.bytes[index];
+            if byte.is_ascii_whitespace() {
+                index += 1;
+                continue;
+            }
+            if self.dialect.oracle && byte == b'/' {
+                if let Some(end) = self.slash_line_end(index) {
+                    self.finish(index);
+                    index = end;
+                    continue;
+                }
+            }
+            if let Some(end) = self.comment_end(index) {
+                index = end;
+                continue;
+            }
+            if self.bytes[index..].starts_with(self.delimiter.as_bytes()) && !self.in_block() {
+                self.finish(index);
+                index += self.delimiter.len();
+                continue;
+            }
+
+            let starts_statement = self.start.is_none();
+            if starts_statement {
+                self.start = Some(index);
+                if self.dialect.oracle {
+                    let end = self.line_end(index);
+                    if sqlplus::is_command(&self.sql[index..end]) {
+                        // SQL*Plus commands end with their line; the `;` is optional.
+                        let line = self.sql[index..end].trim_end();
+                        self.finish(index + line.strip_suffix(';').unwrap_or(line).len());
+                        index = end;
+                        continue;
+                    }
+                }
+            }
+            if let Some(end) = self.quoted_end(index, index) {
+                index = end;
+                continue;
+            }
+            if is_word_byte(byte, &self.dialect) {
+                let end = self.word_end(index);
+                let word = &self.sql[index..end];
+                if starts_statement
+                    && self.dialect.delimiter_command
+                    && word.eq_ignore_ascii_case("DELIMITER")
+                {
+                    index = self.change_delimiter(end);
+                    continue;
+                }
+                // A word directly followed by a quote may be a literal prefix (`q'[..]'`, `E'..'`).
+                if let Some(literal_end) = self.quoted_end(end, index) {
+                    index = literal_end;
+                    continue;
+                }
+                self.note_word(word);
+                index = end;
+                continue;
+            }
+            index += 1;
+        }
+        self.finish(self.bytes.len());
+        self.statements
+    }
+
+    fn finish(&mut self, end: usize) {
+        let Some(start) = self.start.take() else {
+            return;
+        };
+        let text = self.sql[start..end].trim_end();
+        self.line += self.bytes[self.counted_to..start]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count() as u32;
+        self.counted_to = start;
+        if !text.is_empty() {
+            self.statements.push(DbSqlStatement {
+                sql: text.to_string(),
+                line: self.line,
+            });
+        }
+        self.words.clear();
+        self.block_depth = 0;
+        self.saw_begin = false;
+    }
+
+    /// Whether a terminator at this point belongs to the statement body rather than ending it.
+    fn in_block(&self) -> bool {
+        if self.dialect.oracle {
+            return is_plsql_unit(&self.words);
+        }
+        if self.dialect.trigger_bodies && is_sqlite_trigger(&self.words) {
+            return !(self.saw_begin && self.block_depth <= 0);
+        }
+        false
+    }
+
+    fn note_word(&mut self, word: &str) {
+        let upper = word.to_ascii_uppercase();
+        if self.dialect.trigger_bodies {
+            match upper.as_str() {
+                "BEGIN" => {
+                    self.saw_begin = true;
+                    self.block_depth += 1;
+                }
+                "CASE" => self.block_depth += 1,
+                "END" => self.block_depth -= 1,
+                _ => {}
+            }
+        }
+        if self.words.len() < LEADING_WORDS {
+            self.words.push(upper);
+        }
+    }
+
+    /// `DELIMITER $$` swaps the terminator until the next `DELIMITER` line.
+    fn change_delimiter(&mut self, from: usize) -> usize {
+        let end = self.line_end(from);
+        let delimiter = self.sql[from..end].trim();
+        if !delimiter.is_empty() {
+            self.delimiter = delimiter.to_string();
+        }
+        self.start = None;
+        end
+    }
+
+    /// A `/` with nothing else on its line runs the buffer in SQL*Plus.
+    fn slash_line_end(&self, index: usize) -> Option<usize> {
+        let line_start = self.bytes[..index]
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |position| position + 1);
+        let end = self.line_end(index);
+        let blank = |bytes: &[u8]| bytes.iter().all(u8::is_ascii_whitespace);
+        (blank(&self.bytes[line_start..index]) && blank(&self.bytes[index + 1..end])).then_some(end)
+    }
+
+    fn line_end(&self, from: usize) -> usize {
+        self.bytes[from..]
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(self.bytes.len(), |position| from + position)
+    }
+
+    fn comment_end(&self, index: usize) -> Option<usize> {
+        let rest = &self.bytes[index..];
+        if rest.starts_with(b"--") || (self.dialect.hash_comments && rest[0] == b'#') {
+            return Some(self.line_end(index));
+        }
+        if rest.starts_with(b"/*") {
+            return Some(
+                find(self.bytes, index + 2, b"*/").map_or(self.bytes.len(), |end| end + 2),
+            );
+        }
+        None
+    }
+
+    /// Words also stop at the delimiter, since MySQL scripts write `END$$`.
+    fn word_end(&self, from: usize) -> usize {
+        let mut position = from;
+        while position < self.bytes.len()
+            && is_word_byte(self.bytes[position], &self.dialect)
+            && (position == from || !self.bytes[position..].starts_with(self.delimiter.as_bytes()))
+        {
+            position += 1;
+        }
+        position
+    }
+
+    /// End of a quoted literal or identifier opening at `index`. `prefix_start` is where a
+    /// literal prefix such as `q`, `N` or `E` begins, or `index` when there is none.
+    fn quoted_end(&self, index: usize, prefix_start: usize) -> Option<usize> {
+        let quote = *self.bytes.get(index)?;
+        let prefix = self.sql[prefix_start..index].to_ascii_uppercase();
+        if index > prefix_start && quote != b'\'' {
+            return None;
+        }
+        match quote {
+            b'\'' if self.dialect.oracle && (prefix == "Q" || prefix == "NQ") => {
+                Some(self.q_literal_end(index + 1))
+            }
+            b'\'' => match prefix.as_str() {
+                "" | "N" | "X" | "B" => {
+                    Some(self.delimited_end(index, b'\'', self.dialect.backslash_escapes))
+                }
+                "E" if self.dialect.dollar_quotes == DollarQuotes::Tagged => {
+                    Some(self.delimited_end(index, b'\'', true))
+                }
+                _ => None,
+            },
+            b'"' => Some(self.delimited_end(index, b'"', self.dialect.backslash_escapes)),
+            b'`' if self.dialect.backtick_identifiers => {
+                Some(self.delimited_end(index, b'`', false))
+            }
+            b'$' => self.dollar_quoted_end(index),
+            _ => None,
+        }
+    }
+
+    /// Quotes are escaped by doubling them, and by a backslash where the dialect allows it.
+    fn delimited_end(&self, index: usize, quote: u8, backslash_escapes: bool) -> usize {
+        let mut position = index + 1;
+        while position < self.bytes.len() {
+            let byte = self.bytes[position];
+            if backslash_escapes && byte == b'\\' {
+                position += 2;
+                continue;
+            }
+            if byte == quote {
+                if self.bytes.get(position + 1) == Some(&quote) {
+                    position += 2;
+                    continue;
+                }
+                return position + 1;
+            }
+            position += 1;
+        }
+        self.bytes.len()
+    }
+
+    /// `q'[...]'`: the character after the quote picks the closing delimiter.
+    fn q_literal_end(&self, delimiter_index: usize) -> usize {
+        let Some(open) = self.bytes.get(delimiter_index) else {
+            return self.bytes.len();
+        };
+        let close = match open {
+            b'[' => b']',
+            b'{' => b'}',
+            b'(' => b')',
+            b'<' => b'>',
+            other => *other,
+        };
+        find(self.bytes, delimiter_index + 1, &[close, b'\''])
+            .map_or(self.bytes.len(), |end| end + 2)
+    }
+
+    fn dollar_quoted_end(&self, index: usize) -> Option<usize> {
+        if self.dialect.dollar_quotes == DollarQuotes::None {
+            return None;
+        }
+        let tag_len = self.bytes[index + 1..]
+            .iter()
+            .position(|byte| !(byte.is_ascii_alphanumeric() || *byte == b'_'))?;
+        let tag_end = index + 1 + tag_len;
+        if self.bytes.get(tag_end) != Some(&b'$')
+            || (tag_len > 0 && self.dialect.dollar_quotes == DollarQuotes::Untagged)
+            || self.bytes[index + 1..tag_end]
+                .first()
+                .is_some_and(u8::is_ascii_digit)
+        {
+            return None;
+        }
+        let tag = &self.bytes[index..=tag_end];
+        Some(find(self.bytes, tag_end + 1, tag).map_or(self.bytes.len(), |end| end + tag.len()))
+    }
+}
+
+fn find(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| from + position)
+}
+
+/// Non-ASCII bytes count as word characters so multi-byte identifiers stay whole.
+fn is_word_byte(byte: u8, dialect: &Dialect) -> bool {
+    byte.is_ascii_alphanumeric()
+        || byte == b'_'
+        || byte == b'$'
+        || (byte == b'#' && !dialect.hash_comments)
+        || !byte.is_ascii()
+}
+
+/// Anonymous blocks and the stored units SQL*Plus collects until a `/` line.
+fn is_plsql_unit(words: &[String]) -> bool {
+    let mut words = words.iter().map(String::as_str);
+    match words.next() {
+        Some("DECLARE" | "BEGIN") => true,
+        Some("CREATE") => {
+            let mut word = words.next();
+            if word == Some("OR") {
+                words.next();
+                word = words.next();
+            }
+            if matches!(word, Some("EDITIONABLE" | "NONEDITIONABLE")) {
+                word = words.next();
+            }
+            matches!(
+                word,
+                Some("PROCEDURE" | "FUNCTION" | "PACKAGE" | "TRIGGER" | "TYPE" | "LIBRARY")
+            )
+        }
+        _ => false,
+    }
+}
+
+fn is_sqlite_trigger(words: &[String]) -> bool {
+    let mut words = words.iter().map(String::as_str);
+    if words.next() != Some("CREATE") {
+        return false;
+    }
+    let word = words.next();
+    if matches!(word, Some("TEMP" | "TEMPORARY")) {
+        return words.next() == Some("TRIGGER");
+    }
+    word == Some("TRIGGER")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_statements;
+    use crate::types::DatabaseProvider;
+
+    fn split(sql: &str, provider: DatabaseProvider) -> Vec<String> {
+        split_statements(sql, provider)
+            .into_iter()
+            .map(|statement| statement.sql)
+            .collect()
+    }
+
+    #[test]
+    fn splits_oracle_scripts_with_literals_comments_and_plsql() {
+        let script = "-- setup\n\
+            insert into t values ('a;b', q'[it's; here]', \"x;y\");\n\
+            /* block; comment */ select 1 from dual\n\
+            /\n\
+            create or replace package body pkg as\n  procedure p is begin null; end;\nend pkg;\n/\n\
+            begin\n  dbms_output.put_line('/');\nend;\n/\n\
+            select q'{a'b}' from dual;";
+        let statements = split_statements(script, DatabaseProvider::Oracle);
+        let texts = statements
+            .iter()
+            .map(|statement| statement.sql.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "insert into t values ('a;b', q'[it's; here]', \"x;y\")",
+                "select 1 from dual",
+                "create or replace package body pkg as\n  procedure p is begin null; end;\nend pkg;",
+                "begin\n  dbms_output.put_line('/');\nend;",
+                "select q'{a'b}' from dual",
+            ]
+        );
+        assert_eq!(
+            statements
+                .iter()
+                .map(|statement| statement.line)
+                .collect::<Vec<_>>(),
+            vec![2, 3, 5, 9, 13]
+        );
+        assert_eq!(
+            split("select 1 / 2 from dual", DatabaseProvider::Oracle).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn applies_provider_quoting_and_block_rules() {
+        assert_eq!(
+            split(
+                "create function f() returns int as $body$ select 1; $body$ language sql;\nselect E'a\\';b';",
+                DatabaseProvider::Postgres
+            ),
+            vec![
+                "create function f() returns int as $body$ select 1; $body$ language sql",
+                "select E'a\\';b'",
+            ]
+        );
+        assert_eq!(
+            split(
+                "select `a;b`, 'it\\'s;' # note;\nfrom t;\nDELIMITER $$\ncreate procedure p() begin select 1; end$$\nDELIMITER ;\nselect 2;",
+                DatabaseProvider::Mysql
+            ),
+            vec![
+                "select `a;b`, 'it\\'s;' # note;\nfrom t",
+                "create procedure p() begin select 1; end",
+                "select 2",
+            ]
+        );
+        assert_eq!(
+            split(
+                "create trigger trg after insert on t begin update u set n = case when 1 then 2 end; insert into v values (1); end;\nselect 1;",
+                DatabaseProvider::Sqlite
+            ),
+            vec![
+                "create trigger trg after insert on t begin update u set n = case when 1 then 2 end; insert into v values (1); end",
+                "select 1",
+            ]
+        );
+        assert_eq!(
+            split("select 1 /\n/\nselect 2", DatabaseProvider::Postgres),
+            vec!["select 1 /\n/\nselect 2"]
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use crate::sql_splitter::split_statements;
+use crate::types::{DatabaseProvider, DbSqlStatement};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Deeper nesting is refused, which also stops a script that includes itself.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// `SET` statements that are SQL rather than SQL*Plus settings.
+const SQL_SET_STATEMENTS: [&str; 4] = ["CONSTRAINT", "CONSTRAINTS", "ROLE", "TRANSACTION"];
+
+/// A SQL*Plus command left in a prepared script, for the script runner to apply. `SHOW`
+/// is not one: the Oracle provider runs it as a query.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Directive {
+    /// `SET SERVEROUTPUT ON|OFF`: collect `DBMS_OUTPUT` after each statement.
+    ServerOutput(bool),
+    WheneverSqlError(OnSqlError),
+    Prompt(String),
+    /// `EXIT` or `QUIT`, with the `COMMIT` or `ROLLBACK` it asks for.
+    Exit(Option<&'static str>),
+    /// Already applied by `prepare`: `DEFINE`, `UNDEFINE`, `SET DEFINE`.
+    Applied,
+    /// No effect in Clarity: `SPOOL`, `REM` and other `SET` options such as `ECHO`.
+    Ignored,
+}
+
+/// What `WHENEVER SQLERROR` does after a statement fails.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct OnSqlError {
+    /// `EXIT` stops the script, `CONTINUE` carries on.
+    pub(crate) exit: bool,
+    /// `COMMIT` or `ROLLBACK` to run first. Unlike SQL*Plus, `EXIT` does not commit by
+    /// default: the session stays open, so the choice is left to the user.
+    pub(crate) end_transaction: Option<&'static str>,
+}
+
+/// Whether a line at the start of a statement is a SQL*Plus command, which ends at the end
+/// of the line rather than at a `;`.
+pub(crate) fn is_command(line: &str) -> bool {
+    let line = line.trim_start();
+    if line.starts_with('@') {
+        return true;
+    }
+    let words = words(line);
+    match words.first().map(String::as_str) {
+        Some(
+            "DEF" | "DEFINE" | "UNDEF" | "UNDEFINE" | "PRO" | "PROMPT" | "REM" | "REMARK" | "SPO"
+            | "SPOOL" | "WHENEVER" | "EXIT" | "QUIT" | "STA" | "START",
+        ) => true,
+        Some("SET") => words
+            .get(1)
+            .is_some_and(|option| !SQL_SET_STATEMENTS.contains(&option.as_str())),
+        _ => false,
+    }
+}
+
+/// The command a statement of a prepared script holds, or `None` for SQL.
+pub(crate) fn directive(sql: &str) -> Option<Directive> {
+    if !is_command(sql) {
+        return None;
+    }
+    let words = words(sql);
+    let has = |word: &str| words.iter().any(|candidate| candidate == word);
+    let end_transaction = if has("ROLLBACK") {
+        Some("ROLLBACK")
+    } else if has("COMMIT") {
+        Some("COMMIT")
+    } else {
+        None
+    };
+    let option = words.get(1).map(String::as_str).unwrap_or_default();
+    Some(match words[0].as_str() {
+        "PRO" | "PROMPT" => Directive::Prompt(rest_of_line(sql).to_string()),
+        "EXIT" | "QUIT" => Directive::Exit(end_transaction),
+        "WHENEVER" if option == "SQLERROR" => Directive::WheneverSqlError(OnSqlError {
+            exit: words.get(2).is_some_and(|action| action == "EXIT"),
+            end_transaction,
+        }),
+        "SET" if abbreviates(option, "SERVEROUTPUT", 9) => {
+            Directive::ServerOutput(words.get(2).is_some_and(|value| value == "ON"))
+        }
+        "DEF" | "DEFINE" | "UNDEF" | "UNDEFINE" => Directive::Applied,
+        "SET" if abbreviates(option, "DEFINE", 3) => Directive::Applied,
+        _ => Directive::Ignored,
+    })
+}
+
+/// Reads an Oracle script the way SQL*Plus would before it runs: `@file`, `@@file` and
+/// `START file` are replaced by that file's statements, and `&name` by the value `DEFINE`
+/// gave it. Relative `@` and `START` paths resolve against `base_directory`, `@@` paths
+/// against the directory of the including file. `substitutions` are values given up front,
+/// which `DEFINE` can override. A variable without a value refuses the script, since there
+/// is no one to prompt.
+pub(crate) fn prepare(
+    statements: Vec<DbSqlStatement>,
+    base_directory: Option<&Path>,
+    substitutions: &HashMap<String, String>,
+) -> Result<Vec<DbSqlStatement>, String> {
+    let mut preparer = Preparer {
+        base_directory,
+        variables: upper_case_names(substitutions),
+        prefix: Some('&'),
+        prepared: Vec::new(),
+    };
+    preparer.add(statements, base_directory, 0)?;
+    Ok(preparer.prepared)
+}
+
+struct Preparer<'a> {
+    base_directory: Option<&'a Path>,
+    /// Upper-cased names.
+    variables: HashMap<String, String>,
+    /// `None` after `SET DEFINE OFF`.
+    prefix: Option<char>,
+    prepared: Vec<DbSqlStatement>,
+}
+
+impl Preparer<'_> {
+    fn add(
+        &mut self,
+        statements: Vec<DbSqlStatement>,
+        directory: Option<&Path>,
+        depth: usize,
+    ) -> Result<(), String> {
+        for statement in statements {
+            let sql = self.substitute(&statement.sql).map_err(|name| {
+                format!(
+                    "Nothing was run: substitution variable &{name} on line {} is not defined. DEFINE it first, pass a value in substitutions, or SET DEFINE OFF if the & is meant literally.",
+                    statement.line
+                )
+            })?;
+            if is_command(&sql) {
+                let words = words(&sql);
+                let option = words.get(1).map(String::as_str).unwrap_or_default();
+                match words[0].as_str() {
+                    "DEF" | "DEFINE" => self.define(rest_of_line(&sql)),
+                    "UNDEF" | "UNDEFINE" => {
+                        for name in rest_of_line(&sql).split_whitespace() {
+                            self.variables.remove(&name.to_ascii_uppercase());
+                        }
+                    }
+                    "SET" if abbreviates(option, "DEFINE", 3) => self.set_define(&sql),
+                    first
+                        if sql.trim_start().starts_with('@')
+                            || matches!(first, "STA" | "START") =>
+                    {
+                        self.include(&sql, directory, depth)?;
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            self.prepared.push(DbSqlStatement {
+                sql,
+                line: statement.line,
+            });
+        }
+        Ok(())
+    }
+
+    /// `DEFINE name = value`; the value may be quoted. `DEFINE name` only lists a value
+    /// in SQL*Plus and changes nothing.
+    fn define(&mut self, definition: &str) {
+        let Some((name, value)) = definition.split_once('=') else {
+            return;
+        };
+        let name = name.trim().to_ascii_uppercase();
+        if !name.is_empty() {
+            self.variables.insert(
+                name,
+                unquote(value.trim().trim_end_matches(';')).to_string(),
+            );
+        }
+    }
+
+    /// `SET DEFINE OFF`, `SET DEFINE ON`, or `SET DEFINE <char>` to change the prefix.
+    fn set_define(&mut self, sql: &str) {
+        let value = rest_of_line(rest_of_line(sql)).trim_end_matches(';').trim();
+        self.prefix = if value.eq_ignore_ascii_case("OFF") {
+            None
+        } else if value.eq_ignore_ascii_case("ON") {
+            Some('&')
+        } else {
+            match value.chars().collect::<Vec<_>>()[..] {
+                [prefix] => Some(prefix),
+                _ => self.prefix,
+            }
+        };
+    }
+
+    /// Splices in another script. Arguments after the path become `&1`, `&2`, ...
+    fn include(&mut self, sql: &str, directory: Option<&Path>, depth: usize) -> Result<(), String> {
+        let command = sql.trim().trim_end_matches(';');
+        let (relative_to, target) = if let Some(target) = command.strip_prefix("@@") {
+            (directory, target)
+        } else if let Some(target) = command.strip_prefix('@') {
+            (self.base_directory, target)
+        } else {
+            (self.base_directory, rest_of_line(command))
+        };
+        let mut arguments = target.split_whitespace().map(unquote);
+        let Some(file) = arguments.next() else {
+            return Err(format!("Nothing was run: '{command}' names no script."));
+        };
+        if depth >= MAX_INCLUDE_DEPTH {
+            return Err(format!(
+                "Nothing was run: scripts are nested more than {MAX_INCLUDE_DEPTH} deep at '{command}'."
+            ));
+        }
+
+        let mut path = PathBuf::from(file);
+        if path.extension().is_none() {
+            path.set_extension("sql");
+        }
+        if path.is_relative() {
+            let Some(relative_to) = relative_to else {
+                return Err(format!(
+                    "Nothing was run: '{command}' uses a relative path, but the script has no location to resolve it from."
+                ));
+            };
+            path = relative_to.join(path);
+        }
+        let contents = fs::read_to_string(&path).map_err(|error| {
+            format!(
+                "Nothing was run: failed to read '{}' for '{command}': {error}",
+                path.display()
+            )
+        })?;
+        for (index, argument) in arguments.enumerate() {
+            self.variables
+                .insert((index + 1).to_string(), argument.to_string());
+        }
+        let statements = split_statements(&contents, DatabaseProvider::Oracle);
+        self.add(statements, path.parent(), depth + 1)
+    }
+
+    /// Returns the name of an undefined variable as the error.
+    fn substitute(&self, sql: &str) -> Result<String, String> {
+        let Some(prefix) = self.prefix else {
+            return Ok(sql.to_string());
+        };
+        replace_variables(sql, prefix, |name| {
+            self.variables
+                .get(&name.to_ascii_uppercase())
+                .cloned()
+                .ok_or_else(|| name.to_string())
+        })
+    }
+}
+
+/// Substitution variables of a statement that `values` has no value for, in the order they
+/// first appear, each once. Like SQL*Plus, `&` inside string literals counts too.
+pub(crate) fn missing_variables(sql: &str, values: &HashMap<String, String>) -> Vec<String> {
+    let values = upper_case_names(values);
+    let mut names = Vec::<String>::new();
+    let _ = replace_variables(sql, '&', |name| {
+        if !values.contains_key(&name.to_ascii_uppercase())
+            && !names.iter().any(|seen| seen.eq_ignore_ascii_case(name))
+        {
+            names.push(name.to_string());
+        }
+        Ok(String::new())
+    });
+    names
+}
+
+/// Replaces substitution variables with `values`, whose names match case-insensitively.
+/// Returns the name of a variable without a value as the error.
+pub(crate) fn substitute_variables(
+    sql: &str,
+    values: &HashMap<String, String>,
+) -> Result<String, String> {
+    let values = upper_case_names(values);
+    replace_variables(sql, '&', |name| {
+        values
+            .get(&name.to_ascii_uppercase())
+            .cloned()
+            .ok_or_else(|| name.to_string())
+    })
+}
+
+/// Replaces `&name` and `&&name` with `value(name)`; a `.` right after the name ends it and
+/// is dropped. A prefix not followed by a name is kept.
+fn replace_variables(
+    sql: &str,
+    prefix: char,
+    mut value: impl FnMut(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    if !sql.contains(prefix) {
+        return Ok(sql.to_string());
+    }
+
+    let mut substituted = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != prefix {
+            substituted.push(ch);
+            continue;
+        }
+        let doubled = chars.next_if_eq(&prefix).is_some();
+        let mut name = String::new();
+        while let Some(ch) = chars.next_if(|ch| ch.is_alphanumeric() || "_$#".contains(*ch)) {
+            name.push(ch);
+        }
+        if name.is_empty() {
+            substituted.push(prefix);
+            if doubled {
+                substituted.push(prefix);
+            }
+            continue;
+        }
+        substituted.push_str(&value(&name)?);
+        chars.next_if_eq(&'.');
+    }
+    Ok(substituted)
+}
+
+fn upper_case_names(values: &HashMap<String, String>) -> HashMap<String, String> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_ascii_uppercase(), value.clone()))
+        .collect()
+}
+
+/// Upper-cased words, without a trailing `;`.
+fn words(line: &str) -> Vec<String> {
+    line.split_whitespace()
+        .map(|word| word.trim_end_matches(';').to_ascii_uppercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// The line after its first word.
+fn rest_of_line(line: &str) -> &str {
+    let line = line.trim_start();
+    line.split_once(char::is_whitespace)
+        .map_or("", |(_, rest)| rest.trim())
+}
+
+/// SQL*Plus accepts commands and options shortened to at least `shortest` characters.
+fn abbreviates(word: &str, full: &str, shortest: usize) -> bool {
+    word.len() >= shortest && full.starts_with(word)
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['\'', '"'] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        directive, missing_variables, prepare, substitute_variables, Directive, OnSqlError,
+    };
+    use crate::sql_splitter::split_statements;
+    use crate::types::DatabaseProvider;
+    use std::collections::HashMap;
+    use std::fs;
+
+    #[test]
+    fn substitutes_defines_and_splices_in_included_scripts() {
+        let directory =
+            std::env::temp_dir().join(format!("clarity-sqlplus-{}", std::process::id()));
+        fs::create_dir_all(directory.join("tables")).unwrap();
+        fs::write(
+            directory.join("tables/orders.sql"),
+            "CREATE TABLE &owner..orders (id NUMBER);\nPROMPT created by &1\n",
+        )
+        .unwrap();
+        let script = "DEFINE owner = 'APP'\n\
+                      SET SERVEROUTPUT ON\n\
+                      WHENEVER SQLERROR EXIT ROLLBACK\n\
+                      @tables/orders deploy\n\
+                      SET DEFINE OFF\n\
+                      INSERT INTO notes VALUES ('R&D');\n\
+                      SET TRANSACTION READ ONLY;";
+        let statements = split_statements(script, DatabaseProvider::Oracle);
+        let prepared = prepare(statements, Some(&directory), &HashMap::new()).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let sql = prepared
+            .iter()
+            .map(|statement| statement.sql.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sql,
+            vec![
+                "DEFINE owner = 'APP'",
+                "SET SERVEROUTPUT ON",
+                "WHENEVER SQLERROR EXIT ROLLBACK",
+                "CREATE TABLE APP.orders (id NUMBER)",
+                "PROMPT created by deploy",
+                "SET DEFINE OFF",
+                "INSERT INTO notes VALUES ('R&D')",
+                "SET TRANSACTION READ ONLY",
+            ]
+        );
+        assert_eq!(directive(sql[1]), Some(Directive::ServerOutput(true)));
+        assert_eq!(
+            directive(sql[2]),
+            Some(Directive::WheneverSqlError(OnSqlError {
+                exit: true,
+                end_transaction: Some("ROLLBACK"),
+            }))
+        );
+        assert_eq!(
+            directive(sql[4]),
+            Some(Directive::Prompt("created by deploy".to_string()))
+        );
+        assert_eq!(directive(sql[7]), None);
+
+        let undefined = split_statements("SELECT * FROM &missing.t;", DatabaseProvider::Oracle);
+        let error = prepare(undefined, None, &HashMap::new()).unwrap_err();
+        assert!(error.contains("&missing on line 1"), "{error}");
+    }
+
+    #[test]
+    fn lists_missing_variables_once_and_substitutes_given_values() {
+        let sql = "SELECT * FROM &&owner..orders WHERE status = '&status' AND region = &Owner";
+        let mut values = HashMap::new();
+        assert_eq!(missing_variables(sql, &values), vec!["owner", "status"]);
+
+        values.insert("OWNER".to_string(), "APP".to_string());
+        assert_eq!(missing_variables(sql, &values), vec!["status"]);
+        assert_eq!(substitute_variables(sql, &values).unwrap_err(), "status");
+
+        values.insert("status".to_string(), "OPEN".to_string());
+        assert!(missing_variables(sql, &values).is_empty());
+        assert_eq!(
+            substitute_variables(sql, &values).unwrap(),
+            "SELECT * FROM APP.orders WHERE status = 'OPEN' AND region = APP"
+        );
+        assert_eq!(substitute_variables("a && b", &values).unwrap(), "a && b");
+    }
+}
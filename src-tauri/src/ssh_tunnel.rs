@@ -0,0 +1,402 @@
+use crate::types::{
+    DatabaseProvider, DbConnectConnection, DbConnectRequest, SshTunnelConnectOptions,
+};
+use std::fs;
+use std::io::Read;
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const DEFAULT_SSH_CLI: &str = "ssh";
+const DEFAULT_SSH_PORT: u16 = 22;
+const DEFAULT_ORACLE_PORT: u16 = 1521;
+const DEFAULT_POSTGRES_PORT: u16 = 5432;
+const DEFAULT_MYSQL_PORT: u16 = 3306;
+const DEFAULT_CLICKHOUSE_HTTP_PORT: u16 = 8123;
+const DEFAULT_CLICKHOUSE_HTTPS_PORT: u16 = 8443;
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const MAX_STDERR_CHARS: usize = 4000;
+const PASSWORD_ENV: &str = "CLARITY_SSH_PASSWORD";
+
+// There is no SSH library in the build, so the tunnel is an `ssh -N -L` process owned by
+// the session. Keys and agents work as they do on the command line; a password or key
+// passphrase is answered through `SSH_ASKPASS`, which needs OpenSSH 8.4 or later.
+pub(crate) struct SshTunnel {
+    child: Child,
+    local_port: u16,
+    askpass_path: Option<PathBuf>,
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        if let Some(path) = &self.askpass_path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Opens the tunnel for `request` and returns it with a copy of the request that points
+/// at the forwarded local port. Only host-based providers can be tunneled.
+pub(crate) fn open_for_request(
+    request: &DbConnectRequest,
+    options: &SshTunnelConnectOptions,
+) -> Result<(SshTunnel, DbConnectRequest), String> {
+    let (target_host, target_port) = connection_target(&request.connection)?;
+    let tunnel = open(options, target_host.as_str(), target_port)?;
+
+    let mut tunneled = request.clone();
+    tunneled.ssh_tunnel = None;
+    let local_host = Ipv4Addr::LOCALHOST.to_string();
+    let local_port = Some(tunnel.local_port);
+    match &mut tunneled.connection {
+        DbConnectConnection::Oracle(connection) => {
+            connection.host = local_host;
+            connection.port = local_port;
+        }
+        DbConnectConnection::Postgres(connection) | DbConnectConnection::Mysql(connection) => {
+            connection.host = local_host;
+            connection.port = local_port;
+        }
+        DbConnectConnection::Clickhouse(connection) => {
+            // Keep the scheme; HTTPS through a tunnel needs a certificate valid for localhost.
+            connection.host = match connection.host.trim().split_once("://") {
+                Some((scheme, _)) => format!("{scheme}://{local_host}"),
+                None => local_host,
+            };
+            connection.port = local_port;
+        }
+        _ => unreachable!("connection_target accepts host-based providers only"),
+    }
+
+    Ok((tunnel, tunneled))
+}
+
+fn connection_target(connection: &DbConnectConnection) -> Result<(String, u16), String> {
+    let (host, port) = match connection {
+        DbConnectConnection::Oracle(connection) => (
+            connection.host.as_str(),
+            Some(
+                connection
+                    .port
+                    .unwrap_or(connection.protocol.default_port()),
+            ),
+        ),
+        DbConnectConnection::Postgres(connection)
+        | DbConnectConnection::Mysql(connection)
+        | DbConnectConnection::Clickhouse(connection) => {
+            (connection.host.as_str(), connection.port)
+        }
+        _ => ("", None),
+    };
+    network_target(connection.provider(), host, port).ok_or_else(|| {
+        format!(
+            "SSH tunnels are not supported for {} connections.",
+            connection.provider().label()
+        )
+    })
+}
+
+/// Host and port a host-based provider connects to, filling in the provider's default
+/// port. `None` for providers that are not reached through a host and port.
+pub(crate) fn network_target(
+    provider: DatabaseProvider,
+    host: &str,
+    port: Option<u16>,
+) -> Option<(String, u16)> {
+    let host = host.trim();
+    match provider {
+        DatabaseProvider::Oracle => Some((host.to_string(), port.unwrap_or(DEFAULT_ORACLE_PORT))),
+        DatabaseProvider::Postgres => {
+            Some((host.to_string(), port.unwrap_or(DEFAULT_POSTGRES_PORT)))
+        }
+        DatabaseProvider::Mysql => Some((host.to_string(), port.unwrap_or(DEFAULT_MYSQL_PORT))),
+        DatabaseProvider::Clickhouse => {
+            let host = host.trim_end_matches('/');
+            let (default_port, authority) = match host.split_once("://") {
+                Some((scheme, rest)) if scheme.eq_ignore_ascii_case("https") => {
+                    (DEFAULT_CLICKHOUSE_HTTPS_PORT, rest)
+                }
+                Some((_, rest)) => (DEFAULT_CLICKHOUSE_HTTP_PORT, rest),
+                None => (DEFAULT_CLICKHOUSE_HTTP_PORT, host),
+            };
+            let (host, url_port) = match authority.rsplit_once(':') {
+                Some((host, url_port)) if url_port.parse::<u16>().is_ok() => {
+                    (host, url_port.parse::<u16>().ok())
+                }
+                _ => (authority, None),
+            };
+            Some((host.to_string(), url_port.or(port).unwrap_or(default_port)))
+        }
+        DatabaseProvider::Sqlite
+        | DatabaseProvider::Duckdb
+        | DatabaseProvider::Snowflake
+        | DatabaseProvider::Libsql
+        | DatabaseProvider::External => None,
+    }
+}
+
+fn open(
+    options: &SshTunnelConnectOptions,
+    target_host: &str,
+    target_port: u16,
+) -> Result<SshTunnel, String> {
+    let local_port = free_local_port()?;
+    let password = options
+        .password
+        .as_deref()
+        .filter(|value| !value.is_empty());
+    let askpass_path = password.map(|_| write_askpass_script()).transpose()?;
+
+    let mut command = Command::new(
+        options
+            .cli_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|cli_path| !cli_path.is_empty())
+            .unwrap_or(DEFAULT_SSH_CLI),
+    );
+    command.args(ssh_arguments(options, local_port, target_host, target_port));
+    if let (Some(password), Some(path)) = (password, &askpass_path) {
+        command
+            .env("SSH_ASKPASS", path)
+            .env("SSH_ASKPASS_REQUIRE", "force")
+            .env(PASSWORD_ENV, password);
+        if std::env::var_os("DISPLAY").is_none() {
+            // Older clients only consult SSH_ASKPASS when DISPLAY is set.
+            command.env("DISPLAY", ":0");
+        }
+    }
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            if let Some(path) = &askpass_path {
+                let _ = fs::remove_file(path);
+            }
+            return Err(format!("Failed to start ssh: {error}"));
+        }
+    };
+    let stderr = Arc::new(Mutex::new(String::new()));
+    if let Some(mut pipe) = child.stderr.take() {
+        let stderr = Arc::clone(&stderr);
+        // Drain stderr for the life of the process so a chatty server cannot block it.
+        std::thread::spawn(move || {
+            let mut buffer = [0_u8; 1024];
+            while let Ok(read) = pipe.read(&mut buffer) {
+                if read == 0 {
+                    break;
+                }
+                if let Ok(mut text) = stderr.lock() {
+                    text.push_str(&String::from_utf8_lossy(&buffer[..read]));
+                    if text.len() > MAX_STDERR_CHARS {
+                        let cut = text.len() - MAX_STDERR_CHARS;
+                        let cut = (cut..text.len())
+                            .find(|index| text.is_char_boundary(*index))
+                            .unwrap_or(text.len());
+                        text.drain(..cut);
+                    }
+                }
+            }
+        });
+    }
+
+    let tunnel = SshTunnel {
+        child,
+        local_port,
+        askpass_path,
+    };
+    wait_until_listening(tunnel, &stderr, options.host.trim())
+}
+
+/// Waits for ssh to accept connections on the local port, which it does only after the
+/// bastion has authenticated the user.
+fn wait_until_listening(
+    mut tunnel: SshTunnel,
+    stderr: &Mutex<String>,
+    bastion: &str,
+) -> Result<SshTunnel, String> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, tunnel.local_port));
+    let started = Instant::now();
+    loop {
+        if let Ok(Some(status)) = tunnel.child.try_wait() {
+            // Give the reader thread a moment to collect the final message.
+            std::thread::sleep(STARTUP_POLL_INTERVAL);
+            let detail = stderr
+                .lock()
+                .map(|text| text.trim().to_string())
+                .unwrap_or_default();
+            return Err(if detail.is_empty() {
+                format!("SSH tunnel through {bastion} exited with {status}")
+            } else {
+                format!("SSH tunnel through {bastion} failed: {detail}")
+            });
+        }
+        if TcpStream::connect_timeout(&address, STARTUP_POLL_INTERVAL).is_ok() {
+            return Ok(tunnel);
+        }
+        if started.elapsed() > STARTUP_TIMEOUT {
+            return Err(format!(
+                "Timed out waiting for the SSH tunnel through {bastion}"
+            ));
+        }
+        std::thread::sleep(STARTUP_POLL_INTERVAL);
+    }
+}
+
+fn ssh_arguments(
+    options: &SshTunnelConnectOptions,
+    local_port: u16,
+    target_host: &str,
+    target_port: u16,
+) -> Vec<String> {
+    let has_password = options
+        .password
+        .as_deref()
+        .is_some_and(|value| !value.is_empty());
+    let mut args = vec![
+        "-N".to_string(),
+        "-o".to_string(),
+        "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(),
+        "ServerAliveInterval=30".to_string(),
+        "-o".to_string(),
+        "ConnectTimeout=15".to_string(),
+        // Never stop at an interactive prompt; without a password, keys and agents only.
+        "-o".to_string(),
+        format!("BatchMode={}", if has_password { "no" } else { "yes" }),
+        "-L".to_string(),
+        format!(
+            "{}:{local_port}:{target_host}:{target_port}",
+            Ipv4Addr::LOCALHOST
+        ),
+        "-p".to_string(),
+        options.port.unwrap_or(DEFAULT_SSH_PORT).to_string(),
+    ];
+    if let Some(identity_file) = options
+        .identity_file
+        .as_deref()
+        .map(str::trim)
+        .filter(|identity_file| !identity_file.is_empty())
+    {
+        args.extend([
+            "-i".to_string(),
+            identity_file.to_string(),
+            "-o".to_string(),
+            "IdentitiesOnly=yes".to_string(),
+        ]);
+    }
+    // Ends option parsing, so the destination is never read as an option.
+    args.push("--".to_string());
+    args.push(format!(
+        "{}@{}",
+        options.username.trim(),
+        options.host.trim()
+    ));
+    args
+}
+
+/// Asks the OS for a free port. Another process could take it before ssh binds it, in
+/// which case `ExitOnForwardFailure` makes ssh exit and the connect fails with its message.
+fn free_local_port() -> Result<u16, String> {
+    TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|address| address.port())
+        .map_err(|error| format!("Failed to reserve a local port for the SSH tunnel: {error}"))
+}
+
+/// Writes a helper that prints the password from the environment, so the secret itself
+/// never touches the disk.
+fn write_askpass_script() -> Result<PathBuf, String> {
+    let unique = format!("{}-{}", std::process::id(), crate::workspace::now_millis());
+    #[cfg(target_os = "windows")]
+    let (path, script) = (
+        std::env::temp_dir().join(format!("clarity-askpass-{unique}.cmd")),
+        format!("@echo off\r\npowershell -NoProfile -Command \"[Console]::Out.WriteLine($env:{PASSWORD_ENV})\"\r\n"),
+    );
+    #[cfg(not(target_os = "windows"))]
+    let (path, script) = (
+        std::env::temp_dir().join(format!("clarity-askpass-{unique}.sh")),
+        format!("#!/bin/sh\nprintf '%s\\n' \"${PASSWORD_ENV}\"\n"),
+    );
+
+    fs::write(&path, script)
+        .map_err(|error| format!("Failed to write SSH askpass helper: {error}"))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o700))
+            .map_err(|error| format!("Failed to make SSH askpass helper executable: {error}"))?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{connection_target, ssh_arguments};
+    use crate::types::{DbConnectConnection, NetworkConnectOptions, SshTunnelConnectOptions};
+
+    #[test]
+    fn builds_forwarding_arguments() {
+        let mut options = SshTunnelConnectOptions {
+            host: "bastion.example.com".to_string(),
+            port: Some(2222),
+            username: "ops".to_string(),
+            identity_file: Some("/home/ops/.ssh/id_ed25519".to_string()),
+            password: None,
+            cli_path: None,
+        };
+        let args = ssh_arguments(&options, 40000, "db.internal", 1521);
+        assert!(args.contains(&"BatchMode=yes".to_string()));
+        assert!(args.contains(&"127.0.0.1:40000:db.internal:1521".to_string()));
+        assert!(args.windows(2).any(|pair| pair == ["-p", "2222"]));
+        assert!(args.windows(2).any(|pair| pair[0] == "-i"));
+        assert_eq!(
+            args[args.len() - 2..],
+            ["--".to_string(), "ops@bastion.example.com".to_string()]
+        );
+
+        options.password = Some("secret".to_string());
+        options.identity_file = None;
+        let args = ssh_arguments(&options, 40000, "db.internal", 1521);
+        assert!(args.contains(&"BatchMode=no".to_string()));
+        assert!(!args.iter().any(|arg| arg == "-i" || arg.contains("secret")));
+    }
+
+    #[test]
+    fn resolves_connection_targets() {
+        let network = |host: &str, port: Option<u16>| NetworkConnectOptions {
+            host: host.to_string(),
+            port,
+            database: "app".to_string(),
+            username: "app".to_string(),
+            password: "secret".to_string(),
+            schema: None,
+        };
+        assert_eq!(
+            connection_target(&DbConnectConnection::Mysql(network("db.internal", None))),
+            Ok(("db.internal".to_string(), 3306))
+        );
+        assert_eq!(
+            connection_target(&DbConnectConnection::Clickhouse(network(
+                "https://ch.internal/",
+                None
+            ))),
+            Ok(("ch.internal".to_string(), 8443))
+        );
+        assert!(connection_target(&DbConnectConnection::Sqlite(
+            crate::types::SqliteConnectionOptions {
+                file_path: "app.db".to_string(),
+            }
+        ))
+        .is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::profiles;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::state::AppState;
+use crate::types::{DbConnectError, DbConnectRequest, DbStandbyConnection};
+use crate::workspace::now_millis;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(60);
+
+/// Connections opened ahead of time for profiles marked as warm standby, by profile id.
+/// `db_connect` takes one over instead of connecting, and a replacement is opened in the
+/// background.
+#[derive(Default)]
+pub(crate) struct StandbyRegistry {
+    entries: Mutex<HashMap<String, StandbyEntry>>,
+    /// Held while the profiles are checked, so two refreshes never open the same standby.
+    refreshing: Mutex<()>,
+}
+
+struct StandbyEntry {
+    profile_name: String,
+    connection: Option<StandbyConnection>,
+    last_checked_at: u64,
+    error: Option<String>,
+}
+
+pub(crate) struct StandbyConnection {
+    pub(crate) session: AppSession,
+    pub(crate) display_name: String,
+    pub(crate) schema: String,
+    /// What the connection was opened with; see [`request_key`].
+    key: String,
+    opened_at: u64,
+}
+
+impl StandbyRegistry {
+    /// Hands over the profile's standby connection if it was opened with the same settings
+    /// and credentials as `request`.
+    pub(crate) fn take(&self, request: &DbConnectRequest) -> Option<StandbyConnection> {
+        let profile_id = request.profile_id.as_deref()?;
+        let key = request_key(request)?;
+        let mut entries = self.entries.lock().ok()?;
+        let entry = entries.get_mut(profile_id)?;
+        if entry.connection.as_ref()?.key != key {
+            return None;
+        }
+        entry.connection.take()
+    }
+
+    pub(crate) fn list(&self) -> Vec<DbStandbyConnection> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let mut list = entries
+            .iter()
+            .map(|(profile_id, entry)| DbStandbyConnection {
+                profile_id: profile_id.clone(),
+                profile_name: entry.profile_name.clone(),
+                ready: entry.connection.is_some(),
+                opened_at: entry
+                    .connection
+                    .as_ref()
+                    .map(|connection| connection.opened_at),
+                last_checked_at: entry.last_checked_at,
+                error: entry.error.clone(),
+            })
+            .collect::<Vec<_>>();
+        list.sort_by(|left, right| left.profile_name.cmp(&right.profile_name));
+        list
+    }
+
+    /// Opens missing standby connections, health-checks the open ones and closes those
+    /// whose profile no longer asks for one.
+    fn refresh(&self, app: &AppHandle) {
+        let Ok(_guard) = self.refreshing.try_lock() else {
+            return;
+        };
+        let Ok(profiles) = profiles::read_profiles(app) else {
+            return;
+        };
+        let profiles = profiles
+            .into_iter()
+            .filter(|profile| profile.warm_standby)
+            .collect::<Vec<_>>();
+        if let Ok(mut entries) = self.entries.lock() {
+            entries
+                .retain(|profile_id, _| profiles.iter().any(|profile| &profile.id == profile_id));
+        }
+
+        for profile in profiles {
+            let request = profiles::profile_connect_request(app, &profile);
+            let key = request.as_ref().ok().and_then(request_key);
+            // Checked outside the lock; a connect meanwhile falls back to a normal connection.
+            // A connection opened before the profile was edited is replaced.
+            let current = self
+                .entries
+                .lock()
+                .ok()
+                .and_then(|mut entries| {
+                    entries
+                        .get_mut(&profile.id)
+                        .and_then(|entry| entry.connection.take())
+                })
+                .filter(|connection| key.as_ref() == Some(&connection.key));
+            let healthy = current.and_then(|mut connection| {
+                ProviderRegistry::ping(&mut connection.session)
+                    .ok()
+                    .map(|_| connection)
+            });
+            let (connection, error) = match (healthy, request, key) {
+                (Some(connection), _, _) => (Some(connection), None),
+                (None, Ok(request), Some(key)) => match open(&request, key) {
+                    Ok(connection) => (Some(connection), None),
+                    Err(error) => (None, Some(error)),
+                },
+                (None, Ok(_), None) => (
+                    None,
+                    Some("The connection settings could not be compared".to_string()),
+                ),
+                (None, Err(error), _) => (None, Some(error)),
+            };
+            if let Ok(mut entries) = self.entries.lock() {
+                entries.insert(
+                    profile.id.clone(),
+                    StandbyEntry {
+                        profile_name: profile.name.clone(),
+                        connection,
+                        last_checked_at: now_millis(),
+                        error,
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Starts the background task that keeps standby connections open and healthy.
+pub(crate) fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        app.state::<AppState>().standby.refresh(&app);
+        thread::sleep(TICK);
+    });
+}
+
+/// Refreshes now rather than at the next tick, e.g. after a profile was saved or a
+/// standby was taken.
+pub(crate) fn refresh_in_background(app: &AppHandle) {
+    let app = app.clone();
+    thread::spawn(move || app.state::<AppState>().standby.refresh(&app));
+}
+
+fn open(request: &DbConnectRequest, key: String) -> Result<StandbyConnection, String> {
+    let (session, display_name, schema) =
+        ProviderRegistry::connect(request).map_err(DbConnectError::into_message)?;
+    Ok(StandbyConnection {
+        session,
+        display_name,
+        schema,
+        key,
+        opened_at: now_millis(),
+    })
+}
+
+/// The settings that shape the opened connection, secrets included, so a standby is only
+/// handed to a request that would have opened the same session.
+fn request_key(request: &DbConnectRequest) -> Option<String> {
+    serde_json::to_string(&(
+        &request.connection,
+        &request.ssh_tunnel,
+        request.timestamp_tz_mode,
+        &request.startup_sql,
+    ))
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::request_key;
+    use crate::types::{
+        DbConnectConnection, DbConnectRequest, NetworkConnectOptions, TimestampTzMode,
+    };
+
+    #[test]
+    fn keys_requests_by_connection_settings_and_secrets() {
+        let request = DbConnectRequest {
+            connection: DbConnectConnection::Postgres(NetworkConnectOptions {
+                host: "db.example.com".to_string(),
+                port: Some(5432),
+                database: "sales".to_string(),
+                username: "app".to_string(),
+                password: "secret".to_string(),
+                schema: None,
+            }),
+            ssh_tunnel: None,
+            timestamp_tz_mode: Default::default(),
+            prefetch_ai_schema_context: false,
+            read_only: false,
+            environment: None,
+            drop_allowlist: Vec::new(),
+            connect_timeout_secs: None,
+            connect_attempts: None,
+            retry_delay_millis: None,
+            startup_sql: None,
+            pool_size: None,
+            profile_id: Some("profile-1".to_string()),
+            reader_endpoint: None,
+        };
+
+        let mut same = request.clone();
+        same.pool_size = Some(4);
+        same.read_only = true;
+        assert_eq!(request_key(&request), request_key(&same));
+
+        let mut other_password = request.clone();
+        if let Some(secret) = other_password.connection.secret_mut() {
+            *secret = "changed".to_string();
+        }
+        assert_ne!(request_key(&request), request_key(&other_password));
+
+        let mut other_mode = request.clone();
+        other_mode.timestamp_tz_mode = TimestampTzMode::Utc;
+        assert_ne!(request_key(&request), request_key(&other_mode));
+    }
+}
@@ -1,12 +1,159 @@
-use crate::providers::AppSession;
+use crate::ai::AiSchemaContextCache;
+use crate::cancellation::CancelRegistry;
+use crate::error_summary::ErrorAggregator;
+use crate::external_edit::ExternalEditRegistry;
+use crate::jobs::JobRegistry;
+use crate::keep_alive::DEFAULT_KEEP_ALIVE_SECS;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::read_replica::ReadReplicaRegistry;
+use crate::scratch::{self, ScratchTableRegistry};
+use crate::session_pool::SessionPoolRegistry;
+use crate::session_timeline::SessionTimelineRegistry;
+use crate::standby::StandbyRegistry;
+use crate::state_recovery::StateRecoveries;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 pub(crate) struct AppState {
     pub(crate) next_session_id: AtomicU64,
     pub(crate) next_profile_id: AtomicU64,
-    pub(crate) sessions: Arc<Mutex<HashMap<u64, AppSession>>>,
+    pub(crate) sessions: SessionRegistry,
+    pub(crate) jobs: Arc<JobRegistry>,
+    pub(crate) ai_schema_context: Arc<AiSchemaContextCache>,
+    pub(crate) scratch_tables: ScratchTableRegistry,
+    pub(crate) external_edits: Arc<ExternalEditRegistry>,
+    /// Seconds between keep-alive pings of an idle session; 0 disables them.
+    pub(crate) keep_alive_interval_secs: Arc<AtomicU64>,
+    pub(crate) session_pools: Arc<SessionPoolRegistry>,
+    pub(crate) read_replicas: ReadReplicaRegistry,
+    /// Seconds without use after which a session is disconnected; 0 keeps sessions open.
+    pub(crate) idle_timeout_secs: Arc<AtomicU64>,
+    pub(crate) session_timeline: SessionTimelineRegistry,
+    pub(crate) standby: StandbyRegistry,
+    pub(crate) cancellations: CancelRegistry,
+    pub(crate) errors: ErrorAggregator,
+    pub(crate) state_recoveries: StateRecoveries,
+}
+
+impl AppState {
+    /// Removes a session and releases what it holds: scratch tables, cached AI schema
+    /// context, external edits, pooled and read replica connections and its error summary.
+    /// Returns false if it was not open.
+    pub(crate) fn close_session(&self, session_id: u64) -> Result<bool, String> {
+        let Some(session) = self.sessions.remove(session_id)? else {
+            return Ok(false);
+        };
+        let mut session = lock_session(&session)?;
+        // Failures leave a prefixed table behind at worst; the session closes regardless.
+        let _ = scratch::drop_all_scratch_tables(&mut session, &self.scratch_tables, session_id);
+        self.ai_schema_context.remove(session_id);
+        self.external_edits.stop_session(session_id);
+        self.session_pools.remove(session_id);
+        self.read_replicas.remove(session_id);
+        self.errors.remove(session_id);
+        Ok(true)
+    }
+}
+
+pub(crate) type SharedSession = Arc<Mutex<AppSession>>;
+
+/// Open sessions by id. The map is locked only to look a session up; each session has its
+/// own lock, so a long-running statement never holds up commands on other sessions.
+#[derive(Clone, Default)]
+pub(crate) struct SessionRegistry {
+    sessions: Arc<Mutex<HashMap<u64, SharedSession>>>,
+}
+
+impl SessionRegistry {
+    pub(crate) fn insert(&self, session_id: u64, session: AppSession) -> Result<(), String> {
+        self.map()?
+            .insert(session_id, Arc::new(Mutex::new(session)));
+        Ok(())
+    }
+
+    /// Takes a session out of the registry. Commands already running on it keep their
+    /// handle; locking the returned session waits for them.
+    pub(crate) fn remove(&self, session_id: u64) -> Result<Option<SharedSession>, String> {
+        Ok(self.map()?.remove(&session_id))
+    }
+
+    pub(crate) fn get(&self, session_id: u64) -> Result<SharedSession, String> {
+        self.map()?
+            .get(&session_id)
+            .cloned()
+            .ok_or_else(|| "Session not found".to_string())
+    }
+
+    /// Marks a session as used unless a command holds it, which already counts as use.
+    pub(crate) fn touch(&self, session_id: u64) {
+        if let Ok(session) = self.get(session_id) {
+            if let Ok(mut session) = session.try_lock() {
+                session.touch();
+            }
+        }
+    }
+
+    /// Sessions unused for at least `timeout`. Sessions busy running a command are skipped,
+    /// as are sessions with an open transaction: disconnecting would roll it back.
+    pub(crate) fn idle_session_ids(&self, timeout: Duration) -> Vec<u64> {
+        let Ok(sessions) = self.map() else {
+            return Vec::new();
+        };
+        sessions
+            .iter()
+            .filter(|(_, session)| {
+                session
+                    .try_lock()
+                    .map(|session| {
+                        let transaction_active =
+                            ProviderRegistry::transaction_active(&session).unwrap_or(true);
+                        idle_timed_out(session.idle_for(), transaction_active, timeout)
+                    })
+                    .unwrap_or(false)
+            })
+            .map(|(session_id, _)| *session_id)
+            .collect()
+    }
+
+    /// Every open session; the caller locks the ones it needs.
+    pub(crate) fn all(&self) -> Vec<SharedSession> {
+        self.map()
+            .map(|sessions| sessions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Runs `f` with the session locked and marked as used.
+    pub(crate) fn with_session<T>(
+        &self,
+        session_id: u64,
+        f: impl FnOnce(&mut AppSession) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let session = self.get(session_id)?;
+        let mut session = lock_session(&session)?;
+        session.touch();
+        f(&mut session)
+    }
+
+    fn map(&self) -> Result<MutexGuard<'_, HashMap<u64, SharedSession>>, String> {
+        self.sessions
+            .lock()
+            .map_err(|_| "Failed to acquire session lock".to_string())
+    }
+}
+
+pub(crate) fn lock_session(
+    session: &Mutex<AppSession>,
+) -> Result<MutexGuard<'_, AppSession>, String> {
+    session
+        .lock()
+        .map_err(|_| "Failed to acquire session lock".to_string())
+}
+
+/// An open transaction keeps a session connected however long it sits unused.
+fn idle_timed_out(idle_for: Duration, transaction_active: bool, timeout: Duration) -> bool {
+    !transaction_active && idle_for >= timeout
 }
 
 impl Default for AppState {
@@ -14,7 +161,34 @@ impl Default for AppState {
         Self {
             next_session_id: AtomicU64::new(1),
             next_profile_id: AtomicU64::new(1),
-            sessions: Arc::new(Mutex::new(HashMap::new())),
+            sessions: SessionRegistry::default(),
+            jobs: Arc::new(JobRegistry::default()),
+            ai_schema_context: Arc::new(AiSchemaContextCache::default()),
+            scratch_tables: ScratchTableRegistry::default(),
+            external_edits: Arc::new(ExternalEditRegistry::default()),
+            keep_alive_interval_secs: Arc::new(AtomicU64::new(DEFAULT_KEEP_ALIVE_SECS)),
+            session_pools: Arc::new(SessionPoolRegistry::default()),
+            read_replicas: ReadReplicaRegistry::default(),
+            idle_timeout_secs: Arc::new(AtomicU64::new(0)),
+            session_timeline: SessionTimelineRegistry::default(),
+            cancellations: CancelRegistry::default(),
+            errors: ErrorAggregator::default(),
+            state_recoveries: StateRecoveries::default(),
+            standby: StandbyRegistry::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::idle_timed_out;
+    use std::time::Duration;
+
+    #[test]
+    fn open_transactions_keep_idle_sessions_connected() {
+        let timeout = Duration::from_secs(600);
+        assert!(idle_timed_out(Duration::from_secs(600), false, timeout));
+        assert!(!idle_timed_out(Duration::from_secs(599), false, timeout));
+        assert!(!idle_timed_out(Duration::from_secs(3600), true, timeout));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::ai;
+use crate::menu::EVENT_STATE_RECOVERED;
+use crate::profiles::{self, app_data_file_path};
+use crate::state::AppState;
+use crate::types::DbStateFileRecovery;
+use crate::workspace::{self, now_millis};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+type CheckStore = fn(&Path) -> Result<(), String>;
+
+/// Stores that profile, history and settings commands read, with what they hold and how
+/// they are parsed.
+const STATE_FILES: [(&str, &str, CheckStore); 3] = [
+    (
+        profiles::PROFILE_STORE_FILE,
+        "connection profiles",
+        |path| profiles::read_profiles_from_path(path).map(drop),
+    ),
+    (
+        workspace::WORKSPACE_STORE_FILE,
+        "query history, snippets and editor buffers",
+        |path| workspace::read_workspace_from_path(path).map(drop),
+    ),
+    (ai::AI_SETTINGS_STORE_FILE, "AI settings", |path| {
+        ai::read_prompt_settings_from_path(path).map(drop)
+    }),
+];
+
+/// Files moved aside at startup, kept for a UI that loads after the event was sent.
+#[derive(Default)]
+pub(crate) struct StateRecoveries {
+    recoveries: Mutex<Vec<DbStateFileRecovery>>,
+}
+
+impl StateRecoveries {
+    pub(crate) fn list(&self) -> Result<Vec<DbStateFileRecovery>, String> {
+        self.recoveries
+            .lock()
+            .map(|recoveries| recoveries.clone())
+            .map_err(|_| "Failed to acquire state recovery lock".to_string())
+    }
+}
+
+/// Safe-mode startup: a store that no longer parses is renamed aside so Clarity starts with
+/// defaults instead of failing every command that reads it. Each recovery is kept in
+/// `AppState` and sent to the UI.
+pub(crate) fn recover_on_startup(app: &AppHandle) {
+    let recovered_at = now_millis();
+    let recoveries = STATE_FILES
+        .iter()
+        .filter_map(|(file_name, contents, check)| {
+            let path = app_data_file_path(app, file_name).ok()?;
+            let error = check(&path).err()?;
+            Some(move_aside(&path, file_name, contents, error, recovered_at))
+        })
+        .collect::<Vec<_>>();
+    if recoveries.is_empty() {
+        return;
+    }
+
+    for recovery in &recoveries {
+        eprintln!("state recovery: {}", recovery.message);
+    }
+    let state = app.state::<AppState>();
+    if let Ok(mut stored) = state.state_recoveries.recoveries.lock() {
+        stored.extend(recoveries.iter().cloned());
+    }
+    let _ = app.emit(EVENT_STATE_RECOVERED, recoveries);
+}
+
+fn move_aside(
+    path: &Path,
+    file_name: &str,
+    contents: &str,
+    error: String,
+    recovered_at: u64,
+) -> DbStateFileRecovery {
+    let moved_to = path.with_file_name(format!("{file_name}.corrupt-{recovered_at}"));
+    let (moved_to, message) = match fs::rename(path, &moved_to) {
+        Ok(()) => (
+            Some(moved_to.display().to_string()),
+            format!(
+                "{file_name} could not be read, so Clarity started without your {contents}. The damaged file was kept as {}.",
+                moved_to.display()
+            ),
+        ),
+        Err(rename_error) => (
+            None,
+            format!(
+                "{file_name} could not be read and could not be moved aside ({rename_error}); {contents} are unavailable until it is fixed or removed."
+            ),
+        ),
+    };
+    DbStateFileRecovery {
+        file_name: file_name.to_string(),
+        error,
+        moved_to,
+        message,
+        recovered_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::move_aside;
+    use std::fs;
+
+    #[test]
+    fn moves_a_corrupt_store_aside_and_keeps_its_contents() {
+        let directory =
+            std::env::temp_dir().join(format!("clarity-state-recovery-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("workspace.json");
+        fs::write(&path, "{\"history\": [").unwrap();
+
+        let recovery = move_aside(
+            &path,
+            "workspace.json",
+            "query history",
+            "Failed to parse workspace file".to_string(),
+            42,
+        );
+        let moved_to = directory.join("workspace.json.corrupt-42");
+        let kept = fs::read_to_string(&moved_to).unwrap();
+        let still_there = path.exists();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(!still_there);
+        assert_eq!(kept, "{\"history\": [");
+        assert_eq!(
+            recovery.moved_to.as_deref(),
+            Some(moved_to.display().to_string().as_str())
+        );
+        assert!(recovery.message.contains("without your query history"));
+    }
+}
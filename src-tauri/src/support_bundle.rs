@@ -0,0 +1,376 @@
+use crate::diagnostics;
+use crate::error_summary::ErrorAggregator;
+use crate::oracle_clients;
+use crate::profiles;
+use crate::session_timeline::SessionTimelineRegistry;
+use crate::types::{
+    DbCreateSupportBundleRequest, DbRunDiagnosticsRequest, DbSessionTimeline,
+    DbSessionTimelineEvent, DbSupportBundleResult,
+};
+use crate::workspace::now_millis;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Stores whose settings go into the bundle. Query history, snippets and result snapshots
+/// stay out: they hold the user's data rather than configuration.
+const SETTINGS_FILES: &[&str] = &[
+    "connection_profiles.json",
+    "ai_settings.json",
+    "schema-snapshots.json",
+];
+/// Timeline events kept per session.
+const RECENT_EVENTS: usize = 200;
+/// Parts of a key, lower-cased without `_` or `-`, whose values are never written.
+const SECRET_KEY_PARTS: &[&str] = &[
+    "password",
+    "passphrase",
+    "secret",
+    "accesstoken",
+    "authtoken",
+    "apikey",
+    "credential",
+];
+const REDACTED: &str = "[redacted]";
+/// SQL clauses followed by a password, and whether the password may be an unquoted word
+/// (`PASSWORD EXPIRE` is not a password, `IDENTIFIED BY secret` is).
+const SECRET_SQL_CLAUSES: &[(&[&str], bool)] = &[
+    (&["IDENTIFIED", "BY"], true),
+    (&["PASSWORD"], false),
+    (&["SECRET"], false),
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LastFailure<'a> {
+    session_id: u64,
+    label: &'a str,
+    event: &'a DbSessionTimelineEvent,
+}
+
+/// Writes a zip of what a bug report needs: diagnostics, redacted settings, the recent
+/// timeline and errors of each session, and the context of the last failed command.
+pub(crate) fn create_support_bundle(
+    request: &DbCreateSupportBundleRequest,
+    app: &AppHandle,
+    timelines: &SessionTimelineRegistry,
+    errors: &ErrorAggregator,
+) -> Result<DbSupportBundleResult, String> {
+    let file_path = request.file_path.trim();
+    if file_path.is_empty() {
+        return Err("Destination file is required".to_string());
+    }
+    let path = PathBuf::from(file_path);
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let report = diagnostics::run_diagnostics_blocking(
+        &DbRunDiagnosticsRequest {
+            oracle_client_lib_dir: request.oracle_client_lib_dir.clone(),
+            skip_network: true,
+        },
+        app,
+    );
+    entries.push(json_entry("diagnostics.json", &report)?);
+    entries.push(json_entry(
+        "oracle-clients.json",
+        &oracle_clients::list_clients(app).unwrap_or_default(),
+    )?);
+
+    if let Some(settings) = &request.settings {
+        let mut settings = settings.clone();
+        redact(&mut settings);
+        entries.push(json_entry("settings/app-settings.json", &settings)?);
+    }
+    for file_name in SETTINGS_FILES {
+        let Ok(store_path) = profiles::app_data_file_path(app, file_name) else {
+            continue;
+        };
+        let Ok(contents) = fs::read_to_string(&store_path) else {
+            continue;
+        };
+        let mut value = serde_json::from_str::<Value>(&contents)
+            .unwrap_or_else(|error| Value::String(format!("Unreadable: {error}")));
+        redact(&mut value);
+        entries.push(json_entry(&format!("settings/{file_name}"), &value)?);
+    }
+
+    let mut recent = timelines.recent(RECENT_EVENTS);
+    recent
+        .iter_mut()
+        .flat_map(|timeline| timeline.events.iter_mut())
+        .for_each(mask_event_secrets);
+    for timeline in &recent {
+        entries.push(json_entry(
+            &format!("sessions/{}-timeline.json", timeline.session_id),
+            timeline,
+        )?);
+        if let Ok(summary) = errors.summary(timeline.session_id) {
+            if !summary.errors.is_empty() {
+                entries.push(json_entry(
+                    &format!("sessions/{}-errors.json", timeline.session_id),
+                    &summary,
+                )?);
+            }
+        }
+    }
+    if let Some(failure) = last_failure(&recent) {
+        entries.push(json_entry("last-failure.json", &failure)?);
+    }
+
+    write_zip(&path, &entries)?;
+    let names = entries
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    Ok(DbSupportBundleResult {
+        file_path: path.to_string_lossy().into_owned(),
+        message: format!(
+            "Saved a support bundle with {} file(s) to {}",
+            names.len(),
+            path.display()
+        ),
+        entries: names,
+    })
+}
+
+fn json_entry(name: &str, value: &impl Serialize) -> Result<(String, String), String> {
+    serde_json::to_string_pretty(value)
+        .map(|contents| (name.to_string(), contents))
+        .map_err(|error| format!("Failed to serialize {name}: {error}"))
+}
+
+fn write_zip(path: &Path, entries: &[(String, String)]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!("Failed to create directory '{}': {error}", parent.display())
+        })?;
+    }
+    let write_error =
+        |error: &dyn std::fmt::Display| format!("Failed to write '{}': {error}", path.display());
+    let file = File::create(path).map_err(|error| write_error(&error))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    zip.set_comment(format!(
+        "Clarity support bundle, created at {}",
+        now_millis()
+    ));
+    for (name, contents) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|error| write_error(&error))?;
+        zip.write_all(contents.as_bytes())
+            .map_err(|error| write_error(&error))?;
+    }
+    zip.finish().map_err(|error| write_error(&error))?;
+    Ok(())
+}
+
+/// The newest failed event across all sessions.
+fn last_failure(timelines: &[DbSessionTimeline]) -> Option<LastFailure<'_>> {
+    timelines
+        .iter()
+        .flat_map(|timeline| {
+            timeline
+                .events
+                .iter()
+                .filter(|event| !event.succeeded)
+                .map(move |event| LastFailure {
+                    session_id: timeline.session_id,
+                    label: &timeline.label,
+                    event,
+                })
+        })
+        .max_by_key(|failure| failure.event.at)
+}
+
+/// Replaces the value of every key that looks like it holds a secret, at any depth.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase().replace(['_', '-'], "");
+                if SECRET_KEY_PARTS.iter().any(|part| key.contains(part)) {
+                    if !value.is_null() {
+                        *value = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Masks passwords a recent `ALTER USER`, `CREATE DATABASE LINK` or similar statement
+/// left in an event's SQL, summary or error.
+fn mask_event_secrets(event: &mut DbSessionTimelineEvent) {
+    event.summary = mask_sql_secrets(&event.summary);
+    for text in [&mut event.sql, &mut event.error].into_iter().flatten() {
+        *text = mask_sql_secrets(text);
+    }
+}
+
+/// Replaces the value after each password clause, e.g. `IDENTIFIED BY [redacted]`.
+fn mask_sql_secrets(sql: &str) -> String {
+    let bytes = sql.as_bytes();
+    let mut masked = String::with_capacity(sql.len());
+    let mut copied = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        let secret = SECRET_SQL_CLAUSES.iter().find_map(|(words, bare)| {
+            let end = match_words(bytes, index, words)?;
+            secret_value(bytes, end, *bare)
+        });
+        match secret {
+            Some((start, end)) => {
+                masked.push_str(&sql[copied..start]);
+                masked.push_str(REDACTED);
+                copied = end;
+                index = end;
+            }
+            None => index += 1,
+        }
+    }
+    masked.push_str(&sql[copied..]);
+    masked
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$' | b'#') || !byte.is_ascii()
+}
+
+/// End of `words` (whitespace apart, any case) when they start at `index` as whole words.
+fn match_words(bytes: &[u8], index: usize, words: &[&str]) -> Option<usize> {
+    if index > 0 && is_word_byte(bytes[index - 1]) {
+        return None;
+    }
+    let mut position = index;
+    for (word_index, word) in words.iter().enumerate() {
+        if word_index > 0 {
+            let spaces = skip_whitespace(bytes, position);
+            if spaces == position {
+                return None;
+            }
+            position = spaces;
+        }
+        let end = position + word.len();
+        if !bytes
+            .get(position..end)?
+            .eq_ignore_ascii_case(word.as_bytes())
+        {
+            return None;
+        }
+        position = end;
+    }
+    if bytes.get(position).is_some_and(|byte| is_word_byte(*byte)) {
+        return None;
+    }
+    Some(position)
+}
+
+fn skip_whitespace(bytes: &[u8], mut position: usize) -> usize {
+    while bytes.get(position).is_some_and(u8::is_ascii_whitespace) {
+        position += 1;
+    }
+    position
+}
+
+/// Byte range of the password after a clause: a quoted string, or with `bare` a word.
+/// `IDENTIFIED BY VALUES '…'` masks the quoted hash.
+fn secret_value(bytes: &[u8], clause_end: usize, bare: bool) -> Option<(usize, usize)> {
+    let mut start = skip_whitespace(bytes, clause_end);
+    if bytes.get(start) == Some(&b'=') {
+        start = skip_whitespace(bytes, start + 1);
+    }
+    match *bytes.get(start)? {
+        quote @ (b'\'' | b'"') => {
+            let mut end = start + 1;
+            while end < bytes.len() {
+                if bytes[end] == quote {
+                    if bytes.get(end + 1) == Some(&quote) {
+                        end += 2;
+                        continue;
+                    }
+                    return Some((start, end + 1));
+                }
+                end += 1;
+            }
+            Some((start, end))
+        }
+        _ if bare => {
+            let end = (start..bytes.len())
+                .find(|&position| {
+                    bytes[position].is_ascii_whitespace()
+                        || matches!(bytes[position], b';' | b',' | b')')
+                })
+                .unwrap_or(bytes.len());
+            if bytes[start..end].eq_ignore_ascii_case(b"VALUES") {
+                return secret_value(bytes, end, false);
+            }
+            (end > start).then_some((start, end))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mask_sql_secrets, redact};
+    use serde_json::json;
+
+    #[test]
+    fn redacts_secrets_at_any_depth() {
+        let mut settings = json!({
+            "theme": "dark",
+            "ai": { "apiKey": "sk-123", "model": "m" },
+            "profiles": [
+                { "name": "prod", "ssh_password": "hunter2", "walletPassword": null }
+            ],
+        });
+        redact(&mut settings);
+        assert_eq!(
+            settings,
+            json!({
+                "theme": "dark",
+                "ai": { "apiKey": "[redacted]", "model": "m" },
+                "profiles": [
+                    { "name": "prod", "ssh_password": "[redacted]", "walletPassword": null }
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn masks_passwords_in_sql_text() {
+        assert_eq!(
+            mask_sql_secrets("ALTER USER app IDENTIFIED BY \"s3cr;et\" ACCOUNT UNLOCK"),
+            "ALTER USER app IDENTIFIED BY [redacted] ACCOUNT UNLOCK"
+        );
+        assert_eq!(
+            mask_sql_secrets(
+                "create database link hr connect to hr identified\n by hunter2 using 'HRDB'"
+            ),
+            "create database link hr connect to hr identified\n by [redacted] using 'HRDB'"
+        );
+        assert_eq!(
+            mask_sql_secrets("ALTER USER app IDENTIFIED BY VALUES 'S:ABC'"),
+            "ALTER USER app IDENTIFIED BY VALUES [redacted]"
+        );
+        assert_eq!(
+            mask_sql_secrets("CREATE ROLE r LOGIN PASSWORD 'it''s'; ALTER USER u PASSWORD EXPIRE"),
+            "CREATE ROLE r LOGIN PASSWORD [redacted]; ALTER USER u PASSWORD EXPIRE"
+        );
+        assert_eq!(
+            mask_sql_secrets("CREATE USER u PASSWORD = 'pw'"),
+            "CREATE USER u PASSWORD = [redacted]"
+        );
+        let query = "SELECT password_hash, identified_by FROM users WHERE name = 'Zoë'";
+        assert_eq!(mask_sql_secrets(query), query);
+    }
+}
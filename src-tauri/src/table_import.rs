@@ -0,0 +1,786 @@
+use crate::cancellation::CancelToken;
+use crate::jobs::{start_job, JobRegistry};
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::sql_ident;
+use crate::sql_safety;
+use crate::state::{lock_session, SessionRegistry};
+use crate::types::{
+    DatabaseProvider, DbClipboardImportResult, DbConnectRequest, DbImportClipboardRowsRequest,
+    DbImportColumn, DbImportColumnMapping, DbImportValueType, DbJobKind, DbJobStatus,
+    DbQueryRequest, DbSqlStatement, DbXlsxImportRequest, DbXlsxPreview, DbXlsxPreviewRequest,
+};
+use calamine::{open_workbook, Data, Reader, Xlsx};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+const DEFAULT_PREVIEW_ROWS: u32 = 50;
+const MAX_PREVIEW_ROWS: u32 = 1000;
+const DEFAULT_BATCH_SIZE: u32 = 500;
+const MAX_BATCH_SIZE: u32 = 5000;
+
+/// Rows to import, with the header row (if any) already split off. Clipboard text is held
+/// as `Data::String` cells and typed from its content.
+struct ImportRows {
+    column_names: Vec<String>,
+    rows: Vec<Vec<Data>>,
+}
+
+struct SheetData {
+    sheet_names: Vec<String>,
+    sheet_name: String,
+    data: ImportRows,
+}
+
+/// Where mapped columns go.
+struct ImportTarget<'a> {
+    schema: Option<&'a str>,
+    table_name: &'a str,
+    columns: &'a [DbImportColumnMapping],
+    batch_size: Option<u32>,
+    production_confirmation: Option<&'a str>,
+}
+
+pub(crate) fn preview_xlsx_import(request: &DbXlsxPreviewRequest) -> Result<DbXlsxPreview, String> {
+    let sheet = read_sheet(
+        &request.file_path,
+        request.sheet_name.as_deref(),
+        request.first_row_is_header,
+    )?;
+    Ok(DbXlsxPreview {
+        columns: infer_columns(&sheet.data),
+        rows: preview_rows(&sheet.data, request.preview_rows),
+        total_rows: sheet.data.rows.len() as u64,
+        sheet_names: sheet.sheet_names,
+        sheet_name: sheet.sheet_name,
+    })
+}
+
+/// Inserts a worksheet into an existing table as a background job. On Oracle and MySQL the
+/// whole import is one transaction; elsewhere each batch commits on its own.
+pub(crate) fn start_xlsx_import_job(
+    request: DbXlsxImportRequest,
+    sessions: SessionRegistry,
+    jobs: Arc<JobRegistry>,
+    app: AppHandle,
+) -> Result<DbJobStatus, String> {
+    validate_target(&request.table_name, &request.columns)?;
+
+    let label = format!(
+        "Import {} into {}",
+        request.file_path.trim(),
+        request.table_name.trim()
+    );
+    start_job(jobs, app, DbJobKind::XlsxImport, label, move |reporter| {
+        let session = sessions.get(request.session_id)?;
+        let mut session = lock_session(&session)?;
+        let session = &mut *session;
+        let sheet = read_sheet(
+            &request.file_path,
+            request.sheet_name.as_deref(),
+            request.first_row_is_header,
+        )?;
+        let target = ImportTarget {
+            schema: request.schema.as_deref(),
+            table_name: &request.table_name,
+            columns: &request.columns,
+            batch_size: request.batch_size,
+            production_confirmation: request.production_confirmation.as_deref(),
+        };
+        insert_rows(
+            session,
+            request.session_id,
+            &target,
+            &sheet.data,
+            &mut |rows| reporter.progress(rows, &format!("{rows} row(s) inserted.")),
+            reporter.cancel_token(),
+        )
+        .map(|rows| format!("{rows} row(s) inserted into {}.", request.table_name.trim()))
+    })
+}
+
+/// Parses a pasted grid (tab-separated, as Excel and most grids copy it) and, unless
+/// `dry_run` is set, inserts it in one go. Meant for small ad-hoc loads.
+pub(crate) fn import_clipboard_rows(
+    session: &mut AppSession,
+    request: &DbImportClipboardRowsRequest,
+) -> Result<DbClipboardImportResult, String> {
+    let data = split_header(
+        parse_tab_separated(&request.text),
+        request.first_row_is_header,
+    );
+    if data.rows.is_empty() {
+        return Err("The pasted text has no data rows".to_string());
+    }
+
+    let rows_inserted = if request.dry_run {
+        None
+    } else {
+        validate_target(&request.table_name, &request.columns)?;
+        let target = ImportTarget {
+            schema: request.schema.as_deref(),
+            table_name: &request.table_name,
+            columns: &request.columns,
+            batch_size: None,
+            production_confirmation: request.production_confirmation.as_deref(),
+        };
+        Some(insert_rows(
+            session,
+            request.session_id,
+            &target,
+            &data,
+            &mut |_| {},
+            &CancelToken::default(),
+        )?)
+    };
+
+    let message = match rows_inserted {
+        Some(rows) => format!("{rows} row(s) inserted into {}.", request.table_name.trim()),
+        None => format!("{} row(s) ready to import.", data.rows.len()),
+    };
+    Ok(DbClipboardImportResult {
+        columns: infer_columns(&data),
+        rows: preview_rows(&data, request.preview_rows),
+        total_rows: data.rows.len() as u64,
+        rows_inserted,
+        message,
+    })
+}
+
+fn validate_target(table_name: &str, columns: &[DbImportColumnMapping]) -> Result<(), String> {
+    if table_name.trim().is_empty() {
+        return Err("Target table is required".to_string());
+    }
+    if !columns
+        .iter()
+        .any(|mapping| !mapping.target_column.trim().is_empty())
+    {
+        return Err("Map at least one source column to a table column".to_string());
+    }
+    Ok(())
+}
+
+fn preview_rows(data: &ImportRows, limit: Option<u32>) -> Vec<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_PREVIEW_ROWS).min(MAX_PREVIEW_ROWS) as usize;
+    data.rows
+        .iter()
+        .take(limit)
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell_text(cell).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+/// An import changes data, so a production session needs the confirmation token as any
+/// other INSERT would. The token is for the table, not the batches, so it stays the same
+/// when the import is run again.
+fn check_import_allowed(
+    session_id: u64,
+    request: Option<&DbConnectRequest>,
+    table: &str,
+    confirmation: Option<&str>,
+) -> Result<(), String> {
+    let statement = DbSqlStatement {
+        sql: format!("INSERT INTO {table}"),
+        line: 1,
+    };
+    sql_safety::check_production_for(session_id, request, &[statement], confirmation)
+}
+
+/// On Oracle and MySQL all batches share one transaction, rolled back on failure;
+/// elsewhere each batch commits on its own.
+fn insert_rows(
+    session: &mut AppSession,
+    session_id: u64,
+    target: &ImportTarget<'_>,
+    data: &ImportRows,
+    on_progress: &mut dyn FnMut(u64),
+    cancel: &CancelToken,
+) -> Result<u64, String> {
+    let columns = infer_columns(data);
+    let provider = session.provider;
+
+    let mut mapped = Vec::new();
+    for mapping in target.columns {
+        let target_column = mapping.target_column.trim();
+        if target_column.is_empty() {
+            continue;
+        }
+        let column = columns
+            .get(mapping.source_index)
+            .ok_or_else(|| format!("The source has no column {}", mapping.source_index + 1))?;
+        let value_type = mapping.value_type.unwrap_or(column.value_type);
+        mapped.push((mapping.source_index, value_type, target_column));
+    }
+
+    let table = sql_ident::qualified_name(provider, target.schema, target.table_name.trim());
+    check_import_allowed(
+        session_id,
+        session.connect_request(),
+        &table,
+        target.production_confirmation,
+    )?;
+    let column_list = mapped
+        .iter()
+        .map(|(_, _, target)| sql_ident::format_identifier(provider, target))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let batch_size = target
+        .batch_size
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+        .clamp(1, MAX_BATCH_SIZE) as usize;
+
+    let transactional = matches!(provider, DatabaseProvider::Oracle | DatabaseProvider::Mysql);
+    if transactional {
+        if ProviderRegistry::transaction_active(session)? {
+            return Err(
+                "Commit or roll back the open transaction before importing into this session"
+                    .to_string(),
+            );
+        }
+        ProviderRegistry::begin_transaction(session)?;
+    }
+
+    let mut inserted = 0u64;
+    for batch in data.rows.chunks(batch_size) {
+        let values = batch
+            .iter()
+            .map(|row| {
+                mapped
+                    .iter()
+                    .map(|(index, value_type, _)| {
+                        sql_literal(
+                            provider,
+                            *value_type,
+                            row.get(*index).unwrap_or(&Data::Empty),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        let sql = insert_statement(provider, &table, &column_list, &values);
+        let result = cancel.check().and_then(|()| {
+            ProviderRegistry::run_query(
+                session,
+                &DbQueryRequest {
+                    session_id,
+                    sql,
+                    row_limit: None,
+                    binds: Vec::new(),
+                    sample_mode: false,
+                    binary_preview_bytes: None,
+                    production_confirmation: None,
+                    timeout_secs: None,
+                },
+                cancel,
+            )
+        });
+        if let Err(error) = result {
+            if transactional {
+                let _ = ProviderRegistry::rollback_transaction(session);
+                return Err(format!(
+                    "Import failed near data row {}; nothing was imported: {error}",
+                    inserted + 1
+                ));
+            }
+            return Err(format!(
+                "Import failed near data row {} after {inserted} row(s) were inserted: {error}",
+                inserted + 1
+            ));
+        }
+        inserted += batch.len() as u64;
+        on_progress(inserted);
+    }
+
+    if transactional {
+        ProviderRegistry::commit_transaction(session)?;
+    }
+    Ok(inserted)
+}
+
+fn read_sheet(
+    file_path: &str,
+    sheet_name: Option<&str>,
+    first_row_is_header: bool,
+) -> Result<SheetData, String> {
+    let file_path = file_path.trim();
+    if file_path.is_empty() {
+        return Err("File path is required".to_string());
+    }
+    let mut workbook: Xlsx<_> = open_workbook(file_path)
+        .map_err(|error| format!("Failed to open '{file_path}': {error}"))?;
+    let sheet_names = workbook.sheet_names();
+    let sheet_name = match sheet_name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => sheet_names
+            .iter()
+            .find(|candidate| candidate.as_str() == name)
+            .cloned()
+            .ok_or_else(|| format!("Workbook has no sheet named '{name}'"))?,
+        None => sheet_names
+            .first()
+            .cloned()
+            .ok_or_else(|| "Workbook has no sheets".to_string())?,
+    };
+    let range = workbook
+        .worksheet_range(&sheet_name)
+        .map_err(|error| format!("Failed to read sheet '{sheet_name}': {error}"))?;
+
+    let rows = range.rows().map(<[Data]>::to_vec).collect();
+    Ok(SheetData {
+        sheet_names,
+        sheet_name,
+        data: split_header(rows, first_row_is_header),
+    })
+}
+
+/// Splits clipboard text into cells. Cells holding tabs, line breaks or quotes arrive
+/// wrapped in double quotes, with embedded quotes doubled.
+fn parse_tab_separated(text: &str) -> Vec<Vec<Data>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut chars = text.chars().peekable();
+    let mut at_cell_start = true;
+    let mut in_quotes = false;
+
+    while let Some(ch) = chars.next() {
+        if in_quotes {
+            match ch {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => cell.push(ch),
+            }
+            continue;
+        }
+        match ch {
+            '"' if at_cell_start => {
+                in_quotes = true;
+                at_cell_start = false;
+            }
+            '\t' => {
+                row.push(clipboard_cell(std::mem::take(&mut cell)));
+                at_cell_start = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(clipboard_cell(std::mem::take(&mut cell)));
+                rows.push(std::mem::take(&mut row));
+                at_cell_start = true;
+            }
+            _ => {
+                cell.push(ch);
+                at_cell_start = false;
+            }
+        }
+    }
+    if !at_cell_start || !row.is_empty() {
+        row.push(clipboard_cell(cell));
+        rows.push(row);
+    }
+    rows
+}
+
+fn clipboard_cell(text: String) -> Data {
+    if text.trim().is_empty() {
+        Data::Empty
+    } else {
+        Data::String(text)
+    }
+}
+
+fn split_header(mut rows: Vec<Vec<Data>>, first_row_is_header: bool) -> ImportRows {
+    // Trailing blank rows are common where a sheet was formatted further down than filled.
+    while rows
+        .last()
+        .is_some_and(|row| row.iter().all(|cell| cell_text(cell).is_none()))
+    {
+        rows.pop();
+    }
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let header = if first_row_is_header && !rows.is_empty() {
+        rows.remove(0)
+    } else {
+        Vec::new()
+    };
+    let column_names = (0..width)
+        .map(|index| {
+            header
+                .get(index)
+                .and_then(cell_text)
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("column_{}", index + 1))
+        })
+        .collect();
+    ImportRows { column_names, rows }
+}
+
+fn infer_columns(data: &ImportRows) -> Vec<DbImportColumn> {
+    data.column_names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| DbImportColumn {
+            name: name.clone(),
+            value_type: data
+                .rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .map(cell_type)
+                .fold(DbImportValueType::Empty, merge_types),
+        })
+        .collect()
+}
+
+fn cell_type(cell: &Data) -> DbImportValueType {
+    match cell {
+        Data::Empty | Data::Error(_) => DbImportValueType::Empty,
+        Data::Int(_) => DbImportValueType::Integer,
+        // Excel stores every number as a double; whole numbers come back as e.g. 3.0.
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 9e15 => {
+            DbImportValueType::Integer
+        }
+        Data::Float(_) => DbImportValueType::Decimal,
+        Data::Bool(_) => DbImportValueType::Boolean,
+        // Serial dates count days; a whole number is midnight.
+        Data::DateTime(value) if value.is_datetime() && value.as_f64().fract() == 0.0 => {
+            DbImportValueType::Date
+        }
+        Data::DateTime(value) if value.is_datetime() => DbImportValueType::Timestamp,
+        Data::DateTimeIso(value) if value.len() <= 10 => DbImportValueType::Date,
+        Data::DateTimeIso(_) => DbImportValueType::Timestamp,
+        Data::String(value) => text_type(value.trim()),
+        Data::DateTime(_) | Data::DurationIso(_) => DbImportValueType::Text,
+    }
+}
+
+/// Types text the way it would have been typed had it come from a real cell. Numbers with
+/// leading zeros stay text so codes like ZIPs keep them.
+fn text_type(text: &str) -> DbImportValueType {
+    if text.is_empty() {
+        return DbImportValueType::Empty;
+    }
+    if text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false") {
+        return DbImportValueType::Boolean;
+    }
+
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let (whole, fraction) = match digits.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (digits, None),
+    };
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    if is_digits(whole) && (whole == "0" || !whole.starts_with('0')) && whole.len() <= 30 {
+        match fraction {
+            None => return DbImportValueType::Integer,
+            Some(fraction) if is_digits(fraction) => return DbImportValueType::Decimal,
+            Some(_) => {}
+        }
+    }
+
+    let bytes = text.as_bytes();
+    let shaped = |pattern: &str| {
+        bytes.len() >= pattern.len()
+            && pattern
+                .bytes()
+                .zip(bytes)
+                .all(|(expected, &actual)| match expected {
+                    b'9' => actual.is_ascii_digit(),
+                    b'T' => actual == b'T' || actual == b' ',
+                    other => other == actual,
+                })
+    };
+    if bytes.len() == 10 && shaped("9999-99-99") {
+        DbImportValueType::Date
+    } else if shaped("9999-99-99T99:99")
+        && text[16..]
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || matches!(byte, b':' | b'.'))
+    {
+        DbImportValueType::Timestamp
+    } else {
+        DbImportValueType::Text
+    }
+}
+
+fn merge_types(left: DbImportValueType, right: DbImportValueType) -> DbImportValueType {
+    use DbImportValueType::{Date, Decimal, Empty, Integer, Text, Timestamp};
+    match (left, right) {
+        (Empty, other) | (other, Empty) => other,
+        (left, right) if left == right => left,
+        (Integer, Decimal) | (Decimal, Integer) => Decimal,
+        (Date, Timestamp) | (Timestamp, Date) => Timestamp,
+        _ => Text,
+    }
+}
+
+/// Display text for a cell; `None` for blanks and Excel errors such as `#N/A`.
+fn cell_text(cell: &Data) -> Option<String> {
+    match cell {
+        Data::Empty | Data::Error(_) => None,
+        Data::Int(value) => Some(value.to_string()),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 9e15 => {
+            Some(format!("{value:.0}"))
+        }
+        Data::Float(value) => Some(value.to_string()),
+        Data::Bool(value) => Some(value.to_string()),
+        Data::String(value) if value.trim().is_empty() => None,
+        Data::String(value) => Some(value.clone()),
+        Data::DateTime(value) => Some(match value.as_datetime() {
+            Some(datetime) if value.as_f64().fract() == 0.0 => datetime.date().to_string(),
+            Some(datetime) => datetime.to_string(),
+            None => value.as_f64().to_string(),
+        }),
+        Data::DateTimeIso(value) => Some(value.replacen('T', " ", 1)),
+        Data::DurationIso(value) => Some(value.clone()),
+    }
+}
+
+fn sql_literal(provider: DatabaseProvider, value_type: DbImportValueType, cell: &Data) -> String {
+    let Some(text) = cell_text(cell) else {
+        return "NULL".to_string();
+    };
+    // The cell's own type decides the literal's shape, so a type override never emits
+    // unquoted text.
+    let text = text.trim().to_string();
+    match (value_type, cell_type(cell)) {
+        (DbImportValueType::Text, _) | (_, DbImportValueType::Text) => {
+            string_literal(provider, cell_text(cell).as_deref().unwrap_or_default())
+        }
+        (_, DbImportValueType::Integer | DbImportValueType::Decimal) => text,
+        // Oracle before 23ai has no SQL boolean.
+        (_, DbImportValueType::Boolean) if provider == DatabaseProvider::Oracle => {
+            if text.eq_ignore_ascii_case("true") {
+                "1"
+            } else {
+                "0"
+            }
+            .to_string()
+        }
+        (_, DbImportValueType::Boolean) => text.to_uppercase(),
+        // ClickHouse parses date strings on insert but has no typed literals.
+        (_, DbImportValueType::Date | DbImportValueType::Timestamp)
+            if provider == DatabaseProvider::Clickhouse =>
+        {
+            string_literal(provider, &text)
+        }
+        (DbImportValueType::Date, DbImportValueType::Date) => {
+            format!("DATE {}", string_literal(provider, &text))
+        }
+        (_, DbImportValueType::Date | DbImportValueType::Timestamp) => {
+            let text = if text.len() == 10 {
+                format!("{text} 00:00:00")
+            } else {
+                text.replacen('T', " ", 1)
+            };
+            format!("TIMESTAMP {}", string_literal(provider, &text))
+        }
+        (_, DbImportValueType::Empty) => "NULL".to_string(),
+    }
+}
+
+pub(crate) fn string_literal(provider: DatabaseProvider, text: &str) -> String {
+    let escaped = match provider {
+        // Backslash escapes are on by default in both.
+        DatabaseProvider::Mysql | DatabaseProvider::Clickhouse => text.replace('\\', "\\\\"),
+        _ => text.to_string(),
+    };
+    format!("'{}'", escaped.replace('\'', "''"))
+}
+
+fn insert_statement(
+    provider: DatabaseProvider,
+    table: &str,
+    column_list: &str,
+    values: &[String],
+) -> String {
+    match provider {
+        // Multi-row VALUES only arrived in Oracle 23ai.
+        DatabaseProvider::Oracle => {
+            let mut sql = "INSERT ALL".to_string();
+            for row in values {
+                sql.push_str(&format!(" INTO {table} ({column_list}) VALUES ({row})"));
+            }
+            sql.push_str(" SELECT 1 FROM DUAL");
+            sql
+        }
+        _ => format!(
+            "INSERT INTO {table} ({column_list}) VALUES {}",
+            values
+                .iter()
+                .map(|row| format!("({row})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cell_text, cell_type, check_import_allowed, infer_columns, insert_statement, merge_types,
+        parse_tab_separated, split_header, sql_literal,
+    };
+    use crate::types::{DatabaseProvider, DbConnectRequest, DbImportValueType};
+    use calamine::Data;
+
+    #[test]
+    fn infers_column_types_from_cells() {
+        let infer = |cells: &[Data]| {
+            cells
+                .iter()
+                .map(cell_type)
+                .fold(DbImportValueType::Empty, merge_types)
+        };
+        assert_eq!(
+            infer(&[Data::Float(1.0), Data::Empty, Data::Int(7)]),
+            DbImportValueType::Integer
+        );
+        assert_eq!(
+            infer(&[Data::Float(1.0), Data::Float(2.5)]),
+            DbImportValueType::Decimal
+        );
+        assert_eq!(
+            infer(&[
+                Data::DateTimeIso("2024-03-01".to_string()),
+                Data::DateTimeIso("2024-03-01T10:30:00".to_string()),
+            ]),
+            DbImportValueType::Timestamp
+        );
+        assert_eq!(
+            infer(&[Data::Int(1), Data::String("n/a".to_string())]),
+            DbImportValueType::Text
+        );
+        assert_eq!(infer(&[Data::Empty]), DbImportValueType::Empty);
+    }
+
+    #[test]
+    fn parses_pasted_grids_and_types_their_text() {
+        let data = split_header(
+            parse_tab_separated(
+                "id\tzip\tnote\tshipped\r\n1\t01234\t\"two\tcells\nand \"\"quotes\"\"\"\t2024-03-01\r\n2.5\t98101\t\t2024-03-02 08:15:00\r\n",
+            ),
+            true,
+        );
+        assert_eq!(data.column_names, vec!["id", "zip", "note", "shipped"]);
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(
+            cell_text(&data.rows[0][2]).as_deref(),
+            Some("two\tcells\nand \"quotes\"")
+        );
+        assert_eq!(cell_text(&data.rows[1][2]), None);
+
+        let types = infer_columns(&data)
+            .into_iter()
+            .map(|column| column.value_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                DbImportValueType::Decimal,
+                DbImportValueType::Text,
+                DbImportValueType::Text,
+                DbImportValueType::Timestamp,
+            ]
+        );
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Mysql,
+                DbImportValueType::Timestamp,
+                &data.rows[0][3]
+            ),
+            "TIMESTAMP '2024-03-01 00:00:00'"
+        );
+    }
+
+    #[test]
+    fn renders_literals_and_batched_inserts_per_provider() {
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Mysql,
+                DbImportValueType::Text,
+                &Data::String("O'Brien \\ Co".to_string())
+            ),
+            "'O''Brien \\\\ Co'"
+        );
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Oracle,
+                DbImportValueType::Boolean,
+                &Data::Bool(true)
+            ),
+            "1"
+        );
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Oracle,
+                DbImportValueType::Timestamp,
+                &Data::DateTimeIso("2024-03-01".to_string())
+            ),
+            "TIMESTAMP '2024-03-01 00:00:00'"
+        );
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Snowflake,
+                DbImportValueType::Integer,
+                &Data::Empty
+            ),
+            "NULL"
+        );
+        assert_eq!(
+            sql_literal(
+                DatabaseProvider::Snowflake,
+                DbImportValueType::Integer,
+                &Data::String("1); DROP TABLE t; --".to_string())
+            ),
+            "'1); DROP TABLE t; --'"
+        );
+
+        let rows = vec!["1, 'a'".to_string(), "2, 'b'".to_string()];
+        assert_eq!(
+            insert_statement(DatabaseProvider::Duckdb, "t", "id, name", &rows),
+            "INSERT INTO t (id, name) VALUES (1, 'a'), (2, 'b')"
+        );
+        assert_eq!(
+            insert_statement(DatabaseProvider::Oracle, "T", "ID, NAME", &rows),
+            "INSERT ALL INTO T (ID, NAME) VALUES (1, 'a') INTO T (ID, NAME) VALUES (2, 'b') SELECT 1 FROM DUAL"
+        );
+    }
+
+    #[test]
+    fn imports_into_production_need_confirmation() {
+        let request: DbConnectRequest = serde_json::from_value(serde_json::json!({
+            "provider": "mysql",
+            "connection": {
+                "host": "db",
+                "port": 3306,
+                "database": "shop",
+                "username": "app",
+                "password": "secret",
+            },
+            "environment": "production",
+        }))
+        .unwrap();
+
+        let refusal = check_import_allowed(7, Some(&request), "public.orders", None).unwrap_err();
+        let token = refusal
+            .split('"')
+            .nth(1)
+            .expect("the refusal names a confirmation token");
+        assert!(check_import_allowed(7, Some(&request), "public.orders", Some("wrong")).is_err());
+        assert!(check_import_allowed(7, Some(&request), "public.orders", Some(token)).is_ok());
+        assert!(check_import_allowed(8, Some(&request), "public.orders", Some(token)).is_err());
+
+        let request = DbConnectRequest {
+            environment: Some("dev".to_string()),
+            ..request
+        };
+        assert!(check_import_allowed(7, Some(&request), "public.orders", None).is_ok());
+        assert!(check_import_allowed(7, None, "public.orders", None).is_ok());
+    }
+}
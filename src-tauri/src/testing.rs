@@ -0,0 +1,126 @@
+//! Drives the provider layer without Tauri, for the integration tests under `tests/`.
+//! Requests and results use the same JSON shapes as the IPC commands, so the crate's
+//! types can stay private.
+
+use crate::cancellation::CancelToken;
+use crate::data_export::export_query_data_blocking;
+use crate::providers::{AppSession, ProviderRegistry};
+use crate::state::SessionRegistry;
+use crate::types::{
+    DbConnectError, DbConnectRequest, DbExportQueryDataRequest, DbObjectRef, DbQueryRequest,
+};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Open provider sessions, keyed like the app's session map.
+#[derive(Default)]
+pub struct ProviderHarness {
+    sessions: SessionRegistry,
+    next_session_id: AtomicU64,
+}
+
+impl ProviderHarness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connects with a `db_connect` request body and returns the new session id.
+    pub fn connect(&self, request: Value) -> Result<u64, String> {
+        let request: DbConnectRequest = serde_json::from_value(request)
+            .map_err(|error| format!("Invalid connect request: {error}"))?;
+        let (session, _, _) =
+            ProviderRegistry::connect(&request).map_err(DbConnectError::into_message)?;
+        let session_id = self.next_session_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.sessions.insert(session_id, session)?;
+        Ok(session_id)
+    }
+
+    pub fn disconnect(&self, session_id: u64) -> Result<(), String> {
+        self.sessions
+            .remove(session_id)?
+            .map(|_| ())
+            .ok_or_else(|| "Session not found".to_string())
+    }
+
+    /// Runs one statement and returns the `db_run_query` result.
+    pub fn run_query(&self, session_id: u64, sql: &str) -> Result<Value, String> {
+        let request = DbQueryRequest {
+            session_id,
+            sql: sql.to_string(),
+            row_limit: None,
+            binds: Vec::new(),
+            sample_mode: false,
+            binary_preview_bytes: None,
+            production_confirmation: None,
+            timeout_secs: None,
+        };
+        let result = self.with_session(session_id, |session| {
+            ProviderRegistry::run_query(session, &request, &CancelToken::default())
+        })?;
+        to_value(&result)
+    }
+
+    /// Returns the `db_list_objects` result.
+    pub fn list_objects(&self, session_id: u64) -> Result<Value, String> {
+        let objects = self.with_session(session_id, |session| {
+            ProviderRegistry::list_objects(session)
+        })?;
+        to_value(&objects)
+    }
+
+    pub fn object_ddl(
+        &self,
+        session_id: u64,
+        schema: &str,
+        object_type: &str,
+        object_name: &str,
+    ) -> Result<String, String> {
+        let request = DbObjectRef {
+            session_id,
+            schema: schema.to_string(),
+            object_type: object_type.to_string(),
+            object_name: object_name.to_string(),
+        };
+        self.with_session(session_id, |session| {
+            ProviderRegistry::get_object_ddl(session, &request)
+        })
+    }
+
+    /// Exports a query's rows to a CSV file with default options and returns the
+    /// `db_export_query_data` result.
+    pub fn export_query_data(
+        &self,
+        session_id: u64,
+        sql: &str,
+        file_path: &Path,
+    ) -> Result<Value, String> {
+        let request = DbExportQueryDataRequest {
+            session_id,
+            sql: sql.to_string(),
+            file_path: file_path.to_string_lossy().into_owned(),
+            options: Default::default(),
+            key_column: None,
+            resume: false,
+        };
+        let result = export_query_data_blocking(
+            request,
+            self.sessions.clone(),
+            &mut |_| {},
+            &CancelToken::default(),
+        )?;
+        to_value(&result)
+    }
+
+    fn with_session<T>(
+        &self,
+        session_id: u64,
+        f: impl FnOnce(&mut AppSession) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.sessions.with_session(session_id, f)
+    }
+}
+
+fn to_value(value: &impl serde::Serialize) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|error| format!("Failed to serialize result: {error}"))
+}
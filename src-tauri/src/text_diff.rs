@@ -0,0 +1,226 @@
+use crate::files::normalize_export_file_content;
+use crate::types::{
+    DbDiffHunk, DbDiffLine, DbDiffLineKind, DbObjectFileDiffResult, DbTextDiffResult,
+};
+use similar::{Algorithm, ChangeTag, TextDiff};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const DEFAULT_CONTEXT_LINES: u32 = 3;
+const MAX_CONTEXT_LINES: u32 = 1000;
+const MAX_DIFF_INPUT_BYTES: usize = 32 * 1024 * 1024;
+// Past the deadline the diff falls back to a coarser (still correct) result instead of stalling.
+const DIFF_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Line-level diff of two text values, grouped into hunks with `context_lines` of
+/// unchanged text around each change.
+pub(crate) fn diff_text(
+    old: &str,
+    new: &str,
+    context_lines: Option<u32>,
+) -> Result<DbTextDiffResult, String> {
+    if old.len() + new.len() > MAX_DIFF_INPUT_BYTES {
+        return Err(format!(
+            "Values are too large to diff (limit is {} MB combined)",
+            MAX_DIFF_INPUT_BYTES / (1024 * 1024)
+        ));
+    }
+
+    let context_lines = context_lines
+        .unwrap_or(DEFAULT_CONTEXT_LINES)
+        .min(MAX_CONTEXT_LINES);
+    let diff = configured_diff(old, new);
+
+    let mut added_lines = 0_u32;
+    let mut removed_lines = 0_u32;
+    let mut hunks = Vec::new();
+
+    for group in diff.grouped_ops(context_lines as usize) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => DbDiffLineKind::Context,
+                    ChangeTag::Insert => {
+                        added_lines += 1;
+                        DbDiffLineKind::Added
+                    }
+                    ChangeTag::Delete => {
+                        removed_lines += 1;
+                        DbDiffLineKind::Removed
+                    }
+                };
+                lines.push(DbDiffLine {
+                    kind,
+                    old_line: change.old_index().map(|index| index as u32 + 1),
+                    new_line: change.new_index().map(|index| index as u32 + 1),
+                    text: trim_line_ending(change.value()).to_string(),
+                });
+            }
+        }
+
+        hunks.push(DbDiffHunk {
+            old_start: hunk_start(&old_range),
+            old_lines: old_range.len() as u32,
+            new_start: hunk_start(&new_range),
+            new_lines: new_range.len() as u32,
+            lines,
+        });
+    }
+
+    Ok(DbTextDiffResult {
+        identical: hunks.is_empty(),
+        added_lines,
+        removed_lines,
+        hunks,
+    })
+}
+
+/// Unified diff of live DDL against a local file. Both sides are normalized the way schema
+/// export writes files (LF line endings, one trailing newline), so an exported-and-committed
+/// file compares identical to the object it came from.
+pub(crate) fn diff_ddl_against_file(
+    ddl: &str,
+    ddl_label: &str,
+    file_path: &Path,
+    context_lines: Option<u32>,
+) -> Result<DbObjectFileDiffResult, String> {
+    let metadata = fs::metadata(file_path)
+        .map_err(|error| format!("Failed to read '{}': {error}", file_path.display()))?;
+    if metadata.len() as usize > MAX_DIFF_INPUT_BYTES {
+        return Err(format!("'{}' is too large to diff", file_path.display()));
+    }
+    let bytes = fs::read(file_path)
+        .map_err(|error| format!("Failed to read '{}': {error}", file_path.display()))?;
+    let file_text = String::from_utf8(bytes)
+        .map_err(|_| format!("'{}' is not valid UTF-8 text", file_path.display()))?;
+
+    let old = normalize_for_compare(ddl);
+    let new = normalize_for_compare(file_text.as_str());
+    let context_lines = context_lines
+        .unwrap_or(DEFAULT_CONTEXT_LINES)
+        .min(MAX_CONTEXT_LINES);
+    let diff = configured_diff(old.as_str(), new.as_str());
+
+    let mut added_lines = 0_u32;
+    let mut removed_lines = 0_u32;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added_lines += 1,
+            ChangeTag::Delete => removed_lines += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let identical = added_lines == 0 && removed_lines == 0;
+    let unified_diff = if identical {
+        String::new()
+    } else {
+        diff.unified_diff()
+            .context_radius(context_lines as usize)
+            .header(ddl_label, file_path.to_string_lossy().as_ref())
+            .to_string()
+    };
+
+    Ok(DbObjectFileDiffResult {
+        identical,
+        added_lines,
+        removed_lines,
+        unified_diff,
+    })
+}
+
+fn normalize_for_compare(text: &str) -> String {
+    normalize_export_file_content(text.replace("\r\n", "\n").as_str())
+}
+
+fn configured_diff<'a>(old: &'a str, new: &'a str) -> TextDiff<'a, 'a, 'a, str> {
+    TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(old, new)
+}
+
+// Unified diff convention: 1-based, except an empty range points at the line before it.
+fn hunk_start(range: &std::ops::Range<usize>) -> u32 {
+    if range.is_empty() {
+        range.start as u32
+    } else {
+        range.start as u32 + 1
+    }
+}
+
+fn trim_line_ending(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_ddl_against_file, diff_text};
+    use crate::types::DbDiffLineKind;
+
+    #[test]
+    fn groups_changes_into_hunks_with_line_numbers() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let result = diff_text(old, new, Some(1)).expect("diff should succeed");
+
+        assert!(!result.identical);
+        assert_eq!(result.added_lines, 2);
+        assert_eq!(result.removed_lines, 1);
+        assert_eq!(result.hunks.len(), 2);
+
+        let first = &result.hunks[0];
+        assert_eq!((first.old_start, first.old_lines), (1, 3));
+        assert_eq!((first.new_start, first.new_lines), (1, 3));
+        assert_eq!(first.lines[1].kind, DbDiffLineKind::Removed);
+        assert_eq!(first.lines[1].old_line, Some(2));
+        assert_eq!(first.lines[2].kind, DbDiffLineKind::Added);
+        assert_eq!(first.lines[2].text, "B");
+
+        let second = &result.hunks[1];
+        assert_eq!(
+            second.lines.last().map(|line| line.new_line),
+            Some(Some(11))
+        );
+
+        assert!(
+            diff_text("same\r\n", "same\r\n", None)
+                .expect("diff should succeed")
+                .identical
+        );
+    }
+
+    #[test]
+    fn diffs_ddl_against_file_ignoring_line_endings() {
+        let path = std::env::temp_dir().join(format!(
+            "clarity-ddl-diff-{}-{}.sql",
+            std::process::id(),
+            crate::workspace::now_millis()
+        ));
+        std::fs::write(&path, "CREATE VIEW V AS\r\nSELECT 1 FROM DUAL;\r\n").expect("write file");
+
+        let same =
+            diff_ddl_against_file("CREATE VIEW V AS\nSELECT 1 FROM DUAL;", "db", &path, None)
+                .expect("diff should succeed");
+        assert!(same.identical);
+        assert!(same.unified_diff.is_empty());
+
+        let changed =
+            diff_ddl_against_file("CREATE VIEW V AS\nSELECT 2 FROM DUAL;\n", "db", &path, None)
+                .expect("diff should succeed");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!((changed.added_lines, changed.removed_lines), (1, 1));
+        assert!(changed.unified_diff.starts_with("--- db\n+++ "));
+        assert!(changed
+            .unified_diff
+            .contains("-SELECT 2 FROM DUAL;\n+SELECT 1 FROM DUAL;\n"));
+    }
+}
@@ -0,0 +1,324 @@
+use crate::types::{
+    DbConnectConnection, DbConnectRequest, DbTnsAlias, DbTnsAliasList, OracleConnectOptions,
+    OracleConnectionOptions, OracleProtocol, OracleTlsOptions, OracleTnsAliasRef,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const TNSNAMES_FILE: &str = "tnsnames.ora";
+const TNS_ADMIN_ENV: &str = "TNS_ADMIN";
+const ORACLE_HOME_ENV: &str = "ORACLE_HOME";
+
+/// Where a `tnsnames.ora` alias points. Only the first address of a descriptor is used, so
+/// failover and load-balancing address lists connect to their first entry.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct TnsTarget {
+    pub(crate) protocol: OracleProtocol,
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+    pub(crate) service_name: String,
+    pub(crate) server_cert_dn: Option<String>,
+    pub(crate) server_dn_match: bool,
+    pub(crate) wallet_directory: Option<String>,
+}
+
+impl TnsTarget {
+    pub(crate) fn endpoint(&self) -> (String, u16) {
+        (
+            self.host.clone(),
+            self.port.unwrap_or(self.protocol.default_port()),
+        )
+    }
+}
+
+/// Aliases in the `tnsnames.ora` of `tns_admin`, or of the default location. Entries Clarity
+/// cannot connect to, such as those using `SID`, are reported separately.
+pub(crate) fn list_aliases(tns_admin: Option<&str>) -> Result<DbTnsAliasList, String> {
+    let directory = tns_admin_dir(tns_admin)?;
+    let mut aliases = Vec::new();
+    let mut skipped = Vec::new();
+    for (alias, descriptor) in read_tnsnames(&directory)? {
+        match parse_descriptor(descriptor.as_str()) {
+            Ok(target) => aliases.push(DbTnsAlias {
+                alias,
+                protocol: target.protocol,
+                host: target.host,
+                port: target.port.unwrap_or(target.protocol.default_port()),
+                service_name: target.service_name,
+            }),
+            Err(error) => skipped.push(format!("{alias}: {error}")),
+        }
+    }
+    Ok(DbTnsAliasList {
+        file_path: directory.join(TNSNAMES_FILE).to_string_lossy().to_string(),
+        aliases,
+        skipped,
+    })
+}
+
+/// Fills the host, port, service and TLS settings of an Oracle request that names a TNS
+/// alias. Other requests are returned unchanged.
+pub(crate) fn resolve_connect_request(
+    mut request: DbConnectRequest,
+) -> Result<DbConnectRequest, String> {
+    if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+        if let Some(tns_alias) = connection.tns_alias.clone() {
+            apply_target(connection, resolve_alias(&tns_alias)?);
+        }
+    }
+    Ok(request)
+}
+
+/// Replaces the address fields of a connection with those of a resolved descriptor.
+pub(crate) fn apply_target(connection: &mut OracleConnectOptions, target: TnsTarget) {
+    connection.protocol = target.protocol;
+    connection.host = target.host;
+    connection.port = target.port;
+    connection.service_name = target.service_name;
+    connection.tls = match target.protocol {
+        OracleProtocol::Tcp => None,
+        OracleProtocol::Tcps => Some(Box::new(OracleTlsOptions {
+            wallet_location: target.wallet_directory,
+            server_cert_dn: target.server_cert_dn,
+            server_dn_match: Some(target.server_dn_match),
+        })),
+    };
+}
+
+/// Host and port a TNS alias profile connects to, for reachability checks.
+pub(crate) fn profile_target(connection: &OracleConnectionOptions) -> Option<(String, u16)> {
+    resolve_alias(connection.tns_alias.as_ref()?)
+        .ok()
+        .map(|target| target.endpoint())
+}
+
+/// First host and port of a profile's raw connect descriptor, for reachability checks.
+pub(crate) fn descriptor_target(connection: &OracleConnectionOptions) -> Option<(String, u16)> {
+    parse_descriptor(connection.connect_descriptor.as_deref()?)
+        .ok()
+        .map(|target| target.endpoint())
+}
+
+/// Host and port behind a password store's alias, looked up in the wallet directory's
+/// `tnsnames.ora`, for reachability checks.
+pub(crate) fn password_store_target(connection: &OracleConnectionOptions) -> Option<(String, u16)> {
+    let store = connection.external_password_store.as_deref()?;
+    resolve_alias(&OracleTnsAliasRef {
+        alias: store.alias.clone(),
+        tns_admin: Some(store.wallet_dir.clone()),
+    })
+    .ok()
+    .map(|target| target.endpoint())
+}
+
+fn resolve_alias(tns_alias: &OracleTnsAliasRef) -> Result<TnsTarget, String> {
+    let directory = tns_admin_dir(tns_alias.tns_admin.as_deref())?;
+    let alias = tns_alias.alias.trim();
+    let descriptor = read_tnsnames(&directory)?
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(alias))
+        .map(|(_, descriptor)| descriptor)
+        .ok_or_else(|| {
+            format!(
+                "TNS alias {alias} was not found in {}",
+                directory.join(TNSNAMES_FILE).display()
+            )
+        })?;
+    parse_descriptor(descriptor.as_str()).map_err(|error| format!("TNS alias {alias}: {error}"))
+}
+
+/// The configured folder, else `TNS_ADMIN`, else `$ORACLE_HOME/network/admin`, the same
+/// order the Oracle client searches.
+pub(crate) fn tns_admin_dir(configured: Option<&str>) -> Result<PathBuf, String> {
+    let non_empty = |value: String| {
+        let value = value.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+    if let Some(directory) = configured.map(str::to_string).and_then(non_empty) {
+        return Ok(PathBuf::from(directory));
+    }
+    if let Some(directory) = std::env::var(TNS_ADMIN_ENV).ok().and_then(non_empty) {
+        return Ok(PathBuf::from(directory));
+    }
+    if let Some(home) = std::env::var(ORACLE_HOME_ENV).ok().and_then(non_empty) {
+        return Ok(PathBuf::from(home).join("network").join("admin"));
+    }
+    Err("TNS_ADMIN is not set; choose the folder that contains tnsnames.ora".to_string())
+}
+
+pub(crate) fn read_tnsnames(directory: &Path) -> Result<Vec<(String, String)>, String> {
+    let path = directory.join(TNSNAMES_FILE);
+    let content = fs::read_to_string(&path)
+        .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    Ok(parse_tnsnames(content.as_str()))
+}
+
+/// Splits `alias = (DESCRIPTION=...)` entries. Entries may span lines, and one descriptor
+/// can be shared by a comma-separated list of aliases.
+fn parse_tnsnames(content: &str) -> Vec<(String, String)> {
+    let content = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut entries = Vec::new();
+    let mut rest = content.as_str();
+    while let Some(equals) = rest.find('=') {
+        let names = rest[..equals].trim();
+        let after = rest[equals + 1..].trim_start();
+        if !after.starts_with('(') {
+            rest = &rest[equals + 1..];
+            continue;
+        }
+
+        let mut depth = 0usize;
+        let mut end = after.len();
+        for (index, ch) in after.char_indices() {
+            match ch {
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        end = index + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let descriptor = after[..end]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        for name in names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            entries.push((name.to_string(), descriptor.clone()));
+        }
+        rest = &after[end..];
+    }
+    entries
+}
+
+pub(crate) fn parse_descriptor(descriptor: &str) -> Result<TnsTarget, String> {
+    let host =
+        descriptor_value(descriptor, "HOST").ok_or_else(|| "The entry has no HOST".to_string())?;
+    let service_name = descriptor_value(descriptor, "SERVICE_NAME")
+        .ok_or_else(|| "The entry has no SERVICE_NAME".to_string())?;
+    let protocol = match descriptor_value(descriptor, "PROTOCOL") {
+        Some(protocol) if protocol.eq_ignore_ascii_case("tcps") => OracleProtocol::Tcps,
+        _ => OracleProtocol::Tcp,
+    };
+    Ok(TnsTarget {
+        protocol,
+        host,
+        port: descriptor_value(descriptor, "PORT").and_then(|port| port.parse().ok()),
+        service_name,
+        server_cert_dn: descriptor_value(descriptor, "SSL_SERVER_CERT_DN"),
+        server_dn_match: descriptor_value(descriptor, "SSL_SERVER_DN_MATCH").is_none_or(|value| {
+            !matches!(value.to_ascii_lowercase().as_str(), "no" | "off" | "false")
+        }),
+
+        wallet_directory: descriptor_value(descriptor, "MY_WALLET_DIRECTORY"),
+    })
+}
+
+/// First `(KEY=value)` in a descriptor, matched case-insensitively; quotes are removed.
+fn descriptor_value(descriptor: &str, key: &str) -> Option<String> {
+    let upper = descriptor.to_ascii_uppercase();
+    let mut search_from = 0;
+    while let Some(offset) = upper[search_from..].find('(') {
+        let start = search_from + offset + 1;
+        search_from = start;
+        let rest = upper[start..].trim_start();
+        let Some(after_key) = rest.strip_prefix(key) else {
+            continue;
+        };
+        let Some(value_start) = after_key.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        // `upper` and `descriptor` share byte offsets, since only ASCII letters change case.
+        let value_offset = descriptor.len() - value_start.len();
+        let raw = descriptor[value_offset..].trim_start();
+        let value = match raw.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next().unwrap_or(""),
+            None => raw.split(')').next().unwrap_or("").trim(),
+        };
+        return Some(value.to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_aliases, parse_descriptor, parse_tnsnames, TnsTarget};
+    use crate::types::OracleProtocol;
+
+    #[test]
+    fn parses_autonomous_database_tnsnames() {
+        let content = "\
+# Generated by Oracle Cloud
+mydb_high = (description= (retry_count=20)(retry_delay=3)(address=(protocol=tcps)(port=1522)
+  (host=adb.eu-frankfurt-1.oraclecloud.com))(connect_data=(service_name=abc_mydb_high.adb.oraclecloud.com))
+  (security=(ssl_server_dn_match=yes)))
+
+mydb_low, mydb_tp = (description=(address=(protocol=tcps)(port=1522)(host=adb.example.com))
+  (connect_data=(service_name=abc_mydb_low.adb.oraclecloud.com))
+  (security=(ssl_server_cert_dn=\"CN=adb.example.com, O=Oracle Corporation\")))
+";
+        let entries = parse_tnsnames(content);
+        let aliases = entries
+            .iter()
+            .map(|(alias, _)| alias.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(aliases, vec!["mydb_high", "mydb_low", "mydb_tp"]);
+
+        assert_eq!(
+            parse_descriptor(entries[0].1.as_str()).expect("descriptor"),
+            TnsTarget {
+                protocol: OracleProtocol::Tcps,
+                host: "adb.eu-frankfurt-1.oraclecloud.com".to_string(),
+                port: Some(1522),
+                service_name: "abc_mydb_high.adb.oraclecloud.com".to_string(),
+                server_cert_dn: None,
+                server_dn_match: true,
+                wallet_directory: None,
+            }
+        );
+        assert_eq!(
+            parse_descriptor(entries[2].1.as_str())
+                .expect("descriptor")
+                .server_cert_dn
+                .as_deref(),
+            Some("CN=adb.example.com, O=Oracle Corporation")
+        );
+        assert!(parse_descriptor("(description=(address=(port=1)))").is_err());
+    }
+
+    #[test]
+    fn lists_client_aliases_and_skips_sid_entries() {
+        let directory = std::env::temp_dir().join(format!(
+            "clarity-tnsnames-test-{}",
+            crate::workspace::now_millis()
+        ));
+        std::fs::create_dir_all(&directory).expect("create directory");
+        std::fs::write(
+            directory.join(super::TNSNAMES_FILE),
+            "ORCL = (DESCRIPTION = (ADDRESS = (HOST = db01)(PORT = 1521))\n\
+             (CONNECT_DATA = (SERVICE_NAME = orcl.example.com)))\n\
+             LEGACY = (DESCRIPTION = (ADDRESS = (PROTOCOL = TCP)(HOST = db02))(CONNECT_DATA = (SID = LEG)))\n",
+        )
+        .expect("write tnsnames");
+
+        let list = list_aliases(directory.to_str()).expect("list");
+        let _ = std::fs::remove_dir_all(&directory);
+        assert_eq!(list.aliases.len(), 1);
+        assert_eq!(list.aliases[0].alias, "ORCL");
+        assert_eq!(list.aliases[0].protocol, OracleProtocol::Tcp);
+        assert_eq!(list.aliases[0].port, 1521);
+        assert_eq!(list.skipped, vec!["LEGACY: The entry has no SERVICE_NAME"]);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -7,6 +8,11 @@ pub(crate) enum DatabaseProvider {
     Postgres,
     Mysql,
     Sqlite,
+    Duckdb,
+    Snowflake,
+    Clickhouse,
+    Libsql,
+    External,
 }
 
 impl DatabaseProvider {
@@ -16,6 +22,11 @@ impl DatabaseProvider {
             DatabaseProvider::Postgres => "postgres",
             DatabaseProvider::Mysql => "mysql",
             DatabaseProvider::Sqlite => "sqlite",
+            DatabaseProvider::Duckdb => "duckdb",
+            DatabaseProvider::Snowflake => "snowflake",
+            DatabaseProvider::Clickhouse => "clickhouse",
+            DatabaseProvider::Libsql => "libsql",
+            DatabaseProvider::External => "external",
         }
     }
 }
@@ -28,6 +39,151 @@ pub(crate) enum OracleAuthMode {
     Sysdba,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OracleProtocol {
+    #[default]
+    Tcp,
+    Tcps,
+}
+
+impl OracleProtocol {
+    pub(crate) fn default_port(self) -> u16 {
+        match self {
+            OracleProtocol::Tcp => 1521,
+            OracleProtocol::Tcps => 2484,
+        }
+    }
+}
+
+/// A further listener for the same service, such as another RAC node.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleAddress {
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+}
+
+/// How RAC and Data Guard services are reached: how `host` and `failover_hosts` are tried
+/// when connecting, and whether an open session moves to a surviving instance (Transparent
+/// Application Failover) when its own goes down.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleFailoverOptions {
+    /// Picks an address at random for each new session instead of trying them in order.
+    #[serde(default)]
+    pub(crate) load_balance: bool,
+    /// Times the whole address list is tried again before connecting fails.
+    #[serde(default)]
+    pub(crate) retry_count: Option<u32>,
+    /// Seconds between those attempts.
+    #[serde(default)]
+    pub(crate) retry_delay_secs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) taf: Option<OracleTafOptions>,
+}
+
+/// Transparent Application Failover: the client reconnects a session whose instance failed
+/// and carries on. Uncommitted work is rolled back either way.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleTafOptions {
+    #[serde(default)]
+    pub(crate) failover_type: OracleTafType,
+    /// Reconnect attempts after a failure; 30 when not set.
+    #[serde(default)]
+    pub(crate) retries: Option<u32>,
+    /// Seconds between reconnect attempts; 5 when not set.
+    #[serde(default)]
+    pub(crate) delay_secs: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OracleTafType {
+    /// Also reopens the open query and skips the rows already fetched.
+    #[default]
+    Select,
+    /// Only reconnects; open queries fail.
+    Session,
+}
+
+/// Settings for TCPS listeners. Without a wallet location the client falls back to the
+/// wallet configured in `sqlnet.ora`, if any.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleTlsOptions {
+    #[serde(default)]
+    pub(crate) wallet_location: Option<String>,
+    /// Distinguished name the server certificate must carry, e.g. `CN=db.example.com`.
+    #[serde(default)]
+    pub(crate) server_cert_dn: Option<String>,
+    /// Checks the server certificate against `server_cert_dn`, or the host name when no DN
+    /// is given. On unless turned off.
+    #[serde(default)]
+    pub(crate) server_dn_match: Option<bool>,
+}
+
+/// An alias in an imported wallet. When set, the host, port, service name and TLS settings
+/// come from the wallet's `tnsnames.ora` instead of the connection fields.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleWalletRef {
+    pub(crate) wallet_name: String,
+    pub(crate) service_alias: String,
+}
+
+/// Looks the net service name up in an LDAP directory (Oracle Internet Directory or Active
+/// Directory) instead of using the connection's host and service fields.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleLdapNaming {
+    pub(crate) name: String,
+    /// `host[:port]` entries; read from `DIRECTORY_SERVERS` in `ldap.ora` when empty.
+    #[serde(default)]
+    pub(crate) directory_servers: Vec<String>,
+    /// e.g. `dc=example,dc=com`; read from `ldap.ora` when missing.
+    #[serde(default)]
+    pub(crate) default_admin_context: Option<String>,
+    /// Folder holding `ldap.ora`, searched like `tnsAdmin`.
+    #[serde(default)]
+    pub(crate) tns_admin: Option<String>,
+}
+
+/// An alias in an existing `tnsnames.ora`. When set, the host, port, service name and
+/// protocol come from the alias instead of the connection fields.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleTnsAliasRef {
+    pub(crate) alias: String,
+    /// Folder holding `tnsnames.ora`; defaults to `TNS_ADMIN`, then
+    /// `$ORACLE_HOME/network/admin`.
+    #[serde(default)]
+    pub(crate) tns_admin: Option<String>,
+}
+
+/// Signs in with the credential a Secure External Password Store (an auto-login wallet
+/// filled with `mkstore -createCredential`) holds for `alias`, like `/@alias` in SQL*Plus.
+/// Clarity keeps no username or password for the connection.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct OracleExternalPasswordStore {
+    /// The connect string the credential is stored under, usually a `tnsnames.ora` alias.
+    pub(crate) alias: String,
+    /// Folder holding `cwallet.sso` and the `sqlnet.ora` and `tnsnames.ora` that refer to
+    /// it. It becomes the Oracle client's configuration directory.
+    pub(crate) wallet_dir: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TimestampTzMode {
+    #[default]
+    Original,
+    Session,
+    Utc,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct OracleConnectionOptions {
@@ -38,6 +194,36 @@ pub(crate) struct OracleConnectionOptions {
     pub(crate) schema: String,
     #[serde(default)]
     pub(crate) oracle_auth_mode: OracleAuthMode,
+    /// Proxy authentication: `username` signs in with its own password and the session
+    /// runs as this user, like `username[proxy_user]` in SQL*Plus.
+    #[serde(default)]
+    pub(crate) proxy_user: Option<String>,
+    #[serde(default)]
+    pub(crate) edition: Option<String>,
+    #[serde(default)]
+    pub(crate) protocol: OracleProtocol,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tls: Option<Box<OracleTlsOptions>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) wallet: Option<OracleWalletRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) tns_alias: Option<OracleTnsAliasRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ldap: Option<Box<OracleLdapNaming>>,
+    /// Listeners tried in order after `host` when it cannot be reached.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) failover_hosts: Vec<OracleAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) failover: Option<Box<OracleFailoverOptions>>,
+    /// A full `(DESCRIPTION=...)` connect descriptor, used instead of the host, port and
+    /// service fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) connect_descriptor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) external_password_store: Option<Box<OracleExternalPasswordStore>>,
+    /// Instant Client directory for this profile, overriding the one in settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) oracle_client_lib_dir: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -51,7 +237,31 @@ pub(crate) struct OracleConnectOptions {
     pub(crate) schema: String,
     #[serde(default)]
     pub(crate) oracle_auth_mode: OracleAuthMode,
+    /// Proxy authentication: `username` signs in with its own password and the session
+    /// runs as this user, like `username[proxy_user]` in SQL*Plus.
+    #[serde(default)]
+    pub(crate) proxy_user: Option<String>,
     pub(crate) oracle_client_lib_dir: Option<String>,
+    #[serde(default)]
+    pub(crate) edition: Option<String>,
+    #[serde(default)]
+    pub(crate) protocol: OracleProtocol,
+    #[serde(default)]
+    pub(crate) tls: Option<Box<OracleTlsOptions>>,
+    #[serde(default)]
+    pub(crate) wallet: Option<OracleWalletRef>,
+    #[serde(default)]
+    pub(crate) tns_alias: Option<OracleTnsAliasRef>,
+    #[serde(default)]
+    pub(crate) ldap: Option<Box<OracleLdapNaming>>,
+    #[serde(default)]
+    pub(crate) failover_hosts: Vec<OracleAddress>,
+    #[serde(default)]
+    pub(crate) failover: Option<Box<OracleFailoverOptions>>,
+    #[serde(default)]
+    pub(crate) connect_descriptor: Option<String>,
+    #[serde(default)]
+    pub(crate) external_password_store: Option<Box<OracleExternalPasswordStore>>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -87,251 +297,2670 @@ pub(crate) struct SqliteConnectionOptions {
     pub(crate) file_path: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DuckdbConnectionOptions {
+    /// A `.duckdb` database, or a `.parquet` / `.csv` file opened as a view.
+    pub(crate) file_path: String,
+    /// `duckdb` executable; looked up on `PATH` when omitted.
+    #[serde(default)]
+    pub(crate) cli_path: Option<String>,
+}
+
+/// How the SQL API token is sent; the API does not accept passwords.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SnowflakeTokenType {
+    #[default]
+    ProgrammaticAccessToken,
+    Oauth,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnowflakeConnectionOptions {
+    /// Account identifier (`orgname-account`) or full `*.snowflakecomputing.com` host.
+    pub(crate) account: String,
+    pub(crate) username: String,
+    pub(crate) database: String,
+    pub(crate) schema: Option<String>,
+    pub(crate) warehouse: Option<String>,
+    pub(crate) role: Option<String>,
+    #[serde(default)]
+    pub(crate) token_type: SnowflakeTokenType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnowflakeConnectOptions {
+    pub(crate) account: String,
+    pub(crate) username: String,
+    pub(crate) token: String,
+    pub(crate) database: String,
+    pub(crate) schema: Option<String>,
+    pub(crate) warehouse: Option<String>,
+    pub(crate) role: Option<String>,
+    #[serde(default)]
+    pub(crate) token_type: SnowflakeTokenType,
+}
+
+/// A hosted libSQL database such as Turso, reached over the Hrana HTTP API.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibsqlConnectionOptions {
+    /// `libsql://`, `https://` or `http://` database URL.
+    pub(crate) url: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibsqlConnectOptions {
+    pub(crate) url: String,
+    /// Database token; may be empty for a local `sqld` without authentication.
+    #[serde(default)]
+    pub(crate) auth_token: String,
+}
+
+/// Connection settings for an adapter installed under the app data `providers` directory.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExternalConnectionOptions {
+    pub(crate) provider_id: String,
+    /// Passed to the adapter's `connect` unchanged.
+    #[serde(default)]
+    pub(crate) options: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExternalConnectOptions {
+    pub(crate) provider_id: String,
+    #[serde(default)]
+    pub(crate) password: String,
+    #[serde(default)]
+    pub(crate) options: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An adapter manifest, `<app data>/providers/<name>.json`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExternalProvider {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) name: String,
+    /// SQL dialect shown next to the provider name, e.g. "Firebird".
+    #[serde(default)]
+    pub(crate) dialect: Option<String>,
+    pub(crate) command: String,
+    #[serde(default)]
+    pub(crate) args: Vec<String>,
+}
+
+/// Bastion host a connection is forwarded through, stored with the profile.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SshTunnelOptions {
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+    pub(crate) username: String,
+    /// Private key; the ssh agent and default keys are used when omitted.
+    #[serde(default)]
+    pub(crate) identity_file: Option<String>,
+    /// `ssh` executable; looked up on `PATH` when omitted.
+    #[serde(default)]
+    pub(crate) cli_path: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SshTunnelConnectOptions {
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+    pub(crate) username: String,
+    #[serde(default)]
+    pub(crate) identity_file: Option<String>,
+    /// SSH password, or the passphrase of `identity_file`.
+    #[serde(default)]
+    pub(crate) password: Option<String>,
+    #[serde(default)]
+    pub(crate) cli_path: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct DbConnectRequest {
     #[serde(flatten)]
     pub(crate) connection: DbConnectConnection,
+    #[serde(default)]
+    pub(crate) ssh_tunnel: Option<SshTunnelConnectOptions>,
+    #[serde(default)]
+    pub(crate) timestamp_tz_mode: TimestampTzMode,
+    #[serde(default)]
+    pub(crate) prefetch_ai_schema_context: bool,
+    /// Marks the session for the UI's safety banners; statements are not blocked.
+    #[serde(default)]
+    pub(crate) read_only: bool,
+    /// Free-form tag such as `prod` or `dev`, echoed back in the session summary. `prod`
+    /// and `production` turn on the production guard (see `sql_safety`).
+    #[serde(default)]
+    pub(crate) environment: Option<String>,
+    /// Objects a production session may still drop or truncate, as names or patterns such
+    /// as `STAGING_*`.
+    #[serde(default)]
+    pub(crate) drop_allowlist: Vec<String>,
+    /// Seconds to wait for each connection attempt; defaults to 30.
+    #[serde(default)]
+    pub(crate) connect_timeout_secs: Option<u32>,
+    /// Total attempts, including the first; defaults to 1.
+    #[serde(default)]
+    pub(crate) connect_attempts: Option<u32>,
+    #[serde(default)]
+    pub(crate) retry_delay_millis: Option<u64>,
+    /// Statements run after connecting, before the session is used.
+    #[serde(default)]
+    pub(crate) startup_sql: Option<String>,
+    /// Connections the session may hold, including the primary one; defaults to 2.
+    #[serde(default)]
+    pub(crate) pool_size: Option<u32>,
+    /// Saved profile the request was built from, so `db_connect` can take over its warm
+    /// standby connection.
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+    /// Read replica that read-only queries are sent to; see `read_replica`.
+    #[serde(default)]
+    pub(crate) reader_endpoint: Option<DbReaderEndpoint>,
+}
+
+/// A read replica of the profile's database, reached with the same credentials and
+/// settings. A blank host means none.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbReaderEndpoint {
+    pub(crate) host: String,
+    /// Defaults to the primary's port.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) port: Option<u16>,
+    /// Oracle service of the replica, such as an Active Data Guard read-only service;
+    /// defaults to the primary's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) service_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "provider", content = "connection", rename_all = "lowercase")]
+pub(crate) enum DbConnectConnection {
+    Oracle(OracleConnectOptions),
+    Postgres(NetworkConnectOptions),
+    Mysql(NetworkConnectOptions),
+    Sqlite(SqliteConnectionOptions),
+    Duckdb(DuckdbConnectionOptions),
+    Snowflake(SnowflakeConnectOptions),
+    Clickhouse(NetworkConnectOptions),
+    Libsql(LibsqlConnectOptions),
+    External(ExternalConnectOptions),
+}
+
+impl DbConnectRequest {
+    pub(crate) fn provider(&self) -> DatabaseProvider {
+        self.connection.provider()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTestConnectionRequest {
+    #[serde(flatten)]
+    pub(crate) connect: DbConnectRequest,
+    /// Saved profile whose stored secrets fill in an empty password or SSH password.
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbConnectionTestResult {
+    pub(crate) provider: DatabaseProvider,
+    pub(crate) display_name: String,
+    pub(crate) schema: String,
+    pub(crate) server_version: Option<String>,
+    pub(crate) instance_name: Option<String>,
+    pub(crate) connect_millis: u64,
+    /// Round trip of a trivial query; `None` for external adapters.
+    pub(crate) latency_millis: Option<u64>,
+    pub(crate) message: String,
+}
+
+impl DbConnectConnection {
+    /// Password or token sent on connect; `None` for file-based providers.
+    pub(crate) fn secret_mut(&mut self) -> Option<&mut String> {
+        match self {
+            DbConnectConnection::Oracle(connection) => Some(&mut connection.password),
+            DbConnectConnection::Postgres(connection)
+            | DbConnectConnection::Mysql(connection)
+            | DbConnectConnection::Clickhouse(connection) => Some(&mut connection.password),
+            DbConnectConnection::Snowflake(connection) => Some(&mut connection.token),
+            DbConnectConnection::Libsql(connection) => Some(&mut connection.auth_token),
+            DbConnectConnection::External(connection) => Some(&mut connection.password),
+            DbConnectConnection::Sqlite(_) | DbConnectConnection::Duckdb(_) => None,
+        }
+    }
+
+    pub(crate) fn provider(&self) -> DatabaseProvider {
+        match self {
+            DbConnectConnection::Oracle(_) => DatabaseProvider::Oracle,
+            DbConnectConnection::Postgres(_) => DatabaseProvider::Postgres,
+            DbConnectConnection::Mysql(_) => DatabaseProvider::Mysql,
+            DbConnectConnection::Sqlite(_) => DatabaseProvider::Sqlite,
+            DbConnectConnection::Duckdb(_) => DatabaseProvider::Duckdb,
+            DbConnectConnection::Snowflake(_) => DatabaseProvider::Snowflake,
+            DbConnectConnection::Clickhouse(_) => DatabaseProvider::Clickhouse,
+            DbConnectConnection::Libsql(_) => DatabaseProvider::Libsql,
+            DbConnectConnection::External(_) => DatabaseProvider::External,
+        }
+    }
+}
+
+/// Payload of the session-reconnected event, sent after a dropped connection was replaced
+/// (or could not be).
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionReconnected {
+    pub(crate) session_id: u64,
+    pub(crate) reconnected: bool,
+    pub(crate) replayed: bool,
+    /// The server rejected the stored password; reconnect with a new one.
+    pub(crate) needs_password: bool,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbIdleTimeoutRequest {
+    /// 0 keeps idle sessions open.
+    pub(crate) minutes: u32,
+}
+
+/// Payload of the event sent when an unused session is closed.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionIdleDisconnected {
+    pub(crate) session_id: u64,
+    pub(crate) idle_minutes: u64,
+    pub(crate) message: String,
+}
+
+/// A state file that failed to parse at startup and was moved aside.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbStateFileRecovery {
+    pub(crate) file_name: String,
+    /// The parse error.
+    pub(crate) error: String,
+    /// Where the damaged file now is; `None` if it could not be moved.
+    pub(crate) moved_to: Option<String>,
+    pub(crate) message: String,
+    pub(crate) recovered_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbErrorSummaryEntry {
+    pub(crate) message: String,
+    pub(crate) count: u64,
+    /// Unix milliseconds.
+    pub(crate) first_at: u64,
+    pub(crate) last_at: u64,
+}
+
+/// Payload of the event sent once a burst of errors on a session has settled. `count` is
+/// how often each error occurred in the burst.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionErrors {
+    pub(crate) session_id: u64,
+    pub(crate) errors: Vec<DbErrorSummaryEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbErrorSummary {
+    pub(crate) session_id: u64,
+    pub(crate) total_errors: u64,
+    /// Most recent first, with counts since the session opened.
+    pub(crate) errors: Vec<DbErrorSummaryEntry>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbSessionTimelineEventKind {
+    Connect,
+    Reconnect,
+    Disconnect,
+    Statement,
+    Script,
+    DdlEdit,
+    Export,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionTimelineEvent {
+    /// Milliseconds since the Unix epoch.
+    pub(crate) at: u64,
+    pub(crate) kind: DbSessionTimelineEventKind,
+    pub(crate) summary: String,
+    pub(crate) sql: Option<String>,
+    pub(crate) succeeded: bool,
+    pub(crate) error: Option<String>,
+    pub(crate) elapsed_millis: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionTimeline {
+    pub(crate) session_id: u64,
+    pub(crate) label: String,
+    /// Oldest events dropped once the timeline reached its size limit.
+    pub(crate) dropped_events: u64,
+    pub(crate) events: Vec<DbSessionTimelineEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExportSessionTimelineRequest {
+    pub(crate) session_id: u64,
+    pub(crate) file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionTimelineExport {
+    pub(crate) file_path: String,
+    pub(crate) event_count: usize,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbKeepAliveRequest {
+    /// 0 turns keep-alive pings off.
+    pub(crate) interval_secs: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbReconnectRequest {
+    pub(crate) session_id: u64,
+    /// Replaces the password, token or auth token the session was opened with.
+    #[serde(default)]
+    pub(crate) password: Option<String>,
+    /// Reads the secrets saved for this profile again, e.g. after a password change.
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbReconnectSessionRequest {
+    pub(crate) session_id: u64,
+    #[serde(default)]
+    pub(crate) password: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) row_limit: Option<u32>,
+    /// Values for `:name` placeholders; only Oracle sessions accept them.
+    #[serde(default)]
+    pub(crate) binds: Vec<DbQueryBind>,
+    /// Exploratory run: fetch only the first `row_limit` rows of a SELECT, under a short
+    /// time limit where the provider supports one.
+    #[serde(default)]
+    pub(crate) sample_mode: bool,
+    /// Bytes of RAW, LONG RAW and BLOB values shown as hex; longer values are cut off.
+    /// Oracle only.
+    #[serde(default)]
+    pub(crate) binary_preview_bytes: Option<u32>,
+    /// Token from an earlier refusal, confirming statements that change data or objects
+    /// on a production session.
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+    /// Seconds the run may take before it is stopped.
+    #[serde(default)]
+    pub(crate) timeout_secs: Option<u32>,
+}
+
+/// `db_run_query` input: a query plus values for its substitution variables.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRunQueryRequest {
+    #[serde(flatten)]
+    pub(crate) query: DbQueryRequest,
+    /// Values for `&name` substitution variables by name, matched case-insensitively.
+    /// Oracle only.
+    #[serde(default)]
+    pub(crate) substitutions: HashMap<String, String>,
+    /// Confirms DROP, TRUNCATE and unfiltered DELETE/UPDATE statements; without it they are
+    /// refused before anything runs.
+    #[serde(default)]
+    pub(crate) allow_destructive: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryBind {
+    /// Placeholder name without the leading colon.
+    pub(crate) name: String,
+    /// `None` binds NULL.
+    pub(crate) value: Option<String>,
+    #[serde(default)]
+    pub(crate) value_type: DbBindType,
+}
+
+/// How a bind value is sent. Text leaves the conversion to Oracle; the others are
+/// converted before the statement runs, so a bad value fails with a clear message.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbBindType {
+    #[default]
+    Text,
+    Number,
+    /// `YYYY-MM-DD`, optionally followed by a time.
+    Date,
+    /// `YYYY-MM-DD HH:MI:SS[.FF]`; a `T` may separate date and time.
+    Timestamp,
+    /// OUT `SYS_REFCURSOR` of a PL/SQL call; the value is ignored and the cursor's rows are
+    /// returned in `ref_cursors`.
+    RefCursor,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbListBindNamesRequest {
+    pub(crate) sql: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTimestampTzModeRequest {
+    pub(crate) session_id: u64,
+    pub(crate) mode: TimestampTzMode,
+}
+
+/// Session NLS parameters. Fields left out of a set request keep their current value.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSetSessionNlsRequest {
+    pub(crate) session_id: u64,
+    #[serde(default)]
+    pub(crate) date_format: Option<String>,
+    #[serde(default)]
+    pub(crate) timestamp_format: Option<String>,
+    /// Decimal then group separator, e.g. `.,`.
+    #[serde(default)]
+    pub(crate) numeric_characters: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionNls {
+    pub(crate) date_format: String,
+    pub(crate) timestamp_format: String,
+    pub(crate) numeric_characters: String,
+}
+
+/// Limits for heavy ad-hoc queries. Each request states the full set: a field left out
+/// removes that limit.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSetResourceLimitsRequest {
+    pub(crate) session_id: u64,
+    /// Resource Manager consumer group to switch the session to. Leaving it out switches
+    /// back to the group the session started in.
+    #[serde(default)]
+    pub(crate) consumer_group: Option<String>,
+    /// Longest any single database call may run before it is cancelled.
+    #[serde(default)]
+    pub(crate) call_timeout_secs: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSetContainerRequest {
+    pub(crate) session_id: u64,
+    /// `CDB$ROOT` or a pluggable database name.
+    pub(crate) container: String,
+}
+
+/// A row of `V$CONTAINERS`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbContainer {
+    pub(crate) container_id: u64,
+    pub(crate) name: String,
+    /// `READ WRITE`, `READ ONLY`, `MOUNTED` or `MIGRATE`.
+    pub(crate) open_mode: Option<String>,
+    pub(crate) restricted: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbResourceLimits {
+    pub(crate) consumer_group: Option<String>,
+    /// The group in effect before the first switch, restored when `consumer_group` is cleared.
+    pub(crate) original_consumer_group: Option<String>,
+    pub(crate) call_timeout_secs: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbFilteredQueryRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) row_limit: Option<u32>,
+    pub(crate) global_search: Option<String>,
+    pub(crate) column_filters: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSchemaSearchRequest {
+    pub(crate) session_id: u64,
+    pub(crate) search_term: String,
+    pub(crate) limit: Option<u32>,
+    pub(crate) include_object_names: Option<bool>,
+    pub(crate) include_source: Option<bool>,
+    pub(crate) include_ddl: Option<bool>,
+    /// Profile whose object tags are attached to results and matched against the term.
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+    /// Seconds the search may take before it is stopped.
+    #[serde(default)]
+    pub(crate) timeout_secs: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectRef {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectDdl {
+    pub(crate) ddl: String,
+    /// Rebuilt from the data dictionary because the provider's DDL generator was unavailable.
+    pub(crate) reconstructed: bool,
+}
+
+impl DbObjectDdl {
+    pub(crate) fn generated(ddl: String) -> Self {
+        Self {
+            ddl,
+            reconstructed: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectDdlAsOfRequest {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    /// Epoch milliseconds.
+    pub(crate) as_of: u64,
+}
+
+/// An earlier version of an object's DDL.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectDdlAsOf {
+    pub(crate) ddl: String,
+    pub(crate) source: DbDdlHistorySource,
+    /// When a backup was taken; the object was replaced through Clarity right after.
+    pub(crate) captured_at: Option<u64>,
+    pub(crate) message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbDdlHistorySource {
+    /// Read from the database with a flashback query.
+    Flashback,
+    /// Clarity's own DDL backup.
+    Backup,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectDdlUpdateRequest {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) ddl: String,
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExportSchemaRequest {
+    pub(crate) session_id: u64,
+    pub(crate) destination_directory: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbImportValueType {
+    Integer,
+    Decimal,
+    Boolean,
+    Date,
+    Timestamp,
+    Text,
+    /// Every cell in the column is blank.
+    Empty,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbXlsxPreviewRequest {
+    pub(crate) file_path: String,
+    /// First sheet when omitted.
+    pub(crate) sheet_name: Option<String>,
+    #[serde(default = "default_first_row_is_header")]
+    pub(crate) first_row_is_header: bool,
+    pub(crate) preview_rows: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbImportColumn {
+    pub(crate) name: String,
+    pub(crate) value_type: DbImportValueType,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbXlsxPreview {
+    pub(crate) sheet_names: Vec<String>,
+    pub(crate) sheet_name: String,
+    pub(crate) columns: Vec<DbImportColumn>,
+    pub(crate) rows: Vec<Vec<String>>,
+    pub(crate) total_rows: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbImportColumnMapping {
+    /// Zero-based spreadsheet column.
+    pub(crate) source_index: usize,
+    /// Blank skips the column.
+    pub(crate) target_column: String,
+    /// Overrides the inferred type, e.g. to keep leading zeros as text.
+    #[serde(default)]
+    pub(crate) value_type: Option<DbImportValueType>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbXlsxImportRequest {
+    pub(crate) session_id: u64,
+    pub(crate) file_path: String,
+    pub(crate) sheet_name: Option<String>,
+    #[serde(default = "default_first_row_is_header")]
+    pub(crate) first_row_is_header: bool,
+    pub(crate) schema: Option<String>,
+    pub(crate) table_name: String,
+    pub(crate) columns: Vec<DbImportColumnMapping>,
+    pub(crate) batch_size: Option<u32>,
+    /// Token from an earlier refusal, confirming an import into a production session.
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbImportClipboardRowsRequest {
+    pub(crate) session_id: u64,
+    /// Tab-separated rows as copied from a spreadsheet or result grid.
+    pub(crate) text: String,
+    #[serde(default = "default_first_row_is_header")]
+    pub(crate) first_row_is_header: bool,
+    #[serde(default)]
+    pub(crate) schema: Option<String>,
+    #[serde(default)]
+    pub(crate) table_name: String,
+    #[serde(default)]
+    pub(crate) columns: Vec<DbImportColumnMapping>,
+    pub(crate) preview_rows: Option<u32>,
+    /// Parse and preview only.
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+    /// Token from an earlier refusal, confirming an import into a production session.
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbClipboardImportResult {
+    pub(crate) columns: Vec<DbImportColumn>,
+    pub(crate) rows: Vec<Vec<String>>,
+    pub(crate) total_rows: u64,
+    /// `None` for dry runs.
+    pub(crate) rows_inserted: Option<u64>,
+    pub(crate) message: String,
+}
+
+fn default_first_row_is_header() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbCodegenObject {
+    pub(crate) schema: String,
+    pub(crate) object_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGenerateCodeRequest {
+    pub(crate) session_id: u64,
+    pub(crate) objects: Vec<DbCodegenObject>,
+    /// Tera template source.
+    pub(crate) template: String,
+    /// Tera template for each file's path relative to `destination_directory`, e.g.
+    /// `{{ table.pascalName }}.java`.
+    pub(crate) file_name: String,
+    pub(crate) destination_directory: String,
+    #[serde(default)]
+    pub(crate) overwrite: bool,
+    /// Render without writing, for previews.
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+}
+
+/// What a generated grant script gives access to.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbGrantAccess {
+    /// `SELECT` on tables, views and sequences.
+    Read,
+    /// `INSERT`, `UPDATE` and `DELETE` on tables.
+    Write,
+    /// `EXECUTE` on procedures, functions and packages.
+    Execute,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGrantObject {
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGenerateGrantsRequest {
+    pub(crate) session_id: u64,
+    /// Role or user receiving the privileges.
+    pub(crate) grantee: String,
+    pub(crate) access: Vec<DbGrantAccess>,
+    #[serde(default)]
+    pub(crate) objects: Vec<DbGrantObject>,
+    /// Object names in the session's schema, with `*` or `%` for any run of characters and
+    /// `?` for a single one, e.g. `V_SALES*`.
+    #[serde(default)]
+    pub(crate) patterns: Vec<String>,
+    /// Also create private synonyms in this schema, so its user can leave out the owner.
+    #[serde(default)]
+    pub(crate) synonym_schema: Option<String>,
+}
+
+/// A grant script for review. Nothing has been run; pass `script` to `db_run_script`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGrantScript {
+    pub(crate) script: String,
+    pub(crate) grant_count: usize,
+    pub(crate) synonym_count: usize,
+    /// Matched objects none of the requested privileges apply to, as `SCHEMA.NAME (TYPE)`.
+    pub(crate) skipped: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAqBrowseRequest {
+    pub(crate) session_id: u64,
+    pub(crate) queue_name: String,
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRecentErrorsRequest {
+    pub(crate) session_id: u64,
+    pub(crate) since_minutes: Option<u32>,
+    pub(crate) error_code: Option<String>,
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRowLocksRequest {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) table_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectAccessStatsRequest {
+    pub(crate) session_id: u64,
+    /// Read the AWR history for this many days instead of the counters since instance
+    /// startup. Only used where the Diagnostics Pack is enabled.
+    #[serde(default)]
+    pub(crate) history_days: Option<u32>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDiffCellValuesRequest {
+    pub(crate) left: String,
+    pub(crate) right: String,
+    pub(crate) context_lines: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectFileDiffRequest {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) file_path: String,
+    pub(crate) context_lines: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbEditObjectExternallyRequest {
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    /// Editor command line, e.g. `code --wait`; `{file}` marks where the path goes.
+    /// The OS default application for `.sql` files is used when omitted.
+    pub(crate) editor_command: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExternalEdit {
+    pub(crate) edit_id: u64,
+    pub(crate) session_id: u64,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) file_path: String,
+    pub(crate) started_at: u64,
+}
+
+/// Payload of the external edit saved event: the file content after a save.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExternalEditSaved {
+    pub(crate) edit: DbExternalEdit,
+    pub(crate) ddl: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbStopExternalEditRequest {
+    pub(crate) edit_id: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CsvEncoding {
+    #[default]
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Windows1252,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CsvQuoteStyle {
+    #[default]
+    Minimal,
+    All,
+    /// No quotes, except around values holding the delimiter, a quote or a line break.
+    Never,
+}
+
+/// How NULL cells are written. Text equal to the NULL marker is quoted so loaders can
+/// tell the two apart.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CsvNullStyle {
+    #[default]
+    Empty,
+    Null,
+    /// `\N`, as read by MySQL `LOAD DATA` and Postgres `COPY ... TEXT`.
+    BackslashN,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbCsvExportOptions {
+    pub(crate) delimiter: Option<String>,
+    pub(crate) quote_style: CsvQuoteStyle,
+    pub(crate) null_style: CsvNullStyle,
+    pub(crate) encoding: CsvEncoding,
+    pub(crate) decimal_separator: Option<String>,
+    pub(crate) date_format: Option<String>,
+    pub(crate) include_header: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExportQueryDataRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) file_path: String,
+    #[serde(default)]
+    pub(crate) options: DbCsvExportOptions,
+    /// Unique, NOT NULL column the rows are exported in order of, so an interrupted export
+    /// can resume after the last key it wrote. Resuming needs one; a NULL key fails the export.
+    #[serde(default)]
+    pub(crate) key_column: Option<String>,
+    #[serde(default)]
+    pub(crate) resume: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbJobKind {
+    DataExport,
+    XlsxImport,
+    SqlFileImport,
+    SchemaSnapshot,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbJobState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbJobStatus {
+    pub(crate) job_id: u64,
+    pub(crate) kind: DbJobKind,
+    pub(crate) label: String,
+    pub(crate) state: DbJobState,
+    pub(crate) processed: u64,
+    pub(crate) message: String,
+    pub(crate) started_at: u64,
+    pub(crate) updated_at: u64,
+    pub(crate) finished_at: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbJobRequest {
+    pub(crate) job_id: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDataExportResult {
+    pub(crate) file_path: String,
+    pub(crate) row_count: u64,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveQuerySheetRequest {
+    pub(crate) suggested_file_name: String,
+    pub(crate) sql: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveQuerySheetInput {
+    pub(crate) title: String,
+    pub(crate) sql: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveQuerySheetsRequest {
+    pub(crate) sheets: Vec<DbSaveQuerySheetInput>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConnectionProfileRef {
+    pub(crate) profile_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveConnectionProfileRequest {
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) connection: DbConnectionProfile,
+    #[serde(default)]
+    pub(crate) ssh_tunnel: Option<SshTunnelOptions>,
+    pub(crate) save_password: bool,
+    pub(crate) password: Option<String>,
+    /// Saved alongside `password` when `save_password` is set.
+    #[serde(default)]
+    pub(crate) ssh_password: Option<String>,
+    #[serde(default)]
+    pub(crate) read_only: bool,
+    #[serde(default)]
+    pub(crate) environment: Option<String>,
+    #[serde(default)]
+    pub(crate) startup_sql: Option<String>,
+    #[serde(default)]
+    pub(crate) warm_standby: bool,
+    #[serde(default)]
+    pub(crate) drop_allowlist: Vec<String>,
+    #[serde(default)]
+    pub(crate) reader_endpoint: Option<DbReaderEndpoint>,
+}
+
+/// State of a warm standby connection kept for a profile.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbStandbyConnection {
+    pub(crate) profile_id: String,
+    pub(crate) profile_name: String,
+    pub(crate) ready: bool,
+    pub(crate) opened_at: Option<u64>,
+    pub(crate) last_checked_at: u64,
+    /// Why the last attempt to open the connection failed.
+    pub(crate) error: Option<String>,
+}
+
+/// A recurring export of a profile's DDL into a directory, optionally committed to git.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSchemaSnapshotSchedule {
+    pub(crate) id: String,
+    pub(crate) profile_id: String,
+    pub(crate) destination_directory: String,
+    pub(crate) interval_hours: u32,
+    #[serde(default)]
+    pub(crate) git_commit: bool,
+    #[serde(default = "default_enabled")]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) last_run_at: Option<u64>,
+    #[serde(default)]
+    pub(crate) last_error: Option<String>,
+    #[serde(default)]
+    pub(crate) last_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveSchemaSnapshotScheduleRequest {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    pub(crate) profile_id: String,
+    pub(crate) destination_directory: String,
+    /// Hours between runs; defaults to 24 for a nightly history.
+    #[serde(default)]
+    pub(crate) interval_hours: Option<u32>,
+    #[serde(default)]
+    pub(crate) git_commit: bool,
+    #[serde(default = "default_enabled")]
+    pub(crate) enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSchemaSnapshotScheduleRef {
+    pub(crate) id: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionInfo {
+    pub(crate) session_id: u64,
+    pub(crate) provider: DatabaseProvider,
+    pub(crate) pool_size: u32,
+    pub(crate) open_connections: u32,
+    /// Pooled connections currently running an explorer or search read.
+    pub(crate) busy_pooled_connections: u32,
+    pub(crate) idle_secs: u64,
+    /// Unix milliseconds when Clarity opened the session.
+    pub(crate) connected_at: u64,
+    pub(crate) server: DbServerInfo,
+}
+
+/// What the server reports about itself and the session; fields it cannot report are null.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbServerInfo {
+    pub(crate) version: Option<String>,
+    pub(crate) banner: Option<String>,
+    pub(crate) instance_name: Option<String>,
+    /// Oracle container: `CDB$ROOT` or a pluggable database name.
+    pub(crate) container_name: Option<String>,
+    /// Oracle container id; 1 is the root, 2 the seed and higher ids are PDBs.
+    pub(crate) container_id: Option<u64>,
+    pub(crate) current_user: Option<String>,
+    pub(crate) current_schema: Option<String>,
+    /// Oracle SID or MySQL connection id.
+    pub(crate) server_session_id: Option<u64>,
+    pub(crate) serial_number: Option<u64>,
+    /// Logon time as the server reports it, `YYYY-MM-DDTHH:MM:SS`.
+    pub(crate) logon_time: Option<String>,
+    /// Host of the instance the session runs on.
+    pub(crate) server_host: Option<String>,
+    /// Whether Transparent Application Failover has moved the session to another instance.
+    /// Oracle only, and only for accounts that can read `V$SESSION`.
+    pub(crate) failed_over: Option<bool>,
+}
+
+/// A known Oracle Instant Client installation.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbOracleClient {
+    pub(crate) path: String,
+    pub(crate) label: String,
+    pub(crate) library_found: bool,
+    /// The client libraries of this process were loaded from here.
+    pub(crate) loaded: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveOracleClientRequest {
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbOracleClientPathRequest {
+    pub(crate) path: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRunDiagnosticsRequest {
+    /// Instant Client directory from settings; the usual lookup applies when omitted.
+    #[serde(default)]
+    pub(crate) oracle_client_lib_dir: Option<String>,
+    /// Skips the TCP checks of saved profiles, which can take a few seconds offline.
+    #[serde(default)]
+    pub(crate) skip_network: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbDiagnosticStatus {
+    Ok,
+    Failed,
+    Skipped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDiagnosticCheck {
+    /// Stable key: `keyring`, `oracleClient`, `appData`, or `profile:<id>`.
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) status: DbDiagnosticStatus,
+    pub(crate) detail: String,
+    pub(crate) duration_ms: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDiagnosticsReport {
+    pub(crate) app_version: String,
+    pub(crate) os: String,
+    pub(crate) arch: String,
+    pub(crate) generated_at: u64,
+    pub(crate) portable_data_dir: Option<String>,
+    pub(crate) checks: Vec<DbDiagnosticCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbCreateSupportBundleRequest {
+    pub(crate) file_path: String,
+    /// Instant Client directory from settings, for the diagnostics in the bundle.
+    #[serde(default)]
+    pub(crate) oracle_client_lib_dir: Option<String>,
+    /// The frontend's settings; secrets are redacted before they are written.
+    #[serde(default)]
+    pub(crate) settings: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSupportBundleResult {
+    pub(crate) file_path: String,
+    /// Files in the archive.
+    pub(crate) entries: Vec<String>,
+    pub(crate) message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSessionSummary {
+    pub(crate) session_id: u64,
+    pub(crate) display_name: String,
+    pub(crate) schema: String,
+    pub(crate) provider: DatabaseProvider,
+    /// Server host, database URL, or `None` for file-based providers. With an SSH tunnel
+    /// this is the server behind the bastion.
+    pub(crate) host: Option<String>,
+    /// Oracle service name, database name, or the database file.
+    pub(crate) service: Option<String>,
+    pub(crate) username: Option<String>,
+    pub(crate) read_only: bool,
+    pub(crate) environment: Option<String>,
+    pub(crate) server_version: Option<String>,
+    /// Oracle instance the session landed on, which tells RAC nodes apart.
+    pub(crate) instance_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConnectionProfile {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) connection: DbConnectionProfile,
+    #[serde(default)]
+    pub(crate) ssh_tunnel: Option<SshTunnelOptions>,
+    pub(crate) has_password: bool,
+    #[serde(default)]
+    pub(crate) has_ssh_password: bool,
+    #[serde(default)]
+    pub(crate) read_only: bool,
+    #[serde(default)]
+    pub(crate) environment: Option<String>,
+    #[serde(default)]
+    pub(crate) startup_sql: Option<String>,
+    #[serde(default)]
+    pub(crate) warm_standby: bool,
+    #[serde(default)]
+    pub(crate) drop_allowlist: Vec<String>,
+    #[serde(default)]
+    pub(crate) reader_endpoint: Option<DbReaderEndpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoredConnectionProfile {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) connection: DbConnectionProfile,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) ssh_tunnel: Option<SshTunnelOptions>,
+    #[serde(default)]
+    pub(crate) read_only: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) environment: Option<String>,
+    /// Statements run after each connect, e.g. `ALTER SESSION SET NLS_DATE_FORMAT = ...`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) startup_sql: Option<String>,
+    /// Keep a connection open in the background so connecting is instant.
+    #[serde(default)]
+    pub(crate) warm_standby: bool,
+    /// Objects that may be dropped or truncated when the profile is tagged as production.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) drop_allowlist: Vec<String>,
+    /// Read replica for read-only queries of sessions opened from the profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) reader_endpoint: Option<DbReaderEndpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "provider", content = "connection", rename_all = "lowercase")]
+pub(crate) enum DbConnectionProfile {
+    Oracle(OracleConnectionOptions),
+    Postgres(NetworkConnectionOptions),
+    Mysql(NetworkConnectionOptions),
+    Sqlite(SqliteConnectionOptions),
+    Duckdb(DuckdbConnectionOptions),
+    Snowflake(SnowflakeConnectionOptions),
+    Clickhouse(NetworkConnectionOptions),
+    Libsql(LibsqlConnectionOptions),
+    External(ExternalConnectionOptions),
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectEntry {
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) status: Option<String>,
+    pub(crate) invalid_reason: Option<String>,
+    pub(crate) edition_name: Option<String>,
+    /// `YYYY-MM-DDTHH:MM:SS` in the server's time zone, so the values sort as text.
+    pub(crate) created: Option<String>,
+    pub(crate) last_ddl_time: Option<String>,
+    /// Oracle global temporary table.
+    pub(crate) temporary: bool,
+    /// Generated by Oracle for a domain index rather than created directly.
+    pub(crate) secondary: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectEditionInfo {
+    pub(crate) session_edition: String,
+    pub(crate) object_edition: Option<String>,
+    pub(crate) inherited: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectDependent {
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectColumnEntry {
+    pub(crate) schema: String,
+    pub(crate) object_name: String,
+    pub(crate) column_name: String,
+    pub(crate) data_type: String,
+    pub(crate) nullable: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbListObjectColumnsRequest {
+    pub(crate) session_id: u64,
+    /// Only these objects, such as the tables of an ER diagram; `None` lists every column
+    /// of the connected schema.
+    #[serde(default)]
+    pub(crate) objects: Option<Vec<DbObjectName>>,
+    /// Metadata connections read from at once, up to the session's pool size.
+    #[serde(default)]
+    pub(crate) concurrency: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectName {
+    pub(crate) schema: String,
+    pub(crate) object_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbMetadataQueryRequest {
+    pub(crate) session_id: u64,
+    /// Objects to describe; when empty, the schema's objects that pass `filter`.
+    #[serde(default)]
+    pub(crate) objects: Vec<DbObjectName>,
+    #[serde(default)]
+    pub(crate) filter: DbMetadataFilter,
+    /// What to read for each object besides its name, type and status.
+    #[serde(default)]
+    pub(crate) include: Vec<DbMetadataFacet>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbMetadataFilter {
+    /// Name or pattern such as `ORDER_*`.
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    /// Types such as `TABLE` or `VIEW`; any type when empty.
+    #[serde(default)]
+    pub(crate) object_types: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbMetadataFacet {
+    Columns,
+    Constraints,
+    Stats,
+    Grants,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbMetadataDocument {
+    pub(crate) objects: Vec<DbObjectMetadata>,
+    /// More objects matched than are described.
+    pub(crate) truncated: bool,
+    /// Requested facets that could not be read, with the reason; the others are filled in.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) unavailable: Vec<String>,
+}
+
+/// One object with the requested facets; facets that were not requested are left out.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectMetadata {
+    pub(crate) schema: String,
+    pub(crate) object_name: String,
+    /// `None` for a listed object that was not found.
+    pub(crate) object_type: Option<String>,
+    pub(crate) status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) columns: Option<Vec<DbMetadataColumn>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) constraints: Option<Vec<DbObjectConstraint>>,
+    /// Optimizer statistics; tables only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) stats: Option<DbObjectStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) grants: Option<Vec<DbObjectGrant>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbMetadataColumn {
+    pub(crate) name: String,
+    pub(crate) data_type: String,
+    pub(crate) nullable: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectConstraint {
+    pub(crate) name: String,
+    pub(crate) constraint_type: DbConstraintType,
+    pub(crate) columns: Vec<String>,
+    /// Table a foreign key points at, as `SCHEMA.TABLE`.
+    pub(crate) references: Option<String>,
+    pub(crate) enabled: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbConstraintType {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+    Check,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectStats {
+    /// `None` until statistics are gathered.
+    pub(crate) num_rows: Option<u64>,
+    pub(crate) blocks: Option<u64>,
+    pub(crate) avg_row_len: Option<u64>,
+    /// `YYYY-MM-DDTHH:MM:SS` in the server's time zone.
+    pub(crate) last_analyzed: Option<String>,
+    pub(crate) stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectGrant {
+    pub(crate) grantee: String,
+    pub(crate) privilege: String,
+    pub(crate) grantable: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryResult {
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+    /// Parallel to `rows`, true where a cell is NULL rather than the text shown for it.
+    /// Empty when no cell is NULL.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) null_flags: Vec<Vec<bool>>,
+    /// Parallel to `rows`, the full size of binary cells, whose text is a hex preview.
+    /// Empty when the result has no binary values.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) byte_lengths: Vec<Vec<Option<u64>>>,
+    #[serde(default)]
+    pub(crate) rows_affected: Option<u64>,
+    #[serde(default)]
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) lossy_conversion: bool,
+    /// Set while more rows remain; `db_fetch_more_rows` pages through them. Oracle only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cursor_id: Option<u64>,
+    /// `&name` substitution variables the SQL still needs values for. When not empty,
+    /// nothing was run. Oracle only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) required_variables: Vec<String>,
+    /// Rows of the REF CURSOR binds a PL/SQL call opened, in bind order, followed by the
+    /// implicit results it returned with `DBMS_SQL.RETURN_RESULT`. Oracle only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) ref_cursors: Vec<DbRefCursorResult>,
+}
+
+/// A cursor returned through a REF CURSOR bind or as an implicit result, read up to the row
+/// limit.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRefCursorResult {
+    /// Bind name without the leading colon, or the position of an implicit result from 1.
+    pub(crate) name: String,
+    /// Returned with `DBMS_SQL.RETURN_RESULT` rather than through a bind.
+    #[serde(default)]
+    pub(crate) implicit: bool,
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) null_flags: Vec<Vec<bool>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) byte_lengths: Vec<Vec<Option<u64>>>,
+    /// More rows remained past the row limit; they are not kept.
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbFetchMoreRowsRequest {
+    pub(crate) session_id: u64,
+    pub(crate) cursor_id: u64,
+    #[serde(default)]
+    pub(crate) count: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbCloseCursorRequest {
+    pub(crate) session_id: u64,
+    pub(crate) cursor_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExportResultReportRequest {
+    pub(crate) session_id: u64,
+    pub(crate) file_path: String,
+    #[serde(default)]
+    pub(crate) title: Option<String>,
+    pub(crate) sql: String,
+    /// The result as the UI shows it; the query is not run again.
+    pub(crate) result: DbQueryResult,
+    /// Shown in the header, usually the profile name.
+    #[serde(default)]
+    pub(crate) connection_label: Option<String>,
+    #[serde(default)]
+    pub(crate) elapsed_millis: Option<u64>,
+    /// When the query ran, already formatted for the reader's locale.
+    #[serde(default)]
+    pub(crate) executed_at: Option<String>,
+    /// Adds the execution plan; only Oracle sessions can produce one.
+    #[serde(default)]
+    pub(crate) include_plan: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbResultReport {
+    pub(crate) file_path: String,
+    pub(crate) row_count: usize,
+    pub(crate) plan_included: bool,
+    pub(crate) message: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSqlStatement {
+    pub(crate) sql: String,
+    /// 1-based line of the script the statement starts on.
+    pub(crate) line: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSplitSqlRequest {
+    pub(crate) provider: DatabaseProvider,
+    pub(crate) sql: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRunScriptRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) row_limit: Option<u32>,
+    #[serde(default = "default_stop_on_error")]
+    pub(crate) stop_on_error: bool,
+    /// Confirms DROP, TRUNCATE and unfiltered DELETE/UPDATE statements; without it the
+    /// script is refused before anything runs.
+    #[serde(default)]
+    pub(crate) allow_destructive: bool,
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+    /// Seconds the script may take before it is stopped.
+    #[serde(default)]
+    pub(crate) timeout_secs: Option<u32>,
+    /// Directory that relative `@file` includes in Oracle scripts resolve against.
+    #[serde(default)]
+    pub(crate) base_directory: Option<String>,
+    /// Values for `&name` substitution variables the script does not `DEFINE` itself.
+    #[serde(default)]
+    pub(crate) substitutions: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSqlFileImportRequest {
+    pub(crate) session_id: u64,
+    pub(crate) file_path: String,
+    #[serde(default = "default_stop_on_error")]
+    pub(crate) stop_on_error: bool,
+    #[serde(default)]
+    pub(crate) allow_destructive: bool,
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+}
+
+fn default_stop_on_error() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbScriptStatementResult {
+    pub(crate) sql: String,
+    pub(crate) line: u32,
+    pub(crate) result: Option<DbQueryResult>,
+    pub(crate) error: Option<String>,
+    /// `DBMS_OUTPUT` lines the statement wrote while `SET SERVEROUTPUT ON` was in effect.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) output: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbScriptResult {
+    /// Statements that ran, in order; those after a failure are missing when stopping on
+    /// errors.
+    pub(crate) statements: Vec<DbScriptStatementResult>,
+    pub(crate) total_statements: u32,
+    pub(crate) failed_statements: u32,
+}
+
+/// Identifies a cell by re-running the query, so the row must still be at `row_index`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDownloadLobRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) row_index: u32,
+    pub(crate) column_index: u32,
+    pub(crate) file_path: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbLobDownloadResult {
+    pub(crate) file_path: String,
+    pub(crate) byte_length: u64,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbConsistentQueriesRequest {
+    pub(crate) session_id: u64,
+    pub(crate) queries: Vec<String>,
+    pub(crate) row_limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbConsistentQueriesResult {
+    /// System change number the snapshot was taken at, when the session may read it.
+    pub(crate) scn: Option<String>,
+    /// One result per query, in request order.
+    pub(crate) results: Vec<DbQueryResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbEncodingInfo {
+    pub(crate) database_charset: String,
+    pub(crate) national_charset: String,
+    pub(crate) client_charset: String,
+    pub(crate) nls_lang: Option<String>,
+    pub(crate) length_semantics: String,
+    pub(crate) warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTransactionState {
+    pub(crate) active: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSchemaSearchResult {
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) match_scope: String,
+    pub(crate) line: Option<u32>,
+    pub(crate) snippet: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSchemaExportResult {
+    pub(crate) destination_directory: String,
+    pub(crate) object_count: usize,
+    pub(crate) file_count: usize,
+    pub(crate) skipped_count: usize,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGeneratedFile {
+    pub(crate) path: String,
+    pub(crate) content: String,
+    pub(crate) object_names: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbGenerateCodeResult {
+    pub(crate) files: Vec<DbGeneratedFile>,
+    pub(crate) written: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAqQueueEntry {
+    pub(crate) schema: String,
+    pub(crate) queue_name: String,
+    pub(crate) queue_table: String,
+    pub(crate) queue_type: String,
+    pub(crate) payload_type: String,
+    pub(crate) enqueue_enabled: bool,
+    pub(crate) dequeue_enabled: bool,
+    pub(crate) max_retries: Option<i64>,
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAqMessage {
+    pub(crate) message_id: String,
+    pub(crate) correlation_id: Option<String>,
+    pub(crate) priority: Option<i64>,
+    pub(crate) state: String,
+    pub(crate) enqueued_at: Option<String>,
+    pub(crate) retry_count: Option<i64>,
+    pub(crate) exception_queue: Option<String>,
+    pub(crate) payload: Option<String>,
+    pub(crate) payload_format: String,
+    pub(crate) payload_truncated: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAqBrowseResult {
+    pub(crate) queue_name: String,
+    pub(crate) payload_type: String,
+    pub(crate) messages: Vec<DbAqMessage>,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbServerErrorEntry {
+    pub(crate) timestamp: String,
+    pub(crate) error_code: Option<String>,
+    pub(crate) message: String,
+    pub(crate) level: Option<i64>,
+    pub(crate) module: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRecentErrorsResult {
+    pub(crate) source: String,
+    pub(crate) entries: Vec<DbServerErrorEntry>,
+    pub(crate) message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTableLockHolder {
+    pub(crate) sid: i64,
+    pub(crate) serial: i64,
+    pub(crate) username: Option<String>,
+    pub(crate) os_user: Option<String>,
+    pub(crate) machine: Option<String>,
+    pub(crate) program: Option<String>,
+    pub(crate) status: Option<String>,
+    pub(crate) lock_mode: String,
+    pub(crate) sql_id: Option<String>,
+    pub(crate) blocked_sessions: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRowLockWaiter {
+    pub(crate) sid: i64,
+    pub(crate) serial: i64,
+    pub(crate) username: Option<String>,
+    pub(crate) blocking_sid: Option<i64>,
+    pub(crate) wait_event: Option<String>,
+    pub(crate) seconds_waiting: Option<i64>,
+    pub(crate) row_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRowLocksResult {
+    pub(crate) schema: String,
+    pub(crate) table_name: String,
+    pub(crate) holders: Vec<DbTableLockHolder>,
+    pub(crate) waiters: Vec<DbRowLockWaiter>,
+    pub(crate) message: String,
+}
+
+/// Activity on a table and its indexes, in blocks.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTableAccessStats {
+    pub(crate) table_name: String,
+    pub(crate) logical_reads: u64,
+    pub(crate) physical_reads: u64,
+    pub(crate) physical_writes: u64,
+    pub(crate) block_changes: u64,
+    pub(crate) table_scans: u64,
+    /// 1 for the most-read table; None when the table was not read at all.
+    pub(crate) read_rank: Option<u32>,
+    /// 1 for the most-changed table; None when the table was not changed at all.
+    pub(crate) write_rank: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectAccessStatsResult {
+    pub(crate) schema: String,
+    /// `history` (AWR) or `segmentStatistics` (since instance startup).
+    pub(crate) source: String,
+    /// Start of the period the counters cover, when known.
+    pub(crate) since: Option<String>,
+    /// Busiest first; tables without any activity come last.
+    pub(crate) tables: Vec<DbTableAccessStats>,
+    pub(crate) idle_tables: u32,
+    pub(crate) message: String,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbDiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDiffLine {
+    pub(crate) kind: DbDiffLineKind,
+    pub(crate) old_line: Option<u32>,
+    pub(crate) new_line: Option<u32>,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDiffHunk {
+    pub(crate) old_start: u32,
+    pub(crate) old_lines: u32,
+    pub(crate) new_start: u32,
+    pub(crate) new_lines: u32,
+    pub(crate) lines: Vec<DbDiffLine>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTextDiffResult {
+    pub(crate) identical: bool,
+    pub(crate) added_lines: u32,
+    pub(crate) removed_lines: u32,
+    pub(crate) hunks: Vec<DbDiffHunk>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectFileDiffResult {
+    pub(crate) identical: bool,
+    pub(crate) added_lines: u32,
+    pub(crate) removed_lines: u32,
+    pub(crate) unified_diff: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAiSchemaContextStatus {
+    pub(crate) session_id: u64,
+    pub(crate) object_count: u32,
+    pub(crate) column_count: u32,
+    pub(crate) built_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbEditorBuffer {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) sql: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveEditorBuffersRequest {
+    pub(crate) buffers: Vec<DbEditorBuffer>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryHistoryEntry {
+    pub(crate) id: String,
+    pub(crate) sql: String,
+    pub(crate) provider: DatabaseProvider,
+    pub(crate) executed_at: u64,
+    pub(crate) succeeded: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryHistoryRequest {
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbIdentifierUsageRequest {
+    /// Only count history from this provider.
+    pub(crate) provider: Option<DatabaseProvider>,
+    pub(crate) limit: Option<u32>,
+}
+
+/// How many successful history entries refer to a name, for ranking completions.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbIdentifierUsage {
+    pub(crate) name: String,
+    pub(crate) count: u32,
+    pub(crate) last_used_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSnippet {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) sql: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    pub(crate) updated_at: u64,
+    #[serde(default)]
+    pub(crate) parameter_sets: Vec<DbSnippetParameterSet>,
+}
+
+/// An object pinned to a profile's quick-access list.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbPinnedObject {
+    pub(crate) profile_id: String,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) pinned_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbPinObjectRequest {
+    pub(crate) profile_id: String,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    /// `false` removes the pin.
+    #[serde(default = "default_pinned")]
+    pub(crate) pinned: bool,
+}
+
+fn default_pinned() -> bool {
+    true
+}
+
+/// User-defined tags on an object of a profile, e.g. "billing" or "deprecated".
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTaggedObject {
+    pub(crate) profile_id: String,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbObjectTagRequest {
+    pub(crate) profile_id: String,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) tag: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbListTaggedObjectsRequest {
+    pub(crate) profile_id: String,
+    /// Only objects carrying this tag; every tagged object when omitted.
+    #[serde(default)]
+    pub(crate) tag: Option<String>,
+}
+
+/// Named bind values for a snippet, e.g. "Customer A / March".
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSnippetParameterSet {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) values: Vec<DbQueryBind>,
+    pub(crate) updated_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveSnippetParameterSetRequest {
+    pub(crate) snippet_id: String,
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) values: Vec<DbQueryBind>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSnippetParameterSetRef {
+    pub(crate) snippet_id: String,
+    pub(crate) parameter_set_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbRunSnippetRequest {
+    pub(crate) session_id: u64,
+    pub(crate) snippet_id: String,
+    /// Runs the snippet without binds when omitted.
+    pub(crate) parameter_set_id: Option<String>,
+    pub(crate) row_limit: Option<u32>,
+    #[serde(default)]
+    pub(crate) allow_destructive: bool,
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveSnippetRequest {
+    pub(crate) id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) sql: String,
+    pub(crate) description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSnippetRef {
+    pub(crate) snippet_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveResultSnapshotRequest {
+    pub(crate) name: String,
+    pub(crate) notes: Option<String>,
+    /// Query that produced the rows, kept so the snapshot can be re-run later.
+    pub(crate) sql: Option<String>,
+    /// Connection display name the rows came from.
+    pub(crate) source: Option<String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbResultSnapshot {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) notes: Option<String>,
+    #[serde(default)]
+    pub(crate) sql: Option<String>,
+    #[serde(default)]
+    pub(crate) source: Option<String>,
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+    pub(crate) created_at: u64,
+}
+
+/// Snapshot listing entry; rows are only loaded when a snapshot is reopened.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbResultSnapshotSummary {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) notes: Option<String>,
+    pub(crate) sql: Option<String>,
+    pub(crate) source: Option<String>,
+    pub(crate) column_count: u32,
+    pub(crate) row_count: u32,
+    pub(crate) created_at: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbResultSnapshotRef {
+    pub(crate) snapshot_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbImportOracleWalletRequest {
+    pub(crate) file_path: String,
+    /// Defaults to the zip file name.
+    pub(crate) name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbOracleWallet {
+    pub(crate) name: String,
+    pub(crate) directory: String,
+    /// Service aliases from the wallet's `tnsnames.ora`, in file order.
+    pub(crate) aliases: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbOracleWalletNameRequest {
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbListTnsAliasesRequest {
+    #[serde(default)]
+    pub(crate) tns_admin: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTnsAlias {
+    pub(crate) alias: String,
+    pub(crate) protocol: OracleProtocol,
+    pub(crate) host: String,
+    pub(crate) port: u16,
+    pub(crate) service_name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbTnsAliasList {
+    pub(crate) file_path: String,
+    /// In file order.
+    pub(crate) aliases: Vec<DbTnsAlias>,
+    /// `alias: reason` for entries that cannot be used, such as `SID`-only descriptors.
+    pub(crate) skipped: Vec<String>,
+}
+
+/// Server-side DDL archived before `db_update_object_ddl` replaced it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDdlBackup {
+    pub(crate) id: String,
+    /// Saved profile of the session the backup was taken on, so objects with the same name
+    /// in another database are not mixed up; `None` for ad hoc connections.
+    #[serde(default)]
+    pub(crate) profile_id: Option<String>,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) file_path: String,
+    pub(crate) size_bytes: u64,
+    pub(crate) created_at: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbDdlBackupRetention {
+    /// Newest backups kept per object; older ones are deleted.
+    pub(crate) max_per_object: u32,
+    /// Backups older than this are deleted; `None` keeps them regardless of age.
+    pub(crate) max_age_days: Option<u32>,
+}
+
+impl Default for DbDdlBackupRetention {
+    fn default() -> Self {
+        Self {
+            max_per_object: 20,
+            max_age_days: Some(90),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbListDdlBackupsRequest {
+    pub(crate) schema: Option<String>,
+    pub(crate) object_type: Option<String>,
+    pub(crate) object_name: Option<String>,
+}
+
+/// A DDL statement applied through Clarity, kept in the changelog of its profile.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDdlChange {
+    pub(crate) id: String,
+    /// `None` for sessions opened without a saved profile.
+    pub(crate) profile_id: Option<String>,
+    pub(crate) environment: Option<String>,
+    /// `CREATE`, `ALTER`, `DROP`, `TRUNCATE`, or `REPLACE` for an edit in the object editor.
+    pub(crate) action: String,
+    pub(crate) schema: String,
+    pub(crate) object_type: String,
+    pub(crate) object_name: String,
+    pub(crate) sql: String,
+    /// SHA-256 of the object's DDL before and after the statement; `None` when the object
+    /// did not exist, its DDL could not be read, or (after) the statement failed.
+    pub(crate) before_hash: Option<String>,
+    pub(crate) after_hash: Option<String>,
+    pub(crate) user: Option<String>,
+    pub(crate) executed_at: u64,
+    pub(crate) error: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(tag = "provider", content = "connection", rename_all = "lowercase")]
-pub(crate) enum DbConnectConnection {
-    Oracle(OracleConnectOptions),
-    Postgres(NetworkConnectOptions),
-    Mysql(NetworkConnectOptions),
-    Sqlite(SqliteConnectionOptions),
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbGetDdlChangelogRequest {
+    /// `None` lists every profile.
+    pub(crate) profile_id: Option<String>,
+    /// Epoch milliseconds, inclusive.
+    pub(crate) since: Option<u64>,
+    pub(crate) until: Option<u64>,
 }
 
-impl DbConnectRequest {
-    pub(crate) fn provider(&self) -> DatabaseProvider {
-        self.connection.provider()
-    }
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbExportDdlChangelogRequest {
+    #[serde(flatten)]
+    pub(crate) filter: DbGetDdlChangelogRequest,
+    pub(crate) file_path: String,
+    /// Heading of the report, such as the profile name.
+    pub(crate) title: Option<String>,
 }
 
-impl DbConnectConnection {
-    pub(crate) fn provider(&self) -> DatabaseProvider {
-        match self {
-            DbConnectConnection::Oracle(_) => DatabaseProvider::Oracle,
-            DbConnectConnection::Postgres(_) => DatabaseProvider::Postgres,
-            DbConnectConnection::Mysql(_) => DatabaseProvider::Mysql,
-            DbConnectConnection::Sqlite(_) => DatabaseProvider::Sqlite,
-        }
-    }
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbDdlChangelogExport {
+    pub(crate) file_path: String,
+    pub(crate) change_count: u32,
+    pub(crate) message: String,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbQueryRequest {
-    pub(crate) session_id: u64,
-    pub(crate) sql: String,
-    pub(crate) row_limit: Option<u32>,
+pub(crate) struct DbExportWorkspaceRequest {
+    pub(crate) file_path: String,
+    /// Encrypts the archive; the same passphrase is needed to import it.
+    pub(crate) passphrase: String,
+    /// Adds saved passwords, SSH passwords and the AI API key.
+    #[serde(default)]
+    pub(crate) include_secrets: bool,
+    /// The frontend's settings, including key bindings, handed back as-is on import.
+    #[serde(default)]
+    pub(crate) settings: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbFilteredQueryRequest {
-    pub(crate) session_id: u64,
-    pub(crate) sql: String,
-    pub(crate) row_limit: Option<u32>,
-    pub(crate) global_search: Option<String>,
-    pub(crate) column_filters: Option<Vec<String>>,
+pub(crate) struct DbImportWorkspaceRequest {
+    pub(crate) file_path: String,
+    pub(crate) passphrase: String,
+}
+
+/// What a workspace archive held; on import, what was merged into this machine.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbWorkspaceArchiveSummary {
+    pub(crate) file_path: String,
+    pub(crate) profile_count: u32,
+    /// Profiles whose passwords travelled with the archive.
+    pub(crate) secret_count: u32,
+    pub(crate) snippet_count: u32,
+    pub(crate) pinned_object_count: u32,
+    pub(crate) tagged_object_count: u32,
+    /// Frontend settings from the archive, for the frontend to apply. Set on import only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) settings: Option<serde_json::Value>,
+    pub(crate) message: String,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSchemaSearchRequest {
-    pub(crate) session_id: u64,
+pub(crate) struct DbWorkspaceSearchRequest {
     pub(crate) search_term: String,
     pub(crate) limit: Option<u32>,
-    pub(crate) include_object_names: Option<bool>,
-    pub(crate) include_source: Option<bool>,
-    pub(crate) include_ddl: Option<bool>,
+    pub(crate) include_buffers: Option<bool>,
+    pub(crate) include_history: Option<bool>,
+    pub(crate) include_snippets: Option<bool>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbWorkspaceSearchResult {
+    pub(crate) source: String,
+    pub(crate) item_id: String,
+    pub(crate) title: String,
+    pub(crate) line: Option<u32>,
+    pub(crate) snippet: String,
+    pub(crate) timestamp: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbSaveQuerySheetsResult {
+    pub(crate) directory: String,
+    pub(crate) file_count: usize,
 }
 
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbObjectRef {
-    pub(crate) session_id: u64,
+pub(crate) struct DbAiSchemaContextObject {
     pub(crate) schema: String,
-    pub(crate) object_type: String,
     pub(crate) object_name: String,
+    pub(crate) columns: Vec<String>,
+    #[serde(default)]
+    pub(crate) is_referenced_in_query: bool,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbObjectDdlUpdateRequest {
+pub(crate) struct DbAiSuggestQueryRequest {
+    pub(crate) current_sql: String,
+    pub(crate) connected_schema: String,
+    pub(crate) endpoint: String,
+    pub(crate) model: String,
+    #[serde(default)]
+    pub(crate) schema_context: Vec<DbAiSchemaContextObject>,
+    #[serde(default)]
+    pub(crate) cursor_clause: Option<String>,
+    /// When set and `schema_context` is empty, the context cached for this session is used.
+    #[serde(default)]
+    pub(crate) session_id: Option<u64>,
+    /// SQL dialect for the prompt; resolved from the session when omitted.
+    #[serde(default)]
+    pub(crate) provider: Option<DatabaseProvider>,
+    /// Checks tables and qualified columns in the suggestion against the session's cached
+    /// catalog.
+    #[serde(default)]
+    pub(crate) identifier_check: DbAiIdentifierCheck,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum DbAiIdentifierCheck {
+    #[default]
+    Off,
+    /// Keep the suggestion and list unknown identifiers in `warnings`.
+    Annotate,
+    /// Discard the whole suggestion when it references any unknown identifier; the
+    /// identifiers are still listed in `warnings`. Also accepted as `drop`.
+    #[serde(alias = "drop")]
+    Discard,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbAiReviewDdlRequest {
     pub(crate) session_id: u64,
     pub(crate) schema: String,
     pub(crate) object_type: String,
     pub(crate) object_name: String,
+    /// The DDL about to be passed to `db_update_object_ddl`.
     pub(crate) ddl: String,
+    pub(crate) endpoint: String,
+    pub(crate) model: String,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct DbExportSchemaRequest {
-    pub(crate) session_id: u64,
-    pub(crate) destination_directory: String,
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DbAiRiskSeverity {
+    #[default]
+    Low,
+    Medium,
+    High,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSaveQuerySheetRequest {
-    pub(crate) suggested_file_name: String,
-    pub(crate) sql: String,
+pub(crate) struct DbAiDdlRiskNote {
+    /// `breakingChange`, `grants`, `locking`, `dependencies`, `dataLoss` or `other`.
+    pub(crate) category: String,
+    pub(crate) severity: DbAiRiskSeverity,
+    pub(crate) message: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSaveQuerySheetInput {
-    pub(crate) title: String,
-    pub(crate) sql: String,
+pub(crate) struct DbAiDdlReviewResult {
+    pub(crate) risk_level: DbAiRiskSeverity,
+    pub(crate) summary: String,
+    pub(crate) notes: Vec<DbAiDdlRiskNote>,
+    /// Objects that depend on the reviewed one, as sent to the model.
+    pub(crate) dependents: Vec<DbObjectDependent>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSaveQuerySheetsRequest {
-    pub(crate) sheets: Vec<DbSaveQuerySheetInput>,
+pub(crate) struct DbCreateScratchTableRequest {
+    pub(crate) session_id: u64,
+    /// Suffix for the table name; the `CLARITY_SCRATCH_` prefix is added by the backend.
+    pub(crate) name: String,
+    /// `SELECT` whose result fills the table.
+    pub(crate) query: String,
+    /// Token from an earlier refusal, confirming the `CREATE TABLE` on a production session.
+    #[serde(default)]
+    pub(crate) production_confirmation: Option<String>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct ConnectionProfileRef {
-    pub(crate) profile_id: String,
+pub(crate) struct DbScratchTableRef {
+    pub(crate) session_id: u64,
+    pub(crate) table_name: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct SaveConnectionProfileRequest {
-    pub(crate) id: Option<String>,
-    pub(crate) name: String,
-    #[serde(flatten)]
-    pub(crate) connection: DbConnectionProfile,
-    pub(crate) save_password: bool,
-    pub(crate) password: Option<String>,
+pub(crate) enum DbScratchTableKind {
+    /// Oracle global temporary table; rows are private to the session, the definition is
+    /// dropped on disconnect.
+    GlobalTemporary,
+    /// MySQL `TEMPORARY` table, gone when the connection closes.
+    Temporary,
+    /// Regular table with the scratch prefix, for providers without session temp tables.
+    Prefixed,
 }
 
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSessionSummary {
-    pub(crate) session_id: u64,
-    pub(crate) display_name: String,
-    pub(crate) schema: String,
-    pub(crate) provider: DatabaseProvider,
+pub(crate) struct DbScratchTable {
+    pub(crate) table_name: String,
+    pub(crate) kind: DbScratchTableKind,
+    pub(crate) created_at: u64,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct ConnectionProfile {
-    pub(crate) id: String,
-    pub(crate) name: String,
-    #[serde(flatten)]
-    pub(crate) connection: DbConnectionProfile,
-    pub(crate) has_password: bool,
+pub(crate) struct DbDetectSensitiveColumnsRequest {
+    pub(crate) session_id: u64,
+    /// Tables/views to scan; empty scans every table and view in the session schema.
+    #[serde(default)]
+    pub(crate) object_names: Vec<String>,
+    /// Rows sampled per object; `0` checks column names only.
+    pub(crate) sample_rows: Option<u32>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct StoredConnectionProfile {
-    pub(crate) id: String,
-    pub(crate) name: String,
-    #[serde(flatten)]
-    pub(crate) connection: DbConnectionProfile,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "provider", content = "connection", rename_all = "lowercase")]
-pub(crate) enum DbConnectionProfile {
-    Oracle(OracleConnectionOptions),
-    Postgres(NetworkConnectionOptions),
-    Mysql(NetworkConnectionOptions),
-    Sqlite(SqliteConnectionOptions),
+pub(crate) enum DbSensitiveDataCategory {
+    Email,
+    Phone,
+    NationalId,
+    CardNumber,
+    BankAccount,
+    DateOfBirth,
+    PersonName,
+    PostalAddress,
 }
 
-#[derive(Clone, Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct DbObjectEntry {
-    pub(crate) schema: String,
-    pub(crate) object_type: String,
-    pub(crate) object_name: String,
-    pub(crate) status: Option<String>,
-    pub(crate) invalid_reason: Option<String>,
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DbSensitiveDataConfidence {
+    Low,
+    Medium,
+    High,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbObjectColumnEntry {
+pub(crate) struct DbSensitiveColumnFinding {
     pub(crate) schema: String,
     pub(crate) object_name: String,
     pub(crate) column_name: String,
     pub(crate) data_type: String,
-    pub(crate) nullable: String,
+    pub(crate) category: DbSensitiveDataCategory,
+    pub(crate) confidence: DbSensitiveDataConfidence,
+    pub(crate) matched_column_name: bool,
+    /// Non-NULL sampled values, and how many of them matched the category's pattern.
+    pub(crate) sampled_values: u32,
+    pub(crate) matching_values: u32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DbMaskingStrategy {
+    /// Replaces the value with `****`.
+    Redact,
+    /// Keeps the last four characters and masks the rest with `*`.
+    Partial,
+    Null,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbQueryResult {
-    pub(crate) columns: Vec<String>,
-    pub(crate) rows: Vec<Vec<String>>,
-    pub(crate) rows_affected: Option<u64>,
-    pub(crate) message: String,
+pub(crate) struct DbMaskingRule {
+    /// Result column name; `*` matches any run of characters and case is ignored.
+    pub(crate) column_pattern: String,
+    pub(crate) strategy: DbMaskingStrategy,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbTransactionState {
-    pub(crate) active: bool,
+pub(crate) struct DbSaveMaskingRulesRequest {
+    pub(crate) rules: Vec<DbMaskingRule>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSchemaSearchResult {
-    pub(crate) schema: String,
-    pub(crate) object_type: String,
-    pub(crate) object_name: String,
-    pub(crate) match_scope: String,
-    pub(crate) line: Option<u32>,
-    pub(crate) snippet: String,
+pub(crate) struct DbMaskedQueryRequest {
+    pub(crate) session_id: u64,
+    pub(crate) sql: String,
+    pub(crate) row_limit: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSchemaExportResult {
-    pub(crate) destination_directory: String,
-    pub(crate) object_count: usize,
-    pub(crate) file_count: usize,
-    pub(crate) skipped_count: usize,
-    pub(crate) message: String,
+pub(crate) struct DbMaskedColumn {
+    pub(crate) column_name: String,
+    pub(crate) strategy: DbMaskingStrategy,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbSaveQuerySheetsResult {
-    pub(crate) directory: String,
-    pub(crate) file_count: usize,
+pub(crate) struct DbMaskedQueryResult {
+    /// The SQL that was run, with the masking expressions.
+    pub(crate) masked_sql: String,
+    pub(crate) masked_columns: Vec<DbMaskedColumn>,
+    pub(crate) result: DbQueryResult,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct DbAiSchemaContextObject {
-    pub(crate) schema: String,
-    pub(crate) object_name: String,
-    pub(crate) columns: Vec<String>,
-    #[serde(default)]
-    pub(crate) is_referenced_in_query: bool,
+pub(crate) struct DbSensitiveColumnsReport {
+    pub(crate) findings: Vec<DbSensitiveColumnFinding>,
+    pub(crate) scanned_objects: u32,
+    pub(crate) scanned_columns: u32,
+    /// Objects whose sample query failed; their findings rely on column names only.
+    pub(crate) warnings: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct DbAiSuggestQueryRequest {
-    pub(crate) current_sql: String,
-    pub(crate) connected_schema: String,
-    pub(crate) endpoint: String,
-    pub(crate) model: String,
-    pub(crate) schema_context: Vec<DbAiSchemaContextObject>,
-    #[serde(default)]
-    pub(crate) cursor_clause: Option<String>,
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct DbAiPromptSettings {
+    /// Custom system prompt; `None` uses the built-in template.
+    pub(crate) system_prompt_template: Option<String>,
+    pub(crate) extra_rules: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -344,6 +2973,8 @@ pub(crate) struct DbAiSuggestQueryResult {
     pub(crate) reasoning_short: String,
     #[serde(default)]
     pub(crate) is_potentially_mutating: bool,
+    #[serde(default)]
+    pub(crate) warnings: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -362,6 +2993,51 @@ pub(crate) struct DbSchemaExportProgress {
     pub(crate) current_object: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbStreamQueryRequest {
+    pub(crate) session_id: u64,
+    /// Chosen by the caller, which listens for it before invoking the command.
+    pub(crate) stream_id: String,
+    pub(crate) sql: String,
+    /// Rows per event.
+    #[serde(default)]
+    pub(crate) batch_size: Option<u32>,
+    /// Seconds the query may take before it is stopped.
+    #[serde(default)]
+    pub(crate) timeout_secs: Option<u32>,
+}
+
+/// One event of a streamed query: batches of rows in order, then one summary.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum DbQueryRowsEvent {
+    #[serde(rename_all = "camelCase")]
+    Rows {
+        stream_id: String,
+        batch_index: u64,
+        /// Sent with the first batch only.
+        columns: Option<Vec<String>>,
+        rows: Vec<Vec<String>>,
+        /// Parallel to `rows`; empty when no cell of the batch is NULL.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        null_flags: Vec<Vec<bool>>,
+    },
+    Summary(DbQueryStreamSummary),
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DbQueryStreamSummary {
+    pub(crate) stream_id: String,
+    pub(crate) columns: Vec<String>,
+    pub(crate) row_count: u64,
+    pub(crate) batch_count: u64,
+    pub(crate) message: String,
+    /// Set when the query failed part-way; the rows already sent are kept.
+    pub(crate) error: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub(crate) enum DbConnectError {
@@ -375,6 +3051,13 @@ impl DbConnectError {
             message: message.into(),
         }
     }
+
+    pub(crate) fn into_message(self) -> String {
+        match self {
+            DbConnectError::OracleClientMissing { message }
+            | DbConnectError::General { message } => message,
+        }
+    }
 }
 
 fn default_ai_confidence() -> f32 {
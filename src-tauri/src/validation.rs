@@ -1,32 +1,68 @@
+use crate::oracle_wallets::AUTO_LOGIN_WALLET_FILE;
 use crate::types::{
-    DbAiSuggestQueryRequest, DbConnectConnection, DbConnectRequest, DbConnectionProfile,
-    SaveConnectionProfileRequest,
+    DatabaseProvider, DbAiReviewDdlRequest, DbAiSuggestQueryRequest, DbConnectConnection,
+    DbConnectRequest, DbConnectionProfile, DbCreateScratchTableRequest, DbReaderEndpoint,
+    OracleAddress, OracleExternalPasswordStore, OracleLdapNaming, OracleProtocol, OracleTlsOptions,
+    OracleTnsAliasRef, OracleWalletRef, SaveConnectionProfileRequest,
 };
+use std::path::Path;
+
+// Leaves room for the scratch prefix within MySQL's 64-character limit.
+const MAX_SCRATCH_TABLE_NAME_LENGTH: usize = 30;
 
 pub(crate) fn validate_connect_request(request: &DbConnectRequest) -> Result<(), String> {
+    let mut named = false;
     match &request.connection {
         DbConnectConnection::Oracle(connection) => {
-            if connection.host.trim().is_empty() {
+            named = validate_oracle_naming(
+                connection.wallet.as_ref(),
+                connection.tns_alias.as_ref(),
+                connection.ldap.as_deref(),
+                connection.connect_descriptor.as_deref(),
+                connection.external_password_store.as_deref(),
+            )?;
+            validate_oracle_addresses(
+                named,
+                connection.connect_descriptor.as_deref(),
+                &connection.failover_hosts,
+                connection.failover.is_some(),
+                request.ssh_tunnel.is_some(),
+            )?;
+            if !named && connection.host.trim().is_empty() {
                 return Err("Host is required".to_string());
             }
 
-            if connection.username.trim().is_empty() {
-                return Err("Username is required".to_string());
-            }
+            if let Some(store) = &connection.external_password_store {
+                validate_oracle_password_store(
+                    store,
+                    connection.proxy_user.as_deref(),
+                    request.ssh_tunnel.is_some(),
+                )?;
+            } else {
+                if connection.username.trim().is_empty() {
+                    return Err("Username is required".to_string());
+                }
 
-            if connection.password.is_empty() {
-                return Err("Password is required".to_string());
+                if connection.password.is_empty() {
+                    return Err("Password is required".to_string());
+                }
             }
 
-            if connection.service_name.trim().is_empty() {
+            if !named && connection.service_name.trim().is_empty() {
                 return Err("Service name is required".to_string());
             }
 
             if connection.schema.trim().is_empty() {
                 return Err("Schema is required".to_string());
             }
+
+            if !named {
+                validate_oracle_tls(connection.protocol, connection.tls.as_deref())?;
+            }
         }
-        DbConnectConnection::Postgres(connection) | DbConnectConnection::Mysql(connection) => {
+        DbConnectConnection::Postgres(connection)
+        | DbConnectConnection::Mysql(connection)
+        | DbConnectConnection::Clickhouse(connection) => {
             if connection.host.trim().is_empty() {
                 return Err("Host is required".to_string());
             }
@@ -48,9 +84,52 @@ pub(crate) fn validate_connect_request(request: &DbConnectRequest) -> Result<(),
                 return Err("File path is required".to_string());
             }
         }
+        DbConnectConnection::Duckdb(connection) => {
+            if connection.file_path.trim().is_empty() {
+                return Err("File path is required".to_string());
+            }
+        }
+        DbConnectConnection::Snowflake(connection) => {
+            if connection.account.trim().is_empty() {
+                return Err("Account is required".to_string());
+            }
+
+            if connection.token.trim().is_empty() {
+                return Err("Access token is required".to_string());
+            }
+
+            if connection.database.trim().is_empty() {
+                return Err("Database is required".to_string());
+            }
+        }
+        DbConnectConnection::Libsql(connection) => {
+            if connection.url.trim().is_empty() {
+                return Err("Database URL is required".to_string());
+            }
+        }
+        DbConnectConnection::External(connection) => {
+            if connection.provider_id.trim().is_empty() {
+                return Err("External provider is required".to_string());
+            }
+        }
     }
 
-    Ok(())
+    if let Some(tunnel) = &request.ssh_tunnel {
+        validate_ssh_tunnel(tunnel.host.as_str(), tunnel.username.as_str())?;
+    }
+
+    validate_reader_endpoint(
+        request.reader_endpoint.as_ref(),
+        !named
+            && matches!(
+                request.connection,
+                DbConnectConnection::Oracle(_)
+                    | DbConnectConnection::Postgres(_)
+                    | DbConnectConnection::Mysql(_)
+                    | DbConnectConnection::Clickhouse(_)
+            ),
+        request.provider() == DatabaseProvider::Oracle,
+    )
 }
 
 pub(crate) fn validate_profile_request(
@@ -60,25 +139,52 @@ pub(crate) fn validate_profile_request(
         return Err("Profile name is required".to_string());
     }
 
+    let mut named = false;
     match &request.connection {
         DbConnectionProfile::Oracle(connection) => {
-            if connection.host.trim().is_empty() {
+            named = validate_oracle_naming(
+                connection.wallet.as_ref(),
+                connection.tns_alias.as_ref(),
+                connection.ldap.as_deref(),
+                connection.connect_descriptor.as_deref(),
+                connection.external_password_store.as_deref(),
+            )?;
+            validate_oracle_addresses(
+                named,
+                connection.connect_descriptor.as_deref(),
+                &connection.failover_hosts,
+                connection.failover.is_some(),
+                request.ssh_tunnel.is_some(),
+            )?;
+            if !named && connection.host.trim().is_empty() {
                 return Err("Host is required".to_string());
             }
 
-            if connection.username.trim().is_empty() {
+            if let Some(store) = &connection.external_password_store {
+                validate_oracle_password_store(
+                    store,
+                    connection.proxy_user.as_deref(),
+                    request.ssh_tunnel.is_some(),
+                )?;
+            } else if connection.username.trim().is_empty() {
                 return Err("Username is required".to_string());
             }
 
-            if connection.service_name.trim().is_empty() {
+            if !named && connection.service_name.trim().is_empty() {
                 return Err("Service name is required".to_string());
             }
 
             if connection.schema.trim().is_empty() {
                 return Err("Schema is required".to_string());
             }
+
+            if !named {
+                validate_oracle_tls(connection.protocol, connection.tls.as_deref())?;
+            }
         }
-        DbConnectionProfile::Postgres(connection) | DbConnectionProfile::Mysql(connection) => {
+        DbConnectionProfile::Postgres(connection)
+        | DbConnectionProfile::Mysql(connection)
+        | DbConnectionProfile::Clickhouse(connection) => {
             if connection.host.trim().is_empty() {
                 return Err("Host is required".to_string());
             }
@@ -96,6 +202,250 @@ pub(crate) fn validate_profile_request(
                 return Err("File path is required".to_string());
             }
         }
+        DbConnectionProfile::Duckdb(connection) => {
+            if connection.file_path.trim().is_empty() {
+                return Err("File path is required".to_string());
+            }
+        }
+        DbConnectionProfile::Snowflake(connection) => {
+            if connection.account.trim().is_empty() {
+                return Err("Account is required".to_string());
+            }
+
+            if connection.database.trim().is_empty() {
+                return Err("Database is required".to_string());
+            }
+        }
+        DbConnectionProfile::Libsql(connection) => {
+            if connection.url.trim().is_empty() {
+                return Err("Database URL is required".to_string());
+            }
+        }
+        DbConnectionProfile::External(connection) => {
+            if connection.provider_id.trim().is_empty() {
+                return Err("External provider is required".to_string());
+            }
+        }
+    }
+
+    if let Some(tunnel) = &request.ssh_tunnel {
+        validate_ssh_tunnel(tunnel.host.as_str(), tunnel.username.as_str())?;
+    }
+
+    validate_reader_endpoint(
+        request.reader_endpoint.as_ref(),
+        !named
+            && matches!(
+                request.connection,
+                DbConnectionProfile::Oracle(_)
+                    | DbConnectionProfile::Postgres(_)
+                    | DbConnectionProfile::Mysql(_)
+                    | DbConnectionProfile::Clickhouse(_)
+            ),
+        matches!(request.connection, DbConnectionProfile::Oracle(_)),
+    )
+}
+
+/// A reader endpoint takes the place of the host, so it needs a connection made by host
+/// and port. A blank host means there is none.
+fn validate_reader_endpoint(
+    endpoint: Option<&DbReaderEndpoint>,
+    connects_by_host: bool,
+    oracle: bool,
+) -> Result<(), String> {
+    let Some(endpoint) = endpoint.filter(|endpoint| !endpoint.host.trim().is_empty()) else {
+        return Ok(());
+    };
+    if !connects_by_host {
+        return Err(
+            "A reader endpoint needs a connection by host and port, without a wallet, a TNS alias, LDAP naming or a connect descriptor"
+                .to_string(),
+        );
+    }
+    if !oracle
+        && endpoint
+            .service_name
+            .as_deref()
+            .is_some_and(|service| !service.trim().is_empty())
+    {
+        return Err("Only Oracle reader endpoints take a service name".to_string());
+    }
+    Ok(())
+}
+
+fn validate_ssh_tunnel(host: &str, username: &str) -> Result<(), String> {
+    if host.trim().is_empty() {
+        return Err("SSH host is required".to_string());
+    }
+
+    if username.trim().is_empty() {
+        return Err("SSH username is required".to_string());
+    }
+
+    // Both end up on the ssh command line; a leading `-` would be read as an option such
+    // as `-oProxyCommand=...`.
+    for (label, value) in [("host", host.trim()), ("username", username.trim())] {
+        if value.starts_with('-') {
+            return Err(format!("SSH {label} must not start with '-'"));
+        }
+        if value
+            .chars()
+            .any(|character| character.is_whitespace() || character.is_control())
+        {
+            return Err(format!(
+                "SSH {label} must not contain spaces or control characters"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_oracle_tls(
+    protocol: OracleProtocol,
+    tls: Option<&OracleTlsOptions>,
+) -> Result<(), String> {
+    if protocol == OracleProtocol::Tcp && tls.is_some() {
+        return Err("TLS settings require the TCPS protocol".to_string());
+    }
+
+    Ok(())
+}
+
+/// Wallet, TNS alias, LDAP, raw descriptor and password store connections take their host
+/// and service from a connect descriptor, so at most one of them may be set. Returns
+/// whether one is.
+fn validate_oracle_naming(
+    wallet: Option<&OracleWalletRef>,
+    tns_alias: Option<&OracleTnsAliasRef>,
+    ldap: Option<&OracleLdapNaming>,
+    connect_descriptor: Option<&str>,
+    password_store: Option<&OracleExternalPasswordStore>,
+) -> Result<bool, String> {
+    let connect_descriptor = connect_descriptor
+        .map(str::trim)
+        .filter(|descriptor| !descriptor.is_empty());
+    let count = [
+        wallet.is_some(),
+        tns_alias.is_some(),
+        ldap.is_some(),
+        connect_descriptor.is_some(),
+        password_store.is_some(),
+    ]
+    .into_iter()
+    .filter(|set| *set)
+    .count();
+    if count > 1 {
+        return Err(
+            "Choose only one of a wallet, a TNS alias, LDAP naming, a connect descriptor or a password store"
+                .to_string(),
+        );
+    }
+
+    if let Some(wallet) = wallet {
+        validate_oracle_wallet(wallet)?;
+    }
+    if let Some(tns_alias) = tns_alias {
+        validate_oracle_tns_alias(tns_alias)?;
+    }
+    if let Some(ldap) = ldap {
+        if ldap.name.trim().is_empty() {
+            return Err("Net service name is required".to_string());
+        }
+    }
+    if let Some(descriptor) = connect_descriptor {
+        if !descriptor.starts_with('(') || !descriptor.ends_with(')') {
+            return Err("The connect descriptor must be a (DESCRIPTION=...) entry".to_string());
+        }
+    }
+    Ok(count == 1)
+}
+
+/// Failover hosts and settings extend the host field, so they need one. An SSH tunnel
+/// forwards to the host field's listener only, so it rules out failover hosts and raw
+/// descriptors.
+fn validate_oracle_addresses(
+    named: bool,
+    connect_descriptor: Option<&str>,
+    failover_hosts: &[OracleAddress],
+    has_failover_settings: bool,
+    ssh_tunnel: bool,
+) -> Result<(), String> {
+    let has_descriptor = connect_descriptor.is_some_and(|descriptor| !descriptor.trim().is_empty());
+    if has_descriptor && ssh_tunnel {
+        return Err("A connect descriptor cannot be used through an SSH tunnel".to_string());
+    }
+    if has_failover_settings && named {
+        return Err(
+            "Failover settings cannot be combined with a wallet, a TNS alias, LDAP naming or a connect descriptor; set them in the descriptor or alias instead"
+                .to_string(),
+        );
+    }
+    if failover_hosts.is_empty() {
+        return Ok(());
+    }
+    if named {
+        return Err(
+            "Failover hosts cannot be combined with a wallet, a TNS alias, LDAP naming or a connect descriptor"
+                .to_string(),
+        );
+    }
+    if ssh_tunnel {
+        return Err("Failover hosts cannot be used through an SSH tunnel".to_string());
+    }
+    if failover_hosts
+        .iter()
+        .any(|address| address.host.trim().is_empty())
+    {
+        return Err("Each failover host needs a host name".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_oracle_tns_alias(tns_alias: &OracleTnsAliasRef) -> Result<(), String> {
+    if tns_alias.alias.trim().is_empty() {
+        return Err("TNS alias is required".to_string());
+    }
+
+    Ok(())
+}
+
+/// The Oracle client resolves the alias itself and signs in as whoever the wallet names, so
+/// neither an SSH tunnel nor a proxy user can be applied.
+fn validate_oracle_password_store(
+    store: &OracleExternalPasswordStore,
+    proxy_user: Option<&str>,
+    ssh_tunnel: bool,
+) -> Result<(), String> {
+    if store.alias.trim().is_empty() {
+        return Err("Password store alias is required".to_string());
+    }
+    let wallet_dir = store.wallet_dir.trim();
+    if wallet_dir.is_empty() {
+        return Err("Password store wallet directory is required".to_string());
+    }
+    if !Path::new(wallet_dir).join(AUTO_LOGIN_WALLET_FILE).is_file() {
+        return Err(format!(
+            "{wallet_dir} has no auto-login wallet ({AUTO_LOGIN_WALLET_FILE})"
+        ));
+    }
+    if proxy_user.is_some_and(|proxy_user| !proxy_user.trim().is_empty()) {
+        return Err("A proxy user cannot be combined with a password store".to_string());
+    }
+    if ssh_tunnel {
+        return Err("A password store cannot be used through an SSH tunnel".to_string());
+    }
+    Ok(())
+}
+
+fn validate_oracle_wallet(wallet: &OracleWalletRef) -> Result<(), String> {
+    if wallet.wallet_name.trim().is_empty() {
+        return Err("Wallet is required".to_string());
+    }
+
+    if wallet.service_alias.trim().is_empty() {
+        return Err("Service alias is required".to_string());
     }
 
     Ok(())
@@ -125,13 +475,70 @@ pub(crate) fn validate_ai_suggest_request(request: &DbAiSuggestQueryRequest) ->
     Ok(())
 }
 
+pub(crate) fn validate_ai_review_ddl_request(request: &DbAiReviewDdlRequest) -> Result<(), String> {
+    if request.ddl.trim().is_empty() {
+        return Err("DDL is required.".to_string());
+    }
+
+    if request.object_name.trim().is_empty() {
+        return Err("Object name is required.".to_string());
+    }
+
+    if request.model.trim().is_empty() {
+        return Err("AI model is required.".to_string());
+    }
+
+    if request.endpoint.trim().is_empty() {
+        return Err("AI endpoint is required.".to_string());
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_create_scratch_table_request(
+    request: &DbCreateScratchTableRequest,
+) -> Result<(), String> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err("Scratch table name is required.".to_string());
+    }
+
+    if name.len() > MAX_SCRATCH_TABLE_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+    {
+        return Err(format!(
+            "Scratch table names may only use letters, digits and underscores (up to {MAX_SCRATCH_TABLE_NAME_LENGTH} characters)."
+        ));
+    }
+
+    let first_keyword = request
+        .query
+        .trim_start_matches(|ch: char| ch.is_whitespace() || ch == '(')
+        .split(|ch: char| !ch.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    if !matches!(first_keyword.as_str(), "SELECT" | "WITH") {
+        return Err("Scratch tables are filled from a SELECT query.".to_string());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{validate_ai_suggest_request, validate_connect_request, validate_profile_request};
+    use super::{
+        validate_ai_suggest_request, validate_connect_request,
+        validate_create_scratch_table_request, validate_profile_request, validate_ssh_tunnel,
+    };
     use crate::types::{
-        DbAiSchemaContextObject, DbAiSuggestQueryRequest, DbConnectConnection, DbConnectRequest,
-        DbConnectionProfile, NetworkConnectOptions, NetworkConnectionOptions, OracleConnectOptions,
-        OracleConnectionOptions, SaveConnectionProfileRequest, SqliteConnectionOptions,
+        DbAiIdentifierCheck, DbAiSchemaContextObject, DbAiSuggestQueryRequest, DbConnectConnection,
+        DbConnectRequest, DbConnectionProfile, DbCreateScratchTableRequest, NetworkConnectOptions,
+        NetworkConnectionOptions, OracleAddress, OracleConnectOptions, OracleConnectionOptions,
+        OracleExternalPasswordStore, OracleProtocol, OracleTlsOptions,
+        SaveConnectionProfileRequest, SqliteConnectionOptions, SshTunnelConnectOptions,
     };
 
     fn valid_postgres_connect_request() -> DbConnectRequest {
@@ -144,6 +551,19 @@ mod tests {
                 password: "secret".to_string(),
                 schema: Some("public".to_string()),
             }),
+            ssh_tunnel: None,
+            timestamp_tz_mode: Default::default(),
+            prefetch_ai_schema_context: false,
+            read_only: false,
+            environment: None,
+            drop_allowlist: Vec::new(),
+            startup_sql: None,
+            pool_size: None,
+            connect_timeout_secs: None,
+            connect_attempts: None,
+            retry_delay_millis: None,
+            profile_id: None,
+            reader_endpoint: None,
         }
     }
 
@@ -158,8 +578,16 @@ mod tests {
                 username: "app_user".to_string(),
                 schema: Some("public".to_string()),
             }),
+            ssh_tunnel: None,
             save_password: false,
             password: None,
+            ssh_password: None,
+            read_only: false,
+            environment: None,
+            startup_sql: None,
+            warm_standby: false,
+            drop_allowlist: Vec::new(),
+            reader_endpoint: None,
         }
     }
 
@@ -174,7 +602,31 @@ mod tests {
                 schema: "APP".to_string(),
                 oracle_auth_mode: Default::default(),
                 oracle_client_lib_dir: None,
+                edition: None,
+                proxy_user: None,
+                protocol: Default::default(),
+                tls: None,
+                wallet: None,
+                tns_alias: None,
+                ldap: None,
+                failover_hosts: Vec::new(),
+                failover: None,
+                connect_descriptor: None,
+                external_password_store: None,
             }),
+            ssh_tunnel: None,
+            timestamp_tz_mode: Default::default(),
+            prefetch_ai_schema_context: false,
+            read_only: false,
+            environment: None,
+            drop_allowlist: Vec::new(),
+            startup_sql: None,
+            pool_size: None,
+            connect_timeout_secs: None,
+            connect_attempts: None,
+            retry_delay_millis: None,
+            profile_id: None,
+            reader_endpoint: None,
         }
     }
 
@@ -189,9 +641,29 @@ mod tests {
                 username: "system".to_string(),
                 schema: "APP".to_string(),
                 oracle_auth_mode: Default::default(),
+                edition: None,
+                proxy_user: None,
+                protocol: Default::default(),
+                tls: None,
+                wallet: None,
+                tns_alias: None,
+                ldap: None,
+                failover_hosts: Vec::new(),
+                failover: None,
+                connect_descriptor: None,
+                external_password_store: None,
+                oracle_client_lib_dir: None,
             }),
+            ssh_tunnel: None,
             save_password: false,
             password: None,
+            ssh_password: None,
+            read_only: false,
+            environment: None,
+            startup_sql: None,
+            warm_standby: false,
+            drop_allowlist: Vec::new(),
+            reader_endpoint: None,
         }
     }
 
@@ -200,6 +672,19 @@ mod tests {
             connection: DbConnectConnection::Sqlite(SqliteConnectionOptions {
                 file_path: "/tmp/clarity.db".to_string(),
             }),
+            ssh_tunnel: None,
+            timestamp_tz_mode: Default::default(),
+            prefetch_ai_schema_context: false,
+            read_only: false,
+            environment: None,
+            drop_allowlist: Vec::new(),
+            startup_sql: None,
+            pool_size: None,
+            connect_timeout_secs: None,
+            connect_attempts: None,
+            retry_delay_millis: None,
+            profile_id: None,
+            reader_endpoint: None,
         }
     }
 
@@ -210,8 +695,16 @@ mod tests {
             connection: DbConnectionProfile::Sqlite(SqliteConnectionOptions {
                 file_path: "/tmp/clarity.db".to_string(),
             }),
+            ssh_tunnel: None,
             save_password: false,
             password: None,
+            ssh_password: None,
+            read_only: false,
+            environment: None,
+            startup_sql: None,
+            warm_standby: false,
+            drop_allowlist: Vec::new(),
+            reader_endpoint: None,
         }
     }
 
@@ -228,9 +721,21 @@ mod tests {
                 is_referenced_in_query: true,
             }],
             cursor_clause: None,
+            session_id: None,
+            provider: None,
+            identifier_check: DbAiIdentifierCheck::Off,
         }
     }
 
+    #[test]
+    fn ssh_host_and_username_cannot_pass_as_options() {
+        assert_eq!(validate_ssh_tunnel("bastion.example.com", "ops"), Ok(()));
+        assert!(validate_ssh_tunnel("-oProxyCommand=touch /tmp/x", "ops").is_err());
+        assert!(validate_ssh_tunnel("bastion", " -oProxyCommand=id").is_err());
+        assert!(validate_ssh_tunnel("bastion example", "ops").is_err());
+        assert!(validate_ssh_tunnel("bastion", "ops\u{7}").is_err());
+    }
+
     #[test]
     fn validate_connect_request_accepts_valid_postgres_input() {
         let request = valid_postgres_connect_request();
@@ -263,6 +768,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_connect_request_requires_ssh_tunnel_username() {
+        let mut request = valid_oracle_connect_request();
+        request.ssh_tunnel = Some(SshTunnelConnectOptions {
+            host: "bastion.example.com".to_string(),
+            port: None,
+            username: " ".to_string(),
+            identity_file: None,
+            password: None,
+            cli_path: None,
+        });
+
+        assert_eq!(
+            validate_connect_request(&request),
+            Err("SSH username is required".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_connect_request_rejects_tls_settings_over_tcp() {
+        let mut request = valid_oracle_connect_request();
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.tls = Some(Box::new(OracleTlsOptions::default()));
+        }
+        assert_eq!(
+            validate_connect_request(&request),
+            Err("TLS settings require the TCPS protocol".to_string())
+        );
+
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.protocol = OracleProtocol::Tcps;
+        }
+        assert_eq!(validate_connect_request(&request), Ok(()));
+    }
+
+    #[test]
+    fn validate_connect_request_checks_failover_hosts_and_descriptors() {
+        let mut request = valid_oracle_connect_request();
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.failover_hosts = vec![OracleAddress {
+                host: "rac2.example.com".to_string(),
+                port: None,
+            }];
+        }
+        assert_eq!(validate_connect_request(&request), Ok(()));
+
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.connect_descriptor = Some("(DESCRIPTION=(ADDRESS_LIST=...))".to_string());
+        }
+        assert!(validate_connect_request(&request)
+            .unwrap_err()
+            .starts_with("Failover hosts cannot be combined"));
+
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.failover_hosts.clear();
+            connection.host = String::new();
+            connection.service_name = String::new();
+        }
+        assert_eq!(validate_connect_request(&request), Ok(()));
+
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.connect_descriptor = Some("rac1:1521/SALES".to_string());
+        }
+        assert_eq!(
+            validate_connect_request(&request),
+            Err("The connect descriptor must be a (DESCRIPTION=...) entry".to_string())
+        );
+    }
+
+    #[test]
+    fn validate_connect_request_allows_password_store_without_credentials() {
+        let wallet_dir = std::env::temp_dir().join(format!(
+            "clarity_validation_tests_seps_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&wallet_dir).expect("failed to create temp wallet directory");
+        let mut request = valid_oracle_connect_request();
+        if let DbConnectConnection::Oracle(connection) = &mut request.connection {
+            connection.host = String::new();
+            connection.service_name = String::new();
+            connection.username = String::new();
+            connection.password = String::new();
+            connection.external_password_store = Some(Box::new(OracleExternalPasswordStore {
+                alias: "SALES_BATCH".to_string(),
+                wallet_dir: wallet_dir.to_string_lossy().into_owned(),
+            }));
+        }
+        assert!(validate_connect_request(&request)
+            .unwrap_err()
+            .contains("has no auto-login wallet"));
+
+        std::fs::write(wallet_dir.join("cwallet.sso"), b"").expect("failed to write wallet");
+        let result = validate_connect_request(&request);
+        request.ssh_tunnel = Some(SshTunnelConnectOptions {
+            host: "bastion.example.com".to_string(),
+            port: None,
+            username: "ops".to_string(),
+            identity_file: None,
+            password: None,
+            cli_path: None,
+        });
+        let tunneled = validate_connect_request(&request);
+        let _ = std::fs::remove_dir_all(&wallet_dir);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            tunneled,
+            Err("A password store cannot be used through an SSH tunnel".to_string())
+        );
+    }
+
     #[test]
     fn validate_connect_request_requires_sqlite_file_path() {
         let mut request = valid_sqlite_connect_request();
@@ -386,4 +1002,25 @@ mod tests {
             Err("Schema context is too large.".to_string())
         );
     }
+
+    #[test]
+    fn validate_create_scratch_table_request_checks_name_and_query() {
+        let mut request = DbCreateScratchTableRequest {
+            session_id: 1,
+            name: "open_orders".to_string(),
+            query: "  WITH o AS (SELECT * FROM orders) SELECT * FROM o".to_string(),
+            production_confirmation: None,
+        };
+        assert_eq!(validate_create_scratch_table_request(&request), Ok(()));
+
+        request.name = "open orders".to_string();
+        assert!(validate_create_scratch_table_request(&request).is_err());
+
+        request.name = "open_orders".to_string();
+        request.query = "DELETE FROM orders".to_string();
+        assert_eq!(
+            validate_create_scratch_table_request(&request),
+            Err("Scratch tables are filled from a SELECT query.".to_string())
+        );
+    }
 }
@@ -0,0 +1,1151 @@
+use crate::profiles::app_data_file_path;
+use crate::sql_safety;
+use crate::types::{
+    DatabaseProvider, DbEditorBuffer, DbIdentifierUsage, DbIdentifierUsageRequest,
+    DbObjectTagRequest, DbPinObjectRequest, DbPinnedObject, DbQueryBind, DbQueryHistoryEntry,
+    DbSaveSnippetParameterSetRequest, DbSaveSnippetRequest, DbSchemaSearchResult, DbSnippet,
+    DbSnippetParameterSet, DbTaggedObject, DbWorkspaceSearchRequest, DbWorkspaceSearchResult,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::AppHandle;
+
+pub(crate) const WORKSPACE_STORE_FILE: &str = "workspace.json";
+const MAX_QUERY_HISTORY_ENTRIES: usize = 500;
+const DEFAULT_QUERY_HISTORY_LIMIT: u32 = 100;
+const DEFAULT_IDENTIFIER_USAGE_LIMIT: u32 = 500;
+const MAX_IDENTIFIER_USAGE_LIMIT: u32 = 5000;
+const DEFAULT_WORKSPACE_SEARCH_LIMIT: u32 = 200;
+const MAX_WORKSPACE_SEARCH_RESULTS: u32 = 1000;
+const MAX_SEARCH_SNIPPET_CHARS: usize = 220;
+
+static WORKSPACE_STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct WorkspaceState {
+    pub(crate) buffers: Vec<DbEditorBuffer>,
+    pub(crate) history: Vec<DbQueryHistoryEntry>,
+    pub(crate) snippets: Vec<DbSnippet>,
+    pub(crate) pinned_objects: Vec<DbPinnedObject>,
+    pub(crate) object_tags: Vec<DbTaggedObject>,
+}
+
+pub(crate) fn read_workspace(app: &AppHandle) -> Result<WorkspaceState, String> {
+    let path = app_data_file_path(app, WORKSPACE_STORE_FILE)?;
+    let _guard = lock_workspace_store()?;
+    read_workspace_from_path(path.as_path())
+}
+
+pub(crate) fn update_workspace<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut WorkspaceState) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = app_data_file_path(app, WORKSPACE_STORE_FILE)?;
+    let _guard = lock_workspace_store()?;
+    let mut workspace = read_workspace_from_path(path.as_path())?;
+    let value = f(&mut workspace)?;
+    write_workspace_to_path(path.as_path(), &workspace)?;
+    Ok(value)
+}
+
+pub(crate) fn save_editor_buffers(
+    app: &AppHandle,
+    buffers: Vec<DbEditorBuffer>,
+) -> Result<(), String> {
+    update_workspace(app, |workspace| {
+        workspace.buffers = buffers;
+        Ok(())
+    })
+}
+
+pub(crate) fn record_query_history(
+    app: &AppHandle,
+    sql: &str,
+    provider: DatabaseProvider,
+    succeeded: bool,
+) -> Result<(), String> {
+    let sql = sql.trim();
+    if sql.is_empty() {
+        return Ok(());
+    }
+
+    update_workspace(app, |workspace| {
+        push_history_entry(workspace, sql, provider, succeeded, now_millis());
+        Ok(())
+    })
+}
+
+pub(crate) fn list_query_history(
+    app: &AppHandle,
+    limit: Option<u32>,
+) -> Result<Vec<DbQueryHistoryEntry>, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_QUERY_HISTORY_LIMIT)
+        .clamp(1, MAX_QUERY_HISTORY_ENTRIES as u32) as usize;
+    let workspace = read_workspace(app)?;
+    Ok(workspace.history.into_iter().rev().take(limit).collect())
+}
+
+/// Names the query history refers to, most used first, so completions can rank the tables
+/// and columns the user actually queries above similarly named ones.
+pub(crate) fn identifier_usage(
+    app: &AppHandle,
+    request: &DbIdentifierUsageRequest,
+) -> Result<Vec<DbIdentifierUsage>, String> {
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_IDENTIFIER_USAGE_LIMIT)
+        .clamp(1, MAX_IDENTIFIER_USAGE_LIMIT) as usize;
+    let workspace = read_workspace(app)?;
+    Ok(count_identifier_usage(
+        &workspace.history,
+        request.provider,
+        limit,
+    ))
+}
+
+/// Counts each name once per successful entry; ties go to the most recently used.
+fn count_identifier_usage(
+    history: &[DbQueryHistoryEntry],
+    provider: Option<DatabaseProvider>,
+    limit: usize,
+) -> Vec<DbIdentifierUsage> {
+    let mut usage = HashMap::<String, DbIdentifierUsage>::new();
+    for entry in history.iter().filter(|entry| {
+        entry.succeeded && provider.is_none_or(|provider| entry.provider == provider)
+    }) {
+        for name in sql_safety::identifiers(&entry.sql) {
+            let counted = usage.entry(name.clone()).or_insert(DbIdentifierUsage {
+                name,
+                count: 0,
+                last_used_at: 0,
+            });
+            counted.count += 1;
+            counted.last_used_at = counted.last_used_at.max(entry.executed_at);
+        }
+    }
+    let mut usage = usage.into_values().collect::<Vec<_>>();
+    usage.sort_by(|left, right| {
+        right
+            .count
+            .cmp(&left.count)
+            .then(right.last_used_at.cmp(&left.last_used_at))
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    usage.truncate(limit);
+    usage
+}
+
+pub(crate) fn clear_query_history(app: &AppHandle) -> Result<(), String> {
+    update_workspace(app, |workspace| {
+        workspace.history.clear();
+        Ok(())
+    })
+}
+
+pub(crate) fn save_snippet(
+    app: &AppHandle,
+    request: DbSaveSnippetRequest,
+) -> Result<DbSnippet, String> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Snippet name is required".to_string());
+    }
+    if request.sql.trim().is_empty() {
+        return Err("Snippet SQL is required".to_string());
+    }
+
+    update_workspace(app, |workspace| {
+        let updated_at = now_millis();
+        let id = request
+            .id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| next_snippet_id(workspace, updated_at));
+        let snippet = DbSnippet {
+            id: id.clone(),
+            name,
+            sql: request.sql,
+            description: request
+                .description
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            updated_at,
+            parameter_sets: Vec::new(),
+        };
+
+        if let Some(position) = workspace.snippets.iter().position(|item| item.id == id) {
+            // Parameter sets are edited separately and survive changes to the SQL.
+            let snippet = DbSnippet {
+                parameter_sets: std::mem::take(&mut workspace.snippets[position].parameter_sets),
+                ..snippet
+            };
+            workspace.snippets[position] = snippet.clone();
+            return Ok(snippet);
+        } else {
+            workspace.snippets.push(snippet.clone());
+        }
+
+        Ok(snippet)
+    })
+}
+
+pub(crate) fn delete_snippet(app: &AppHandle, snippet_id: &str) -> Result<(), String> {
+    let snippet_id = snippet_id.trim();
+    if snippet_id.is_empty() {
+        return Err("Snippet id is required".to_string());
+    }
+
+    update_workspace(app, |workspace| {
+        let before = workspace.snippets.len();
+        workspace
+            .snippets
+            .retain(|snippet| snippet.id != snippet_id);
+        if workspace.snippets.len() == before {
+            return Err("Snippet not found".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// Pins or unpins an object for a profile and returns that profile's pins.
+pub(crate) fn pin_object(
+    app: &AppHandle,
+    request: DbPinObjectRequest,
+) -> Result<Vec<DbPinnedObject>, String> {
+    update_workspace(app, |workspace| {
+        set_pinned(workspace, &request, now_millis())?;
+        Ok(pinned_objects_for(workspace, request.profile_id.trim()))
+    })
+}
+
+pub(crate) fn list_pinned_objects(
+    app: &AppHandle,
+    profile_id: &str,
+) -> Result<Vec<DbPinnedObject>, String> {
+    let workspace = read_workspace(app)?;
+    Ok(pinned_objects_for(&workspace, profile_id.trim()))
+}
+
+/// Drops the pins and object tags of a deleted profile.
+pub(crate) fn remove_profile_objects(app: &AppHandle, profile_id: &str) -> Result<(), String> {
+    update_workspace(app, |workspace| {
+        workspace
+            .pinned_objects
+            .retain(|pin| pin.profile_id != profile_id);
+        workspace
+            .object_tags
+            .retain(|tagged| tagged.profile_id != profile_id);
+        Ok(())
+    })
+}
+
+fn set_pinned(
+    workspace: &mut WorkspaceState,
+    request: &DbPinObjectRequest,
+    now: u64,
+) -> Result<(), String> {
+    let profile_id = request.profile_id.trim();
+    let schema = request.schema.trim();
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+    let object_name = request.object_name.trim();
+    if profile_id.is_empty() {
+        return Err("Profile id is required".to_string());
+    }
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+
+    let position = workspace.pinned_objects.iter().position(|pin| {
+        pin.profile_id == profile_id
+            && pin.schema == schema
+            && pin.object_type == object_type
+            && pin.object_name == object_name
+    });
+    match (position, request.pinned) {
+        (Some(index), false) => {
+            workspace.pinned_objects.remove(index);
+        }
+        (None, true) => workspace.pinned_objects.push(DbPinnedObject {
+            profile_id: profile_id.to_string(),
+            schema: schema.to_string(),
+            object_type,
+            object_name: object_name.to_string(),
+            pinned_at: now,
+        }),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn pinned_objects_for(workspace: &WorkspaceState, profile_id: &str) -> Vec<DbPinnedObject> {
+    workspace
+        .pinned_objects
+        .iter()
+        .filter(|pin| pin.profile_id == profile_id)
+        .cloned()
+        .collect()
+}
+
+/// Adds a tag to an object and returns the object with all of its tags.
+pub(crate) fn tag_object(
+    app: &AppHandle,
+    request: DbObjectTagRequest,
+) -> Result<DbTaggedObject, String> {
+    update_workspace(app, |workspace| set_object_tag(workspace, &request, true))
+}
+
+/// Removes a tag from an object and returns the object with its remaining tags.
+pub(crate) fn untag_object(
+    app: &AppHandle,
+    request: DbObjectTagRequest,
+) -> Result<DbTaggedObject, String> {
+    update_workspace(app, |workspace| set_object_tag(workspace, &request, false))
+}
+
+pub(crate) fn list_tagged_objects(
+    app: &AppHandle,
+    profile_id: &str,
+    tag: Option<&str>,
+) -> Result<Vec<DbTaggedObject>, String> {
+    let workspace = read_workspace(app)?;
+    Ok(tagged_objects_for(&workspace, profile_id.trim(), tag))
+}
+
+/// Attaches the profile's tags to schema search results and adds tagged objects whose tag
+/// matches the search term.
+pub(crate) fn tag_schema_search_results(
+    app: &AppHandle,
+    profile_id: &str,
+    search_term: &str,
+    results: &mut Vec<DbSchemaSearchResult>,
+) -> Result<(), String> {
+    let workspace = read_workspace(app)?;
+    apply_object_tags(
+        &tagged_objects_for(&workspace, profile_id.trim(), None),
+        search_term,
+        results,
+    );
+    Ok(())
+}
+
+fn set_object_tag(
+    workspace: &mut WorkspaceState,
+    request: &DbObjectTagRequest,
+    tagged: bool,
+) -> Result<DbTaggedObject, String> {
+    let profile_id = request.profile_id.trim();
+    let schema = request.schema.trim();
+    let object_type = request.object_type.trim().to_ascii_uppercase();
+    let object_name = request.object_name.trim();
+    let tag = normalize_tag(&request.tag);
+    if profile_id.is_empty() {
+        return Err("Profile id is required".to_string());
+    }
+    if object_name.is_empty() {
+        return Err("Object name is required".to_string());
+    }
+    if tag.is_empty() {
+        return Err("Tag is required".to_string());
+    }
+
+    let position = workspace.object_tags.iter().position(|existing| {
+        existing.profile_id == profile_id
+            && existing.schema == schema
+            && existing.object_type == object_type
+            && existing.object_name == object_name
+    });
+    let index = match position {
+        Some(index) => index,
+        None => {
+            workspace.object_tags.push(DbTaggedObject {
+                profile_id: profile_id.to_string(),
+                schema: schema.to_string(),
+                object_type,
+                object_name: object_name.to_string(),
+                tags: Vec::new(),
+            });
+            workspace.object_tags.len() - 1
+        }
+    };
+
+    let object = &mut workspace.object_tags[index];
+    if tagged {
+        if !object.tags.contains(&tag) {
+            object.tags.push(tag);
+            object.tags.sort();
+        }
+    } else {
+        object.tags.retain(|existing| *existing != tag);
+    }
+    let result = object.clone();
+    if result.tags.is_empty() {
+        workspace.object_tags.remove(index);
+    }
+    Ok(result)
+}
+
+fn tagged_objects_for(
+    workspace: &WorkspaceState,
+    profile_id: &str,
+    tag: Option<&str>,
+) -> Vec<DbTaggedObject> {
+    let tag = tag.map(normalize_tag).filter(|tag| !tag.is_empty());
+    workspace
+        .object_tags
+        .iter()
+        .filter(|tagged| tagged.profile_id == profile_id)
+        .filter(|tagged| tag.as_ref().is_none_or(|tag| tagged.tags.contains(tag)))
+        .cloned()
+        .collect()
+}
+
+fn apply_object_tags(
+    tagged_objects: &[DbTaggedObject],
+    search_term: &str,
+    results: &mut Vec<DbSchemaSearchResult>,
+) {
+    let is_same_object = |tagged: &DbTaggedObject, result: &DbSchemaSearchResult| {
+        tagged.schema.eq_ignore_ascii_case(&result.schema)
+            && tagged.object_type.eq_ignore_ascii_case(&result.object_type)
+            && tagged.object_name == result.object_name
+    };
+    for result in results.iter_mut() {
+        if let Some(tagged) = tagged_objects
+            .iter()
+            .find(|tagged| is_same_object(tagged, result))
+        {
+            result.tags = tagged.tags.clone();
+        }
+    }
+
+    let term = normalize_tag(search_term);
+    if term.is_empty() {
+        return;
+    }
+    for tagged in tagged_objects {
+        let already_listed = results.iter().any(|result| is_same_object(tagged, result));
+        if already_listed || !tagged.tags.iter().any(|tag| tag.contains(&term)) {
+            continue;
+        }
+        results.push(DbSchemaSearchResult {
+            schema: tagged.schema.clone(),
+            object_type: tagged.object_type.clone(),
+            object_name: tagged.object_name.clone(),
+            match_scope: "tag".to_string(),
+            line: None,
+            snippet: tagged.tags.join(", "),
+            tags: tagged.tags.clone(),
+        });
+    }
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+pub(crate) fn save_snippet_parameter_set(
+    app: &AppHandle,
+    request: DbSaveSnippetParameterSetRequest,
+) -> Result<DbSnippetParameterSet, String> {
+    update_workspace(app, |workspace| {
+        upsert_parameter_set(workspace, request, now_millis())
+    })
+}
+
+pub(crate) fn delete_snippet_parameter_set(
+    app: &AppHandle,
+    snippet_id: &str,
+    parameter_set_id: &str,
+) -> Result<(), String> {
+    update_workspace(app, |workspace| {
+        let snippet = find_snippet_mut(workspace, snippet_id)?;
+        let before = snippet.parameter_sets.len();
+        snippet
+            .parameter_sets
+            .retain(|parameter_set| parameter_set.id != parameter_set_id.trim());
+        if snippet.parameter_sets.len() == before {
+            return Err("Parameter set not found".to_string());
+        }
+        Ok(())
+    })
+}
+
+/// The snippet's SQL and the bind values of the chosen parameter set, if any.
+pub(crate) fn snippet_query(
+    app: &AppHandle,
+    snippet_id: &str,
+    parameter_set_id: Option<&str>,
+) -> Result<(String, Vec<DbQueryBind>), String> {
+    let mut workspace = read_workspace(app)?;
+    let snippet = find_snippet_mut(&mut workspace, snippet_id)?;
+    let binds = match parameter_set_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(parameter_set_id) => snippet
+            .parameter_sets
+            .iter()
+            .find(|parameter_set| parameter_set.id == parameter_set_id)
+            .map(|parameter_set| parameter_set.values.clone())
+            .ok_or_else(|| "Parameter set not found".to_string())?,
+        None => Vec::new(),
+    };
+    Ok((snippet.sql.clone(), binds))
+}
+
+fn upsert_parameter_set(
+    workspace: &mut WorkspaceState,
+    request: DbSaveSnippetParameterSetRequest,
+    updated_at: u64,
+) -> Result<DbSnippetParameterSet, String> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Parameter set name is required".to_string());
+    }
+
+    let mut values = Vec::<DbQueryBind>::new();
+    for value in request.values {
+        let bind_name = value.name.trim().trim_start_matches(':').to_string();
+        if bind_name.is_empty() {
+            return Err("Bind variable names cannot be empty".to_string());
+        }
+        if values
+            .iter()
+            .any(|existing| existing.name.eq_ignore_ascii_case(&bind_name))
+        {
+            return Err(format!("Bind variable :{bind_name} is listed twice"));
+        }
+        values.push(DbQueryBind {
+            name: bind_name,
+            value: value.value,
+            value_type: value.value_type,
+        });
+    }
+
+    let snippet = find_snippet_mut(workspace, &request.snippet_id)?;
+    let id = request
+        .id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| next_parameter_set_id(snippet, updated_at));
+    let parameter_set = DbSnippetParameterSet {
+        id: id.clone(),
+        name,
+        values,
+        updated_at,
+    };
+
+    match snippet.parameter_sets.iter().position(|item| item.id == id) {
+        Some(position) => snippet.parameter_sets[position] = parameter_set.clone(),
+        None => snippet.parameter_sets.push(parameter_set.clone()),
+    }
+    Ok(parameter_set)
+}
+
+fn find_snippet_mut<'a>(
+    workspace: &'a mut WorkspaceState,
+    snippet_id: &str,
+) -> Result<&'a mut DbSnippet, String> {
+    let snippet_id = snippet_id.trim();
+    workspace
+        .snippets
+        .iter_mut()
+        .find(|snippet| snippet.id == snippet_id)
+        .ok_or_else(|| "Snippet not found".to_string())
+}
+
+pub(crate) fn search_workspace(
+    app: &AppHandle,
+    request: &DbWorkspaceSearchRequest,
+) -> Result<Vec<DbWorkspaceSearchResult>, String> {
+    let workspace = read_workspace(app)?;
+    search_workspace_state(&workspace, request)
+}
+
+fn search_workspace_state(
+    workspace: &WorkspaceState,
+    request: &DbWorkspaceSearchRequest,
+) -> Result<Vec<DbWorkspaceSearchResult>, String> {
+    let search_term = request.search_term.trim();
+    if search_term.is_empty() {
+        return Err("Search term is required".to_string());
+    }
+
+    let include_buffers = request.include_buffers.unwrap_or(true);
+    let include_history = request.include_history.unwrap_or(true);
+    let include_snippets = request.include_snippets.unwrap_or(true);
+    if !(include_buffers || include_history || include_snippets) {
+        return Err("Select at least one search scope".to_string());
+    }
+
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_WORKSPACE_SEARCH_LIMIT)
+        .clamp(1, MAX_WORKSPACE_SEARCH_RESULTS) as usize;
+    let needle = search_term.to_lowercase();
+    let mut matches = Vec::new();
+
+    if include_buffers {
+        for buffer in &workspace.buffers {
+            push_text_matches(
+                &mut matches,
+                limit,
+                "buffer",
+                buffer.id.as_str(),
+                buffer.title.as_str(),
+                buffer.sql.as_str(),
+                None,
+                needle.as_str(),
+            );
+        }
+    }
+
+    if include_snippets {
+        for snippet in &workspace.snippets {
+            let label_matches = snippet.name.to_lowercase().contains(needle.as_str())
+                || snippet
+                    .description
+                    .as_deref()
+                    .is_some_and(|value| value.to_lowercase().contains(needle.as_str()));
+            if label_matches && matches.len() < limit {
+                matches.push(DbWorkspaceSearchResult {
+                    source: "snippet".to_string(),
+                    item_id: snippet.id.clone(),
+                    title: snippet.name.clone(),
+                    line: None,
+                    snippet: truncate_for_snippet(
+                        snippet
+                            .description
+                            .as_deref()
+                            .unwrap_or(snippet.name.as_str()),
+                    ),
+                    timestamp: Some(snippet.updated_at),
+                });
+            }
+            push_text_matches(
+                &mut matches,
+                limit,
+                "snippet",
+                snippet.id.as_str(),
+                snippet.name.as_str(),
+                snippet.sql.as_str(),
+                Some(snippet.updated_at),
+                needle.as_str(),
+            );
+        }
+    }
+
+    if include_history {
+        for entry in workspace.history.iter().rev() {
+            let title = first_line_title(entry.sql.as_str());
+            push_text_matches(
+                &mut matches,
+                limit,
+                "history",
+                entry.id.as_str(),
+                title.as_str(),
+                entry.sql.as_str(),
+                Some(entry.executed_at),
+                needle.as_str(),
+            );
+        }
+    }
+
+    Ok(matches)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_text_matches(
+    matches: &mut Vec<DbWorkspaceSearchResult>,
+    limit: usize,
+    source: &str,
+    item_id: &str,
+    title: &str,
+    text: &str,
+    timestamp: Option<u64>,
+    needle_lower: &str,
+) {
+    for (index, line) in text.lines().enumerate() {
+        if matches.len() >= limit {
+            return;
+        }
+        if !line.to_lowercase().contains(needle_lower) {
+            continue;
+        }
+
+        matches.push(DbWorkspaceSearchResult {
+            source: source.to_string(),
+            item_id: item_id.to_string(),
+            title: title.to_string(),
+            line: Some((index + 1).min(u32::MAX as usize) as u32),
+            snippet: truncate_for_snippet(line),
+            timestamp,
+        });
+    }
+}
+
+fn push_history_entry(
+    workspace: &mut WorkspaceState,
+    sql: &str,
+    provider: DatabaseProvider,
+    succeeded: bool,
+    executed_at: u64,
+) {
+    let mut id = format!("history-{executed_at}");
+    let mut suffix = 2;
+    while workspace.history.iter().any(|entry| entry.id == id) {
+        id = format!("history-{executed_at}-{suffix}");
+        suffix += 1;
+    }
+
+    workspace.history.push(DbQueryHistoryEntry {
+        id,
+        sql: sql.to_string(),
+        provider,
+        executed_at,
+        succeeded,
+    });
+
+    let overflow = workspace
+        .history
+        .len()
+        .saturating_sub(MAX_QUERY_HISTORY_ENTRIES);
+    if overflow > 0 {
+        workspace.history.drain(..overflow);
+    }
+}
+
+fn next_snippet_id(workspace: &WorkspaceState, seed: u64) -> String {
+    let mut candidate = format!("snippet-{seed}");
+    let mut suffix = 2;
+    while workspace
+        .snippets
+        .iter()
+        .any(|snippet| snippet.id == candidate)
+    {
+        candidate = format!("snippet-{seed}-{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+fn next_parameter_set_id(snippet: &DbSnippet, seed: u64) -> String {
+    let mut candidate = format!("params-{seed}");
+    let mut suffix = 2;
+    while snippet
+        .parameter_sets
+        .iter()
+        .any(|parameter_set| parameter_set.id == candidate)
+    {
+        candidate = format!("params-{seed}-{suffix}");
+        suffix += 1;
+    }
+    candidate
+}
+
+fn first_line_title(sql: &str) -> String {
+    let line = sql
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    truncate_for_snippet(line)
+}
+
+fn truncate_for_snippet(value: &str) -> String {
+    let trimmed = value.trim();
+    if trimmed.chars().count() <= MAX_SEARCH_SNIPPET_CHARS {
+        return trimmed.to_string();
+    }
+
+    let mut snippet = trimmed
+        .chars()
+        .take(MAX_SEARCH_SNIPPET_CHARS)
+        .collect::<String>();
+    snippet.push_str("...");
+    snippet
+}
+
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis().min(u64::MAX as u128) as u64)
+        .unwrap_or_default()
+}
+
+fn lock_workspace_store() -> Result<std::sync::MutexGuard<'static, ()>, String> {
+    WORKSPACE_STORE_LOCK
+        .lock()
+        .map_err(|_| "Failed to acquire workspace store lock".to_string())
+}
+
+pub(crate) fn read_workspace_from_path(path: &Path) -> Result<WorkspaceState, String> {
+    if !path.exists() {
+        return Ok(WorkspaceState::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read workspace file: {error}"))?;
+    if content.trim().is_empty() {
+        return Ok(WorkspaceState::default());
+    }
+
+    serde_json::from_str::<WorkspaceState>(&content)
+        .map_err(|error| format!("Failed to parse workspace file: {error}"))
+}
+
+fn write_workspace_to_path(path: &Path, workspace: &WorkspaceState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create app data directory: {error}"))?;
+    }
+
+    let payload = serde_json::to_string_pretty(workspace)
+        .map_err(|error| format!("Failed to serialize workspace: {error}"))?;
+    fs::write(path, payload).map_err(|error| format!("Failed to write workspace file: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        apply_object_tags, count_identifier_usage, pinned_objects_for, push_history_entry,
+        read_workspace_from_path, search_workspace_state, set_object_tag, set_pinned,
+        tagged_objects_for, upsert_parameter_set, write_workspace_to_path, WorkspaceState,
+        MAX_QUERY_HISTORY_ENTRIES,
+    };
+    use crate::types::{
+        DatabaseProvider, DbBindType, DbEditorBuffer, DbObjectTagRequest, DbPinObjectRequest,
+        DbQueryBind, DbSaveSnippetParameterSetRequest, DbSchemaSearchResult, DbSnippet,
+        DbWorkspaceSearchRequest,
+    };
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    struct TempTestDir {
+        path: PathBuf,
+    }
+
+    impl TempTestDir {
+        fn new(name: &str) -> Self {
+            let unique = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("system clock should be after unix epoch")
+                .as_nanos();
+            let path = std::env::temp_dir().join(format!(
+                "clarity_workspace_tests_{name}_{}_{}",
+                std::process::id(),
+                unique
+            ));
+            fs::create_dir_all(&path).expect("failed to create temp test directory");
+            Self { path }
+        }
+    }
+
+    impl Drop for TempTestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    fn sample_workspace() -> WorkspaceState {
+        let mut workspace = WorkspaceState {
+            buffers: vec![DbEditorBuffer {
+                id: "sheet-1".to_string(),
+                title: "Orders".to_string(),
+                sql: "select *\nfrom orders\nwhere status = 'OPEN'".to_string(),
+            }],
+            history: Vec::new(),
+            snippets: vec![DbSnippet {
+                id: "snippet-1".to_string(),
+                name: "Recent orders CTE".to_string(),
+                sql: "with recent_orders as (\n  select * from orders\n)\nselect * from recent_orders"
+                    .to_string(),
+                description: None,
+                updated_at: 10,
+                parameter_sets: Vec::new(),
+            }],
+            pinned_objects: Vec::new(),
+            object_tags: Vec::new(),
+        };
+        push_history_entry(
+            &mut workspace,
+            "with totals as (select 1 from dual) select * from totals",
+            DatabaseProvider::Oracle,
+            true,
+            20,
+        );
+        workspace
+    }
+
+    fn search_request(term: &str) -> DbWorkspaceSearchRequest {
+        DbWorkspaceSearchRequest {
+            search_term: term.to_string(),
+            limit: None,
+            include_buffers: None,
+            include_history: None,
+            include_snippets: None,
+        }
+    }
+
+    #[test]
+    fn searches_buffers_snippets_and_history_case_insensitively() {
+        let workspace = sample_workspace();
+        let results = search_workspace_state(&workspace, &search_request("ORDERS"))
+            .expect("search should succeed");
+
+        let sources = results
+            .iter()
+            .map(|result| (result.source.as_str(), result.line))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sources,
+            vec![
+                ("buffer", Some(2)),
+                ("snippet", None),
+                ("snippet", Some(1)),
+                ("snippet", Some(2)),
+                ("snippet", Some(4))
+            ]
+        );
+
+        let cte_results = search_workspace_state(&workspace, &search_request("with "))
+            .expect("search should succeed");
+        assert_eq!(cte_results.len(), 2);
+        assert_eq!(cte_results[1].source, "history");
+        assert_eq!(cte_results[1].timestamp, Some(20));
+    }
+
+    #[test]
+    fn search_respects_scopes_and_limit() {
+        let workspace = sample_workspace();
+        let mut request = search_request("select");
+        request.include_buffers = Some(false);
+        request.include_snippets = Some(false);
+        let history_only = search_workspace_state(&workspace, &request).expect("search");
+        assert!(history_only.iter().all(|result| result.source == "history"));
+
+        let mut limited = search_request("select");
+        limited.limit = Some(2);
+        assert_eq!(
+            search_workspace_state(&workspace, &limited)
+                .expect("search")
+                .len(),
+            2
+        );
+
+        request.include_history = Some(false);
+        assert_eq!(
+            search_workspace_state(&workspace, &request),
+            Err("Select at least one search scope".to_string())
+        );
+        assert_eq!(
+            search_workspace_state(&workspace, &search_request("  ")),
+            Err("Search term is required".to_string())
+        );
+    }
+
+    #[test]
+    fn pins_are_scoped_per_profile_and_toggle_without_duplicates() {
+        let mut workspace = WorkspaceState::default();
+        let pin = |profile_id: &str, pinned: bool| DbPinObjectRequest {
+            profile_id: profile_id.to_string(),
+            schema: "HR".to_string(),
+            object_type: "table".to_string(),
+            object_name: "EMPLOYEES".to_string(),
+            pinned,
+        };
+        set_pinned(&mut workspace, &pin("prod", true), 1).expect("pin");
+        set_pinned(&mut workspace, &pin("prod", true), 2).expect("pin again");
+        set_pinned(&mut workspace, &pin("dev", true), 3).expect("pin dev");
+
+        let prod = pinned_objects_for(&workspace, "prod");
+        assert_eq!(prod.len(), 1);
+        assert_eq!(prod[0].object_type, "TABLE");
+        assert_eq!(prod[0].pinned_at, 1);
+
+        set_pinned(&mut workspace, &pin("prod", false), 4).expect("unpin");
+        assert!(pinned_objects_for(&workspace, "prod").is_empty());
+        assert_eq!(pinned_objects_for(&workspace, "dev").len(), 1);
+    }
+
+    #[test]
+    fn tags_are_normalized_listed_by_tag_and_found_by_search() {
+        let mut workspace = WorkspaceState::default();
+        let tag = |object_name: &str, tag: &str| DbObjectTagRequest {
+            profile_id: "prod".to_string(),
+            schema: "HR".to_string(),
+            object_type: "table".to_string(),
+            object_name: object_name.to_string(),
+            tag: tag.to_string(),
+        };
+        set_object_tag(&mut workspace, &tag("INVOICES", " Billing "), true).expect("tag");
+        set_object_tag(&mut workspace, &tag("INVOICES", "billing"), true).expect("tag again");
+        set_object_tag(&mut workspace, &tag("OLD_INVOICES", "deprecated"), true).expect("tag");
+        let invoices =
+            set_object_tag(&mut workspace, &tag("INVOICES", "archive"), true).expect("tag");
+        assert_eq!(invoices.tags, vec!["archive", "billing"]);
+        assert_eq!(
+            tagged_objects_for(&workspace, "prod", Some("BILLING"))[0].object_name,
+            "INVOICES"
+        );
+
+        let mut results = vec![DbSchemaSearchResult {
+            schema: "HR".to_string(),
+            object_type: "TABLE".to_string(),
+            object_name: "INVOICES".to_string(),
+            match_scope: "object_name".to_string(),
+            line: None,
+            snippet: "INVOICES".to_string(),
+            tags: Vec::new(),
+        }];
+        apply_object_tags(
+            &tagged_objects_for(&workspace, "prod", None),
+            "deprec",
+            &mut results,
+        );
+        assert_eq!(results[0].tags, vec!["archive", "billing"]);
+        assert_eq!(results[1].object_name, "OLD_INVOICES");
+        assert_eq!(results[1].match_scope, "tag");
+
+        set_object_tag(&mut workspace, &tag("OLD_INVOICES", "deprecated"), false).expect("untag");
+        assert_eq!(tagged_objects_for(&workspace, "prod", None).len(), 1);
+    }
+
+    #[test]
+    fn history_is_capped_and_ids_stay_unique() {
+        let mut workspace = WorkspaceState::default();
+        for _ in 0..(MAX_QUERY_HISTORY_ENTRIES + 5) {
+            push_history_entry(
+                &mut workspace,
+                "select 1",
+                DatabaseProvider::Oracle,
+                true,
+                7,
+            );
+        }
+
+        assert_eq!(workspace.history.len(), MAX_QUERY_HISTORY_ENTRIES);
+        let mut ids = workspace
+            .history
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), MAX_QUERY_HISTORY_ENTRIES);
+    }
+
+    #[test]
+    fn ranks_identifiers_by_successful_history_use() {
+        let mut workspace = WorkspaceState::default();
+        let entries = [
+            ("SELECT * FROM orders", DatabaseProvider::Oracle, true, 1),
+            (
+                "SELECT status FROM orders",
+                DatabaseProvider::Oracle,
+                true,
+                2,
+            ),
+            ("SELECT * FROM customers", DatabaseProvider::Oracle, true, 3),
+            (
+                "SELECT * FROM customers",
+                DatabaseProvider::Oracle,
+                false,
+                4,
+            ),
+            (
+                "SELECT * FROM customers",
+                DatabaseProvider::Postgres,
+                true,
+                5,
+            ),
+        ];
+        for (sql, provider, succeeded, executed_at) in entries {
+            push_history_entry(&mut workspace, sql, provider, succeeded, executed_at);
+        }
+
+        let usage = count_identifier_usage(&workspace.history, Some(DatabaseProvider::Oracle), 2)
+            .into_iter()
+            .map(|usage| (usage.name, usage.count, usage.last_used_at))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            usage,
+            vec![
+                ("ORDERS".to_string(), 2, 2),
+                ("CUSTOMERS".to_string(), 1, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_round_trips_and_tolerates_missing_file() {
+        let temp_dir = TempTestDir::new("round_trip");
+        let path = temp_dir.path.join("workspace.json");
+
+        let missing = read_workspace_from_path(path.as_path()).expect("missing file is empty");
+        assert!(missing.buffers.is_empty() && missing.history.is_empty());
+
+        write_workspace_to_path(path.as_path(), &sample_workspace()).expect("write");
+        let restored = read_workspace_from_path(path.as_path()).expect("read");
+        assert_eq!(restored.buffers.len(), 1);
+        assert_eq!(restored.snippets[0].name, "Recent orders CTE");
+        assert_eq!(restored.history[0].executed_at, 20);
+    }
+
+    #[test]
+    fn parameter_sets_are_saved_per_snippet_and_kept_on_snippet_edits() {
+        let mut workspace = sample_workspace();
+        let request =
+            |id: Option<&str>, name: &str, bind_names: &[&str]| DbSaveSnippetParameterSetRequest {
+                snippet_id: "snippet-1".to_string(),
+                id: id.map(str::to_string),
+                name: name.to_string(),
+                values: bind_names
+                    .iter()
+                    .map(|bind_name| DbQueryBind {
+                        name: bind_name.to_string(),
+                        value: Some("42".to_string()),
+                        value_type: DbBindType::Text,
+                    })
+                    .collect(),
+            };
+
+        let saved = upsert_parameter_set(
+            &mut workspace,
+            request(None, " Customer A / March ", &[":customer_id", "month"]),
+            30,
+        )
+        .expect("valid parameter set");
+        assert_eq!(saved.id, "params-30");
+        assert_eq!(saved.name, "Customer A / March");
+        assert_eq!(saved.values[0].name, "customer_id");
+
+        upsert_parameter_set(
+            &mut workspace,
+            request(Some("params-30"), "Renamed", &[]),
+            31,
+        )
+        .expect("update in place");
+        assert_eq!(workspace.snippets[0].parameter_sets.len(), 1);
+        assert_eq!(workspace.snippets[0].parameter_sets[0].name, "Renamed");
+
+        assert_eq!(
+            upsert_parameter_set(&mut workspace, request(None, "Dup", &["ID", ":id"]), 32)
+                .map(|_| ()),
+            Err("Bind variable :id is listed twice".to_string())
+        );
+    }
+}
@@ -0,0 +1,558 @@
+use crate::ai;
+use crate::profiles;
+use crate::types::{
+    DbAiPromptSettings, DbExportWorkspaceRequest, DbImportWorkspaceRequest, DbPinnedObject,
+    DbSnippet, DbTaggedObject, DbWorkspaceArchiveSummary, StoredConnectionProfile,
+};
+use crate::workspace::{self, now_millis, WorkspaceState};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::AppHandle;
+
+const ARCHIVE_MAGIC: &[u8] = b"CLARITY-WORKSPACE-1\n";
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// PBKDF2-HMAC-SHA256 rounds for new archives; the count is stored in the archive.
+const PBKDF2_ITERATIONS: u32 = 600_000;
+/// Most rounds an archive may ask for, so a crafted file cannot stall the import.
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+const MIN_PASSPHRASE_CHARS: usize = 8;
+
+/// Everything a workspace archive carries. Editor buffers and query history stay behind:
+/// they belong to the machine's work in progress rather than its setup.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+struct WorkspaceArchive {
+    exported_at: u64,
+    profiles: Vec<StoredConnectionProfile>,
+    secrets: Vec<ArchivedSecrets>,
+    ai_api_key: Option<String>,
+    ai_prompt_settings: Option<DbAiPromptSettings>,
+    snippets: Vec<DbSnippet>,
+    pinned_objects: Vec<DbPinnedObject>,
+    object_tags: Vec<DbTaggedObject>,
+    settings: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedSecrets {
+    profile_id: String,
+    password: Option<String>,
+    ssh_password: Option<String>,
+}
+
+/// Writes profiles, AI prompt settings, snippets, pinned and tagged objects and the
+/// frontend's settings to one file sealed with a key derived from the passphrase.
+/// Secrets are only added when asked for.
+pub(crate) fn export_workspace(
+    app: &AppHandle,
+    request: &DbExportWorkspaceRequest,
+) -> Result<DbWorkspaceArchiveSummary, String> {
+    let path = archive_path(&request.file_path)?;
+    validate_passphrase(&request.passphrase)?;
+
+    let profiles = profiles::read_profiles(app)?;
+    let mut secrets = Vec::new();
+    let mut ai_api_key = None;
+    if request.include_secrets {
+        for profile in &profiles {
+            let password = profiles::read_profile_secret(&profile.id)?;
+            let ssh_password = profiles::read_profile_ssh_secret(&profile.id)?;
+            if password.is_some() || ssh_password.is_some() {
+                secrets.push(ArchivedSecrets {
+                    profile_id: profile.id.clone(),
+                    password,
+                    ssh_password,
+                });
+            }
+        }
+        ai_api_key = profiles::read_ai_api_key()?;
+    }
+    let state = workspace::read_workspace(app)?;
+    let archive = WorkspaceArchive {
+        exported_at: now_millis(),
+        profiles,
+        secrets,
+        ai_api_key,
+        ai_prompt_settings: Some(ai::read_prompt_settings(app)?),
+        snippets: state.snippets,
+        pinned_objects: state.pinned_objects,
+        object_tags: state.object_tags,
+        settings: request.settings.clone(),
+    };
+
+    let sealed = seal_archive(&archive, &request.passphrase, PBKDF2_ITERATIONS)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| {
+            format!("Failed to create directory '{}': {error}", parent.display())
+        })?;
+    }
+    fs::write(&path, sealed)
+        .map_err(|error| format!("Failed to write '{}': {error}", path.display()))?;
+
+    let mut summary = summarize(&path, &archive);
+    summary.message = format!(
+        "Exported {} profile(s), {} snippet(s) and {} pinned object(s) to {}{}",
+        summary.profile_count,
+        summary.snippet_count,
+        summary.pinned_object_count,
+        path.display(),
+        if request.include_secrets {
+            format!(
+                ", with the passwords of {} profile(s)",
+                summary.secret_count
+            )
+        } else {
+            ", without passwords".to_string()
+        }
+    );
+    Ok(summary)
+}
+
+/// Merges an archive into this machine: profiles are added, snippets replace those with
+/// the same id, pins and tags are added to the existing ones, and AI prompt settings are
+/// written over the local ones. The frontend's settings are returned for it to apply.
+///
+/// Profile ids are only unique per machine, so an imported profile whose id is taken here
+/// gets a new one, along with its pins, tags and secrets. Secrets of imported profiles come
+/// from the archive alone; whatever the keychain held for their ids is cleared.
+pub(crate) fn import_workspace(
+    app: &AppHandle,
+    request: &DbImportWorkspaceRequest,
+    next_profile_id: &AtomicU64,
+) -> Result<DbWorkspaceArchiveSummary, String> {
+    let path = archive_path(&request.file_path)?;
+    let sealed =
+        fs::read(&path).map_err(|error| format!("Failed to read '{}': {error}", path.display()))?;
+    let mut archive = open_archive(&sealed, &request.passphrase)?;
+
+    let mut stored = profiles::read_profiles(app)?;
+    remap_profile_ids(&mut archive, &stored, || {
+        format!(
+            "profile-{}",
+            next_profile_id.fetch_add(1, Ordering::Relaxed)
+        )
+    });
+    stored.extend(archive.profiles.iter().cloned());
+    profiles::write_profiles(app, &stored)?;
+    for profile in &archive.profiles {
+        let secrets = archive
+            .secrets
+            .iter()
+            .find(|secrets| secrets.profile_id == profile.id);
+        match secrets.and_then(|secrets| secrets.password.as_deref()) {
+            Some(password) => profiles::write_profile_secret(&profile.id, password)?,
+            None => profiles::clear_profile_secret(&profile.id)?,
+        }
+        match secrets.and_then(|secrets| secrets.ssh_password.as_deref()) {
+            Some(ssh_password) => profiles::write_profile_ssh_secret(&profile.id, ssh_password)?,
+            None => profiles::clear_profile_ssh_secret(&profile.id)?,
+        }
+    }
+    if let Some(api_key) = archive.ai_api_key.as_deref().filter(|key| !key.is_empty()) {
+        profiles::write_ai_api_key(api_key)?;
+    }
+    if let Some(settings) = &archive.ai_prompt_settings {
+        ai::save_prompt_settings(app, settings.clone())?;
+    }
+    workspace::update_workspace(app, |state| {
+        merge_workspace(state, &archive);
+        Ok(())
+    })?;
+
+    let mut summary = summarize(&path, &archive);
+    summary.settings = archive.settings;
+    summary.message = format!(
+        "Imported {} profile(s), {} snippet(s) and {} pinned object(s) from {}",
+        summary.profile_count,
+        summary.snippet_count,
+        summary.pinned_object_count,
+        path.display()
+    );
+    Ok(summary)
+}
+
+fn archive_path(file_path: &str) -> Result<PathBuf, String> {
+    let file_path = file_path.trim();
+    if file_path.is_empty() {
+        return Err("Archive file is required".to_string());
+    }
+    Ok(PathBuf::from(file_path))
+}
+
+fn validate_passphrase(passphrase: &str) -> Result<(), String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(format!(
+            "Passphrase must be at least {MIN_PASSPHRASE_CHARS} characters"
+        ));
+    }
+    Ok(())
+}
+
+fn summarize(path: &Path, archive: &WorkspaceArchive) -> DbWorkspaceArchiveSummary {
+    DbWorkspaceArchiveSummary {
+        file_path: path.to_string_lossy().into_owned(),
+        profile_count: archive.profiles.len() as u32,
+        secret_count: archive.secrets.len() as u32,
+        snippet_count: archive.snippets.len() as u32,
+        pinned_object_count: archive.pinned_objects.len() as u32,
+        tagged_object_count: archive.object_tags.len() as u32,
+        settings: None,
+        message: String::new(),
+    }
+}
+
+/// Gives imported profiles whose id is already used here, or repeated in the archive, a
+/// fresh id, and points their secrets, pins, tags and the last-used profile setting at it.
+fn remap_profile_ids(
+    archive: &mut WorkspaceArchive,
+    local: &[StoredConnectionProfile],
+    mut fresh_id: impl FnMut() -> String,
+) {
+    let mut taken = local
+        .iter()
+        .map(|profile| profile.id.clone())
+        .collect::<Vec<_>>();
+    let archived = archive
+        .profiles
+        .iter()
+        .map(|profile| profile.id.clone())
+        .collect::<Vec<_>>();
+    let mut renamed = HashMap::new();
+    for profile in &mut archive.profiles {
+        if !taken.contains(&profile.id) {
+            taken.push(profile.id.clone());
+            continue;
+        }
+        let mut id = fresh_id();
+        while taken.contains(&id) || archived.contains(&id) {
+            id = fresh_id();
+        }
+        taken.push(id.clone());
+        renamed.insert(profile.id.clone(), id.clone());
+        profile.id = id;
+    }
+    if renamed.is_empty() {
+        return;
+    }
+
+    let rename = |profile_id: &mut String| {
+        if let Some(id) = renamed.get(profile_id.as_str()) {
+            *profile_id = id.clone();
+        }
+    };
+    archive
+        .secrets
+        .iter_mut()
+        .for_each(|secrets| rename(&mut secrets.profile_id));
+    archive
+        .pinned_objects
+        .iter_mut()
+        .for_each(|pinned| rename(&mut pinned.profile_id));
+    archive
+        .object_tags
+        .iter_mut()
+        .for_each(|tagged| rename(&mut tagged.profile_id));
+    if let Some(serde_json::Value::String(last_used)) = archive
+        .settings
+        .as_mut()
+        .and_then(|settings| settings.get_mut("lastUsedConnectionProfileId"))
+    {
+        rename(last_used);
+    }
+}
+
+fn merge_workspace(state: &mut WorkspaceState, archive: &WorkspaceArchive) {
+    merge_by_key(
+        &mut state.snippets,
+        archive.snippets.iter().cloned(),
+        |snippet| snippet.id.clone(),
+    );
+    for pinned in &archive.pinned_objects {
+        let exists = state.pinned_objects.iter().any(|existing| {
+            existing.profile_id == pinned.profile_id
+                && existing.schema == pinned.schema
+                && existing.object_type == pinned.object_type
+                && existing.object_name == pinned.object_name
+        });
+        if !exists {
+            state.pinned_objects.push(pinned.clone());
+        }
+    }
+    for tagged in &archive.object_tags {
+        let existing = state.object_tags.iter_mut().find(|existing| {
+            existing.profile_id == tagged.profile_id
+                && existing.schema == tagged.schema
+                && existing.object_type == tagged.object_type
+                && existing.object_name == tagged.object_name
+        });
+        match existing {
+            Some(existing) => {
+                for tag in &tagged.tags {
+                    if !existing.tags.contains(tag) {
+                        existing.tags.push(tag.clone());
+                    }
+                }
+                existing.tags.sort();
+            }
+            None => state.object_tags.push(tagged.clone()),
+        }
+    }
+}
+
+/// Replaces items whose key is already present and appends the rest.
+fn merge_by_key<T>(
+    existing: &mut Vec<T>,
+    incoming: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> String,
+) {
+    for item in incoming {
+        match existing
+            .iter()
+            .position(|current| key(current) == key(&item))
+        {
+            Some(index) => existing[index] = item,
+            None => existing.push(item),
+        }
+    }
+}
+
+/// Layout: magic, PBKDF2 iterations (big-endian u32), salt, nonce, then the archive JSON
+/// sealed with ChaCha20-Poly1305. The header is authenticated along with the contents.
+fn seal_archive(
+    archive: &WorkspaceArchive,
+    passphrase: &str,
+    iterations: u32,
+) -> Result<Vec<u8>, String> {
+    let random = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    random
+        .fill(&mut salt)
+        .and_then(|()| random.fill(&mut nonce))
+        .map_err(|_| "Failed to generate archive salt".to_string())?;
+
+    let mut sealed = ARCHIVE_MAGIC.to_vec();
+    sealed.extend_from_slice(&iterations.to_be_bytes());
+    sealed.extend_from_slice(&salt);
+    sealed.extend_from_slice(&nonce);
+    let key = archive_key(passphrase, iterations, &salt)?;
+    let mut in_out = serde_json::to_vec(archive)
+        .map_err(|error| format!("Failed to serialize workspace: {error}"))?;
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(sealed.as_slice()),
+        &mut in_out,
+    )
+    .map_err(|_| "Failed to encrypt workspace archive".to_string())?;
+    sealed.extend_from_slice(&in_out);
+    Ok(sealed)
+}
+
+fn open_archive(sealed: &[u8], passphrase: &str) -> Result<WorkspaceArchive, String> {
+    let header_len = ARCHIVE_MAGIC.len() + 4 + SALT_LEN + NONCE_LEN;
+    if !sealed.starts_with(ARCHIVE_MAGIC) {
+        return Err("The file is not a Clarity workspace archive".to_string());
+    }
+    if sealed.len() < header_len {
+        return Err("The workspace archive is truncated".to_string());
+    }
+    let (header, ciphertext) = sealed.split_at(header_len);
+    let rest = &header[ARCHIVE_MAGIC.len()..];
+    let (iterations, rest) = rest.split_at(4);
+    let (salt, nonce) = rest.split_at(SALT_LEN);
+    let iterations = u32::from_be_bytes(
+        iterations
+            .try_into()
+            .map_err(|_| "The workspace archive is truncated".to_string())?,
+    );
+
+    if iterations > MAX_PBKDF2_ITERATIONS {
+        return Err("The workspace archive has an invalid key setting".to_string());
+    }
+
+    let key = archive_key(passphrase, iterations, salt)?;
+    let nonce = Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| "The workspace archive has an invalid nonce".to_string())?;
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::from(header), &mut in_out)
+        .map_err(|_| {
+            "Failed to decrypt the workspace archive: wrong passphrase or damaged file".to_string()
+        })?;
+    serde_json::from_slice(plaintext)
+        .map_err(|error| format!("Failed to parse the workspace archive: {error}"))
+}
+
+fn archive_key(passphrase: &str, iterations: u32, salt: &[u8]) -> Result<LessSafeKey, String> {
+    let iterations = NonZeroU32::new(iterations)
+        .ok_or_else(|| "The workspace archive has an invalid key setting".to_string())?;
+    let mut bytes = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut bytes,
+    );
+    let key = UnboundKey::new(&CHACHA20_POLY1305, &bytes)
+        .map_err(|_| "Failed to derive the archive key".to_string())?;
+    Ok(LessSafeKey::new(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        merge_workspace, open_archive, remap_profile_ids, seal_archive, ArchivedSecrets,
+        WorkspaceArchive, ARCHIVE_MAGIC, MAX_PBKDF2_ITERATIONS,
+    };
+    use crate::types::{DbPinnedObject, DbSnippet, DbTaggedObject, StoredConnectionProfile};
+    use crate::workspace::WorkspaceState;
+
+    fn profile(id: &str, host: &str) -> StoredConnectionProfile {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": host,
+            "provider": "postgres",
+            "connection": { "host": host, "database": "app", "username": "app" },
+        }))
+        .unwrap()
+    }
+
+    fn snippet(id: &str, sql: &str) -> DbSnippet {
+        DbSnippet {
+            id: id.to_string(),
+            name: id.to_string(),
+            sql: sql.to_string(),
+            description: None,
+            updated_at: 1,
+            parameter_sets: Vec::new(),
+        }
+    }
+
+    fn pinned(object_name: &str) -> DbPinnedObject {
+        DbPinnedObject {
+            profile_id: "p1".to_string(),
+            schema: "HR".to_string(),
+            object_type: "TABLE".to_string(),
+            object_name: object_name.to_string(),
+            pinned_at: 1,
+        }
+    }
+
+    #[test]
+    fn archive_round_trips_and_rejects_a_wrong_passphrase() {
+        let archive = WorkspaceArchive {
+            snippets: vec![snippet("s1", "SELECT 1 FROM dual")],
+            settings: Some(serde_json::json!({ "theme": "dark" })),
+            ..WorkspaceArchive::default()
+        };
+        let sealed = seal_archive(&archive, "correct horse", 10).unwrap();
+        assert!(!String::from_utf8_lossy(&sealed).contains("SELECT 1"));
+
+        let opened = open_archive(&sealed, "correct horse").unwrap();
+        assert_eq!(opened.snippets[0].sql, "SELECT 1 FROM dual");
+        assert_eq!(opened.settings, archive.settings);
+        assert!(open_archive(&sealed, "wrong horse").is_err());
+        assert!(open_archive(b"PK\x03\x04", "correct horse").is_err());
+    }
+
+    #[test]
+    fn archives_asking_for_too_many_rounds_are_refused() {
+        let mut sealed = seal_archive(&WorkspaceArchive::default(), "correct horse", 10).unwrap();
+        let at = ARCHIVE_MAGIC.len();
+        sealed[at..at + 4].copy_from_slice(&(MAX_PBKDF2_ITERATIONS + 1).to_be_bytes());
+        assert!(open_archive(&sealed, "correct horse")
+            .unwrap_err()
+            .contains("invalid key setting"));
+    }
+
+    #[test]
+    fn colliding_profile_ids_are_remapped_with_their_references() {
+        let local = [
+            profile("profile-1", "local-db"),
+            profile("profile-2", "other"),
+        ];
+        let mut archive = WorkspaceArchive {
+            profiles: vec![
+                profile("profile-1", "remote-db"),
+                profile("profile-7", "kept"),
+            ],
+            secrets: vec![ArchivedSecrets {
+                profile_id: "profile-1".to_string(),
+                password: Some("remote-secret".to_string()),
+                ssh_password: None,
+            }],
+            pinned_objects: vec![DbPinnedObject {
+                profile_id: "profile-1".to_string(),
+                ..pinned("ORDERS")
+            }],
+            settings: Some(serde_json::json!({ "lastUsedConnectionProfileId": "profile-1" })),
+            ..WorkspaceArchive::default()
+        };
+        let mut next = 1;
+        remap_profile_ids(&mut archive, &local, || {
+            next += 1;
+            format!("profile-{next}")
+        });
+
+        let ids = archive
+            .profiles
+            .iter()
+            .map(|profile| profile.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["profile-3", "profile-7"]);
+        assert_eq!(archive.secrets[0].profile_id, "profile-3");
+        assert_eq!(archive.pinned_objects[0].profile_id, "profile-3");
+        assert_eq!(
+            archive.settings.unwrap()["lastUsedConnectionProfileId"],
+            "profile-3"
+        );
+    }
+
+    #[test]
+    fn merge_replaces_snippets_by_id_and_adds_pins_and_tags() {
+        let mut state = WorkspaceState {
+            snippets: vec![snippet("s1", "old"), snippet("s2", "kept")],
+            pinned_objects: vec![pinned("ORDERS")],
+            object_tags: vec![DbTaggedObject {
+                profile_id: "p1".to_string(),
+                schema: "HR".to_string(),
+                object_type: "TABLE".to_string(),
+                object_name: "ORDERS".to_string(),
+                tags: vec!["sales".to_string()],
+            }],
+            ..WorkspaceState::default()
+        };
+        let archive = WorkspaceArchive {
+            snippets: vec![snippet("s1", "new"), snippet("s3", "added")],
+            pinned_objects: vec![pinned("ORDERS"), pinned("LINES")],
+            object_tags: vec![DbTaggedObject {
+                profile_id: "p1".to_string(),
+                schema: "HR".to_string(),
+                object_type: "TABLE".to_string(),
+                object_name: "ORDERS".to_string(),
+                tags: vec!["billing".to_string(), "sales".to_string()],
+            }],
+            ..WorkspaceArchive::default()
+        };
+        merge_workspace(&mut state, &archive);
+
+        let snippets = state
+            .snippets
+            .iter()
+            .map(|snippet| (snippet.id.as_str(), snippet.sql.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(snippets, [("s1", "new"), ("s2", "kept"), ("s3", "added")]);
+        assert_eq!(state.pinned_objects.len(), 2);
+        assert_eq!(state.object_tags[0].tags, ["billing", "sales"]);
+    }
+}
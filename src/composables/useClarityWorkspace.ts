@@ -5,17 +5,33 @@ import {
   normalizeCreateObjectName,
   normalizeCreateObjectType,
 } from "../constants/createObjectTemplates";
+import { formatIdentifier } from "../constants/sqlIdent";
 import type {
   BusyState,
   ConnectionProfile,
+  DatabaseProvider,
+  DbSqlStatement,
   DbTransactionState,
   ObjectDetailTabDefinition,
   ObjectDetailTabId,
+  DbConnectionTestResult,
   DbObjectColumnEntry,
+  DbObjectDdl,
   DbObjectEntry,
+  DbQueryBind,
   DbQueryResult,
   DbSchemaSearchResult,
   DbSessionSummary,
+  DbConnectRetryOptions,
+  DbSessionReconnected,
+  DbSessionIdleDisconnected,
+  DbStateFileRecovery,
+  DbPinnedObject,
+  DbPinObjectRequest,
+  DbObjectTagRequest,
+  DbListTaggedObjectsRequest,
+  DbTaggedObject,
+  DbTestConnectionRequest,
   OracleConnectionProfile,
   OracleDbConnectRequest,
   SaveConnectionProfileRequest,
@@ -36,15 +52,6 @@ const OBJECT_DATA_ROW_ID_COLUMN = "__CLARITY_ROWID__";
 const DEFAULT_QUERY_ROW_LIMIT = 1000;
 const MAX_QUERY_ROW_LIMIT = 10000;
 const SCRIPT_LINE_HISTORY_LIMIT = 200;
-const NON_STANDALONE_SQL_KEYWORDS = new Set([
-  "END",
-  "EXCEPTION",
-  "WHEN",
-  "ELSE",
-  "ELSIF",
-  "LOOP",
-  "THEN",
-]);
 
 interface PersistedQuerySheet {
   id: string;
@@ -141,165 +148,18 @@ function isLikelyNumeric(value: string): boolean {
   return /^-?\d+(?:\.\d+)?(?:e[+-]?\d+)?$/i.test(normalized);
 }
 
-function extractLeadingSqlKeyword(sql: string): string | null {
-  const normalized = sql
-    .replace(/\/\*[\s\S]*?\*\//g, " ")
-    .replace(/--.*$/gm, " ")
-    .trimStart()
-    .toUpperCase();
-  const match = normalized.match(/^[A-Z]+/);
-  return match ? match[0] : null;
-}
-
-function removeSqlComments(sql: string): string {
-  return sql.replace(/\/\*[\s\S]*?\*\//g, " ").replace(/--.*$/gm, " ");
-}
-
-function isCommentOnlySqlFragment(sql: string): boolean {
-  return removeSqlComments(sql).trim().length === 0;
-}
-
-function normalizeStatementForExecution(sql: string): string {
-  const trimmed = sql.trim();
-  if (!trimmed) {
-    return "";
-  }
-
-  const leadingKeyword = extractLeadingSqlKeyword(trimmed);
-  if (leadingKeyword === "BEGIN" || leadingKeyword === "DECLARE") {
-    return trimmed;
-  }
-
-  return trimmed.replace(/;+\s*$/g, "").trimEnd();
-}
-
-function splitSqlStatements(sql: string): string[] {
-  const statements: string[] = [];
-  let buffer = "";
-  let inSingleQuote = false;
-  let inDoubleQuote = false;
-  let inLineComment = false;
-  let inBlockComment = false;
-
-  const pushBuffer = () => {
-    const statement = buffer.trim();
-    buffer = "";
-    if (!statement || isCommentOnlySqlFragment(statement)) {
-      return;
-    }
-    statements.push(statement);
-  };
-
-  for (let index = 0; index < sql.length; index += 1) {
-    const char = sql[index];
-    const nextChar = sql[index + 1] ?? "";
-
-    if (inLineComment) {
-      buffer += char;
-      if (char === "\n") {
-        inLineComment = false;
-      }
-      continue;
-    }
-
-    if (inBlockComment) {
-      buffer += char;
-      if (char === "*" && nextChar === "/") {
-        buffer += "/";
-        index += 1;
-        inBlockComment = false;
-      }
-      continue;
-    }
-
-    if (inSingleQuote) {
-      buffer += char;
-      if (char === "'" && nextChar === "'") {
-        buffer += "'";
-        index += 1;
-        continue;
-      }
-      if (char === "'") {
-        inSingleQuote = false;
-      }
-      continue;
-    }
-
-    if (inDoubleQuote) {
-      buffer += char;
-      if (char === '"' && nextChar === '"') {
-        buffer += '"';
-        index += 1;
-        continue;
-      }
-      if (char === '"') {
-        inDoubleQuote = false;
-      }
-      continue;
-    }
-
-    if (char === "-" && nextChar === "-") {
-      buffer += "--";
-      index += 1;
-      inLineComment = true;
-      continue;
-    }
-
-    if (char === "/" && nextChar === "*") {
-      buffer += "/*";
-      index += 1;
-      inBlockComment = true;
-      continue;
-    }
-
-    if (char === "'") {
-      buffer += char;
-      inSingleQuote = true;
-      continue;
-    }
-
-    if (char === '"') {
-      buffer += char;
-      inDoubleQuote = true;
-      continue;
-    }
-
-    if (char === ";") {
-      pushBuffer();
-      continue;
-    }
-
-    buffer += char;
-  }
-
-  pushBuffer();
-  return statements;
-}
-
-function splitQueryTextForExecution(sql: string): string[] {
-  const trimmed = sql.trim();
-  if (!trimmed) {
+async function splitQueryTextForExecution(
+  sql: string,
+  provider: DatabaseProvider,
+): Promise<string[]> {
+  if (!sql.trim()) {
     return [];
   }
 
-  const candidates = splitSqlStatements(trimmed)
-    .map((statement) => normalizeStatementForExecution(statement))
-    .filter((statement) => statement.length > 0);
-
-  if (candidates.length <= 1) {
-    const fallback = normalizeStatementForExecution(candidates[0] ?? trimmed);
-    return fallback ? [fallback] : [];
-  }
-
-  for (const statement of candidates) {
-    const keyword = extractLeadingSqlKeyword(statement);
-    if (!keyword || NON_STANDALONE_SQL_KEYWORDS.has(keyword)) {
-      const fallback = normalizeStatementForExecution(trimmed);
-      return fallback ? [fallback] : [];
-    }
-  }
-
-  return candidates;
+  const statements = await invoke<DbSqlStatement[]>("db_split_sql", {
+    request: { provider, sql },
+  });
+  return statements.map((statement) => statement.sql);
 }
 
 async function yieldUiFrame(): Promise<void> {
@@ -524,10 +384,12 @@ export function useClarityWorkspace() {
       schema: readDebugConnectionString(import.meta.env.VITE_ORACLE_SCHEMA, "HR"),
       oracleAuthMode: "normal",
     },
+    readerEndpoint: { host: "", serviceName: null },
   });
   const profileName = ref("");
   const selectedProfileId = ref("");
   const saveProfilePassword = ref(true);
+  const profileWarmStandby = ref(false);
   const initialQuerySheetState = readStoredQuerySheetState(
     connection.connection.schema,
   );
@@ -535,6 +397,8 @@ export function useClarityWorkspace() {
   const session = ref<DbSessionSummary | null>(null);
   const connectionProfiles = ref<ConnectionProfile[]>([]);
   const objects = ref<DbObjectEntry[]>([]);
+  const pinnedObjects = ref<DbPinnedObject[]>([]);
+  const objectTags = ref<DbTaggedObject[]>([]);
   const objectColumns = ref<DbObjectColumnEntry[]>([]);
   const selectedObject = ref<DbObjectEntry | null>(null);
   const ddlTabs = ref<WorkspaceDdlTab[]>([]);
@@ -559,6 +423,7 @@ export function useClarityWorkspace() {
       ),
     ),
   );
+  const querySampleMode = ref(false);
   const schemaSearchResults = ref<DbSchemaSearchResult[]>([]);
   const schemaSearchPerformed = ref(false);
   const transactionActive = ref(false);
@@ -576,6 +441,7 @@ export function useClarityWorkspace() {
     loadingProfiles: false,
     savingProfile: false,
     deletingProfile: false,
+    testingConnection: false,
     loadingProfileSecret: false,
     loadingObjects: false,
     loadingDdl: false,
@@ -848,8 +714,8 @@ export function useClarityWorkspace() {
     return getObjectDetailTabs(object).some((tab) => tab.id === tabId);
   }
 
-  function toQuotedIdentifier(name: string): string {
-    return `"${name.replace(/"/g, '""')}"`;
+  function toSqlIdentifier(name: string): string {
+    return formatIdentifier(session.value?.provider ?? "oracle", name);
   }
 
   function toSqlStringLiteral(value: string): string {
@@ -865,7 +731,7 @@ export function useClarityWorkspace() {
   }
 
   function buildQualifiedObjectName(object: DbObjectEntry): string {
-    return `${toQuotedIdentifier(object.schema)}.${toQuotedIdentifier(object.objectName)}`;
+    return `${toSqlIdentifier(object.schema)}.${toSqlIdentifier(object.objectName)}`;
   }
 
   function buildDropTableSql(
@@ -894,12 +760,12 @@ export function useClarityWorkspace() {
   }
 
   function buildObjectDataPreviewSql(object: DbObjectEntry): string {
-    const owner = `${toQuotedIdentifier(object.schema)}.${toQuotedIdentifier(object.objectName)}`;
+    const owner = `${toSqlIdentifier(object.schema)}.${toSqlIdentifier(object.objectName)}`;
     if (!isTableObject(object.objectType)) {
       return `select * from ${owner} fetch first ${OBJECT_DATA_PREVIEW_LIMIT} rows only`;
     }
 
-    return `select rowidtochar(t.rowid) as ${toQuotedIdentifier(OBJECT_DATA_ROW_ID_COLUMN)}, t.* from ${owner} t fetch first ${OBJECT_DATA_PREVIEW_LIMIT} rows only`;
+    return `select rowidtochar(t.rowid) as ${toSqlIdentifier(OBJECT_DATA_ROW_ID_COLUMN)}, t.* from ${owner} t fetch first ${OBJECT_DATA_PREVIEW_LIMIT} rows only`;
   }
 
   function buildObjectMetadataSql(object: DbObjectEntry): string {
@@ -1065,20 +931,130 @@ export function useClarityWorkspace() {
     return activeLocation;
   }
 
+  const lastBindValues = new Map<string, string>();
+
+  async function promptForBinds(sql: string): Promise<DbQueryBind[]> {
+    const names = await invoke<string[]>("db_list_bind_names", {
+      request: { sql },
+    });
+    const binds: DbQueryBind[] = [];
+    for (const name of names) {
+      const value = window.prompt(
+        `Value for :${name} (leave empty for NULL, CURSOR for an OUT ref cursor):`,
+        lastBindValues.get(name) ?? "",
+      );
+      if (value === null) {
+        throw new Error(`No value given for bind variable :${name}.`);
+      }
+      lastBindValues.set(name, value);
+      binds.push(
+        value.trim().toUpperCase() === "CURSOR"
+          ? { name, value: null, valueType: "refCursor" }
+          : { name, value: value === "" ? null : value },
+      );
+    }
+    return binds;
+  }
+
   async function runQueryForSession(
     sessionId: number,
     sql: string,
     rowLimit?: number,
+    sampleMode = false,
   ): Promise<DbQueryResult> {
-    const result = await invoke<DbQueryResult>("db_run_query", {
-      request: {
-        sessionId,
-        sql,
-        rowLimit,
-      },
-    });
-    await syncTransactionState(sessionId);
-    return result;
+    const binds =
+      session.value?.provider === "oracle" ? await promptForBinds(sql) : [];
+    const substitutions: Record<string, string> = {};
+    let allowDestructive = false;
+    for (;;) {
+      let result: DbQueryResult;
+      try {
+        result = await invoke<DbQueryResult>("db_run_query", {
+          request: {
+            sessionId,
+            sql,
+            rowLimit,
+            sampleMode,
+            binds,
+            substitutions,
+            allowDestructive,
+          },
+        });
+      } catch (error) {
+        const message = String(error);
+        if (
+          allowDestructive ||
+          !message.includes("allowDestructive") ||
+          !window.confirm(`${message}\n\nRun it anyway?`)
+        ) {
+          throw error;
+        }
+        allowDestructive = true;
+        continue;
+      }
+      if (!result.requiredVariables?.length) {
+        await syncTransactionState(sessionId);
+        return result;
+      }
+      promptForSubstitutions(result.requiredVariables, substitutions);
+    }
+  }
+
+  const lastSubstitutionValues = new Map<string, string>();
+
+  function promptForSubstitutions(
+    names: string[],
+    substitutions: Record<string, string>,
+  ): void {
+    for (const name of names) {
+      const key = name.toUpperCase();
+      const value = window.prompt(
+        `Value for &${name}:`,
+        lastSubstitutionValues.get(key) ?? "",
+      );
+      if (value === null) {
+        throw new Error(`No value given for substitution variable &${name}.`);
+      }
+      lastSubstitutionValues.set(key, value);
+      substitutions[name] = value;
+    }
+  }
+
+  async function handleSessionReconnected(
+    payload: DbSessionReconnected,
+  ): Promise<void> {
+    if (!session.value || payload.sessionId !== session.value.sessionId) {
+      return;
+    }
+
+    if (payload.reconnected) {
+      transactionActive.value = false;
+      statusMessage.value = payload.message;
+      return;
+    }
+
+    errorMessage.value = payload.message;
+    if (!payload.needsPassword) {
+      return;
+    }
+
+    const password = window.prompt(
+      `${payload.message}\n\nEnter the password to reconnect:`,
+    );
+    if (!password) {
+      return;
+    }
+
+    try {
+      await invoke("db_reconnect_session", {
+        request: { sessionId: payload.sessionId, password },
+      });
+      transactionActive.value = false;
+      errorMessage.value = "";
+      statusMessage.value = "Reconnected.";
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
   }
 
   async function syncTransactionState(sessionId: number): Promise<void> {
@@ -1317,6 +1293,154 @@ export function useClarityWorkspace() {
     }
   }
 
+  function findPinnedObject(object: DbObjectEntry): DbPinnedObject | undefined {
+    const objectType = object.objectType.trim().toUpperCase();
+    return pinnedObjects.value.find(
+      (pin) =>
+        pin.schema === object.schema &&
+        pin.objectType === objectType &&
+        pin.objectName === object.objectName,
+    );
+  }
+
+  function isObjectPinned(object: DbObjectEntry): boolean {
+    return !!findPinnedObject(object);
+  }
+
+  async function loadPinnedObjects(): Promise<void> {
+    if (!selectedProfileId.value) {
+      pinnedObjects.value = [];
+      return;
+    }
+
+    try {
+      pinnedObjects.value = await invoke<DbPinnedObject[]>(
+        "db_list_pinned_objects",
+        { request: { profileId: selectedProfileId.value } },
+      );
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
+  }
+
+  async function togglePinnedObject(object: DbObjectEntry): Promise<void> {
+    if (!selectedProfileId.value) {
+      statusMessage.value = "Save the connection as a profile to pin objects.";
+      return;
+    }
+
+    try {
+      const request: DbPinObjectRequest = {
+        profileId: selectedProfileId.value,
+        schema: object.schema,
+        objectType: object.objectType,
+        objectName: object.objectName,
+        pinned: !isObjectPinned(object),
+      };
+      pinnedObjects.value = await invoke<DbPinnedObject[]>("db_pin_object", {
+        request,
+      });
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
+  }
+
+  function findObjectTags(object: DbObjectEntry): string[] {
+    const objectType = object.objectType.trim().toUpperCase();
+    return (
+      objectTags.value.find(
+        (tagged) =>
+          tagged.schema === object.schema &&
+          tagged.objectType === objectType &&
+          tagged.objectName === object.objectName,
+      )?.tags ?? []
+    );
+  }
+
+  async function loadObjectTags(): Promise<void> {
+    if (!selectedProfileId.value) {
+      objectTags.value = [];
+      return;
+    }
+
+    try {
+      const request: DbListTaggedObjectsRequest = {
+        profileId: selectedProfileId.value,
+      };
+      objectTags.value = await invoke<DbTaggedObject[]>(
+        "db_list_tagged_objects",
+        { request },
+      );
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
+  }
+
+  async function editObjectTags(object: DbObjectEntry): Promise<void> {
+    if (!selectedProfileId.value) {
+      statusMessage.value = "Save the connection as a profile to tag objects.";
+      return;
+    }
+
+    const current = findObjectTags(object);
+    const input = window.prompt(
+      `Tags for ${object.objectName} (comma-separated)`,
+      current.join(", "),
+    );
+    if (input === null) {
+      return;
+    }
+
+    const next = [
+      ...new Set(
+        input
+          .split(",")
+          .map((tag) => tag.trim().toLowerCase())
+          .filter((tag) => tag.length > 0),
+      ),
+    ];
+    const changes = [
+      ...next
+        .filter((tag) => !current.includes(tag))
+        .map((tag) => ({ tag, command: "db_tag_object" })),
+      ...current
+        .filter((tag) => !next.includes(tag))
+        .map((tag) => ({ tag, command: "db_untag_object" })),
+    ];
+
+    try {
+      for (const change of changes) {
+        const request: DbObjectTagRequest = {
+          profileId: selectedProfileId.value,
+          schema: object.schema,
+          objectType: object.objectType,
+          objectName: object.objectName,
+          tag: change.tag,
+        };
+        await invoke<DbTaggedObject>(change.command, { request });
+      }
+      await loadObjectTags();
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
+  }
+
+  function openPinnedObject(pin: DbPinnedObject): void {
+    const loaded = objects.value.find(
+      (object) =>
+        object.schema === pin.schema &&
+        object.objectType.trim().toUpperCase() === pin.objectType &&
+        object.objectName === pin.objectName,
+    );
+    openObjectFromExplorer(
+      loaded ?? {
+        schema: pin.schema,
+        objectType: pin.objectType,
+        objectName: pin.objectName,
+      },
+    );
+  }
+
   function openObjectFromExplorer(object: DbObjectEntry): void {
     selectedObject.value = object;
     const tabId = buildDdlTabId(object);
@@ -1528,10 +1652,10 @@ export function useClarityWorkspace() {
     const setClauses = changedIndexes
       .map(
         (index) =>
-          `${toQuotedIdentifier(editableColumns[index])} = ${toSqlDataLiteral(values[index])}`,
+          `${toSqlIdentifier(editableColumns[index])} = ${toSqlDataLiteral(values[index])}`,
       )
       .join(", ");
-    const sql = `update ${toQuotedIdentifier(tab.object.schema)}.${toQuotedIdentifier(tab.object.objectName)} set ${setClauses} where rowidtochar(rowid) = ${toSqlStringLiteral(rowId)}`;
+    const sql = `update ${toSqlIdentifier(tab.object.schema)}.${toSqlIdentifier(tab.object.objectName)} set ${setClauses} where rowidtochar(rowid) = ${toSqlStringLiteral(rowId)}`;
 
     errorMessage.value = "";
     busy.updatingData = true;
@@ -1602,12 +1726,12 @@ export function useClarityWorkspace() {
     }
 
     const columnsSql = providedIndexes
-      .map((index) => toQuotedIdentifier(editableColumns[index]))
+      .map((index) => toSqlIdentifier(editableColumns[index]))
       .join(", ");
     const valuesSql = providedIndexes
       .map((index) => toSqlDataLiteral(values[index]))
       .join(", ");
-    const sql = `insert into ${toQuotedIdentifier(tab.object.schema)}.${toQuotedIdentifier(tab.object.objectName)} (${columnsSql}) values (${valuesSql})`;
+    const sql = `insert into ${toSqlIdentifier(tab.object.schema)}.${toSqlIdentifier(tab.object.objectName)} (${columnsSql}) values (${valuesSql})`;
 
     errorMessage.value = "";
     busy.updatingData = true;
@@ -1667,7 +1791,7 @@ export function useClarityWorkspace() {
       return false;
     }
 
-    const sql = `delete from ${toQuotedIdentifier(tab.object.schema)}.${toQuotedIdentifier(tab.object.objectName)} where rowidtochar(rowid) = ${toSqlStringLiteral(rowId)}`;
+    const sql = `delete from ${toSqlIdentifier(tab.object.schema)}.${toSqlIdentifier(tab.object.objectName)} where rowidtochar(rowid) = ${toSqlStringLiteral(rowId)}`;
 
     errorMessage.value = "";
     busy.updatingData = true;
@@ -1785,6 +1909,7 @@ export function useClarityWorkspace() {
 
     profileName.value = selectedProfile.value.name;
     saveProfilePassword.value = selectedProfile.value.hasPassword;
+    profileWarmStandby.value = selectedProfile.value.warmStandby ?? false;
   }
 
   async function applySelectedProfile(): Promise<void> {
@@ -1801,6 +1926,14 @@ export function useClarityWorkspace() {
     connection.connection.username = profile.connection.username;
     connection.connection.schema = profile.connection.schema;
     connection.connection.oracleAuthMode = profile.connection.oracleAuthMode;
+    connection.connection.oracleClientLibDir =
+      profile.connection.oracleClientLibDir ?? null;
+    connection.startupSql = profile.startupSql ?? null;
+    connection.readerEndpoint = {
+      host: profile.readerEndpoint?.host ?? "",
+      port: profile.readerEndpoint?.port ?? null,
+      serviceName: profile.readerEndpoint?.serviceName ?? null,
+    };
     connection.connection.password = "";
     syncSelectedProfileUi();
 
@@ -1848,7 +1981,14 @@ export function useClarityWorkspace() {
           username: connection.connection.username,
           schema: connection.connection.schema,
           oracleAuthMode: connection.connection.oracleAuthMode,
+          oracleClientLibDir:
+            connection.connection.oracleClientLibDir?.trim() || null,
         },
+        startupSql: connection.startupSql?.trim() || null,
+        warmStandby: profileWarmStandby.value,
+        readerEndpoint: connection.readerEndpoint?.host.trim()
+          ? connection.readerEndpoint
+          : null,
         savePassword: saveProfilePassword.value,
         password: saveProfilePassword.value
           ? connection.connection.password
@@ -1892,6 +2032,7 @@ export function useClarityWorkspace() {
       });
       selectedProfileId.value = "";
       profileName.value = "";
+      profileWarmStandby.value = false;
       await loadConnectionProfiles();
       statusMessage.value = `Deleted profile: ${profile.name}`;
     } catch (error) {
@@ -1901,6 +2042,35 @@ export function useClarityWorkspace() {
     }
   }
 
+  async function testConnection(
+    retryOptions: DbConnectRetryOptions = {},
+  ): Promise<void> {
+    errorMessage.value = "";
+    busy.testingConnection = true;
+
+    try {
+      const request: DbTestConnectionRequest = {
+        provider: connection.provider,
+        connection: { ...connection.connection },
+        startupSql: connection.startupSql,
+        profileId: selectedProfileId.value || null,
+        ...retryOptions,
+      };
+      const result = await invoke<DbConnectionTestResult>("db_test_connection", {
+        request,
+      });
+      const version = result.serverVersion ? ` Server ${result.serverVersion}.` : "";
+      statusMessage.value = `${result.message} Schema ${result.schema}.${version}`;
+    } catch (error) {
+      errorMessage.value = isDbConnectError(error)
+        ? error.message
+        : toErrorMessage(error);
+      statusMessage.value = `Connection test failed: ${errorMessage.value}`;
+    } finally {
+      busy.testingConnection = false;
+    }
+  }
+
   async function refreshObjects(): Promise<void> {
     if (!session.value) {
       return;
@@ -1930,19 +2100,26 @@ export function useClarityWorkspace() {
 
   async function connectOracle(
     oracleClientLibDirOverride?: string,
+    retryOptions: DbConnectRetryOptions = {},
   ): Promise<void> {
     errorMessage.value = "";
     oracleClientMissing.value = false;
     busy.connecting = true;
 
     try {
-      const oracleClientLibDir = oracleClientLibDirOverride?.trim();
+      const oracleClientLibDir =
+        connection.connection.oracleClientLibDir?.trim() ||
+        oracleClientLibDirOverride?.trim();
       const connectRequest: OracleDbConnectRequest = {
         provider: connection.provider,
         connection: {
           ...connection.connection,
           ...(oracleClientLibDir ? { oracleClientLibDir } : {}),
         },
+        startupSql: connection.startupSql,
+        readerEndpoint: connection.readerEndpoint,
+        profileId: selectedProfileId.value || null,
+        ...retryOptions,
       };
       const summary = await invoke<DbSessionSummary>("db_connect", {
         request: connectRequest,
@@ -1961,7 +2138,8 @@ export function useClarityWorkspace() {
       ) {
         firstQueryTab.queryText = buildDefaultSchemaQuery(summary.schema);
       }
-      statusMessage.value = `Connected: ${summary.displayName}`;
+      const instance = summary.instanceName ? ` (instance ${summary.instanceName})` : "";
+      statusMessage.value = `Connected: ${summary.displayName}${instance}`;
       await syncTransactionState(summary.sessionId);
       await refreshObjects();
     } catch (error) {
@@ -1994,27 +2172,79 @@ export function useClarityWorkspace() {
     } catch (error) {
       errorMessage.value = toErrorMessage(error);
     } finally {
-      session.value = null;
-      transactionActive.value = false;
-      objects.value = [];
-      objectColumns.value = [];
-      expandedObjectTypes.value = {};
-      ddlTabs.value = [];
-      activeWorkspaceTabId.value = queryTabs.value[0]?.id ?? FIRST_QUERY_TAB_ID;
-      selectedObject.value = null;
-      schemaSearchText.value = "";
-      exportDestinationDirectory.value = "";
-      selectedExportSessionId.value = null;
-      schemaSearchResults.value = [];
-      schemaSearchPerformed.value = false;
-      schemaSearchFocusToken.value = 0;
-      scriptLineBackHistory.value = [];
-      scriptLineForwardHistory.value = [];
-      currentScriptLineLocation.value = null;
+      clearSessionState();
       statusMessage.value = "Disconnected.";
     }
   }
 
+  async function reconnectSession(): Promise<void> {
+    if (!session.value || busy.connecting) {
+      return;
+    }
+
+    busy.connecting = true;
+    errorMessage.value = "";
+
+    try {
+      const result = await invoke<DbSessionReconnected>("db_reconnect", {
+        request: {
+          sessionId: session.value.sessionId,
+          profileId: selectedProfileId.value || null,
+        },
+      });
+      transactionActive.value = false;
+      statusMessage.value = result.message;
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+      statusMessage.value = `Reconnect failed: ${errorMessage.value}`;
+    } finally {
+      busy.connecting = false;
+    }
+  }
+
+  function handleSessionIdleDisconnected(payload: DbSessionIdleDisconnected): void {
+    if (!session.value || payload.sessionId !== session.value.sessionId) {
+      return;
+    }
+
+    clearSessionState();
+    statusMessage.value = payload.message;
+  }
+
+  function handleStateRecovered(recoveries: DbStateFileRecovery[]): void {
+    if (recoveries.length > 0) {
+      statusMessage.value = recoveries.map((recovery) => recovery.message).join(" ");
+    }
+  }
+
+  async function loadStateRecoveries(): Promise<void> {
+    try {
+      handleStateRecovered(await invoke<DbStateFileRecovery[]>("db_get_state_recoveries"));
+    } catch (error) {
+      statusMessage.value = `Failed to check state files: ${String(error)}`;
+    }
+  }
+
+  function clearSessionState(): void {
+    session.value = null;
+    transactionActive.value = false;
+    objects.value = [];
+    objectColumns.value = [];
+    expandedObjectTypes.value = {};
+    ddlTabs.value = [];
+    activeWorkspaceTabId.value = queryTabs.value[0]?.id ?? FIRST_QUERY_TAB_ID;
+    selectedObject.value = null;
+    schemaSearchText.value = "";
+    exportDestinationDirectory.value = "";
+    selectedExportSessionId.value = null;
+    schemaSearchResults.value = [];
+    schemaSearchPerformed.value = false;
+    schemaSearchFocusToken.value = 0;
+    scriptLineBackHistory.value = [];
+    scriptLineForwardHistory.value = [];
+    currentScriptLineLocation.value = null;
+  }
+
   async function saveActiveQuerySheetToDisk(): Promise<void> {
     const queryTab = activeQueryTab.value;
     if (!queryTab) {
@@ -2116,7 +2346,7 @@ export function useClarityWorkspace() {
     activateWorkspaceTab(tabId);
 
     try {
-      const ddl = await invoke<string>("db_get_object_ddl", {
+      const { ddl, reconstructed } = await invoke<DbObjectDdl>("db_get_object_ddl", {
         request: {
           sessionId: session.value.sessionId,
           schema: object.schema,
@@ -2150,7 +2380,9 @@ export function useClarityWorkspace() {
         );
         void ensureObjectDetailLoaded(objectTab, objectTab.activeDetailTabId);
       }
-      statusMessage.value = `Loaded DDL: ${object.schema}.${object.objectName}`;
+      statusMessage.value = reconstructed
+        ? `Loaded reconstructed DDL: ${object.schema}.${object.objectName} (DBMS_METADATA is not available)`
+        : `Loaded DDL: ${object.schema}.${object.objectName}`;
       return true;
     } catch (error) {
       errorMessage.value = toErrorMessage(error);
@@ -2212,6 +2444,8 @@ export function useClarityWorkspace() {
     }
   }
 
+  let queryCancelRequested = false;
+
   async function runQuery(selectedText?: string): Promise<void> {
     if (!session.value || !activeQueryTab.value) {
       return;
@@ -2227,7 +2461,16 @@ export function useClarityWorkspace() {
     const querySource = selectedText?.trim()
       ? selectedText
       : queryTab.queryText;
-    const statements = splitQueryTextForExecution(querySource);
+    let statements: string[];
+    try {
+      statements = await splitQueryTextForExecution(
+        querySource,
+        session.value.provider,
+      );
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+      return;
+    }
     if (!statements.length) {
       errorMessage.value = "Query cannot be empty.";
       return;
@@ -2241,19 +2484,35 @@ export function useClarityWorkspace() {
     );
     errorMessage.value = "";
     busy.runningQuery = true;
+    queryCancelRequested = false;
     let completedStatements = 0;
 
     try {
       for (let index = 0; index < statements.length; index += 1) {
+        if (queryCancelRequested) {
+          statusMessage.value = `Cancelled at statement ${completedStatements} of ${statements.length}.`;
+          return;
+        }
         const result = await runQueryForSession(
           sessionId,
           statements[index],
           effectiveRowLimit,
+          querySampleMode.value,
         );
 
         const pane = queryTab.resultPanes[index];
         if (pane) {
-          pane.queryResult = result;
+          const [cursor] = result.refCursors ?? [];
+          pane.queryResult =
+            cursor && !result.columns.length
+              ? {
+                  ...result,
+                  columns: cursor.columns,
+                  rows: cursor.rows,
+                  nullFlags: cursor.nullFlags,
+                  byteLengths: cursor.byteLengths,
+                }
+              : result;
           pane.errorMessage = "";
           pane.sourceSql = statements[index] ?? pane.sourceSql;
           pane.sourceSessionId = sessionId;
@@ -2282,6 +2541,7 @@ export function useClarityWorkspace() {
           rows: [],
           rowsAffected: null,
           message: `Execution failed: ${message}`,
+          lossyConversion: false,
         };
         queryTab.activeResultPaneId = failedPane.id;
       }
@@ -2296,6 +2556,21 @@ export function useClarityWorkspace() {
     }
   }
 
+  async function cancelQuery(): Promise<void> {
+    if (!session.value || !busy.runningQuery) {
+      return;
+    }
+
+    queryCancelRequested = true;
+    try {
+      await invoke<boolean>("db_cancel_query", {
+        request: { sessionId: session.value.sessionId },
+      });
+    } catch (error) {
+      errorMessage.value = toErrorMessage(error);
+    }
+  }
+
   async function beginTransaction(): Promise<void> {
     if (!session.value || busy.managingTransaction) {
       return;
@@ -2402,6 +2677,7 @@ export function useClarityWorkspace() {
             includeObjectNames: schemaSearchIncludeObjectNames.value,
             includeSource: schemaSearchIncludeSource.value,
             includeDdl: schemaSearchIncludeDdl.value,
+            profileId: selectedProfileId.value || null,
           },
         },
       );
@@ -2479,6 +2755,11 @@ export function useClarityWorkspace() {
     }
   }
 
+  watch(selectedProfileId, () => {
+    void loadPinnedObjects();
+    void loadObjectTags();
+  });
+
   watch(
     () => ({
       queryTabs: queryTabs.value.map((tab) => ({
@@ -2517,6 +2798,7 @@ export function useClarityWorkspace() {
     profileName,
     selectedProfileId,
     saveProfilePassword,
+    profileWarmStandby,
     session,
     connectionProfiles,
     selectedProfile,
@@ -2524,6 +2806,10 @@ export function useClarityWorkspace() {
     busy,
     isConnected,
     transactionActive,
+    handleSessionReconnected,
+    handleSessionIdleDisconnected,
+    handleStateRecovered,
+    loadStateRecoveries,
     connectedSchema,
     selectedProviderLabel,
     objectTree,
@@ -2547,6 +2833,7 @@ export function useClarityWorkspace() {
     activeQueryText,
     activeDdlText,
     queryRowLimit,
+    querySampleMode,
     schemaSearchText,
     schemaSearchIncludeObjectNames,
     schemaSearchIncludeSource,
@@ -2569,6 +2856,11 @@ export function useClarityWorkspace() {
     closeQueryTab,
     closeDdlTab,
     openObjectFromExplorer,
+    pinnedObjects,
+    isObjectPinned,
+    togglePinnedObject,
+    openPinnedObject,
+    editObjectTags,
     openCreateObjectTemplate,
     dropTableFromExplorer,
     activateObjectDetailTab,
@@ -2584,13 +2876,16 @@ export function useClarityWorkspace() {
     applySelectedProfile,
     saveConnectionProfile,
     deleteSelectedProfile,
+    testConnection,
     connectOracle,
     disconnectOracle,
+    reconnectSession,
     refreshObjects,
     saveActiveQuerySheetToDisk,
     saveAllQuerySheetsToDisk,
     saveDdl,
     runQuery,
+    cancelQuery,
     beginTransaction,
     commitTransaction,
     rollbackTransaction,
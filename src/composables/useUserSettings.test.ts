@@ -59,6 +59,12 @@ describe("useUserSettings", () => {
     expect(settings.value.uiFontSize).toBe(16);
     expect(settings.value.queryEditorFontSize).toBe(15);
     expect(settings.value.dataFontSize).toBe(11);
+    expect(settings.value.exportNullStyle).toBe("empty");
+    expect(settings.value.connectTimeoutSeconds).toBe(30);
+    expect(settings.value.connectAttempts).toBe(1);
+    expect(settings.value.keepAliveIntervalSeconds).toBe(300);
+    expect(settings.value.idleDisconnectMinutes).toBe(0);
+    expect(settings.value.connectionPoolSize).toBe(2);
     expect(settings.value.aiModel).toBe("gpt-4o-mini");
     expect(settings.value.aiEndpoint).toBe("https://api.openai.com/v1/chat/completions");
     expect(document.documentElement.getAttribute("data-theme")).toBe("light");
@@ -180,7 +186,10 @@ describe("useUserSettings", () => {
       updateQueryEditorFontSize,
       updateDataFontFamily,
       updateDataFontSize,
+      updateExportNullStyle,
       updateOracleClientLibDir,
+      updateConnectRetrySettings,
+      updateKeepAliveIntervalSeconds,
       updateAiSuggestionsEnabled,
       updateAiModel,
       updateAiEndpoint,
@@ -194,7 +203,10 @@ describe("useUserSettings", () => {
     updateQueryEditorFontSize(1);
     updateDataFontFamily(" Menlo ");
     updateDataFontSize(100);
+    updateExportNullStyle("backslashN");
     updateOracleClientLibDir(" /usr/local/oracle ");
+    updateConnectRetrySettings(0, 3, 250.4);
+    updateKeepAliveIntervalSeconds(0);
     updateAiSuggestionsEnabled(true);
     updateAiModel("gpt-4.1-mini");
     updateAiEndpoint("https://example.com/chat");
@@ -208,7 +220,12 @@ describe("useUserSettings", () => {
     expect(settings.value.queryEditorFontSize).toBe(10);
     expect(settings.value.dataFontFamily).toBe("Menlo");
     expect(settings.value.dataFontSize).toBe(24);
+    expect(settings.value.exportNullStyle).toBe("backslashN");
     expect(settings.value.oracleClientLibDir).toBe("/usr/local/oracle");
+    expect(settings.value.connectTimeoutSeconds).toBe(1);
+    expect(settings.value.connectAttempts).toBe(3);
+    expect(settings.value.connectRetryDelayMs).toBe(250);
+    expect(settings.value.keepAliveIntervalSeconds).toBe(0);
     expect(settings.value.aiSuggestionsEnabled).toBe(true);
     expect(settings.value.aiModel).toBe("gpt-4.1-mini");
     expect(settings.value.aiEndpoint).toBe("https://example.com/chat");
@@ -238,7 +255,10 @@ describe("useUserSettings", () => {
       updateQueryEditorFontSize,
       updateDataFontFamily,
       updateDataFontSize,
+      updateExportNullStyle,
       updateOracleClientLibDir,
+      updateConnectRetrySettings,
+      updateKeepAliveIntervalSeconds,
       updateAiSuggestionsEnabled,
       updateAiModel,
       updateAiEndpoint,
@@ -255,7 +275,14 @@ describe("useUserSettings", () => {
     updateQueryEditorFontSize(settings.value.queryEditorFontSize);
     updateDataFontFamily(settings.value.dataFontFamily);
     updateDataFontSize(settings.value.dataFontSize);
+    updateExportNullStyle(settings.value.exportNullStyle);
     updateOracleClientLibDir(` ${settings.value.oracleClientLibDir} `);
+    updateConnectRetrySettings(
+      settings.value.connectTimeoutSeconds,
+      settings.value.connectAttempts,
+      settings.value.connectRetryDelayMs,
+    );
+    updateKeepAliveIntervalSeconds(settings.value.keepAliveIntervalSeconds);
     updateAiSuggestionsEnabled(settings.value.aiSuggestionsEnabled);
     updateAiModel(` ${settings.value.aiModel} `);
     updateAiEndpoint(` ${settings.value.aiEndpoint} `);
@@ -1,4 +1,5 @@
 import { computed, ref, watch } from "vue";
+import type { CsvNullStyle, CsvQuoteStyle } from "../types/clarity";
 import type { KeyBindings, ThemeSetting, UserSettings } from "../types/settings";
 import { DEFAULT_KEY_BINDINGS, normalizeKeyBindings } from "./useKeyBindings";
 
@@ -11,6 +12,12 @@ const QUERY_EDITOR_FONT_FAMILY_DEFAULT = 'Consolas, "Courier New", monospace';
 const QUERY_EDITOR_FONT_SIZE_DEFAULT = 15;
 const DATA_FONT_FAMILY_DEFAULT = 'Consolas, "Courier New", monospace';
 const DATA_FONT_SIZE_DEFAULT = 11;
+const CONNECT_TIMEOUT_SECONDS_DEFAULT = 30;
+const CONNECT_ATTEMPTS_DEFAULT = 1;
+const CONNECT_RETRY_DELAY_MS_DEFAULT = 1000;
+const KEEP_ALIVE_INTERVAL_SECONDS_DEFAULT = 300;
+const CONNECTION_POOL_SIZE_DEFAULT = 2;
+const IDLE_DISCONNECT_MINUTES_DEFAULT = 0;
 const DEFAULT_USER_SETTINGS: UserSettings = {
   theme: "light",
   uiFontFamily: UI_FONT_FAMILY_DEFAULT,
@@ -19,7 +26,16 @@ const DEFAULT_USER_SETTINGS: UserSettings = {
   queryEditorFontSize: QUERY_EDITOR_FONT_SIZE_DEFAULT,
   dataFontFamily: DATA_FONT_FAMILY_DEFAULT,
   dataFontSize: DATA_FONT_SIZE_DEFAULT,
+  exportNullStyle: "empty",
+  exportDelimiter: ",",
+  exportQuoteStyle: "minimal",
   oracleClientLibDir: "",
+  connectTimeoutSeconds: CONNECT_TIMEOUT_SECONDS_DEFAULT,
+  connectAttempts: CONNECT_ATTEMPTS_DEFAULT,
+  connectRetryDelayMs: CONNECT_RETRY_DELAY_MS_DEFAULT,
+  keepAliveIntervalSeconds: KEEP_ALIVE_INTERVAL_SECONDS_DEFAULT,
+  idleDisconnectMinutes: IDLE_DISCONNECT_MINUTES_DEFAULT,
+  connectionPoolSize: CONNECTION_POOL_SIZE_DEFAULT,
   aiSuggestionsEnabled: false,
   aiModel: "gpt-4o-mini",
   aiEndpoint: "https://api.openai.com/v1/chat/completions",
@@ -31,6 +47,18 @@ function isThemeSetting(value: unknown): value is ThemeSetting {
   return value === "light" || value === "dark";
 }
 
+function isCsvNullStyle(value: unknown): value is CsvNullStyle {
+  return value === "empty" || value === "null" || value === "backslashN";
+}
+
+function isCsvQuoteStyle(value: unknown): value is CsvQuoteStyle {
+  return value === "minimal" || value === "all" || value === "never";
+}
+
+function isCsvDelimiter(value: unknown): value is string {
+  return value === "," || value === ";" || value === "\t" || value === "|";
+}
+
 function normalizeFontFamily(value: unknown, fallback: string): string {
   if (typeof value !== "string") {
     return fallback;
@@ -40,7 +68,7 @@ function normalizeFontFamily(value: unknown, fallback: string): string {
   return normalized.length > 0 ? normalized : fallback;
 }
 
-function normalizeFontSize(
+function normalizeBoundedInteger(
   value: unknown,
   fallback: number,
   min: number,
@@ -60,6 +88,35 @@ function normalizeFontSize(
   return Math.min(max, Math.max(min, rounded));
 }
 
+function normalizeConnectTimeoutSeconds(value: unknown): number {
+  return normalizeBoundedInteger(value, CONNECT_TIMEOUT_SECONDS_DEFAULT, 1, 600);
+}
+
+function normalizeConnectAttempts(value: unknown): number {
+  return normalizeBoundedInteger(value, CONNECT_ATTEMPTS_DEFAULT, 1, 10);
+}
+
+function normalizeConnectRetryDelayMs(value: unknown): number {
+  return normalizeBoundedInteger(value, CONNECT_RETRY_DELAY_MS_DEFAULT, 0, 60000);
+}
+
+function normalizeKeepAliveIntervalSeconds(value: unknown): number {
+  return normalizeBoundedInteger(
+    value,
+    KEEP_ALIVE_INTERVAL_SECONDS_DEFAULT,
+    0,
+    3600,
+  );
+}
+
+function normalizeIdleDisconnectMinutes(value: unknown): number {
+  return normalizeBoundedInteger(value, IDLE_DISCONNECT_MINUTES_DEFAULT, 0, 1440);
+}
+
+function normalizeConnectionPoolSize(value: unknown): number {
+  return normalizeBoundedInteger(value, CONNECTION_POOL_SIZE_DEFAULT, 1, 8);
+}
+
 function normalizeUserSettings(value: unknown): UserSettings {
   if (typeof value !== "object" || value === null) {
     return { ...DEFAULT_USER_SETTINGS };
@@ -80,7 +137,7 @@ function normalizeUserSettings(value: unknown): UserSettings {
       raw.uiFontFamily,
       DEFAULT_USER_SETTINGS.uiFontFamily,
     ),
-    uiFontSize: normalizeFontSize(
+    uiFontSize: normalizeBoundedInteger(
       raw.uiFontSize,
       DEFAULT_USER_SETTINGS.uiFontSize,
       12,
@@ -90,7 +147,7 @@ function normalizeUserSettings(value: unknown): UserSettings {
       raw.queryEditorFontFamily,
       DEFAULT_USER_SETTINGS.queryEditorFontFamily,
     ),
-    queryEditorFontSize: normalizeFontSize(
+    queryEditorFontSize: normalizeBoundedInteger(
       raw.queryEditorFontSize,
       DEFAULT_USER_SETTINGS.queryEditorFontSize,
       10,
@@ -100,13 +157,30 @@ function normalizeUserSettings(value: unknown): UserSettings {
       raw.dataFontFamily,
       DEFAULT_USER_SETTINGS.dataFontFamily,
     ),
-    dataFontSize: normalizeFontSize(
+    dataFontSize: normalizeBoundedInteger(
       raw.dataFontSize,
       DEFAULT_USER_SETTINGS.dataFontSize,
       9,
       24,
     ),
+    exportNullStyle: isCsvNullStyle(raw.exportNullStyle)
+      ? raw.exportNullStyle
+      : DEFAULT_USER_SETTINGS.exportNullStyle,
+    exportDelimiter: isCsvDelimiter(raw.exportDelimiter)
+      ? raw.exportDelimiter
+      : DEFAULT_USER_SETTINGS.exportDelimiter,
+    exportQuoteStyle: isCsvQuoteStyle(raw.exportQuoteStyle)
+      ? raw.exportQuoteStyle
+      : DEFAULT_USER_SETTINGS.exportQuoteStyle,
     oracleClientLibDir: normalizedOracleClientLibDir,
+    connectTimeoutSeconds: normalizeConnectTimeoutSeconds(raw.connectTimeoutSeconds),
+    connectAttempts: normalizeConnectAttempts(raw.connectAttempts),
+    connectRetryDelayMs: normalizeConnectRetryDelayMs(raw.connectRetryDelayMs),
+    keepAliveIntervalSeconds: normalizeKeepAliveIntervalSeconds(
+      raw.keepAliveIntervalSeconds,
+    ),
+    idleDisconnectMinutes: normalizeIdleDisconnectMinutes(raw.idleDisconnectMinutes),
+    connectionPoolSize: normalizeConnectionPoolSize(raw.connectionPoolSize),
     aiSuggestionsEnabled:
       typeof raw.aiSuggestionsEnabled === "boolean"
         ? raw.aiSuggestionsEnabled
@@ -241,7 +315,7 @@ export function useUserSettings() {
   }
 
   function updateUiFontSize(value: number): void {
-    const normalized = normalizeFontSize(
+    const normalized = normalizeBoundedInteger(
       value,
       DEFAULT_USER_SETTINGS.uiFontSize,
       12,
@@ -273,7 +347,7 @@ export function useUserSettings() {
   }
 
   function updateQueryEditorFontSize(value: number): void {
-    const normalized = normalizeFontSize(
+    const normalized = normalizeBoundedInteger(
       value,
       DEFAULT_USER_SETTINGS.queryEditorFontSize,
       10,
@@ -305,7 +379,7 @@ export function useUserSettings() {
   }
 
   function updateDataFontSize(value: number): void {
-    const normalized = normalizeFontSize(
+    const normalized = normalizeBoundedInteger(
       value,
       DEFAULT_USER_SETTINGS.dataFontSize,
       9,
@@ -321,6 +395,95 @@ export function useUserSettings() {
     };
   }
 
+  function updateExportNullStyle(value: CsvNullStyle): void {
+    if (settings.value.exportNullStyle === value) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      exportNullStyle: value,
+    };
+  }
+
+  function updateExportCsvFormat(delimiter: string, quoteStyle: CsvQuoteStyle): void {
+    if (!isCsvDelimiter(delimiter) || !isCsvQuoteStyle(quoteStyle)) {
+      return;
+    }
+    if (
+      settings.value.exportDelimiter === delimiter &&
+      settings.value.exportQuoteStyle === quoteStyle
+    ) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      exportDelimiter: delimiter,
+      exportQuoteStyle: quoteStyle,
+    };
+  }
+
+  function updateConnectRetrySettings(
+    timeoutSeconds: number,
+    attempts: number,
+    retryDelayMs: number,
+  ): void {
+    const connectTimeoutSeconds = normalizeConnectTimeoutSeconds(timeoutSeconds);
+    const connectAttempts = normalizeConnectAttempts(attempts);
+    const connectRetryDelayMs = normalizeConnectRetryDelayMs(retryDelayMs);
+    if (
+      settings.value.connectTimeoutSeconds === connectTimeoutSeconds &&
+      settings.value.connectAttempts === connectAttempts &&
+      settings.value.connectRetryDelayMs === connectRetryDelayMs
+    ) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      connectTimeoutSeconds,
+      connectAttempts,
+      connectRetryDelayMs,
+    };
+  }
+
+  function updateKeepAliveIntervalSeconds(value: number): void {
+    const normalized = normalizeKeepAliveIntervalSeconds(value);
+    if (settings.value.keepAliveIntervalSeconds === normalized) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      keepAliveIntervalSeconds: normalized,
+    };
+  }
+
+  function updateIdleDisconnectMinutes(value: number): void {
+    const normalized = normalizeIdleDisconnectMinutes(value);
+    if (settings.value.idleDisconnectMinutes === normalized) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      idleDisconnectMinutes: normalized,
+    };
+  }
+
+  function updateConnectionPoolSize(value: number): void {
+    const normalized = normalizeConnectionPoolSize(value);
+    if (settings.value.connectionPoolSize === normalized) {
+      return;
+    }
+
+    settings.value = {
+      ...settings.value,
+      connectionPoolSize: normalized,
+    };
+  }
+
   function updateAiSuggestionsEnabled(value: boolean): void {
     if (settings.value.aiSuggestionsEnabled === value) {
       return;
@@ -378,6 +541,10 @@ export function useUserSettings() {
     };
   }
 
+  function replaceSettings(value: unknown): void {
+    settings.value = normalizeUserSettings(value);
+  }
+
   return {
     settings,
     theme,
@@ -388,11 +555,18 @@ export function useUserSettings() {
     updateQueryEditorFontSize,
     updateDataFontFamily,
     updateDataFontSize,
+    updateExportNullStyle,
+    updateExportCsvFormat,
     updateOracleClientLibDir,
+    updateConnectRetrySettings,
+    updateKeepAliveIntervalSeconds,
+    updateIdleDisconnectMinutes,
+    updateConnectionPoolSize,
     updateAiSuggestionsEnabled,
     updateAiModel,
     updateAiEndpoint,
     updateLastUsedConnectionProfileId,
     updateKeyBindings,
+    replaceSettings,
   };
 }
@@ -0,0 +1,27 @@
+import { describe, expect, it } from "vitest";
+import { formatIdentifier, qualifiedName, quoteIdentifier } from "./sqlIdent";
+
+describe("sqlIdent", () => {
+  it("quotes identifiers with each provider's quote character", () => {
+    expect(quoteIdentifier("oracle", 'A"B')).toBe('"A""B"');
+    expect(quoteIdentifier("mysql", "a`b")).toBe("`a``b`");
+    expect(quoteIdentifier("clickhouse", "a`b")).toBe("`a\\`b`");
+  });
+
+  it("quotes only names the provider would fold or reject", () => {
+    expect(formatIdentifier("oracle", "ORDER_ID")).toBe("ORDER_ID");
+    expect(formatIdentifier("oracle", "order_id")).toBe('"order_id"');
+    expect(formatIdentifier("oracle", "DATE")).toBe('"DATE"');
+    expect(formatIdentifier("postgres", "order_id")).toBe("order_id");
+    expect(formatIdentifier("postgres", "OrderId")).toBe('"OrderId"');
+    expect(formatIdentifier("mysql", "OrderId")).toBe("OrderId");
+    expect(formatIdentifier("mysql", "123")).toBe("`123`");
+  });
+
+  it("qualifies names with an optional schema", () => {
+    expect(qualifiedName("oracle", "APP", "Order Lines")).toBe(
+      'APP."Order Lines"',
+    );
+    expect(qualifiedName("postgres", " ", "t")).toBe("t");
+  });
+});
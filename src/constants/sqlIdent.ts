@@ -0,0 +1,125 @@
+import type { DatabaseProvider } from "../types/clarity";
+
+// Mirrors src-tauri/src/sql_ident.rs so SQL built in the UI quotes names like the backend.
+
+const ORACLE_RESERVED_WORDS = `
+  ACCESS ADD ALL ALTER AND ANY AS ASC AUDIT BETWEEN BY CHAR CHECK CLUSTER COLUMN COMMENT
+  COMPRESS CONNECT CREATE CURRENT DATE DECIMAL DEFAULT DELETE DESC DISTINCT DROP ELSE
+  EXCLUSIVE EXISTS FILE FLOAT FOR FROM GRANT GROUP HAVING IDENTIFIED IMMEDIATE IN INCREMENT
+  INDEX INITIAL INSERT INTEGER INTERSECT INTO IS LEVEL LIKE LOCK LONG MAXEXTENTS MINUS MODE
+  MODIFY NOAUDIT NOCOMPRESS NOT NOWAIT NULL NUMBER OF OFFLINE ON ONLINE OPTION OR ORDER
+  PCTFREE PRIOR PUBLIC RAW RENAME RESOURCE REVOKE ROW ROWID ROWNUM ROWS SELECT SESSION SET
+  SHARE SIZE SMALLINT START SYNONYM SYSDATE TABLE THEN TO TRIGGER UID UNION UNIQUE UPDATE
+  USER VALIDATE VALUES VARCHAR VARCHAR2 VIEW WHENEVER WHERE WITH`;
+
+const POSTGRES_RESERVED_WORDS = `
+  all analyse analyze and any array as asc asymmetric both case cast check collate column
+  constraint create current_date current_role current_time current_timestamp current_user
+  default deferrable desc distinct do else end except false fetch for foreign from grant
+  group having in initially intersect into lateral leading limit localtime localtimestamp
+  not null offset on only or order placing primary references returning select session_user
+  some symmetric table then to trailing true union unique user using variadic when where
+  window with`;
+
+const MYSQL_RESERVED_WORDS = `
+  ADD ALL ALTER AND AS ASC BETWEEN BY CASE CHECK COLUMN CONDITION CONSTRAINT CREATE CROSS
+  DATABASE DEFAULT DELETE DESC DISTINCT DROP ELSE EXISTS FALSE FOR FOREIGN FROM GROUP HAVING
+  IN INDEX INSERT INTERVAL INTO IS JOIN KEY KEYS LEFT LIKE LIMIT LOCK NOT NULL ON OR ORDER
+  PRIMARY RANGE READ REFERENCES RENAME REPLACE RIGHT ROW ROWS SELECT SET SHOW TABLE THEN TO
+  TRUE UNION UNIQUE UPDATE USAGE USE USING VALUES WHEN WHERE WITH WRITE`;
+
+const SNOWFLAKE_RESERVED_WORDS = `
+  ALL ALTER AND ANY AS BETWEEN BY CASE CAST CHECK COLUMN CONNECT CONSTRAINT CREATE CROSS
+  CURRENT CURRENT_DATE CURRENT_TIME CURRENT_TIMESTAMP CURRENT_USER DELETE DISTINCT DROP ELSE
+  EXISTS FALSE FOLLOWING FOR FROM FULL GRANT GROUP HAVING ILIKE IN INCREMENT INNER INSERT
+  INTERSECT INTO IS JOIN LATERAL LEFT LIKE LOCALTIME LOCALTIMESTAMP MINUS NATURAL NOT NULL
+  OF ON OR ORDER QUALIFY REGEXP REVOKE RIGHT RLIKE ROW ROWS SAMPLE SELECT SET SOME START
+  TABLE TABLESAMPLE THEN TO TRIGGER TRUE TRY_CAST UNION UNIQUE UPDATE USING VALUES VIEW
+  WHEN WHENEVER WHERE WITH`;
+
+const CLICKHOUSE_RESERVED_WORDS = `
+  ALL ANY ARRAY AS ASC BETWEEN BY CASE CROSS DESC DISTINCT ELSE END EXCEPT FINAL FORMAT
+  FROM FULL GLOBAL GROUP HAVING IN INNER INTERSECT INTO JOIN LEFT LIKE LIMIT NOT NULL OFFSET
+  ON OR ORDER PREWHERE RIGHT SAMPLE SELECT SETTINGS THEN UNION USING WHEN WHERE WITH`;
+
+const SQLITE_RESERVED_WORDS = `
+  ADD ALL ALTER AND AS AUTOINCREMENT BETWEEN CASE CHECK COLLATE COMMIT CONSTRAINT CREATE
+  DEFAULT DEFERRABLE DELETE DISTINCT DROP ELSE ESCAPE EXCEPT EXISTS FOREIGN FROM GROUP
+  HAVING IN INDEX INSERT INTERSECT INTO IS ISNULL JOIN LIMIT NOT NOTNULL NULL ON OR ORDER
+  PRIMARY REFERENCES SELECT SET TABLE THEN TO TRANSACTION UNION UNIQUE UPDATE USING VALUES
+  WHEN WHERE`;
+
+const RESERVED_WORDS: Record<DatabaseProvider, Set<string>> = {
+  oracle: toWordSet(ORACLE_RESERVED_WORDS),
+  snowflake: toWordSet(SNOWFLAKE_RESERVED_WORDS),
+  postgres: toWordSet(POSTGRES_RESERVED_WORDS),
+  duckdb: toWordSet(POSTGRES_RESERVED_WORDS),
+  external: toWordSet(POSTGRES_RESERVED_WORDS),
+  mysql: toWordSet(MYSQL_RESERVED_WORDS),
+  sqlite: toWordSet(SQLITE_RESERVED_WORDS),
+  libsql: toWordSet(SQLITE_RESERVED_WORDS),
+  clickhouse: toWordSet(CLICKHOUSE_RESERVED_WORDS),
+};
+
+const PLAIN_IDENTIFIER: Record<DatabaseProvider, RegExp> = {
+  oracle: /^[A-Z][A-Z0-9_$#]*$/,
+  snowflake: /^[A-Z_][A-Z0-9_$]*$/,
+  postgres: /^[a-z_][a-z0-9_$]*$/,
+  mysql: /^[A-Za-z0-9_$]+$/,
+  sqlite: /^[A-Za-z_][A-Za-z0-9_]*$/,
+  libsql: /^[A-Za-z_][A-Za-z0-9_]*$/,
+  duckdb: /^[A-Za-z_][A-Za-z0-9_]*$/,
+  clickhouse: /^[A-Za-z_][A-Za-z0-9_]*$/,
+  external: /^[A-Za-z_][A-Za-z0-9_]*$/,
+};
+
+function toWordSet(words: string): Set<string> {
+  return new Set(
+    words
+      .split(/\s+/)
+      .filter(Boolean)
+      .map((word) => word.toUpperCase()),
+  );
+}
+
+export function quoteIdentifier(
+  provider: DatabaseProvider,
+  identifier: string,
+): string {
+  if (provider === "mysql") {
+    return `\`${identifier.replace(/`/g, "``")}\``;
+  }
+  if (provider === "clickhouse") {
+    return `\`${identifier.replace(/\\/g, "\\\\").replace(/`/g, "\\`")}\``;
+  }
+  return `"${identifier.replace(/"/g, '""')}"`;
+}
+
+function needsQuoting(provider: DatabaseProvider, identifier: string): boolean {
+  const shapeIsPlain =
+    PLAIN_IDENTIFIER[provider].test(identifier) &&
+    !(provider === "mysql" && /^[0-9]+$/.test(identifier));
+  return (
+    !shapeIsPlain || RESERVED_WORDS[provider].has(identifier.toUpperCase())
+  );
+}
+
+export function formatIdentifier(
+  provider: DatabaseProvider,
+  identifier: string,
+): string {
+  return needsQuoting(provider, identifier)
+    ? quoteIdentifier(provider, identifier)
+    : identifier;
+}
+
+export function qualifiedName(
+  provider: DatabaseProvider,
+  schema: string | null,
+  name: string,
+): string {
+  const trimmedSchema = schema?.trim() ?? "";
+  return trimmedSchema
+    ? `${formatIdentifier(provider, trimmedSchema)}.${formatIdentifier(provider, name)}`
+    : formatIdentifier(provider, name);
+}
@@ -1,6 +1,65 @@
-export type DatabaseProvider = "oracle" | "postgres" | "mysql" | "sqlite";
+export type DatabaseProvider =
+  | "oracle"
+  | "postgres"
+  | "mysql"
+  | "sqlite"
+  | "duckdb"
+  | "snowflake"
+  | "clickhouse"
+  | "libsql"
+  | "external";
 export type OracleAuthMode = "normal" | "sysdba";
 
+export type OracleProtocol = "tcp" | "tcps";
+
+export interface OracleWalletRef {
+  walletName: string;
+  serviceAlias: string;
+}
+
+export interface OracleLdapNaming {
+  name: string;
+  directoryServers?: string[];
+  defaultAdminContext?: string | null;
+  tnsAdmin?: string | null;
+}
+
+export interface OracleTnsAliasRef {
+  alias: string;
+  tnsAdmin?: string | null;
+}
+
+export interface OracleExternalPasswordStore {
+  alias: string;
+  walletDir: string;
+}
+
+export interface OracleAddress {
+  host: string;
+  port?: number | null;
+}
+
+export type OracleTafType = "select" | "session";
+
+export interface OracleTafOptions {
+  failoverType?: OracleTafType;
+  retries?: number | null;
+  delaySecs?: number | null;
+}
+
+export interface OracleFailoverOptions {
+  loadBalance?: boolean;
+  retryCount?: number | null;
+  retryDelaySecs?: number | null;
+  taf?: OracleTafOptions | null;
+}
+
+export interface OracleTlsOptions {
+  walletLocation?: string | null;
+  serverCertDn?: string | null;
+  serverDnMatch?: boolean | null;
+}
+
 export interface OracleConnectionOptions {
   host: string;
   port?: number;
@@ -8,162 +67,1473 @@ export interface OracleConnectionOptions {
   username: string;
   schema: string;
   oracleAuthMode: OracleAuthMode;
+  proxyUser?: string | null;
+  edition?: string | null;
+  protocol?: OracleProtocol;
+  tls?: OracleTlsOptions | null;
+  wallet?: OracleWalletRef | null;
+  tnsAlias?: OracleTnsAliasRef | null;
+  ldap?: OracleLdapNaming | null;
+  failoverHosts?: OracleAddress[];
+  failover?: OracleFailoverOptions | null;
+  connectDescriptor?: string | null;
+  externalPasswordStore?: OracleExternalPasswordStore | null;
+  oracleClientLibDir?: string | null;
+}
+
+export interface OracleConnectOptions extends OracleConnectionOptions {
+  password: string;
+}
+
+export interface NetworkConnectionOptions {
+  host: string;
+  port?: number;
+  database: string;
+  username: string;
+  schema?: string;
+}
+
+export interface NetworkConnectOptions extends NetworkConnectionOptions {
+  password: string;
+}
+
+export interface SqliteConnectionOptions {
+  filePath: string;
+}
+
+export interface DuckdbConnectionOptions {
+  filePath: string;
+  cliPath?: string | null;
+}
+
+export type SnowflakeTokenType = "programmaticAccessToken" | "oauth";
+
+export interface SnowflakeConnectionOptions {
+  account: string;
+  username: string;
+  database: string;
+  schema?: string | null;
+  warehouse?: string | null;
+  role?: string | null;
+  tokenType?: SnowflakeTokenType;
+}
+
+export interface SnowflakeConnectOptions extends SnowflakeConnectionOptions {
+  token: string;
+}
+
+export interface LibsqlConnectionOptions {
+  url: string;
+}
+
+export interface LibsqlConnectOptions extends LibsqlConnectionOptions {
+  authToken?: string;
+}
+
+export interface ExternalConnectionOptions {
+  providerId: string;
+  options?: Record<string, unknown>;
+}
+
+export interface ExternalConnectOptions extends ExternalConnectionOptions {
+  password?: string;
+}
+
+export interface DbExternalProvider {
+  id: string;
+  name: string;
+  dialect?: string | null;
+  command: string;
+  args: string[];
+}
+
+export interface SshTunnelOptions {
+  host: string;
+  port?: number | null;
+  username: string;
+  identityFile?: string | null;
+  cliPath?: string | null;
+}
+
+export interface SshTunnelConnectOptions extends SshTunnelOptions {
+  password?: string | null;
+}
+
+export type TimestampTzMode = "original" | "session" | "utc";
+
+export interface DbSetSessionNlsRequest {
+  sessionId: number;
+  dateFormat?: string | null;
+  timestampFormat?: string | null;
+  numericCharacters?: string | null;
+}
+
+export interface DbSessionNls {
+  dateFormat: string;
+  timestampFormat: string;
+  numericCharacters: string;
+}
+
+export interface DbSetResourceLimitsRequest {
+  sessionId: number;
+  consumerGroup?: string | null;
+  callTimeoutSecs?: number | null;
+}
+
+export interface DbResourceLimits {
+  consumerGroup: string | null;
+  originalConsumerGroup: string | null;
+  callTimeoutSecs: number | null;
+}
+
+export interface DbSetContainerRequest {
+  sessionId: number;
+  container: string;
+}
+
+export interface DbContainer {
+  containerId: number;
+  name: string;
+  openMode: string | null;
+  restricted: boolean;
+}
+
+export type DbConnectRequest = (
+  | { provider: "oracle"; connection: OracleConnectOptions }
+  | { provider: "postgres"; connection: NetworkConnectOptions }
+  | { provider: "mysql"; connection: NetworkConnectOptions }
+  | { provider: "sqlite"; connection: SqliteConnectionOptions }
+  | { provider: "duckdb"; connection: DuckdbConnectionOptions }
+  | { provider: "snowflake"; connection: SnowflakeConnectOptions }
+  | { provider: "clickhouse"; connection: NetworkConnectOptions }
+  | { provider: "libsql"; connection: LibsqlConnectOptions }
+  | { provider: "external"; connection: ExternalConnectOptions }
+) & {
+  sshTunnel?: SshTunnelConnectOptions | null;
+  timestampTzMode?: TimestampTzMode;
+  prefetchAiSchemaContext?: boolean;
+  readOnly?: boolean;
+  environment?: string | null;
+  dropAllowlist?: string[];
+  connectTimeoutSecs?: number | null;
+  connectAttempts?: number | null;
+  retryDelayMillis?: number | null;
+  startupSql?: string | null;
+  poolSize?: number | null;
+  profileId?: string | null;
+  readerEndpoint?: DbReaderEndpoint | null;
+};
+
+export interface DbReaderEndpoint {
+  host: string;
+  port?: number | null;
+  serviceName?: string | null;
+}
+
+export type DbTestConnectionRequest = DbConnectRequest & {
+  profileId?: string | null;
+};
+
+export interface DbConnectionTestResult {
+  provider: DatabaseProvider;
+  displayName: string;
+  schema: string;
+  serverVersion: string | null;
+  instanceName: string | null;
+  connectMillis: number;
+  latencyMillis: number | null;
+  message: string;
+}
+
+export type OracleDbConnectRequest = Extract<
+  DbConnectRequest,
+  { provider: "oracle" }
+>;
+
+export type DbConnectRetryOptions = Pick<
+  DbConnectRequest,
+  "connectTimeoutSecs" | "connectAttempts" | "retryDelayMillis" | "poolSize"
+>;
+
+export interface DbSessionInfo {
+  sessionId: number;
+  provider: DatabaseProvider;
+  poolSize: number;
+  openConnections: number;
+  busyPooledConnections: number;
+  idleSecs: number;
+  connectedAt: number;
+  server: DbServerInfo;
+}
+
+export interface DbServerInfo {
+  version: string | null;
+  banner: string | null;
+  instanceName: string | null;
+  containerName: string | null;
+  containerId: number | null;
+  currentUser: string | null;
+  currentSchema: string | null;
+  serverSessionId: number | null;
+  serialNumber: number | null;
+  logonTime: string | null;
+  serverHost: string | null;
+  failedOver: boolean | null;
+}
+
+export interface DbConnectErrorOracleClientMissing {
+  kind: "oracleClientMissing";
+  message: string;
+}
+
+export interface DbConnectErrorGeneral {
+  kind: "general";
+  message: string;
+}
+
+export type DbConnectError =
+  | DbConnectErrorOracleClientMissing
+  | DbConnectErrorGeneral;
+
+export function isDbConnectError(value: unknown): value is DbConnectError {
+  return (
+    typeof value === "object" &&
+    value !== null &&
+    "kind" in value &&
+    "message" in value
+  );
+}
+
+export interface DbSessionSummary {
+  sessionId: number;
+  displayName: string;
+  schema: string;
+  provider: DatabaseProvider;
+  host: string | null;
+  service: string | null;
+  username: string | null;
+  readOnly: boolean;
+  environment: string | null;
+  serverVersion: string | null;
+  instanceName: string | null;
+}
+
+export interface SchemaExportTarget {
+  sessionId: number;
+  label: string;
+  schema: string;
+  provider: DatabaseProvider;
+}
+
+export interface SchemaExportResult {
+  destinationDirectory: string;
+  objectCount: number;
+  fileCount: number;
+  skippedCount: number;
+  message: string;
+}
+
+export type CsvEncoding = "utf8" | "utf8Bom" | "utf16Le" | "windows1252";
+
+export type CsvQuoteStyle = "minimal" | "all" | "never";
+
+export type CsvNullStyle = "empty" | "null" | "backslashN";
+
+export interface CsvExportOptions {
+  delimiter?: string;
+  quoteStyle?: CsvQuoteStyle;
+  nullStyle?: CsvNullStyle;
+  encoding?: CsvEncoding;
+  decimalSeparator?: string;
+  dateFormat?: string;
+  includeHeader?: boolean;
+}
+
+export interface DataExportResult {
+  filePath: string;
+  rowCount: number;
+  message: string;
+}
+
+export type DbJobKind =
+  | "dataExport"
+  | "xlsxImport"
+  | "sqlFileImport"
+  | "schemaSnapshot";
+
+export interface DbSchemaSnapshotSchedule {
+  id: string;
+  profileId: string;
+  destinationDirectory: string;
+  intervalHours: number;
+  gitCommit: boolean;
+  enabled: boolean;
+  lastRunAt?: number | null;
+  lastError?: string | null;
+  lastMessage?: string | null;
+}
+
+export interface DbSaveSchemaSnapshotScheduleRequest {
+  id?: string | null;
+  profileId: string;
+  destinationDirectory: string;
+  intervalHours?: number | null;
+  gitCommit?: boolean;
+  enabled?: boolean;
+}
+
+export interface DbSchemaSnapshotScheduleRef {
+  id: string;
+}
+
+export type DbJobState = "running" | "completed" | "failed" | "cancelled";
+
+export interface DbJobStatus {
+  jobId: number;
+  kind: DbJobKind;
+  label: string;
+  state: DbJobState;
+  processed: number;
+  message: string;
+  startedAt: number;
+  updatedAt: number;
+  finishedAt: number | null;
+}
+
+export interface DbJobRequest {
+  jobId: number;
+}
+
+export type DbImportValueType =
+  | "integer"
+  | "decimal"
+  | "boolean"
+  | "date"
+  | "timestamp"
+  | "text"
+  | "empty";
+
+export interface DbXlsxPreviewRequest {
+  filePath: string;
+  sheetName?: string | null;
+  firstRowIsHeader?: boolean;
+  previewRows?: number;
+}
+
+export interface DbImportColumn {
+  name: string;
+  valueType: DbImportValueType;
+}
+
+export interface DbXlsxPreview {
+  sheetNames: string[];
+  sheetName: string;
+  columns: DbImportColumn[];
+  rows: string[][];
+  totalRows: number;
+}
+
+export interface DbImportColumnMapping {
+  sourceIndex: number;
+  targetColumn: string;
+  valueType?: DbImportValueType | null;
+}
+
+export interface DbXlsxImportRequest {
+  sessionId: number;
+  filePath: string;
+  sheetName?: string | null;
+  firstRowIsHeader?: boolean;
+  schema?: string | null;
+  tableName: string;
+  columns: DbImportColumnMapping[];
+  batchSize?: number;
+  productionConfirmation?: string | null;
+}
+
+export interface DbImportClipboardRowsRequest {
+  sessionId: number;
+  text: string;
+  firstRowIsHeader?: boolean;
+  schema?: string | null;
+  tableName?: string;
+  columns?: DbImportColumnMapping[];
+  previewRows?: number;
+  dryRun?: boolean;
+  productionConfirmation?: string | null;
+}
+
+export interface DbClipboardImportResult {
+  columns: DbImportColumn[];
+  rows: string[][];
+  totalRows: number;
+  rowsInserted: number | null;
+  message: string;
+}
+
+export interface DbAqQueueEntry {
+  schema: string;
+  queueName: string;
+  queueTable: string;
+  queueType: string;
+  payloadType: string;
+  enqueueEnabled: boolean;
+  dequeueEnabled: boolean;
+  maxRetries: number | null;
+  comment: string | null;
+}
+
+export interface DbAqMessage {
+  messageId: string;
+  correlationId: string | null;
+  priority: number | null;
+  state: string;
+  enqueuedAt: string | null;
+  retryCount: number | null;
+  exceptionQueue: string | null;
+  payload: string | null;
+  payloadFormat: "json" | "xml" | "text" | "none";
+  payloadTruncated: boolean;
+}
+
+export interface DbAqBrowseResult {
+  queueName: string;
+  payloadType: string;
+  messages: DbAqMessage[];
+  message: string;
+}
+
+export interface DbServerErrorEntry {
+  timestamp: string;
+  errorCode: string | null;
+  message: string;
+  level: number | null;
+  module: string | null;
+}
+
+export interface DbRecentErrorsResult {
+  source: "alertLog" | "alertHistory";
+  entries: DbServerErrorEntry[];
+  message: string;
+}
+
+export interface DbTableLockHolder {
+  sid: number;
+  serial: number;
+  username: string | null;
+  osUser: string | null;
+  machine: string | null;
+  program: string | null;
+  status: string | null;
+  lockMode: string;
+  sqlId: string | null;
+  blockedSessions: number;
+}
+
+export interface DbRowLockWaiter {
+  sid: number;
+  serial: number;
+  username: string | null;
+  blockingSid: number | null;
+  waitEvent: string | null;
+  secondsWaiting: number | null;
+  rowId: string | null;
+}
+
+export interface DbRowLocksResult {
+  schema: string;
+  tableName: string;
+  holders: DbTableLockHolder[];
+  waiters: DbRowLockWaiter[];
+  message: string;
+}
+
+export interface DbObjectAccessStatsRequest {
+  sessionId: number;
+  historyDays?: number | null;
+  limit?: number | null;
+}
+
+export interface DbTableAccessStats {
+  tableName: string;
+  logicalReads: number;
+  physicalReads: number;
+  physicalWrites: number;
+  blockChanges: number;
+  tableScans: number;
+  readRank: number | null;
+  writeRank: number | null;
+}
+
+export interface DbObjectAccessStatsResult {
+  schema: string;
+  source: "history" | "segmentStatistics";
+  since: string | null;
+  tables: DbTableAccessStats[];
+  idleTables: number;
+  message: string;
+}
+
+export interface DbEncodingInfo {
+  databaseCharset: string;
+  nationalCharset: string;
+  clientCharset: string;
+  nlsLang: string | null;
+  lengthSemantics: string;
+  warnings: string[];
+}
+
+export interface DbCreateScratchTableRequest {
+  sessionId: number;
+  name: string;
+  query: string;
+  productionConfirmation?: string | null;
+}
+
+export interface DbScratchTableRef {
+  sessionId: number;
+  tableName: string;
+}
+
+export type ScratchTableKind = "globalTemporary" | "temporary" | "prefixed";
+
+export interface DbScratchTable {
+  tableName: string;
+  kind: ScratchTableKind;
+  createdAt: number;
+}
+
+export interface DbDetectSensitiveColumnsRequest {
+  sessionId: number;
+  objectNames?: string[];
+  sampleRows?: number | null;
+}
+
+export type SensitiveDataCategory =
+  | "email"
+  | "phone"
+  | "nationalId"
+  | "cardNumber"
+  | "bankAccount"
+  | "dateOfBirth"
+  | "personName"
+  | "postalAddress";
+
+export type SensitiveDataConfidence = "low" | "medium" | "high";
+
+export interface DbSensitiveColumnFinding {
+  schema: string;
+  objectName: string;
+  columnName: string;
+  dataType: string;
+  category: SensitiveDataCategory;
+  confidence: SensitiveDataConfidence;
+  matchedColumnName: boolean;
+  sampledValues: number;
+  matchingValues: number;
+}
+
+export interface DbSensitiveColumnsReport {
+  findings: DbSensitiveColumnFinding[];
+  scannedObjects: number;
+  scannedColumns: number;
+  warnings: string[];
+}
+
+export type DbMaskingStrategy = "redact" | "partial" | "null";
+
+export interface DbMaskingRule {
+  columnPattern: string;
+  strategy: DbMaskingStrategy;
+}
+
+export interface DbSaveMaskingRulesRequest {
+  rules: DbMaskingRule[];
+}
+
+export interface DbMaskedQueryRequest {
+  sessionId: number;
+  sql: string;
+  rowLimit?: number | null;
+}
+
+export interface DbMaskedColumn {
+  columnName: string;
+  strategy: DbMaskingStrategy;
+}
+
+export interface DbMaskedQueryResult {
+  maskedSql: string;
+  maskedColumns: DbMaskedColumn[];
+  result: DbQueryResult;
+}
+
+export interface DbDiffCellValuesRequest {
+  left: string;
+  right: string;
+  contextLines?: number | null;
+}
+
+export type DbDiffLineKind = "context" | "added" | "removed";
+
+export interface DbDiffLine {
+  kind: DbDiffLineKind;
+  oldLine: number | null;
+  newLine: number | null;
+  text: string;
+}
+
+export interface DbDiffHunk {
+  oldStart: number;
+  oldLines: number;
+  newStart: number;
+  newLines: number;
+  lines: DbDiffLine[];
+}
+
+export interface DbTextDiffResult {
+  identical: boolean;
+  addedLines: number;
+  removedLines: number;
+  hunks: DbDiffHunk[];
+}
+
+export interface DbObjectFileDiffRequest {
+  sessionId: number;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  filePath: string;
+  contextLines?: number | null;
+}
+
+export interface DbObjectDdlAsOfRequest {
+  sessionId: number;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  asOf: number;
+}
+
+export interface DbObjectDdlAsOf {
+  ddl: string;
+  source: "flashback" | "backup";
+  capturedAt: number | null;
+  message: string;
+}
+
+export interface DbEditObjectExternallyRequest {
+  sessionId: number;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  editorCommand?: string | null;
+}
+
+export interface DbExternalEdit {
+  editId: number;
+  sessionId: number;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  filePath: string;
+  startedAt: number;
+}
+
+export interface DbExternalEditSaved {
+  edit: DbExternalEdit;
+  ddl: string;
+}
+
+export interface DbStopExternalEditRequest {
+  editId: number;
+}
+
+export interface DbObjectFileDiffResult {
+  identical: boolean;
+  addedLines: number;
+  removedLines: number;
+  unifiedDiff: string;
+}
+
+export interface DbAiSchemaContextStatus {
+  sessionId: number;
+  objectCount: number;
+  columnCount: number;
+  builtAt: number;
+}
+
+export interface DbTransactionState {
+  active: boolean;
+}
+
+export type DbConnectionProfile =
+  | { provider: "oracle"; connection: OracleConnectionOptions }
+  | { provider: "postgres"; connection: NetworkConnectionOptions }
+  | { provider: "mysql"; connection: NetworkConnectionOptions }
+  | { provider: "sqlite"; connection: SqliteConnectionOptions }
+  | { provider: "duckdb"; connection: DuckdbConnectionOptions }
+  | { provider: "snowflake"; connection: SnowflakeConnectionOptions }
+  | { provider: "clickhouse"; connection: NetworkConnectionOptions }
+  | { provider: "libsql"; connection: LibsqlConnectionOptions }
+  | { provider: "external"; connection: ExternalConnectionOptions };
+
+export interface DbOracleClient {
+  path: string;
+  label: string;
+  libraryFound: boolean;
+  loaded: boolean;
+}
+
+export interface DbSaveOracleClientRequest {
+  path: string;
+  label?: string | null;
+}
+
+export interface DbOracleClientPathRequest {
+  path: string;
+}
+
+export interface DbRunDiagnosticsRequest {
+  oracleClientLibDir?: string | null;
+  skipNetwork?: boolean;
+}
+
+export type DbDiagnosticStatus = "ok" | "failed" | "skipped";
+
+export interface DbDiagnosticCheck {
+  id: string;
+  label: string;
+  status: DbDiagnosticStatus;
+  detail: string;
+  durationMs: number;
+}
+
+export interface DbDiagnosticsReport {
+  appVersion: string;
+  os: string;
+  arch: string;
+  generatedAt: number;
+  portableDataDir?: string | null;
+  checks: DbDiagnosticCheck[];
+}
+
+export interface DbCreateSupportBundleRequest {
+  filePath: string;
+  oracleClientLibDir?: string | null;
+  settings?: Record<string, unknown> | null;
+}
+
+export interface DbSupportBundleResult {
+  filePath: string;
+  entries: string[];
+  message: string;
+}
+
+export interface DbExportWorkspaceRequest {
+  filePath: string;
+  passphrase: string;
+  includeSecrets?: boolean;
+  settings?: Record<string, unknown> | null;
+}
+
+export interface DbImportWorkspaceRequest {
+  filePath: string;
+  passphrase: string;
+}
+
+export interface DbWorkspaceArchiveSummary {
+  filePath: string;
+  profileCount: number;
+  secretCount: number;
+  snippetCount: number;
+  pinnedObjectCount: number;
+  taggedObjectCount: number;
+  settings?: Record<string, unknown>;
+  message: string;
+}
+
+export type ConnectionProfile = {
+  id: string;
+  name: string;
+  sshTunnel?: SshTunnelOptions | null;
+  hasPassword: boolean;
+  hasSshPassword: boolean;
+  readOnly?: boolean;
+  environment?: string | null;
+  dropAllowlist?: string[];
+  startupSql?: string | null;
+  warmStandby?: boolean;
+  readerEndpoint?: DbReaderEndpoint | null;
+} & DbConnectionProfile;
+
+export type OracleConnectionProfile = Extract<
+  ConnectionProfile,
+  { provider: "oracle" }
+>;
+
+export type SaveConnectionProfileRequest = {
+  id?: string | null;
+  name: string;
+  sshTunnel?: SshTunnelOptions | null;
+  savePassword: boolean;
+  password?: string | null;
+  sshPassword?: string | null;
+  readOnly?: boolean;
+  environment?: string | null;
+  dropAllowlist?: string[];
+  startupSql?: string | null;
+  warmStandby?: boolean;
+  readerEndpoint?: DbReaderEndpoint | null;
+} & DbConnectionProfile;
+
+export interface DbObjectEntry {
+  schema: string;
+  objectType: string;
+  objectName: string;
+  status?: string | null;
+  invalidReason?: string | null;
+  editionName?: string | null;
+  created?: string | null;
+  lastDdlTime?: string | null;
+  temporary?: boolean;
+  secondary?: boolean;
+}
+
+export interface DbObjectDdl {
+  ddl: string;
+  reconstructed: boolean;
+}
+
+export interface DbObjectEditionInfo {
+  sessionEdition: string;
+  objectEdition: string | null;
+  inherited: boolean;
+}
+
+export interface DbObjectColumnEntry {
+  schema: string;
+  objectName: string;
+  columnName: string;
+  dataType: string;
+  nullable: string;
+}
+
+export interface DbObjectName {
+  schema: string;
+  objectName: string;
+}
+
+export interface DbListObjectColumnsRequest {
+  sessionId: number;
+  objects?: DbObjectName[] | null;
+  concurrency?: number | null;
+}
+
+export type SqlCompletionSchema = Record<string, Record<string, string[]>>;
+
+export type SqlCompletionUsage = Record<string, number>;
+
+export interface DbIdentifierUsageRequest {
+  provider?: DatabaseProvider | null;
+  limit?: number | null;
+}
+
+export interface DbIdentifierUsage {
+  name: string;
+  count: number;
+  lastUsedAt: number;
+}
+
+export interface AiSchemaContextObject {
+  schema: string;
+  objectName: string;
+  columns: string[];
+  isReferencedInQuery: boolean;
+}
+
+export interface AiQuerySuggestionRequest {
+  currentSql: string;
+  connectedSchema: string;
+  endpoint: string;
+  model: string;
+  schemaContext: AiSchemaContextObject[];
+  cursorClause?: string;
+  sessionId?: number;
+  provider?: DatabaseProvider;
+  identifierCheck?: AiIdentifierCheck;
+}
+
+export type AiIdentifierCheck = "off" | "annotate" | "discard";
+
+export interface AiDdlReviewRequest {
+  sessionId: number;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  ddl: string;
+  endpoint: string;
+  model: string;
+}
+
+export type AiRiskSeverity = "low" | "medium" | "high";
+
+export type AiDdlRiskCategory =
+  | "breakingChange"
+  | "grants"
+  | "locking"
+  | "dependencies"
+  | "dataLoss"
+  | "other";
+
+export interface AiDdlRiskNote {
+  category: AiDdlRiskCategory;
+  severity: AiRiskSeverity;
+  message: string;
+}
+
+export interface DbObjectDependent {
+  schema: string;
+  objectType: string;
+  objectName: string;
+}
+
+export interface AiDdlReviewResult {
+  riskLevel: AiRiskSeverity;
+  summary: string;
+  notes: AiDdlRiskNote[];
+  dependents: DbObjectDependent[];
+}
+
+export interface AiPromptSettings {
+  systemPromptTemplate: string | null;
+  extraRules: string[];
+}
+
+export interface AiQuerySuggestionResponse {
+  suggestionText: string;
+  confidence: number;
+  reasoningShort: string;
+  isPotentiallyMutating: boolean;
+  warnings: string[];
+}
+
+export interface DbQueryResult {
+  columns: string[];
+  rows: string[][];
+  nullFlags?: boolean[][];
+  byteLengths?: (number | null)[][];
+  rowsAffected: number | null;
+  message: string;
+  lossyConversion: boolean;
+  cursorId?: number | null;
+  requiredVariables?: string[];
+  refCursors?: DbRefCursorResult[];
+}
+
+export interface DbRefCursorResult {
+  name: string;
+  implicit: boolean;
+  columns: string[];
+  rows: string[][];
+  nullFlags?: boolean[][];
+  byteLengths?: (number | null)[][];
+  truncated: boolean;
+}
+
+export type DbMetadataFacet = "columns" | "constraints" | "stats" | "grants";
+
+export interface DbMetadataQueryRequest {
+  sessionId: number;
+  objects?: DbObjectName[];
+  filter?: {
+    name?: string | null;
+    objectTypes?: string[];
+  };
+  include: DbMetadataFacet[];
+}
+
+export interface DbMetadataDocument {
+  objects: DbObjectMetadata[];
+  truncated: boolean;
+  unavailable?: string[];
+}
+
+export interface DbObjectMetadata {
+  schema: string;
+  objectName: string;
+  objectType: string | null;
+  status: string | null;
+  columns?: DbMetadataColumn[];
+  constraints?: DbObjectConstraint[];
+  stats?: DbObjectStats;
+  grants?: DbObjectGrant[];
 }
 
-export interface OracleConnectOptions extends OracleConnectionOptions {
-  password: string;
-  oracleClientLibDir?: string;
+export interface DbMetadataColumn {
+  name: string;
+  dataType: string;
+  nullable: boolean;
 }
 
-export interface NetworkConnectionOptions {
-  host: string;
-  port?: number;
-  database: string;
-  username: string;
-  schema?: string;
+export interface DbObjectConstraint {
+  name: string;
+  constraintType: "primaryKey" | "unique" | "foreignKey" | "check";
+  columns: string[];
+  references: string | null;
+  enabled: boolean;
 }
 
-export interface NetworkConnectOptions extends NetworkConnectionOptions {
-  password: string;
+export interface DbObjectStats {
+  numRows: number | null;
+  blocks: number | null;
+  avgRowLen: number | null;
+  lastAnalyzed: string | null;
+  stale: boolean;
 }
 
-export interface SqliteConnectionOptions {
+export interface DbObjectGrant {
+  grantee: string;
+  privilege: string;
+  grantable: boolean;
+}
+
+export interface DbFetchMoreRowsRequest {
+  sessionId: number;
+  cursorId: number;
+  count?: number | null;
+}
+
+export interface DbCloseCursorRequest {
+  sessionId: number;
+  cursorId: number;
+}
+
+export interface DbStreamQueryRequest {
+  sessionId: number;
+  streamId: string;
+  sql: string;
+  batchSize?: number | null;
+  timeoutSecs?: number | null;
+}
+
+export interface DbQueryStreamSummary {
+  streamId: string;
+  columns: string[];
+  rowCount: number;
+  batchCount: number;
+  message: string;
+  error: string | null;
+}
+
+export type DbQueryRowsEvent =
+  | {
+      kind: "rows";
+      streamId: string;
+      batchIndex: number;
+      columns: string[] | null;
+      rows: string[][];
+      nullFlags?: boolean[][];
+    }
+  | ({ kind: "summary" } & DbQueryStreamSummary);
+
+export interface DbExportResultReportRequest {
+  sessionId: number;
   filePath: string;
+  title?: string | null;
+  sql: string;
+  result: DbQueryResult;
+  connectionLabel?: string | null;
+  elapsedMillis?: number | null;
+  executedAt?: string | null;
+  includePlan: boolean;
 }
 
-export type DbConnectRequest =
-  | { provider: "oracle"; connection: OracleConnectOptions }
-  | { provider: "postgres"; connection: NetworkConnectOptions }
-  | { provider: "mysql"; connection: NetworkConnectOptions }
-  | { provider: "sqlite"; connection: SqliteConnectionOptions };
+export interface DbResultReport {
+  filePath: string;
+  rowCount: number;
+  planIncluded: boolean;
+  message: string;
+}
 
-export type OracleDbConnectRequest = Extract<
-  DbConnectRequest,
-  { provider: "oracle" }
->;
+export interface DbSqlStatement {
+  sql: string;
+  line: number;
+}
 
-export interface DbConnectErrorOracleClientMissing {
-  kind: "oracleClientMissing";
+export interface DbSplitSqlRequest {
+  provider: DatabaseProvider;
+  sql: string;
+}
+
+export interface DbRunScriptRequest {
+  sessionId: number;
+  sql: string;
+  rowLimit?: number | null;
+  stopOnError?: boolean;
+  allowDestructive?: boolean;
+  productionConfirmation?: string | null;
+  timeoutSecs?: number | null;
+  baseDirectory?: string | null;
+  substitutions?: Record<string, string>;
+}
+
+export interface DbSqlFileImportRequest {
+  sessionId: number;
+  filePath: string;
+  stopOnError?: boolean;
+  allowDestructive?: boolean;
+  productionConfirmation?: string | null;
+}
+
+export interface DbScriptStatementResult {
+  sql: string;
+  line: number;
+  result: DbQueryResult | null;
+  error: string | null;
+  output?: string[];
+}
+
+export interface DbScriptResult {
+  statements: DbScriptStatementResult[];
+  totalStatements: number;
+  failedStatements: number;
+}
+
+export interface DbConsistentQueriesRequest {
+  sessionId: number;
+  queries: string[];
+  rowLimit?: number | null;
+}
+
+export interface DbConsistentQueriesResult {
+  scn: string | null;
+  results: DbQueryResult[];
+}
+
+export interface DbSessionIdleDisconnected {
+  sessionId: number;
+  idleMinutes: number;
   message: string;
 }
 
-export interface DbConnectErrorGeneral {
-  kind: "general";
+export interface DbStateFileRecovery {
+  fileName: string;
+  error: string;
+  movedTo: string | null;
   message: string;
+  recoveredAt: number;
 }
 
-export type DbConnectError =
-  | DbConnectErrorOracleClientMissing
-  | DbConnectErrorGeneral;
+export interface DbErrorSummaryEntry {
+  message: string;
+  count: number;
+  firstAt: number;
+  lastAt: number;
+}
 
-export function isDbConnectError(value: unknown): value is DbConnectError {
-  return (
-    typeof value === "object" &&
-    value !== null &&
-    "kind" in value &&
-    "message" in value
-  );
+export interface DbSessionErrors {
+  sessionId: number;
+  errors: DbErrorSummaryEntry[];
 }
 
-export interface DbSessionSummary {
+export interface DbErrorSummary {
   sessionId: number;
-  displayName: string;
-  schema: string;
-  provider: DatabaseProvider;
+  totalErrors: number;
+  errors: DbErrorSummaryEntry[];
 }
 
-export interface SchemaExportTarget {
+export type DbSessionTimelineEventKind =
+  | "connect"
+  | "reconnect"
+  | "disconnect"
+  | "statement"
+  | "script"
+  | "ddlEdit"
+  | "export";
+
+export interface DbSessionTimelineEvent {
+  at: number;
+  kind: DbSessionTimelineEventKind;
+  summary: string;
+  sql: string | null;
+  succeeded: boolean;
+  error: string | null;
+  elapsedMillis: number | null;
+}
+
+export interface DbSessionTimeline {
   sessionId: number;
   label: string;
-  schema: string;
-  provider: DatabaseProvider;
+  droppedEvents: number;
+  events: DbSessionTimelineEvent[];
 }
 
-export interface SchemaExportResult {
-  destinationDirectory: string;
-  objectCount: number;
-  fileCount: number;
-  skippedCount: number;
+export interface DbExportSessionTimelineRequest {
+  sessionId: number;
+  filePath: string;
+}
+
+export interface DbSessionTimelineExport {
+  filePath: string;
+  eventCount: number;
   message: string;
 }
 
-export interface DbTransactionState {
-  active: boolean;
+export interface DbReconnectRequest {
+  sessionId: number;
+  password?: string | null;
+  profileId?: string | null;
 }
 
-export type DbConnectionProfile =
-  | { provider: "oracle"; connection: OracleConnectionOptions }
-  | { provider: "postgres"; connection: NetworkConnectionOptions }
-  | { provider: "mysql"; connection: NetworkConnectionOptions }
-  | { provider: "sqlite"; connection: SqliteConnectionOptions };
+export interface DbSessionReconnected {
+  sessionId: number;
+  reconnected: boolean;
+  replayed: boolean;
+  needsPassword: boolean;
+  message: string;
+}
 
-export type ConnectionProfile = {
+export interface DbDownloadLobRequest {
+  sessionId: number;
+  sql: string;
+  rowIndex: number;
+  columnIndex: number;
+  filePath: string;
+}
+
+export interface DbLobDownloadResult {
+  filePath: string;
+  byteLength: number;
+  message: string;
+}
+
+export interface DbSaveResultSnapshotRequest {
+  name: string;
+  notes?: string | null;
+  sql?: string | null;
+  source?: string | null;
+  columns: string[];
+  rows: string[][];
+}
+
+export interface DbImportOracleWalletRequest {
+  filePath: string;
+  name?: string | null;
+}
+
+export interface DbOracleWallet {
+  name: string;
+  directory: string;
+  aliases: string[];
+}
+
+export interface DbOracleWalletNameRequest {
+  name: string;
+}
+
+export interface DbListTnsAliasesRequest {
+  tnsAdmin?: string | null;
+}
+
+export interface DbTnsAlias {
+  alias: string;
+  protocol: OracleProtocol;
+  host: string;
+  port: number;
+  serviceName: string;
+}
+
+export interface DbTnsAliasList {
+  filePath: string;
+  aliases: DbTnsAlias[];
+  skipped: string[];
+}
+
+export interface DbDdlBackup {
+  id: string;
+  profileId?: string | null;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  filePath: string;
+  sizeBytes: number;
+  createdAt: number;
+}
+
+export interface DbDdlBackupRetention {
+  maxPerObject: number;
+  maxAgeDays: number | null;
+}
+
+export interface DbListDdlBackupsRequest {
+  schema?: string | null;
+  objectType?: string | null;
+  objectName?: string | null;
+}
+
+export interface DbDdlChange {
+  id: string;
+  profileId: string | null;
+  environment: string | null;
+  action: string;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  sql: string;
+  beforeHash: string | null;
+  afterHash: string | null;
+  user: string | null;
+  executedAt: number;
+  error: string | null;
+}
+
+export interface DbGetDdlChangelogRequest {
+  profileId?: string | null;
+  since?: number | null;
+  until?: number | null;
+}
+
+export interface DbExportDdlChangelogRequest extends DbGetDdlChangelogRequest {
+  filePath: string;
+  title?: string | null;
+}
+
+export interface DbDdlChangelogExport {
+  filePath: string;
+  changeCount: number;
+  message: string;
+}
+
+export interface DbResultSnapshotSummary {
   id: string;
   name: string;
-  hasPassword: boolean;
-} & DbConnectionProfile;
+  notes: string | null;
+  sql: string | null;
+  source: string | null;
+  columnCount: number;
+  rowCount: number;
+  createdAt: number;
+}
 
-export type OracleConnectionProfile = Extract<
-  ConnectionProfile,
-  { provider: "oracle" }
->;
+export interface DbResultSnapshot {
+  id: string;
+  name: string;
+  notes: string | null;
+  sql: string | null;
+  source: string | null;
+  columns: string[];
+  rows: string[][];
+  createdAt: number;
+}
 
-export type SaveConnectionProfileRequest = {
+export interface DbResultSnapshotRef {
+  snapshotId: string;
+}
+
+export type DbBindType = "text" | "number" | "date" | "timestamp" | "refCursor";
+
+export interface DbQueryBind {
+  name: string;
+  value: string | null;
+  valueType?: DbBindType;
+}
+
+export interface DbSnippetParameterSet {
+  id: string;
+  name: string;
+  values: DbQueryBind[];
+  updatedAt: number;
+}
+
+export interface DbSaveSnippetParameterSetRequest {
+  snippetId: string;
   id?: string | null;
   name: string;
-  savePassword: boolean;
-  password?: string | null;
-} & DbConnectionProfile;
+  values: DbQueryBind[];
+}
 
-export interface DbObjectEntry {
+export interface DbSnippetParameterSetRef {
+  snippetId: string;
+  parameterSetId: string;
+}
+
+export interface DbPinnedObject {
+  profileId: string;
   schema: string;
   objectType: string;
   objectName: string;
-  status?: string | null;
-  invalidReason?: string | null;
+  pinnedAt: number;
 }
 
-export interface DbObjectColumnEntry {
+export interface DbPinObjectRequest {
+  profileId: string;
   schema: string;
+  objectType: string;
   objectName: string;
-  columnName: string;
-  dataType: string;
-  nullable: string;
+  pinned?: boolean;
 }
 
-export type SqlCompletionSchema = Record<string, Record<string, string[]>>;
+export interface DbRunSnippetRequest {
+  sessionId: number;
+  snippetId: string;
+  parameterSetId?: string | null;
+  rowLimit?: number | null;
+  allowDestructive?: boolean;
+  productionConfirmation?: string | null;
+}
 
-export interface AiSchemaContextObject {
+export interface DbCodegenObject {
   schema: string;
   objectName: string;
-  columns: string[];
-  isReferencedInQuery: boolean;
 }
 
-export interface AiQuerySuggestionRequest {
-  currentSql: string;
-  connectedSchema: string;
-  endpoint: string;
-  model: string;
-  schemaContext: AiSchemaContextObject[];
-  cursorClause?: string;
+export interface DbGenerateCodeRequest {
+  sessionId: number;
+  objects: DbCodegenObject[];
+  template: string;
+  fileName: string;
+  destinationDirectory: string;
+  overwrite?: boolean;
+  dryRun?: boolean;
 }
 
-export interface AiQuerySuggestionResponse {
-  suggestionText: string;
-  confidence: number;
-  reasoningShort: string;
-  isPotentiallyMutating: boolean;
+export interface DbGeneratedFile {
+  path: string;
+  content: string;
+  objectNames: string[];
 }
 
-export interface DbQueryResult {
-  columns: string[];
-  rows: string[][];
-  rowsAffected: number | null;
-  message: string;
+export interface DbGenerateCodeResult {
+  files: DbGeneratedFile[];
+  written: boolean;
+}
+
+export type DbGrantAccess = "read" | "write" | "execute";
+
+export interface DbGrantObject {
+  schema: string;
+  objectType: string;
+  objectName: string;
+}
+
+export interface DbGenerateGrantsRequest {
+  sessionId: number;
+  grantee: string;
+  access: DbGrantAccess[];
+  objects?: DbGrantObject[];
+  patterns?: string[];
+  synonymSchema?: string | null;
 }
 
-export type SchemaSearchMatchScope = "object_name" | "source" | "ddl";
+export interface DbGrantScript {
+  script: string;
+  grantCount: number;
+  synonymCount: number;
+  skipped: string[];
+}
+
+export type SchemaSearchMatchScope = "object_name" | "source" | "ddl" | "tag";
 
 export interface DbSchemaSearchResult {
   schema: string;
@@ -172,6 +1542,28 @@ export interface DbSchemaSearchResult {
   matchScope: SchemaSearchMatchScope;
   line: number | null;
   snippet: string;
+  tags?: string[];
+}
+
+export interface DbTaggedObject {
+  profileId: string;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  tags: string[];
+}
+
+export interface DbObjectTagRequest {
+  profileId: string;
+  schema: string;
+  objectType: string;
+  objectName: string;
+  tag: string;
+}
+
+export interface DbListTaggedObjectsRequest {
+  profileId: string;
+  tag?: string | null;
 }
 
 export type ObjectDetailTabId = "data" | "ddl" | "metadata";
@@ -220,6 +1612,7 @@ export interface BusyState {
   loadingProfiles: boolean;
   savingProfile: boolean;
   deletingProfile: boolean;
+  testingConnection: boolean;
   loadingProfileSecret: boolean;
   loadingObjects: boolean;
   loadingDdl: boolean;
@@ -235,3 +1628,12 @@ export interface ObjectTreeNode {
   objectType: string;
   entries: DbObjectEntry[];
 }
+
+export interface DbStandbyConnection {
+  profileId: string;
+  profileName: string;
+  ready: boolean;
+  openedAt: number | null;
+  lastCheckedAt: number;
+  error: string | null;
+}
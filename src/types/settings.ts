@@ -1,3 +1,5 @@
+import type { CsvNullStyle, CsvQuoteStyle } from "./clarity";
+
 export type ThemeSetting = "light" | "dark";
 
 export interface KeyBindings {
@@ -15,7 +17,16 @@ export interface UserSettings {
   queryEditorFontSize: number;
   dataFontFamily: string;
   dataFontSize: number;
+  exportNullStyle: CsvNullStyle;
+  exportDelimiter: string;
+  exportQuoteStyle: CsvQuoteStyle;
   oracleClientLibDir: string;
+  connectTimeoutSeconds: number;
+  connectAttempts: number;
+  connectRetryDelayMs: number;
+  keepAliveIntervalSeconds: number;
+  idleDisconnectMinutes: number;
+  connectionPoolSize: number;
   aiSuggestionsEnabled: boolean;
   aiModel: string;
   aiEndpoint: string;